@@ -54,3 +54,17 @@ impl NativeAssetToken for Contract {
         mint_to_address(amount, recipient);
     }
 }
+
+// ANCHOR: native_token_test
+#[test]
+fn test_mint_and_burn_coins() {
+    let token = abi(NativeAssetToken, CONTRACT_ID);
+    let contract_id = ContractId::from(CONTRACT_ID);
+
+    token.mint_coins(100);
+    assert_balance(contract_id, contract_id, 100);
+
+    token.burn_coins(40);
+    assert_balance(contract_id, contract_id, 60);
+}
+// ANCHOR_END: native_token_test
@@ -1,8 +1,6 @@
 // ANCHOR: body
 contract;
 
-use std::hash::sha256;
-
 ////////////////////////////////////////
 // Event declarations
 ////////////////////////////////////////
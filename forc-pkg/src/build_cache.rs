@@ -0,0 +1,476 @@
+//! An on-disk cache of compiled packages, allowing builds to skip compiling packages that have not
+//! changed since they were last built.
+//!
+//! Each package is identified by a [Fingerprint] of its manifest, its Sway sources, the build
+//! settings and the fingerprints of its dependencies. A package whose fingerprint matches that of
+//! its cached build is loaded from the cache.
+//!
+//! The warnings reported when compiling a package are cached along with it, and replayed when it
+//! is loaded, so that an unchanged package reports the same warnings as when it was compiled.
+//!
+//! Only the compiled artifacts of packages are cached, at the granularity of packages rather than
+//! modules, and not their typed ASTs or namespaces, which point into the type and declaration
+//! engines of the compilation that produced them. Compiling a package that has changed requires
+//! the namespaces of the libraries it depends on, so those libraries, including `std`, are type
+//! checked again, though the rest of their compilation is skipped. The contracts it depends on are
+//! not, as only their bytecode and storage slots are required.
+
+use crate::{
+    manifest::{BuildProfile, PackageManifestFile},
+    BuiltPackage, BuiltPackageBytecode, PackageDescriptor, PkgEntry, PkgEntryKind,
+};
+use anyhow::Result;
+use fuel_abi_types::program_abi;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf, str::FromStr, sync::Arc};
+use sway_core::{
+    abi_generation::fuel_abi::AbiExtensions,
+    asm_generation::ProgramABI,
+    debug_info::DebugInfo,
+    fuel_prelude::{fuel_crypto::Hasher, fuel_tx::StorageSlot},
+    language::{parsed::TreeType, ty},
+    source_map::SourceMap,
+    BuildTarget, FinalizedEntry, FunctionSize, GasEstimate,
+};
+use sway_error::warning::{CompileWarning, Suggestion, Warning};
+use sway_types::{SourceEngine, Span};
+use sway_utils::constants;
+
+/// The name of the directory within the output directory holding the cache.
+pub const CACHE_DIRECTORY_NAME: &str = ".cache";
+
+/// Identifies everything a compiled package depends upon.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint(String);
+
+/// The directory holding the cached builds of packages, one file per package.
+pub struct BuildCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedPackage {
+    fingerprint: Fingerprint,
+    tree_type: String,
+    program_abi: program_abi::ProgramABI,
+    abi_extensions: AbiExtensions,
+    storage_slots: Vec<StorageSlot>,
+    storage_layout: Vec<ty::StorageFieldLayout>,
+    source_map: SourceMap,
+    bytecode: CachedBytecode,
+    bytecode_without_tests: Option<CachedBytecode>,
+    #[serde(default)]
+    warnings: Vec<CachedWarning>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedBytecode {
+    /// The hex encoded bytecode.
+    bytes: String,
+    entries: Vec<CachedEntry>,
+    #[serde(default)]
+    function_sizes: Vec<FunctionSize>,
+    #[serde(default)]
+    gas_estimates: Vec<GasEstimate>,
+    #[serde(default)]
+    debug_info: DebugInfo,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedEntry {
+    fn_name: String,
+    imm: u64,
+    selector: Option<[u8; 4]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedWarning {
+    code: String,
+    lint_name: String,
+    message: String,
+    location: Option<CachedLocation>,
+    suggestions: Vec<CachedSuggestion>,
+}
+
+/// A range of a source file, by the byte offsets of its start and end.
+#[derive(Serialize, Deserialize)]
+struct CachedLocation {
+    path: PathBuf,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedSuggestion {
+    message: String,
+    location: Option<CachedLocation>,
+    replacement: String,
+}
+
+impl Fingerprint {
+    /// Fingerprints the package with the given manifest, built using `target` and `profile`.
+    ///
+    /// The `deps` are the names and fingerprints of the package's direct dependencies.
+    pub fn new<'a>(
+        manifest: &PackageManifestFile,
+        target: BuildTarget,
+        profile: &BuildProfile,
+        deps: impl IntoIterator<Item = (&'a str, &'a Fingerprint)>,
+    ) -> Result<Self> {
+        let mut hasher = Hasher::default();
+        hash_bytes(&mut hasher, env!("CARGO_PKG_VERSION"));
+        hash_bytes(&mut hasher, sway_core::VERSION);
+        hash_bytes(&mut hasher, target.to_string());
+        hash_bytes(&mut hasher, serde_json::to_vec(profile)?);
+        hash_contents(&mut hasher, manifest)?;
+
+        let mut deps = deps.into_iter().collect::<Vec<_>>();
+        deps.sort_by_key(|(name, _)| *name);
+        for (name, fingerprint) in deps {
+            hash_bytes(&mut hasher, name);
+            hash_bytes(&mut hasher, &fingerprint.0);
+        }
+        Ok(Self(hasher.finalize().to_string()))
+    }
+}
+
+impl BuildCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Whether builds using the given settings can be cached.
+    ///
+    /// Builds that print, time or emit the graphs of the compilation have to compile every
+    /// package, and test builds require the typed declarations of the tests.
+    pub fn is_enabled_for(target: BuildTarget, profile: &BuildProfile) -> bool {
+        target == BuildTarget::Fuel
+            && !profile.include_tests
+            && !profile.print_ast
+            && profile.print_dca_graph.is_none()
+            && !profile.print_ir
+            && !profile.print_smt
+            && profile.dump_fn.is_none()
+            && !profile.print_finalized_asm
+            && !profile.print_intermediate_asm
+            && !profile.time_phases
+            && !profile.verify_ir
+            && profile.metrics_outfile.is_none()
+            && !profile.emit_cfg
+    }
+
+    /// Loads the cached build of the described package, if there is one with the given
+    /// fingerprint.
+    ///
+    /// The warnings of the loaded package are those reported when it was compiled, pointing into
+    /// its sources as recorded in `source_engine`.
+    pub fn load(
+        &self,
+        source_engine: &SourceEngine,
+        descriptor: &PackageDescriptor,
+        fingerprint: &Fingerprint,
+    ) -> Option<BuiltPackage> {
+        let contents = fs::read(self.entry_path(descriptor)).ok()?;
+        let cached: CachedPackage = serde_json::from_slice(&contents).ok()?;
+        if cached.fingerprint != *fingerprint {
+            return None;
+        }
+        Some(BuiltPackage {
+            descriptor: descriptor.clone(),
+            program_abi: ProgramABI::Fuel(cached.program_abi),
+            abi_extensions: cached.abi_extensions,
+            storage_slots: cached.storage_slots,
+            storage_layout: cached.storage_layout,
+            warnings: cached
+                .warnings
+                .into_iter()
+                .map(|warning| warning.into_compile_warning(source_engine))
+                .collect(),
+            source_map: cached.source_map,
+            tree_type: TreeType::from_str(&cached.tree_type).ok()?,
+            bytecode: cached.bytecode.into_built()?,
+            bytecode_without_tests: match cached.bytecode_without_tests {
+                Some(bytecode) => Some(bytecode.into_built()?),
+                None => None,
+            },
+            program_graphs: None,
+        })
+    }
+
+    /// Caches the build of a package with the given fingerprint, replacing any previous build of
+    /// the same package.
+    pub fn store(
+        &self,
+        source_engine: &SourceEngine,
+        fingerprint: &Fingerprint,
+        built: &BuiltPackage,
+    ) -> Result<()> {
+        let ProgramABI::Fuel(program_abi) = &built.program_abi else {
+            return Ok(());
+        };
+        let cached = CachedPackage {
+            fingerprint: fingerprint.clone(),
+            tree_type: built.tree_type.to_string(),
+            program_abi: program_abi.clone(),
+            abi_extensions: built.abi_extensions.clone(),
+            storage_slots: built.storage_slots.clone(),
+            storage_layout: built.storage_layout.clone(),
+            source_map: built.source_map.clone(),
+            bytecode: CachedBytecode::from_built(&built.bytecode),
+            bytecode_without_tests: built
+                .bytecode_without_tests
+                .as_ref()
+                .map(CachedBytecode::from_built),
+            warnings: built
+                .warnings
+                .iter()
+                .map(|warning| CachedWarning::from_compile_warning(source_engine, warning))
+                .collect(),
+        };
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.entry_path(&built.descriptor),
+            serde_json::to_vec(&cached)?,
+        )?;
+        Ok(())
+    }
+
+    fn entry_path(&self, descriptor: &PackageDescriptor) -> PathBuf {
+        let file_name = format!("{}-{}", descriptor.name, descriptor.pinned.id());
+        self.dir.join(file_name).with_extension("json")
+    }
+}
+
+impl CachedBytecode {
+    fn from_built(bytecode: &BuiltPackageBytecode) -> Self {
+        Self {
+            bytes: hex::encode(&bytecode.bytes),
+            entries: bytecode
+                .entries
+                .iter()
+                .map(|entry| CachedEntry {
+                    fn_name: entry.finalized.fn_name.clone(),
+                    imm: entry.finalized.imm,
+                    selector: entry.finalized.selector,
+                })
+                .collect(),
+            function_sizes: bytecode.function_sizes.clone(),
+            gas_estimates: bytecode.gas_estimates.clone(),
+            debug_info: bytecode.debug_info.clone(),
+        }
+    }
+
+    fn into_built(self) -> Option<BuiltPackageBytecode> {
+        Some(BuiltPackageBytecode {
+            bytes: hex::decode(self.bytes).ok()?,
+            // Test builds are never cached, so all entries are main entries.
+            entries: self
+                .entries
+                .into_iter()
+                .map(|entry| PkgEntry {
+                    finalized: FinalizedEntry {
+                        fn_name: entry.fn_name,
+                        imm: entry.imm,
+                        selector: entry.selector,
+                        test_decl_ref: None,
+                    },
+                    kind: PkgEntryKind::Main,
+                })
+                .collect(),
+            function_sizes: self.function_sizes,
+            gas_estimates: self.gas_estimates,
+            debug_info: self.debug_info,
+        })
+    }
+}
+
+impl CachedWarning {
+    fn from_compile_warning(source_engine: &SourceEngine, warning: &CompileWarning) -> Self {
+        Self {
+            code: warning.code().to_string(),
+            lint_name: warning.warning_content.lint_name().to_string(),
+            message: warning.to_friendly_warning_string(),
+            location: CachedLocation::from_span(source_engine, &warning.span),
+            suggestions: warning
+                .suggestions()
+                .into_iter()
+                .map(|suggestion| CachedSuggestion {
+                    location: CachedLocation::from_span(source_engine, &suggestion.span),
+                    message: suggestion.message,
+                    replacement: suggestion.replacement,
+                })
+                .collect(),
+        }
+    }
+
+    fn into_compile_warning(self, source_engine: &SourceEngine) -> CompileWarning {
+        CompileWarning {
+            span: location_span(source_engine, self.location),
+            warning_content: Warning::Replayed {
+                code: self.code,
+                lint_name: self.lint_name,
+                message: self.message,
+                suggestions: self
+                    .suggestions
+                    .into_iter()
+                    .map(|suggestion| Suggestion {
+                        message: suggestion.message,
+                        span: location_span(source_engine, suggestion.location),
+                        replacement: suggestion.replacement,
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl CachedLocation {
+    fn from_span(source_engine: &SourceEngine, span: &Span) -> Option<Self> {
+        Some(Self {
+            path: source_engine.get_path(span.source_id()?),
+            start: span.start(),
+            end: span.end(),
+        })
+    }
+}
+
+/// The span of the location within the text of its source file, or a dummy span if there is no
+/// location or its file can't be read. The sources of a cached package are unchanged since it was
+/// compiled, so the offsets of its locations still hold.
+fn location_span(source_engine: &SourceEngine, location: Option<CachedLocation>) -> Span {
+    let span = location.and_then(|location| {
+        let source_id = source_engine.get_source_id(&location.path);
+        let text = match source_engine.get_source_text(&source_id) {
+            Some(text) => text,
+            None => {
+                let contents = fs::read_to_string(&location.path).ok()?;
+                source_engine.set_source_text(source_id, Arc::from(contents))
+            }
+        };
+        text.with_range(location.start, location.end)
+    });
+    span.unwrap_or_else(Span::dummy)
+}
+
+/// Hashes the manifest and Sway sources of the package, regardless of how it is built and of its
+/// dependencies.
+///
+/// This identifies the version of the package's own code, e.g. to track its compilation metrics
+/// over time.
+pub fn content_hash(manifest: &PackageManifestFile) -> Result<String> {
+    let mut hasher = Hasher::default();
+    hash_contents(&mut hasher, manifest)?;
+    Ok(hasher.finalize().to_string())
+}
+
+fn hash_contents(hasher: &mut Hasher, manifest: &PackageManifestFile) -> Result<()> {
+    hash_bytes(hasher, fs::read(manifest.path())?);
+    let mut sources = walkdir::WalkDir::new(manifest.dir().join(constants::SRC_DIR))
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry.path().extension().and_then(|ext| ext.to_str())
+                    == Some(constants::SWAY_EXTENSION)
+        })
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+    sources.sort();
+    for path in sources {
+        let relative_path = path.strip_prefix(manifest.dir()).unwrap_or(&path);
+        hash_bytes(hasher, relative_path.to_string_lossy().as_bytes());
+        hash_bytes(hasher, fs::read(&path)?);
+    }
+    Ok(())
+}
+
+/// Hashes `bytes` prefixed with their length, so that consecutive inputs can't be confused.
+fn hash_bytes(hasher: &mut Hasher, bytes: impl AsRef<[u8]>) {
+    let bytes = bytes.as_ref();
+    hasher.input((bytes.len() as u64).to_be_bytes());
+    hasher.input(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn counter_manifest() -> PackageManifestFile {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples/counter");
+        PackageManifestFile::from_dir(&dir).unwrap()
+    }
+
+    #[test]
+    fn fingerprint_depends_on_settings_and_dependencies() {
+        let manifest = counter_manifest();
+        let debug = BuildProfile::debug();
+        let fingerprint = |profile, deps: &[(&str, &Fingerprint)]| {
+            Fingerprint::new(&manifest, BuildTarget::Fuel, profile, deps.iter().copied()).unwrap()
+        };
+
+        let base = fingerprint(&debug, &[]);
+        assert_eq!(base, fingerprint(&debug, &[]));
+
+        let terse = BuildProfile {
+            terse: true,
+            ..BuildProfile::debug()
+        };
+        assert_ne!(base, fingerprint(&terse, &[]));
+
+        let dep = Fingerprint("dep".to_string());
+        let other_dep = Fingerprint("other".to_string());
+        let with_dep = fingerprint(&debug, &[("std", &dep)]);
+        assert_ne!(base, with_dep);
+        assert_ne!(with_dep, fingerprint(&debug, &[("std", &other_dep)]));
+        assert_eq!(
+            fingerprint(&debug, &[("a", &dep), ("b", &other_dep)]),
+            fingerprint(&debug, &[("b", &other_dep), ("a", &dep)]),
+        );
+    }
+
+    #[test]
+    fn warnings_are_replayed_pointing_into_the_same_sources() {
+        let path = counter_manifest().entry_path();
+        let text: Arc<str> = Arc::from(fs::read_to_string(&path).unwrap());
+        let start = text.find("value").unwrap();
+
+        let compiled_with = SourceEngine::default();
+        let source_id = compiled_with.get_source_id(&path);
+        let span = compiled_with
+            .set_source_text(source_id, text)
+            .with_range(start, start + "value".len())
+            .unwrap();
+        let warning = CompileWarning {
+            span: span.clone(),
+            warning_content: Warning::UnusedVariable {
+                variable_name: sway_types::Ident::new(span),
+            },
+        };
+        let cached = serde_json::to_vec(&CachedWarning::from_compile_warning(
+            &compiled_with,
+            &warning,
+        ))
+        .unwrap();
+
+        let loaded_with = SourceEngine::default();
+        let replayed = serde_json::from_slice::<CachedWarning>(&cached)
+            .unwrap()
+            .into_compile_warning(&loaded_with);
+        assert_eq!(replayed.code(), warning.code());
+        assert_eq!(
+            replayed.to_friendly_warning_string(),
+            warning.to_friendly_warning_string()
+        );
+        assert_eq!(replayed.span.as_str(), "value");
+        assert_eq!(replayed.span.start(), start);
+        assert_eq!(
+            replayed.source_id().map(|id| loaded_with.get_path(&id)),
+            Some(path)
+        );
+        let suggestions = replayed.suggestions();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].replacement, "_value");
+        assert_eq!(suggestions[0].span.as_str(), "value");
+    }
+}
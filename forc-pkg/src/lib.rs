@@ -4,8 +4,10 @@
 //! The project should consist of one or more Sway modules under a `src` directory. It may also
 //! declare a set of forc package dependencies within its manifest.
 
+mod build_cache;
 pub mod lock;
 pub mod manifest;
+mod metrics;
 mod pkg;
 pub mod source;
 
@@ -9,7 +9,11 @@ use std::{
     sync::Arc,
 };
 
-use sway_core::{fuel_prelude::fuel_tx, language::parsed::TreeType, parse_tree_type, BuildTarget};
+use sway_core::{
+    asm_generation::backend::supported_vm_versions, fuel_prelude::fuel_tx,
+    language::parsed::TreeType, parse_tree_type, BuildTarget, ExperimentalFeature, Inlining,
+    LintLevel, OptLevel, OverflowBehavior, Target,
+};
 use sway_utils::constants;
 
 /// The name of a workspace member package.
@@ -157,6 +161,12 @@ pub struct Project {
     pub entry: String,
     pub implicit_std: Option<bool>,
     pub forc_version: Option<semver::Version>,
+    /// Experimental language features enabled for this package.
+    #[serde(default)]
+    pub experimental: Vec<ExperimentalFeature>,
+    /// The target that the package's code is generated for, e.g. `fuel-0.34`, if it requires a
+    /// specific revision of the VM.
+    pub target: Option<Target>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -211,6 +221,9 @@ pub struct BuildProfile {
     #[serde(default)]
     pub print_ir: bool,
     #[serde(default)]
+    pub print_smt: bool,
+    pub dump_fn: Option<String>,
+    #[serde(default)]
     pub print_finalized_asm: bool,
     #[serde(default)]
     pub print_intermediate_asm: bool,
@@ -220,6 +233,10 @@ pub struct BuildProfile {
     pub time_phases: bool,
     #[serde(default)]
     pub metrics_outfile: Option<String>,
+    /// Whether the control-flow graph of each function and the call graph of the program are
+    /// written to the output directory.
+    #[serde(default)]
+    pub emit_cfg: bool,
     #[serde(default)]
     pub include_tests: bool,
     #[serde(default)]
@@ -227,6 +244,33 @@ pub struct BuildProfile {
     #[serde(default)]
     pub error_on_warnings: bool,
     pub reverse_results: bool,
+    #[serde(default)]
+    pub experimental: Vec<ExperimentalFeature>,
+    /// The levels of lints, by lint name, e.g. `unused_imports = "deny"`.
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintLevel>,
+    /// How much to optimize the IR, from `0` for not at all to `2`.
+    #[serde(default)]
+    pub optimization_level: OptLevel,
+    /// The IR passes to run, by name and in order, instead of those of the optimization level.
+    #[serde(default)]
+    pub ir_passes: Option<Vec<String>>,
+    /// Whether the IR is verified after each pass which modifies it.
+    #[serde(default)]
+    pub verify_ir: bool,
+    /// The heuristics deciding which function calls are inlined.
+    #[serde(default)]
+    pub inlining: Inlining,
+    /// Whether common subexpression elimination also deduplicates storage reads.
+    #[serde(default)]
+    pub cse_storage_reads: bool,
+    /// Whether the conditions of the `#[requires]` and `#[ensures]` attributes are checked at
+    /// runtime. They always are in tests.
+    #[serde(default)]
+    pub check_specifications: bool,
+    /// What the arithmetic operators do when they overflow: `"panic"`, `"wrap"` or `"checked"`.
+    #[serde(default)]
+    pub overflow: OverflowBehavior,
 }
 
 impl DependencyDetails {
@@ -327,6 +371,7 @@ impl PackageManifestFile {
     /// This checks:
     /// 1. Validity of the underlying `PackageManifest`.
     /// 2. Existence of the entry file.
+    /// 3. That the compiler can generate code for the revision of the VM in the `target`.
     pub fn validate(&self) -> Result<()> {
         self.manifest.validate()?;
         let mut entry_path = self.path.clone();
@@ -352,6 +397,25 @@ impl PackageManifestFile {
             // remove file name from nested_package_manifest
             bail!("Nested packages are not supported, please consider seperating the nested package at {} from the package at {}, or if it makes sense consider creating a workspace.", nested_package.display(), pkg_dir.display())
         }
+
+        if let Some(target) = &self.project.target {
+            let supported_vm_versions = supported_vm_versions(target.build_target);
+            if let Some(vm_version) = &target.vm_version {
+                if !supported_vm_versions.contains(&vm_version.as_str()) {
+                    bail!(
+                        "invalid `target = \"{target}\"` in {}: the compiler cannot generate code \
+                         for revision \"{vm_version}\" of the \"{}\" VM, {}",
+                        self.path.display(),
+                        target.build_target,
+                        if supported_vm_versions.is_empty() {
+                            "which has no revisions to choose from".to_string()
+                        } else {
+                            format!("only for {}", supported_vm_versions.join(", "))
+                        }
+                    );
+                }
+            }
+        }
         Ok(())
     }
 
@@ -690,15 +754,27 @@ impl BuildProfile {
             print_dca_graph: None,
             print_dca_graph_url_format: None,
             print_ir: false,
+            print_smt: false,
+            dump_fn: None,
             print_finalized_asm: false,
             print_intermediate_asm: false,
             terse: false,
             time_phases: false,
             metrics_outfile: None,
+            emit_cfg: false,
             include_tests: false,
             json_abi_with_callpaths: false,
             error_on_warnings: false,
             reverse_results: false,
+            experimental: vec![],
+            lints: BTreeMap::new(),
+            optimization_level: OptLevel::default(),
+            ir_passes: None,
+            verify_ir: false,
+            inlining: Inlining::default(),
+            cse_storage_reads: false,
+            check_specifications: true,
+            overflow: OverflowBehavior::default(),
         }
     }
 
@@ -708,15 +784,27 @@ impl BuildProfile {
             print_dca_graph: None,
             print_dca_graph_url_format: None,
             print_ir: false,
+            print_smt: false,
+            dump_fn: None,
             print_finalized_asm: false,
             print_intermediate_asm: false,
             terse: false,
             time_phases: false,
             metrics_outfile: None,
+            emit_cfg: false,
             include_tests: false,
             json_abi_with_callpaths: false,
             error_on_warnings: false,
             reverse_results: false,
+            experimental: vec![],
+            lints: BTreeMap::new(),
+            optimization_level: OptLevel::default(),
+            ir_passes: None,
+            verify_ir: false,
+            inlining: Inlining::default(),
+            cse_storage_reads: false,
+            check_specifications: false,
+            overflow: OverflowBehavior::default(),
         }
     }
 }
@@ -1211,4 +1299,24 @@ mod tests {
         assert!(dependency_details_git_rev.validate().is_ok());
         assert!(dependency_details_ipfs.validate().is_ok());
     }
+
+    #[test]
+    fn unsupported_vm_versions_are_reported_at_the_manifest() {
+        use super::PackageManifestFile;
+        use std::path::Path;
+        use sway_core::{BuildTarget, Target};
+
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples/counter");
+        let mut manifest = PackageManifestFile::from_dir(&dir).unwrap();
+        manifest.manifest.project.target =
+            Some(Target::new(BuildTarget::Fuel, Some("0.25".to_string())));
+        assert!(manifest.validate().is_ok());
+
+        manifest.manifest.project.target =
+            Some(Target::new(BuildTarget::Fuel, Some("0.1".to_string())));
+        let error = manifest.validate().unwrap_err().to_string();
+        assert!(error.starts_with("invalid `target = \"fuel-0.1\"` in "));
+        assert!(error.contains(&manifest.path().display().to_string()));
+        assert!(error.ends_with("only for 0.34, 0.25"));
+    }
 }
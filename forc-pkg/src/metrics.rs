@@ -0,0 +1,140 @@
+//! The self-contained HTML flame view of the metrics of a build.
+
+use crate::pkg::BuildMetrics;
+use std::fmt::Write;
+
+/// The phases timed within each top-level phase of a package.
+const SUBPHASES: &[(&str, &[&str])] = &[("compile_to_ast", &["parse_cst", "parse_ast"])];
+
+/// The height of each row of the flame view, in pixels.
+const ROW_HEIGHT: usize = 22;
+
+/// A bar of the flame view, positioned by its offset and width as fractions of the build.
+struct Bar {
+    depth: usize,
+    left: f64,
+    width: f64,
+    label: String,
+    elapsed: f64,
+}
+
+/// Renders `metrics` as a flame view: the build, the packages it compiled side by side, the
+/// phases of each package and the subphases and IR passes within those phases, each as wide as
+/// the time it took. The JSON `report_json` is embedded below the view.
+pub(crate) fn flame_view_html(metrics: &BuildMetrics, report_json: &str) -> String {
+    let packages_elapsed: f64 = metrics.packages.values().map(|pkg| pkg.elapsed).sum();
+    let total = metrics.elapsed.max(packages_elapsed).max(f64::EPSILON);
+
+    let mut bars = vec![Bar {
+        depth: 0,
+        left: 0.0,
+        width: 1.0,
+        label: "build".to_string(),
+        elapsed: metrics.elapsed,
+    }];
+    let mut pkg_left = 0.0;
+    for (name, pkg) in &metrics.packages {
+        bars.push(Bar {
+            depth: 1,
+            left: pkg_left,
+            width: pkg.elapsed / total,
+            label: name.clone(),
+            elapsed: pkg.elapsed,
+        });
+
+        let mut phase_left = pkg_left;
+        let top_level = pkg
+            .data
+            .metrics
+            .iter()
+            .filter(|metric| !is_subphase(&metric.phase));
+        for phase in top_level {
+            bars.push(Bar {
+                depth: 2,
+                left: phase_left,
+                width: phase.elapsed / total,
+                label: phase.phase.clone(),
+                elapsed: phase.elapsed,
+            });
+
+            let mut sub_left = phase_left;
+            let mut push_sub = |label: &str, elapsed: f64| {
+                bars.push(Bar {
+                    depth: 3,
+                    left: sub_left,
+                    width: elapsed / total,
+                    label: label.to_string(),
+                    elapsed,
+                });
+                sub_left += elapsed / total;
+            };
+            if let Some((_, subphases)) = SUBPHASES.iter().find(|(key, _)| *key == phase.phase) {
+                for sub in pkg
+                    .data
+                    .metrics
+                    .iter()
+                    .filter(|metric| subphases.contains(&metric.phase.as_str()))
+                {
+                    push_sub(&sub.phase, sub.elapsed);
+                }
+            }
+            if phase.phase == "compile_ast_to_asm" {
+                for pass in &pkg.data.passes {
+                    push_sub(&pass.pass, pass.elapsed);
+                }
+            }
+            phase_left += phase.elapsed / total;
+        }
+        pkg_left += pkg.elapsed / total;
+    }
+
+    let depth = bars.iter().map(|bar| bar.depth).max().unwrap_or(0) + 1;
+    let mut html = String::new();
+    html.push_str(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Build metrics</title>\n\
+         <style>\n\
+         body { font-family: sans-serif; margin: 1em; }\n\
+         .flame { position: relative; width: 100%; }\n\
+         .bar { position: absolute; box-sizing: border-box; overflow: hidden; \
+         white-space: nowrap; font-size: 12px; line-height: 20px; padding: 0 4px; \
+         border: 1px solid #fff; border-radius: 2px; }\n\
+         .d0 { background: #e8a33d; } .d1 { background: #eec06c; } \
+         .d2 { background: #f2d59a; } .d3 { background: #f6e6c2; }\n\
+         </style>\n</head>\n<body>\n<h1>Build metrics</h1>\n",
+    );
+    let _ = writeln!(
+        html,
+        "<div class=\"flame\" style=\"height: {}px\">",
+        depth * ROW_HEIGHT
+    );
+    for bar in &bars {
+        let title = format!("{}: {:.3}s", escape(&bar.label), bar.elapsed);
+        let _ = writeln!(
+            html,
+            "<div class=\"bar d{}\" style=\"top: {}px; left: {:.4}%; width: {:.4}%\" title=\"{}\">{}</div>",
+            bar.depth.min(3),
+            bar.depth * ROW_HEIGHT,
+            bar.left * 100.0,
+            bar.width * 100.0,
+            title,
+            escape(&bar.label),
+        );
+    }
+    html.push_str("</div>\n<h2>Report</h2>\n<pre>");
+    html.push_str(&escape(report_json));
+    html.push_str("</pre>\n</body>\n</html>\n");
+    html
+}
+
+fn is_subphase(phase: &str) -> bool {
+    SUBPHASES
+        .iter()
+        .any(|(_, subphases)| subphases.contains(&phase))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
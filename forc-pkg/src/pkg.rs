@@ -1,13 +1,19 @@
 use crate::{
+    build_cache::{self, BuildCache, Fingerprint, CACHE_DIRECTORY_NAME},
     lock::Lock,
     manifest::{BuildProfile, Dependency, ManifestFile, MemberManifestFiles, PackageManifestFile},
+    metrics,
     source::{self, IPFSNode, Source},
     CORE, PRELUDE, STD,
 };
 use anyhow::{anyhow, bail, Context, Error, Result};
 use forc_util::{
-    default_output_directory, find_file_name, kebab_to_snake_case, print_compiling,
-    print_on_failure, print_warnings, user_forc_directory,
+    default_output_directory, find_file_name, kebab_to_snake_case, print_on_failure,
+    print_warnings,
+    progress::{self, BuildPhase, Verbosity},
+    sarif,
+    tx_utils::checksum_encode,
+    user_forc_directory,
 };
 use fuel_abi_types::program_abi;
 use petgraph::{
@@ -15,9 +21,10 @@ use petgraph::{
     visit::{Bfs, Dfs, EdgeRef, Walker},
     Directed, Direction,
 };
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{hash_map, BTreeSet, HashMap, HashSet},
+    collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
     fmt,
     fs::{self, File},
     hash::{Hash, Hasher},
@@ -33,20 +40,25 @@ use sway_core::{
         fuel_abi::{self, AbiContext},
     },
     asm_generation::ProgramABI,
+    debug_info::DebugInfo,
     decl_engine::DeclRefFunction,
     fuel_prelude::{
         fuel_crypto,
         fuel_tx::{self, Contract, ContractId, StorageSlot},
     },
-    language::{parsed::TreeType, Visibility},
-    semantic_analysis::namespace,
+    language::{parsed::TreeType, ty, Visibility},
+    semantic_analysis::{namespace, program_graphs::ProgramGraphs},
     source_map::SourceMap,
     transform::AttributeKind,
-    BuildTarget, CompileResult, Engines, FinalizedEntry,
+    BuildTarget, CompileResult, Engines, ExperimentalFeature, FinalizedEntry, FunctionSize,
+    GasEstimate, LintLevel, OptLevel, Target,
+};
+use sway_error::{
+    error::CompileError,
+    warning::{CompileWarning, Warning},
 };
-use sway_error::{error::CompileError, warning::CompileWarning};
 use sway_types::{Ident, Span, Spanned};
-use sway_utils::{constants, time_expr, PerformanceData, PerformanceMetric};
+use sway_utils::{constants, time_expr, PassMetric, PerformanceData, PerformanceMetric};
 use tracing::{info, warn};
 
 type GraphIx = u32;
@@ -99,9 +111,13 @@ pub struct PinnedId(u64);
 pub struct BuiltPackage {
     pub descriptor: PackageDescriptor,
     pub program_abi: ProgramABI,
+    /// Extra information written alongside the Fuel JSON ABI.
+    pub abi_extensions: fuel_abi::AbiExtensions,
     pub storage_slots: Vec<StorageSlot>,
+    /// The name, type and storage key of each storage field. Empty for non-contracts.
+    pub storage_layout: Vec<ty::StorageFieldLayout>,
     pub warnings: Vec<CompileWarning>,
-    source_map: SourceMap,
+    pub(crate) source_map: SourceMap,
     pub tree_type: TreeType,
     pub bytecode: BuiltPackageBytecode,
     /// `Some` for contract member builds where tests were included. This is
@@ -110,6 +126,8 @@ pub struct BuiltPackage {
     ///
     /// For non-contract members, this is always `None`.
     pub bytecode_without_tests: Option<BuiltPackageBytecode>,
+    /// The control-flow and call graphs of the program, if the build profile emits them.
+    pub program_graphs: Option<ProgramGraphs>,
 }
 
 /// The package descriptors that a `BuiltPackage` holds so that the source used for building the
@@ -127,6 +145,12 @@ pub struct PackageDescriptor {
 pub struct BuiltPackageBytecode {
     pub bytes: Vec<u8>,
     pub entries: Vec<PkgEntry>,
+    /// The size of each function in the bytecode, largest first.
+    pub function_sizes: Vec<FunctionSize>,
+    /// The estimated gas used by each entry point which isn't a test.
+    pub gas_estimates: Vec<GasEstimate>,
+    /// The functions of the bytecode and their variables, for debuggers.
+    pub debug_info: DebugInfo,
 }
 
 /// Represents a package entry point.
@@ -141,6 +165,8 @@ pub struct PkgEntry {
 pub enum PkgEntryKind {
     Main,
     Test(PkgTestEntry),
+    /// A function run before or after each test.
+    TestHook(ty::TestHook),
 }
 
 /// The possible conditions for a test result to be considered "passing".
@@ -154,6 +180,10 @@ pub enum TestPassCondition {
 #[derive(Debug, Clone)]
 pub struct PkgTestEntry {
     pub pass_condition: TestPassCondition,
+    /// The height of the block the test is executed in, if set with `block_height`.
+    pub block_height: Option<u32>,
+    /// The timestamp of the block the test is executed in, if set with `timestamp`.
+    pub timestamp: Option<u64>,
     pub span: Span,
     pub file_path: Arc<PathBuf>,
 }
@@ -174,13 +204,53 @@ pub struct CompiledPackage {
     pub source_map: SourceMap,
     pub tree_type: TreeType,
     pub program_abi: ProgramABI,
+    pub abi_revert_codes: Vec<fuel_abi::AbiRevertCode>,
     pub storage_slots: Vec<StorageSlot>,
+    pub storage_layout: Vec<ty::StorageFieldLayout>,
     pub bytecode: BuiltPackageBytecode,
+    pub program_graphs: Option<ProgramGraphs>,
     pub namespace: namespace::Root,
     pub warnings: Vec<CompileWarning>,
     pub metrics: PerformanceData,
 }
 
+/// The compilation metrics of every package compiled by a build, as written to the metrics
+/// outfile.
+#[derive(Debug, Default, Serialize)]
+pub struct BuildMetrics {
+    /// The wall time of the whole build, in seconds.
+    pub elapsed: f64,
+    /// The metrics of each compiled package, by package name.
+    pub packages: BTreeMap<String, PackageMetrics>,
+}
+
+/// The compilation metrics of a single package.
+#[derive(Debug, Serialize)]
+pub struct PackageMetrics {
+    /// The hash of the package's manifest and sources, identifying the version of the package
+    /// that the metrics were collected for.
+    pub content_hash: String,
+    /// The wall time of the package's compilation, in seconds.
+    pub elapsed: f64,
+    /// The peak resident set size of the whole process once the package was compiled, in bytes,
+    /// where the platform reports it. Packages compiled in parallel all count towards it, so it's
+    /// not the memory used by this package alone.
+    pub process_peak_rss: Option<u64>,
+    /// The sizes of the type and declaration engines once the package was compiled. They're
+    /// shared by the packages of a build, so these include the entries of its dependencies.
+    pub engine_sizes: EngineSizes,
+    /// The phase and IR pass timings and bytecode size of the package.
+    #[serde(flatten)]
+    pub data: PerformanceData,
+}
+
+/// The number of live entries in the engines of a build.
+#[derive(Debug, Default, Serialize)]
+pub struct EngineSizes {
+    pub types: usize,
+    pub decls: usize,
+}
+
 /// Compiled contract dependency parts relevant to calculating a contract's ID.
 pub struct CompiledContractDependency {
     pub bytecode: Vec<u8>,
@@ -262,8 +332,20 @@ pub struct PrintOpts {
     pub intermediate_asm: bool,
     /// Print the generated Sway IR (Intermediate Representation).
     pub ir: bool,
+    /// Print the pure functions of the optimized IR in SMT-LIB 2, along with their path
+    /// conditions.
+    pub smt: bool,
+    /// Print the IR, the finalized ASM and the range in the bytecode of each function whose name
+    /// matches this pattern, in which `*` matches any sequence of characters.
+    pub dump_fn: Option<String>,
     /// Output build errors and warnings in reverse order.
     pub reverse_order: bool,
+    /// Print the size of each function in the bytecode of each built package, and write it to a
+    /// `<name>-size_report.json` file in the output directory.
+    pub size_report: bool,
+    /// Print the estimated gas used by each ABI method or entry function of each built package,
+    /// and write it to a `<name>-gas_report.json` file in the output directory.
+    pub gas_report: bool,
 }
 
 #[derive(Default, Clone)]
@@ -300,7 +382,8 @@ pub struct BuildOpts {
     pub release: bool,
     /// Output the time elapsed over each part of the compilation process.
     pub time_phases: bool,
-    /// If set, outputs compilation metrics info in JSON format.
+    /// If set, outputs the compilation metrics of every compiled package into this file, in JSON
+    /// format.
     pub metrics_outfile: Option<String>,
     /// Warnings must be treated as compiler errors.
     pub error_on_warnings: bool,
@@ -308,6 +391,22 @@ pub struct BuildOpts {
     pub tests: bool,
     /// The set of options to filter by member project kind.
     pub member_filter: MemberFilter,
+    /// Experimental language features to enable, in addition to those enabled by the manifests.
+    pub experimental: Vec<ExperimentalFeature>,
+    /// The levels of lints by lint name, overriding those of the build profile.
+    pub lints: BTreeMap<String, LintLevel>,
+    /// How much to optimize the IR, overriding the level of the build profile.
+    pub optimization_level: Option<OptLevel>,
+    /// The IR passes to run, by name and in order, instead of those of the optimization level.
+    pub ir_passes: Option<Vec<String>>,
+    /// Records the errors and warnings of the packages built, along with printing them.
+    pub sarif: Option<Arc<sarif::Collector>>,
+    /// Verify the IR after each pass which modifies it.
+    pub verify_ir: bool,
+    /// Write the control-flow graph of each function and the call graph of each built package to
+    /// `<name>-cfg` and `<name>-call_graph` files in the output directory, in the DOT and JSON
+    /// formats.
+    pub emit_cfg: bool,
 }
 
 /// The set of options to filter type of projects to build in a workspace.
@@ -434,6 +533,10 @@ impl BuiltPackage {
             ProgramABI::Fuel(program_abi) => {
                 if !program_abi.functions.is_empty() {
                     let file = File::create(path)?;
+                    let program_abi = fuel_abi::ExtendedProgramABI {
+                        program_abi,
+                        extensions: &self.abi_extensions,
+                    };
                     let res = if minify.json_abi {
                         serde_json::to_writer(&file, &program_abi)
                     } else {
@@ -479,6 +582,13 @@ impl BuiltPackage {
         let json_abi_path = output_dir.join(program_abi_stem).with_extension("json");
         self.write_json_abi(&json_abi_path, minify.clone())?;
 
+        // Along with the functions and variables of the bytecode, so that debuggers can show them
+        // by name.
+        let debug_info_stem = format!("{pkg_name}-debug_info");
+        let debug_info_path = output_dir.join(debug_info_stem).with_extension("json");
+        let debug_info_file = File::create(debug_info_path)?;
+        serde_json::to_writer(&debug_info_file, &self.bytecode.debug_info)?;
+
         info!("      Bytecode size: {} bytes", self.bytecode.bytes.len());
         // Additional ops required depending on the program type
         match self.tree_type {
@@ -494,18 +604,30 @@ impl BuiltPackage {
                 };
 
                 res?;
+
+                // Along with the layout of the storage fields, used to validate storage
+                // initialization values at deploy time.
+                let storage_layout_stem = format!("{pkg_name}-storage_layout");
+                let storage_layout_path =
+                    output_dir.join(storage_layout_stem).with_extension("json");
+                let storage_layout_file = File::create(storage_layout_path)?;
+                let res = if minify.json_storage_slots {
+                    serde_json::to_writer(&storage_layout_file, &self.storage_layout)
+                } else {
+                    serde_json::to_writer_pretty(&storage_layout_file, &self.storage_layout)
+                };
+
+                res?;
             }
             TreeType::Predicate => {
                 // Get the root hash of the bytecode for predicates and store the result in a file in the output directory
                 // TODO: Pass the user specified `ChainId` into `predicate_owner`
-                let root = format!(
-                    "0x{}",
-                    fuel_tx::Input::predicate_owner(&self.bytecode.bytes, &ChainId::default(),)
-                );
+                let root =
+                    fuel_tx::Input::predicate_owner(&self.bytecode.bytes, &ChainId::default());
                 let root_file_name = format!("{}{}", &pkg_name, SWAY_BIN_ROOT_SUFFIX);
                 let root_path = output_dir.join(root_file_name);
-                fs::write(root_path, &root)?;
-                info!("      Predicate root: {}", root);
+                fs::write(root_path, format!("0x{root}"))?;
+                info!("      Predicate root: {}", checksum_encode(&root));
             }
             TreeType::Script => {
                 // hash the bytecode for scripts and store the result in a file in the output directory
@@ -521,6 +643,76 @@ impl BuiltPackage {
 
         Ok(())
     }
+
+    /// Prints the size of each function in the bytecode, largest first, and writes it to a
+    /// `<pkg_name>-size_report.json` file in the output directory.
+    pub fn write_size_report(&self, pkg_name: &str, output_dir: &Path) -> Result<()> {
+        let function_sizes = &self.bytecode.function_sizes;
+        let total_bytes = self.bytecode.bytes.len().max(1) as f64;
+        info!("      Size report:");
+        info!(
+            "        {:>8} {:>8} {:>6}  function",
+            "bytes", "instrs", "%"
+        );
+        for size in function_sizes {
+            info!(
+                "        {:>8} {:>8} {:>5.1}%  {}",
+                size.bytes,
+                size.instructions,
+                size.bytes as f64 * 100.0 / total_bytes,
+                size.signature
+            );
+        }
+
+        let size_report_stem = format!("{pkg_name}-size_report");
+        let size_report_path = output_dir.join(size_report_stem).with_extension("json");
+        let size_report_file = File::create(size_report_path)?;
+        serde_json::to_writer_pretty(&size_report_file, function_sizes)?;
+        Ok(())
+    }
+
+    /// Prints the estimated gas used by each ABI method or entry function, and writes it to a
+    /// `<pkg_name>-gas_report.json` file in the output directory.
+    pub fn write_gas_report(&self, pkg_name: &str, output_dir: &Path) -> Result<()> {
+        let gas_estimates = &self.bytecode.gas_estimates;
+        info!("      Gas report:");
+        for estimate in gas_estimates {
+            let gas = match (estimate.max_gas, estimate.has_dynamic_costs) {
+                (None, _) => "unbounded: loops or recurses".to_string(),
+                (Some(gas), false) => format!("at most {gas} gas"),
+                (Some(gas), true) => format!("at most {gas} gas, plus size-dependent costs"),
+            };
+            info!("        {}: {gas}", estimate.name);
+        }
+
+        let gas_report_stem = format!("{pkg_name}-gas_report");
+        let gas_report_path = output_dir.join(gas_report_stem).with_extension("json");
+        let gas_report_file = File::create(gas_report_path)?;
+        serde_json::to_writer_pretty(&gas_report_file, gas_estimates)?;
+        Ok(())
+    }
+
+    /// Writes the control-flow graphs of the functions of the package to `<pkg_name>-cfg.dot`
+    /// and `<pkg_name>-cfg.json` files, and its call graph to `<pkg_name>-call_graph.dot` and
+    /// `<pkg_name>-call_graph.json` files, in the output directory.
+    pub fn write_program_graphs(&self, pkg_name: &str, output_dir: &Path) -> Result<()> {
+        let Some(program_graphs) = &self.program_graphs else {
+            return Ok(());
+        };
+        let cfg_path = output_dir.join(format!("{pkg_name}-cfg"));
+        fs::write(cfg_path.with_extension("dot"), program_graphs.cfg_dot())?;
+        let cfg_file = File::create(cfg_path.with_extension("json"))?;
+        serde_json::to_writer_pretty(&cfg_file, &program_graphs.functions)?;
+
+        let call_graph_path = output_dir.join(format!("{pkg_name}-call_graph"));
+        fs::write(
+            call_graph_path.with_extension("dot"),
+            program_graphs.call_graph_dot(),
+        )?;
+        let call_graph_file = File::create(call_graph_path.with_extension("json"))?;
+        serde_json::to_writer_pretty(&call_graph_file, &program_graphs.call_graph)?;
+        Ok(())
+    }
 }
 
 impl Built {
@@ -1551,12 +1743,36 @@ pub(crate) fn path_lock(path: &Path) -> Result<fd_lock::RwLock<File>> {
 
 /// Given a `forc_pkg::BuildProfile`, produce the necessary `sway_core::BuildConfig` required for
 /// compilation.
+///
+/// The experimental features enabled are those of the build profile along with the
+/// `experimental` features of the package's manifest. Fails if the build profile configures the
+/// level of an unknown lint.
 pub fn sway_build_config(
     manifest_dir: &Path,
     entry_path: &Path,
     build_target: BuildTarget,
     build_profile: &BuildProfile,
+    experimental: &[ExperimentalFeature],
+    target: Option<&Target>,
 ) -> Result<sway_core::BuildConfig> {
+    if let Some(lint) = build_profile
+        .lints
+        .keys()
+        .find(|lint| !Warning::LINTS.contains(&lint.as_str()))
+    {
+        bail!(
+            "unknown lint `{lint}`, expected one of: {}",
+            Warning::LINTS.join(", ")
+        );
+    }
+    if let Some(target) = target {
+        if target.build_target != build_target {
+            bail!(
+                "the package targets `{target}`, so it cannot be built for the `{build_target}` \
+                 build target"
+            );
+        }
+    }
     // Prepare the build config to pass through to the compiler.
     let file_name = find_file_name(manifest_dir, entry_path)?;
     let build_config = sway_core::BuildConfig::root_from_file_name_and_manifest_path(
@@ -1569,9 +1785,28 @@ pub fn sway_build_config(
     .print_finalized_asm(build_profile.print_finalized_asm)
     .print_intermediate_asm(build_profile.print_intermediate_asm)
     .print_ir(build_profile.print_ir)
+    .print_smt(build_profile.print_smt)
+    .dump_fn(build_profile.dump_fn.clone())
     .include_tests(build_profile.include_tests)
     .time_phases(build_profile.time_phases)
-    .metrics(build_profile.metrics_outfile.clone());
+    .metrics(build_profile.metrics_outfile.clone())
+    .vm_version(target.and_then(|target| target.vm_version.clone()))
+    .lint_levels(build_profile.lints.clone())
+    .deny_warnings(build_profile.error_on_warnings)
+    .optimization_level(build_profile.optimization_level)
+    .ir_passes(build_profile.ir_passes.clone())
+    .verify_ir(build_profile.verify_ir)
+    .inlining(build_profile.inlining)
+    .cse_storage_reads(build_profile.cse_storage_reads)
+    .check_specifications(build_profile.check_specifications)
+    .overflow(build_profile.overflow)
+    .experimental(
+        build_profile
+            .experimental
+            .iter()
+            .chain(experimental)
+            .copied(),
+    );
     Ok(build_config)
 }
 
@@ -1753,21 +1988,36 @@ pub fn compile(
     engines: &Engines,
     namespace: namespace::Module,
     source_map: &mut SourceMap,
+    sarif: Option<&sarif::Collector>,
 ) -> Result<CompiledPackage> {
     let mut metrics = PerformanceData::default();
 
     let entry_path = pkg.manifest_file.entry_path();
-    let sway_build_config =
-        sway_build_config(pkg.manifest_file.dir(), &entry_path, pkg.target, profile)?;
+    let sway_build_config = sway_build_config(
+        pkg.manifest_file.dir(),
+        &entry_path,
+        pkg.target,
+        profile,
+        &pkg.manifest_file.project.experimental,
+        pkg.manifest_file.project.target.as_ref(),
+    )?;
     let terse_mode = profile.terse;
     let reverse_results = profile.reverse_results;
     let fail = |warnings, errors| {
-        print_on_failure(engines.se(), terse_mode, warnings, errors, reverse_results);
+        print_on_failure(
+            engines.se(),
+            terse_mode,
+            warnings,
+            errors,
+            reverse_results,
+            sarif,
+        );
         bail!("Failed to compile {}", pkg.name);
     };
     let source = pkg.manifest_file.entry_string()?;
 
     // First, compile to an AST. We'll update the namespace and check for JSON ABI output.
+    progress::phase(&pkg.name, BuildPhase::CompileToAst);
     let ast_res = time_expr!(
         "compile to ast",
         "compile_to_ast",
@@ -1796,7 +2046,12 @@ pub fn compile(
     }
 
     let storage_slots = typed_program.storage_slots.clone();
+    let storage_layout = typed_program.storage_layout.clone();
     let tree_type = typed_program.kind.tree_type();
+    let abi_revert_codes = fuel_abi::generate_revert_codes(typed_program);
+    let program_graphs = profile
+        .emit_cfg
+        .then(|| ProgramGraphs::new(engines, typed_program));
 
     let namespace = typed_program.root.namespace.clone().into();
 
@@ -1804,14 +2059,16 @@ pub fn compile(
         return fail(&ast_res.warnings, &ast_res.errors);
     }
 
+    progress::phase(&pkg.name, BuildPhase::CompileAstToAsm);
     let asm_res = time_expr!(
         "compile ast to asm",
         "compile_ast_to_asm",
-        sway_core::ast_to_asm(engines, &ast_res, &sway_build_config),
+        sway_core::ast_to_asm(engines, &ast_res, &sway_build_config, &mut metrics),
         Some(sway_build_config.clone()),
         metrics
     );
 
+    progress::phase(&pkg.name, BuildPhase::GenerateAbi);
     let mut program_abi = match pkg.target {
         BuildTarget::Fuel => {
             let mut types = vec![];
@@ -1867,6 +2124,7 @@ pub fn compile(
         .iter()
         .map(|finalized_entry| PkgEntry::from_finalized_entry(finalized_entry, engines))
         .collect::<anyhow::Result<_>>()?;
+    progress::phase(&pkg.name, BuildPhase::CompileAsmToBytecode);
     let bc_res = time_expr!(
         "compile asm to bytecode",
         "compile_asm_to_bytecode",
@@ -1875,11 +2133,10 @@ pub fn compile(
         metrics
     );
 
-    let errored =
-        !bc_res.errors.is_empty() || (!bc_res.warnings.is_empty() && profile.error_on_warnings);
-
+    // With `error_on_warnings`, the compiler has reported the warnings of the lints without an
+    // explicit level as errors already.
     let compiled = match bc_res.value {
-        Some(compiled) if !errored => compiled,
+        Some(compiled) if bc_res.errors.is_empty() => compiled,
         _ => return fail(&bc_res.warnings, &bc_res.errors),
     };
 
@@ -1889,6 +2146,7 @@ pub fn compile(
         &pkg.name,
         &bc_res.warnings,
         &tree_type,
+        sarif,
     );
 
     // TODO: This should probably be in `fuel_abi_json::generate_json_abi_program`?
@@ -1910,13 +2168,19 @@ pub fn compile(
     let bytecode = BuiltPackageBytecode {
         bytes: compiled.bytecode,
         entries,
+        function_sizes: compiled.function_sizes,
+        gas_estimates: compiled.gas_estimates,
+        debug_info: compiled.debug_info,
     };
     let compiled_package = CompiledPackage {
         source_map: source_map.clone(),
         program_abi,
+        abi_revert_codes,
         storage_slots,
+        storage_layout,
         tree_type,
         bytecode,
+        program_graphs,
         namespace,
         warnings: bc_res.warnings,
         metrics,
@@ -1932,10 +2196,13 @@ impl PkgEntry {
 
     fn from_finalized_entry(finalized_entry: &FinalizedEntry, engines: &Engines) -> Result<Self> {
         let pkg_entry_kind = match &finalized_entry.test_decl_ref {
-            Some(test_decl_ref) => {
-                let pkg_test_entry = PkgTestEntry::from_decl(test_decl_ref.clone(), engines)?;
-                PkgEntryKind::Test(pkg_test_entry)
-            }
+            Some(test_decl_ref) => match engines.de().get_function(test_decl_ref).is_test_hook() {
+                Some(hook) => PkgEntryKind::TestHook(hook),
+                None => {
+                    let pkg_test_entry = PkgTestEntry::from_decl(test_decl_ref.clone(), engines)?;
+                    PkgEntryKind::Test(pkg_test_entry)
+                }
+            },
             None => PkgEntryKind::Main,
         };
 
@@ -1954,6 +2221,14 @@ impl PkgEntryKind {
             _ => None,
         }
     }
+
+    /// Returns `Some` if the `PkgEntryKind` is `TestHook`.
+    pub fn test_hook(&self) -> Option<ty::TestHook> {
+        match self {
+            PkgEntryKind::TestHook(hook) => Some(*hook),
+            _ => None,
+        }
+    }
 }
 
 impl PkgTestEntry {
@@ -1962,6 +2237,8 @@ impl PkgTestEntry {
         let test_function_decl = engines.de().get_function(&decl_ref);
 
         const FAILING_TEST_KEYWORD: &str = "should_revert";
+        const BLOCK_HEIGHT_KEYWORD: &str = "block_height";
+        const TIMESTAMP_KEYWORD: &str = "timestamp";
 
         let test_args: HashMap<String, Option<String>> = test_function_decl
             .attributes
@@ -1979,21 +2256,48 @@ impl PkgTestEntry {
             })
             .collect();
 
-        let pass_condition = if test_args.is_empty() {
-            anyhow::Ok(TestPassCondition::ShouldNotRevert)
-        } else if let Some(args) = test_args.get(FAILING_TEST_KEYWORD) {
-            let expected_revert_code = args
-                .as_ref()
-                .map(|arg| {
-                    let arg_str = arg.replace('"', "");
-                    arg_str.parse::<u64>()
-                })
-                .transpose()?;
-            anyhow::Ok(TestPassCondition::ShouldRevert(expected_revert_code))
-        } else {
-            let test_name = &test_function_decl.name;
-            bail!("Invalid test argument(s) for test: {test_name}.")
-        }?;
+        let test_name = &test_function_decl.name;
+        if let Some(arg_name) = test_args.keys().find(|arg_name| {
+            ![
+                FAILING_TEST_KEYWORD,
+                BLOCK_HEIGHT_KEYWORD,
+                TIMESTAMP_KEYWORD,
+            ]
+            .contains(&arg_name.as_str())
+        }) {
+            bail!("Invalid test argument `{arg_name}` for test: {test_name}.")
+        }
+
+        let pass_condition = match test_args.get(FAILING_TEST_KEYWORD) {
+            Some(args) => {
+                let expected_revert_code = args
+                    .as_ref()
+                    .map(|arg| {
+                        let arg_str = arg.replace('"', "");
+                        arg_str.parse::<u64>()
+                    })
+                    .transpose()?;
+                TestPassCondition::ShouldRevert(expected_revert_code)
+            }
+            None => TestPassCondition::ShouldNotRevert,
+        };
+
+        // The block the test is executed in, e.g. `#[test(block_height = 100)]`.
+        let block_arg = |keyword: &str| -> Result<Option<String>> {
+            match test_args.get(keyword) {
+                Some(Some(arg)) => Ok(Some(arg.replace('"', ""))),
+                Some(None) => bail!("Expected a value for `{keyword}` of test: {test_name}."),
+                None => Ok(None),
+            }
+        };
+        let block_height = block_arg(BLOCK_HEIGHT_KEYWORD)?
+            .map(|arg| arg.parse::<u32>())
+            .transpose()
+            .with_context(|| format!("Invalid `{BLOCK_HEIGHT_KEYWORD}` of test: {test_name}."))?;
+        let timestamp = block_arg(TIMESTAMP_KEYWORD)?
+            .map(|arg| arg.parse::<u64>())
+            .transpose()
+            .with_context(|| format!("Invalid `{TIMESTAMP_KEYWORD}` of test: {test_name}."))?;
 
         let file_path = Arc::new(
             engines.se().get_path(
@@ -2003,6 +2307,8 @@ impl PkgTestEntry {
         );
         Ok(Self {
             pass_condition,
+            block_height,
+            timestamp,
             span,
             file_path,
         })
@@ -2031,6 +2337,12 @@ fn build_profile_from_opts(
         metrics_outfile,
         tests,
         error_on_warnings,
+        experimental,
+        lints,
+        optimization_level,
+        ir_passes,
+        verify_ir,
+        emit_cfg,
         ..
     } = build_options;
     let mut selected_build_profile = BuildProfile::DEBUG;
@@ -2074,6 +2386,10 @@ fn build_profile_from_opts(
         profile.print_dca_graph_url_format = print.dca_graph_url_format.clone();
     }
     profile.print_ir |= print.ir;
+    profile.print_smt |= print.smt;
+    if profile.dump_fn.is_none() {
+        profile.dump_fn = print.dump_fn.clone();
+    }
     profile.print_finalized_asm |= print.finalized_asm;
     profile.print_intermediate_asm |= print.intermediate_asm;
     profile.terse |= pkg.terse;
@@ -2084,6 +2400,16 @@ fn build_profile_from_opts(
     profile.include_tests |= tests;
     profile.json_abi_with_callpaths |= pkg.json_abi_with_callpaths;
     profile.error_on_warnings |= error_on_warnings;
+    profile.experimental.extend(experimental);
+    profile.lints.extend(lints.clone());
+    if let Some(optimization_level) = optimization_level {
+        profile.optimization_level = *optimization_level;
+    }
+    if ir_passes.is_some() {
+        profile.ir_passes = ir_passes.clone();
+    }
+    profile.verify_ir |= verify_ir;
+    profile.emit_cfg |= emit_cfg;
 
     Ok((selected_build_profile.to_string(), profile))
 }
@@ -2139,17 +2465,34 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
     };
 
     let outputs = member_filter.filter_outputs(&build_plan, outputs);
+    let output_dir = pkg.output_directory.as_ref().map(PathBuf::from);
+    let cache_dir = output_dir
+        .clone()
+        .unwrap_or_else(|| default_output_directory(path))
+        .join(CACHE_DIRECTORY_NAME)
+        .join(&profile_name);
 
     // Build it!
     let mut built_workspace = Vec::new();
     let build_start = std::time::Instant::now();
-    let built_packages = build(&build_plan, *build_target, &build_profile, &outputs)?;
-    let output_dir = pkg.output_directory.as_ref().map(PathBuf::from);
-
-    let finished = ansi_term::Colour::Green.bold().paint("Finished");
-    info!("  {finished} {profile_name} in {:?}", build_start.elapsed());
+    let built_packages = build(
+        &build_plan,
+        *build_target,
+        &build_profile,
+        &outputs,
+        Some(&cache_dir),
+        build_options.sarif.as_deref(),
+    )?;
+
+    let quiet = progress::verbosity() == Verbosity::Quiet;
+    if !quiet {
+        let finished = ansi_term::Colour::Green.bold().paint("Finished");
+        info!("  {finished} {profile_name} in {:?}", build_start.elapsed());
+    }
     for (node_ix, built_package) in built_packages.into_iter() {
-        print_pkg_summary_header(&built_package);
+        if !quiet {
+            print_pkg_summary_header(&built_package);
+        }
         let pinned = &graph[node_ix];
         let pkg_manifest = manifest_map
             .get(&pinned.id())
@@ -2165,6 +2508,13 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
             built_package.write_debug_info(outfile.as_ref())?;
         }
         built_package.write_output(minify.clone(), &pkg_manifest.project.name, &output_dir)?;
+        if build_options.print.size_report {
+            built_package.write_size_report(&pkg_manifest.project.name, &output_dir)?;
+        }
+        if build_options.print.gas_report {
+            built_package.write_gas_report(&pkg_manifest.project.name, &output_dir)?;
+        }
+        built_package.write_program_graphs(&pkg_manifest.project.name, &output_dir)?;
         built_workspace.push(Arc::new(built_package));
     }
 
@@ -2237,210 +2587,568 @@ fn validate_contract_deps(graph: &Graph) -> Result<()> {
 /// This compiles all packages (including dependencies) in the order specified by the `BuildPlan`.
 ///
 /// Also returns the resulting `sway_core::SourceMap` which may be useful for debugging purposes.
+///
+/// If a `cache_dir` is given, packages that have not changed since they were last built there are
+/// loaded from it rather than compiled. Those that are libraries of packages which have to be
+/// compiled are type checked again for their namespaces, but their code isn't generated again.
+///
+/// The errors and warnings printed are also recorded in `sarif`, if given.
 pub fn build(
     plan: &BuildPlan,
     target: BuildTarget,
     profile: &BuildProfile,
     outputs: &HashSet<NodeIx>,
+    cache_dir: Option<&Path>,
+    sarif: Option<&sarif::Collector>,
 ) -> anyhow::Result<Vec<(NodeIx, BuiltPackage)>> {
     let mut built_packages = Vec::new();
+    let build_start = std::time::Instant::now();
 
     let required: HashSet<NodeIx> = outputs
         .iter()
         .flat_map(|output_node| plan.node_deps(*output_node))
         .collect();
 
+    let cache = cache_dir
+        .filter(|_| BuildCache::is_enabled_for(target, profile))
+        .map(|dir| BuildCache::new(dir.to_path_buf()));
     let engines = Engines::default();
-    let include_tests = profile.include_tests;
-
-    // This is the Contract ID of the current contract being compiled.
-    // We will need this for `forc test`.
-    let mut contract_id_value: Option<ContractIdConst> = None;
+    let mut fingerprints = HashMap::new();
+    let mut cached_packages = HashMap::new();
+    if let Some(cache) = &cache {
+        for &node in plan
+            .compilation_order
+            .iter()
+            .filter(|node| required.contains(node))
+        {
+            let pkg = &plan.graph()[node];
+            let manifest = &plan.manifest_map()[&pkg.id()];
+            let deps = plan
+                .graph()
+                .edges_directed(node, Direction::Outgoing)
+                .map(|edge| (edge.weight().name.as_str(), &fingerprints[&edge.target()]));
+            let fingerprint = Fingerprint::new(manifest, target, profile, deps)?;
+            let descriptor = PackageDescriptor {
+                name: pkg.name.clone(),
+                target,
+                pinned: pkg.clone(),
+                manifest_file: manifest.clone(),
+            };
+            if let Some(built_pkg) = cache.load(engines.se(), &descriptor, &fingerprint) {
+                cached_packages.insert(node, built_pkg);
+            }
+            fingerprints.insert(node, fingerprint);
+        }
+    }
+    // Compiling a package requires the namespaces of the libraries it depends on, so those whose
+    // builds are cached are type checked again, though their code isn't generated again.
+    let to_compile: HashSet<NodeIx> = required
+        .iter()
+        .copied()
+        .filter(|node| !cached_packages.contains_key(node))
+        .collect();
+    let to_check = cached_libraries_to_check(plan, &to_compile, &cached_packages);
 
-    let mut lib_namespace_map = Default::default();
+    let _progress = progress::start(to_compile.len() + to_check.len());
+    let mut lib_namespace_map = HashMap::new();
     let mut compiled_contract_deps = HashMap::new();
+    let mut build_metrics = BuildMetrics::default();
+    for level in compilation_levels(plan, &required) {
+        let mut level_to_compile = vec![];
+        let mut level_to_check = vec![];
+        for node in level {
+            if let Some(built_pkg) = cached_packages.remove(&node) {
+                print_warnings(
+                    engines.se(),
+                    profile.terse,
+                    &built_pkg.descriptor.name,
+                    &built_pkg.warnings,
+                    &built_pkg.tree_type,
+                    sarif,
+                );
+                // The packages depending on a contract only need its bytecode and storage slots.
+                if is_contract_dependency(plan.graph(), node) {
+                    let bytecode = built_pkg
+                        .bytecode_without_tests
+                        .as_ref()
+                        .unwrap_or(&built_pkg.bytecode);
+                    compiled_contract_deps.insert(
+                        node,
+                        CompiledContractDependency {
+                            bytecode: bytecode.bytes.clone(),
+                            storage_slots: built_pkg.storage_slots.clone(),
+                        },
+                    );
+                }
+                if to_check.contains(&node) {
+                    let pkg = &plan.graph()[node];
+                    let manifest = &plan.manifest_map()[&pkg.id()];
+                    progress::compiling(
+                        Some(&built_pkg.tree_type),
+                        &pkg.name,
+                        &pkg.source.display_compiling(manifest.dir()),
+                    );
+                    level_to_check.push(node);
+                }
+                if outputs.contains(&node) {
+                    built_packages.push((node, built_pkg));
+                }
+                continue;
+            }
+            let pkg = &plan.graph()[node];
+            let manifest = &plan.manifest_map()[&pkg.id()];
+            progress::compiling(
+                manifest.program_type().ok().as_ref(),
+                &pkg.name,
+                &pkg.source.display_compiling(manifest.dir()),
+            );
+            level_to_compile.push(node);
+        }
+
+        // The packages within a level only depend on those of the previous levels, so they can
+        // be compiled in parallel. Their results are merged in compilation order, so that the
+        // namespaces and outputs don't depend on which package finishes first.
+        let build_level_node = |&node: &NodeIx| {
+            let start = std::time::Instant::now();
+            let built_node = build_node(
+                plan,
+                node,
+                target,
+                profile,
+                &engines,
+                &lib_namespace_map,
+                &compiled_contract_deps,
+                sarif,
+            );
+            progress::compiled(&plan.graph()[node].name);
+            (built_node, start.elapsed())
+        };
+        let results: Vec<_> = if level_to_compile.len() > 1 {
+            level_to_compile.par_iter().map(build_level_node).collect()
+        } else {
+            level_to_compile.iter().map(build_level_node).collect()
+        };
+        let check_level_node = |&node: &NodeIx| {
+            let namespace = check_node(
+                plan,
+                node,
+                target,
+                profile,
+                &engines,
+                &lib_namespace_map,
+                &compiled_contract_deps,
+                sarif,
+            );
+            progress::compiled(&plan.graph()[node].name);
+            namespace
+        };
+        let namespaces: Vec<_> = if level_to_check.len() > 1 {
+            level_to_check.par_iter().map(check_level_node).collect()
+        } else {
+            level_to_check.iter().map(check_level_node).collect()
+        };
+        for (node, namespace) in level_to_check.into_iter().zip(namespaces) {
+            lib_namespace_map.insert(node, namespace?);
+        }
+        for (node, (result, elapsed)) in level_to_compile.into_iter().zip(results) {
+            let BuiltNode {
+                built_pkg,
+                lib_namespace,
+                contract_dep,
+                metrics,
+            } = result?;
+
+            if profile.metrics_outfile.is_some() {
+                let pkg = &plan.graph()[node];
+                let manifest = &plan.manifest_map()[&pkg.id()];
+                build_metrics.packages.insert(
+                    pkg.name.clone(),
+                    PackageMetrics {
+                        content_hash: build_cache::content_hash(manifest)?,
+                        elapsed: elapsed.as_secs_f64(),
+                        process_peak_rss: sway_utils::peak_rss(),
+                        engine_sizes: EngineSizes {
+                            types: engines.te().live_count(),
+                            decls: engines.de().live_count(),
+                        },
+                        data: metrics,
+                    },
+                );
+            }
+            if let Some(namespace) = lib_namespace {
+                lib_namespace_map.insert(node, namespace);
+            }
+            if let Some(contract_dep) = contract_dep {
+                compiled_contract_deps.insert(node, contract_dep);
+            }
+            if let Some(cache) = &cache {
+                if let Err(err) = cache.store(engines.se(), &fingerprints[&node], &built_pkg) {
+                    warn!(
+                        "failed to cache the build of {}: {err}",
+                        built_pkg.descriptor.name
+                    );
+                }
+            }
+            if outputs.contains(&node) {
+                built_packages.push((node, built_pkg));
+            }
+        }
+    }
+
+    if let Some(outfile) = &profile.metrics_outfile {
+        build_metrics.elapsed = build_start.elapsed().as_secs_f64();
+        let metrics_json =
+            serde_json::to_string(&build_metrics).expect("JSON serialization failed");
+        // An outfile with an `.html` extension gets the flame view, along with the report.
+        let is_html = Path::new(outfile)
+            .extension()
+            .map_or(false, |extension| extension == "html");
+        if is_html {
+            fs::write(
+                outfile,
+                metrics::flame_view_html(&build_metrics, &metrics_json),
+            )?;
+        } else {
+            fs::write(outfile, metrics_json)?;
+        }
+    }
+
+    // Return the packages in compilation order, regardless of which level they were built in.
+    built_packages.sort_by_key(|(node, _)| {
+        plan.compilation_order
+            .iter()
+            .position(|n| n == node)
+            .expect("built packages are part of the compilation order")
+    });
+    Ok(built_packages)
+}
+
+/// A package compiled by [build_node].
+struct BuiltNode {
+    built_pkg: BuiltPackage,
+    /// The namespace of the package if it is a library, for the packages depending on it.
+    lib_namespace: Option<namespace::Module>,
+    /// The bytecode and storage slots of the package if it is a contract dependency, for the
+    /// packages depending on it.
+    contract_dep: Option<CompiledContractDependency>,
+    metrics: PerformanceData,
+}
+
+/// Groups the `required` nodes of the plan into levels, each of which only depends on the nodes
+/// of the previous levels.
+///
+/// The nodes of each level are in compilation order.
+fn compilation_levels(plan: &BuildPlan, required: &HashSet<NodeIx>) -> Vec<Vec<NodeIx>> {
+    let mut node_levels: HashMap<NodeIx, usize> = HashMap::new();
+    let mut levels: Vec<Vec<NodeIx>> = vec![];
     for &node in plan
         .compilation_order
         .iter()
         .filter(|node| required.contains(node))
     {
-        let mut source_map = SourceMap::new();
-        let pkg = &plan.graph()[node];
-        let manifest = &plan.manifest_map()[&pkg.id()];
-        let program_ty = manifest.program_type().ok();
-
-        print_compiling(
-            program_ty.as_ref(),
-            &pkg.name,
-            &pkg.source.display_compiling(manifest.dir()),
-        );
+        // Dependencies always precede their dependents in the compilation order.
+        let level = plan
+            .graph()
+            .edges_directed(node, Direction::Outgoing)
+            .map(|edge| node_levels[&edge.target()] + 1)
+            .max()
+            .unwrap_or(0);
+        node_levels.insert(node, level);
+        if levels.len() <= level {
+            levels.resize_with(level + 1, Vec::new);
+        }
+        levels[level].push(node);
+    }
+    levels
+}
 
-        let descriptor = PackageDescriptor {
-            name: pkg.name.clone(),
-            target,
-            pinned: pkg.clone(),
-            manifest_file: manifest.clone(),
-        };
+/// The libraries among the `cached` packages whose namespaces are required to compile the
+/// packages `to_compile`, directly or through other libraries.
+fn cached_libraries_to_check<T>(
+    plan: &BuildPlan,
+    to_compile: &HashSet<NodeIx>,
+    cached: &HashMap<NodeIx, T>,
+) -> HashSet<NodeIx> {
+    let mut to_check = HashSet::new();
+    let mut stack: Vec<NodeIx> = to_compile.iter().copied().collect();
+    while let Some(node) = stack.pop() {
+        let lib_deps = plan
+            .graph()
+            .edges_directed(node, Direction::Outgoing)
+            .filter(|edge| matches!(edge.weight().kind, DepKind::Library))
+            .map(|edge| edge.target())
+            .chain(find_core_dep(plan.graph(), node));
+        for dep in lib_deps {
+            if cached.contains_key(&dep) && to_check.insert(dep) {
+                stack.push(dep);
+            }
+        }
+    }
+    to_check
+}
 
-        let fail = |warnings, errors| {
-            print_on_failure(
-                engines.se(),
-                profile.terse,
-                warnings,
-                errors,
-                profile.reverse_results,
-            );
-            bail!("Failed to compile {}", pkg.name);
-        };
+/// Type checks the library at `node`, whose build is cached, returning the namespace the packages
+/// depending on it are compiled in.
+fn check_node(
+    plan: &BuildPlan,
+    node: NodeIx,
+    target: BuildTarget,
+    profile: &BuildProfile,
+    engines: &Engines,
+    lib_namespace_map: &HashMap<NodeIx, namespace::Module>,
+    compiled_contract_deps: &CompiledContractDeps,
+    sarif: Option<&sarif::Collector>,
+) -> Result<namespace::Module> {
+    let pkg = &plan.graph()[node];
+    let manifest = &plan.manifest_map()[&pkg.id()];
+    let fail = |warnings, errors| {
+        print_on_failure(
+            engines.se(),
+            profile.terse,
+            warnings,
+            errors,
+            profile.reverse_results,
+            sarif,
+        );
+        bail!("Failed to compile {}", pkg.name);
+    };
 
-        let is_contract_dependency = is_contract_dependency(plan.graph(), node);
-        // If we are building a contract and tests are enabled or we are building a contract
-        // dependency, we need the tests exlcuded bytecode.
-        let bytecode_without_tests = if (include_tests
-            && matches!(manifest.program_type(), Ok(TreeType::Contract)))
-            || is_contract_dependency
-        {
-            // We will build a contract with tests enabled, we will also need the same contract with tests
-            // disabled for:
-            //
-            //   1. Interpreter deployment in `forc-test`.
-            //   2. Contract ID injection in `forc-pkg` if this is a contract dependency to any
-            //      other pkg, so that injected contract id is not effected by the tests.
-            let profile = BuildProfile {
-                include_tests: false,
-                ..profile.clone()
-            };
+    let dep_namespace = match dependency_namespace(
+        lib_namespace_map,
+        compiled_contract_deps,
+        plan.graph(),
+        node,
+        engines,
+        None,
+    ) {
+        Ok(o) => o,
+        Err(errs) => return fail(&[], &errs),
+    };
 
-            // `ContractIdConst` is a None here since we do not yet have a
-            // contract ID value at this point.
-            let dep_namespace = match dependency_namespace(
-                &lib_namespace_map,
-                &compiled_contract_deps,
-                plan.graph(),
-                node,
-                &engines,
-                None,
-            ) {
-                Ok(o) => o,
-                Err(errs) => return fail(&[], &errs),
-            };
+    // The cached build has reported the warnings of the package already, denied or not.
+    let profile = BuildProfile {
+        include_tests: false,
+        error_on_warnings: false,
+        lints: BTreeMap::new(),
+        ..profile.clone()
+    };
+    let build_config = sway_build_config(
+        manifest.dir(),
+        &manifest.entry_path(),
+        target,
+        &profile,
+        &manifest.project.experimental,
+        manifest.project.target.as_ref(),
+    )?;
+
+    progress::phase(&pkg.name, BuildPhase::CompileToAst);
+    let mut metrics = PerformanceData::default();
+    let ast_res = sway_core::compile_to_ast(
+        engines,
+        manifest.entry_string()?,
+        dep_namespace,
+        Some(&build_config),
+        &pkg.name,
+        &mut metrics,
+    );
+    let typed_program = match ast_res.value.as_ref().and_then(|p| p.typed.as_ref()) {
+        Some(typed_program) if ast_res.errors.is_empty() => typed_program,
+        _ => return fail(&ast_res.warnings, &ast_res.errors),
+    };
 
-            let compiled_without_tests = compile(
-                &descriptor,
-                &profile,
-                &engines,
-                dep_namespace,
-                &mut source_map,
-            )?;
+    let mut namespace =
+        namespace::Module::from(namespace::Root::from(typed_program.root.namespace.clone()));
+    namespace.name = Some(Ident::new_no_span(pkg.name.clone()));
+    Ok(namespace)
+}
 
-            if let Some(outfile) = profile.metrics_outfile {
-                let path = Path::new(&outfile);
-                let metrics_json = serde_json::to_string(&compiled_without_tests.metrics)
-                    .expect("JSON serialization failed");
-                fs::write(path, metrics_json)?;
-            }
+/// Compiles the package at `node`, given the namespaces and compiled contract dependencies of all
+/// of the packages it depends on.
+fn build_node(
+    plan: &BuildPlan,
+    node: NodeIx,
+    target: BuildTarget,
+    profile: &BuildProfile,
+    engines: &Engines,
+    lib_namespace_map: &HashMap<NodeIx, namespace::Module>,
+    compiled_contract_deps: &CompiledContractDeps,
+    sarif: Option<&sarif::Collector>,
+) -> Result<BuiltNode> {
+    let include_tests = profile.include_tests;
+    let mut source_map = SourceMap::new();
+    let pkg = &plan.graph()[node];
+    let manifest = &plan.manifest_map()[&pkg.id()];
+
+    let descriptor = PackageDescriptor {
+        name: pkg.name.clone(),
+        target,
+        pinned: pkg.clone(),
+        manifest_file: manifest.clone(),
+    };
 
-            // If this contract is built because:
-            // 1) it is a contract dependency, or
-            // 2) tests are enabled,
-            // we need to insert its CONTRACT_ID into a map for later use.
-            if is_contract_dependency {
-                let compiled_contract_dep = CompiledContractDependency {
-                    bytecode: compiled_without_tests.bytecode.bytes.clone(),
-                    storage_slots: compiled_without_tests.storage_slots.clone(),
-                };
-                compiled_contract_deps.insert(node, compiled_contract_dep);
-            } else {
-                // `forc-test` interpreter deployments are done with zeroed salt.
-                let contract_id = contract_id(
-                    compiled_without_tests.bytecode.bytes.clone(),
-                    compiled_without_tests.storage_slots,
-                    &fuel_tx::Salt::zeroed(),
-                );
-                // We finally set the contract ID value here to use for compilation later if tests are enabled.
-                contract_id_value = Some(format!("0x{contract_id}"));
-            }
-            Some(compiled_without_tests.bytecode)
-        } else {
-            None
-        };
+    let fail = |warnings, errors| {
+        print_on_failure(
+            engines.se(),
+            profile.terse,
+            warnings,
+            errors,
+            profile.reverse_results,
+            sarif,
+        );
+        bail!("Failed to compile {}", pkg.name);
+    };
 
-        // Build all non member nodes with tests disabled by overriding the current profile.
-        let profile = if !plan.member_nodes().any(|member| member == node) {
-            BuildProfile {
-                include_tests: false,
-                ..profile.clone()
-            }
-        } else {
-            profile.clone()
+    // This is the Contract ID of the contract being compiled, if it is compiled with tests.
+    // We will need this for `forc test`.
+    let mut contract_id_value: Option<ContractIdConst> = None;
+    let mut contract_dep = None;
+
+    let is_contract_dependency = is_contract_dependency(plan.graph(), node);
+    // If we are building a contract and tests are enabled or we are building a contract
+    // dependency, we need the tests exlcuded bytecode.
+    let bytecode_without_tests = if (include_tests
+        && matches!(manifest.program_type(), Ok(TreeType::Contract)))
+        || is_contract_dependency
+    {
+        // We will build a contract with tests enabled, we will also need the same contract with tests
+        // disabled for:
+        //
+        //   1. Interpreter deployment in `forc-test`.
+        //   2. Contract ID injection in `forc-pkg` if this is a contract dependency to any
+        //      other pkg, so that injected contract id is not effected by the tests.
+        let profile = BuildProfile {
+            include_tests: false,
+            ..profile.clone()
         };
 
-        // Note that the contract ID value here is only Some if tests are enabled.
+        // `ContractIdConst` is a None here since we do not yet have a
+        // contract ID value at this point.
         let dep_namespace = match dependency_namespace(
-            &lib_namespace_map,
-            &compiled_contract_deps,
+            lib_namespace_map,
+            compiled_contract_deps,
             plan.graph(),
             node,
-            &engines,
-            contract_id_value.clone(),
+            engines,
+            None,
         ) {
             Ok(o) => o,
-            Err(errs) => {
-                print_on_failure(
-                    engines.se(),
-                    profile.terse,
-                    &[],
-                    &errs,
-                    profile.reverse_results,
-                );
-                bail!("Failed to compile {}", pkg.name);
-            }
+            Err(errs) => return fail(&[], &errs),
         };
 
-        let mut compiled = compile(
+        let compiled_without_tests = compile(
             &descriptor,
             &profile,
-            &engines,
+            engines,
             dep_namespace,
             &mut source_map,
+            sarif,
         )?;
 
-        if let Some(outfile) = profile.metrics_outfile {
-            let path = Path::new(&outfile);
-            let metrics_json =
-                serde_json::to_string(&compiled.metrics).expect("JSON serialization failed");
-            fs::write(path, metrics_json)?;
+        // If this contract is built because:
+        // 1) it is a contract dependency, or
+        // 2) tests are enabled,
+        // we need to insert its CONTRACT_ID into a map for later use.
+        if is_contract_dependency {
+            contract_dep = Some(CompiledContractDependency {
+                bytecode: compiled_without_tests.bytecode.bytes.clone(),
+                storage_slots: compiled_without_tests.storage_slots.clone(),
+            });
+        } else {
+            // `forc-test` interpreter deployments are done with zeroed salt.
+            let contract_id = contract_id(
+                compiled_without_tests.bytecode.bytes.clone(),
+                compiled_without_tests.storage_slots,
+                &fuel_tx::Salt::zeroed(),
+            );
+            // We finally set the contract ID value here to use for compilation later if tests are enabled.
+            contract_id_value = Some(format!("0x{contract_id}"));
         }
+        Some(compiled_without_tests.bytecode)
+    } else {
+        None
+    };
 
-        if let TreeType::Library = compiled.tree_type {
-            let mut namespace = namespace::Module::from(compiled.namespace);
-            namespace.name = Some(Ident::new_no_span(pkg.name.clone()));
-            lib_namespace_map.insert(node, namespace);
+    // Build all non member nodes with tests disabled by overriding the current profile. The lint
+    // levels are meant for the members' own code, so dependencies are built without them.
+    let profile = if !plan.member_nodes().any(|member| member == node) {
+        BuildProfile {
+            include_tests: false,
+            lints: BTreeMap::new(),
+            ..profile.clone()
         }
-        source_map.insert_dependency(descriptor.manifest_file.dir());
+    } else {
+        profile.clone()
+    };
 
-        // TODO: This should probably be in `fuel_abi_json::generate_json_abi_program`?
-        if let ProgramABI::Fuel(ref mut program_abi) = compiled.program_abi {
-            standardize_json_abi_types(program_abi);
+    // Note that the contract ID value here is only Some if tests are enabled.
+    let dep_namespace = match dependency_namespace(
+        lib_namespace_map,
+        compiled_contract_deps,
+        plan.graph(),
+        node,
+        engines,
+        contract_id_value,
+    ) {
+        Ok(o) => o,
+        Err(errs) => {
+            print_on_failure(
+                engines.se(),
+                profile.terse,
+                &[],
+                &errs,
+                profile.reverse_results,
+                sarif,
+            );
+            bail!("Failed to compile {}", pkg.name);
         }
+    };
 
-        let built_pkg = BuiltPackage {
-            descriptor,
-            program_abi: compiled.program_abi,
-            storage_slots: compiled.storage_slots,
-            source_map: compiled.source_map,
-            tree_type: compiled.tree_type,
-            bytecode: compiled.bytecode,
-            warnings: compiled.warnings,
-            bytecode_without_tests,
-        };
+    let mut compiled = compile(
+        &descriptor,
+        &profile,
+        engines,
+        dep_namespace,
+        &mut source_map,
+        sarif,
+    )?;
 
-        if outputs.contains(&node) {
-            built_packages.push((node, built_pkg));
+    let lib_namespace = match compiled.tree_type {
+        TreeType::Library => {
+            let mut namespace = namespace::Module::from(compiled.namespace);
+            namespace.name = Some(Ident::new_no_span(pkg.name.clone()));
+            Some(namespace)
         }
-    }
+        _ => None,
+    };
+    source_map.insert_dependency(descriptor.manifest_file.dir());
 
-    Ok(built_packages)
+    // TODO: This should probably be in `fuel_abi_json::generate_json_abi_program`?
+    let mut abi_extensions = fuel_abi::AbiExtensions::default();
+    if let ProgramABI::Fuel(ref mut program_abi) = compiled.program_abi {
+        standardize_json_abi_types(program_abi);
+        abi_extensions = fuel_abi::generate_abi_extensions(program_abi, compiled.abi_revert_codes);
+    }
+
+    let built_pkg = BuiltPackage {
+        descriptor,
+        program_abi: compiled.program_abi,
+        abi_extensions,
+        storage_slots: compiled.storage_slots,
+        storage_layout: compiled.storage_layout,
+        source_map: compiled.source_map,
+        tree_type: compiled.tree_type,
+        bytecode: compiled.bytecode,
+        warnings: compiled.warnings,
+        bytecode_without_tests,
+        program_graphs: compiled.program_graphs,
+    };
+
+    Ok(BuiltNode {
+        built_pkg,
+        lib_namespace,
+        contract_dep,
+        metrics: compiled.metrics,
+    })
 }
 
 /// Standardize the JSON ABI data structure by eliminating duplicate types. This is an iterative
@@ -2583,13 +3291,86 @@ pub fn check(
     include_tests: bool,
     engines: &Engines,
 ) -> anyhow::Result<Vec<CompileResult<Programs>>> {
+    let results = check_packages(
+        plan,
+        &plan.compilation_order,
+        build_target,
+        terse_mode,
+        include_tests,
+        engines,
+        &mut Default::default(),
+    )?;
+
+    if results.is_empty() {
+        bail!("unable to check sway program: build plan contains no packages")
+    }
+
+    Ok(results)
+}
+
+/// Check the dependencies of the project, returning the namespace the project is compiled within.
+///
+/// This allows checking the project repeatedly, e.g. each time it changes, without checking its
+/// dependencies again.
+pub fn check_dependencies(
+    plan: &BuildPlan,
+    build_target: BuildTarget,
+    engines: &Engines,
+) -> anyhow::Result<namespace::Module> {
+    let Some((&project, dependencies)) = plan.compilation_order.split_last() else {
+        bail!("unable to check sway program: build plan contains no packages")
+    };
     let mut lib_namespace_map = Default::default();
+    let results = check_packages(
+        plan,
+        dependencies,
+        build_target,
+        true,
+        false,
+        engines,
+        &mut lib_namespace_map,
+    )?;
+    for (res, &node) in results.iter().zip(dependencies) {
+        if res.value.is_none() || !res.errors.is_empty() {
+            print_on_failure(engines.se(), true, &res.warnings, &res.errors, false, None);
+            bail!("Failed to check {}", plan.graph[node].name);
+        }
+    }
+    match dependency_namespace(
+        &lib_namespace_map,
+        &HashMap::new(),
+        &plan.graph,
+        project,
+        engines,
+        None,
+    ) {
+        Ok(namespace) => Ok(namespace),
+        Err(errs) => {
+            print_on_failure(engines.se(), true, &[], &errs, false, None);
+            bail!("Failed to check {}", plan.graph[project].name);
+        }
+    }
+}
+
+/// Check the packages `nodes` of `plan` in order, until one of them fails to type check.
+///
+/// The namespaces of the libraries are added to `lib_namespace_map`, in which the packages
+/// depending on them are checked.
+fn check_packages(
+    plan: &BuildPlan,
+    nodes: &[NodeIx],
+    build_target: BuildTarget,
+    terse_mode: bool,
+    include_tests: bool,
+    engines: &Engines,
+    lib_namespace_map: &mut HashMap<NodeIx, namespace::Module>,
+) -> anyhow::Result<Vec<CompileResult<Programs>>> {
     let mut source_map = SourceMap::new();
     // During `check`, we don't compile so this stays empty.
     let compiled_contract_deps = HashMap::new();
 
     let mut results = vec![];
-    for (idx, &node) in plan.compilation_order.iter().enumerate() {
+    for &node in nodes {
         let pkg = &plan.graph[node];
         let manifest = &plan.manifest_map()[&pkg.id()];
 
@@ -2603,10 +3384,10 @@ pub fn check(
         const DUMMY_CONTRACT_ID: &str =
             "0x0000000000000000000000000000000000000000000000000000000000000000";
         let contract_id_value =
-            (idx == plan.compilation_order.len() - 1).then(|| DUMMY_CONTRACT_ID.to_string());
+            (plan.compilation_order.last() == Some(&node)).then(|| DUMMY_CONTRACT_ID.to_string());
 
         let dep_namespace = dependency_namespace(
-            &lib_namespace_map,
+            lib_namespace_map,
             &compiled_contract_deps,
             &plan.graph,
             node,
@@ -2625,6 +3406,8 @@ pub fn check(
             &manifest.entry_path(),
             build_target,
             &profile,
+            &manifest.project.experimental,
+            manifest.project.target.as_ref(),
         )?
         .include_tests(include_tests);
 
@@ -2674,10 +3457,6 @@ pub fn check(
         results.push(programs_res)
     }
 
-    if results.is_empty() {
-        bail!("unable to check sway program: build plan contains no packages")
-    }
-
     Ok(results)
 }
 
@@ -2743,3 +3522,108 @@ fn test_root_pkg_order() {
         .collect();
     assert_eq!(order, vec!["test_lib", "test_contract", "test_script"])
 }
+
+#[test]
+fn test_cached_libraries_to_check() {
+    let current_dir = env!("CARGO_MANIFEST_DIR");
+    let manifest_dir = PathBuf::from(current_dir)
+        .parent()
+        .unwrap()
+        .join("test/src/e2e_vm_tests/test_programs/should_pass/forc/workspace_building/");
+    let manifest_file = ManifestFile::from_dir(&manifest_dir).unwrap();
+    let member_manifests = manifest_file.member_manifests().unwrap();
+    let lock_path = manifest_file.lock_path().unwrap();
+    let build_plan = BuildPlan::from_lock_and_manifests(
+        &lock_path,
+        &member_manifests,
+        false,
+        false,
+        Default::default(),
+    )
+    .unwrap();
+    let graph = build_plan.graph();
+    let node = |name: &str| {
+        graph
+            .node_indices()
+            .find(|&node| graph[node].name == name)
+            .unwrap()
+    };
+    let (lib, contract, script) = (node("test_lib"), node("test_contract"), node("test_script"));
+    let to_check = |to_compile: &[NodeIx]| {
+        let cached: HashMap<NodeIx, ()> = [lib, contract, script]
+            .into_iter()
+            .filter(|node| !to_compile.contains(node))
+            .map(|node| (node, ()))
+            .collect();
+        let to_compile = to_compile.iter().copied().collect();
+        cached_libraries_to_check(&build_plan, &to_compile, &cached)
+    };
+
+    // Nothing is type checked if nothing is compiled.
+    assert!(to_check(&[]).is_empty());
+    // The library is type checked for the script, but the contract it depends on isn't.
+    assert_eq!(to_check(&[script]), HashSet::from([lib]));
+    assert_eq!(to_check(&[contract]), HashSet::from([lib]));
+    // The library is compiled rather than type checked once it has changed.
+    assert!(to_check(&[lib, contract, script]).is_empty());
+}
+
+#[test]
+fn test_build_metrics_json() {
+    let mut build_metrics = BuildMetrics {
+        elapsed: 1.5,
+        ..Default::default()
+    };
+    build_metrics.packages.insert(
+        "test_lib".to_string(),
+        PackageMetrics {
+            content_hash: "abc".to_string(),
+            elapsed: 1.0,
+            process_peak_rss: Some(1024),
+            engine_sizes: EngineSizes {
+                types: 10,
+                decls: 5,
+            },
+            data: PerformanceData {
+                bytecode_size: 42,
+                metrics: vec![PerformanceMetric {
+                    phase: "compile_to_ast".to_string(),
+                    elapsed: 0.5,
+                    memory_usage: None,
+                    process_peak_rss: None,
+                }],
+                passes: vec![PassMetric {
+                    pass: "inline".to_string(),
+                    elapsed: 0.25,
+                    runs: 2,
+                }],
+            },
+        },
+    );
+    assert_eq!(
+        serde_json::to_value(&build_metrics).unwrap(),
+        serde_json::json!({
+            "elapsed": 1.5,
+            "packages": {
+                "test_lib": {
+                    "content_hash": "abc",
+                    "elapsed": 1.0,
+                    "process_peak_rss": 1024,
+                    "engine_sizes": { "types": 10, "decls": 5 },
+                    "bytecode_size": 42,
+                    "metrics": [
+                        {
+                            "phase": "compile_to_ast",
+                            "elapsed": 0.5,
+                            "memory_usage": null,
+                            "process_peak_rss": null
+                        }
+                    ],
+                    "passes": [
+                        { "pass": "inline", "elapsed": 0.25, "runs": 2 }
+                    ]
+                }
+            }
+        })
+    );
+}
@@ -5,6 +5,7 @@ use forc_tracing::init_tracing_subscriber;
 async fn main() {
     init_tracing_subscriber(Default::default());
     let command = forc_client::cmd::Run::parse();
+    forc_util::set_color_choice(command.color);
     if let Err(err) = forc_client::op::run(command).await {
         tracing::error!("Error: {:?}", err);
         std::process::exit(1);
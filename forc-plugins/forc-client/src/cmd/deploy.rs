@@ -1,10 +1,11 @@
 use clap::Parser;
 use fuel_crypto::SecretKey;
+use std::path::PathBuf;
 
 pub use crate::util::Target;
 pub use forc::cli::shared::{BuildOutput, BuildProfile, Minify, Pkg, Print};
 pub use forc_tx::{Gas, Maturity};
-pub use forc_util::tx_utils::Salt;
+pub use forc_util::{tx_utils::Salt, ColorChoice};
 
 #[derive(Debug, Default, Parser)]
 #[clap(bin_name = "forc deploy", version)]
@@ -59,4 +60,14 @@ pub struct Command {
     /// Use preset configuration for the latest testnet.
     #[clap(long)]
     pub testnet: bool,
+    /// Path to a JSON file of initial storage values, eg.: { "counter": 42, "paused": false }
+    ///
+    /// The values are validated against the contract's storage layout and override the
+    /// initializers of the corresponding storage fields. Only supported when deploying a single
+    /// contract, and only for fields of type u8, u16, u32, u64, bool and b256.
+    #[clap(long)]
+    pub storage_init: Option<PathBuf>,
+    /// When to color the errors and warnings: `auto`, `always` or `never`.
+    #[clap(long, value_enum, default_value_t)]
+    pub color: ColorChoice,
 }
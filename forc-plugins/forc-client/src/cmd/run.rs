@@ -4,6 +4,7 @@ use fuel_crypto::SecretKey;
 pub use super::submit::Network;
 pub use forc::cli::shared::{BuildOutput, BuildProfile, Minify, Pkg, Print};
 pub use forc_tx::{Gas, Maturity};
+pub use forc_util::ColorChoice;
 
 /// Run script project.
 /// Crafts a script transaction then sends it to a running node.
@@ -30,15 +31,34 @@ pub struct Command {
     #[clap(long, env = "FUEL_NODE_URL")]
     pub node_url: Option<String>,
     /// Hex string of data to input to script.
-    #[clap(short, long)]
+    #[clap(short, long, conflicts_with = "args")]
     pub data: Option<String>,
+    /// An argument of the script's `main` function, given once per argument, in order.
+    /// The arguments are encoded as script data according to the types of `main`'s parameters
+    /// in the script's ABI.
+    #[clap(long = "arg")]
+    pub args: Option<Vec<String>>,
+    /// An argument of a predicate's `main` function, given once per argument, in order.
+    /// When provided, predicate members are built too, and the predicate data encoding these
+    /// arguments according to the types of `main`'s parameters in the predicate's ABI is printed.
+    #[clap(long = "predicate-data")]
+    pub predicate_data: Option<Vec<String>>,
     /// Only craft transaction and print it out.
     #[clap(long)]
     pub dry_run: bool,
     /// Pretty-print the outputs from the node.
     #[clap(long = "pretty-print", short = 'r')]
     pub pretty_print: bool,
+    /// Print the receipts as a tree of the contract calls made by the script, with the logs,
+    /// returns and transfers of each call, instead of a flat list.
+    ///
+    /// Contracts and their methods are named after the packages they were deployed from by
+    /// `forc deploy`, as recorded in the `deployments.json` file of its output directory.
+    #[clap(long)]
+    pub trace: bool,
     /// 32-byte contract ID that will be called during the transaction.
+    ///
+    /// Accepts lowercase or uppercase hex, or a checksummed ID as printed by `forc deploy`.
     #[clap(long = "contract")]
     pub contract: Option<Vec<String>>,
     /// Execute the transaction and return the final mutated transaction along with receipts
@@ -54,4 +74,7 @@ pub struct Command {
     /// Sign the deployment transaction manually.
     #[clap(long)]
     pub manual_signing: bool,
+    /// When to color the errors and warnings: `auto`, `always` or `never`.
+    #[clap(long, value_enum, default_value_t)]
+    pub color: ColorChoice,
 }
@@ -1,13 +1,16 @@
 use crate::{
     cmd::{self, deploy::Target},
     util::{
+        deployments::Deployments,
         pkg::built_pkgs,
+        storage::{apply_storage_init, read_storage_init, storage_init_slots},
         tx::{TransactionBuilderExt, WalletSelectionMode, TX_SUBMIT_TIMEOUT_MS},
     },
 };
 use anyhow::{bail, Context, Result};
 use forc_pkg::{self as pkg, PackageManifestFile};
 use forc_tx::Gas;
+use forc_util::tx_utils::checksum_encode;
 use fuel_core_client::client::types::TransactionStatus;
 use fuel_core_client::client::FuelClient;
 use fuel_tx::{Output, Salt, TransactionBuilder};
@@ -126,6 +129,19 @@ pub async fn deploy(command: cmd::Deploy) -> Result<Vec<DeployedContract>> {
         None
     };
 
+    if command.storage_init.is_some() {
+        let num_contracts = built_pkgs
+            .iter()
+            .filter(|pkg| pkg.tree_type == TreeType::Contract)
+            .count();
+        if num_contracts > 1 {
+            bail!("`--storage-init` can only be used when deploying a single contract");
+        }
+    }
+
+    // The deployed contracts are recorded so that `forc run --trace` can name them.
+    let deployments_path = Deployments::path(&curr_dir);
+    let mut deployments = Deployments::load(&deployments_path)?;
     for pkg in built_pkgs {
         if pkg
             .descriptor
@@ -147,8 +163,10 @@ pub async fn deploy(command: cmd::Deploy) -> Result<Vec<DeployedContract>> {
                     bail!("Both `--salt` and `--default-salt` were specified: must choose one")
                 }
             };
-            let contract_id =
-                deploy_pkg(&command, &pkg.descriptor.manifest_file, &pkg, salt).await?;
+            let manifest = &pkg.descriptor.manifest_file;
+            let contract_id = deploy_pkg(&command, manifest, &pkg, salt).await?;
+            deployments.insert(&contract_id.id, &pkg, node_url(&command, manifest));
+            deployments.save(&deployments_path)?;
             contract_ids.push(contract_id);
         }
     }
@@ -204,16 +222,24 @@ pub async fn deploy_pkg(
     compiled: &BuiltPackage,
     salt: Salt,
 ) -> Result<DeployedContract> {
-    let node_url = command
-        .node_url
-        .as_deref()
-        .or_else(|| manifest.network.as_ref().map(|nw| &nw.url[..]))
-        .unwrap_or(crate::default::NODE_URL);
+    let node_url = node_url(command, manifest);
     let client = FuelClient::new(node_url)?;
 
     let bytecode = &compiled.bytecode.bytes;
 
     let mut storage_slots = compiled.storage_slots.clone();
+    if let Some(storage_init) = &command.storage_init {
+        let values = read_storage_init(storage_init)?;
+        let init_slots =
+            storage_init_slots(&compiled.storage_layout, &values).with_context(|| {
+                format!(
+                    "storage init file {} does not match the storage of contract {}",
+                    storage_init.display(),
+                    manifest.project_name()
+                )
+            })?;
+        apply_storage_init(&mut storage_slots, init_slots);
+    }
     storage_slots.sort();
 
     let contract = Contract::from(bytecode.clone());
@@ -252,7 +278,7 @@ pub async fn deploy_pkg(
                 info!("\n\nContract {pkg_name} Deployed!");
 
                 info!("\nNetwork: {node_url}");
-                info!("Contract ID: 0x{contract_id}");
+                info!("Contract ID: {}", checksum_encode(&contract_id));
                 info!("Deployed in block {}", &block_id);
                 Ok(contract_id)
             }
@@ -282,6 +308,15 @@ pub async fn deploy_pkg(
     Ok(DeployedContract { id: contract_id })
 }
 
+/// The URL of the node to deploy the package of `manifest` to.
+fn node_url<'a>(command: &'a cmd::Deploy, manifest: &'a PackageManifestFile) -> &'a str {
+    command
+        .node_url
+        .as_deref()
+        .or_else(|| manifest.network.as_ref().map(|nw| &nw.url[..]))
+        .unwrap_or(crate::default::NODE_URL)
+}
+
 fn build_opts_from_cmd(cmd: &cmd::Deploy) -> pkg::BuildOpts {
     pkg::BuildOpts {
         pkg: pkg::PkgOpts {
@@ -300,7 +335,11 @@ fn build_opts_from_cmd(cmd: &cmd::Deploy) -> pkg::BuildOpts {
             finalized_asm: cmd.print.finalized_asm,
             intermediate_asm: cmd.print.intermediate_asm,
             ir: cmd.print.ir,
+            smt: cmd.print.smt,
+            dump_fn: cmd.print.dump_fn.clone(),
             reverse_order: cmd.print.reverse_order,
+            size_report: cmd.print.size_report,
+            gas_report: cmd.print.gas_report,
         },
         time_phases: cmd.print.time_phases,
         metrics_outfile: cmd.print.metrics_outfile.clone(),
@@ -316,6 +355,13 @@ fn build_opts_from_cmd(cmd: &cmd::Deploy) -> pkg::BuildOpts {
         build_target: BuildTarget::default(),
         tests: false,
         member_filter: pkg::MemberFilter::only_contracts(),
+        lints: cmd.build_profile.lints(),
+        optimization_level: cmd.build_profile.optimization_level,
+        ir_passes: cmd.build_profile.ir_passes.clone(),
+        sarif: None,
+        verify_ir: cmd.build_profile.verify_ir,
+        emit_cfg: cmd.build_output.emit_cfg(),
+        experimental: cmd.build_profile.experimental.clone(),
     }
 }
 
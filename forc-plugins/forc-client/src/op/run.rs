@@ -1,18 +1,23 @@
 use crate::{
     cmd,
     util::{
+        deployments::Deployments,
+        encode::encode_main_args,
         pkg::built_pkgs,
+        trace::format_call_tree,
         tx::{TransactionBuilderExt, WalletSelectionMode, TX_SUBMIT_TIMEOUT_MS},
     },
 };
 use anyhow::{anyhow, bail, Context, Result};
 use forc_pkg::{self as pkg, fuel_core_not_running, PackageManifestFile};
-use forc_util::tx_utils::format_log_receipts;
+use forc_receipts::format_receipts;
+use forc_util::tx_utils::{checksum_decode, receipt_decoder};
+use fuel_abi_types::abi::program::ProgramABI;
 use fuel_core_client::client::FuelClient;
 use fuel_tx::{ContractId, Transaction, TransactionBuilder};
 use pkg::BuiltPackage;
+use std::path::PathBuf;
 use std::time::Duration;
-use std::{path::PathBuf, str::FromStr};
 use sway_core::language::parsed::TreeType;
 use sway_core::BuildTarget;
 use tokio::time::timeout;
@@ -38,14 +43,22 @@ pub async fn run(command: cmd::Run) -> Result<Vec<RanScript>> {
     let build_opts = build_opts_from_cmd(&command);
     let built_pkgs_with_manifest = built_pkgs(&curr_dir, build_opts)?;
     for built in built_pkgs_with_manifest {
-        if built
-            .descriptor
-            .manifest_file
-            .check_program_type(vec![TreeType::Script])
-            .is_ok()
-        {
-            let pkg_receipts = run_pkg(&command, &built.descriptor.manifest_file, &built).await?;
+        let manifest = &built.descriptor.manifest_file;
+        if manifest.check_program_type(vec![TreeType::Script]).is_ok() {
+            let pkg_receipts = run_pkg(&command, manifest, &built).await?;
             receipts.push(pkg_receipts);
+        } else if let Some(args) = &command.predicate_data {
+            if manifest
+                .check_program_type(vec![TreeType::Predicate])
+                .is_ok()
+            {
+                let predicate_data = encode_main_args(&fuel_program_abi(&built)?, args)?;
+                info!(
+                    "{} predicate data: 0x{}",
+                    built.descriptor.name,
+                    hex::encode(predicate_data)
+                );
+            }
         }
     }
 
@@ -57,9 +70,14 @@ pub async fn run_pkg(
     manifest: &PackageManifestFile,
     compiled: &BuiltPackage,
 ) -> Result<RanScript> {
-    let input_data = command.data.as_deref().unwrap_or("");
-    let data = input_data.strip_prefix("0x").unwrap_or(input_data);
-    let script_data = hex::decode(data).expect("Invalid hex");
+    let script_data = match &command.args {
+        Some(args) => encode_main_args(&fuel_program_abi(compiled)?, args)?,
+        None => {
+            let input_data = command.data.as_deref().unwrap_or("");
+            let data = input_data.strip_prefix("0x").unwrap_or(input_data);
+            hex::decode(data).expect("Invalid hex")
+        }
+    };
 
     let node_url = command
         .node_url
@@ -73,7 +91,8 @@ pub async fn run_pkg(
         .into_iter()
         .flat_map(|contracts| contracts.iter())
         .map(|contract| {
-            ContractId::from_str(contract)
+            checksum_decode(contract)
+                .map(ContractId::new)
                 .map_err(|e| anyhow!("Failed to parse contract id: {}", e))
         })
         .collect::<Result<Vec<ContractId>>>()?;
@@ -98,16 +117,37 @@ pub async fn run_pkg(
         info!("{:?}", tx);
         Ok(RanScript { receipts: vec![] })
     } else {
-        let receipts =
-            try_send_tx(node_url, &tx.into(), command.pretty_print, command.simulate).await?;
+        let receipts = try_send_tx(node_url, &tx.into(), command.simulate).await?;
+        let decoder = receipt_decoder(&compiled.program_abi)?;
+        if command.trace {
+            let deployments = Deployments::load_from_ancestors(manifest.dir())?;
+            info!(
+                "{}",
+                format_call_tree(&receipts, compiled, &decoder, &deployments)
+            );
+        } else {
+            info!(
+                "{}",
+                format_receipts(&receipts, &decoder, command.pretty_print)?
+            );
+        }
         Ok(RanScript { receipts })
     }
 }
 
+/// The Fuel ABI of `built`, as understood by the SDK.
+fn fuel_program_abi(built: &BuiltPackage) -> Result<ProgramABI> {
+    match &built.program_abi {
+        sway_core::asm_generation::ProgramABI::Fuel(program_abi) => {
+            Ok(serde_json::from_value(serde_json::to_value(program_abi)?)?)
+        }
+        _ => bail!("{} has no Fuel ABI", built.descriptor.name),
+    }
+}
+
 async fn try_send_tx(
     node_url: &str,
     tx: &Transaction,
-    pretty_print: bool,
     simulate: bool,
 ) -> Result<Vec<fuel_tx::Receipt>> {
     let client = FuelClient::new(node_url)?;
@@ -115,7 +155,7 @@ async fn try_send_tx(
     match client.health().await {
         Ok(_) => timeout(
             Duration::from_millis(TX_SUBMIT_TIMEOUT_MS),
-            send_tx(&client, tx, pretty_print, simulate),
+            send_tx(&client, tx, simulate),
         )
         .await
         .with_context(|| format!("timeout waiting for {:?} to be included in a block", tx))?,
@@ -126,7 +166,6 @@ async fn try_send_tx(
 async fn send_tx(
     client: &FuelClient,
     tx: &Transaction,
-    pretty_print: bool,
     simulate: bool,
 ) -> Result<Vec<fuel_tx::Receipt>> {
     use fuels_accounts::provider::ClientExt;
@@ -144,10 +183,7 @@ async fn send_tx(
     };
 
     match outputs {
-        Ok(logs) => {
-            info!("{}", format_log_receipts(&logs, pretty_print)?);
-            Ok(logs)
-        }
+        Ok(logs) => Ok(logs),
         Err(e) => bail!("{e}"),
     }
 }
@@ -170,7 +206,11 @@ fn build_opts_from_cmd(cmd: &cmd::Run) -> pkg::BuildOpts {
             finalized_asm: cmd.print.finalized_asm,
             intermediate_asm: cmd.print.intermediate_asm,
             ir: cmd.print.ir,
+            smt: cmd.print.smt,
+            dump_fn: cmd.print.dump_fn.clone(),
             reverse_order: cmd.print.reverse_order,
+            size_report: cmd.print.size_report,
+            gas_report: cmd.print.gas_report,
         },
         minify: pkg::MinifyOpts {
             json_abi: cmd.minify.json_abi,
@@ -185,6 +225,16 @@ fn build_opts_from_cmd(cmd: &cmd::Run) -> pkg::BuildOpts {
         binary_outfile: cmd.build_output.bin_file.clone(),
         debug_outfile: cmd.build_output.debug_file.clone(),
         tests: false,
-        member_filter: pkg::MemberFilter::only_scripts(),
+        member_filter: pkg::MemberFilter {
+            build_predicates: cmd.predicate_data.is_some(),
+            ..pkg::MemberFilter::only_scripts()
+        },
+        lints: cmd.build_profile.lints(),
+        optimization_level: cmd.build_profile.optimization_level,
+        ir_passes: cmd.build_profile.ir_passes.clone(),
+        sarif: None,
+        verify_ir: cmd.build_profile.verify_ir,
+        emit_cfg: cmd.build_output.emit_cfg(),
+        experimental: cmd.build_profile.experimental.clone(),
     }
 }
@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use forc_pkg::BuiltPackage;
+use forc_util::tx_utils::checksum_encode;
+use fuel_tx::ContractId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The name of the file in the output directory in which `forc deploy` records the contracts it
+/// deployed.
+pub(crate) const DEPLOYMENTS_FILE_NAME: &str = "deployments.json";
+
+/// The contracts deployed from a package or workspace, by contract ID, as recorded in its
+/// deployments manifest.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Deployments {
+    pub(crate) contracts: BTreeMap<String, Deployment>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct Deployment {
+    /// The name of the deployed package.
+    pub(crate) name: String,
+    /// The URL of the node the contract was deployed to.
+    pub(crate) node_url: String,
+    /// The names of the ABI methods of the contract, by function selector, e.g. `0x2151bd4b`.
+    pub(crate) methods: BTreeMap<String, String>,
+}
+
+impl Deployments {
+    /// The deployments manifest in the output directory of `dir`.
+    pub(crate) fn path(dir: &Path) -> PathBuf {
+        forc_util::default_output_directory(dir).join(DEPLOYMENTS_FILE_NAME)
+    }
+
+    /// Loads the deployments manifest at `path`, or returns an empty one if there is none.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse deployments manifest {}", path.display()))
+    }
+
+    /// Loads the deployments manifests of `dir` and of each of its ancestors, so that those of a
+    /// workspace are found from within its members.  Those closest to `dir` take precedence.
+    pub(crate) fn load_from_ancestors(dir: &Path) -> Result<Self> {
+        let mut deployments = Self::default();
+        for dir in dir.ancestors() {
+            for (contract_id, deployment) in Self::load(&Self::path(dir))?.contracts {
+                deployments
+                    .contracts
+                    .entry(contract_id)
+                    .or_insert(deployment);
+            }
+        }
+        Ok(deployments)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Records the deployment of `built` at `contract_id`.
+    pub(crate) fn insert(
+        &mut self,
+        contract_id: &ContractId,
+        built: &BuiltPackage,
+        node_url: &str,
+    ) {
+        let methods = built
+            .bytecode
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let selector = entry.finalized.selector?;
+                Some((
+                    format!("0x{}", hex::encode(selector)),
+                    entry.finalized.fn_name.clone(),
+                ))
+            })
+            .collect();
+        self.contracts.insert(
+            checksum_encode(contract_id),
+            Deployment {
+                name: built.descriptor.name.clone(),
+                node_url: node_url.to_string(),
+                methods,
+            },
+        );
+    }
+
+    /// The deployment of the contract `contract_id`, if it's recorded.
+    pub(crate) fn get(&self, contract_id: &ContractId) -> Option<&Deployment> {
+        self.contracts.get(&checksum_encode(contract_id))
+    }
+}
+
+impl Deployment {
+    /// The name of the method with the given function selector, as passed in the first
+    /// parameter of a call.
+    pub(crate) fn method_name(&self, selector: u64) -> Option<&str> {
+        self.methods
+            .get(&format!("0x{selector:08x}"))
+            .map(String::as_str)
+    }
+}
@@ -1,6 +1,8 @@
 use std::str::FromStr;
 
-use fuel_abi_types::abi::full_program::FullTypeApplication;
+use anyhow::{anyhow, bail, Context};
+use fuel_abi_types::abi::{full_program::FullTypeApplication, program::ProgramABI};
+use fuels_core::abi_encoder::ABIEncoder;
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// A wrapper around fuels_core::types::Token, which enables serde de/serialization.
@@ -66,7 +68,6 @@ impl Token {
     ///
     /// Generates an error if there is a mismatch between the type information and the provided
     /// value for that type.
-    #[allow(dead_code)]
     pub(crate) fn from_type_and_value(arg_type: &Type, value: &str) -> anyhow::Result<Self> {
         match arg_type {
             Type::Unit => Ok(Token(fuels_core::types::Token::Unit)),
@@ -94,6 +95,44 @@ impl Token {
     }
 }
 
+/// Encodes `args` as the arguments of the `main` function of the program described by
+/// `program_abi`, e.g. as the script data of a script or the predicate data of a predicate.
+pub(crate) fn encode_main_args(
+    program_abi: &ProgramABI,
+    args: &[String],
+) -> anyhow::Result<Vec<u8>> {
+    let main = program_abi
+        .functions
+        .iter()
+        .find(|function| function.name == "main")
+        .ok_or_else(|| anyhow!("the program ABI has no `main` function"))?;
+    if main.inputs.len() != args.len() {
+        bail!(
+            "`main` takes {} argument(s) but {} were provided",
+            main.inputs.len(),
+            args.len()
+        );
+    }
+    let tokens = main
+        .inputs
+        .iter()
+        .zip(args)
+        .map(|(input, arg)| {
+            let type_decl = program_abi
+                .types
+                .iter()
+                .find(|type_decl| type_decl.type_id == input.type_id)
+                .ok_or_else(|| anyhow!("the type of `{}` is missing from the ABI", input.name))?;
+            let arg_type = Type::from_str(&type_decl.type_field)?;
+            let token = Token::from_type_and_value(&arg_type, arg)
+                .with_context(|| format!("invalid value {arg:?} for `{}`", input.name))?;
+            Ok(token.0)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    // None of the supported types are encoded on the heap, so the data offset is irrelevant.
+    Ok(ABIEncoder::encode(&tokens)?.resolve(0))
+}
+
 impl FromStr for Type {
     type Err = anyhow::Error;
 
@@ -160,6 +199,42 @@ mod tests {
         assert_eq!(types, expected_types)
     }
 
+    #[test]
+    fn test_encode_main_args() {
+        let program_abi: ProgramABI = serde_json::from_str(
+            r#"{
+                "types": [
+                    { "typeId": 0, "type": "bool", "components": null, "typeParameters": null },
+                    { "typeId": 1, "type": "u64", "components": null, "typeParameters": null }
+                ],
+                "functions": [{
+                    "name": "main",
+                    "inputs": [
+                        { "name": "amount", "type": 1, "typeArguments": null },
+                        { "name": "flag", "type": 0, "typeArguments": null }
+                    ],
+                    "output": { "name": "", "type": 0, "typeArguments": null },
+                    "attributes": null
+                }],
+                "loggedTypes": [],
+                "messagesTypes": [],
+                "configurables": []
+            }"#,
+        )
+        .unwrap();
+
+        let args = ["7".to_string(), "true".to_string()];
+        let data = encode_main_args(&program_abi, &args).unwrap();
+        assert_eq!(
+            data,
+            [[0, 0, 0, 0, 0, 0, 0, 7], [0, 0, 0, 0, 0, 0, 0, 1]].concat()
+        );
+
+        assert!(encode_main_args(&program_abi, &args[..1]).is_err());
+        let args = ["true".to_string(), "7".to_string()];
+        assert!(encode_main_args(&program_abi, &args).is_err());
+    }
+
     #[test]
     #[should_panic(expected = "u2 type is not supported.")]
     fn test_type_generation_fail_invalid_type() {
@@ -1,7 +1,10 @@
 use std::str::FromStr;
 
+pub(crate) mod deployments;
 pub(crate) mod encode;
 pub(crate) mod pkg;
+pub(crate) mod storage;
+pub(crate) mod trace;
 pub(crate) mod tx;
 
 use crate::default::{BETA_2_ENDPOINT_URL, BETA_3_ENDPOINT_URL, NODE_URL};
@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::{anyhow, bail, Context, Result};
+use fuel_tx::{Bytes32, StorageSlot};
+use serde_json::Value;
+use sway_core::language::ty::StorageFieldLayout;
+
+/// Reads the storage initialization values passed via `--storage-init`.
+///
+/// The file must contain a JSON object mapping storage field names to their initial values.
+pub(crate) fn read_storage_init(path: &Path) -> Result<BTreeMap<String, Value>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read storage init file {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| {
+        format!(
+            "storage init file {} must contain a JSON object of storage field names to values",
+            path.display()
+        )
+    })
+}
+
+/// Validates the given values against the contract's storage layout and encodes each of them
+/// into the storage slot of its field.
+pub(crate) fn storage_init_slots(
+    layout: &[StorageFieldLayout],
+    values: &BTreeMap<String, Value>,
+) -> Result<Vec<StorageSlot>> {
+    values
+        .iter()
+        .map(|(name, value)| {
+            let field = layout
+                .iter()
+                .find(|field| &field.name == name)
+                .ok_or_else(|| {
+                    let fields = layout
+                        .iter()
+                        .map(|field| field.name.as_str())
+                        .collect::<Vec<_>>();
+                    anyhow!(
+                        "contract storage has no field named `{name}`. Available fields: [{}]",
+                        fields.join(", ")
+                    )
+                })?;
+            let value = encode_storage_value(&field.type_name, value)
+                .with_context(|| format!("invalid value for storage field `{name}`"))?;
+            Ok(StorageSlot::new(field.key, value))
+        })
+        .collect()
+}
+
+/// Replaces the slots with the same key as any of the `init_slots`, and appends the rest.
+pub(crate) fn apply_storage_init(
+    storage_slots: &mut Vec<StorageSlot>,
+    init_slots: Vec<StorageSlot>,
+) {
+    for init_slot in init_slots {
+        match storage_slots
+            .iter()
+            .position(|slot| slot.key() == init_slot.key())
+        {
+            Some(ix) => storage_slots[ix] = init_slot,
+            None => storage_slots.push(init_slot),
+        }
+    }
+}
+
+/// Encodes a value the way the compiler lays out initialized storage of the given type.
+fn encode_storage_value(type_name: &str, value: &Value) -> Result<Bytes32> {
+    let mut bytes = [0u8; 32];
+    match type_name {
+        "u8" | "u16" | "u32" | "u64" => {
+            let number = value.as_u64().ok_or_else(|| {
+                anyhow!("expected an unsigned integer of type {type_name}, found {value}")
+            })?;
+            let max = match type_name {
+                "u8" => u8::MAX as u64,
+                "u16" => u16::MAX as u64,
+                "u32" => u32::MAX as u64,
+                _ => u64::MAX,
+            };
+            if number > max {
+                bail!("{number} does not fit in type {type_name}");
+            }
+            bytes[..8].copy_from_slice(&number.to_be_bytes());
+        }
+        "bool" => {
+            let boolean = value
+                .as_bool()
+                .ok_or_else(|| anyhow!("expected a boolean, found {value}"))?;
+            bytes[7] = boolean as u8;
+        }
+        "b256" => {
+            let b256 = value
+                .as_str()
+                .and_then(|s| s.strip_prefix("0x"))
+                .filter(|s| s.len() == 64)
+                .and_then(|s| s.parse::<Bytes32>().ok())
+                .ok_or_else(|| {
+                    anyhow!("expected a 0x-prefixed 32 byte hexadecimal string, found {value}")
+                })?;
+            return Ok(b256);
+        }
+        _ => bail!(
+            "storage fields of type {type_name} cannot be initialized at deploy time; \
+            only u8, u16, u32, u64, bool and b256 fields are supported"
+        ),
+    }
+    Ok(Bytes32::new(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn layout() -> Vec<StorageFieldLayout> {
+        vec![
+            StorageFieldLayout {
+                name: "counter".to_string(),
+                type_name: "u64".to_string(),
+                key: Bytes32::new([1; 32]),
+                offset: 0,
+                fields: vec![],
+            },
+            StorageFieldLayout {
+                name: "paused".to_string(),
+                type_name: "bool".to_string(),
+                key: Bytes32::new([2; 32]),
+                offset: 0,
+                fields: vec![],
+            },
+            StorageFieldLayout {
+                name: "owner".to_string(),
+                type_name: "b256".to_string(),
+                key: Bytes32::new([3; 32]),
+                offset: 0,
+                fields: vec![],
+            },
+        ]
+    }
+
+    fn values(value: Value) -> BTreeMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_storage_init_slots() {
+        let owner = format!("0x{}", "ab".repeat(32));
+        let slots = storage_init_slots(
+            &layout(),
+            &values(json!({ "counter": 258, "paused": true, "owner": owner })),
+        )
+        .unwrap();
+
+        let mut counter = [0u8; 32];
+        counter[6..8].copy_from_slice(&[1, 2]);
+        let mut paused = [0u8; 32];
+        paused[7] = 1;
+        assert_eq!(
+            slots,
+            vec![
+                StorageSlot::new(Bytes32::new([1; 32]), Bytes32::new(counter)),
+                StorageSlot::new(Bytes32::new([3; 32]), Bytes32::new([0xab; 32])),
+                StorageSlot::new(Bytes32::new([2; 32]), Bytes32::new(paused)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_storage_init_slots_mismatch() {
+        let err = storage_init_slots(&layout(), &values(json!({ "count": 1 }))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "contract storage has no field named `count`. Available fields: [counter, paused, owner]"
+        );
+
+        let err = storage_init_slots(&layout(), &values(json!({ "paused": 1 }))).unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid value for storage field `paused`: expected a boolean, found 1"
+        );
+
+        let err = storage_init_slots(&layout(), &values(json!({ "owner": "0x01" }))).unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid value for storage field `owner`: \
+            expected a 0x-prefixed 32 byte hexadecimal string, found \"0x01\""
+        );
+    }
+
+    #[test]
+    fn test_apply_storage_init() {
+        let mut slots = vec![StorageSlot::new(Bytes32::new([1; 32]), Bytes32::zeroed())];
+        apply_storage_init(
+            &mut slots,
+            vec![
+                StorageSlot::new(Bytes32::new([1; 32]), Bytes32::new([4; 32])),
+                StorageSlot::new(Bytes32::new([2; 32]), Bytes32::new([5; 32])),
+            ],
+        );
+        assert_eq!(
+            slots,
+            vec![
+                StorageSlot::new(Bytes32::new([1; 32]), Bytes32::new([4; 32])),
+                StorageSlot::new(Bytes32::new([2; 32]), Bytes32::new([5; 32])),
+            ]
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::util::deployments::Deployments;
+use forc_pkg::BuiltPackage;
+use forc_receipts::{DecodedReceipt, DecodedValue, ReceiptDecoder};
+use forc_util::tx_utils::checksum_encode;
+use fuel_tx::{ContractId, Receipt};
+use std::fmt::Write;
+
+/// Renders the receipts of a transaction running the script `script` as a tree of the contract
+/// calls it made, with the logs, returns and transfers of each call nested within it.
+///
+/// Contracts are named after the packages they were deployed from if they're in `deployments`,
+/// in which case their methods are named too, and the calls made by the script itself are
+/// located in its sources.
+pub(crate) fn format_call_tree(
+    receipts: &[Receipt],
+    script: &BuiltPackage,
+    decoder: &ReceiptDecoder,
+    deployments: &Deployments,
+) -> String {
+    let no_decoder = ReceiptDecoder::default();
+    let mut tree = format!("script {}\n", script.descriptor.name);
+    let mut depth = 1;
+    let mut line = |depth: usize, text: String| {
+        let _ = writeln!(tree, "{}{text}", "  ".repeat(depth));
+    };
+    for receipt in receipts {
+        // Only the values of the script itself can be decoded with its ABI.
+        let decoder = match receipt_id(receipt) {
+            Some(id) if *id == ContractId::zeroed() => decoder,
+            _ => &no_decoder,
+        };
+        match receipt {
+            Receipt::Call {
+                id,
+                to,
+                amount,
+                asset_id,
+                gas,
+                param1,
+                pc,
+                is,
+                ..
+            } => {
+                let deployment = deployments.get(to);
+                let contract = match deployment {
+                    Some(deployment) => format!("{} ({})", deployment.name, checksum_encode(to)),
+                    None => checksum_encode(to),
+                };
+                let method = deployment
+                    .and_then(|deployment| deployment.method_name(*param1))
+                    .map_or_else(|| format!("0x{param1:08x}"), str::to_string);
+                let mut text = format!("call {contract}::{method}, {gas} gas forwarded");
+                if *amount != 0 {
+                    let _ = write!(text, ", {amount} of asset {}", checksum_encode(asset_id));
+                }
+                if *id == ContractId::zeroed() {
+                    if let Some(location) = script_location(script, *pc, *is) {
+                        let _ = write!(text, " at {location}");
+                    }
+                }
+                line(depth, text);
+                depth += 1;
+            }
+            Receipt::Transfer {
+                to,
+                amount,
+                asset_id,
+                ..
+            } => {
+                let to = match deployments.get(to) {
+                    Some(deployment) => format!("{} ({})", deployment.name, checksum_encode(to)),
+                    None => checksum_encode(to),
+                };
+                line(
+                    depth,
+                    format!(
+                        "transfer {amount} of asset {} to contract {to}",
+                        checksum_encode(asset_id)
+                    ),
+                );
+            }
+            Receipt::TransferOut {
+                to,
+                amount,
+                asset_id,
+                ..
+            } => line(
+                depth,
+                format!(
+                    "transfer {amount} of asset {} to address {}",
+                    checksum_encode(asset_id),
+                    checksum_encode(to)
+                ),
+            ),
+            Receipt::ScriptResult { result, gas_used } => {
+                line(0, format!("{result:?}, {gas_used} gas used"));
+            }
+            receipt => match decoder.decode(receipt) {
+                DecodedReceipt::Log { value, .. } => {
+                    line(depth, format!("log {}", value_text(&value)))
+                }
+                DecodedReceipt::Return { contract_id, value } => {
+                    line(depth, format!("return {}", value_text(&value)));
+                    // The return of the script itself ends the tree rather than a call.
+                    if contract_id != ContractId::zeroed() {
+                        depth = depth.saturating_sub(1).max(1);
+                    }
+                }
+                DecodedReceipt::Panic { reason, .. } => line(depth, format!("panic: {reason:?}")),
+                DecodedReceipt::Revert { code, signal, .. } => {
+                    let mut text = format!("revert {code:#x}");
+                    if let Some(signal) = signal {
+                        let _ = write!(text, " ({signal})");
+                    }
+                    line(depth, text);
+                }
+                DecodedReceipt::Other(_) => (),
+            },
+        }
+    }
+    tree
+}
+
+fn receipt_id(receipt: &Receipt) -> Option<&ContractId> {
+    match receipt {
+        Receipt::Log { id, .. }
+        | Receipt::LogData { id, .. }
+        | Receipt::Return { id, .. }
+        | Receipt::ReturnData { id, .. } => Some(id),
+        _ => None,
+    }
+}
+
+fn value_text(value: &DecodedValue) -> String {
+    match (&value.token, &value.type_name) {
+        (Some(token), Some(type_name)) => format!("{token}: {type_name}"),
+        _ => format!("0x{}", hex::encode(&value.data)),
+    }
+}
+
+/// The location in the sources of the script of the instruction at `pc`, where `is` is the
+/// address the script starts at, as `path:line:col`.  If the instruction was inlined, the call
+/// sites it was inlined at follow, innermost first.
+fn script_location(script: &BuiltPackage, pc: u64, is: u64) -> Option<String> {
+    let instruction = usize::try_from(pc.checked_sub(is)? / 4).ok()?;
+    let locations = script
+        .source_map
+        .addr_to_call_stack(instruction)
+        .into_iter()
+        .filter_map(|location| {
+            let start = location.start?;
+            Some(format!(
+                "{}:{}:{}",
+                location.path.display(),
+                start.line,
+                start.col
+            ))
+        })
+        .collect::<Vec<_>>();
+    let (location, call_sites) = locations.split_first()?;
+    let mut text = location.clone();
+    for call_site in call_sites {
+        let _ = write!(text, ", inlined at {call_site}");
+    }
+    Some(text)
+}
@@ -32,6 +32,10 @@ pub struct Command {
     /// Do not build documentation for dependencies.
     #[clap(long)]
     pub no_deps: bool,
+    /// Write a single HTML file per package, with all stylesheets, scripts, fonts and icons
+    /// inlined, so that it can be viewed or shared without the rest of the doc directory.
+    #[clap(long)]
+    pub self_contained: bool,
     /// The IPFS Node to use for fetching IPFS sources.
     ///
     /// Possible values: PUBLIC, LOCAL, <GATEWAY_URL>
@@ -22,6 +22,8 @@ mod doc;
 mod render;
 
 pub(crate) const ASSETS_DIR_NAME: &str = "static.files";
+/// CSS, fonts, icons, logos and scripts shared by all pages.
+pub(crate) static ASSETS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/static.files");
 
 /// Information passed to the render phase to get TypeInfo, CallPath or visibility for type anchors.
 #[derive(Clone)]
@@ -147,26 +149,32 @@ pub fn main() -> Result<()> {
         build_docs(program_info, &doc_path, &build_instructions)?;
     }
 
-    // CSS, icons and logos
-    static ASSETS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/static.files");
-    let assets_path = doc_path.join(ASSETS_DIR_NAME);
-    fs::create_dir_all(&assets_path)?;
-    for file in ASSETS_DIR.files() {
-        let asset_path = assets_path.join(file.path());
-        fs::write(asset_path, file.contents())?;
+    // Self-contained docs already inline everything they need.
+    if !build_instructions.self_contained {
+        // CSS, icons and logos
+        let assets_path = doc_path.join(ASSETS_DIR_NAME);
+        fs::create_dir_all(&assets_path)?;
+        for file in ASSETS_DIR.files() {
+            let asset_path = assets_path.join(file.path());
+            fs::write(asset_path, file.contents())?;
+        }
+        // Sway syntax highlighting file
+        const SWAY_HJS_FILENAME: &str = "highlight.js";
+        let sway_hjs = std::include_bytes!("static.files/highlight.js");
+        fs::write(assets_path.join(SWAY_HJS_FILENAME), sway_hjs)?;
     }
-    // Sway syntax highlighting file
-    const SWAY_HJS_FILENAME: &str = "highlight.js";
-    let sway_hjs = std::include_bytes!("static.files/highlight.js");
-    fs::write(assets_path.join(SWAY_HJS_FILENAME), sway_hjs)?;
 
     // check if the user wants to open the doc in the browser
     // if opening in the browser fails, attempt to open using a file explorer
     if build_instructions.open {
         const BROWSER_ENV_VAR: &str = "BROWSER";
-        let path = doc_path
-            .join(pkg_manifest.project_name())
-            .join(INDEX_FILENAME);
+        let path = if build_instructions.self_contained {
+            self_contained_file_path(&doc_path, pkg_manifest.project_name())
+        } else {
+            doc_path
+                .join(pkg_manifest.project_name())
+                .join(INDEX_FILENAME)
+        };
         let default_browser_opt = std::env::var_os(BROWSER_ENV_VAR);
         match default_browser_opt {
             Some(def_browser) => {
@@ -197,6 +205,7 @@ fn build_docs(
 ) -> Result<()> {
     let Command {
         document_private_items,
+        self_contained,
         ..
     } = *build_instructions;
     let ProgramInfo {
@@ -236,12 +245,25 @@ fn build_docs(
     )?;
 
     // write file contents to doc folder
-    write_content(rendered_docs, doc_path)?;
+    if self_contained {
+        let bundle = rendered_docs.to_self_contained(pkg_manifest.project_name())?;
+        fs::write(
+            self_contained_file_path(doc_path, pkg_manifest.project_name()),
+            bundle.0.as_bytes(),
+        )?;
+    } else {
+        write_content(rendered_docs, doc_path)?;
+    }
     println!("    {}", "Finished".bold().yellow());
 
     Ok(())
 }
 
+/// The path of the single HTML file written for a package with `--self-contained`.
+fn self_contained_file_path(doc_path: &Path, project_name: &str) -> PathBuf {
+    doc_path.join(format!("{project_name}.html"))
+}
+
 fn write_content(rendered_docs: RenderedDocumentation, doc_path: &Path) -> Result<()> {
     for doc in rendered_docs.0 {
         let mut doc_path = doc_path.to_path_buf();
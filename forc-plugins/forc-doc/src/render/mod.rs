@@ -22,6 +22,7 @@ pub mod constant;
 mod index;
 pub mod item;
 pub mod link;
+mod self_contained;
 mod sidebar;
 mod title;
 pub mod util;
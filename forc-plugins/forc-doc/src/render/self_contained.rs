@@ -0,0 +1,238 @@
+//! Bundling of a package's rendered documentation into a single HTML file.
+use crate::{
+    render::{constant::INDEX_FILENAME, HTMLString, RenderedDocument, RenderedDocumentation},
+    ASSETS_DIR,
+};
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use horrorshow::{helper::doctype, html, Raw, Template};
+use std::path::Path;
+
+const STYLESHEETS: [&str; 4] = ["normalize.css", "swaydoc.css", "ayu.css", "ayu.min.css"];
+const SWAY_HJS_FILENAME: &str = "highlight.js";
+const FAVICON_FILENAME: &str = "sway-logo.svg";
+const PAGE_CLASS: &str = "swaydoc-page";
+/// Shows the page named by the URL fragment, or the page containing the fragment's target,
+/// falling back to the project index.
+const PAGE_SWITCHER_JS: &str = r#"
+function showPage() {
+    var pages = document.querySelectorAll(".swaydoc-page");
+    var target = document.getElementById(decodeURIComponent(location.hash.slice(1)));
+    var page = (target && target.closest(".swaydoc-page")) || pages[0];
+    pages.forEach(function (p) { p.hidden = p !== page; });
+}
+window.addEventListener("hashchange", showPage);
+showPage();
+hljs.highlightAll();
+"#;
+
+impl RenderedDocumentation {
+    /// Combines every page of a package into one HTML document with all assets inlined.
+    ///
+    /// Each page becomes a section whose id is the page's path relative to the doc root, and
+    /// links between pages are rewritten to point at those sections.
+    pub(crate) fn to_self_contained(&self, project_name: &str) -> Result<HTMLString> {
+        let root_index = format!("{project_name}/{INDEX_FILENAME}");
+        let mut pages = self
+            .0
+            .iter()
+            .map(|doc| Ok((page_path(doc), page_contents(doc)?)))
+            .collect::<Result<Vec<_>>>()?;
+        // The project index comes first, since it is shown when no page is selected.
+        pages.sort_by(|(lhs, _), (rhs, _)| {
+            (lhs != &root_index, lhs).cmp(&(rhs != &root_index, rhs))
+        });
+
+        let styles = STYLESHEETS
+            .iter()
+            .map(|file_name| Ok(inline_css_urls(&asset_str(file_name)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let sway_hjs = asset_str(SWAY_HJS_FILENAME)?;
+        let favicon = data_uri(FAVICON_FILENAME)?;
+
+        Ok(HTMLString(
+            html! {
+                : doctype::HTML;
+                html {
+                    head {
+                        meta(charset="utf-8");
+                        meta(name="viewport", content="width=device-width, initial-scale=1.0");
+                        meta(name="generator", content="swaydoc");
+                        meta(
+                            name="description",
+                            content=format!("API documentation for the Sway `{project_name}` package.")
+                        );
+                        meta(name="keywords", content=format!("sway, swaylang, sway-lang, {project_name}"));
+                        link(rel="icon", href=&favicon);
+                        title: format!("{project_name} - Sway");
+                        @ for stylesheet in &styles {
+                            style { : Raw(stylesheet); }
+                        }
+                    }
+                    body(class="swaydoc mod") {
+                        main {
+                            @ for (path, contents) in &pages {
+                                section(id=path, class=PAGE_CLASS) {
+                                    : Raw(contents);
+                                }
+                            }
+                        }
+                        script { : Raw(&sway_hjs); }
+                        script { : Raw(PAGE_SWITCHER_JS); }
+                    }
+                }
+            }
+            .into_string()?,
+        ))
+    }
+}
+
+/// The path of a page relative to the doc root, e.g. `project/module/struct.Name.html`.
+fn page_path(doc: &RenderedDocument) -> String {
+    let mut components = doc.module_info.module_prefixes.clone();
+    components.push(doc.html_filename.clone());
+    components.join("/")
+}
+
+/// The main content of a page, with links to other pages rewritten to point at their sections.
+fn page_contents(doc: &RenderedDocument) -> Result<String> {
+    let html = &doc.file_contents.0;
+    let start = html
+        .find("<main>")
+        .map(|index| index + "<main>".len())
+        .ok_or_else(|| anyhow!("page {} has no main content", doc.html_filename))?;
+    let end = html[start..]
+        .rfind("</main>")
+        .map(|index| start + index)
+        .ok_or_else(|| anyhow!("page {} has no main content", doc.html_filename))?;
+    let page_dir = doc.module_info.module_prefixes.join("/");
+    Ok(rewrite_hrefs(&html[start..end], &page_dir))
+}
+
+/// Rewrites every relative `href` pointing at another HTML page into a fragment link.
+fn rewrite_hrefs(html: &str, page_dir: &str) -> String {
+    const HREF: &str = "href=\"";
+    let mut rewritten = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(index) = rest.find(HREF) {
+        let (before, after) = rest.split_at(index + HREF.len());
+        rewritten.push_str(before);
+        let end = after.find('"').unwrap_or(after.len());
+        let href = &after[..end];
+        match resolve_page_link(href, page_dir) {
+            Some(page) => {
+                rewritten.push('#');
+                rewritten.push_str(&page);
+            }
+            None => rewritten.push_str(href),
+        }
+        rest = &after[end..];
+    }
+    rewritten.push_str(rest);
+    rewritten
+}
+
+/// Resolves a relative link to an HTML page against the directory of the page containing it.
+///
+/// Returns `None` for links that do not point at another page, such as fragments and URLs.
+fn resolve_page_link(href: &str, page_dir: &str) -> Option<String> {
+    let target = href.split('#').next().unwrap_or_default();
+    if target.is_empty() || target.contains("://") || !target.ends_with(".html") {
+        return None;
+    }
+    let mut components = page_dir
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>();
+    for component in target.split('/') {
+        match component {
+            "" | "." => (),
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    Some(components.join("/"))
+}
+
+/// Replaces the `url("...")` references of a stylesheet with data URIs of the bundled assets.
+fn inline_css_urls(css: &str) -> String {
+    const URL: &str = "url(\"";
+    let mut inlined = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(index) = rest.find(URL) {
+        let (before, after) = rest.split_at(index + URL.len());
+        inlined.push_str(before);
+        let end = after.find('"').unwrap_or(after.len());
+        let file_name = &after[..end];
+        match data_uri(file_name) {
+            Ok(uri) => inlined.push_str(&uri),
+            Err(_) => inlined.push_str(file_name),
+        }
+        rest = &after[end..];
+    }
+    inlined.push_str(rest);
+    inlined
+}
+
+fn asset_str(file_name: &str) -> Result<String> {
+    ASSETS_DIR
+        .get_file(file_name)
+        .and_then(|file| file.contents_utf8())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("missing documentation asset {file_name}"))
+}
+
+fn data_uri(file_name: &str) -> Result<String> {
+    let file = ASSETS_DIR
+        .get_file(file_name)
+        .ok_or_else(|| anyhow!("missing documentation asset {file_name}"))?;
+    let mime_type = match Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some("svg") => "image/svg+xml",
+        Some("woff2") => "font/woff2",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        _ => "application/octet-stream",
+    };
+    Ok(format!(
+        "data:{mime_type};base64,{}",
+        STANDARD.encode(file.contents())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_page_link() {
+        let page_dir = "project/module";
+        assert_eq!(
+            resolve_page_link("struct.Foo.html", page_dir),
+            Some("project/module/struct.Foo.html".to_string())
+        );
+        assert_eq!(
+            resolve_page_link("../index.html", page_dir),
+            Some("project/index.html".to_string())
+        );
+        assert_eq!(
+            resolve_page_link("sub/enum.Bar.html#variant.Baz", page_dir),
+            Some("project/module/sub/enum.Bar.html".to_string())
+        );
+        assert_eq!(resolve_page_link("#method.foo", page_dir), None);
+        assert_eq!(resolve_page_link("https://fuel.network", page_dir), None);
+    }
+
+    #[test]
+    fn test_rewrite_hrefs() {
+        let html = r##"<a href="../index.html">root</a><a href="#impl">impl</a>"##;
+        assert_eq!(
+            rewrite_hrefs(html, "project/module"),
+            r##"<a href="#project/index.html">root</a><a href="#impl">impl</a>"##
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Decodes the receipts of Fuel transactions against the ABI of the program which produced them,
+//! turning the logged and returned bytes back into the values of the program's types and the
+//! codes of panics and reverts into their causes.
+//!
+//! The receipts of a program without an ABI can be decoded too, in which case only their raw data
+//! is available.
+
+use anyhow::Result;
+use fuel_abi_types::{
+    abi::program::{ProgramABI, TypeApplication, TypeDeclaration},
+    error_codes::ErrorSignal,
+};
+use fuel_asm::PanicReason;
+use fuel_tx::{ContractId, Receipt};
+use fuels_core::{abi_decoder::ABIDecoder, types::param_types::ParamType, types::Token};
+use std::collections::HashMap;
+
+/// A receipt, with the values it carries decoded.
+#[derive(Debug)]
+pub enum DecodedReceipt {
+    /// A value logged by a script, predicate or contract.
+    Log {
+        contract_id: ContractId,
+        log_id: u64,
+        value: DecodedValue,
+    },
+    /// The value returned by a script or a contract call.
+    Return {
+        contract_id: ContractId,
+        value: DecodedValue,
+    },
+    /// The VM panicked, e.g. due to an arithmetic overflow.
+    Panic {
+        contract_id: ContractId,
+        reason: PanicReason,
+        /// The contract which was missing from the inputs of the transaction, if that's why it
+        /// panicked.
+        missing_contract_id: Option<ContractId>,
+    },
+    /// The program reverted.
+    Revert {
+        contract_id: ContractId,
+        code: u64,
+        /// The standard library function which reverted, e.g. `require`, if the code is one of
+        /// those it reverts with.
+        signal: Option<ErrorSignal>,
+    },
+    /// A receipt which carries nothing to decode, e.g. a call or a transfer.
+    Other(Receipt),
+}
+
+/// The data of a log or a return, and the value it decodes to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedValue {
+    /// The type of the value as declared in the ABI, e.g. `u64` or `struct Foo`, if it's known.
+    pub type_name: Option<String>,
+    /// The value, if its type is known and the data could be decoded as one.
+    pub token: Option<Token>,
+    /// The data as it appears in the receipt.
+    pub data: Vec<u8>,
+}
+
+/// Decodes receipts using the types in the ABI of a program.
+#[derive(Debug, Clone, Default)]
+pub struct ReceiptDecoder {
+    types: HashMap<usize, TypeDeclaration>,
+    logged_types: HashMap<u64, TypeApplication>,
+    /// The type returned by the `main` function of a script.
+    main_output: Option<TypeApplication>,
+}
+
+impl ReceiptDecoder {
+    /// A decoder for the receipts of the program described by `program_abi`.
+    pub fn new(program_abi: &ProgramABI) -> Self {
+        Self {
+            types: program_abi
+                .types
+                .iter()
+                .map(|type_decl| (type_decl.type_id, type_decl.clone()))
+                .collect(),
+            logged_types: program_abi
+                .logged_types
+                .iter()
+                .flatten()
+                .map(|logged_type| (logged_type.log_id, logged_type.application.clone()))
+                .collect(),
+            main_output: program_abi
+                .functions
+                .iter()
+                .find(|function| function.name == "main")
+                .map(|main| main.output.clone()),
+        }
+    }
+
+    /// A decoder for the receipts of the program whose JSON ABI is `program_abi`.
+    pub fn from_json(program_abi: serde_json::Value) -> Result<Self> {
+        Ok(Self::new(&serde_json::from_value(program_abi)?))
+    }
+
+    /// Decode `receipt`.
+    pub fn decode(&self, receipt: &Receipt) -> DecodedReceipt {
+        match receipt {
+            Receipt::Log { id, ra, rb, .. } => DecodedReceipt::Log {
+                contract_id: *id,
+                log_id: *rb,
+                value: self.decode_value(self.logged_types.get(rb), ra.to_be_bytes().to_vec()),
+            },
+            Receipt::LogData { id, rb, data, .. } => DecodedReceipt::Log {
+                contract_id: *id,
+                log_id: *rb,
+                value: self.decode_value(self.logged_types.get(rb), data.clone()),
+            },
+            Receipt::Return { id, val, .. } => DecodedReceipt::Return {
+                contract_id: *id,
+                value: self.decode_value(self.return_type(id), val.to_be_bytes().to_vec()),
+            },
+            Receipt::ReturnData { id, data, .. } => DecodedReceipt::Return {
+                contract_id: *id,
+                value: self.decode_value(self.return_type(id), data.clone()),
+            },
+            Receipt::Panic {
+                id,
+                reason,
+                contract_id,
+                ..
+            } => DecodedReceipt::Panic {
+                contract_id: *id,
+                reason: *reason.reason(),
+                missing_contract_id: *contract_id,
+            },
+            Receipt::Revert { id, ra, .. } => DecodedReceipt::Revert {
+                contract_id: *id,
+                code: *ra,
+                signal: ErrorSignal::try_from_revert_code(*ra).ok(),
+            },
+            receipt => DecodedReceipt::Other(receipt.clone()),
+        }
+    }
+
+    /// Decode each of `receipts`.
+    pub fn decode_all(&self, receipts: &[Receipt]) -> Vec<DecodedReceipt> {
+        receipts
+            .iter()
+            .map(|receipt| self.decode(receipt))
+            .collect()
+    }
+
+    /// The type of the value returned within the contract `id`.  Only the return of `main` is
+    /// known, which is the one outside of any contract.
+    fn return_type(&self, id: &ContractId) -> Option<&TypeApplication> {
+        (*id == ContractId::zeroed())
+            .then_some(self.main_output.as_ref())
+            .flatten()
+    }
+
+    fn decode_value(
+        &self,
+        type_application: Option<&TypeApplication>,
+        data: Vec<u8>,
+    ) -> DecodedValue {
+        let type_name = type_application
+            .and_then(|type_application| self.types.get(&type_application.type_id))
+            .map(|type_decl| type_decl.type_field.clone());
+        let token = type_application
+            .and_then(|type_application| {
+                ParamType::try_from_type_application(type_application, &self.types).ok()
+            })
+            .and_then(|param_type| ABIDecoder::decode_single(&param_type, &data).ok());
+        DecodedValue {
+            type_name,
+            token,
+            data,
+        }
+    }
+}
+
+/// Format `receipts` as JSON, with their data as hex strings.
+///
+/// The logs and returns whose types are known are given a `decoded` field holding their values,
+/// and the reverts with a known cause a `signal` field describing it.
+pub fn format_receipts(
+    receipts: &[Receipt],
+    decoder: &ReceiptDecoder,
+    pretty_print: bool,
+) -> Result<String> {
+    let mut receipts_json = serde_json::to_value(receipts)?;
+    for (rec_index, receipt) in receipts.iter().enumerate() {
+        let rec_value = receipts_json.get_mut(rec_index).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Serialized receipts does not contain {} th index",
+                rec_index
+            )
+        })?;
+        let (variant, data) = match receipt {
+            Receipt::LogData { data, .. } => ("LogData", Some(data)),
+            Receipt::ReturnData { data, .. } => ("ReturnData", Some(data)),
+            Receipt::Log { .. } => ("Log", None),
+            Receipt::Return { .. } => ("Return", None),
+            Receipt::Revert { .. } => ("Revert", None),
+            _ => continue,
+        };
+        let Some(fields) = rec_value
+            .get_mut(variant)
+            .and_then(serde_json::Value::as_object_mut)
+        else {
+            continue;
+        };
+        if let Some(data) = data {
+            fields.insert("data".to_string(), hex::encode(data).into());
+        }
+        match decoder.decode(receipt) {
+            DecodedReceipt::Log { value, .. } | DecodedReceipt::Return { value, .. } => {
+                if let Some(token) = value.token {
+                    fields.insert("decoded".to_string(), token.to_string().into());
+                }
+            }
+            DecodedReceipt::Revert {
+                signal: Some(signal),
+                ..
+            } => {
+                fields.insert("signal".to_string(), signal.to_string().into());
+            }
+            _ => (),
+        }
+    }
+    if pretty_print {
+        Ok(serde_json::to_string_pretty(&receipts_json)?)
+    } else {
+        Ok(serde_json::to_string(&receipts_json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_tx::Bytes32;
+
+    fn decoder() -> ReceiptDecoder {
+        ReceiptDecoder::from_json(serde_json::json!({
+            "types": [
+                { "typeId": 0, "type": "u64", "components": null, "typeParameters": null },
+                { "typeId": 1, "type": "bool", "components": null, "typeParameters": null },
+            ],
+            "functions": [{
+                "name": "main",
+                "inputs": [],
+                "output": { "name": "", "type": 1, "typeArguments": null },
+                "attributes": null,
+            }],
+            "loggedTypes": [{
+                "logId": 0,
+                "loggedType": { "name": "", "type": 0, "typeArguments": null },
+            }],
+            "messagesTypes": [],
+            "configurables": [],
+        }))
+        .unwrap()
+    }
+
+    fn log_data(log_id: u64, data: Vec<u8>) -> Receipt {
+        Receipt::LogData {
+            id: ContractId::zeroed(),
+            ra: 0,
+            rb: log_id,
+            ptr: 0,
+            len: data.len() as u64,
+            digest: Bytes32::zeroed(),
+            data,
+            pc: 0,
+            is: 0,
+        }
+    }
+
+    #[test]
+    fn decode_logs_and_returns() {
+        let decoder = decoder();
+        let DecodedReceipt::Log { log_id, value, .. } =
+            decoder.decode(&log_data(0, 42u64.to_be_bytes().to_vec()))
+        else {
+            panic!("expected a log");
+        };
+        assert_eq!(log_id, 0);
+        assert_eq!(value.type_name.as_deref(), Some("u64"));
+        assert_eq!(value.token, Some(Token::U64(42)));
+
+        let ret = Receipt::Return {
+            id: ContractId::zeroed(),
+            val: 1,
+            pc: 0,
+            is: 0,
+        };
+        let DecodedReceipt::Return { value, .. } = decoder.decode(&ret) else {
+            panic!("expected a return");
+        };
+        assert_eq!(value.token, Some(Token::Bool(true)));
+    }
+
+    #[test]
+    fn decode_without_types() {
+        let data = vec![1, 2, 3];
+        let DecodedReceipt::Log { value, .. } =
+            ReceiptDecoder::default().decode(&log_data(7, data.clone()))
+        else {
+            panic!("expected a log");
+        };
+        assert_eq!(value.type_name, None);
+        assert_eq!(value.token, None);
+        assert_eq!(value.data, data);
+
+        let formatted =
+            format_receipts(&[log_data(7, data)], &ReceiptDecoder::default(), false).unwrap();
+        assert!(formatted.contains("\"data\":\"010203\""));
+        assert!(!formatted.contains("decoded"));
+    }
+}
@@ -0,0 +1,827 @@
+//! Model-based fuzzing of the ABI methods of contracts.
+//!
+//! Each run deploys the contract and calls its ABI methods in a random order with random
+//! arguments, checking the `#[invariant]` functions of the package after each call. Calls which
+//! revert or panic are taken as invalid inputs, and leave the storage as it was. Once an invariant
+//! is violated, the sequence of calls which led to it is minimized by removing the calls and
+//! simplifying the arguments the violation doesn't depend on.
+//!
+//! Only the methods whose arguments are stored inline can be called, i.e. those without heap
+//! types such as `Vec` or `String` among their arguments.
+
+use crate::{exec_hooks, exec_script_with_data, test_hooks, BuiltTests, PackageTests, TestSetup};
+use fuel_abi_types::program_abi::{self, TypeApplication, TypeDeclaration};
+use fuel_tx as tx;
+use fuel_vm::{
+    fuel_asm::{op, GTFArgs, RegId},
+    state::ProgramState,
+};
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use sway_core::{asm_generation::ProgramABI, language::ty::TestHook};
+
+/// The maximum number of sequences replayed while minimizing a failing one.
+const MAX_MINIMIZING_REPLAYS: usize = 2000;
+
+/// The options of a fuzzing campaign.
+#[derive(Debug, Clone)]
+pub struct FuzzOpts {
+    /// The number of sequences of calls to try.
+    pub runs: usize,
+    /// The maximum number of calls in a sequence.
+    pub depth: usize,
+    /// The seed of the generator of the calls and their arguments. The same seed always produces
+    /// the same calls.
+    pub seed: u64,
+}
+
+/// The result of fuzzing a contract.
+#[derive(Debug)]
+pub struct FuzzedPackage {
+    /// The name of the contract's package.
+    pub name: String,
+    /// The ABI methods which were called.
+    pub methods: Vec<String>,
+    /// The ABI methods which weren't called, as some of their arguments can't be generated.
+    pub skipped: Vec<String>,
+    /// The number of sequences of calls which were tried.
+    pub runs: usize,
+    /// The number of calls which were made.
+    pub calls: usize,
+    /// The number of calls which reverted or panicked.
+    pub rejected: usize,
+    /// The violation of an invariant which was found, if any.
+    pub failure: Option<FuzzFailure>,
+}
+
+/// A sequence of calls after which an invariant doesn't hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzFailure {
+    /// The name of the `#[invariant]` function which reverted.
+    pub invariant: String,
+    /// The minimized sequence of calls, each formatted as `method(arg, ...)`.
+    pub calls: Vec<String>,
+    /// The number of calls in the sequence as it was found, before it was minimized.
+    pub original_len: usize,
+}
+
+impl Default for FuzzOpts {
+    fn default() -> Self {
+        Self {
+            runs: 256,
+            depth: 20,
+            seed: 0,
+        }
+    }
+}
+
+impl FuzzedPackage {
+    /// Whether all the invariants held after each of the calls.
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+impl BuiltTests {
+    /// Fuzz the ABI methods of the built contract, or of each contract of the built workspace.
+    pub fn fuzz(&self, opts: &FuzzOpts) -> anyhow::Result<Vec<FuzzedPackage>> {
+        let pkgs: Vec<&PackageTests> = match self {
+            BuiltTests::Package(pkg @ PackageTests::Contract(_)) => vec![pkg],
+            BuiltTests::Package(_) => {
+                anyhow::bail!(
+                    "only contracts can be fuzzed, as they are the only packages with ABI methods"
+                )
+            }
+            BuiltTests::Workspace(pkgs) => pkgs
+                .iter()
+                .filter(|pkg| matches!(pkg, PackageTests::Contract(_)))
+                .collect(),
+        };
+        if pkgs.is_empty() {
+            anyhow::bail!("the workspace has no contracts to fuzz");
+        }
+        pkgs.into_iter()
+            .map(|pkg| Fuzzer::new(pkg)?.fuzz(opts))
+            .collect()
+    }
+}
+
+/// The ABI methods and the invariants of a contract.
+struct Fuzzer<'a> {
+    pkg: &'a PackageTests,
+    /// The bytecode of the contract with its tests, which includes the hooks.
+    bytecode: &'a [u8],
+    before_each: Vec<(String, u32)>,
+    invariants: Vec<(String, u32)>,
+    methods: Vec<Method>,
+    skipped: Vec<String>,
+}
+
+/// An ABI method whose arguments can be generated.
+struct Method {
+    name: String,
+    selector: [u8; 4],
+    args: Vec<FuzzType>,
+}
+
+/// A call of the ABI method at index `method`.
+#[derive(Debug, Clone)]
+struct Call {
+    method: usize,
+    args: Vec<FuzzValue>,
+}
+
+impl<'a> Fuzzer<'a> {
+    fn new(pkg: &'a PackageTests) -> anyhow::Result<Self> {
+        let built = pkg.built_pkg_with_tests();
+        let name = &built.descriptor.name;
+        let invariants = test_hooks(built, TestHook::Invariant);
+        if invariants.is_empty() {
+            anyhow::bail!("`{name}` has no `#[invariant]` functions to check");
+        }
+        let program_abi: program_abi::ProgramABI = match &built.program_abi {
+            ProgramABI::Fuel(program_abi) => {
+                serde_json::from_value(serde_json::to_value(program_abi)?)?
+            }
+            _ => anyhow::bail!("`{name}` has no Fuel ABI"),
+        };
+        let types = program_abi
+            .types
+            .iter()
+            .map(|type_decl| (type_decl.type_id, type_decl.clone()))
+            .collect();
+        let selectors: HashMap<&str, [u8; 4]> = built
+            .bytecode
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let selector = entry.finalized.selector?;
+                Some((entry.finalized.fn_name.as_str(), selector))
+            })
+            .collect();
+
+        let mut methods = vec![];
+        let mut skipped = vec![];
+        for function in &program_abi.functions {
+            let Some(selector) = selectors.get(function.name.as_str()) else {
+                continue;
+            };
+            let args = function
+                .inputs
+                .iter()
+                .map(|input| FuzzType::from_abi(&types, input, &HashMap::new()))
+                .collect::<Option<Vec<_>>>();
+            match args {
+                Some(args) => methods.push(Method {
+                    name: function.name.clone(),
+                    selector: *selector,
+                    args,
+                }),
+                None => skipped.push(function.name.clone()),
+            }
+        }
+        if methods.is_empty() {
+            anyhow::bail!("`{name}` has no ABI methods whose arguments can be generated");
+        }
+
+        Ok(Self {
+            pkg,
+            bytecode: &built.bytecode.bytes,
+            before_each: test_hooks(built, TestHook::BeforeEach),
+            invariants,
+            methods,
+            skipped,
+        })
+    }
+
+    fn fuzz(&self, opts: &FuzzOpts) -> anyhow::Result<FuzzedPackage> {
+        let mut rng = StdRng::seed_from_u64(opts.seed);
+        let mut fuzzed = FuzzedPackage {
+            name: self.pkg.built_pkg_with_tests().descriptor.name.clone(),
+            methods: self.methods.iter().map(|m| m.name.clone()).collect(),
+            skipped: self.skipped.clone(),
+            runs: 0,
+            calls: 0,
+            rejected: 0,
+            failure: None,
+        };
+        for _ in 0..opts.runs {
+            fuzzed.runs += 1;
+            let mut test_setup = self.setup()?;
+            let mut calls = vec![];
+            let mut violated = self.violated_invariant(&mut test_setup);
+            while violated.is_none() && calls.len() < opts.depth {
+                let call = self.generate_call(&mut rng);
+                fuzzed.calls += 1;
+                if !self.exec_call(&call, &mut test_setup) {
+                    fuzzed.rejected += 1;
+                }
+                calls.push(call);
+                violated = self.violated_invariant(&mut test_setup);
+            }
+            if let Some(invariant) = violated {
+                let original_len = calls.len();
+                let calls = self.minimize(calls, &invariant)?;
+                fuzzed.failure = Some(FuzzFailure {
+                    invariant,
+                    calls: calls.iter().map(|call| self.format_call(call)).collect(),
+                    original_len,
+                });
+                break;
+            }
+        }
+        Ok(fuzzed)
+    }
+
+    /// Deploy the contract and run the `#[before_each]` functions.
+    fn setup(&self) -> anyhow::Result<TestSetup> {
+        let mut test_setup = self.pkg.setup()?;
+        let (_, failed_hook) = exec_hooks(
+            self.bytecode,
+            &self.before_each,
+            TestHook::BeforeEach,
+            &mut test_setup,
+        );
+        if let Some(failed_hook) = failed_hook {
+            anyhow::bail!("{} failed: {:?}", failed_hook.name, failed_hook.state);
+        }
+        Ok(test_setup)
+    }
+
+    /// The first invariant which doesn't hold in the storage of `test_setup`, if any.
+    fn violated_invariant(&self, test_setup: &mut TestSetup) -> Option<String> {
+        // The invariants are checked without keeping whatever they store.
+        let storage = test_setup.storage().clone();
+        let (_, failed_hook) = exec_hooks(
+            self.bytecode,
+            &self.invariants,
+            TestHook::Invariant,
+            test_setup,
+        );
+        *test_setup.storage_mut() = storage;
+        failed_hook.map(|failed_hook| failed_hook.name)
+    }
+
+    fn generate_call(&self, rng: &mut StdRng) -> Call {
+        let method = rng.gen_range(0..self.methods.len());
+        let args = self.methods[method]
+            .args
+            .iter()
+            .map(|arg| arg.generate(rng))
+            .collect();
+        Call { method, args }
+    }
+
+    /// Make `call` in the storage of `test_setup`, keeping the storage it leaves unless it
+    /// reverted or panicked.
+    ///
+    /// Returns whether the call succeeded.
+    fn exec_call(&self, call: &Call, test_setup: &mut TestSetup) -> bool {
+        let contract_id = test_setup
+            .root_contract_id()
+            .expect("only contracts are fuzzed");
+        let (script, script_data) = call_script(contract_id, &self.methods[call.method], call);
+        let (state, _, receipts, storage) = exec_script_with_data(script, script_data, test_setup);
+        let succeeded = !matches!(state, ProgramState::Revert(_))
+            && !receipts
+                .iter()
+                .any(|receipt| matches!(receipt, tx::Receipt::Panic { .. }));
+        if succeeded {
+            *test_setup.storage_mut() = storage;
+        }
+        succeeded
+    }
+
+    /// The number of calls of `calls` after which `invariant` is violated, when they are made from
+    /// a fresh deployment, if it is.
+    fn violated_after(&self, calls: &[Call], invariant: &str) -> anyhow::Result<Option<usize>> {
+        let mut test_setup = self.setup()?;
+        for len in 0..=calls.len() {
+            if len > 0 {
+                self.exec_call(&calls[len - 1], &mut test_setup);
+            }
+            if let Some(violated) = self.violated_invariant(&mut test_setup) {
+                return Ok((violated == invariant).then_some(len));
+            }
+        }
+        Ok(None)
+    }
+
+    /// A shorter or simpler sequence than `calls` which still violates `invariant`.
+    ///
+    /// The calls the violation doesn't depend on are removed first, then the arguments of the
+    /// others are simplified one at a time, for as long as the violation remains.
+    fn minimize(&self, mut calls: Vec<Call>, invariant: &str) -> anyhow::Result<Vec<Call>> {
+        let mut replays = 0;
+        let mut ix = 0;
+        while ix < calls.len() && replays < MAX_MINIMIZING_REPLAYS {
+            replays += 1;
+            let mut candidate = calls.clone();
+            candidate.remove(ix);
+            match self.violated_after(&candidate, invariant)? {
+                Some(len) => {
+                    candidate.truncate(len);
+                    calls = candidate;
+                }
+                None => ix += 1,
+            }
+        }
+
+        // Start over from the first argument after each simplification, as the violation may now
+        // happen earlier in the sequence.
+        let mut simplified = true;
+        while simplified {
+            simplified = false;
+            'calls: for call_ix in 0..calls.len() {
+                let method = &self.methods[calls[call_ix].method];
+                for (arg_ix, arg_ty) in method.args.iter().enumerate() {
+                    for arg in arg_ty.shrink(&calls[call_ix].args[arg_ix]) {
+                        if replays == MAX_MINIMIZING_REPLAYS {
+                            break 'calls;
+                        }
+                        replays += 1;
+                        let mut candidate = calls.clone();
+                        candidate[call_ix].args[arg_ix] = arg;
+                        if let Some(len) = self.violated_after(&candidate, invariant)? {
+                            candidate.truncate(len);
+                            calls = candidate;
+                            simplified = true;
+                            break 'calls;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(calls)
+    }
+
+    fn format_call(&self, call: &Call) -> String {
+        let method = &self.methods[call.method];
+        let args = method
+            .args
+            .iter()
+            .zip(&call.args)
+            .map(|(arg_ty, arg)| arg_ty.format(arg))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({args})", method.name)
+    }
+}
+
+/// The script calling `method` of the contract `contract_id` with the arguments of `call`, and
+/// its data.
+///
+/// The data starts with the contract id and the two parameters of the call, which the script
+/// copies to the stack, followed by the id of the asset forwarded with the call, none of which
+/// is, and the arguments if they are passed by reference. A single argument which fits in a word
+/// is passed by value, as the second parameter, and any others by reference.
+fn call_script(contract_id: tx::ContractId, method: &Method, call: &Call) -> (Vec<u8>, Vec<u8>) {
+    let mut encoded_args = vec![];
+    for (arg_ty, arg) in method.args.iter().zip(&call.args) {
+        arg_ty.encode(arg, &mut encoded_args);
+    }
+    let by_value = match method.args.as_slice() {
+        [] => true,
+        [arg_ty] => arg_ty.is_copy(),
+        _ => false,
+    };
+
+    let mut script_data = contract_id.to_vec();
+    script_data.extend([0; 4]);
+    script_data.extend(method.selector);
+    if by_value {
+        encoded_args.resize(8, 0);
+        script_data.append(&mut encoded_args);
+    } else {
+        // The address of the arguments is filled in by the script.
+        script_data.extend([0; 8]);
+    }
+    script_data.extend([0; 32]);
+    script_data.append(&mut encoded_args);
+
+    let data = RegId::new(0x10);
+    let call_params = RegId::new(0x11);
+    let args = RegId::new(0x12);
+    let asset_id = RegId::new(0x13);
+    let mut ops = vec![
+        op::gtf_args(data, RegId::ZERO, GTFArgs::ScriptData),
+        op::move_(call_params, RegId::SP),
+        op::cfei(48),
+        op::mcpi(call_params, data, 48),
+    ];
+    if !by_value {
+        // The arguments follow the asset id, and the second parameter is the sixth word.
+        ops.push(op::addi(args, data, 80));
+        ops.push(op::sw(call_params, args, 5));
+    }
+    ops.extend([
+        op::addi(asset_id, data, 48),
+        op::call(call_params, RegId::ZERO, asset_id, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ]);
+    let script = ops
+        .into_iter()
+        .flat_map(|instruction| instruction.to_bytes())
+        .collect();
+    (script, script_data)
+}
+
+/// The type of an argument of an ABI method, whose values can be generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FuzzType {
+    Unit,
+    Bool,
+    Uint(u8),
+    B256,
+    Str(usize),
+    Array(Box<FuzzType>, usize),
+    Tuple(Vec<FuzzType>),
+    Struct {
+        name: String,
+        fields: Vec<(String, FuzzType)>,
+    },
+    Enum {
+        name: String,
+        variants: Vec<(String, FuzzType)>,
+    },
+}
+
+/// A value of a [FuzzType].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FuzzValue {
+    Unit,
+    Bool(bool),
+    Uint(u64),
+    B256([u8; 32]),
+    Str(String),
+    /// The elements of an array or a tuple, or the fields of a struct.
+    Aggregate(Vec<FuzzValue>),
+    /// The index of the variant of an enum, and its value.
+    Enum(usize, Box<FuzzValue>),
+}
+
+impl FuzzType {
+    /// The type of `type_application`, where `generics` are the types of the generic parameters
+    /// in scope by type id.
+    ///
+    /// Returns `None` if the values of the type can't be generated, i.e. if it is or holds a heap
+    /// type such as `Vec`, or a raw pointer.
+    fn from_abi(
+        types: &HashMap<usize, TypeDeclaration>,
+        type_application: &TypeApplication,
+        generics: &HashMap<usize, FuzzType>,
+    ) -> Option<Self> {
+        if let Some(ty) = generics.get(&type_application.type_id) {
+            return Some(ty.clone());
+        }
+        let type_decl = types.get(&type_application.type_id)?;
+        let generics = type_decl
+            .type_parameters
+            .iter()
+            .flatten()
+            .zip(type_application.type_arguments.iter().flatten())
+            .map(|(param, arg)| Some((*param, Self::from_abi(types, arg, generics)?)))
+            .collect::<Option<HashMap<_, _>>>()?;
+        let components = type_decl
+            .components
+            .iter()
+            .flatten()
+            .map(|component| {
+                let ty = Self::from_abi(types, component, &generics)?;
+                Some((component.name.clone(), ty))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let type_field = type_decl.type_field.as_str();
+        let ty = match type_field {
+            "()" => FuzzType::Unit,
+            "bool" => FuzzType::Bool,
+            "u8" => FuzzType::Uint(8),
+            "u16" => FuzzType::Uint(16),
+            "u32" => FuzzType::Uint(32),
+            "u64" => FuzzType::Uint(64),
+            "b256" => FuzzType::B256,
+            _ if type_field.starts_with("str[") => {
+                let len = type_field["str[".len()..].strip_suffix(']')?;
+                FuzzType::Str(len.parse().ok()?)
+            }
+            _ if type_field.starts_with("[_; ") => {
+                let len = type_field["[_; ".len()..].strip_suffix(']')?;
+                let (_, elem) = components.into_iter().next()?;
+                FuzzType::Array(Box::new(elem), len.parse().ok()?)
+            }
+            _ if type_field.starts_with('(') => {
+                FuzzType::Tuple(components.into_iter().map(|(_, ty)| ty).collect())
+            }
+            _ if type_field.starts_with("struct ") => FuzzType::Struct {
+                name: type_field["struct ".len()..].to_string(),
+                fields: components,
+            },
+            _ if type_field.starts_with("enum ") && !components.is_empty() => FuzzType::Enum {
+                name: type_field["enum ".len()..].to_string(),
+                variants: components,
+            },
+            _ => return None,
+        };
+        Some(ty)
+    }
+
+    /// The size in bytes of the values of the type, as they are laid out in memory.
+    fn size(&self) -> usize {
+        match self {
+            FuzzType::Unit | FuzzType::Bool | FuzzType::Uint(_) => 8,
+            FuzzType::B256 => 32,
+            FuzzType::Str(len) => (len + 7) / 8 * 8,
+            FuzzType::Array(elem, len) => elem.size() * len,
+            FuzzType::Tuple(elems) => elems.iter().map(FuzzType::size).sum(),
+            FuzzType::Struct { fields, .. } => fields.iter().map(|(_, ty)| ty.size()).sum(),
+            FuzzType::Enum { variants, .. } => {
+                // Enums whose variants are all units are only their tag.
+                let union_size = if variants.iter().all(|(_, ty)| *ty == FuzzType::Unit) {
+                    0
+                } else {
+                    variants.iter().map(|(_, ty)| ty.size()).max().unwrap_or(0)
+                };
+                8 + union_size
+            }
+        }
+    }
+
+    /// Whether the values of the type fit in a word, and are passed by value.
+    fn is_copy(&self) -> bool {
+        matches!(self, FuzzType::Unit | FuzzType::Bool | FuzzType::Uint(_))
+    }
+
+    /// A random value of the type.
+    fn generate(&self, rng: &mut StdRng) -> FuzzValue {
+        match self {
+            FuzzType::Unit => FuzzValue::Unit,
+            FuzzType::Bool => FuzzValue::Bool(rng.gen()),
+            FuzzType::Uint(bits) => FuzzValue::Uint(generate_uint(rng, *bits)),
+            FuzzType::B256 => FuzzValue::B256(generate_b256(rng)),
+            FuzzType::Str(len) => FuzzValue::Str(
+                (0..*len)
+                    .map(|_| char::from(rng.sample(Alphanumeric)))
+                    .collect(),
+            ),
+            FuzzType::Array(elem, len) => {
+                FuzzValue::Aggregate((0..*len).map(|_| elem.generate(rng)).collect())
+            }
+            FuzzType::Tuple(elems) => {
+                FuzzValue::Aggregate(elems.iter().map(|ty| ty.generate(rng)).collect())
+            }
+            FuzzType::Struct { fields, .. } => {
+                FuzzValue::Aggregate(fields.iter().map(|(_, ty)| ty.generate(rng)).collect())
+            }
+            FuzzType::Enum { variants, .. } => {
+                let variant_ix = rng.gen_range(0..variants.len());
+                let value = variants[variant_ix].1.generate(rng);
+                FuzzValue::Enum(variant_ix, Box::new(value))
+            }
+        }
+    }
+
+    /// The simplest value of the type, made of zeros and the first variant of each enum.
+    fn simplest(&self) -> FuzzValue {
+        match self {
+            FuzzType::Unit => FuzzValue::Unit,
+            FuzzType::Bool => FuzzValue::Bool(false),
+            FuzzType::Uint(_) => FuzzValue::Uint(0),
+            FuzzType::B256 => FuzzValue::B256([0; 32]),
+            FuzzType::Str(len) => FuzzValue::Str("0".repeat(*len)),
+            FuzzType::Array(elem, len) => FuzzValue::Aggregate(vec![elem.simplest(); *len]),
+            FuzzType::Tuple(elems) => {
+                FuzzValue::Aggregate(elems.iter().map(FuzzType::simplest).collect())
+            }
+            FuzzType::Struct { fields, .. } => {
+                FuzzValue::Aggregate(fields.iter().map(|(_, ty)| ty.simplest()).collect())
+            }
+            FuzzType::Enum { variants, .. } => {
+                FuzzValue::Enum(0, Box::new(variants[0].1.simplest()))
+            }
+        }
+    }
+
+    /// Values of the type which are simpler than `value`, the simplest first.
+    fn shrink(&self, value: &FuzzValue) -> Vec<FuzzValue> {
+        match (self, value) {
+            (FuzzType::Uint(_), FuzzValue::Uint(n)) if *n > 0 => {
+                let mut simpler = vec![0, n / 2, n - 1];
+                simpler.dedup();
+                simpler.into_iter().map(FuzzValue::Uint).collect()
+            }
+            (FuzzType::Array(elem, _), FuzzValue::Aggregate(values)) => {
+                shrink_each(values, |_| elem)
+            }
+            (FuzzType::Tuple(elems), FuzzValue::Aggregate(values)) => {
+                shrink_each(values, |ix| &elems[ix])
+            }
+            (FuzzType::Struct { fields, .. }, FuzzValue::Aggregate(values)) => {
+                shrink_each(values, |ix| &fields[ix].1)
+            }
+            (FuzzType::Enum { variants, .. }, FuzzValue::Enum(variant_ix, value)) => {
+                let mut simpler = vec![];
+                if *variant_ix > 0 {
+                    simpler.push(self.simplest());
+                }
+                simpler.extend(
+                    variants[*variant_ix]
+                        .1
+                        .shrink(value)
+                        .into_iter()
+                        .map(|value| FuzzValue::Enum(*variant_ix, Box::new(value))),
+                );
+                simpler
+            }
+            _ => {
+                let simplest = self.simplest();
+                if *value == simplest {
+                    vec![]
+                } else {
+                    vec![simplest]
+                }
+            }
+        }
+    }
+
+    /// Append `value` to `bytes`, laid out in memory as the contract expects its arguments.
+    fn encode(&self, value: &FuzzValue, bytes: &mut Vec<u8>) {
+        match (self, value) {
+            (FuzzType::Unit, FuzzValue::Unit) => bytes.extend([0; 8]),
+            (FuzzType::Bool, FuzzValue::Bool(b)) => bytes.extend(u64::from(*b).to_be_bytes()),
+            (FuzzType::Uint(_), FuzzValue::Uint(n)) => bytes.extend(n.to_be_bytes()),
+            (FuzzType::B256, FuzzValue::B256(b256)) => bytes.extend(b256),
+            (FuzzType::Str(len), FuzzValue::Str(s)) => {
+                bytes.extend(s.as_bytes());
+                bytes.resize(bytes.len() + self.size() - len, 0);
+            }
+            (FuzzType::Array(elem, _), FuzzValue::Aggregate(values)) => {
+                for value in values {
+                    elem.encode(value, bytes);
+                }
+            }
+            (FuzzType::Tuple(elems), FuzzValue::Aggregate(values)) => {
+                for (ty, value) in elems.iter().zip(values) {
+                    ty.encode(value, bytes);
+                }
+            }
+            (FuzzType::Struct { fields, .. }, FuzzValue::Aggregate(values)) => {
+                for ((_, ty), value) in fields.iter().zip(values) {
+                    ty.encode(value, bytes);
+                }
+            }
+            (FuzzType::Enum { variants, .. }, FuzzValue::Enum(variant_ix, value)) => {
+                bytes.extend((*variant_ix as u64).to_be_bytes());
+                let union_size = self.size() - 8;
+                if union_size > 0 {
+                    // The variant is right-aligned within the union.
+                    let variant_ty = &variants[*variant_ix].1;
+                    bytes.resize(bytes.len() + union_size - variant_ty.size(), 0);
+                    variant_ty.encode(value, bytes);
+                }
+            }
+            _ => unreachable!("values are generated from their types"),
+        }
+    }
+
+    /// Format `value` as it would be written in Sway.
+    fn format(&self, value: &FuzzValue) -> String {
+        let join = |values: Vec<String>| values.join(", ");
+        match (self, value) {
+            (_, FuzzValue::Unit) => "()".to_string(),
+            (_, FuzzValue::Bool(b)) => b.to_string(),
+            (_, FuzzValue::Uint(n)) => n.to_string(),
+            (_, FuzzValue::B256(b256)) => format!("0x{}", hex::encode(b256)),
+            (_, FuzzValue::Str(s)) => format!("{s:?}"),
+            (FuzzType::Array(elem, _), FuzzValue::Aggregate(values)) => {
+                let values = values.iter().map(|value| elem.format(value)).collect();
+                format!("[{}]", join(values))
+            }
+            (FuzzType::Tuple(elems), FuzzValue::Aggregate(values)) => {
+                let values = elems
+                    .iter()
+                    .zip(values)
+                    .map(|(ty, value)| ty.format(value))
+                    .collect();
+                format!("({})", join(values))
+            }
+            (FuzzType::Struct { name, fields }, FuzzValue::Aggregate(values)) => {
+                let fields = fields
+                    .iter()
+                    .zip(values)
+                    .map(|((field, ty), value)| format!("{field}: {}", ty.format(value)))
+                    .collect();
+                format!("{name} {{ {} }}", join(fields))
+            }
+            (FuzzType::Enum { name, variants }, FuzzValue::Enum(variant_ix, value)) => {
+                match &variants[*variant_ix] {
+                    (variant, FuzzType::Unit) => format!("{name}::{variant}"),
+                    (variant, ty) => format!("{name}::{variant}({})", ty.format(value)),
+                }
+            }
+            _ => unreachable!("values are generated from their types"),
+        }
+    }
+}
+
+/// The values of `values` with one of them simplified, where `ty` gives the type of the value at
+/// an index.
+fn shrink_each<'a>(values: &[FuzzValue], ty: impl Fn(usize) -> &'a FuzzType) -> Vec<FuzzValue> {
+    let mut simpler = vec![];
+    for (ix, value) in values.iter().enumerate() {
+        for simpler_value in ty(ix).shrink(value) {
+            let mut values = values.to_vec();
+            values[ix] = simpler_value;
+            simpler.push(FuzzValue::Aggregate(values));
+        }
+    }
+    simpler
+}
+
+/// A random integer of `bits` bits, which is often `0`, `1`, the maximum or a small one, as those
+/// are what the checks of contracts are usually about.
+fn generate_uint(rng: &mut StdRng, bits: u8) -> u64 {
+    let max = u64::MAX >> (64 - bits);
+    match rng.gen_range(0..8) {
+        0 => 0,
+        1 => 1,
+        2 => max,
+        3..=5 => rng.gen_range(0..=max.min(100)),
+        _ => rng.gen_range(0..=max),
+    }
+}
+
+/// A random `b256`, which is often one of a few small ones, so that the calls of a sequence refer
+/// to the same addresses and ids.
+fn generate_b256(rng: &mut StdRng) -> [u8; 32] {
+    if rng.gen_bool(0.75) {
+        let mut b256 = [0; 32];
+        b256[31] = rng.gen_range(0..4);
+        b256
+    } else {
+        rng.gen()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build, Opts};
+    use std::path::PathBuf;
+
+    #[test]
+    fn enum_variants_are_right_aligned() {
+        let ty = FuzzType::Enum {
+            name: "Input".to_string(),
+            variants: vec![
+                ("Amount".to_string(), FuzzType::Uint(64)),
+                ("Recipient".to_string(), FuzzType::B256),
+            ],
+        };
+        let mut bytes = vec![];
+        ty.encode(
+            &FuzzValue::Enum(0, Box::new(FuzzValue::Uint(5))),
+            &mut bytes,
+        );
+
+        assert_eq!(bytes.len(), ty.size());
+        assert_eq!(bytes[..8], 0u64.to_be_bytes());
+        assert_eq!(bytes[8..32], [0; 24]);
+        assert_eq!(bytes[32..], 5u64.to_be_bytes());
+    }
+
+    #[test]
+    fn shrinking_ends_at_the_simplest_value() {
+        let ty = FuzzType::Tuple(vec![FuzzType::Bool, FuzzType::Uint(8)]);
+        let mut value = FuzzValue::Aggregate(vec![FuzzValue::Bool(true), FuzzValue::Uint(200)]);
+        while let Some(simpler) = ty.shrink(&value).pop() {
+            value = simpler;
+        }
+
+        assert_eq!(value, ty.simplest());
+        assert_eq!(ty.format(&value), "(false, 0)");
+    }
+
+    #[test]
+    fn failing_sequences_are_minimized() {
+        let pkg_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join("test_fuzz_contract");
+        let built = build(Opts {
+            pkg: forc_pkg::PkgOpts {
+                path: Some(pkg_dir.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        let fuzzed = built.fuzz(&FuzzOpts::default()).unwrap();
+        let failure = fuzzed[0].failure.as_ref().unwrap();
+
+        assert_eq!(failure.invariant, "total_supply_is_sum_of_balances");
+        assert_eq!(
+            failure.calls,
+            ["mint_to_alice(1)", "transfer_to_bob(1)", "burn_from_bob(1)"]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use forc_pkg as pkg;
+use forc_receipts::{DecodedReceipt, ReceiptDecoder};
 use fuel_abi_types::error_codes::ErrorSignal;
 use fuel_tx as tx;
 use fuel_vm::checked_transaction::builder::TransactionBuilderExt;
@@ -8,10 +9,20 @@ use pkg::TestPassCondition;
 use pkg::{Built, BuiltPackage};
 use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
-use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
-use sway_core::BuildTarget;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use sway_core::{
+    asm_generation::ProgramABI, language::ty::TestHook, BuildTarget, ExperimentalFeature,
+    LintLevel, OptLevel,
+};
 use sway_types::Span;
 
+pub mod fuzz;
+
 /// The result of a `forc test` invocation.
 #[derive(Debug)]
 pub enum Tested {
@@ -61,11 +72,66 @@ pub struct TestResult {
     pub condition: pkg::TestPassCondition,
     /// Emitted `Recipt`s during the execution of the test.
     pub logs: Vec<fuel_tx::Receipt>,
+    /// The `Transfer` and `TransferOut` receipts of the coins transferred during the test.
+    pub transfers: Vec<fuel_tx::Receipt>,
     /// Gas used while executing this test.
     pub gas_used: u64,
+    /// The storage fields of the deployed contracts whose values were changed by this test.
+    pub storage_changes: Vec<StorageChange>,
+    /// The `#[before_each]`, `#[after_each]` or `#[invariant]` function which reverted, failing
+    /// this test.
+    pub failed_hook: Option<FailedHook>,
+    /// The values asserted with `std::test_utils::assert_snapshot`, in order.
+    pub snapshots: Vec<Snapshot>,
+}
+
+/// A function run before or after a test, which reverted.
+#[derive(Debug)]
+pub struct FailedHook {
+    /// The name of the function.
+    pub name: String,
+    /// When the function is run.
+    pub hook: TestHook,
+    /// The state of the VM after executing the function.
+    pub state: vm::state::ProgramState,
+}
+
+/// A storage field of a contract deployed for a test, whose slot was changed by the test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    /// The name of the contract's package.
+    pub contract: String,
+    /// The name of the storage field.
+    pub field: String,
+    /// The type of the storage field, as written in Sway.
+    pub type_name: String,
+    /// The value of the field's slot before the test, or `None` if it wasn't set.
+    pub old: Option<tx::Bytes32>,
+    /// The value of the field's slot after the test, or `None` if it isn't set.
+    pub new: Option<tx::Bytes32>,
 }
 
+/// A value asserted with `std::test_utils::assert_snapshot`, along with its stored snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The file the snapshot is stored in.
+    pub path: PathBuf,
+    /// The value, as decoded with the ABI of the package.
+    pub value: String,
+    /// The contents of the snapshot file, or `None` if there is none yet.
+    pub stored: Option<String>,
+}
+
+/// The value `std::test_utils::assert_snapshot` logs ahead of the value it asserts.
+pub const SNAPSHOT_SIGNAL: u64 = 0xffff_ffff_ffff_1000;
+/// The directory of a package in which the snapshots of its tests are stored.
+pub const SNAPSHOTS_DIR: &str = "tests/snapshots";
+
 const TEST_METADATA_SEED: u64 = 0x7E57u64;
+/// The timestamp of the genesis block of the in-memory VM, the Unix epoch in TAI64.
+pub const GENESIS_TIMESTAMP: u64 = (1 << 62) + 10;
+/// The number of seconds between two blocks of the in-memory VM.
+pub const BLOCK_TIME: u64 = 10;
 /// A mapping from each member package of a build plan to its compiled contract dependencies.
 type ContractDependencyMap = HashMap<pkg::Pinned, Vec<Arc<pkg::BuiltPackage>>>;
 
@@ -130,6 +196,14 @@ impl DeploymentSetup {
         }
     }
 
+    /// Returns a mutable reference to the storage for this test setup
+    fn storage_mut(&mut self) -> &mut vm::storage::MemoryStorage {
+        match self {
+            DeploymentSetup::Script(script_setup) => &mut script_setup.storage,
+            DeploymentSetup::Contract(contract_setup) => &mut contract_setup.storage,
+        }
+    }
+
     /// Return the root contract id if this is a contract setup.
     fn root_contract_id(&self) -> Option<tx::ContractId> {
         match self {
@@ -162,8 +236,36 @@ pub struct Opts {
     pub error_on_warnings: bool,
     /// Output the time elapsed over each part of the compilation process.
     pub time_phases: bool,
-    /// Output compilation metrics into file.
+    /// Output the compilation metrics of every compiled package into file.
     pub metrics_outfile: Option<String>,
+    /// Experimental language features to enable, in addition to those enabled by the manifests.
+    pub experimental: Vec<ExperimentalFeature>,
+    /// The levels of lints by lint name, overriding those of the build profile.
+    pub lints: BTreeMap<String, LintLevel>,
+    /// How much to optimize the IR, overriding the level of the build profile.
+    pub optimization_level: Option<OptLevel>,
+    /// The IR passes to run, by name and in order, instead of those of the optimization level.
+    pub ir_passes: Option<Vec<String>>,
+    /// Verify the IR after each pass which modifies it.
+    pub verify_ir: bool,
+    /// Write the control-flow and call graphs of each built package to the output directory.
+    pub emit_cfg: bool,
+}
+
+impl StorageChange {
+    /// Formats the value of a slot of this field as a value of its type, where the type is known
+    /// to fit within the slot, or as hex otherwise.
+    pub fn format_value(&self, value: Option<&tx::Bytes32>) -> String {
+        let Some(value) = value else {
+            return "unset".to_string();
+        };
+        let word = u64::from_be_bytes(value[..8].try_into().unwrap());
+        match self.type_name.as_str() {
+            "u8" | "u16" | "u32" | "u64" => word.to_string(),
+            "bool" => (word != 0).to_string(),
+            _ => format!("0x{}", hex::encode(**value)),
+        }
+    }
 }
 
 /// The set of options provided for controlling logs printed for each test.
@@ -189,6 +291,14 @@ impl TestSetup {
         }
     }
 
+    /// Returns a mutable reference to the storage for this test setup
+    fn storage_mut(&mut self) -> &mut vm::storage::MemoryStorage {
+        match self {
+            TestSetup::WithDeployment(deployment_setup) => deployment_setup.storage_mut(),
+            TestSetup::WithoutDeployment(storage) => storage,
+        }
+    }
+
     /// Produces an iterator yielding contract ids of contract dependencies for this test setup.
     fn contract_dependency_ids(&self) -> impl Iterator<Item = &tx::ContractId> + '_ {
         match self {
@@ -240,6 +350,36 @@ impl TestedPackage {
     pub fn tests_passed(&self) -> bool {
         self.tests.iter().all(|test| test.passed())
     }
+
+    /// Stores the values of the snapshots which are missing or don't match, as for
+    /// `forc test --update-goldens`, and returns how many were stored.
+    pub fn update_snapshots(&mut self) -> anyhow::Result<usize> {
+        let mut updated = 0;
+        for snapshot in self.tests.iter_mut().flat_map(|test| &mut test.snapshots) {
+            if !snapshot.matches() {
+                snapshot.store()?;
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+}
+
+impl Snapshot {
+    /// Whether the value matches the stored snapshot.
+    pub fn matches(&self) -> bool {
+        self.stored.as_deref() == Some(&self.value)
+    }
+
+    /// Writes the value to the snapshot file.
+    pub fn store(&mut self) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, &self.value)?;
+        self.stored = Some(self.value.clone());
+        Ok(())
+    }
 }
 
 impl PackageWithDeploymentToTest {
@@ -402,6 +542,21 @@ impl<'a> PackageTests {
         test_filter: Option<&TestFilter>,
     ) -> anyhow::Result<TestedPackage> {
         let pkg_with_tests = self.built_pkg_with_tests();
+        let bytecode = &pkg_with_tests.bytecode.bytes;
+        let before_each = test_hooks(pkg_with_tests, TestHook::BeforeEach);
+        let after_each = test_hooks(pkg_with_tests, TestHook::AfterEach);
+        let invariants = test_hooks(pkg_with_tests, TestHook::Invariant);
+        let decoder = match &pkg_with_tests.program_abi {
+            ProgramABI::Fuel(program_abi) => {
+                ReceiptDecoder::from_json(serde_json::to_value(program_abi)?)?
+            }
+            _ => ReceiptDecoder::default(),
+        };
+        let snapshots_dir = pkg_with_tests
+            .descriptor
+            .manifest_file
+            .dir()
+            .join(SNAPSHOTS_DIR);
         let tests = test_runners.install(|| {
             pkg_with_tests
                 .bytecode
@@ -420,28 +575,95 @@ impl<'a> PackageTests {
                     let offset = u32::try_from(entry.finalized.imm)
                         .expect("test instruction offset out of range");
                     let name = entry.finalized.fn_name.clone();
-                    let test_setup = self.setup()?;
-                    let (state, duration, receipts) =
-                        exec_test(&pkg_with_tests.bytecode.bytes, offset, test_setup);
-
-                    let gas_used = *receipts
-                        .iter()
-                        .find_map(|receipt| match receipt {
-                            tx::Receipt::ScriptResult { gas_used, .. } => Some(gas_used),
-                            _ => None,
-                        })
-                        .ok_or_else(|| {
-                            anyhow::anyhow!("missing used gas information from test execution")
-                        })?;
+                    let mut test_setup = self.setup()?;
+                    let block_height = test_block_height(test_entry)
+                        .map_err(|e| anyhow::anyhow!("{e} for test: {name}"))?;
+                    test_setup
+                        .storage_mut()
+                        .set_block_height(block_height.into());
+                    let deployed_contracts = self.deployed_contracts(&test_setup);
+
+                    // The `#[before_each]` functions set up the storage the test starts from, and
+                    // the `#[after_each]` functions, then the `#[invariant]` functions, are run in
+                    // the storage the test leaves.
+                    let (mut receipts, mut failed_hook) = exec_hooks(
+                        bytecode,
+                        &before_each,
+                        TestHook::BeforeEach,
+                        &mut test_setup,
+                    );
+                    let storage_before = test_setup.storage().clone();
+                    let failed_setup_state = failed_hook.as_ref().map(|hook| hook.state);
+                    let (state, duration, gas_used, storage_after) = match failed_setup_state {
+                        // The test isn't executed if it couldn't be set up.
+                        Some(state) => (
+                            state,
+                            std::time::Duration::default(),
+                            0,
+                            storage_before.clone(),
+                        ),
+                        None => {
+                            let (state, duration, test_receipts, storage_after) =
+                                exec_test(bytecode, offset, &test_setup);
+                            let gas_used = *test_receipts
+                                .iter()
+                                .find_map(|receipt| match receipt {
+                                    tx::Receipt::ScriptResult { gas_used, .. } => Some(gas_used),
+                                    _ => None,
+                                })
+                                .ok_or_else(|| {
+                                    anyhow::anyhow!(
+                                        "missing used gas information from test execution"
+                                    )
+                                })?;
+                            receipts.extend(test_receipts);
+                            *test_setup.storage_mut() = storage_after.clone();
+                            let (hook_receipts, failed_after_hook) = exec_hooks(
+                                bytecode,
+                                &after_each,
+                                TestHook::AfterEach,
+                                &mut test_setup,
+                            );
+                            receipts.extend(hook_receipts);
+                            failed_hook = match failed_after_hook {
+                                Some(failed_after_hook) => Some(failed_after_hook),
+                                None => {
+                                    let (invariant_receipts, failed_invariant) = exec_hooks(
+                                        bytecode,
+                                        &invariants,
+                                        TestHook::Invariant,
+                                        &mut test_setup,
+                                    );
+                                    receipts.extend(invariant_receipts);
+                                    failed_invariant
+                                }
+                            };
+                            (state, duration, gas_used, storage_after)
+                        }
+                    };
+                    let storage_changes =
+                        storage_changes(&deployed_contracts, &storage_before, &storage_after);
 
-                    // Only retain `Log` and `LogData` receipts.
-                    let logs = receipts
+                    // Only retain `Log` and `LogData` receipts, and the transfers.
+                    let (logs, transfers): (Vec<_>, Vec<_>) = receipts
                         .into_iter()
                         .filter(|receipt| {
-                            matches!(receipt, fuel_tx::Receipt::Log { .. })
-                                || matches!(receipt, fuel_tx::Receipt::LogData { .. })
+                            matches!(
+                                receipt,
+                                fuel_tx::Receipt::Log { .. }
+                                    | fuel_tx::Receipt::LogData { .. }
+                                    | fuel_tx::Receipt::Transfer { .. }
+                                    | fuel_tx::Receipt::TransferOut { .. }
+                            )
                         })
-                        .collect();
+                        .partition(|receipt| {
+                            matches!(
+                                receipt,
+                                fuel_tx::Receipt::Log { .. } | fuel_tx::Receipt::LogData { .. }
+                            )
+                        });
+
+                    let snapshots = snapshots(&logs, &decoder, &snapshots_dir, &name);
 
                     let span = test_entry.span.clone();
                     let file_path = test_entry.file_path.clone();
@@ -454,7 +676,11 @@ impl<'a> PackageTests {
                         state,
                         condition,
                         logs,
+                        transfers,
                         gas_used,
+                        storage_changes,
+                        failed_hook,
+                        snapshots,
                     })
                 })
                 .collect::<anyhow::Result<_>>()
@@ -467,6 +693,27 @@ impl<'a> PackageTests {
         Ok(tested_pkg)
     }
 
+    /// The contracts deployed by `test_setup`, with the packages they were built from.
+    fn deployed_contracts(&self, test_setup: &TestSetup) -> Vec<(tx::ContractId, &BuiltPackage)> {
+        let pkg_with_deployment = match self {
+            PackageTests::Contract(pkg) | PackageTests::Script(pkg) => pkg,
+            PackageTests::Predicate(_) | PackageTests::Library(_) => return vec![],
+        };
+        let mut contracts = test_setup
+            .contract_dependency_ids()
+            .cloned()
+            .zip(
+                pkg_with_deployment
+                    .contract_dependencies()
+                    .map(|pkg| pkg.as_ref()),
+            )
+            .collect::<Vec<_>>();
+        if let Some(root_contract_id) = test_setup.root_contract_id() {
+            contracts.push((root_contract_id, pkg_with_deployment.pkg()));
+        }
+        contracts
+    }
+
     /// Setup the storage for a test and returns a contract id for testing contracts.
     ///
     /// For testing contracts, storage returned from this function contains the deployed contract.
@@ -505,6 +752,12 @@ impl Opts {
             metrics_outfile: self.metrics_outfile,
             tests: true,
             member_filter: Default::default(),
+            experimental: self.experimental,
+            lints: self.lints,
+            optimization_level: self.optimization_level,
+            ir_passes: self.ir_passes,
+            verify_ir: self.verify_ir,
+            emit_cfg: self.emit_cfg,
         }
     }
 }
@@ -512,6 +765,9 @@ impl Opts {
 impl TestResult {
     /// Whether or not the test passed.
     pub fn passed(&self) -> bool {
+        if self.failed_hook.is_some() || !self.snapshots.iter().all(Snapshot::matches) {
+            return false;
+        }
         match &self.condition {
             TestPassCondition::ShouldRevert(revert_code) => match revert_code {
                 Some(revert_code) => self.state == vm::state::ProgramState::Revert(*revert_code),
@@ -554,6 +810,45 @@ impl TestResult {
     }
 }
 
+/// The timestamp of the block at `height`, as returned by `std::block::timestamp` in a test
+/// executed in that block.
+///
+/// Like a local node, the in-memory VM derives the timestamp of a block from its height.
+pub fn timestamp_of_block(height: u32) -> u64 {
+    GENESIS_TIMESTAMP + u64::from(height) * BLOCK_TIME
+}
+
+/// The height of the block whose timestamp is `timestamp`, if there is one.
+pub fn block_of_timestamp(timestamp: u64) -> Option<u32> {
+    let since_genesis = timestamp.checked_sub(GENESIS_TIMESTAMP)?;
+    if since_genesis % BLOCK_TIME != 0 {
+        return None;
+    }
+    u32::try_from(since_genesis / BLOCK_TIME).ok()
+}
+
+/// The height of the block a test is executed in, as set with its `block_height` or `timestamp`
+/// arguments.  Tests are executed in the genesis block by default.
+fn test_block_height(test_entry: &pkg::PkgTestEntry) -> anyhow::Result<u32> {
+    let timestamp_height = test_entry
+        .timestamp
+        .map(|timestamp| {
+            block_of_timestamp(timestamp).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "timestamp {timestamp} is not that of a block, which are {BLOCK_TIME} \
+                     seconds apart from {GENESIS_TIMESTAMP}"
+                )
+            })
+        })
+        .transpose()?;
+    match (test_entry.block_height, timestamp_height) {
+        (Some(height), Some(timestamp_height)) if height != timestamp_height => anyhow::bail!(
+            "block height {height} doesn't match the timestamp of block {timestamp_height}"
+        ),
+        (height, timestamp_height) => Ok(height.or(timestamp_height).unwrap_or_default()),
+    }
+}
+
 /// Used to control test runner count for forc-test. Number of runners to use can be specified using
 /// `Manual` or can be left forc-test to decide by using `Auto`.
 pub enum TestRunnerCount {
@@ -758,19 +1053,118 @@ fn patch_test_bytecode(bytecode: &[u8], test_offset: u32) -> std::borrow::Cow<[u
 fn exec_test(
     bytecode: &[u8],
     test_offset: u32,
-    test_setup: TestSetup,
+    test_setup: &TestSetup,
 ) -> (
     vm::state::ProgramState,
     std::time::Duration,
     Vec<fuel_tx::Receipt>,
+    vm::storage::MemoryStorage,
 ) {
-    let storage = test_setup.storage().clone();
-
     // Patch the bytecode to jump to the relevant test.
     let bytecode = patch_test_bytecode(bytecode, test_offset).into_owned();
+    exec_script(bytecode, test_setup)
+}
 
-    // Create a transaction to execute the test function.
-    let script_input_data = vec![];
+/// Execute the given script bytecode in an in-memory VM with no contracts deployed.
+///
+/// Returns the final state of the program, the time taken to execute it, and its receipts.
+pub fn execute_script(
+    bytecode: &[u8],
+) -> (
+    vm::state::ProgramState,
+    std::time::Duration,
+    Vec<fuel_tx::Receipt>,
+) {
+    execute_script_with_data(bytecode, vec![])
+}
+
+/// Like [execute_script], with `script_data` as the data of the script, from which `main` reads
+/// its arguments.
+pub fn execute_script_with_data(
+    bytecode: &[u8],
+    script_data: Vec<u8>,
+) -> (
+    vm::state::ProgramState,
+    std::time::Duration,
+    Vec<fuel_tx::Receipt>,
+) {
+    let test_setup = TestSetup::WithoutDeployment(vm::storage::MemoryStorage::default());
+    let (state, duration, receipts, _) =
+        exec_script_with_data(bytecode.to_vec(), script_data, &test_setup);
+    (state, duration, receipts)
+}
+
+// The `#[before_each]`, `#[after_each]` or `#[invariant]` functions of `pkg`, as told by `hook`,
+// by name and instruction offset.
+fn test_hooks(pkg: &BuiltPackage, hook: TestHook) -> Vec<(String, u32)> {
+    pkg.bytecode
+        .entries
+        .iter()
+        .filter(|entry| entry.kind.test_hook() == Some(hook))
+        .map(|entry| {
+            let offset = u32::try_from(entry.finalized.imm)
+                .expect("test hook instruction offset out of range");
+            (entry.finalized.fn_name.clone(), offset)
+        })
+        .collect()
+}
+
+// Execute the `#[before_each]`, `#[after_each]` or `#[invariant]` functions `hooks`, as told by
+// `hook`, by name and instruction offset, one after the other, each in the storage the previous
+// one left.
+//
+// Returns their receipts, and the first which reverted, after which the others aren't executed.
+fn exec_hooks(
+    bytecode: &[u8],
+    hooks: &[(String, u32)],
+    hook: TestHook,
+    test_setup: &mut TestSetup,
+) -> (Vec<fuel_tx::Receipt>, Option<FailedHook>) {
+    let mut receipts = vec![];
+    for (name, offset) in hooks {
+        let (state, _, hook_receipts, storage) = exec_test(bytecode, *offset, test_setup);
+        receipts.extend(hook_receipts);
+        *test_setup.storage_mut() = storage;
+        if let vm::state::ProgramState::Revert(_) = state {
+            let name = name.clone();
+            return (receipts, Some(FailedHook { name, hook, state }));
+        }
+    }
+    (receipts, None)
+}
+
+// Execute the given bytecode as a script, with the contracts of the test setup as inputs.
+//
+// Also returns the storage as it is after the execution.
+fn exec_script(
+    bytecode: Vec<u8>,
+    test_setup: &TestSetup,
+) -> (
+    vm::state::ProgramState,
+    std::time::Duration,
+    Vec<fuel_tx::Receipt>,
+    vm::storage::MemoryStorage,
+) {
+    exec_script_with_data(bytecode, vec![], test_setup)
+}
+
+// Execute the given bytecode as a script with `script_input_data` as its data, with the contracts
+// of the test setup as inputs.
+//
+// Also returns the storage as it is after the execution.
+fn exec_script_with_data(
+    bytecode: Vec<u8>,
+    script_input_data: Vec<u8>,
+    test_setup: &TestSetup,
+) -> (
+    vm::state::ProgramState,
+    std::time::Duration,
+    Vec<fuel_tx::Receipt>,
+    vm::storage::MemoryStorage,
+) {
+    let storage = test_setup.storage().clone();
+
+    // Create a transaction to execute the script.
     let mut rng = rand::rngs::StdRng::seed_from_u64(TEST_METADATA_SEED);
 
     // Prepare the transaction metadata.
@@ -823,15 +1217,93 @@ fn exec_test(
     let duration = start.elapsed();
     let state = *transition.state();
     let receipts = transition.receipts().to_vec();
+    let storage = interpreter.as_ref().clone();
 
-    (state, duration, receipts)
+    (state, duration, receipts, storage)
+}
+
+/// The values logged by the calls to `std::test_utils::assert_snapshot` of the test `test_name`,
+/// each being the log following a `SNAPSHOT_SIGNAL` log, with their snapshots in `dir`.
+///
+/// The first snapshot of a test is stored in `<test_name>.snap`, and the following ones in
+/// `<test_name>-2.snap`, `<test_name>-3.snap` and so on.
+fn snapshots(
+    logs: &[tx::Receipt],
+    decoder: &ReceiptDecoder,
+    dir: &Path,
+    test_name: &str,
+) -> Vec<Snapshot> {
+    let mut logs = logs.iter();
+    let mut snapshots = Vec::new();
+    while let Some(log) = logs.next() {
+        if !matches!(log, tx::Receipt::Log { ra, .. } if *ra == SNAPSHOT_SIGNAL) {
+            continue;
+        }
+        let Some(DecodedReceipt::Log { value, .. }) = logs.next().map(|log| decoder.decode(log))
+        else {
+            continue;
+        };
+        let value = match value.token {
+            Some(token) => format!("{token}\n"),
+            None => format!("0x{}\n", hex::encode(&value.data)),
+        };
+        let file_name = match snapshots.len() {
+            0 => format!("{test_name}.snap"),
+            idx => format!("{test_name}-{}.snap", idx + 1),
+        };
+        let path = dir.join(file_name);
+        let stored = fs::read_to_string(&path).ok();
+        snapshots.push(Snapshot {
+            path,
+            value,
+            stored,
+        });
+    }
+    snapshots
+}
+
+/// The storage fields of the `contracts` whose slots differ between the storage `before` and
+/// `after` a test.
+fn storage_changes(
+    contracts: &[(tx::ContractId, &BuiltPackage)],
+    before: &vm::storage::MemoryStorage,
+    after: &vm::storage::MemoryStorage,
+) -> Vec<StorageChange> {
+    use vm::storage::InterpreterStorage;
+    let slot =
+        |storage: &vm::storage::MemoryStorage, contract_id: &tx::ContractId, key: &tx::Bytes32| {
+            storage
+                .merkle_contract_state(contract_id, key)
+                .ok()
+                .flatten()
+                .map(|value| value.into_owned())
+        };
+    contracts
+        .iter()
+        .flat_map(|(contract_id, pkg)| {
+            pkg.storage_layout.iter().filter_map(move |field| {
+                let old = slot(before, contract_id, &field.key);
+                let new = slot(after, contract_id, &field.key);
+                (old != new).then(|| StorageChange {
+                    contract: pkg.descriptor.name.clone(),
+                    field: field.name.clone(),
+                    type_name: field.type_name.clone(),
+                    old,
+                    new,
+                })
+            })
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
 
-    use crate::{build, BuiltTests, Opts, TestFilter, TestResult};
+    use crate::{
+        block_of_timestamp, build, snapshots, timestamp_of_block, BuiltTests, Opts, StorageChange,
+        TestFilter, TestResult, BLOCK_TIME, GENESIS_TIMESTAMP, SNAPSHOT_SIGNAL,
+    };
 
     /// Name of the folder containing required data for tests to run, such as an example forc
     /// project.
@@ -949,4 +1421,66 @@ mod tests {
 
         assert_eq!(tested_package_test_count, 2)
     }
+
+    #[test]
+    fn test_storage_change_values() {
+        let change = |type_name: &str| StorageChange {
+            contract: "counter".to_string(),
+            field: "count".to_string(),
+            type_name: type_name.to_string(),
+            old: None,
+            new: None,
+        };
+        let mut bytes = [0u8; 32];
+        bytes[7] = 1;
+        let value = fuel_tx::Bytes32::new(bytes);
+
+        assert_eq!(change("u64").format_value(None), "unset");
+        assert_eq!(change("u64").format_value(Some(&value)), "1");
+        assert_eq!(change("bool").format_value(Some(&value)), "true");
+        assert_eq!(
+            change("b256").format_value(Some(&value)),
+            format!("0x{}", hex::encode(bytes))
+        );
+    }
+
+    #[test]
+    fn test_block_timestamps() {
+        assert_eq!(timestamp_of_block(0), GENESIS_TIMESTAMP);
+        assert_eq!(timestamp_of_block(3), GENESIS_TIMESTAMP + 3 * BLOCK_TIME);
+        assert_eq!(block_of_timestamp(timestamp_of_block(100)), Some(100));
+        assert_eq!(block_of_timestamp(GENESIS_TIMESTAMP + 1), None);
+        assert_eq!(block_of_timestamp(GENESIS_TIMESTAMP - 1), None);
+    }
+
+    #[test]
+    fn test_snapshots_follow_signals() {
+        let log = |ra: u64| fuel_tx::Receipt::Log {
+            id: fuel_tx::ContractId::zeroed(),
+            ra,
+            rb: 0,
+            rc: 0,
+            rd: 0,
+            pc: 0,
+            is: 0,
+        };
+        let logs = [
+            log(1),
+            log(SNAPSHOT_SIGNAL),
+            log(2),
+            log(SNAPSHOT_SIGNAL),
+            log(3),
+        ];
+        let dir = PathBuf::from("tests/snapshots");
+        let snapshots = snapshots(&logs, &Default::default(), &dir, "test_foo");
+
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].path, dir.join("test_foo.snap"));
+        assert_eq!(
+            snapshots[0].value,
+            format!("0x{}\n", hex::encode(2u64.to_be_bytes()))
+        );
+        assert_eq!(snapshots[1].path, dir.join("test_foo-2.snap"));
+        assert!(snapshots.iter().all(|snapshot| !snapshot.matches()));
+    }
 }
@@ -1,26 +1,30 @@
 //! Utility items shared between forc crates.
 
 use annotate_snippets::{
-    display_list::{DisplayList, FormatOptions},
+    display_list::{DisplayList, FormatOptions, Margin},
     snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation},
 };
-use ansi_term::Colour;
 use anyhow::{bail, Result};
 use forc_tracing::{println_red_err, println_yellow_err};
 use std::str;
-use std::{ffi::OsStr, process::Termination};
 use std::{
+    env,
     fmt::Display,
+    io::{self, IsTerminal},
     path::{Path, PathBuf},
+    sync::Mutex,
 };
+use std::{ffi::OsStr, process::Termination};
 use sway_core::language::parsed::TreeType;
 use sway_error::error::CompileError;
-use sway_error::warning::CompileWarning;
-use sway_types::{LineCol, SourceEngine, Spanned};
+use sway_error::warning::{aggregate_warnings, AggregatedWarning, CompileWarning};
+use sway_types::{LineCol, SourceEngine, Span, Spanned};
 use sway_utils::constants;
 use tracing::error;
 
+pub mod progress;
 pub mod restricted;
+pub mod sarif;
 
 pub const DEFAULT_OUTPUT_DIRECTORY: &str = "out";
 pub const DEFAULT_ERROR_EXIT_CODE: u8 = 1;
@@ -132,10 +136,14 @@ macro_rules! forc_result_bail {
 #[cfg(feature = "fuel-tx")]
 pub mod tx_utils {
 
-    use anyhow::Result;
+    use anyhow::{bail, Result};
     use clap::Args;
+    use forc_receipts::ReceiptDecoder;
     use serde::{Deserialize, Serialize};
-    use sway_core::fuel_prelude::fuel_tx;
+    use sway_core::{
+        asm_generation::ProgramABI,
+        fuel_prelude::{fuel_crypto, fuel_tx},
+    };
 
     /// Added salt used to derive the contract ID.
     #[derive(Debug, Args, Default, Deserialize, Serialize)]
@@ -147,37 +155,102 @@ pub mod tx_utils {
         pub salt: Option<fuel_tx::Salt>,
     }
 
-    /// Format `Log` and `LogData` receipts.
-    pub fn format_log_receipts(
-        receipts: &[fuel_tx::Receipt],
-        pretty_print: bool,
-    ) -> Result<String> {
-        let mut receipt_to_json_array = serde_json::to_value(receipts)?;
-        for (rec_index, receipt) in receipts.iter().enumerate() {
-            let rec_value = receipt_to_json_array.get_mut(rec_index).ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Serialized receipts does not contain {} th index",
-                    rec_index
-                )
-            })?;
-            match receipt {
-                fuel_tx::Receipt::LogData { data, .. } => {
-                    if let Some(v) = rec_value.pointer_mut("/LogData/data") {
-                        *v = hex::encode(data).into();
-                    }
-                }
-                fuel_tx::Receipt::ReturnData { data, .. } => {
-                    if let Some(v) = rec_value.pointer_mut("/ReturnData/data") {
-                        *v = hex::encode(data).into();
-                    }
+    /// A decoder for the receipts of the program described by `program_abi`, which only
+    /// provides the raw data of receipts if it isn't a Fuel ABI.
+    pub fn receipt_decoder(program_abi: &ProgramABI) -> Result<ReceiptDecoder> {
+        match program_abi {
+            ProgramABI::Fuel(program_abi) => {
+                ReceiptDecoder::from_json(serde_json::to_value(program_abi)?)
+            }
+            _ => Ok(ReceiptDecoder::default()),
+        }
+    }
+
+    /// Formats a 32 byte address, contract ID or asset ID as `0x`-prefixed hex with a checksum
+    /// encoded in the case of its letters.
+    ///
+    /// A letter is uppercased when the matching nibble of the SHA-256 hash of the lowercase hex
+    /// string (without the `0x` prefix) is greater than 7, similar to EIP-55.
+    pub fn checksum_encode(bytes: &[u8; 32]) -> String {
+        let lowercase = hex::encode(bytes);
+        let hash = fuel_crypto::Hasher::hash(lowercase.as_bytes());
+        let checksummed = lowercase
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let byte = hash[i / 2];
+                let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0xf };
+                if nibble > 7 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
                 }
-                _ => {}
+            })
+            .collect::<String>();
+        format!("0x{checksummed}")
+    }
+
+    /// Parses a 32 byte address, contract ID or asset ID from hex, with or without the `0x`
+    /// prefix.
+    ///
+    /// All lowercase and all uppercase strings are accepted as is, while mixed case strings must
+    /// carry a valid checksum as produced by [checksum_encode].
+    pub fn checksum_decode(s: &str) -> Result<[u8; 32]> {
+        let hex_str = s.strip_prefix("0x").unwrap_or(s);
+        if hex_str.len() != 64 {
+            bail!(
+                "expected 64 hexadecimal characters, found {} in `{s}`",
+                hex_str.len()
+            );
+        }
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(hex_str, &mut bytes)
+            .map_err(|e| anyhow::anyhow!("invalid hexadecimal string `{s}`: {e}"))?;
+        let is_mixed_case = hex_str.chars().any(|c| c.is_ascii_lowercase())
+            && hex_str.chars().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case {
+            let expected = checksum_encode(&bytes);
+            if expected[2..] != *hex_str {
+                bail!("invalid checksum for `{s}`, did you mean `{expected}`?");
             }
         }
-        if pretty_print {
-            Ok(serde_json::to_string_pretty(&receipt_to_json_array)?)
-        } else {
-            Ok(serde_json::to_string(&receipt_to_json_array)?)
+        Ok(bytes)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_checksum_roundtrip() {
+            for bytes in [[0u8; 32], [0xab; 32], [0x5f; 32]] {
+                let encoded = checksum_encode(&bytes);
+                assert_eq!(encoded.len(), 66);
+                assert_eq!(encoded.to_lowercase(), format!("0x{}", hex::encode(bytes)));
+                assert_eq!(checksum_decode(&encoded).unwrap(), bytes);
+            }
+        }
+
+        #[test]
+        fn test_checksum_decode() {
+            let lowercase = "ab".repeat(32);
+            assert_eq!(checksum_decode(&lowercase).unwrap(), [0xab; 32]);
+            let uppercase = format!("0x{}", lowercase.to_uppercase());
+            assert_eq!(checksum_decode(&uppercase).unwrap(), [0xab; 32]);
+
+            let mut wrong_case = checksum_encode(&[0xab; 32]).into_bytes();
+            let ix = wrong_case[2..]
+                .iter()
+                .position(u8::is_ascii_alphabetic)
+                .unwrap()
+                + 2;
+            wrong_case[ix] ^= 0x20;
+            let wrong_case = String::from_utf8(wrong_case).unwrap();
+            let err = checksum_decode(&wrong_case).unwrap_err();
+            assert!(err.to_string().starts_with("invalid checksum"));
+
+            assert!(checksum_decode("0x01").is_err());
+            assert!(checksum_decode(&"zz".repeat(32)).is_err());
         }
     }
 }
@@ -345,18 +418,106 @@ pub fn program_type_str(ty: &TreeType) -> &'static str {
     }
 }
 
-pub fn print_compiling(ty: Option<&TreeType>, name: &str, src: &dyn std::fmt::Display) {
-    // NOTE: We can only print the program type if we can parse the program, so
-    // program type must be optional.
-    let ty = match ty {
-        Some(ty) => format!("{} ", program_type_str(ty)),
-        None => "".to_string(),
-    };
-    tracing::info!(
-        " {} {ty}{} ({src})",
-        Colour::Green.bold().paint("Compiling"),
-        ansi_term::Style::new().bold().paint(name)
-    );
+/// When to color the diagnostics printed by [print_warnings] and [print_on_failure].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color them when printing to a terminal, unless the `NO_COLOR` environment variable is set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+static COLOR_CHOICE: Mutex<ColorChoice> = Mutex::new(ColorChoice::Auto);
+
+/// Sets when to color the diagnostics, for the whole process.
+pub fn set_color_choice(color_choice: ColorChoice) {
+    *COLOR_CHOICE.lock().unwrap() = color_choice;
+}
+
+/// Whether to color the diagnostics, which are printed to stderr.
+fn use_color() -> bool {
+    match *COLOR_CHOICE.lock().unwrap() {
+        ColorChoice::Auto => io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    }
+}
+
+/// The options to format a diagnostic showing `slices` with, coloring it as chosen and fitting its
+/// code frames to the width of the terminal.
+fn format_options(slices: &[Slice]) -> FormatOptions {
+    FormatOptions {
+        color: use_color(),
+        margin: margin(slices),
+        ..Default::default()
+    }
+}
+
+/// The margin cutting the lines of `slices` around their annotations so that they fit in the
+/// terminal, or `None` if they already fit or there is no terminal.
+fn margin(slices: &[Slice]) -> Option<Margin> {
+    // The line numbers and the gutter to their right.
+    const GUTTER_WIDTH: usize = 8;
+
+    let (terminal_size::Width(terminal_width), _) = terminal_size::terminal_size()?;
+    let column_width = (terminal_width as usize).saturating_sub(GUTTER_WIDTH);
+    let lines = || slices.iter().flat_map(|slice| slice.source.lines());
+    let max_line_len = lines().map(|line| line.chars().count()).max()?;
+    if max_line_len <= column_width {
+        return None;
+    }
+    let whitespace_left = lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().take_while(|c| c.is_whitespace()).count())
+        .min()
+        .unwrap_or(0);
+    // The columns of the annotations within their lines, and the columns their labels end at.
+    let columns = slices
+        .iter()
+        .flat_map(|slice| {
+            let column = |offset: usize| {
+                let before = slice.source.chars().take(offset).collect::<String>();
+                before
+                    .rsplit('\n')
+                    .next()
+                    .map_or(0, |line| line.chars().count())
+            };
+            slice.annotations.iter().map(move |annotation| {
+                let (start, end) = annotation.range;
+                let end_column = column(end);
+                (
+                    column(start),
+                    end_column,
+                    end_column + 1 + annotation.label.chars().count(),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let span_left = columns.iter().map(|(start, ..)| *start).min().unwrap_or(0);
+    let span_right = columns.iter().map(|(_, end, _)| *end).max().unwrap_or(0);
+    let label_right = columns
+        .iter()
+        .map(|(.., label_end)| *label_end)
+        .max()
+        .unwrap_or(0);
+    Some(Margin::new(
+        whitespace_left,
+        span_left,
+        span_right,
+        label_right,
+        column_width,
+        max_line_len,
+    ))
+}
+
+/// Prints a summary line of the diagnostics, colored with `println` if coloring is enabled.
+fn println_summary(txt: &str, println: fn(&str)) {
+    if use_color() {
+        println(txt);
+    } else {
+        tracing::error!("{txt}");
+    }
 }
 
 pub fn print_warnings(
@@ -365,29 +526,36 @@ pub fn print_warnings(
     proj_name: &str,
     warnings: &[CompileWarning],
     tree_type: &TreeType,
+    sarif: Option<&sarif::Collector>,
 ) {
     if warnings.is_empty() {
         return;
     }
     let type_str = program_type_str(tree_type);
-
-    if !terse_mode {
-        warnings
-            .iter()
-            .for_each(|w| format_warning(source_engine, w));
+    if let Some(sarif) = sarif {
+        sarif.record(source_engine, warnings, &[]);
     }
 
-    println_yellow_err(&format!(
-        "  Compiled {} {:?} with {} {}.",
-        type_str,
-        proj_name,
-        warnings.len(),
-        if warnings.len() > 1 {
-            "warnings"
-        } else {
-            "warning"
+    progress::suspend(|| {
+        if !terse_mode {
+            format_warnings(source_engine, warnings, false);
         }
-    ));
+
+        println_summary(
+            &format!(
+                "  Compiled {} {:?} with {} {}.",
+                type_str,
+                proj_name,
+                warnings.len(),
+                if warnings.len() > 1 {
+                    "warnings"
+                } else {
+                    "warning"
+                }
+            ),
+            println_yellow_err,
+        );
+    });
 }
 
 pub fn print_on_failure(
@@ -396,103 +564,235 @@ pub fn print_on_failure(
     warnings: &[CompileWarning],
     errors: &[CompileError],
     reverse_results: bool,
+    sarif: Option<&sarif::Collector>,
 ) {
     let e_len = errors.len();
     let w_len = warnings.len();
+    if let Some(sarif) = sarif {
+        sarif.record(source_engine, warnings, errors);
+    }
 
-    if !terse_mode {
-        if reverse_results {
-            warnings
-                .iter()
-                .rev()
-                .for_each(|w| format_warning(source_engine, w));
-            errors
-                .iter()
-                .rev()
-                .for_each(|e| format_err(source_engine, e));
-        } else {
-            warnings
-                .iter()
-                .for_each(|w| format_warning(source_engine, w));
-            errors.iter().for_each(|e| format_err(source_engine, e));
+    progress::suspend(|| {
+        if !terse_mode {
+            format_warnings(source_engine, warnings, reverse_results);
+            if reverse_results {
+                errors
+                    .iter()
+                    .rev()
+                    .for_each(|e| format_err(source_engine, e));
+            } else {
+                errors.iter().for_each(|e| format_err(source_engine, e));
+            }
         }
-    }
 
-    if e_len == 0 && w_len > 0 {
-        println_red_err(&format!(
-            "  Aborting. {} warning(s) treated as error(s).",
-            warnings.len()
-        ));
-    } else {
-        println_red_err(&format!(
-            "  Aborting due to {} {}.",
-            e_len,
-            if e_len > 1 { "errors" } else { "error" }
-        ));
-    }
+        if e_len == 0 && w_len > 0 {
+            println_summary(
+                &format!(
+                    "  Aborting. {} warning(s) treated as error(s).",
+                    warnings.len()
+                ),
+                println_red_err,
+            );
+        } else {
+            println_summary(
+                &format!(
+                    "  Aborting due to {} {}.",
+                    e_len,
+                    if e_len > 1 { "errors" } else { "error" }
+                ),
+                println_red_err,
+            );
+        }
+    });
 }
 
 fn format_err(source_engine: &SourceEngine, err: &CompileError) {
     let span = err.span();
-    let input = span.input();
     let path = err.source_id().map(|id| source_engine.get_path(&id));
     let path_str = path.as_ref().map(|p| p.to_string_lossy());
-    let mut start_pos = span.start();
-    let mut end_pos = span.end();
 
     let friendly_str = maybe_uwuify(&format!("{err}"));
-    let (snippet_title, snippet_slices) = if start_pos < end_pos {
+    // The secondary labels in the same source as the error are shown along with it, and the
+    // others in slices of their own.
+    let secondary_labels = err.secondary_labels();
+    let (local_labels, remote_labels): (Vec<_>, Vec<_>) = secondary_labels
+        .iter()
+        .filter(|label| label.span.start() < label.span.end())
+        .partition(|label| label.span.source_id() == span.source_id());
+    let remote_paths = remote_labels
+        .iter()
+        .map(|label| {
+            label
+                .span
+                .source_id()
+                .map(|id| source_engine.get_path(id).to_string_lossy().to_string())
+        })
+        .collect::<Vec<_>>();
+    let help = err.help();
+    // The context of the error, from where it surfaced out to the outermost declaration.
+    let context = err
+        .context()
+        .iter()
+        .map(|frame| frame.to_string())
+        .collect::<Vec<_>>();
+    let (snippet_title, snippet_slices) = if span.start() < span.end() {
         let title = Some(Annotation {
             label: None,
-            id: None,
+            id: Some(err.code()),
             annotation_type: AnnotationType::Error,
         });
 
-        let (mut start, end) = err.span().line_col();
-        let input = construct_window(&mut start, end, &mut start_pos, &mut end_pos, input);
-        let slices = vec![Slice {
-            source: input,
-            line_start: start.line,
-            origin: path_str.as_deref(),
-            fold: false,
-            annotations: vec![SourceAnnotation {
-                label: &friendly_str,
-                annotation_type: AnnotationType::Error,
-                range: (start_pos, end_pos),
-            }],
-        }];
+        let annotations = std::iter::once((&span, AnnotationType::Error, friendly_str.as_str()))
+            .chain(
+                local_labels
+                    .iter()
+                    .map(|label| (&label.span, AnnotationType::Info, label.message.as_str())),
+            )
+            .collect::<Vec<_>>();
+        let mut slices = vec![annotated_slice(path_str.as_deref(), &annotations)];
+        slices.extend(
+            remote_labels
+                .iter()
+                .zip(&remote_paths)
+                .map(|(label, path)| {
+                    annotated_slice(
+                        path.as_deref(),
+                        &[(&label.span, AnnotationType::Info, label.message.as_str())],
+                    )
+                }),
+        );
 
         (title, slices)
     } else {
         (
             Some(Annotation {
                 label: Some(friendly_str.as_str()),
-                id: None,
+                id: Some(err.code()),
                 annotation_type: AnnotationType::Error,
             }),
             Vec::new(),
         )
     };
 
+    let opt = format_options(&snippet_slices);
     let snippet = Snippet {
         title: snippet_title,
-        footer: vec![],
+        footer: help
+            .iter()
+            .map(|help| (AnnotationType::Help, help))
+            .chain(context.iter().map(|frame| (AnnotationType::Note, frame)))
+            .map(|(annotation_type, label)| Annotation {
+                label: Some(label),
+                id: None,
+                annotation_type,
+            })
+            .collect(),
         slices: snippet_slices,
-        opt: FormatOptions {
-            color: true,
-            ..Default::default()
-        },
+        opt,
     };
     tracing::error!("{}\n____\n", DisplayList::from(snippet))
 }
 
-fn format_warning(source_engine: &SourceEngine, err: &CompileWarning) {
+/// Builds a slice of source code showing all of the given spans, each annotated with its label.
+///
+/// The spans must be within the same source. Lines far from all of them are folded.
+fn annotated_slice<'a>(
+    origin: Option<&'a str>,
+    annotations: &[(&'a Span, AnnotationType, &'a str)],
+) -> Slice<'a> {
+    let input = annotations[0].0.input();
+    let mut start_ix = annotations
+        .iter()
+        .map(|(span, ..)| span.start())
+        .min()
+        .unwrap_or(0);
+    let mut end_ix = annotations
+        .iter()
+        .map(|(span, ..)| span.end())
+        .max()
+        .unwrap_or(0);
+    let mut start = annotations
+        .iter()
+        .map(|(span, ..)| span.line_col().0)
+        .min_by_key(|line_col| line_col.line)
+        .expect("there is at least one annotation");
+    let end = annotations
+        .iter()
+        .map(|(span, ..)| span.line_col().1)
+        .max_by_key(|line_col| line_col.line)
+        .expect("there is at least one annotation");
+    let window = construct_window(&mut start, end, &mut start_ix, &mut end_ix, input);
+    // The byte offset of the window within the input, for positioning each of the annotations.
+    let window_start = window.as_ptr() as usize - input.as_ptr() as usize;
+    let char_offset = |byte_ix: usize| {
+        input[window_start..byte_ix.max(window_start)]
+            .chars()
+            .count()
+    };
+
+    Slice {
+        source: window,
+        line_start: start.line,
+        origin,
+        fold: annotations.len() > 1,
+        annotations: annotations
+            .iter()
+            .map(|&(span, annotation_type, label)| SourceAnnotation {
+                label,
+                annotation_type,
+                range: (char_offset(span.start()), char_offset(span.end())),
+            })
+            .collect(),
+    }
+}
+
+/// Prints the warnings, summarizing the ones repeated for the same span, e.g. once per
+/// instantiation of a generic function, unless verbose output is enabled.
+fn format_warnings(source_engine: &SourceEngine, warnings: &[CompileWarning], reverse: bool) {
+    let mut aggregated = if tracing::enabled!(tracing::Level::DEBUG) {
+        warnings
+            .iter()
+            .map(|warning| AggregatedWarning {
+                warning,
+                occurrences: 1,
+            })
+            .collect()
+    } else {
+        aggregate_warnings(warnings)
+    };
+    if reverse {
+        aggregated.reverse();
+    }
+    for AggregatedWarning {
+        warning,
+        occurrences,
+    } in aggregated
+    {
+        format_warning(source_engine, warning, occurrences);
+    }
+}
+
+fn format_warning(source_engine: &SourceEngine, err: &CompileWarning, occurrences: usize) {
     let span = err.span();
     let input = span.input();
     let path = err.source_id().map(|id| source_engine.get_path(&id));
     let path_str = path.as_ref().map(|p| p.to_string_lossy());
 
     let friendly_str = maybe_uwuify(&err.to_friendly_warning_string());
+    let mut notes = err
+        .suggestions()
+        .into_iter()
+        .map(|suggestion| (AnnotationType::Help, suggestion.message))
+        .collect::<Vec<_>>();
+    if occurrences > 1 {
+        notes.push((
+            AnnotationType::Note,
+            format!(
+                "this warning occurred in {occurrences} instantiations, \
+                 run with `--verbose` to show each of them"
+            ),
+        ));
+    }
     let mut start_pos = span.start();
     let mut end_pos = span.end();
     if start_pos == end_pos {
@@ -502,28 +802,34 @@ fn format_warning(source_engine: &SourceEngine, err: &CompileWarning) {
 
     let (mut start, end) = err.span.line_col();
     let input = construct_window(&mut start, end, &mut start_pos, &mut end_pos, input);
+    let slices = vec![Slice {
+        source: input,
+        line_start: start.line,
+        origin: path_str.as_deref(),
+        fold: false,
+        annotations: vec![SourceAnnotation {
+            label: &friendly_str,
+            annotation_type: AnnotationType::Warning,
+            range: (start_pos, end_pos),
+        }],
+    }];
+    let opt = format_options(&slices);
     let snippet = Snippet {
         title: Some(Annotation {
             label: None,
-            id: None,
+            id: Some(err.code()),
             annotation_type: AnnotationType::Warning,
         }),
-        footer: vec![],
-        slices: vec![Slice {
-            source: input,
-            line_start: start.line,
-            origin: path_str.as_deref(),
-            fold: false,
-            annotations: vec![SourceAnnotation {
-                label: &friendly_str,
-                annotation_type: AnnotationType::Warning,
-                range: (start_pos, end_pos),
-            }],
-        }],
-        opt: FormatOptions {
-            color: true,
-            ..Default::default()
-        },
+        footer: notes
+            .iter()
+            .map(|(annotation_type, note)| Annotation {
+                label: Some(note),
+                id: None,
+                annotation_type: *annotation_type,
+            })
+            .collect(),
+        slices,
+        opt,
     };
     tracing::warn!("{}\n____\n", DisplayList::from(snippet))
 }
@@ -0,0 +1,265 @@
+//! Reports the progress of builds: which of the packages to compile is being compiled, and the
+//! phase of compilation it's in.
+//!
+//! When stderr is a terminal, the progress is shown in a status bar kept below the rest of the
+//! output, and redrawn as packages go through their phases. Otherwise, it is printed as plain
+//! lines. How much is printed depends on the [Verbosity], set once for the whole process with
+//! [set_verbosity].
+
+use crate::program_type_str;
+use ansi_term::Colour;
+use std::{
+    fmt,
+    io::{self, IsTerminal, Write},
+    sync::Mutex,
+};
+use sway_core::language::parsed::TreeType;
+
+/// The width beyond which the packages being compiled are elided from the status bar, so that it
+/// fits on a line of a terminal and can be redrawn in place.
+const PACKAGES_WIDTH: usize = 40;
+const BAR_WIDTH: usize = 25;
+
+/// How much of the progress of builds is printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Nothing but the warnings and errors.
+    Quiet,
+    /// The packages being compiled, and the status bar on terminals.
+    #[default]
+    Normal,
+    /// The phases of compilation of each package too.
+    Verbose,
+}
+
+/// The phases of compiling a package.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildPhase {
+    /// Parsing and type-checking the package into a typed AST.
+    CompileToAst,
+    /// Generating the IR and the ASM from the typed AST.
+    CompileAstToAsm,
+    /// Generating the JSON ABI from the typed AST.
+    GenerateAbi,
+    /// Assembling the ASM into bytecode.
+    CompileAsmToBytecode,
+}
+
+impl fmt::Display for BuildPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BuildPhase::CompileToAst => "type-checking",
+            BuildPhase::CompileAstToAsm => "generating ASM",
+            BuildPhase::GenerateAbi => "generating ABI",
+            BuildPhase::CompileAsmToBytecode => "generating bytecode",
+        })
+    }
+}
+
+static VERBOSITY: Mutex<Verbosity> = Mutex::new(Verbosity::Normal);
+
+/// The progress of the current build, or `None` if no build is in progress.
+static BUILD: Mutex<Option<BuildStatus>> = Mutex::new(None);
+
+struct BuildStatus {
+    total: usize,
+    started: usize,
+    compiled: usize,
+    /// The packages being compiled, in the order they started, with the phases they're in.
+    compiling: Vec<(String, Option<BuildPhase>)>,
+    /// Whether the status bar is shown, rather than plain lines.
+    bar: bool,
+    /// Whether the status bar is currently drawn, on the last line of stderr.
+    drawn: bool,
+}
+
+impl BuildStatus {
+    fn draw(&mut self) {
+        if !self.bar {
+            return;
+        }
+        let line = status_line(self.compiled, self.total, &self.compiling);
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "\r\x1b[2K{line}");
+        let _ = stderr.flush();
+        self.drawn = true;
+    }
+
+    fn clear(&mut self) {
+        if self.drawn {
+            let mut stderr = io::stderr();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+            self.drawn = false;
+        }
+    }
+}
+
+/// Sets how much of the progress of builds is printed.
+pub fn set_verbosity(verbosity: Verbosity) {
+    *VERBOSITY.lock().unwrap() = verbosity;
+}
+
+/// How much of the progress of builds is printed.
+pub fn verbosity() -> Verbosity {
+    *VERBOSITY.lock().unwrap()
+}
+
+/// Starts reporting the progress of a build compiling `total` packages, until the returned guard
+/// is dropped, which clears the status bar.
+pub fn start(total: usize) -> BuildProgress {
+    let verbosity = verbosity();
+    *BUILD.lock().unwrap() = Some(BuildStatus {
+        total,
+        started: 0,
+        compiled: 0,
+        compiling: vec![],
+        bar: verbosity > Verbosity::Quiet && io::stderr().is_terminal(),
+        drawn: false,
+    });
+    BuildProgress(())
+}
+
+/// Reports the progress of a build while it's alive, see [start].
+#[must_use = "the progress of the build is only reported until it is dropped"]
+pub struct BuildProgress(());
+
+impl Drop for BuildProgress {
+    fn drop(&mut self) {
+        if let Some(mut status) = BUILD.lock().unwrap().take() {
+            status.clear();
+        }
+    }
+}
+
+/// Reports that the package `name` is starting to be compiled, printing it along with how many of
+/// the packages of the build have been started.
+pub fn compiling(ty: Option<&TreeType>, name: &str, src: &dyn fmt::Display) {
+    let mut build = BUILD.lock().unwrap();
+    let count = build.as_mut().map(|status| {
+        status.started += 1;
+        status.compiling.push((name.to_string(), None));
+        (status.started, status.total)
+    });
+    if verbosity() > Verbosity::Quiet {
+        // NOTE: We can only print the program type if we can parse the program, so
+        // program type must be optional.
+        let ty = match ty {
+            Some(ty) => format!("{} ", program_type_str(ty)),
+            None => "".to_string(),
+        };
+        let count = match count {
+            Some((started, total)) => format!("[{started}/{total}] "),
+            None => "".to_string(),
+        };
+        if let Some(status) = build.as_mut() {
+            status.clear();
+        }
+        tracing::info!(
+            " {} {count}{ty}{} ({src})",
+            Colour::Green.bold().paint("Compiling"),
+            ansi_term::Style::new().bold().paint(name)
+        );
+    }
+    if let Some(status) = build.as_mut() {
+        status.draw();
+    }
+}
+
+/// Reports that the package `name` entered `phase`.
+pub fn phase(name: &str, phase: BuildPhase) {
+    let mut build = BUILD.lock().unwrap();
+    let Some(status) = build.as_mut() else {
+        return;
+    };
+    if let Some((_, current)) = status.compiling.iter_mut().find(|(pkg, _)| pkg == name) {
+        *current = Some(phase);
+    }
+    if verbosity() == Verbosity::Verbose {
+        status.clear();
+        tracing::info!("{:>12} {name}: {phase}", "");
+    }
+    status.draw();
+}
+
+/// Reports that the package `name` is compiled.
+pub fn compiled(name: &str) {
+    let mut build = BUILD.lock().unwrap();
+    let Some(status) = build.as_mut() else {
+        return;
+    };
+    status.compiled += 1;
+    status.compiling.retain(|(pkg, _)| pkg != name);
+    status.draw();
+}
+
+/// Runs `print`, with the status bar cleared while it prints, so that its output isn't mixed with
+/// the bar.
+pub fn suspend<T>(print: impl FnOnce() -> T) -> T {
+    let mut build = BUILD.lock().unwrap();
+    if let Some(status) = build.as_mut() {
+        status.clear();
+    }
+    let result = print();
+    if let Some(status) = build.as_mut() {
+        status.draw();
+    }
+    result
+}
+
+/// The status bar, e.g. `Building [=====>   ] 2/5: std (generating ASM)`.
+fn status_line(
+    compiled: usize,
+    total: usize,
+    compiling: &[(String, Option<BuildPhase>)],
+) -> String {
+    let filled = BAR_WIDTH * compiled / total.max(1);
+    let bar = match BAR_WIDTH - filled {
+        0 => "=".repeat(BAR_WIDTH),
+        empty => format!("{}>{}", "=".repeat(filled), " ".repeat(empty - 1)),
+    };
+    let mut packages = compiling
+        .iter()
+        .map(|(name, phase)| match phase {
+            Some(phase) => format!("{name} ({phase})"),
+            None => name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    if packages.chars().count() > PACKAGES_WIDTH {
+        packages = packages
+            .chars()
+            .take(PACKAGES_WIDTH - 3)
+            .collect::<String>()
+            + "...";
+    }
+    // The ansi_term formatters ignore the `std::fmt` right-align formatter, so the label is padded
+    // before being painted.
+    let label = Colour::Cyan.bold().paint(format!("{:>12}", "Building"));
+    format!("{label} [{bar}] {compiled}/{total}: {packages}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_line_shows_the_packages_being_compiled() {
+        let line = status_line(
+            2,
+            5,
+            &[
+                ("std".to_string(), Some(BuildPhase::CompileAstToAsm)),
+                ("lib".to_string(), None),
+            ],
+        );
+        assert!(line.ends_with(&format!(
+            "[{}>{}] 2/5: std (generating ASM), lib",
+            "=".repeat(10),
+            " ".repeat(14)
+        )));
+
+        let line = status_line(5, 5, &[]);
+        assert!(line.ends_with(&format!("[{}] 5/5: ", "=".repeat(BAR_WIDTH))));
+    }
+}
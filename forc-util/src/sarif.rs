@@ -0,0 +1,255 @@
+//! Collects diagnostics so they can be written as a
+//! [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) 2.1.0 log, the format
+//! read by code scanning tools.
+//!
+//! A [Collector] is passed to whatever reports the diagnostics, e.g. to
+//! [print_warnings](crate::print_warnings) and [print_on_failure](crate::print_on_failure), which
+//! record those they print, and [Collector::write] outputs them.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use sway_error::{error::CompileError, warning::CompileWarning};
+use sway_types::{LineCol, SourceEngine, Span, Spanned};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The diagnostics reported by one run of a tool.
+///
+/// Diagnostics may be recorded from several threads, e.g. by packages compiled in parallel.
+pub struct Collector {
+    tool_name: &'static str,
+    /// The rules described up front, by id, in the order they're listed in.
+    rules: Vec<(String, String)>,
+    diagnostics: Mutex<Vec<Diagnostic>>,
+}
+
+struct Diagnostic {
+    rule_id: String,
+    level: &'static str,
+    message: String,
+    location: Option<(PathBuf, LineCol, LineCol)>,
+}
+
+impl Collector {
+    /// A collector of the diagnostics of the tool named `tool_name`.
+    pub fn new(tool_name: &'static str) -> Self {
+        Self {
+            tool_name,
+            rules: vec![],
+            diagnostics: Mutex::new(vec![]),
+        }
+    }
+
+    /// Describes the rule with the given `id`, which is listed before the rules of the diagnostics
+    /// recorded without a description, e.g. the codes of compiler diagnostics.
+    pub fn with_rule(mut self, id: &str, description: &str) -> Self {
+        self.rules.push((id.to_string(), description.to_string()));
+        self
+    }
+
+    /// Records compiler diagnostics, with their codes as rules.
+    pub fn record(
+        &self,
+        source_engine: &SourceEngine,
+        warnings: &[CompileWarning],
+        errors: &[CompileError],
+    ) {
+        let mut diagnostics = self.diagnostics.lock().unwrap();
+        diagnostics.extend(warnings.iter().map(|warning| Diagnostic {
+            rule_id: warning.code().to_string(),
+            level: "warning",
+            message: warning.to_friendly_warning_string(),
+            location: location(source_engine, &warning.span()),
+        }));
+        diagnostics.extend(errors.iter().map(|error| Diagnostic {
+            rule_id: error.code().to_string(),
+            level: "error",
+            message: error.to_string(),
+            location: location(source_engine, &error.span()),
+        }));
+    }
+
+    /// Records a warning of the rule `rule_id` about the code at `span`.
+    pub fn record_warning(
+        &self,
+        source_engine: &SourceEngine,
+        rule_id: &str,
+        message: &str,
+        span: &Span,
+    ) {
+        self.diagnostics.lock().unwrap().push(Diagnostic {
+            rule_id: rule_id.to_string(),
+            level: "warning",
+            message: message.to_string(),
+            location: location(source_engine, span),
+        });
+    }
+
+    /// The log of the recorded diagnostics, locating those within `project_dir` relative to it.
+    pub fn to_json(&self, project_dir: &Path) -> Value {
+        let project_dir = project_dir
+            .canonicalize()
+            .unwrap_or_else(|_| project_dir.to_path_buf());
+        let diagnostics = self.diagnostics.lock().unwrap();
+
+        // The described rules come first, followed by every other rule of a diagnostic, once.
+        let mut other_rule_ids = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.rule_id.as_str())
+            .filter(|rule_id| !self.rules.iter().any(|(id, _)| id == rule_id))
+            .collect::<Vec<_>>();
+        other_rule_ids.sort_unstable();
+        other_rule_ids.dedup();
+        let rule_ids = self
+            .rules
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .chain(other_rule_ids.iter().copied())
+            .collect::<Vec<_>>();
+        let rules = self
+            .rules
+            .iter()
+            .map(|(id, description)| {
+                json!({
+                    "id": id,
+                    "shortDescription": { "text": description },
+                    "defaultConfiguration": { "level": "warning" },
+                })
+            })
+            .chain(other_rule_ids.iter().map(|id| json!({ "id": id })))
+            .collect::<Vec<_>>();
+
+        let results = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let locations = diagnostic
+                    .location
+                    .as_ref()
+                    .map(|(path, start, end)| {
+                        let uri = match path.strip_prefix(&project_dir) {
+                            Ok(relative_path) => relative_path.to_string_lossy().replace('\\', "/"),
+                            Err(_) => {
+                                format!("file://{}", path.to_string_lossy().replace('\\', "/"))
+                            }
+                        };
+                        vec![json!({
+                            "physicalLocation": {
+                                "artifactLocation": { "uri": uri },
+                                "region": {
+                                    "startLine": start.line,
+                                    "startColumn": start.col,
+                                    "endLine": end.line,
+                                    "endColumn": end.col,
+                                },
+                            },
+                        })]
+                    })
+                    .unwrap_or_default();
+                json!({
+                    "ruleId": diagnostic.rule_id,
+                    "ruleIndex": rule_ids.iter().position(|id| *id == diagnostic.rule_id),
+                    "level": diagnostic.level,
+                    "message": { "text": diagnostic.message },
+                    "locations": locations,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": self.tool_name,
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://fuellabs.github.io/sway",
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        })
+    }
+
+    /// Writes the log of the recorded diagnostics to `path`, locating those within `project_dir`
+    /// relative to it.
+    pub fn write(&self, path: &Path, project_dir: &Path) -> Result<()> {
+        let log = self.to_json(project_dir);
+        std::fs::write(path, serde_json::to_string_pretty(&log)?)?;
+        Ok(())
+    }
+}
+
+/// The file containing `span`, along with where it starts and ends within it.
+pub fn location(source_engine: &SourceEngine, span: &Span) -> Option<(PathBuf, LineCol, LineCol)> {
+    let path = source_engine.get_path(span.source_id()?);
+    let (start, end) = span.line_col();
+    Some((path, start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn diagnostics_are_serialized_as_sarif() {
+        let source_engine = SourceEngine::default();
+        let project_dir = PathBuf::from("/project");
+        let source_id = source_engine.get_source_id(&project_dir.join("src/main.sw"));
+        let src: Arc<str> = Arc::from("contract;\n\nfn f() {}\n");
+        let span = Span::new(src.clone(), 11, 20, Some(source_id)).unwrap();
+        let outside_id = source_engine.get_source_id(&PathBuf::from("/lib/src/lib.sw"));
+        let outside_span = Span::new(src, 0, 8, Some(outside_id)).unwrap();
+
+        let collector = Collector::new("forc-audit").with_rule("described", "A described rule");
+        collector.record_warning(&source_engine, "other", "Other issue", &outside_span);
+        collector.record_warning(&source_engine, "described", "Described issue", &span);
+
+        let log = collector.to_json(&project_dir);
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["name"], "forc-audit");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            json!([
+                {
+                    "id": "described",
+                    "shortDescription": { "text": "A described rule" },
+                    "defaultConfiguration": { "level": "warning" },
+                },
+                { "id": "other" },
+            ])
+        );
+        assert_eq!(
+            run["results"][1],
+            json!({
+                "ruleId": "described",
+                "ruleIndex": 0,
+                "level": "warning",
+                "message": { "text": "Described issue" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "src/main.sw" },
+                        "region": {
+                            "startLine": 3,
+                            "startColumn": 1,
+                            "endLine": 3,
+                            "endColumn": 10,
+                        },
+                    },
+                }],
+            })
+        );
+        assert_eq!(run["results"][0]["ruleIndex"], 1);
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "file:///lib/src/lib.sw"
+        );
+    }
+}
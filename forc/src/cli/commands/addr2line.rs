@@ -12,7 +12,7 @@ use annotate_snippets::{
     snippet::{AnnotationType, Slice, Snippet, SourceAnnotation},
 };
 
-use sway_core::source_map::{LocationRange, SourceMap};
+use sway_core::source_map::{LocationRange, SourceMap, SOURCE_MAP_VERSION};
 
 /// Show location and context of an opcode address in its source file
 #[derive(Debug, Parser)]
@@ -42,6 +42,15 @@ pub(crate) fn exec(command: Command) -> ForcResult<()> {
             err
         )
     })?;
+    if sm.version > SOURCE_MAP_VERSION {
+        return Err(anyhow!(
+            "{:?}: source map version {} is newer than the supported version {}",
+            command.sourcemap_path,
+            sm.version,
+            SOURCE_MAP_VERSION
+        )
+        .into());
+    }
 
     if let Some((mut path, range)) = sm.addr_to_span(command.opcode_index) {
         if path.is_relative() {
@@ -73,6 +82,19 @@ pub(crate) fn exec(command: Command) -> ForcResult<()> {
         };
         info!("{}", DisplayList::from(snippet));
 
+        // The call sites the opcode was inlined at, innermost first.
+        for call_site in sm.addr_to_call_stack(command.opcode_index).iter().skip(1) {
+            match call_site.start {
+                Some(start) => info!(
+                    "inlined at {}:{}:{}",
+                    call_site.path.display(),
+                    start.line,
+                    start.col
+                ),
+                None => info!("inlined at {}", call_site.path.display()),
+            }
+        }
+
         Ok(())
     } else {
         Err("Address did not map to any source code location".into())
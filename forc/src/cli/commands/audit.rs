@@ -0,0 +1,48 @@
+use crate::ops::forc_audit;
+use clap::Parser;
+use forc_pkg::source::IPFSNode;
+use forc_util::ForcResult;
+use std::path::PathBuf;
+
+/// Audit the current or target project for common security issues.
+///
+/// Type checks the package and runs a set of security rules over it, such as checking that ABI
+/// methods writing to storage restrict who can call them. The findings are printed and can also
+/// be written as SARIF, to be uploaded to code scanning tools.
+#[derive(Debug, Default, Parser)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// The rules to run, separated by commas. All of them are run by default.
+    ///
+    /// See `--list-rules` for the available rules.
+    #[clap(long, use_value_delimiter = true)]
+    pub rules: Option<Vec<String>>,
+    /// Print the available rules and exit.
+    #[clap(long)]
+    pub list_rules: bool,
+    /// Write the findings to this file in the SARIF format, in addition to printing them.
+    #[clap(long)]
+    pub sarif: Option<PathBuf>,
+    /// Exit with an error if there are any findings.
+    #[clap(long)]
+    pub deny_findings: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+    /// Requires that the Forc.lock file is up-to-date. If the lock file is missing, or it
+    /// needs to be updated, Forc will exit with an error
+    #[clap(long)]
+    pub locked: bool,
+    /// The IPFS Node to use for fetching IPFS sources.
+    ///
+    /// Possible values: PUBLIC, LOCAL, <GATEWAY_URL>
+    #[clap(long)]
+    pub ipfs_node: Option<IPFSNode>,
+}
+
+pub(crate) fn exec(command: Command) -> ForcResult<()> {
+    forc_audit::audit(command).map_err(|e| e.into())
+}
@@ -1,6 +1,7 @@
 use crate::{cli, ops::forc_build};
 use clap::Parser;
 use forc_util::ForcResult;
+use std::path::PathBuf;
 
 /// Compile the current or target project.
 ///
@@ -16,6 +17,15 @@ use forc_util::ForcResult;
 ///
 /// - `contract` and `library` projects will also produce the public ABI in JSON format
 /// `<project-name>-abi.json`.
+///
+/// - All projects also produce the debug info of their bytecode in JSON format
+/// `<project-name>-debug_info.json`, describing its functions along with the names, types and
+/// locations of their arguments and variables, for use by debuggers.
+///
+/// Builds are cached under `out/.cache`, so that packages that have not changed since they were
+/// last built are not compiled again. The libraries of packages that have changed are type
+/// checked again, but their code isn't generated again. Builds that print compiler output, include
+/// tests, or target a VM other than Fuel are not cached.
 #[derive(Debug, Default, Parser)]
 pub struct Command {
     #[clap(flatten)]
@@ -23,9 +33,24 @@ pub struct Command {
     /// Also build all tests within the project.
     #[clap(long)]
     pub tests: bool,
+    /// Write the errors and warnings to this file in the SARIF format, in addition to printing
+    /// them, so that code scanning tools can report them.
+    #[clap(long)]
+    pub sarif: Option<PathBuf>,
+    /// Compile the IR in this file, e.g. as printed by `--print-ir`, to bytecode instead of
+    /// building the project.
+    ///
+    /// The IR is optimized according to `-O` and `--ir-passes`, and the bytecode is written to
+    /// the file given with `--output-bin`, or next to the IR file with the `bin` extension.
+    #[clap(long, value_name = "FILE", conflicts_with = "tests")]
+    pub ir_only: Option<PathBuf>,
 }
 
 pub(crate) fn exec(command: Command) -> ForcResult<()> {
+    if command.ir_only.is_some() {
+        forc_build::build_ir(command)?;
+        return Ok(());
+    }
     forc_build::build(command)?;
     Ok(())
 }
@@ -1,7 +1,8 @@
 use crate::ops::forc_check;
 use clap::Parser;
 use forc_pkg::source::IPFSNode;
-use forc_util::{forc_result_bail, ForcResult};
+use forc_util::{forc_result_bail, sarif, ForcResult};
+use std::path::PathBuf;
 use sway_core::{BuildTarget, Engines};
 
 /// Check the current or target project and all of its dependencies for errors.
@@ -35,11 +36,25 @@ pub struct Command {
     /// Possible values: PUBLIC, LOCAL, <GATEWAY_URL>
     #[clap(long)]
     pub ipfs_node: Option<IPFSNode>,
+    /// Write the errors and warnings to this file in the SARIF format, so that code scanning tools
+    /// can report them.
+    #[clap(long)]
+    pub sarif: Option<PathBuf>,
 }
 
 pub(crate) fn exec(command: Command) -> ForcResult<()> {
     let engines = Engines::default();
+    let sarif_path = command.sarif.clone();
+    let project_dir = match &command.path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
     let res = forc_check::check(command, &engines)?;
+    if let Some(sarif_path) = sarif_path {
+        let sarif = sarif::Collector::new("forc");
+        sarif.record(engines.se(), &res.warnings, &res.errors);
+        sarif.write(&sarif_path, &project_dir)?;
+    }
     if !res.is_ok() {
         forc_result_bail!("unable to type check");
     }
@@ -0,0 +1,17 @@
+use crate::ops::forc_explain;
+use clap::Parser;
+use forc_util::ForcResult;
+
+/// Explain an error or warning of the compiler in detail.
+///
+/// Every error and warning has a code, such as `E0001` or `W0001`, shown next to it in the
+/// compiler's output. Prints the extended explanation of the given code, if there is one.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The code of the error or warning to explain, e.g. `E0001`.
+    pub code: String,
+}
+
+pub(crate) fn exec(command: Command) -> ForcResult<()> {
+    forc_explain::explain(&command.code).map_err(|e| e.into())
+}
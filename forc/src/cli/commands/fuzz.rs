@@ -0,0 +1,96 @@
+use crate::cli::{self, commands::test::opts_from_build};
+use ansi_term::Colour;
+use clap::Parser;
+use forc_test::fuzz::{FuzzOpts, FuzzedPackage};
+use forc_util::{ForcError, ForcResult};
+use tracing::info;
+
+/// Fuzz the ABI methods of the current contract against its invariants.
+///
+/// Each run deploys the contract to an in-memory VM and makes a random sequence of calls to its
+/// ABI methods, with generated arguments, running the functions decorated with the `#[invariant]`
+/// attribute after each call. Calls which revert or panic are discarded as invalid inputs.
+///
+/// When an invariant reverts, the sequence of calls which led to it is minimized and printed,
+/// along with the seed to reproduce it with. Methods with heap types such as `Vec` among their
+/// arguments aren't called.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[clap(flatten)]
+    pub build: cli::shared::Build,
+    /// The number of sequences of calls to try.
+    #[clap(long, default_value_t = 256)]
+    pub runs: usize,
+    /// The maximum number of calls in a sequence.
+    #[clap(long, default_value_t = 20)]
+    pub depth: usize,
+    /// The seed of the generator of the calls and their arguments. A random one is used if it's
+    /// not specified.
+    #[clap(long)]
+    pub seed: Option<u64>,
+}
+
+pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
+    let seed = cmd.seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let fuzz_opts = FuzzOpts {
+        runs: cmd.runs,
+        depth: cmd.depth,
+        seed,
+    };
+    let built_tests = forc_test::build(opts_from_build(cmd.build))?;
+    info!("   Fuzzing with seed {seed}");
+    let fuzzed = built_tests.fuzz(&fuzz_opts)?;
+    for pkg in &fuzzed {
+        print_fuzzed_pkg(pkg, &fuzz_opts);
+    }
+
+    if fuzzed.iter().all(FuzzedPackage::passed) {
+        Ok(())
+    } else {
+        let forc_error: ForcError = "Some invariants were violated.".into();
+        const VIOLATED_INVARIANTS_EXIT_CODE: u8 = 101;
+        Err(forc_error.exit_code(VIOLATED_INVARIANTS_EXIT_CODE))
+    }
+}
+
+fn print_fuzzed_pkg(pkg: &FuzzedPackage, fuzz_opts: &FuzzOpts) {
+    info!("\n   fuzzed -- {}\n", pkg.name);
+    info!("      methods: {}", pkg.methods.join(", "));
+    if !pkg.skipped.is_empty() {
+        info!(
+            "      skipped: {} (their arguments can't be generated)",
+            pkg.skipped.join(", ")
+        );
+    }
+    match &pkg.failure {
+        None => info!(
+            "\n   Result: {}. {} runs, {} calls, {} rejected.",
+            Colour::Green.paint("OK"),
+            pkg.runs,
+            pkg.calls,
+            pkg.rejected
+        ),
+        Some(failure) => {
+            info!(
+                "\n   invariant {} violated after run {}, minimized from {} to {} calls:",
+                failure.invariant,
+                pkg.runs,
+                failure.original_len,
+                failure.calls.len()
+            );
+            for call in &failure.calls {
+                info!("      {call}");
+            }
+            info!(
+                "\n   reproduce it with `forc fuzz --seed {} --depth {}`",
+                fuzz_opts.seed, fuzz_opts.depth
+            );
+            info!("\n   Result: {}.", Colour::Red.paint("FAILED"));
+        }
+    }
+}
@@ -26,6 +26,10 @@ pub struct Command {
     /// Set the package name. Defaults to the directory name
     #[clap(long)]
     pub name: Option<String>,
+    /// Also create a Rust SDK integration test harness for the contract (tests/harness.rs) and
+    /// the Cargo.toml to run it with `cargo test`.
+    #[clap(long)]
+    pub with_tests: bool,
 }
 
 pub(crate) fn exec(command: Command) -> ForcResult<()> {
@@ -1,14 +1,19 @@
 pub mod addr2line;
+pub mod audit;
 pub mod build;
 pub mod check;
 pub mod clean;
 pub mod completions;
 pub mod contract_id;
+pub mod explain;
+pub mod fuzz;
 pub mod init;
 pub mod new;
 pub mod parse_bytecode;
+pub mod playground;
 pub mod plugins;
 pub mod predicate_root;
+pub mod repl;
 pub mod template;
 pub mod test;
 pub mod update;
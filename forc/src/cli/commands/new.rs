@@ -26,6 +26,10 @@ pub struct Command {
     /// Set the package name. Defaults to the directory name
     #[clap(long)]
     pub name: Option<String>,
+    /// Also create a Rust SDK integration test harness for the contract (tests/harness.rs) and
+    /// the Cargo.toml to run it with `cargo test`.
+    #[clap(long)]
+    pub with_tests: bool,
     /// The path at which the project directory will be created.
     pub path: String,
 }
@@ -41,6 +45,7 @@ pub(crate) fn exec(command: Command) -> ForcResult<()> {
         library,
         workspace,
         name,
+        with_tests,
         path,
     } = command;
 
@@ -78,6 +83,7 @@ pub(crate) fn exec(command: Command) -> ForcResult<()> {
         library,
         workspace,
         name,
+        with_tests,
     };
 
     init(init_cmd)?;
@@ -0,0 +1,47 @@
+use crate::ops::forc_playground;
+use clap::Parser;
+use forc_util::ForcResult;
+use std::path::PathBuf;
+
+/// Compile and run a throwaway script in the context of the current project.
+///
+/// The script must declare a `main` function, and is compiled without a program type
+/// declaration, which is added automatically. When the current project is a library or a contract,
+/// all of its items are imported into the script, along with the `CONTRACT_ID` of a contract.
+/// The script is executed in an in-memory VM and the value returned by `main`, as well as any
+/// logs, are printed.
+///
+/// With `--node-url`, the script is instead run against a node with `forc run`, which must be
+/// installed. Contracts can only be called this way, as the in-memory VM holds no contracts.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path to the file containing the script. If not specified, the script is read from stdin.
+    pub file: Option<PathBuf>,
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long)]
+    pub offline: bool,
+    /// Pretty-print the logs of the script.
+    #[clap(long = "pretty-print", short = 'r')]
+    pub pretty_print: bool,
+    /// The URL of a Fuel node, e.g. a local one, to run the script against with `forc run`
+    /// instead of the in-memory VM.
+    #[clap(long)]
+    pub node_url: Option<String>,
+    /// The ID of a deployed instance of the current contract, which the script can call through
+    /// the `INSTANCE_ID` constant.
+    ///
+    /// Accepts lowercase or uppercase hex, or a checksummed ID as printed by `forc deploy`.
+    #[clap(long, requires = "node_url")]
+    pub contract_id: Option<String>,
+    /// Do not sign the transaction sent to the node.
+    #[clap(long, requires = "node_url")]
+    pub unsigned: bool,
+}
+
+pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
+    forc_playground::playground(cmd).map_err(|e| e.into())
+}
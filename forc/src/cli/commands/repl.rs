@@ -0,0 +1,29 @@
+use crate::ops::forc_repl;
+use clap::Parser;
+use forc_util::ForcResult;
+
+/// Start an interactive session for evaluating Sway expressions.
+///
+/// Each entry is either an item (a function, type, trait, impl, constant or `use`), a statement,
+/// or an expression. Items and the variables declared by `let` statements are kept for the rest of
+/// the session. Std is only checked once per session. Each statement and expression is compiled
+/// into a script on its own and executed once in an in-memory VM, and the value of an expression
+/// is printed along with its type. When the current project is a library, all of its public items
+/// are in scope.
+///
+/// Variables whose values refer to the memory of the VM, e.g. a `Vec`, can only be used within the
+/// entry declaring them.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long)]
+    pub offline: bool,
+}
+
+pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
+    forc_repl::repl(cmd).map_err(|e| e.into())
+}
@@ -2,8 +2,10 @@ use crate::cli;
 use ansi_term::Colour;
 use clap::Parser;
 use forc_pkg as pkg;
+use forc_receipts::format_receipts;
 use forc_test::{TestFilter, TestRunnerCount, TestedPackage};
-use forc_util::{tx_utils::format_log_receipts, ForcError, ForcResult};
+use forc_util::{tx_utils::receipt_decoder, ForcError, ForcResult};
+use sway_core::language::ty::TestHook;
 use tracing::info;
 
 /// Run the Sway unit tests for the current project.
@@ -38,6 +40,11 @@ pub struct Command {
     /// Number of threads to utilize when running the tests. By default, this is the number of
     /// threads available in your system.
     pub test_threads: Option<usize>,
+    /// Store the values asserted with `std::test_utils::assert_snapshot` whose snapshots are
+    /// missing or don't match, in the `tests/snapshots` directory of their package, rather than
+    /// failing their tests.
+    #[clap(long)]
+    pub update_goldens: bool,
 }
 
 /// The set of options provided for controlling output of a test.
@@ -49,6 +56,13 @@ pub struct TestPrintOpts {
     /// Print `Log` and `LogData` receipts for tests.
     #[clap(long = "logs", short = 'l')]
     pub print_logs: bool,
+    /// Print the `Transfer` and `TransferOut` receipts of the coins transferred by each test.
+    #[clap(long)]
+    pub transfers: bool,
+    /// Print the storage fields of the tested contracts and their dependencies which were changed
+    /// by each test, with their values before and after the test.
+    #[clap(long)]
+    pub storage_diff: bool,
 }
 
 pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
@@ -58,12 +72,13 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
     };
 
     let test_print_opts = cmd.test_print.clone();
+    let update_goldens = cmd.update_goldens;
     let test_filter_phrase = cmd.filter.clone();
     let test_filter = test_filter_phrase.as_ref().map(|filter_phrase| TestFilter {
         filter_phrase,
         exact_match: cmd.filter_exact,
     });
-    let opts = opts_from_cmd(cmd);
+    let opts = opts_from_build(cmd.build);
     let built_tests = forc_test::build(opts)?;
     let start = std::time::Instant::now();
     let test_count = built_tests.test_count(test_filter.as_ref());
@@ -76,9 +91,20 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
         num_tests_ignored,
         formatted_test_count_string(&num_tests_ignored)
     );
-    let tested = built_tests.run(test_runner_count, test_filter)?;
+    let mut tested = built_tests.run(test_runner_count, test_filter)?;
     let duration = start.elapsed();
 
+    if update_goldens {
+        let updated = match &mut tested {
+            forc_test::Tested::Workspace(pkgs) => pkgs
+                .iter_mut()
+                .map(TestedPackage::update_snapshots)
+                .sum::<anyhow::Result<usize>>()?,
+            forc_test::Tested::Package(pkg) => pkg.update_snapshots()?,
+        };
+        info!("   Updated {updated} snapshot(s)");
+    }
+
     // Eventually we'll print this in a fancy manner, but this will do for testing.
     let all_tests_passed = match tested {
         forc_test::Tested::Workspace(pkgs) => {
@@ -108,6 +134,7 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
 fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> ForcResult<()> {
     let succeeded = pkg.tests.iter().filter(|t| t.passed()).count();
     let failed = pkg.tests.len() - succeeded;
+    let decoder = receipt_decoder(&pkg.built.program_abi)?;
     let mut failed_tests = Vec::new();
     for test in &pkg.tests {
         let test_passed = test.passed();
@@ -126,10 +153,30 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
         // If logs are enabled, print them.
         if test_print_opts.print_logs {
             let logs = &test.logs;
-            let formatted_logs = format_log_receipts(logs, test_print_opts.pretty_print)?;
+            let formatted_logs = format_receipts(logs, &decoder, test_print_opts.pretty_print)?;
             info!("{}", formatted_logs);
         }
 
+        if test_print_opts.transfers {
+            let formatted_transfers =
+                format_receipts(&test.transfers, &decoder, test_print_opts.pretty_print)?;
+            info!("{}", formatted_transfers);
+        }
+
+        if test_print_opts.storage_diff && !test.storage_changes.is_empty() {
+            info!("        storage changes:");
+            for change in &test.storage_changes {
+                info!(
+                    "          {}::{}: {} {} -> {}",
+                    change.contract,
+                    change.field,
+                    change.type_name,
+                    change.format_value(change.old.as_ref()),
+                    change.format_value(change.new.as_ref())
+                );
+            }
+        }
+
         // If the test is failing, save the test result for printing the details later on.
         if !test_passed {
             failed_tests.push(test);
@@ -147,11 +194,23 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
             let path = &*failed_test_details.file_path;
             let line_number = failed_test_details.line_number;
             let logs = &failed_test.logs;
-            let formatted_logs = format_log_receipts(logs, test_print_opts.pretty_print)?;
+            let formatted_logs = format_receipts(logs, &decoder, test_print_opts.pretty_print)?;
             info!(
                 "      - test {}, {:?}:{} ",
                 failed_test_name, path, line_number
             );
+            if let Some(failed_hook) = &failed_test.failed_hook {
+                match failed_hook.hook {
+                    TestHook::Invariant => info!(
+                        "        invariant {} violated after this test: {:?}",
+                        failed_hook.name, failed_hook.state
+                    ),
+                    _ => info!(
+                        "        {} failed: {:?}",
+                        failed_hook.name, failed_hook.state
+                    ),
+                }
+            }
             if let Some(revert_code) = failed_test.revert_code() {
                 // If we have a revert_code, try to get a known error signal
                 let mut failed_info_str = format!("        revert code: {revert_code:x}");
@@ -162,6 +221,20 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
                 }
                 info!("{failed_info_str}");
             }
+            for snapshot in failed_test.snapshots.iter().filter(|s| !s.matches()) {
+                let path = snapshot.path.display();
+                match &snapshot.stored {
+                    Some(stored) => {
+                        info!("        snapshot {path} doesn't match");
+                        info!("          stored: {}", stored.trim_end());
+                    }
+                    None => info!("        snapshot {path} is missing"),
+                }
+                info!("          value:  {}", snapshot.value.trim_end());
+            }
+            if failed_test.snapshots.iter().any(|s| !s.matches()) {
+                info!("        run `forc test --update-goldens` to store the values as snapshots");
+            }
             info!("        Logs: {}", formatted_logs);
         }
         info!("\n");
@@ -183,38 +256,49 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
     Ok(())
 }
 
-fn opts_from_cmd(cmd: Command) -> forc_test::Opts {
+/// The options to build the tests of a package with, from the build flags of the command.
+pub(crate) fn opts_from_build(build: cli::shared::Build) -> forc_test::Opts {
     forc_test::Opts {
         pkg: pkg::PkgOpts {
-            path: cmd.build.pkg.path,
-            offline: cmd.build.pkg.offline,
-            terse: cmd.build.pkg.terse,
-            locked: cmd.build.pkg.locked,
-            output_directory: cmd.build.pkg.output_directory,
-            json_abi_with_callpaths: cmd.build.pkg.json_abi_with_callpaths,
-            ipfs_node: cmd.build.pkg.ipfs_node.unwrap_or_default(),
+            path: build.pkg.path,
+            offline: build.pkg.offline,
+            terse: build.pkg.terse,
+            locked: build.pkg.locked,
+            output_directory: build.pkg.output_directory,
+            json_abi_with_callpaths: build.pkg.json_abi_with_callpaths,
+            ipfs_node: build.pkg.ipfs_node.unwrap_or_default(),
         },
         print: pkg::PrintOpts {
-            ast: cmd.build.print.ast,
-            dca_graph: cmd.build.print.dca_graph,
-            dca_graph_url_format: cmd.build.print.dca_graph_url_format,
-            finalized_asm: cmd.build.print.finalized_asm,
-            intermediate_asm: cmd.build.print.intermediate_asm,
-            ir: cmd.build.print.ir,
-            reverse_order: cmd.build.print.reverse_order,
+            ast: build.print.ast,
+            dca_graph: build.print.dca_graph,
+            dca_graph_url_format: build.print.dca_graph_url_format,
+            finalized_asm: build.print.finalized_asm,
+            intermediate_asm: build.print.intermediate_asm,
+            ir: build.print.ir,
+            smt: build.print.smt,
+            dump_fn: build.print.dump_fn,
+            reverse_order: build.print.reverse_order,
+            size_report: build.print.size_report,
+            gas_report: build.print.gas_report,
         },
-        time_phases: cmd.build.print.time_phases,
-        metrics_outfile: cmd.build.print.metrics_outfile,
+        time_phases: build.print.time_phases,
+        metrics_outfile: build.print.metrics_outfile,
         minify: pkg::MinifyOpts {
-            json_abi: cmd.build.minify.json_abi,
-            json_storage_slots: cmd.build.minify.json_storage_slots,
+            json_abi: build.minify.json_abi,
+            json_storage_slots: build.minify.json_storage_slots,
         },
-        build_profile: cmd.build.profile.build_profile,
-        release: cmd.build.profile.release,
-        error_on_warnings: cmd.build.profile.error_on_warnings,
-        binary_outfile: cmd.build.output.bin_file,
-        debug_outfile: cmd.build.output.debug_file,
-        build_target: cmd.build.build_target,
+        build_profile: build.profile.build_profile,
+        release: build.profile.release,
+        error_on_warnings: build.profile.error_on_warnings,
+        lints: build.profile.lints(),
+        optimization_level: build.profile.optimization_level,
+        ir_passes: build.profile.ir_passes.clone(),
+        verify_ir: build.profile.verify_ir,
+        emit_cfg: build.output.emit_cfg(),
+        experimental: build.profile.experimental,
+        binary_outfile: build.output.bin_file,
+        debug_outfile: build.output.debug_file,
+        build_target: build.build_target,
     }
 }
 
@@ -1,48 +1,81 @@
 use std::str::FromStr;
 
 use self::commands::{
-    addr2line, build, check, clean, completions, contract_id, init, new, parse_bytecode, plugins,
-    predicate_root, template, test, update,
+    addr2line, audit, build, check, clean, completions, contract_id, explain, fuzz, init, new,
+    parse_bytecode, playground, plugins, predicate_root, repl, template, test, update,
 };
 use addr2line::Command as Addr2LineCommand;
 use anyhow::anyhow;
+pub use audit::Command as AuditCommand;
 pub use build::Command as BuildCommand;
 pub use check::Command as CheckCommand;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 pub use clean::Command as CleanCommand;
 pub use completions::Command as CompletionsCommand;
 pub(crate) use contract_id::Command as ContractIdCommand;
+pub use explain::Command as ExplainCommand;
+pub(crate) use fuzz::Command as FuzzCommand;
 use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions};
-use forc_util::ForcResult;
+use forc_util::{
+    progress::{self, Verbosity},
+    ColorChoice, ForcResult,
+};
 pub use init::Command as InitCommand;
 pub use new::Command as NewCommand;
 use parse_bytecode::Command as ParseBytecodeCommand;
+pub(crate) use playground::Command as PlaygroundCommand;
 pub use plugins::Command as PluginsCommand;
 pub(crate) use predicate_root::Command as PredicateRootCommand;
+pub(crate) use repl::Command as ReplCommand;
 pub use template::Command as TemplateCommand;
 pub use test::Command as TestCommand;
 use tracing::metadata::LevelFilter;
 pub use update::Command as UpdateCommand;
 
+use crate::ops::forc_version;
+
 mod commands;
-mod plugin;
+pub(crate) mod plugin;
 pub mod shared;
 
 #[derive(Debug, Parser)]
-#[clap(name = "forc", about = "Fuel Orchestrator", version)]
+#[clap(
+    name = "forc",
+    about = "Fuel Orchestrator",
+    version,
+    disable_version_flag = true,
+    arg_required_else_help = true
+)]
 struct Opt {
     /// The command to run
     #[clap(subcommand)]
-    command: Forc,
+    command: Option<Forc>,
+
+    /// Print version information
+    #[clap(short = 'V', long)]
+    version: bool,
 
-    /// Use verbose output
+    /// Print the versions of the toolchain, its supported ABI and VM opcode set versions and its
+    /// experimental features as JSON. Requires `--version`.
+    #[clap(long, requires = "version")]
+    json: bool,
+
+    /// Use verbose output, including the phases each package goes through when building
     #[clap(short, long, parse(from_occurrences), global = true)]
     verbose: u8,
 
+    /// Only print warnings and errors, not the progress of builds
+    #[clap(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
     /// Silence all output
     #[clap(short, long, global = true)]
     silent: bool,
 
+    /// When to color the errors and warnings: `auto`, `always` or `never`
+    #[clap(long, value_enum, default_value_t, global = true)]
+    color: ColorChoice,
+
     /// Set the log level
     #[clap(short='L', long, global = true, parse(try_from_str = LevelFilter::from_str))]
     log_level: Option<LevelFilter>,
@@ -52,14 +85,18 @@ struct Opt {
 enum Forc {
     #[clap(name = "addr2line")]
     Addr2Line(Addr2LineCommand),
+    Audit(AuditCommand),
     #[clap(visible_alias = "b")]
     Build(BuildCommand),
     Check(CheckCommand),
     Clean(CleanCommand),
     Completions(CompletionsCommand),
+    Explain(ExplainCommand),
+    Fuzz(FuzzCommand),
     New(NewCommand),
     Init(InitCommand),
     ParseBytecode(ParseBytecodeCommand),
+    Playground(PlaygroundCommand),
     #[clap(visible_alias = "t")]
     Test(TestCommand),
     Update(UpdateCommand),
@@ -67,6 +104,7 @@ enum Forc {
     Template(TemplateCommand),
     ContractId(ContractIdCommand),
     PredicateRoot(PredicateRootCommand),
+    Repl(ReplCommand),
     /// This is a catch-all for unknown subcommands and their arguments.
     ///
     /// When we receive an unknown subcommand, we check for a plugin exe named
@@ -89,22 +127,43 @@ pub async fn run_cli() -> ForcResult<()> {
     };
 
     init_tracing_subscriber(tracing_options);
+    forc_util::set_color_choice(opt.color);
+    progress::set_verbosity(if opt.quiet || opt.silent {
+        Verbosity::Quiet
+    } else if opt.verbose > 0 {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    });
+
+    if opt.version {
+        return forc_version::print_version(opt.json).map_err(|e| e.into());
+    }
+    let Some(command) = opt.command else {
+        Opt::command().print_help()?;
+        return Ok(());
+    };
 
-    match opt.command {
+    match command {
         Forc::Addr2Line(command) => addr2line::exec(command),
+        Forc::Audit(command) => audit::exec(command),
         Forc::Build(command) => build::exec(command),
         Forc::Check(command) => check::exec(command),
         Forc::Clean(command) => clean::exec(command),
         Forc::Completions(command) => completions::exec(command),
+        Forc::Explain(command) => explain::exec(command),
+        Forc::Fuzz(command) => fuzz::exec(command),
         Forc::Init(command) => init::exec(command),
         Forc::New(command) => new::exec(command),
         Forc::ParseBytecode(command) => parse_bytecode::exec(command),
+        Forc::Playground(command) => playground::exec(command),
         Forc::Plugins(command) => plugins::exec(command),
         Forc::Test(command) => test::exec(command),
         Forc::Update(command) => update::exec(command).await,
         Forc::Template(command) => template::exec(command),
         Forc::ContractId(command) => contract_id::exec(command),
         Forc::PredicateRoot(command) => predicate_root::exec(command),
+        Forc::Repl(command) => repl::exec(command),
         Forc::Plugin(args) => {
             let output = plugin::execute_external_subcommand(args)?;
             let code = output
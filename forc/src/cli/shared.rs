@@ -2,7 +2,8 @@
 
 use clap::{Args, Parser};
 use forc_pkg::source::IPFSNode;
-use sway_core::BuildTarget;
+use std::collections::BTreeMap;
+use sway_core::{BuildTarget, ExperimentalFeature, LintLevel, OptLevel};
 
 /// Args that can be shared between all commands that `build` a package. E.g. `build`, `test`,
 /// `deploy`.
@@ -32,6 +33,26 @@ pub struct BuildOutput {
     /// If set, outputs source file mapping in JSON format
     #[clap(long = "output-debug", short = 'g')]
     pub debug_file: Option<String>,
+    /// Write the results of the analysis of each built package to the output directory.
+    ///
+    /// Can be specified multiple times.
+    #[clap(long, value_enum)]
+    pub emit: Vec<Emit>,
+}
+
+impl BuildOutput {
+    /// Whether the control-flow and call graphs are emitted.
+    pub fn emit_cfg(&self) -> bool {
+        self.emit.contains(&Emit::Cfg)
+    }
+}
+
+/// The results of the analysis of a package which can be written alongside its bytecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Emit {
+    /// The control-flow graph of each function, to `<name>-cfg.dot` and `<name>-cfg.json`, and
+    /// the call graph of the program, to `<name>-call_graph.dot` and `<name>-call_graph.json`.
+    Cfg,
 }
 
 /// Build profile options.
@@ -47,9 +68,68 @@ pub struct BuildProfile {
     ///  If --build-profile is also provided, forc omits this flag and uses provided build-profile.
     #[clap(long)]
     pub release: bool,
-    /// Treat warnings as errors.
-    #[clap(long)]
+    /// Treat warnings as errors, except those of lints with an explicit level.
+    #[clap(long = "deny-warnings", alias = "error-on-warnings")]
     pub error_on_warnings: bool,
+    /// Report the warnings of a lint as errors, e.g. `-D unused_imports`.
+    ///
+    /// Can be specified multiple times.
+    #[clap(long = "deny", short = 'D', value_name = "LINT")]
+    pub deny: Vec<String>,
+    /// Don't report the warnings of a lint.
+    ///
+    /// Can be specified multiple times.
+    #[clap(long = "allow", short = 'A', value_name = "LINT")]
+    pub allow: Vec<String>,
+    /// Report the warnings of a lint as warnings, even with `--deny-warnings`.
+    ///
+    /// Can be specified multiple times.
+    #[clap(long = "warn", short = 'W', value_name = "LINT")]
+    pub warn: Vec<String>,
+    /// Enable an experimental language feature, in addition to those enabled in the manifests.
+    ///
+    /// Can be specified multiple times.
+    #[clap(long, value_enum)]
+    pub experimental: Vec<ExperimentalFeature>,
+    /// How much to optimize the IR, from `0` for not at all to `2`, e.g. `-O2`.
+    ///
+    /// Overrides the optimization level of the build profile.
+    #[clap(long, short = 'O', value_name = "LEVEL", value_parser = parse_optimization_level)]
+    pub optimization_level: Option<OptLevel>,
+    /// The IR passes to run, by name and in order, instead of those of the optimization level,
+    /// e.g. `--ir-passes=mem2reg,inline_module,dce`.
+    ///
+    /// Useful to find the pass which miscompiles a program.
+    #[clap(long, value_delimiter = ',', value_name = "PASSES")]
+    pub ir_passes: Option<Vec<String>>,
+    /// Verify the IR after each optimization pass which modifies it, reporting the first pass
+    /// which produces invalid IR.
+    #[clap(long)]
+    pub verify_ir: bool,
+}
+
+fn parse_optimization_level(level: &str) -> Result<OptLevel, String> {
+    level
+        .parse::<u8>()
+        .map_err(|_| format!("invalid optimization level `{level}`, expected 0, 1 or 2"))?
+        .try_into()
+}
+
+impl BuildProfile {
+    /// The levels of the lints given on the command line, by lint name.
+    ///
+    /// A lint given with more than one level is denied over being warned about, and warned about
+    /// over being allowed.
+    pub fn lints(&self) -> BTreeMap<String, LintLevel> {
+        let allow = self.allow.iter().map(|lint| (lint, LintLevel::Allow));
+        let warn = self.warn.iter().map(|lint| (lint, LintLevel::Warn));
+        let deny = self.deny.iter().map(|lint| (lint, LintLevel::Deny));
+        allow
+            .chain(warn)
+            .chain(deny)
+            .map(|(lint, level)| (lint.clone(), level))
+            .collect()
+    }
 }
 
 /// Options related to printing stages of compiler output.
@@ -83,13 +163,46 @@ pub struct Print {
     /// Print the generated Sway IR (Intermediate Representation).
     #[clap(long)]
     pub ir: bool,
+    /// Print the pure functions of the optimized IR in SMT-LIB 2, along with the conditions of
+    /// the paths through them, for formal-methods tools to check their assertions.
+    ///
+    /// Functions which access memory or storage, or call other functions, are skipped.
+    #[clap(long)]
+    pub smt: bool,
+    /// Print the IR, the finalized ASM and the range in the bytecode of each function whose name
+    /// matches this pattern, e.g. `--dump-fn transfer` or `--dump-fn "get_*"`.
+    ///
+    /// Generic functions are matched by name, so each of their instances is printed.
+    #[clap(long, value_name = "PATTERN")]
+    pub dump_fn: Option<String>,
     /// Output the time elapsed over each part of the compilation process.
     #[clap(long)]
     pub time_phases: bool,
     /// Output build errors and warnings in reverse order.
     #[clap(long)]
     pub reverse_order: bool,
-    /// Output compilation metrics into file.
+    /// Print the number of bytes and instructions each function contributes to the bytecode,
+    /// largest first, and write them to a `<name>-size_report.json` file in the output directory.
+    ///
+    /// Each instance of a generic function is reported separately, along with its signature.
+    #[clap(long)]
+    pub size_report: bool,
+    /// Print a static estimate of the gas used by each ABI method, or by the entry function of a
+    /// script or predicate, and write it to a `<name>-gas_report.json` file in the output
+    /// directory.
+    ///
+    /// The estimate is the gas used along the most expensive path through the method. Methods
+    /// with loops or recursion are reported as unbounded, and the size-dependent costs of
+    /// instructions such as `MCP` and of calls to other contracts are not included.
+    #[clap(long)]
+    pub gas_report: bool,
+    /// Output the compilation metrics of every compiled package into file, as a JSON object
+    /// keyed by package name.
+    ///
+    /// Each package's entry holds a hash of its manifest and sources, its bytecode size, the time
+    /// taken to compile it and by each phase and IR pass of its compilation, the peak memory
+    /// usage of the whole process, which packages compiled in parallel share, and the number of types and declarations it held. A path ending in
+    /// `.html` gets a flame view of the timings, along with the JSON report.
     #[clap(long)]
     pub metrics_outfile: Option<String>,
 }
@@ -138,3 +251,30 @@ pub struct Minify {
     #[clap(long)]
     pub json_storage_slots: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ir_passes_and_optimization_level_are_parsed() {
+        let build =
+            Build::try_parse_from(["forc", "--ir-passes=mem2reg,inline_module,dce"]).unwrap();
+        assert_eq!(
+            build.profile.ir_passes,
+            Some(vec![
+                "mem2reg".to_string(),
+                "inline_module".to_string(),
+                "dce".to_string()
+            ])
+        );
+        assert_eq!(build.profile.optimization_level, None);
+
+        let build = Build::try_parse_from(["forc", "-O2"]).unwrap();
+        assert_eq!(build.profile.optimization_level, Some(OptLevel::O2));
+        assert_eq!(build.profile.ir_passes, None);
+
+        assert!(Build::try_parse_from(["forc", "-O3"]).is_err());
+        assert!(Build::try_parse_from(["forc", "-Ofast"]).is_err());
+    }
+}
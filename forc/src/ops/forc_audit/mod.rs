@@ -0,0 +1,125 @@
+//! Runs a set of security [rules] over the typed AST of a package and reports what they find,
+//! either as text or as SARIF for code scanning tools.
+
+mod rules;
+
+use crate::{
+    cli::{AuditCommand, CheckCommand},
+    ops::forc_check,
+};
+use anyhow::{anyhow, bail, Result};
+use forc_tracing::{println_green, println_yellow_err};
+use forc_util::sarif;
+use rules::{Rule, RULES};
+use std::path::PathBuf;
+use sway_core::{BuildTarget, Engines};
+use sway_types::Span;
+
+/// An issue found by a rule.
+pub struct Finding {
+    /// The id of the rule which found the issue.
+    pub rule_id: &'static str,
+    pub message: String,
+    pub span: Span,
+}
+
+pub fn audit(command: AuditCommand) -> Result<()> {
+    if command.list_rules {
+        for rule in RULES {
+            println!("{:<24} {}", rule.id(), rule.description());
+        }
+        return Ok(());
+    }
+    let rules = select_rules(command.rules.as_deref())?;
+
+    let engines = Engines::default();
+    let check_command = CheckCommand {
+        build_target: BuildTarget::default(),
+        path: command.path.clone(),
+        offline_mode: command.offline_mode,
+        locked: command.locked,
+        terse_mode: false,
+        disable_tests: true,
+        ipfs_node: command.ipfs_node.clone(),
+        sarif: None,
+    };
+    let Some(program) = forc_check::check(check_command, &engines)?.value else {
+        bail!("unable to type check");
+    };
+
+    let findings = rules
+        .iter()
+        .flat_map(|rule| rule.check(&engines, &program))
+        .collect::<Vec<_>>();
+    for finding in &findings {
+        println_yellow_err(&format!(
+            "warning[{}]: {}",
+            finding.rule_id, finding.message
+        ));
+        if let Some((path, start, _end)) = sarif::location(engines.se(), &finding.span) {
+            println_yellow_err(&format!(
+                "  --> {}:{}:{}",
+                path.display(),
+                start.line,
+                start.col
+            ));
+        }
+    }
+
+    if let Some(sarif_path) = &command.sarif {
+        let project_dir = match &command.path {
+            Some(path) => PathBuf::from(path),
+            None => std::env::current_dir()?,
+        };
+        let sarif = rules
+            .iter()
+            .fold(sarif::Collector::new("forc-audit"), |sarif, rule| {
+                sarif.with_rule(rule.id(), rule.description())
+            });
+        for finding in &findings {
+            sarif.record_warning(
+                engines.se(),
+                finding.rule_id,
+                &finding.message,
+                &finding.span,
+            );
+        }
+        sarif.write(sarif_path, &project_dir)?;
+    }
+
+    match findings.len() {
+        0 => println_green("  No issues found."),
+        n => {
+            println_yellow_err(&format!(
+                "  Found {n} {}.",
+                if n > 1 { "issues" } else { "issue" }
+            ));
+            if command.deny_findings {
+                bail!("the audit found issues");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The rules with the given ids, or all of them if there are none.
+fn select_rules(ids: Option<&[String]>) -> Result<Vec<&'static dyn Rule>> {
+    let Some(ids) = ids else {
+        return Ok(RULES.to_vec());
+    };
+    ids.iter()
+        .map(|id| {
+            RULES
+                .iter()
+                .find(|rule| rule.id() == id)
+                .copied()
+                .ok_or_else(|| {
+                    let rule_ids = RULES.iter().map(|rule| rule.id()).collect::<Vec<_>>();
+                    anyhow!(
+                        "unknown rule `{id}`, the available rules are: {}",
+                        rule_ids.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,529 @@
+//! The security rules of `forc audit`.
+//!
+//! Each rule looks for one kind of issue in the typed AST of a program. The rules are heuristics
+//! working on the functions of the audited package: they may report code which is fine, e.g.
+//! because a check happens in a way they don't recognise, and they don't follow calls into other
+//! contracts.
+//!
+//! Adding a rule means implementing [Rule] for it and adding it to [RULES].
+
+use super::Finding;
+use std::collections::HashSet;
+use sway_core::{
+    decl_engine::id::DeclId,
+    language::{ty, Purity},
+    Engines,
+};
+use sway_types::Spanned;
+
+/// A security rule checking the typed AST of a program.
+pub trait Rule: Sync {
+    /// The identifier of the rule, used to select it and to tag its findings.
+    fn id(&self) -> &'static str;
+
+    /// A one line description of the issues the rule finds.
+    fn description(&self) -> &'static str;
+
+    /// Returns the issues found in `program`.
+    fn check(&self, engines: &Engines, program: &ty::TyProgram) -> Vec<Finding>;
+}
+
+/// All of the rules, in the order in which they are run.
+pub const RULES: &[&dyn Rule] = &[
+    &UncheckedTransfer,
+    &MissingAccessControl,
+    &TxOriginAuth,
+    &UnboundedStorageLoop,
+];
+
+/// The `std` functions sending coins out of the contract.
+const TRANSFER_FNS: &[&str] = &[
+    "transfer",
+    "transfer_to_address",
+    "force_transfer_to_contract",
+];
+
+/// The `std` functions which revert unless a condition holds.
+const CHECK_FNS: &[&str] = &["require", "assert", "assert_eq", "revert", "revert_with"];
+
+/// The functions identifying or restricting the caller, from `std` and the common libraries.
+const CALLER_FNS: &[&str] = &["msg_sender", "caller_contract_id", "only_owner"];
+
+/// Finds ABI methods sending coins to a recipient or of an amount given by their caller, without
+/// checking anything first.
+struct UncheckedTransfer;
+
+impl Rule for UncheckedTransfer {
+    fn id(&self) -> &'static str {
+        "unchecked-transfer"
+    }
+
+    fn description(&self) -> &'static str {
+        "ABI methods transferring caller-chosen amounts or to caller-chosen recipients unchecked"
+    }
+
+    fn check(&self, _engines: &Engines, program: &ty::TyProgram) -> Vec<Finding> {
+        let mut findings = vec![];
+        for method in abi_methods(program) {
+            let params = method
+                .parameters
+                .iter()
+                .map(|param| param.name.as_str())
+                .collect::<HashSet<_>>();
+            // Any condition preceding the transfer, in an `if` or an assertion, counts as a check.
+            let mut checked = false;
+            visit_code_block(&method.body, &mut |expr| match &expr.expression {
+                ty::TyExpressionVariant::IfExp { .. } => checked = true,
+                ty::TyExpressionVariant::FunctionApplication {
+                    call_path,
+                    arguments,
+                    ..
+                } => {
+                    let fn_name = call_path.suffix.as_str();
+                    if CHECK_FNS.contains(&fn_name) {
+                        checked = true;
+                    } else if !checked
+                        && TRANSFER_FNS.contains(&fn_name)
+                        && arguments
+                            .iter()
+                            .any(|(_, arg)| uses_any_variable(arg, &params))
+                    {
+                        findings.push(Finding {
+                            rule_id: self.id(),
+                            message: format!(
+                                "`{fn_name}` sends coins as the caller of `{}` asks, without \
+                                checking the amount or the recipient first",
+                                method.name
+                            ),
+                            span: expr.span.clone(),
+                        });
+                    }
+                }
+                _ => (),
+            });
+        }
+        findings
+    }
+}
+
+/// Finds ABI methods writing to storage which anyone can call.
+struct MissingAccessControl;
+
+impl Rule for MissingAccessControl {
+    fn id(&self) -> &'static str {
+        "missing-access-control"
+    }
+
+    fn description(&self) -> &'static str {
+        "ABI methods writing to storage without checking who calls them"
+    }
+
+    fn check(&self, engines: &Engines, program: &ty::TyProgram) -> Vec<Finding> {
+        abi_methods(program)
+            .iter()
+            .filter(|method| matches!(method.purity, Purity::Writes | Purity::ReadsWrites))
+            .filter(|method| !identifies_caller(engines, &method.body, &mut HashSet::new()))
+            .map(|method| Finding {
+                rule_id: self.id(),
+                message: format!(
+                    "`{}` writes to storage but never checks its caller, e.g. with `msg_sender()`",
+                    method.name
+                ),
+                span: method.name.span(),
+            })
+            .collect()
+    }
+}
+
+/// Finds contracts authorizing callers by the owners of the transaction's inputs, which is the
+/// Fuel equivalent of Solidity's `tx.origin`.
+struct TxOriginAuth;
+
+impl Rule for TxOriginAuth {
+    fn id(&self) -> &'static str {
+        "tx-origin"
+    }
+
+    fn description(&self) -> &'static str {
+        "contracts identifying their caller by the owners of the transaction's inputs"
+    }
+
+    fn check(&self, engines: &Engines, program: &ty::TyProgram) -> Vec<Finding> {
+        if !matches!(program.kind, ty::TyProgramKind::Contract { .. }) {
+            return vec![];
+        }
+        let mut findings = vec![];
+        for function in functions(engines, program) {
+            visit_code_block(&function.body, &mut |expr| {
+                if let ty::TyExpressionVariant::FunctionApplication { call_path, .. } =
+                    &expr.expression
+                {
+                    if call_path.suffix.as_str() == "input_owner" {
+                        findings.push(Finding {
+                            rule_id: self.id(),
+                            message: "`input_owner` is whoever signed the transaction, not the \
+                                caller of the contract, which may be another contract acting on \
+                                their behalf; identify the caller with `msg_sender()` instead"
+                                .to_string(),
+                            span: expr.span.clone(),
+                        });
+                    }
+                }
+            });
+        }
+        findings
+    }
+}
+
+/// Finds loops iterating as many times as a value read from storage, e.g. the length of a
+/// `StorageVec`, which may grow until running the loop exceeds the gas limit.
+struct UnboundedStorageLoop;
+
+impl Rule for UnboundedStorageLoop {
+    fn id(&self) -> &'static str {
+        "unbounded-storage-loop"
+    }
+
+    fn description(&self) -> &'static str {
+        "loops whose number of iterations depends on storage"
+    }
+
+    fn check(&self, engines: &Engines, program: &ty::TyProgram) -> Vec<Finding> {
+        let mut findings = vec![];
+        for function in functions(engines, program) {
+            // The locals holding values read from storage, wherever they are declared.
+            let mut from_storage = HashSet::new();
+            visit_code_block_decls(&function.body, &mut |var_decl| {
+                if reads_storage(&var_decl.body) {
+                    from_storage.insert(var_decl.name.as_str().to_string());
+                }
+            });
+            let from_storage = from_storage
+                .iter()
+                .map(String::as_str)
+                .collect::<HashSet<_>>();
+            visit_code_block(&function.body, &mut |expr| {
+                if let ty::TyExpressionVariant::WhileLoop { condition, .. } = &expr.expression {
+                    if reads_storage(condition) || uses_any_variable(condition, &from_storage) {
+                        findings.push(Finding {
+                            rule_id: self.id(),
+                            message: "the number of iterations of this loop depends on storage, \
+                                which may grow until the loop runs out of gas"
+                                .to_string(),
+                            span: condition.span.clone(),
+                        });
+                    }
+                }
+            });
+        }
+        findings
+    }
+}
+
+/// The methods of the ABI implemented by a contract.
+fn abi_methods(program: &ty::TyProgram) -> &[ty::TyFunctionDecl] {
+    match &program.kind {
+        ty::TyProgramKind::Contract { abi_entries } => abi_entries,
+        _ => &[],
+    }
+}
+
+/// The free functions and the methods of all of the modules of the program.
+fn functions(engines: &Engines, program: &ty::TyProgram) -> Vec<ty::TyFunctionDecl> {
+    let decl_engine = engines.de();
+    std::iter::once(&program.root)
+        .chain(
+            program
+                .root
+                .submodules_recursive()
+                .map(|(_, submodule)| &submodule.module),
+        )
+        .flat_map(|module| &module.all_nodes)
+        .flat_map(|node| match &node.content {
+            ty::TyAstNodeContent::Declaration(ty::TyDecl::FunctionDecl(ty::FunctionDecl {
+                decl_id,
+                ..
+            })) => vec![decl_engine.get_function(decl_id)],
+            ty::TyAstNodeContent::Declaration(ty::TyDecl::ImplTrait(ty::ImplTrait {
+                decl_id,
+                ..
+            })) => decl_engine
+                .get_impl_trait(decl_id)
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    ty::TyImplItem::Fn(fn_ref) => Some(decl_engine.get_function(fn_ref)),
+                    ty::TyImplItem::Constant(_) => None,
+                })
+                .collect(),
+            _ => vec![],
+        })
+        .collect()
+}
+
+/// Whether the code block calls one of the [CALLER_FNS], directly or through other functions.
+fn identifies_caller(
+    engines: &Engines,
+    code_block: &ty::TyCodeBlock,
+    visited: &mut HashSet<DeclId<ty::TyFunctionDecl>>,
+) -> bool {
+    let mut callees = vec![];
+    let mut found = false;
+    visit_code_block(code_block, &mut |expr| {
+        if let ty::TyExpressionVariant::FunctionApplication {
+            call_path, fn_ref, ..
+        } = &expr.expression
+        {
+            if CALLER_FNS.contains(&call_path.suffix.as_str()) {
+                found = true;
+            } else if visited.insert(*fn_ref.id()) {
+                callees.push(fn_ref.clone());
+            }
+        }
+    });
+    found
+        || callees.iter().any(|fn_ref| {
+            let callee = engines.de().get_function(fn_ref);
+            identifies_caller(engines, &callee.body, visited)
+        })
+}
+
+/// Whether the expression reads from storage.
+fn reads_storage(expr: &ty::TyExpression) -> bool {
+    let mut found = false;
+    visit_expr(expr, &mut |expr| {
+        found |= matches!(expr.expression, ty::TyExpressionVariant::StorageAccess(_));
+    });
+    found
+}
+
+/// Whether the expression uses one of the named variables.
+fn uses_any_variable(expr: &ty::TyExpression, names: &HashSet<&str>) -> bool {
+    let mut found = false;
+    visit_expr(expr, &mut |expr| {
+        if let ty::TyExpressionVariant::VariableExpression { name, .. } = &expr.expression {
+            found |= names.contains(name.as_str());
+        }
+    });
+    found
+}
+
+/// Calls `f` with every expression of the code block, parents before their children.
+fn visit_code_block(code_block: &ty::TyCodeBlock, f: &mut dyn FnMut(&ty::TyExpression)) {
+    for node in &code_block.contents {
+        match &node.content {
+            ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                visit_expr(&var_decl.body, f)
+            }
+            ty::TyAstNodeContent::Expression(expr)
+            | ty::TyAstNodeContent::ImplicitReturnExpression(expr) => visit_expr(expr, f),
+            ty::TyAstNodeContent::Declaration(_) | ty::TyAstNodeContent::SideEffect(_) => (),
+        }
+    }
+}
+
+/// Calls `f` with every variable declaration of the code block, including those of nested blocks.
+fn visit_code_block_decls(code_block: &ty::TyCodeBlock, f: &mut dyn FnMut(&ty::TyVariableDecl)) {
+    let mut visit_decls = |code_block: &ty::TyCodeBlock| {
+        for node in &code_block.contents {
+            if let ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) =
+                &node.content
+            {
+                f(var_decl);
+            }
+        }
+    };
+    visit_decls(code_block);
+    // Every nested block is visited exactly once by `visit_code_block`.
+    visit_code_block(code_block, &mut |expr| match &expr.expression {
+        ty::TyExpressionVariant::CodeBlock(code_block)
+        | ty::TyExpressionVariant::WhileLoop {
+            body: code_block, ..
+        } => visit_decls(code_block),
+        _ => (),
+    });
+}
+
+/// Calls `f` with the expression and every expression within it, parents before their children.
+///
+/// Doesn't descend into the bodies of called functions.
+fn visit_expr(expr: &ty::TyExpression, f: &mut dyn FnMut(&ty::TyExpression)) {
+    use ty::TyExpressionVariant::*;
+    f(expr);
+    match &expr.expression {
+        Literal(_)
+        | ConstantExpression { .. }
+        | VariableExpression { .. }
+        | FunctionParameter
+        | StorageAccess(_)
+        | AbiName(_)
+        | Break
+        | Continue => (),
+        FunctionApplication {
+            arguments,
+            contract_call_params,
+            ..
+        } => {
+            for expr in contract_call_params.values() {
+                visit_expr(expr, f);
+            }
+            for (_, arg) in arguments {
+                visit_expr(arg, f);
+            }
+        }
+        LazyOperator { lhs, rhs, .. } => {
+            visit_expr(lhs, f);
+            visit_expr(rhs, f);
+        }
+        Tuple { fields: exprs }
+        | Array {
+            contents: exprs, ..
+        } => {
+            for expr in exprs {
+                visit_expr(expr, f);
+            }
+        }
+        ArrayIndex { prefix, index } => {
+            visit_expr(prefix, f);
+            visit_expr(index, f);
+        }
+        StructExpression { fields, .. } => {
+            for field in fields {
+                visit_expr(&field.value, f);
+            }
+        }
+        CodeBlock(code_block) => visit_code_block(code_block, f),
+        MatchExp { desugared, .. } => visit_expr(desugared, f),
+        IfExp {
+            condition,
+            then,
+            r#else,
+        } => {
+            visit_expr(condition, f);
+            visit_expr(then, f);
+            if let Some(r#else) = r#else {
+                visit_expr(r#else, f);
+            }
+        }
+        AsmExpression { registers, .. } => {
+            for initializer in registers.iter().filter_map(|reg| reg.initializer.as_ref()) {
+                visit_expr(initializer, f);
+            }
+        }
+        StructFieldAccess { prefix: expr, .. }
+        | TupleElemAccess { prefix: expr, .. }
+        | AbiCast { address: expr, .. }
+        | EnumTag { exp: expr }
+        | UnsafeDowncast { exp: expr, .. }
+        | Return(expr) => visit_expr(expr, f),
+        EnumInstantiation { contents, .. } => {
+            if let Some(contents) = contents {
+                visit_expr(contents, f);
+            }
+        }
+        IntrinsicFunction(intrinsic) => {
+            for arg in &intrinsic.arguments {
+                visit_expr(arg, f);
+            }
+        }
+        WhileLoop { condition, body } => {
+            visit_expr(condition, f);
+            visit_code_block(body, f);
+        }
+        Reassignment(reassignment) => visit_expr(&reassignment.rhs, f),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cli::CheckCommand, ops::forc_check};
+    use std::{path::PathBuf, sync::OnceLock};
+    use sway_core::BuildTarget;
+
+    /// The ids of the rules and the ABI methods of `test_data/audit_contract` of what they find.
+    /// Each rule has one method it should flag and one it shouldn't.
+    fn findings() -> &'static [(&'static str, String)] {
+        static FINDINGS: OnceLock<Vec<(&'static str, String)>> = OnceLock::new();
+        FINDINGS.get_or_init(|| {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("test_data")
+                .join("audit_contract");
+            let check_command = CheckCommand {
+                build_target: BuildTarget::default(),
+                path: Some(path.to_string_lossy().to_string()),
+                offline_mode: true,
+                locked: false,
+                terse_mode: true,
+                disable_tests: true,
+                ipfs_node: None,
+                sarif: None,
+            };
+            let engines = Engines::default();
+            let program = forc_check::check(check_command, &engines)
+                .unwrap()
+                .value
+                .unwrap();
+            RULES
+                .iter()
+                .flat_map(|rule| rule.check(&engines, &program))
+                .map(|finding| {
+                    let method = abi_methods(&program)
+                        .iter()
+                        .find(|method| {
+                            method.span.start() <= finding.span.start()
+                                && finding.span.end() <= method.span.end()
+                        })
+                        .expect("the findings are in ABI methods");
+                    (finding.rule_id, method.name.to_string())
+                })
+                .collect()
+        })
+    }
+
+    fn finds(rule: &dyn Rule, method: &str) -> bool {
+        findings()
+            .iter()
+            .any(|(rule_id, name)| *rule_id == rule.id() && name == method)
+    }
+
+    #[test]
+    fn unchecked_transfers_are_found() {
+        assert!(finds(&UncheckedTransfer, "withdraw_unchecked"));
+    }
+
+    #[test]
+    fn transfers_after_a_check_are_not_found() {
+        assert!(!finds(&UncheckedTransfer, "withdraw_checked"));
+    }
+
+    #[test]
+    fn storage_writes_by_anyone_are_found() {
+        assert!(finds(&MissingAccessControl, "set_value_unrestricted"));
+    }
+
+    #[test]
+    fn storage_writes_checking_the_caller_are_not_found() {
+        assert!(!finds(&MissingAccessControl, "set_value_restricted"));
+    }
+
+    #[test]
+    fn input_owner_auth_is_found() {
+        assert!(finds(&TxOriginAuth, "is_owner_by_input"));
+    }
+
+    #[test]
+    fn msg_sender_auth_is_not_found() {
+        assert!(!finds(&TxOriginAuth, "is_owner_by_sender"));
+    }
+
+    #[test]
+    fn loops_bounded_by_storage_are_found() {
+        assert!(finds(&UnboundedStorageLoop, "sum_all"));
+    }
+
+    #[test]
+    fn loops_bounded_by_arguments_are_not_found() {
+        assert!(!finds(&UnboundedStorageLoop, "sum_first"));
+    }
+}
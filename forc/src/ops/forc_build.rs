@@ -1,11 +1,92 @@
 use crate::cli::BuildCommand;
+use anyhow::{bail, Context, Result};
 use forc_pkg as pkg;
-use forc_util::ForcResult;
+use forc_util::{print_on_failure, sarif, ForcResult};
+use std::{fs, path::PathBuf, sync::Arc};
+use sway_core::{compile_ir_to_bytecode, source_map::SourceMap, BuildConfig, Engines};
+use sway_utils::PerformanceData;
+use tracing::info;
 
 pub fn build(cmd: BuildCommand) -> ForcResult<pkg::Built> {
-    let opts = opts_from_cmd(cmd);
-    let built = pkg::build_with_options(opts)?;
-    Ok(built)
+    let sarif_path = cmd.sarif.clone();
+    let project_dir = match &cmd.build.pkg.path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let sarif = sarif_path
+        .is_some()
+        .then(|| Arc::new(sarif::Collector::new("forc")));
+    let opts = pkg::BuildOpts {
+        sarif: sarif.clone(),
+        ..opts_from_cmd(cmd)
+    };
+    // The diagnostics are written whether or not the build succeeds.
+    let built = pkg::build_with_options(opts);
+    if let (Some(sarif_path), Some(sarif)) = (sarif_path, sarif) {
+        sarif.write(&sarif_path, &project_dir)?;
+    }
+    Ok(built?)
+}
+
+/// Compiles the IR in the file given with `--ir-only` to bytecode.
+pub fn build_ir(cmd: BuildCommand) -> Result<()> {
+    let ir_path = cmd
+        .ir_only
+        .clone()
+        .expect("`--ir-only` must be given to build IR");
+    let ir_path = ir_path
+        .canonicalize()
+        .with_context(|| format!("failed to find {}", ir_path.display()))?;
+    let ir_source = fs::read_to_string(&ir_path)
+        .with_context(|| format!("failed to read {}", ir_path.display()))?;
+    let ir_dir = ir_path
+        .parent()
+        .expect("a canonical file path has a parent")
+        .to_path_buf();
+    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+        ir_path.clone(),
+        ir_dir,
+        cmd.build.build_target,
+    )
+    .print_ir(cmd.build.print.ir)
+    .print_intermediate_asm(cmd.build.print.intermediate_asm)
+    .print_finalized_asm(cmd.build.print.finalized_asm)
+    .optimization_level(cmd.build.profile.optimization_level.unwrap_or_default())
+    .ir_passes(cmd.build.profile.ir_passes.clone())
+    .verify_ir(cmd.build.profile.verify_ir);
+
+    let engines = Engines::default();
+    let compiled = compile_ir_to_bytecode(
+        &engines,
+        &ir_source,
+        &build_config,
+        &mut SourceMap::new(),
+        &mut PerformanceData::default(),
+    );
+    let Some(compiled) = compiled.value.filter(|_| compiled.errors.is_empty()) else {
+        print_on_failure(
+            engines.se(),
+            cmd.build.pkg.terse,
+            &compiled.warnings,
+            &compiled.errors,
+            cmd.build.print.reverse_order,
+            None,
+        );
+        bail!("failed to compile {}", ir_path.display());
+    };
+
+    let bin_path = match &cmd.build.output.bin_file {
+        Some(bin_file) => PathBuf::from(bin_file),
+        None => ir_path.with_extension("bin"),
+    };
+    fs::write(&bin_path, &compiled.bytecode)
+        .with_context(|| format!("failed to write {}", bin_path.display()))?;
+    info!(
+        "      Bytecode size: {} bytes, written to {}",
+        compiled.bytecode.len(),
+        bin_path.display()
+    );
+    Ok(())
 }
 
 fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
@@ -26,7 +107,11 @@ fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
             finalized_asm: cmd.build.print.finalized_asm,
             intermediate_asm: cmd.build.print.intermediate_asm,
             ir: cmd.build.print.ir,
+            smt: cmd.build.print.smt,
+            dump_fn: cmd.build.print.dump_fn,
             reverse_order: cmd.build.print.reverse_order,
+            size_report: cmd.build.print.size_report,
+            gas_report: cmd.build.print.gas_report,
         },
         time_phases: cmd.build.print.time_phases,
         metrics_outfile: cmd.build.print.metrics_outfile,
@@ -37,10 +122,17 @@ fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
         build_profile: cmd.build.profile.build_profile,
         release: cmd.build.profile.release,
         error_on_warnings: cmd.build.profile.error_on_warnings,
+        emit_cfg: cmd.build.output.emit_cfg(),
         binary_outfile: cmd.build.output.bin_file,
         debug_outfile: cmd.build.output.debug_file,
         build_target: cmd.build.build_target,
         tests: cmd.tests,
         member_filter: Default::default(),
+        lints: cmd.build.profile.lints(),
+        optimization_level: cmd.build.profile.optimization_level,
+        ir_passes: cmd.build.profile.ir_passes.clone(),
+        sarif: None,
+        verify_ir: cmd.build.profile.verify_ir,
+        experimental: cmd.build.profile.experimental,
     }
 }
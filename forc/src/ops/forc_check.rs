@@ -14,6 +14,7 @@ pub fn check(command: CheckCommand, engines: &Engines) -> Result<CompileResult<t
         locked,
         disable_tests,
         ipfs_node,
+        sarif: _,
     } = command;
 
     let this_dir = if let Some(ref path) = path {
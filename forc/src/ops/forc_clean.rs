@@ -26,10 +26,14 @@ pub fn clean(command: CleanCommand) -> Result<()> {
         }
     };
     let manifest = ManifestFile::from_dir(&manifest_dir)?;
-    // If this is a workspace collect all member paths and clean each of them.
+    // If this is a workspace collect all member paths and clean each of them, along with the
+    // workspace's own build cache.
     let paths: Vec<PathBuf> = match manifest {
         ManifestFile::Package(_) => std::iter::once(this_dir).collect(),
-        ManifestFile::Workspace(workspace) => workspace.member_paths()?.collect(),
+        ManifestFile::Workspace(workspace) => workspace
+            .member_paths()?
+            .chain(std::iter::once(manifest_dir))
+            .collect(),
     };
 
     for member_path in paths {
@@ -2,6 +2,7 @@ use crate::cli::ContractIdCommand;
 use anyhow::{bail, Result};
 use forc_pkg::{self as pkg, build_with_options};
 use forc_tracing::println_green;
+use forc_util::tx_utils::checksum_encode;
 use sway_core::{fuel_prelude::fuel_tx, BuildTarget};
 use tracing::info;
 
@@ -39,7 +40,7 @@ pub fn contract_id(command: ContractIdCommand) -> Result<()> {
         let contract_id =
             pkg::contract_id(built_contract.bytecode.bytes.clone(), storage_slots, &salt);
         println_green(&format!(" {name}"));
-        info!("      Contract id: 0x{contract_id}");
+        info!("      Contract id: {}", checksum_encode(&contract_id));
     }
     Ok(())
 }
@@ -62,7 +63,11 @@ fn build_opts_from_cmd(cmd: &ContractIdCommand) -> pkg::BuildOpts {
             finalized_asm: cmd.print.finalized_asm,
             intermediate_asm: cmd.print.intermediate_asm,
             ir: cmd.print.ir,
+            smt: cmd.print.smt,
+            dump_fn: cmd.print.dump_fn.clone(),
             reverse_order: cmd.print.reverse_order,
+            size_report: cmd.print.size_report,
+            gas_report: cmd.print.gas_report,
         },
         time_phases: cmd.print.time_phases,
         metrics_outfile: cmd.print.metrics_outfile.clone(),
@@ -78,5 +83,12 @@ fn build_opts_from_cmd(cmd: &ContractIdCommand) -> pkg::BuildOpts {
         build_target: BuildTarget::default(),
         tests: false,
         member_filter: pkg::MemberFilter::only_contracts(),
+        lints: cmd.build_profile.lints(),
+        optimization_level: cmd.build_profile.optimization_level,
+        ir_passes: cmd.build_profile.ir_passes.clone(),
+        sarif: None,
+        verify_ir: cmd.build_profile.verify_ir,
+        emit_cfg: cmd.build_output.emit_cfg(),
+        experimental: cmd.build_profile.experimental.clone(),
     }
 }
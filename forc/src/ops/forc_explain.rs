@@ -0,0 +1,17 @@
+use anyhow::{bail, Result};
+use sway_error::explanations::{explanation, is_well_formed_code};
+
+/// Prints the extended explanation of the error or warning with the given code.
+pub fn explain(code: &str) -> Result<()> {
+    if !is_well_formed_code(code) {
+        bail!("`{code}` is not an error or warning code, such as `E0001` or `W0001`");
+    }
+    match explanation(code) {
+        Some(explanation) => println!("{explanation}"),
+        None => bail!(
+            "there is no extended explanation for `{}`",
+            code.to_ascii_uppercase()
+        ),
+    }
+    Ok(())
+}
@@ -99,6 +99,10 @@ pub fn init(command: InitCommand) -> ForcResult<()> {
         }
     };
 
+    if command.with_tests && !matches!(init_type, InitType::Package(ProgramType::Contract)) {
+        forc_result_bail!("`--with-tests` is only supported for contracts.");
+    }
+
     // Make a new directory for the project
     let dir_to_create = match init_type {
         InitType::Package(_) => project_dir.join("src"),
@@ -155,6 +159,23 @@ pub fn init(command: InitCommand) -> ForcResult<()> {
         _ => {}
     }
 
+    if command.with_tests {
+        fs::write(
+            Path::new(&project_dir).join(constants::TEST_MANIFEST_FILE_NAME),
+            defaults::default_tests_manifest(&project_name),
+        )?;
+        let tests_dir = Path::new(&project_dir).join(constants::TEST_DIRECTORY);
+        fs::create_dir_all(&tests_dir)?;
+        fs::write(
+            tests_dir.join("harness.rs"),
+            defaults::default_test_harness(&project_name),
+        )?;
+        debug!(
+            "\nCreated test harness at {}",
+            tests_dir.canonicalize()?.display()
+        );
+    }
+
     // Ignore default `out` and `target` directories created by forc and cargo.
     let gitignore_path = Path::new(&project_dir).join(".gitignore");
     // Append to existing gitignore if it exists otherwise create a new one.
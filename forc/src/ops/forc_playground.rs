@@ -0,0 +1,438 @@
+use crate::cli::{plugin, PlaygroundCommand};
+use anyhow::{anyhow, bail, Context, Result};
+use forc_pkg::{self as pkg, BuiltPackage, PackageManifestFile};
+use forc_receipts::format_receipts;
+use forc_util::{
+    kebab_to_snake_case,
+    tx_utils::{checksum_decode, receipt_decoder},
+    user_forc_directory,
+};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use sway_core::{
+    asm_generation::ProgramABI,
+    fuel_prelude::{fuel_tx::Receipt, fuel_vm::state::ProgramState},
+    language::parsed::TreeType,
+};
+use sway_utils::constants::{MAIN_ENTRY, MANIFEST_FILE_NAME, SRC_DIR};
+use tracing::info;
+
+/// The directory within the user's forc directory in which playground projects are created.
+const PLAYGROUND_DIR_NAME: &str = "playground";
+
+/// The name of the constant holding the ID of the deployed contract instance given with
+/// `--contract-id`.
+const INSTANCE_ID_CONSTANT_NAME: &str = "INSTANCE_ID";
+
+pub fn playground(command: PlaygroundCommand) -> Result<()> {
+    let source = match &command.file {
+        Some(file) => fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?,
+        None => {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source)?;
+            source
+        }
+    };
+
+    let this_dir = match &command.path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let mut playground = Playground::new(&this_dir)?;
+    if let Some(contract_id) = &command.contract_id {
+        playground.declare_instance(contract_id)?;
+    }
+    playground.write_script(&source)?;
+    if command.node_url.is_some() {
+        let output = plugin::execute_external_subcommand(forc_run_args(&playground, &command))?;
+        if !output.status.success() {
+            bail!("`forc run` failed with {}", output.status);
+        }
+        return Ok(());
+    }
+    let built = playground.build(command.offline, false)?;
+    let output = run_script(&built)?;
+    if !output.logs.is_empty() {
+        info!(
+            "Logs:\n{}",
+            format_receipts(
+                &output.logs,
+                &receipt_decoder(&built.program_abi)?,
+                command.pretty_print
+            )?
+        );
+    }
+    info!("{}: {}", output.value, output.return_type);
+    info!("Executed in {:?}", output.duration);
+    Ok(())
+}
+
+/// A throwaway script project that has access to the items of the project it was created for.
+pub(crate) struct Playground {
+    dir: PathBuf,
+    /// The program type declaration and imports prepended to every script.
+    header: String,
+    /// The program type of the project the playground was created for.
+    project_type: TreeType,
+}
+
+impl Playground {
+    /// Creates the playground project for the package at `project_dir`, importing all of its
+    /// items if it is a library or a contract.
+    pub(crate) fn new(project_dir: &Path) -> Result<Self> {
+        Self::new_in(
+            &user_forc_directory().join(PLAYGROUND_DIR_NAME),
+            project_dir,
+        )
+    }
+
+    /// Creates the playground project for the package at `project_dir` within `playgrounds_dir`.
+    fn new_in(playgrounds_dir: &Path, project_dir: &Path) -> Result<Self> {
+        let manifest = PackageManifestFile::from_dir(project_dir)?;
+        let project_name = manifest.project_name();
+        let project_type = manifest.program_type()?;
+
+        let playground_name = format!("{project_name}-playground");
+        let dir = playgrounds_dir.join(&playground_name);
+        fs::create_dir_all(dir.join(SRC_DIR))?;
+        let mut playground_manifest = format!(
+            "[project]\nentry = \"{MAIN_ENTRY}\"\nlicense = \"Apache-2.0\"\nname = \"{playground_name}\"\n"
+        );
+        let mut header = "script;\n\n".to_string();
+        let dependencies_table = match project_type {
+            TreeType::Library => Some("dependencies"),
+            TreeType::Contract => Some("contract-dependencies"),
+            TreeType::Script | TreeType::Predicate => None,
+        };
+        if let Some(dependencies_table) = dependencies_table {
+            let project_path =
+                toml::Value::String(manifest.dir().canonicalize()?.display().to_string());
+            playground_manifest.push_str(&format!(
+                "\n[{dependencies_table}]\n{project_name} = {{ path = {project_path} }}\n"
+            ));
+            header.push_str(&format!(
+                "use {}::*;\n\n",
+                kebab_to_snake_case(project_name)
+            ));
+        }
+        fs::write(dir.join(MANIFEST_FILE_NAME), playground_manifest)?;
+        Ok(Self {
+            dir,
+            header,
+            project_type,
+        })
+    }
+
+    /// Declares the ID of a deployed instance of the contract the playground was created for as
+    /// the `INSTANCE_ID` constant of every script.
+    pub(crate) fn declare_instance(&mut self, contract_id: &str) -> Result<()> {
+        if !matches!(self.project_type, TreeType::Contract) {
+            bail!("`--contract-id` can only be used within a contract project");
+        }
+        let contract_id = checksum_decode(contract_id)?;
+        self.header.push_str(&format!(
+            "const {INSTANCE_ID_CONSTANT_NAME}: b256 = 0x{};\n\n",
+            hex::encode(contract_id)
+        ));
+        Ok(())
+    }
+
+    /// The directory of the playground project.
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Replaces the playground script with `source`, which must declare a `main` function.
+    pub(crate) fn write_script(&self, source: &str) -> Result<()> {
+        fs::write(self.dir.join(self.script_path()), self.script(source))?;
+        Ok(())
+    }
+
+    /// The path of the playground script, relative to the playground project.
+    pub(crate) fn script_path(&self) -> PathBuf {
+        Path::new(SRC_DIR).join(MAIN_ENTRY)
+    }
+
+    /// The complete script for `source`, i.e. with the program type and imports prepended.
+    pub(crate) fn script(&self, source: &str) -> String {
+        format!("{}{source}", self.header)
+    }
+
+    /// Builds the playground script, printing any errors.
+    pub(crate) fn build(&self, offline: bool, terse: bool) -> Result<Arc<BuiltPackage>> {
+        let build_options = pkg::BuildOpts {
+            pkg: pkg::PkgOpts {
+                path: Some(self.dir.display().to_string()),
+                offline,
+                terse,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        pkg::build_with_options(build_options)?.expect_pkg()
+    }
+}
+
+/// The arguments of the `forc run` invocation that runs the playground script against the node
+/// given with `--node-url`.
+fn forc_run_args(playground: &Playground, command: &PlaygroundCommand) -> Vec<String> {
+    let mut args = vec![
+        "run".to_string(),
+        "--path".to_string(),
+        playground.dir().display().to_string(),
+    ];
+    if let Some(node_url) = &command.node_url {
+        args.extend(["--node-url".to_string(), node_url.clone()]);
+    }
+    if let Some(contract_id) = &command.contract_id {
+        args.extend(["--contract".to_string(), contract_id.clone()]);
+    }
+    let flags = [
+        (command.offline, "--offline"),
+        (command.pretty_print, "--pretty-print"),
+        (command.unsigned, "--unsigned"),
+    ];
+    args.extend(
+        flags
+            .into_iter()
+            .filter(|(is_set, _)| *is_set)
+            .map(|(_, flag)| flag.to_string()),
+    );
+    args
+}
+
+/// The result of running a script in the playground.
+pub(crate) struct ScriptOutput {
+    /// The value returned by `main`, decoded according to its type where possible.
+    pub(crate) value: String,
+    /// The return type of `main`, as written in the ABI.
+    pub(crate) return_type: String,
+    pub(crate) logs: Vec<Receipt>,
+    pub(crate) duration: Duration,
+}
+
+/// Runs the built script in an in-memory VM and decodes the value returned by `main`.
+pub(crate) fn run_script(built: &BuiltPackage) -> Result<ScriptOutput> {
+    run_bytecode(&built.bytecode.bytes, &built.program_abi, vec![])
+}
+
+/// Runs the script `bytecode`, whose ABI is `program_abi`, in an in-memory VM, with `script_data`
+/// as the data `main` reads its arguments from, and decodes the value returned by `main`.
+pub(crate) fn run_bytecode(
+    bytecode: &[u8],
+    program_abi: &ProgramABI,
+    script_data: Vec<u8>,
+) -> Result<ScriptOutput> {
+    let (state, duration, receipts) = forc_test::execute_script_with_data(bytecode, script_data);
+    let return_type = main_return_type(program_abi)?;
+    let value = match state {
+        ProgramState::Return(value) => match return_type.as_str() {
+            "()" => "()".to_string(),
+            "bool" => (value != 0).to_string(),
+            _ => value.to_string(),
+        },
+        ProgramState::ReturnData(_) => {
+            let data = receipts
+                .iter()
+                .find_map(|receipt| match receipt {
+                    Receipt::ReturnData { data, .. } => Some(data),
+                    _ => None,
+                })
+                .ok_or_else(|| anyhow!("the script returned data, but no data was found"))?;
+            format!("0x{}", hex::encode(data))
+        }
+        ProgramState::Revert(code) => bail!("the script reverted with code {code}"),
+        state => bail!("unexpected program state: {state:?}"),
+    };
+    let logs = receipts
+        .into_iter()
+        .filter(|receipt| matches!(receipt, Receipt::Log { .. } | Receipt::LogData { .. }))
+        .collect();
+    Ok(ScriptOutput {
+        value,
+        return_type,
+        logs,
+        duration,
+    })
+}
+
+/// The type returned by the `main` function of the script, as written in its ABI.
+fn main_return_type(program_abi: &ProgramABI) -> Result<String> {
+    let ProgramABI::Fuel(program_abi) = program_abi else {
+        bail!("only scripts built for the Fuel VM can be run in the playground");
+    };
+    let main_fn = program_abi
+        .functions
+        .iter()
+        .find(|function| function.name == "main")
+        .ok_or_else(|| anyhow!("the script must declare a `main` function"))?;
+    program_abi
+        .types
+        .iter()
+        .find(|ty| ty.type_id == main_fn.output.type_id)
+        .map(|ty| ty.type_field.clone())
+        .ok_or_else(|| anyhow!("the return type of `main` is missing from the ABI"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// Creates a project named `name` whose entry declares the program `kind`, and returns the
+    /// project's directory along with the directory to create playgrounds in.
+    fn project(name: &str, kind: &str) -> (PathBuf, PathBuf) {
+        let root = std::env::temp_dir().join(format!(
+            "forc-playground-test-{name}-{}",
+            std::process::id()
+        ));
+        let project_dir = root.join(name);
+        fs::create_dir_all(project_dir.join(SRC_DIR)).unwrap();
+        fs::write(
+            project_dir.join(MANIFEST_FILE_NAME),
+            format!(
+                "[project]\nentry = \"main.sw\"\nlicense = \"Apache-2.0\"\nname = \"{name}\"\n"
+            ),
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join(SRC_DIR).join(MAIN_ENTRY),
+            format!("{kind};\n"),
+        )
+        .unwrap();
+        (project_dir, root.join(PLAYGROUND_DIR_NAME))
+    }
+
+    fn playground_manifest(playground: &Playground) -> toml::Value {
+        let manifest = fs::read_to_string(playground.dir().join(MANIFEST_FILE_NAME)).unwrap();
+        toml::from_str(&manifest).unwrap()
+    }
+
+    fn dependency_path(manifest: &toml::Value, table: &str, name: &str) -> PathBuf {
+        PathBuf::from(manifest[table][name]["path"].as_str().unwrap())
+    }
+
+    #[test]
+    fn library_items_are_imported() {
+        let (project_dir, playgrounds_dir) = project("my-lib", "library");
+        let playground = Playground::new_in(&playgrounds_dir, &project_dir).unwrap();
+        assert_eq!(playground.dir(), playgrounds_dir.join("my-lib-playground"));
+        assert_eq!(
+            playground.script("fn main() {}\n"),
+            "script;\n\nuse my_lib::*;\n\nfn main() {}\n"
+        );
+        let manifest = playground_manifest(&playground);
+        assert_eq!(
+            dependency_path(&manifest, "dependencies", "my-lib"),
+            project_dir.canonicalize().unwrap()
+        );
+        assert!(manifest.get("contract-dependencies").is_none());
+    }
+
+    #[test]
+    fn contract_items_are_imported() {
+        let (project_dir, playgrounds_dir) = project("counter", "contract");
+        let playground = Playground::new_in(&playgrounds_dir, &project_dir).unwrap();
+        assert_eq!(
+            playground.script("fn main() {}\n"),
+            "script;\n\nuse counter::*;\n\nfn main() {}\n"
+        );
+        let manifest = playground_manifest(&playground);
+        assert_eq!(
+            dependency_path(&manifest, "contract-dependencies", "counter"),
+            project_dir.canonicalize().unwrap()
+        );
+        assert!(manifest.get("dependencies").is_none());
+    }
+
+    #[test]
+    fn scripts_are_not_imported() {
+        let (project_dir, playgrounds_dir) = project("my-script", "script");
+        let playground = Playground::new_in(&playgrounds_dir, &project_dir).unwrap();
+        assert_eq!(
+            playground.script("fn main() {}\n"),
+            "script;\n\nfn main() {}\n"
+        );
+        let manifest = playground_manifest(&playground);
+        assert!(manifest.get("dependencies").is_none());
+        assert!(manifest.get("contract-dependencies").is_none());
+    }
+
+    #[test]
+    fn deployed_instance_is_declared() {
+        let contract_id = format!("0x{}", "AB".repeat(32));
+        let (project_dir, playgrounds_dir) = project("instance", "contract");
+        let mut playground = Playground::new_in(&playgrounds_dir, &project_dir).unwrap();
+        playground.declare_instance(&contract_id).unwrap();
+        assert_eq!(
+            playground.script("fn main() {}\n"),
+            format!(
+                "script;\n\nuse instance::*;\n\nconst INSTANCE_ID: b256 = 0x{};\n\nfn main() {{}}\n",
+                "ab".repeat(32)
+            )
+        );
+        assert!(playground.declare_instance("0x1234").is_err());
+
+        let (project_dir, playgrounds_dir) = project("not-a-contract", "library");
+        let mut playground = Playground::new_in(&playgrounds_dir, &project_dir).unwrap();
+        assert!(playground.declare_instance(&contract_id).is_err());
+    }
+
+    #[test]
+    fn node_mode_runs_the_playground_with_forc_run() {
+        let (project_dir, playgrounds_dir) = project("on-node", "contract");
+        let playground = Playground::new_in(&playgrounds_dir, &project_dir).unwrap();
+        let dir = playground.dir().display().to_string();
+        let contract_id = "11".repeat(32);
+
+        let command = PlaygroundCommand::try_parse_from([
+            "playground",
+            "--node-url",
+            "http://127.0.0.1:4000",
+            "--contract-id",
+            contract_id.as_str(),
+            "--unsigned",
+            "-r",
+        ])
+        .unwrap();
+        assert_eq!(
+            forc_run_args(&playground, &command),
+            [
+                "run",
+                "--path",
+                dir.as_str(),
+                "--node-url",
+                "http://127.0.0.1:4000",
+                "--contract",
+                contract_id.as_str(),
+                "--pretty-print",
+                "--unsigned",
+            ]
+        );
+
+        let command =
+            PlaygroundCommand::try_parse_from(["playground", "--node-url", "http://node"]).unwrap();
+        assert_eq!(
+            forc_run_args(&playground, &command),
+            ["run", "--path", dir.as_str(), "--node-url", "http://node"]
+        );
+    }
+
+    #[test]
+    fn node_only_options_require_a_node() {
+        let contract_id = "11".repeat(32);
+        assert!(PlaygroundCommand::try_parse_from([
+            "playground",
+            "--contract-id",
+            contract_id.as_str()
+        ])
+        .is_err());
+        assert!(PlaygroundCommand::try_parse_from(["playground", "--unsigned"]).is_err());
+    }
+}
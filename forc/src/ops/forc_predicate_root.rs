@@ -30,7 +30,11 @@ fn build_opts_from_cmd(cmd: PredicateRootCommand) -> pkg::BuildOpts {
             finalized_asm: cmd.print.finalized_asm,
             intermediate_asm: cmd.print.intermediate_asm,
             ir: cmd.print.ir,
+            smt: cmd.print.smt,
+            dump_fn: cmd.print.dump_fn.clone(),
             reverse_order: cmd.print.reverse_order,
+            size_report: cmd.print.size_report,
+            gas_report: cmd.print.gas_report,
         },
         time_phases: cmd.print.time_phases,
         metrics_outfile: cmd.print.metrics_outfile,
@@ -46,5 +50,12 @@ fn build_opts_from_cmd(cmd: PredicateRootCommand) -> pkg::BuildOpts {
         build_target: BuildTarget::default(),
         tests: false,
         member_filter: pkg::MemberFilter::only_predicates(),
+        lints: cmd.build_profile.lints(),
+        optimization_level: cmd.build_profile.optimization_level,
+        ir_passes: cmd.build_profile.ir_passes.clone(),
+        sarif: None,
+        verify_ir: cmd.build_profile.verify_ir,
+        emit_cfg: cmd.build_output.emit_cfg(),
+        experimental: cmd.build_profile.experimental,
     }
 }
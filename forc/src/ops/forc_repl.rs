@@ -0,0 +1,499 @@
+use crate::{
+    cli::ReplCommand,
+    ops::forc_playground::{run_bytecode, Playground},
+};
+use anyhow::{anyhow, bail, Result};
+use forc_pkg::{self as pkg, manifest::ManifestFile};
+use forc_receipts::format_receipts;
+use forc_tracing::println_red_err;
+use forc_util::{print_on_failure, tx_utils::receipt_decoder};
+use std::{
+    io::{BufRead, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+use sway_core::{
+    compile_sources_to_bytecode,
+    fuel_prelude::fuel_tx::Receipt,
+    language::ty::{self, TyDecl, TyProgramKind},
+    namespace, BuildTarget, CancellationToken, Engines, TypeId, TypeInfo, TypeParameter,
+};
+use sway_utils::PerformanceData;
+use tracing::info;
+
+/// The name of the variable that the value of an expression is bound to.
+const REPL_VALUE: &str = "__repl_value";
+
+/// The name of the package each entry is compiled as.
+const REPL_PACKAGE: &str = "repl";
+
+/// The keywords that start an item, rather than a statement or an expression.
+const ITEM_KEYWORDS: [&str; 10] = [
+    "fn", "struct", "enum", "const", "impl", "trait", "use", "abi", "type", "pub",
+];
+
+const HELP: &str = "\
+Enter an item, a statement or an expression to evaluate it.
+Commands:
+  :help   Print this message.
+  :reset  Forget all items and variables entered so far.
+  :quit   Exit the REPL.";
+
+pub fn repl(command: ReplCommand) -> Result<()> {
+    let this_dir = match &command.path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let playground = Playground::new(&this_dir)?;
+    let mut session = Session::new(playground, command.offline)?;
+    info!("Sway REPL. Enter `:help` for help, `:quit` to exit.");
+
+    let stdin = std::io::stdin();
+    let mut input = String::new();
+    loop {
+        print!("{}", if input.is_empty() { ">> " } else { ".. " });
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        if input.is_empty() {
+            match line.trim() {
+                "" => continue,
+                ":quit" | ":q" => break,
+                ":help" => {
+                    info!("{HELP}");
+                    continue;
+                }
+                ":reset" => {
+                    session.reset();
+                    continue;
+                }
+                _ => (),
+            }
+        }
+        input.push_str(&line);
+        // Keep reading lines until all delimiters are closed.
+        if open_delimiters(&input) > 0 {
+            continue;
+        }
+        let entry = std::mem::take(&mut input);
+        if let Err(err) = session.eval(entry.trim()) {
+            println_red_err(&format!("{err:#}"));
+        }
+    }
+    Ok(())
+}
+
+/// The items and variables entered so far, and what's needed to compile the next entry.
+///
+/// The dependencies, i.e. std and the current project if it is a library, are only checked once,
+/// and each entry is compiled within the namespace they declare. Each entry is run on its own: the
+/// values of the variables are logged at the end of the script it is compiled into, and passed to
+/// the script of the next entry as the arguments of its `main` function.
+struct Session {
+    playground: Playground,
+    engines: Engines,
+    /// The namespace of the dependencies.
+    namespace: namespace::Module,
+    items: Vec<String>,
+    variables: Vec<Variable>,
+    /// The values of `variables`, laid out as the arguments of `main`.
+    values: Vec<u8>,
+}
+
+/// A variable declared by a `let` statement.
+#[derive(Clone)]
+struct Variable {
+    name: String,
+    /// The type of the variable, as written in Sway.
+    type_name: String,
+    is_mutable: bool,
+}
+
+impl Session {
+    fn new(playground: Playground, offline: bool) -> Result<Self> {
+        playground.write_script("fn main() {}\n")?;
+        let manifest_file = ManifestFile::from_dir(playground.dir())?;
+        let member_manifests = manifest_file.member_manifests()?;
+        let lock_path = manifest_file.lock_path()?;
+        let plan = pkg::BuildPlan::from_lock_and_manifests(
+            &lock_path,
+            &member_manifests,
+            false,
+            offline,
+            Default::default(),
+        )?;
+        let engines = Engines::default();
+        let namespace = pkg::check_dependencies(&plan, BuildTarget::Fuel, &engines)?;
+        Ok(Self {
+            playground,
+            engines,
+            namespace,
+            items: vec![],
+            variables: vec![],
+            values: vec![],
+        })
+    }
+
+    fn reset(&mut self) {
+        self.items.clear();
+        self.variables.clear();
+        self.values.clear();
+    }
+
+    fn eval(&mut self, entry: &str) -> Result<()> {
+        let first_word = entry
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        if ITEM_KEYWORDS.contains(&first_word) || entry.starts_with("#[") {
+            // Items have no effects, so they only need to type-check.
+            let mut items = self.items.clone();
+            items.push(entry.to_string());
+            self.check(&items, "")?;
+            self.items = items;
+            return Ok(());
+        }
+
+        let is_statement = first_word == "let" || entry.ends_with(';');
+        let statement = if is_statement {
+            format!("{};", entry.trim_end_matches(';'))
+        } else {
+            format!("let {REPL_VALUE} = {entry};")
+        };
+        let main_function = self.check(&self.items, &statement)?;
+        let (variables, value_type) = self.declared_by(&main_function)?;
+
+        // Log the values of the variables after the entry, for the next entry to start from.
+        let mut statements = vec![statement];
+        if !variables.is_empty() {
+            let values = variables
+                .iter()
+                .map(|variable| format!("{},", variable.name))
+                .collect::<String>();
+            statements.push(format!("__log(({values}));"));
+        }
+        let (return_type, tail) = match &value_type {
+            Some(value_type) => (value_type.as_str(), REPL_VALUE),
+            None => ("()", ""),
+        };
+        let source = self.playground.script(&script(
+            &self.items,
+            &self.variables,
+            &statements,
+            return_type,
+            tail,
+        ));
+        let compiled = compile_sources_to_bytecode(
+            &self.engines,
+            [(self.playground.script_path(), Arc::from(source))]
+                .into_iter()
+                .collect(),
+            self.playground.script_path(),
+            self.namespace.clone(),
+            BuildTarget::Fuel,
+            REPL_PACKAGE,
+            &CancellationToken::new(),
+        )
+        .map_err(|_| anyhow!("the compilation was cancelled"))?;
+        let Some(compiled) = compiled.value.filter(|_| compiled.errors.is_empty()) else {
+            print_on_failure(self.engines.se(), false, &[], &compiled.errors, false, None);
+            bail!("failed to compile the entry");
+        };
+
+        let mut output = run_bytecode(
+            &compiled.bytecode.bytecode,
+            &compiled.abi,
+            self.values.clone(),
+        )?;
+        let values = if variables.is_empty() {
+            vec![]
+        } else {
+            match output.logs.pop() {
+                Some(Receipt::LogData { data, .. }) => data,
+                _ => bail!("the values of the variables were not logged"),
+            }
+        };
+        if !output.logs.is_empty() {
+            let decoder = receipt_decoder(&compiled.abi)?;
+            info!("{}", format_receipts(&output.logs, &decoder, true)?);
+        }
+        if let Some(value_type) = value_type {
+            info!("{}: {value_type}", output.value);
+        }
+        self.variables = variables;
+        self.values = values;
+        Ok(())
+    }
+
+    /// Type-checks the script running `statement` with the variables of the session in scope,
+    /// returning its `main` function.
+    fn check(&self, items: &[String], statement: &str) -> Result<ty::TyFunctionDecl> {
+        let statements = [statement.to_string()];
+        let source = self
+            .playground
+            .script(&script(items, &self.variables, &statements, "()", ""));
+        let res = sway_core::compile_to_ast(
+            &self.engines,
+            Arc::from(source),
+            self.namespace.clone(),
+            None,
+            REPL_PACKAGE,
+            &mut PerformanceData::default(),
+        );
+        match res.value.and_then(|programs| programs.typed) {
+            Some(ty::TyProgram {
+                kind: TyProgramKind::Script { main_function },
+                ..
+            }) if res.errors.is_empty() => Ok(main_function),
+            _ => {
+                print_on_failure(self.engines.se(), false, &[], &res.errors, false, None);
+                bail!("failed to type-check the entry");
+            }
+        }
+    }
+
+    /// The variables in scope after the statement run by `main_function`, and the type of the
+    /// value of the expression it binds to [REPL_VALUE], if any.
+    fn declared_by(
+        &self,
+        main_function: &ty::TyFunctionDecl,
+    ) -> Result<(Vec<Variable>, Option<String>)> {
+        let mut variables = self.variables.clone();
+        let mut value_type = None;
+        // Skip the mutable variables declared again from the arguments of `main`.
+        let redeclared = self
+            .variables
+            .iter()
+            .filter(|variable| variable.is_mutable)
+            .count();
+        for node in main_function.body.contents.iter().skip(redeclared) {
+            let ty::TyAstNodeContent::Declaration(TyDecl::VariableDecl(decl)) = &node.content
+            else {
+                continue;
+            };
+            let name = decl.name.as_str();
+            if name == REPL_VALUE {
+                value_type = Some(type_name(&self.engines, decl.return_type).ok_or_else(|| {
+                    anyhow!(
+                        "the value of type `{}` can't be returned",
+                        self.engines.help_out(decl.return_type)
+                    )
+                })?);
+                continue;
+            }
+            // The variables the compiler declares, e.g. to destructure tuples, are left out.
+            if name.starts_with("__") {
+                continue;
+            }
+            let type_name = type_name(&self.engines, decl.return_type)
+                .filter(|_| !refers_to_memory(&self.engines, decl.return_type))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "`{name}` can't be kept for the next entries, as its type `{}` refers to \
+                         memory of the script",
+                        self.engines.help_out(decl.return_type)
+                    )
+                })?;
+            variables.retain(|variable| variable.name != name);
+            variables.push(Variable {
+                name: name.to_string(),
+                type_name,
+                is_mutable: decl.mutability.is_mutable(),
+            });
+        }
+        Ok((variables, value_type))
+    }
+}
+
+/// The source of a script declaring `items`, whose `main` function takes the values of
+/// `variables` as its arguments, runs `statements` and returns `tail`.
+fn script(
+    items: &[String],
+    variables: &[Variable],
+    statements: &[String],
+    return_type: &str,
+    tail: &str,
+) -> String {
+    let mut source = String::new();
+    for item in items {
+        source.push_str(item);
+        source.push_str("\n\n");
+    }
+    let params = variables
+        .iter()
+        .map(|variable| format!("{}: {}", variable.name, variable.type_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    source.push_str(&format!("fn main({params}) -> {return_type} {{\n"));
+    for variable in variables.iter().filter(|variable| variable.is_mutable) {
+        source.push_str(&format!("    let mut {0} = {0};\n", variable.name));
+    }
+    for statement in statements {
+        source.push_str(&format!("    {statement}\n"));
+    }
+    if !tail.is_empty() {
+        source.push_str(&format!("    {tail}\n"));
+    }
+    source.push_str("}\n");
+    source
+}
+
+/// The type `type_id` as written in Sway, if it can be written.
+fn type_name(engines: &Engines, type_id: TypeId) -> Option<String> {
+    let type_name = match engines.te().get(type_id) {
+        TypeInfo::UnsignedInteger(_) | TypeInfo::Boolean | TypeInfo::B256 | TypeInfo::Str(_) => {
+            engines.help_out(type_id).to_string()
+        }
+        // Integers whose type wasn't inferred are `u64`.
+        TypeInfo::Numeric => "u64".to_string(),
+        TypeInfo::RawUntypedPtr => "raw_ptr".to_string(),
+        TypeInfo::RawUntypedSlice => "raw_slice".to_string(),
+        TypeInfo::Tuple(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| type_name(engines, field.type_id))
+                .collect::<Option<Vec<_>>>()?;
+            match fields.as_slice() {
+                [field] => format!("({field},)"),
+                fields => format!("({})", fields.join(", ")),
+            }
+        }
+        TypeInfo::Array(elem, length) => {
+            format!("[{}; {}]", type_name(engines, elem.type_id)?, length.val())
+        }
+        TypeInfo::Struct(decl_ref) => {
+            let decl = engines.de().get_struct(decl_ref.id());
+            generic_type_name(
+                engines,
+                decl.call_path.suffix.as_str(),
+                &decl.type_parameters,
+            )?
+        }
+        TypeInfo::Enum(decl_ref) => {
+            let decl = engines.de().get_enum(decl_ref.id());
+            generic_type_name(
+                engines,
+                decl.call_path.suffix.as_str(),
+                &decl.type_parameters,
+            )?
+        }
+        TypeInfo::Alias { name, .. } => name.to_string(),
+        _ => return None,
+    };
+    Some(type_name)
+}
+
+fn generic_type_name(
+    engines: &Engines,
+    name: &str,
+    type_parameters: &[TypeParameter],
+) -> Option<String> {
+    if type_parameters.is_empty() {
+        return Some(name.to_string());
+    }
+    let type_arguments = type_parameters
+        .iter()
+        .map(|type_parameter| type_name(engines, type_parameter.type_id))
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("{name}<{}>", type_arguments.join(", ")))
+}
+
+/// Whether the values of the type `type_id` point into the memory of the script, e.g. a `Vec`, so
+/// that they can't be passed on to the next script.
+fn refers_to_memory(engines: &Engines, type_id: TypeId) -> bool {
+    match engines.te().get(type_id) {
+        TypeInfo::RawUntypedPtr
+        | TypeInfo::RawUntypedSlice
+        | TypeInfo::Ptr(_)
+        | TypeInfo::Slice(_) => true,
+        TypeInfo::Tuple(fields) => fields
+            .iter()
+            .any(|field| refers_to_memory(engines, field.type_id)),
+        TypeInfo::Array(elem, _) => refers_to_memory(engines, elem.type_id),
+        TypeInfo::Struct(decl_ref) => engines
+            .de()
+            .get_struct(decl_ref.id())
+            .fields
+            .iter()
+            .any(|field| refers_to_memory(engines, field.type_argument.type_id)),
+        TypeInfo::Enum(decl_ref) => engines
+            .de()
+            .get_enum(decl_ref.id())
+            .variants
+            .iter()
+            .any(|variant| refers_to_memory(engines, variant.type_argument.type_id)),
+        TypeInfo::Alias { ty, .. } => refers_to_memory(engines, ty.type_id),
+        _ => false,
+    }
+}
+
+/// The number of delimiters in `input` that are not closed yet, where an unterminated string or
+/// block comment counts as one. Delimiters within strings and comments are ignored.
+fn open_delimiters(input: &str) -> i64 {
+    let mut open = 0;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => loop {
+                match chars.next() {
+                    None => return open + 1,
+                    Some('"') => break,
+                    Some('\\') => {
+                        chars.next();
+                    }
+                    Some(_) => (),
+                }
+            },
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // Block comments nest.
+                let mut depth = 1;
+                while depth > 0 {
+                    let (c, next) = (chars.next(), chars.peek().copied());
+                    match (c, next) {
+                        (None, _) => return open + 1,
+                        (Some('/'), Some('*')) => {
+                            chars.next();
+                            depth += 1;
+                        }
+                        (Some('*'), Some('/')) => {
+                            chars.next();
+                            depth -= 1;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            '{' | '(' | '[' => open += 1,
+            '}' | ')' | ']' => open -= 1,
+            _ => (),
+        }
+    }
+    open
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_delimiters_outside_strings_and_comments() {
+        assert_eq!(open_delimiters("fn f() {"), 1);
+        assert_eq!(open_delimiters("fn f() {\n    1\n}"), 0);
+        assert_eq!(open_delimiters("let s = \"}\";"), 0);
+        assert_eq!(open_delimiters("let s = \"\\\" {\";"), 0);
+        assert_eq!(open_delimiters("let s = \"(\n"), 1);
+        assert_eq!(open_delimiters("f(1, // )\n"), 1);
+        assert_eq!(open_delimiters("f(/* ) /* ) */ */ 1)"), 0);
+        assert_eq!(open_delimiters("/* {\n"), 1);
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use sway_core::{
+    abi_generation::fuel_abi::ABI_SPEC_VERSION, asm_generation::backend::supported_vm_versions,
+    BuildTarget, ExperimentalFeature,
+};
+
+/// The versions of the ABI format that generated ABIs conform to, written to them as their
+/// `specVersion`.
+const FUEL_ABI_SPEC_VERSIONS: &[&str] = &[ABI_SPEC_VERSION];
+
+/// Machine readable information about the toolchain, for tools and CI to check that they are
+/// running against a compatible environment.
+///
+/// Fields are only ever added to this, so that consumers can rely on the existing ones.
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    pub forc_version: &'static str,
+    pub compiler_version: &'static str,
+    pub build_targets: Vec<BuildTarget>,
+    pub fuel_abi_spec_versions: &'static [&'static str],
+    pub fuel_vm_opcodes_version: &'static str,
+    pub experimental_features: Vec<ExperimentalFeature>,
+}
+
+impl VersionInfo {
+    pub fn current() -> Self {
+        Self {
+            forc_version: env!("CARGO_PKG_VERSION"),
+            compiler_version: sway_core::VERSION,
+            build_targets: BuildTarget::value_variants().to_vec(),
+            fuel_abi_spec_versions: FUEL_ABI_SPEC_VERSIONS,
+            // The newest revision of the Fuel VM is generated for unless a package chooses another.
+            fuel_vm_opcodes_version: supported_vm_versions(BuildTarget::Fuel)[0],
+            experimental_features: ExperimentalFeature::value_variants().to_vec(),
+        }
+    }
+}
+
+/// Prints the version of `forc`, or all of the [VersionInfo] as JSON if `json` is set.
+pub fn print_version(json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(&VersionInfo::current())?);
+    } else {
+        println!("forc {}", env!("CARGO_PKG_VERSION"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_info_json_fields() {
+        let json = serde_json::to_value(VersionInfo::current()).unwrap();
+        assert_eq!(json["forc_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["compiler_version"], sway_core::VERSION);
+        assert_eq!(
+            json["build_targets"],
+            serde_json::json!(["fuel", "evm", "midenvm"])
+        );
+        assert_eq!(
+            json["fuel_abi_spec_versions"],
+            serde_json::json!([ABI_SPEC_VERSION])
+        );
+        assert_eq!(
+            json["experimental_features"],
+            serde_json::json!(["if_let_chains"])
+        );
+    }
+}
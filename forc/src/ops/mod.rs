@@ -1,8 +1,13 @@
+pub mod forc_audit;
 pub mod forc_build;
 pub mod forc_check;
 pub mod forc_clean;
 pub mod forc_contract_id;
+pub mod forc_explain;
 pub mod forc_init;
+pub mod forc_playground;
 pub mod forc_predicate_root;
+pub mod forc_repl;
 pub mod forc_template;
 pub mod forc_update;
+pub mod forc_version;
@@ -65,6 +65,89 @@ fn main() -> bool {
     .into()
 }
 
+/// The Cargo manifest of the Rust SDK integration tests created by `forc init --with-tests`.
+pub(crate) fn default_tests_manifest(project_name: &str) -> String {
+    let author = get_author();
+
+    format!(
+        r#"[package]
+name = "{project_name}"
+description = "Rust SDK integration tests for the {project_name} contract."
+version = "0.1.0"
+edition = "2021"
+authors = ["{author}"]
+license = "Apache-2.0"
+
+[dev-dependencies]
+fuels = {{ version = "0.44", features = ["fuel-core-lib"] }}
+tokio = {{ version = "1.12", features = ["rt", "macros"] }}
+
+[[test]]
+harness = true
+name = "integration_tests"
+path = "tests/harness.rs"
+"#
+    )
+}
+
+/// The Rust SDK integration tests created by `forc init --with-tests`, generating bindings for the
+/// contract from the JSON ABI that `forc build` writes to `out/debug`.
+pub(crate) fn default_test_harness(project_name: &str) -> String {
+    format!(
+        r#"use fuels::{{prelude::*, types::ContractId}};
+
+// Load abi from json
+abigen!(Contract(
+    name = "MyContract",
+    abi = "out/debug/{project_name}-abi.json"
+));
+
+async fn get_contract_instance() -> (MyContract<WalletUnlocked>, ContractId) {{
+    // Launch a local network and deploy the contract
+    let mut wallets = launch_custom_provider_and_get_wallets(
+        WalletsConfig::new(
+            Some(1),             /* Single wallet */
+            Some(1),             /* Single coin (UTXO) */
+            Some(1_000_000_000), /* Amount per coin */
+        ),
+        None,
+        None,
+    )
+    .await;
+    let wallet = wallets.pop().unwrap();
+
+    let id = Contract::load_from(
+        "./out/debug/{project_name}.bin",
+        LoadConfiguration::default(),
+    )
+    .unwrap()
+    .deploy(&wallet, TxParameters::default())
+    .await
+    .unwrap();
+
+    let instance = MyContract::new(id.clone(), wallet);
+
+    (instance, id.into())
+}}
+
+#[tokio::test]
+async fn can_get_contract_id() {{
+    let (_instance, _id) = get_contract_instance().await;
+
+    // Now you have an instance of your contract you can use to test each function
+}}
+
+#[tokio::test]
+async fn can_call_test_function() {{
+    let (instance, _id) = get_contract_instance().await;
+
+    let result = instance.methods().test_function().call().await.unwrap();
+    assert!(result.value);
+}}
+"#
+    )
+}
+
 pub(crate) fn default_gitignore() -> String {
     r#"out
 target
@@ -92,3 +175,18 @@ fn parse_default_workspace_manifest() {
         toml::from_str::<forc_pkg::PackageManifest>(&default_workspace_manifest()).unwrap()
     )
 }
+
+#[test]
+fn parse_default_tests_manifest() {
+    let manifest = toml::from_str::<toml::Value>(&default_tests_manifest("test_proj")).unwrap();
+    assert_eq!(manifest["package"]["name"].as_str(), Some("test_proj"));
+    assert_eq!(manifest["package"]["edition"].as_str(), Some("2021"));
+    let dev_dependencies = manifest["dev-dependencies"].as_table().unwrap();
+    assert!(dev_dependencies.contains_key("fuels"));
+    assert!(dev_dependencies.contains_key("tokio"));
+    let tests = manifest["test"].as_array().unwrap();
+    assert_eq!(tests.len(), 1);
+    assert_eq!(tests[0]["name"].as_str(), Some("integration_tests"));
+    assert_eq!(tests[0]["path"].as_str(), Some("tests/harness.rs"));
+    assert_eq!(tests[0]["harness"].as_bool(), Some(true));
+}
@@ -0,0 +1,90 @@
+contract;
+
+use std::{
+    constants::{BASE_ASSET_ID, ZERO_B256},
+    inputs::input_owner,
+    storage::storage_vec::*,
+    token::transfer,
+};
+
+abi Vault {
+    fn withdraw_unchecked(amount: u64, to: Identity);
+    fn withdraw_checked(amount: u64, to: Identity);
+
+    #[storage(read, write)]
+    fn set_value_unrestricted(value: u64);
+    #[storage(read, write)]
+    fn set_value_restricted(value: u64);
+
+    #[storage(read)]
+    fn is_owner_by_input() -> bool;
+    #[storage(read)]
+    fn is_owner_by_sender() -> bool;
+
+    #[storage(read)]
+    fn sum_all() -> u64;
+    #[storage(read)]
+    fn sum_first(count: u64) -> u64;
+}
+
+storage {
+    owner: b256 = ZERO_B256,
+    value: u64 = 0,
+    values: StorageVec<u64> = StorageVec {},
+}
+
+impl Vault for Contract {
+    fn withdraw_unchecked(amount: u64, to: Identity) {
+        transfer(amount, BASE_ASSET_ID, to);
+    }
+
+    fn withdraw_checked(amount: u64, to: Identity) {
+        require(amount <= 100, "too much");
+        transfer(amount, BASE_ASSET_ID, to);
+    }
+
+    #[storage(read, write)]
+    fn set_value_unrestricted(value: u64) {
+        storage.value.write(value);
+    }
+
+    #[storage(read, write)]
+    fn set_value_restricted(value: u64) {
+        let owner = Identity::Address(Address::from(storage.owner.read()));
+        require(msg_sender().unwrap() == owner, "not the owner");
+        storage.value.write(value);
+    }
+
+    #[storage(read)]
+    fn is_owner_by_input() -> bool {
+        input_owner(0).unwrap() == Address::from(storage.owner.read())
+    }
+
+    #[storage(read)]
+    fn is_owner_by_sender() -> bool {
+        msg_sender().unwrap() == Identity::Address(Address::from(storage.owner.read()))
+    }
+
+    #[storage(read)]
+    fn sum_all() -> u64 {
+        let len = storage.values.len();
+        let mut sum = 0;
+        let mut i = 0;
+        while i < len {
+            sum += storage.values.get(i).unwrap().read();
+            i += 1;
+        }
+        sum
+    }
+
+    #[storage(read)]
+    fn sum_first(count: u64) -> u64 {
+        let mut sum = 0;
+        let mut i = 0;
+        while i < count {
+            sum += storage.values.get(i).unwrap().read();
+            i += 1;
+        }
+        sum
+    }
+}
@@ -12,6 +12,10 @@ pub struct Annotated<T> {
 //    #[attribute()]
 //    #[attribute(value)]
 //    #[attribute(value0, value1, value2)]
+//
+// except for the specification attributes, which have a condition instead:
+//
+//    #[requires(x > 0)]
 
 #[derive(Clone, Debug, Serialize)]
 pub struct AttributeDecl {
@@ -72,13 +76,17 @@ impl Spanned for AttributeArg {
 pub struct Attribute {
     pub name: Ident,
     pub args: Option<Parens<Punctuated<AttributeArg, CommaToken>>>,
+    /// The condition of a specification attribute, such as `#[requires(..)]`.
+    pub condition: Option<Parens<Box<Expr>>>,
 }
 
 impl Spanned for Attribute {
     fn span(&self) -> Span {
-        self.args
-            .as_ref()
-            .map(|args| Span::join(self.name.span(), args.span()))
+        let args_span = self.args.as_ref().map(|args| args.span());
+        let condition_span = self.condition.as_ref().map(|condition| condition.span());
+        args_span
+            .or(condition_span)
+            .map(|span| Span::join(self.name.span(), span))
             .unwrap_or_else(|| self.name.span())
     }
 }
@@ -15,7 +15,7 @@ pub struct AsmRegisterDeclaration {
 
 #[derive(Clone, Debug, Serialize)]
 pub struct AsmBlockContents {
-    pub instructions: Vec<(Instruction, SemicolonToken)>,
+    pub instructions: Vec<(Option<AsmLabel>, Instruction, SemicolonToken)>,
     pub final_expr_opt: Option<AsmFinalExpr>,
 }
 
@@ -25,10 +25,19 @@ pub struct AsmFinalExpr {
     pub ty_opt: Option<(ColonToken, Ty)>,
 }
 
+/// A label declared in front of an instruction, e.g. `loop_start: addi r1 r1 i1;`.
+#[derive(Clone, Debug, Serialize)]
+pub struct AsmLabel {
+    pub name: Ident,
+    pub colon_token: ColonToken,
+}
+
+/// An instruction immediate. Either a literal such as `i42`, or the name of a constant or of a
+/// label declared in the same block, in which case `parsed` is `None`.
 #[derive(Clone, Debug, Serialize)]
 pub struct AsmImmediate {
     pub span: Span,
-    pub parsed: BigUint,
+    pub parsed: Option<BigUint>,
 }
 
 impl Spanned for AsmImmediate {
@@ -37,6 +46,12 @@ impl Spanned for AsmImmediate {
     }
 }
 
+impl Spanned for AsmLabel {
+    fn span(&self) -> Span {
+        Span::join(self.name.span(), self.colon_token.span())
+    }
+}
+
 impl Spanned for AsmBlock {
     fn span(&self) -> Span {
         Span::join(self.asm_token.span(), self.contents.span())
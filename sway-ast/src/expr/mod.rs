@@ -315,6 +315,13 @@ pub enum IfCondition {
         eq_token: EqToken,
         rhs: Box<Expr>,
     },
+    /// Conditions chained with `&&` where at least one of them is a `let` condition,
+    /// e.g. `let Some(x) = a && x > 0`.
+    And {
+        lhs: Box<IfCondition>,
+        double_ampersand_token: DoubleAmpersandToken,
+        rhs: Box<IfCondition>,
+    },
 }
 
 #[derive(Clone, Debug, Serialize)]
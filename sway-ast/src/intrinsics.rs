@@ -21,6 +21,12 @@ pub enum Intrinsic {
     Add,
     Sub,
     Mul,
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
+    CheckedAdd,
+    CheckedSub,
+    CheckedMul,
     Div,
     And,
     Or,
@@ -33,6 +39,11 @@ pub enum Intrinsic {
     PtrSub,
     Smo,
     Not,
+    Gas,
+    Realloc,
+    HeapStats,
+    Memcpy,
+    Memcmp,
 }
 
 impl fmt::Display for Intrinsic {
@@ -57,6 +68,12 @@ impl fmt::Display for Intrinsic {
             Intrinsic::Add => "add",
             Intrinsic::Sub => "sub",
             Intrinsic::Mul => "mul",
+            Intrinsic::WrappingAdd => "wrapping_add",
+            Intrinsic::WrappingSub => "wrapping_sub",
+            Intrinsic::WrappingMul => "wrapping_mul",
+            Intrinsic::CheckedAdd => "checked_add",
+            Intrinsic::CheckedSub => "checked_sub",
+            Intrinsic::CheckedMul => "checked_mul",
             Intrinsic::Div => "div",
             Intrinsic::And => "and",
             Intrinsic::Or => "or",
@@ -69,6 +86,11 @@ impl fmt::Display for Intrinsic {
             Intrinsic::PtrSub => "ptr_sub",
             Intrinsic::Smo => "smo",
             Intrinsic::Not => "not",
+            Intrinsic::Gas => "gas",
+            Intrinsic::Realloc => "realloc",
+            Intrinsic::HeapStats => "heap_stats",
+            Intrinsic::Memcpy => "memcpy",
+            Intrinsic::Memcmp => "memcmp",
         };
         write!(f, "{s}")
     }
@@ -97,6 +119,12 @@ impl Intrinsic {
             "__add" => Add,
             "__sub" => Sub,
             "__mul" => Mul,
+            "__wrapping_add" => WrappingAdd,
+            "__wrapping_sub" => WrappingSub,
+            "__wrapping_mul" => WrappingMul,
+            "__checked_add" => CheckedAdd,
+            "__checked_sub" => CheckedSub,
+            "__checked_mul" => CheckedMul,
             "__div" => Div,
             "__and" => And,
             "__or" => Or,
@@ -109,6 +137,11 @@ impl Intrinsic {
             "__ptr_sub" => PtrSub,
             "__smo" => Smo,
             "__not" => Not,
+            "__gas" => Gas,
+            "__realloc" => Realloc,
+            "__heap_stats" => HeapStats,
+            "__memcpy" => Memcpy,
+            "__memcmp" => Memcmp,
             _ => return None,
         })
     }
@@ -38,6 +38,10 @@ pub enum ItemKind {
     Storage(ItemStorage),
     Configurable(ItemConfigurable),
     TypeAlias(ItemTypeAlias),
+    /// A malformed item.
+    ///
+    /// Used for parser recovery, holding the spans of the tokens skipped to reach the next item.
+    Error(Box<[Span]>),
 }
 
 impl Spanned for ItemKind {
@@ -55,6 +59,7 @@ impl Spanned for ItemKind {
             ItemKind::Storage(item_storage) => item_storage.span(),
             ItemKind::Configurable(item_configurable) => item_configurable.span(),
             ItemKind::TypeAlias(item_type_alias) => item_type_alias.span(),
+            ItemKind::Error(spans) => spans.iter().cloned().reduce(Span::join).unwrap(),
         }
     }
 }
@@ -85,6 +90,8 @@ pub enum FnArgs {
 
 #[derive(Clone, Debug, Serialize)]
 pub struct FnArg {
+    /// The attributes of the parameter, such as `#[param(min = 1)]`.
+    pub attribute_list: Vec<AttributeDecl>,
     pub pattern: Pattern,
     pub colon_token: ColonToken,
     pub ty: Ty,
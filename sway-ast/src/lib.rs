@@ -45,7 +45,7 @@ pub use crate::{
         FnArg, FnArgs, FnSignature, Item, ItemKind, TypeField,
     },
     keywords::{CommaToken, DoubleColonToken, PubToken},
-    literal::{LitInt, LitIntType, Literal},
+    literal::{LitByteString, LitInt, LitIntType, Literal},
     module::{Module, ModuleKind},
     path::{PathExpr, PathExprSegment, PathType, PathTypeSegment, QualifiedPathRoot},
     pattern::{Pattern, PatternStructField},
@@ -6,6 +6,13 @@ pub struct LitString {
     pub parsed: String,
 }
 
+/// A byte string literal, e.g. `b"\x00abc"`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash, Serialize)]
+pub struct LitByteString {
+    pub span: Span,
+    pub parsed: Vec<u8>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash, Serialize)]
 pub struct LitChar {
     pub span: Span,
@@ -55,6 +62,7 @@ impl From<LitBoolType> for bool {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash, Serialize)]
 pub enum Literal {
     String(LitString),
+    ByteString(LitByteString),
     Char(LitChar),
     Int(LitInt),
     Bool(LitBool),
@@ -66,6 +74,12 @@ impl Spanned for LitString {
     }
 }
 
+impl Spanned for LitByteString {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
 impl Spanned for LitChar {
     fn span(&self) -> Span {
         self.span.clone()
@@ -91,6 +105,7 @@ impl Spanned for Literal {
     fn span(&self) -> Span {
         match self {
             Literal::String(lit_string) => lit_string.span(),
+            Literal::ByteString(lit_byte_string) => lit_byte_string.span(),
             Literal::Char(lit_char) => lit_char.span(),
             Literal::Int(lit_int) => lit_int.span(),
             Literal::Bool(lit_bool) => lit_bool.span(),
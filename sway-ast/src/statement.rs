@@ -9,6 +9,11 @@ pub enum Statement {
         expr: Expr,
         semicolon_token_opt: Option<SemicolonToken>,
     },
+    /// A malformed statement.
+    ///
+    /// Used for parser recovery, holding the spans of the tokens skipped to reach the next
+    /// statement.
+    Error(Box<[Span]>),
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -33,6 +38,7 @@ impl Spanned for Statement {
                 None => expr.span(),
                 Some(semicolon_token) => Span::join(expr.span(), semicolon_token.span()),
             },
+            Statement::Error(spans) => spans.iter().cloned().reduce(Span::join).unwrap(),
         }
     }
 }
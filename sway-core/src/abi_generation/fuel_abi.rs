@@ -1,13 +1,17 @@
 use fuel_abi_types::program_abi;
-use sway_types::integer_bits::IntegerBits;
+use serde::{Deserialize, Serialize};
+use sway_types::{constants::PARAM_ATTRIBUTE_NAME, integer_bits::IntegerBits};
 
 use crate::{
     decl_engine::DeclEngine,
     language::{
-        ty::{TyConstantDecl, TyFunctionDecl, TyProgram, TyProgramKind},
+        ty::{TyConstantDecl, TyFunctionDecl, TyFunctionParameter, TyProgram, TyProgramKind},
         CallPath,
     },
+    semantic_analysis::storage_effects::{function_storage_effects, StorageEffects},
     transform::AttributesMap,
+    type_system::{Decls, Visiting, Walk},
+    types::RevertCode,
     TypeArgument, TypeEngine, TypeId, TypeInfo, TypeParameter,
 };
 
@@ -31,6 +35,7 @@ pub fn generate_program_abi(
             let logged_types = generate_logged_types(ctx, type_engine, decl_engine, types);
             let messages_types = generate_messages_types(ctx, type_engine, decl_engine, types);
             let configurables = generate_configurables(ctx, type_engine, decl_engine, types);
+            complete_recursive_types(types);
             program_abi::ProgramABI {
                 types: types.to_vec(),
                 functions,
@@ -46,6 +51,7 @@ pub fn generate_program_abi(
             let logged_types = generate_logged_types(ctx, type_engine, decl_engine, types);
             let messages_types = generate_messages_types(ctx, type_engine, decl_engine, types);
             let configurables = generate_configurables(ctx, type_engine, decl_engine, types);
+            complete_recursive_types(types);
             program_abi::ProgramABI {
                 types: types.to_vec(),
                 functions,
@@ -64,6 +70,178 @@ pub fn generate_program_abi(
     }
 }
 
+/// Gives the components of a recursive struct or enum to the declarations of it made where it
+/// refers to itself, which are cut short so as not to generate it forever.
+fn complete_recursive_types(types: &mut [program_abi::TypeDeclaration]) {
+    let complete = types
+        .iter()
+        .filter(|decl| decl.components.is_some())
+        .map(|decl| {
+            (
+                decl.type_field.clone(),
+                (decl.components.clone(), decl.type_parameters.clone()),
+            )
+        })
+        .collect::<Vec<_>>();
+    for decl in types.iter_mut() {
+        let is_aggregate =
+            decl.type_field.starts_with("struct ") || decl.type_field.starts_with("enum ");
+        if !is_aggregate || decl.components.is_some() {
+            continue;
+        }
+        if let Some((_, (components, type_parameters))) = complete
+            .iter()
+            .find(|(type_field, _)| *type_field == decl.type_field)
+        {
+            decl.components = components.clone();
+            decl.type_parameters = type_parameters.clone();
+        }
+    }
+}
+
+/// Information emitted alongside the standard JSON ABI, letting SDKs decode enum values and map
+/// on-chain reverts back to the `require`s and constants which caused them.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiExtensions {
+    /// The version of the ABI format, see [ABI_SPEC_VERSION].
+    pub spec_version: String,
+    pub enum_discriminants: Vec<EnumDiscriminants>,
+    pub revert_codes: Vec<AbiRevertCode>,
+    /// The ABI types of the enums logged as errors before reverting, e.g. by `require`.
+    pub error_types: Vec<usize>,
+}
+
+/// The version of the ABI format, bumped whenever SDKs need to be updated to decode it.
+///
+/// Version 2 added the revert codes and the error types, along with the logs of the errors.
+pub const ABI_SPEC_VERSION: &str = "2";
+
+/// The discriminant of every variant of the enum declared by the ABI type `type_id`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumDiscriminants {
+    pub type_id: usize,
+    pub variants: Vec<EnumVariantDiscriminant>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumVariantDiscriminant {
+    pub name: String,
+    pub discriminant: u64,
+}
+
+/// A revert code the program is known to produce.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiRevertCode {
+    /// The code in decimal. It is a string since codes such as `FAILED_REQUIRE_SIGNAL` do not fit
+    /// the integers of every JSON consumer.
+    pub code: String,
+    /// The constant the code was given as, e.g. `FAILED_REQUIRE_SIGNAL`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The source of the call leading to the revert, e.g. `require(amount > 0, "amount too low")`.
+    pub call_site: String,
+    /// The ID in `loggedTypes` of the log made by the same call before reverting, e.g. of the
+    /// error given to `require`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_id: Option<u64>,
+}
+
+/// A JSON ABI followed by its extensions, as written to the ABI file.
+#[derive(Serialize)]
+pub struct ExtendedProgramABI<'a> {
+    #[serde(flatten)]
+    pub program_abi: &'a program_abi::ProgramABI,
+    #[serde(flatten)]
+    pub extensions: &'a AbiExtensions,
+}
+
+/// Generates the revert codes of `program`, in the order they are first reached.
+pub fn generate_revert_codes(program: &TyProgram) -> Vec<AbiRevertCode> {
+    program
+        .revert_codes
+        .iter()
+        .map(
+            |RevertCode {
+                 code,
+                 name,
+                 call_site,
+                 log_id,
+             }| AbiRevertCode {
+                code: code.to_string(),
+                name: name.as_ref().map(|name| name.as_str().to_string()),
+                call_site: call_site.as_str().to_string(),
+                log_id: log_id.map(|log_id| *log_id as u64),
+            },
+        )
+        .collect()
+}
+
+/// Generates the ABI extensions of `program_abi`.
+///
+/// This must be called on the final ABI, i.e. once its type IDs have been standardized, since
+/// enum discriminants and error types refer to its types by ID.
+pub fn generate_abi_extensions(
+    program_abi: &program_abi::ProgramABI,
+    revert_codes: Vec<AbiRevertCode>,
+) -> AbiExtensions {
+    // The components of an enum type are its variants, in declaration order, so the index of a
+    // component is the discriminant of its variant.
+    let enum_discriminants = program_abi
+        .types
+        .iter()
+        .filter(|decl| decl.type_field.starts_with("enum "))
+        .filter_map(|decl| {
+            Some(EnumDiscriminants {
+                type_id: decl.type_id,
+                variants: decl
+                    .components
+                    .as_ref()?
+                    .iter()
+                    .enumerate()
+                    .map(|(discriminant, variant)| EnumVariantDiscriminant {
+                        name: variant.name.clone(),
+                        discriminant: discriminant as u64,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+
+    let mut error_types = vec![];
+    for log_id in revert_codes
+        .iter()
+        .filter_map(|revert_code| revert_code.log_id)
+    {
+        let logged_type = program_abi
+            .logged_types
+            .iter()
+            .flatten()
+            .find(|logged_type| logged_type.log_id == log_id);
+        let Some(logged_type) = logged_type else {
+            continue;
+        };
+        let type_id = logged_type.application.type_id;
+        let is_enum = program_abi
+            .types
+            .iter()
+            .any(|decl| decl.type_id == type_id && decl.type_field.starts_with("enum "));
+        if is_enum && !error_types.contains(&type_id) {
+            error_types.push(type_id);
+        }
+    }
+
+    AbiExtensions {
+        spec_version: ABI_SPEC_VERSION.to_string(),
+        enum_discriminants,
+        revert_codes,
+        error_types,
+    }
+}
+
 fn generate_logged_types(
     ctx: &mut AbiContext,
     type_engine: &TypeEngine,
@@ -337,6 +515,10 @@ impl TypeId {
     ) -> Option<Vec<program_abi::TypeApplication>> {
         match type_engine.get(*self) {
             TypeInfo::Enum(decl_ref) => {
+                // Where a recursive enum refers to itself, its components are left to
+                // `complete_recursive_types`
+                let _visiting =
+                    Visiting::enter(Walk::AbiComponents, Decls::enums(&decl_ref, &decl_ref))?;
                 let decl = decl_engine.get_enum(&decl_ref);
                 // A list of all `program_abi::TypeDeclaration`s needed for the enum variants
                 let variants = decl
@@ -388,6 +570,8 @@ impl TypeId {
                 )
             }
             TypeInfo::Struct(decl_ref) => {
+                let _visiting =
+                    Visiting::enter(Walk::AbiComponents, Decls::structs(&decl_ref, &decl_ref))?;
                 let decl = decl_engine.get_struct(&decl_ref);
 
                 // A list of all `program_abi::TypeDeclaration`s needed for the struct fields
@@ -959,26 +1143,60 @@ impl TyFunctionDecl {
                     self.return_type.type_id,
                 ),
             },
-            attributes: generate_attributes_map(&self.attributes),
+            attributes: generate_attributes_map(
+                &self.attributes,
+                &self.parameters,
+                &function_storage_effects(type_engine, decl_engine, self),
+            ),
         }
     }
 }
 
-fn generate_attributes_map(attr_map: &AttributesMap) -> Option<Vec<program_abi::Attribute>> {
-    if attr_map.is_empty() {
-        None
-    } else {
-        Some(
-            attr_map
-                .iter()
-                .flat_map(|(_attr_kind, attrs)| {
-                    attrs.iter().map(|attr| program_abi::Attribute {
-                        name: attr.name.to_string(),
-                        arguments: attr.args.iter().map(|arg| arg.name.to_string()).collect(),
-                    })
-                })
+/// The attributes of a function, along with a `param` attribute for each parameter validating its
+/// arguments, whose arguments are the name of the parameter and its bounds, e.g.
+/// `["amount", "min = 1", "max = 100"]`, and the `storage_reads` and `storage_writes` attributes
+/// whose arguments are the storage fields the function reads and writes, e.g. `["count"]`, where
+/// `*` stands for the slots accessed outside of the storage fields.
+fn generate_attributes_map(
+    attr_map: &AttributesMap,
+    parameters: &[TyFunctionParameter],
+    storage_effects: &StorageEffects,
+) -> Option<Vec<program_abi::Attribute>> {
+    let param_attrs = parameters.iter().filter_map(|param| {
+        let validation = param.validation.as_ref()?;
+        Some(program_abi::Attribute {
+            name: PARAM_ATTRIBUTE_NAME.to_string(),
+            arguments: std::iter::once(param.name.to_string())
+                .chain(validation.to_attribute_args())
                 .collect(),
+        })
+    });
+    let attrs: Vec<_> = attr_map
+        .iter()
+        .flat_map(|(_attr_kind, attrs)| {
+            attrs.iter().map(|attr| program_abi::Attribute {
+                name: attr.name.to_string(),
+                arguments: attr.args.iter().map(|arg| arg.name.to_string()).collect(),
+            })
+        })
+        .chain(param_attrs)
+        .chain(
+            [
+                ("storage_reads", &storage_effects.reads),
+                ("storage_writes", &storage_effects.writes),
+            ]
+            .into_iter()
+            .filter(|(_, fields)| !fields.is_empty())
+            .map(|(name, fields)| program_abi::Attribute {
+                name: name.to_string(),
+                arguments: fields.iter().cloned().collect(),
+            }),
         )
+        .collect();
+    if attrs.is_empty() {
+        None
+    } else {
+        Some(attrs)
     }
 }
 
@@ -1026,3 +1244,94 @@ impl TypeArgument {
             .abi_str(ctx, type_engine, decl_engine)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn application(name: &str, type_id: usize) -> program_abi::TypeApplication {
+        program_abi::TypeApplication {
+            name: name.to_string(),
+            type_id,
+            type_arguments: None,
+        }
+    }
+
+    fn declaration(
+        type_id: usize,
+        type_field: &str,
+        components: Option<Vec<program_abi::TypeApplication>>,
+    ) -> program_abi::TypeDeclaration {
+        program_abi::TypeDeclaration {
+            type_id,
+            type_field: type_field.to_string(),
+            components,
+            type_parameters: None,
+        }
+    }
+
+    fn logged_type(log_id: u64, type_id: usize) -> program_abi::LoggedType {
+        program_abi::LoggedType {
+            log_id,
+            application: application("", type_id),
+        }
+    }
+
+    fn revert_code(log_id: Option<u64>) -> AbiRevertCode {
+        AbiRevertCode {
+            code: "18446744073709486080".to_string(),
+            name: Some("FAILED_REQUIRE_SIGNAL".to_string()),
+            call_site: "require(condition, error)".to_string(),
+            log_id,
+        }
+    }
+
+    #[test]
+    fn error_types_are_the_enums_logged_before_reverting() {
+        let program_abi = program_abi::ProgramABI {
+            types: vec![
+                declaration(0, "()", None),
+                declaration(
+                    1,
+                    "enum MyError",
+                    Some(vec![
+                        application("ZeroAmount", 0),
+                        application("TooLarge", 3),
+                    ]),
+                ),
+                declaration(2, "struct Details", Some(vec![application("amount", 3)])),
+                declaration(3, "u64", None),
+                declaration(4, "enum Event", Some(vec![application("Called", 0)])),
+            ],
+            functions: vec![],
+            logged_types: Some(vec![
+                logged_type(0, 1),
+                logged_type(1, 2),
+                logged_type(2, 1),
+                logged_type(3, 4),
+            ]),
+            messages_types: None,
+            configurables: None,
+        };
+
+        // Only enums are error types, each one listed once, and only if a revert follows their
+        // log, which isn't the case for the `Event` logged with ID 3.
+        let extensions = generate_abi_extensions(
+            &program_abi,
+            vec![
+                revert_code(Some(0)),
+                revert_code(Some(1)),
+                revert_code(None),
+                revert_code(Some(2)),
+            ],
+        );
+        assert_eq!(extensions.spec_version, ABI_SPEC_VERSION);
+        assert_eq!(extensions.error_types, vec![1]);
+
+        let json = serde_json::to_value(&extensions).unwrap();
+        assert_eq!(json["specVersion"], ABI_SPEC_VERSION);
+        assert_eq!(json["errorTypes"], serde_json::json!([1]));
+        assert_eq!(json["revertCodes"][0]["logId"], 0);
+        assert!(json["revertCodes"][2].get("logId").is_none());
+    }
+}
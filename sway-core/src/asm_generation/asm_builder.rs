@@ -1,6 +1,6 @@
 use sway_ir::Function;
 
-use crate::{asm_lang::Label, CompileResult};
+use crate::{asm_lang::Label, debug_info::DebugVariable, CompileResult};
 
 use super::{
     evm::EvmAsmBuilderResult, fuel::fuel_asm_builder::FuelAsmBuilderResult,
@@ -17,4 +17,10 @@ pub trait AsmBuilder {
     fn func_to_labels(&mut self, func: &Function) -> (Label, Label);
     fn compile_function(&mut self, function: Function) -> CompileResult<()>;
     fn finalize(&self) -> AsmBuilderResult;
+
+    /// The args and locals of the compiled `function`, for the debug info.  Backends which
+    /// don't describe them return none.
+    fn debug_variables(&self, _function: Function) -> (Vec<DebugVariable>, Vec<DebugVariable>) {
+        (vec![], vec![])
+    }
 }
@@ -0,0 +1,346 @@
+//! The code generation backends, one for each VM that Sway programs can be compiled for.
+//!
+//! A [Backend] is selected by the [Target] being built for, i.e. a VM and, optionally, a revision
+//! of it. The IR is lowered the same way for every backend, function by function with the
+//! backend's [AsmBuilder], after which the backend assembles the final program.
+//!
+//! Generating code for a new VM means adding a backend to [BACKENDS], and generating code for a
+//! new revision of a VM means adding it to the [Backend::vm_versions] of its backend, so that
+//! builds can choose between the revisions with the target declared in their manifest.
+//!
+//! [Target]: crate::Target
+
+use super::{
+    asm_builder::{AsmBuilder, AsmBuilderResult},
+    evm::EvmAsmBuilder,
+    fuel::{
+        capabilities, data_section::DataSection, fuel_asm_builder::FuelAsmBuilder,
+        register_sequencer::RegisterSequencer,
+    },
+    programs::{AbstractEntry, AbstractProgram, FinalProgram, ProgramKind},
+    MidenVMAsmBuilder,
+};
+use crate::{err, ok, BuildConfig, BuildTarget, CompileResult, Engines};
+use std::path::Path;
+use sway_error::{error::CompileError, handler::Handler};
+use sway_ir::Context;
+use sway_types::Span;
+
+/// Generates the code of programs for a VM.
+pub(crate) trait Backend: Sync {
+    /// The VM that this backend generates code for.
+    fn build_target(&self) -> BuildTarget;
+
+    /// The revisions of the VM that this backend can generate code for, the newest first.
+    ///
+    /// Empty if the backend does not distinguish between revisions of its VM.
+    fn vm_versions(&self) -> &'static [&'static str];
+
+    /// Creates the builder generating the code of the functions of a program of the given kind.
+    ///
+    /// The `vm_version` is one of the [Backend::vm_versions], if the backend has any.
+    fn can_return_from_calls(&self, vm_version: Option<&str>) -> bool {
+        // Returns jump to the address in $reta, with an opcode older revisions don't have.
+        vm_version.map_or(true, |vm_version| {
+            capabilities::unavailable_in("jmp", vm_version).is_none()
+        })
+    }
+
+    fn asm_builder<'ir, 'eng>(
+        &self,
+        kind: ProgramKind,
+        vm_version: Option<&str>,
+        context: &'ir Context<'eng>,
+    ) -> Box<dyn AsmBuilder + 'ir>;
+
+    /// Checks that the program only uses what is available in the `vm_version` of the VM,
+    /// reporting the uses of anything newer at their source.
+    ///
+    /// Called on the unoptimized IR of the package within `package_dir`.
+    fn check_capabilities(
+        &self,
+        _handler: &Handler,
+        _engines: &Engines,
+        _context: &Context,
+        _vm_version: Option<&str>,
+        _package_dir: &Path,
+    ) {
+    }
+
+    /// Whether the generated code can return from calls to the functions of the program in the
+    /// `vm_version` of the VM. If it can't, all calls are inlined before generating code.
+    fn can_return_from_calls(&self, _vm_version: Option<&str>) -> bool {
+        true
+    }
+
+    /// Assembles the final program from the result of the backend's builder.
+    fn finalize_program(
+        &self,
+        kind: ProgramKind,
+        context: &Context,
+        result: AsmBuilderResult,
+        build_config: Option<&BuildConfig>,
+    ) -> CompileResult<FinalProgram>;
+}
+
+/// All of the backends, one for each [BuildTarget].
+const BACKENDS: &[&dyn Backend] = &[&FuelBackend, &EvmBackend, &MidenVMBackend];
+
+/// Returns the backend generating code for the given revision of the `build_target` VM, along
+/// with the revision to generate code for, the newest one if `vm_version` is `None`.
+///
+/// Forc rejects unsupported revisions when validating the `target` of the manifest, where they
+/// come from, so the error here, which has no source to point at, only reaches other users of the
+/// compiler.
+pub(crate) fn backend_for<'a>(
+    build_target: BuildTarget,
+    vm_version: Option<&'a str>,
+) -> Result<(&'static dyn Backend, Option<&'a str>), CompileError> {
+    let backend = *BACKENDS
+        .iter()
+        .find(|backend| backend.build_target() == build_target)
+        .expect("every build target has a backend");
+    let vm_versions = backend.vm_versions();
+    match vm_version {
+        None => Ok((backend, vm_versions.first().copied())),
+        Some(vm_version) if vm_versions.contains(&vm_version) => Ok((backend, Some(vm_version))),
+        Some(vm_version) => Err(CompileError::UnsupportedVmVersion {
+            build_target: build_target.to_string(),
+            vm_version: vm_version.to_string(),
+            supported_vm_versions: vm_versions.iter().map(|v| v.to_string()).collect(),
+            span: Span::dummy(),
+        }),
+    }
+}
+
+/// Returns the revisions of the `build_target` VM that the compiler can generate code for, the
+/// newest first.
+pub fn supported_vm_versions(build_target: BuildTarget) -> &'static [&'static str] {
+    BACKENDS
+        .iter()
+        .find(|backend| backend.build_target() == build_target)
+        .map(|backend| backend.vm_versions())
+        .unwrap_or_default()
+}
+
+struct FuelBackend;
+
+impl Backend for FuelBackend {
+    fn build_target(&self) -> BuildTarget {
+        BuildTarget::Fuel
+    }
+
+    fn vm_versions(&self) -> &'static [&'static str] {
+        &["0.34", "0.25"]
+    }
+
+    fn check_capabilities(
+        &self,
+        handler: &Handler,
+        engines: &Engines,
+        context: &Context,
+        vm_version: Option<&str>,
+        package_dir: &Path,
+    ) {
+        if let Some(vm_version) = vm_version {
+            capabilities::check_vm_capabilities(handler, engines, context, vm_version, package_dir);
+        }
+    }
+
+    fn asm_builder<'ir, 'eng>(
+        &self,
+        kind: ProgramKind,
+        vm_version: Option<&str>,
+        context: &'ir Context<'eng>,
+    ) -> Box<dyn AsmBuilder + 'ir> {
+        Box::new(FuelAsmBuilder::new(
+            kind,
+            DataSection::default(),
+            RegisterSequencer::new(),
+            context,
+            vm_version,
+        ))
+    }
+
+    fn finalize_program(
+        &self,
+        kind: ProgramKind,
+        context: &Context,
+        result: AsmBuilderResult,
+        build_config: Option<&BuildConfig>,
+    ) -> CompileResult<FinalProgram> {
+        let AsmBuilderResult::Fuel((data_section, reg_seqr, entries, non_entries)) = result else {
+            unreachable!("the Fuel builder only builds Fuel programs");
+        };
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+
+        let entries = entries
+            .into_iter()
+            .map(|(func, label, ops, test_decl_ref)| {
+                let selector = func.get_selector(context);
+                let name = func.get_name(context).to_string();
+                AbstractEntry {
+                    test_decl_ref,
+                    selector,
+                    label,
+                    ops,
+                    name,
+                }
+            })
+            .collect();
+
+        let abstract_program =
+            AbstractProgram::new(kind, data_section, entries, non_entries, reg_seqr);
+
+        if build_config
+            .map(|cfg| cfg.print_intermediate_asm)
+            .unwrap_or(false)
+        {
+            println!(";; --- ABSTRACT VIRTUAL PROGRAM ---\n");
+            println!("{abstract_program}\n");
+        }
+
+        let allocated_program = check!(
+            CompileResult::from(abstract_program.into_allocated_program()),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+
+        if build_config
+            .map(|cfg| cfg.print_intermediate_asm)
+            .unwrap_or(false)
+        {
+            println!(";; --- ABSTRACT ALLOCATED PROGRAM ---\n");
+            println!("{allocated_program}");
+        }
+
+        let final_program = check!(
+            CompileResult::from(allocated_program.into_final_program()),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+
+        if let (Some(vm_version), FinalProgram::Fuel { ops, .. }) = (
+            build_config.and_then(|cfg| cfg.vm_version.as_deref()),
+            &final_program,
+        ) {
+            errors.extend(capabilities::check_final_ops(ops, vm_version));
+            if !errors.is_empty() {
+                return err(warnings, errors);
+            }
+        }
+        ok(final_program, warnings, errors)
+    }
+}
+
+struct EvmBackend;
+
+impl Backend for EvmBackend {
+    fn build_target(&self) -> BuildTarget {
+        BuildTarget::EVM
+    }
+
+    fn vm_versions(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn asm_builder<'ir, 'eng>(
+        &self,
+        kind: ProgramKind,
+        _vm_version: Option<&str>,
+        context: &'ir Context<'eng>,
+    ) -> Box<dyn AsmBuilder + 'ir> {
+        Box::new(EvmAsmBuilder::new(kind, context))
+    }
+
+    fn finalize_program(
+        &self,
+        _kind: ProgramKind,
+        _context: &Context,
+        result: AsmBuilderResult,
+        _build_config: Option<&BuildConfig>,
+    ) -> CompileResult<FinalProgram> {
+        let AsmBuilderResult::Evm(result) = result else {
+            unreachable!("the EVM builder only builds EVM programs");
+        };
+        ok(
+            FinalProgram::Evm {
+                ops: result.ops,
+                abi: result.abi,
+            },
+            vec![],
+            vec![],
+        )
+    }
+}
+
+struct MidenVMBackend;
+
+impl Backend for MidenVMBackend {
+    fn build_target(&self) -> BuildTarget {
+        BuildTarget::MidenVM
+    }
+
+    fn vm_versions(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn asm_builder<'ir, 'eng>(
+        &self,
+        kind: ProgramKind,
+        _vm_version: Option<&str>,
+        context: &'ir Context<'eng>,
+    ) -> Box<dyn AsmBuilder + 'ir> {
+        Box::new(MidenVMAsmBuilder::new(kind, context))
+    }
+
+    fn finalize_program(
+        &self,
+        _kind: ProgramKind,
+        _context: &Context,
+        result: AsmBuilderResult,
+        _build_config: Option<&BuildConfig>,
+    ) -> CompileResult<FinalProgram> {
+        let AsmBuilderResult::MidenVM(result) = result else {
+            unreachable!("the MidenVM builder only builds MidenVM programs");
+        };
+        ok(FinalProgram::MidenVM { ops: result.ops }, vec![], vec![])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backends_are_selected_by_target() {
+        let (backend, vm_version) = backend_for(BuildTarget::Fuel, None).unwrap();
+        assert_eq!(backend.build_target(), BuildTarget::Fuel);
+        assert_eq!(vm_version, Some("0.34"));
+        assert!(backend_for(BuildTarget::Fuel, Some("0.34")).is_ok());
+
+        let error = backend_for(BuildTarget::Fuel, Some("0.1")).err().unwrap();
+        assert!(matches!(error, CompileError::UnsupportedVmVersion { .. }));
+        assert!(backend_for(BuildTarget::EVM, Some("0.34")).is_err());
+        assert_eq!(backend_for(BuildTarget::EVM, None).unwrap().1, None);
+    }
+
+    #[test]
+    fn targets_are_parsed() {
+        use crate::Target;
+        use std::str::FromStr;
+
+        let target = Target::from_str("fuel-0.34").unwrap();
+        assert_eq!(
+            target,
+            Target::new(BuildTarget::Fuel, Some("0.34".to_string()))
+        );
+        assert_eq!(target.to_string(), "fuel-0.34");
+        assert_eq!(Target::from_str("evm").unwrap(), BuildTarget::EVM.into());
+        assert!(Target::from_str("fuel-").is_err());
+        assert!(Target::from_str("wasm-1").is_err());
+    }
+}
@@ -1,10 +1,11 @@
-use super::instruction_set::InstructionSet;
+use super::InstructionSet;
 use super::ToMidenBytecode;
 use super::{
     fuel::{checks, data_section::DataSection},
     ProgramABI, ProgramKind,
 };
-use crate::asm_lang::allocated_ops::{AllocatedOp, AllocatedOpcode};
+use crate::asm_lang::allocated_ops::AllocatedOp;
+use crate::debug_info::DebugInfo;
 use crate::decl_engine::DeclRefFunction;
 use crate::error::*;
 use crate::source_map::SourceMap;
@@ -15,6 +16,7 @@ use sway_types::span::Span;
 use sway_types::SourceEngine;
 
 use either::Either;
+use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fmt};
 
 /// Represents an ASM set which has had register allocation, jump elimination, and optimization
@@ -26,6 +28,12 @@ pub struct FinalizedAsm {
     pub program_kind: ProgramKind,
     pub entries: Vec<FinalizedEntry>,
     pub abi: Option<ProgramABI>,
+    /// The size of each function in the bytecode, largest first.
+    pub function_sizes: Vec<FunctionSize>,
+    /// The estimated gas used by each entry point which isn't a test.
+    pub gas_estimates: Vec<GasEstimate>,
+    /// The functions and variables of the program, for debuggers.
+    pub debug_info: DebugInfo,
 }
 
 #[derive(Clone, Debug)]
@@ -41,11 +49,45 @@ pub struct FinalizedEntry {
     pub test_decl_ref: Option<DeclRefFunction>,
 }
 
+/// The contribution of a function to the size of the bytecode.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionSize {
+    /// The name of the function.
+    pub name: String,
+    /// The signature of the function in the IR, e.g. `transfer(u64, b256) -> ()`, which tells
+    /// apart the instances of a generic function.
+    pub signature: String,
+    /// The number of bytes of the function's instructions.
+    pub bytes: u64,
+    /// The number of instructions of the function.
+    pub instructions: u64,
+}
+
+/// A static estimate of the gas used by an entry point of a program, e.g. an ABI method.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasEstimate {
+    /// The name of the entry function.
+    pub name: String,
+    /// The gas used along the most expensive path through the entry function and the functions
+    /// it calls, or `None` if it loops or recurses, so the gas it uses can't be bounded.
+    pub max_gas: Option<u64>,
+    /// Whether the entry function uses instructions whose cost depends on the size of their
+    /// operands or on another contract, e.g. `MCP` or `CALL`, of which only the base cost is
+    /// included in `max_gas`.
+    pub has_dynamic_costs: bool,
+}
+
 /// The bytecode for a sway program as well as the byte offsets of configuration-time constants in
 /// the bytecode.
 pub struct CompiledBytecode {
     pub bytecode: Vec<u8>,
     pub config_const_offsets: BTreeMap<String, u64>,
+    /// The size of each function in the bytecode, largest first.
+    pub function_sizes: Vec<FunctionSize>,
+    /// The estimated gas used by each entry point which isn't a test.
+    pub gas_estimates: Vec<GasEstimate>,
+    /// The functions and variables of the program, for debuggers.
+    pub debug_info: DebugInfo,
 }
 
 impl FinalizedAsm {
@@ -56,7 +98,14 @@ impl FinalizedAsm {
     ) -> CompileResult<CompiledBytecode> {
         match &self.program_section {
             InstructionSet::Fuel { ops } => {
-                to_bytecode_mut(ops, &mut self.data_section, source_map, source_engine)
+                to_bytecode_mut(ops, &mut self.data_section, source_map, source_engine).map(
+                    |bytecode| CompiledBytecode {
+                        function_sizes: self.function_sizes.clone(),
+                        gas_estimates: self.gas_estimates.clone(),
+                        debug_info: self.debug_info.clone(),
+                        ..bytecode
+                    },
+                )
             }
             InstructionSet::Evm { ops } => {
                 let mut assembler = Assembler::new();
@@ -70,6 +119,9 @@ impl FinalizedAsm {
                         CompiledBytecode {
                             bytecode: assembler.take(),
                             config_const_offsets: BTreeMap::new(),
+                            function_sizes: vec![],
+                            gas_estimates: vec![],
+                            debug_info: DebugInfo::default(),
                         },
                         vec![],
                         vec![],
@@ -80,6 +132,9 @@ impl FinalizedAsm {
                 CompiledBytecode {
                     bytecode: ops.to_bytecode().into(),
                     config_const_offsets: Default::default(),
+                    function_sizes: vec![],
+                    gas_estimates: vec![],
+                    debug_info: DebugInfo::default(),
                 },
                 vec![],
                 vec![],
@@ -125,17 +180,11 @@ fn to_bytecode_mut(
     // this points at the byte (*4*8) address immediately following (+1) the last instruction
     // Some LWs are expanded into two ops to allow for data larger than one word, so we calculate
     // exactly how many ops will be generated to calculate the offset.
-    let offset_to_data_section_in_bytes = ops.iter().fold(0, |acc, item| match &item.opcode {
-        AllocatedOpcode::LWDataId(_reg, data_label)
-            if !data_section
-                .has_copy_type(data_label)
-                .expect("data label references non existent data -- internal error") =>
-        {
-            acc + 8
-        }
-        AllocatedOpcode::BLOB(count) => acc + count.value as u64 * 4,
-        _ => acc + 4,
-    }) + 4;
+    let offset_to_data_section_in_bytes = ops
+        .iter()
+        .map(|op| op.size_in_bytes(data_section))
+        .sum::<u64>()
+        + 4;
 
     // each op is four bytes, so the length of the buf is the number of ops times four.
     let mut buf = vec![0; (ops.len() * 4) + 4];
@@ -143,6 +192,7 @@ fn to_bytecode_mut(
     let mut half_word_ix = 0;
     for op in ops.iter() {
         let span = op.owning_span.clone();
+        let inlined_at = op.inlined_at.clone();
         let op = op.to_fuel_asm(offset_to_data_section_in_bytes, data_section);
         match op {
             Either::Right(data) => {
@@ -157,7 +207,7 @@ fn to_bytecode_mut(
                 }
                 for op in ops {
                     if let Some(span) = &span {
-                        source_map.insert(source_engine, half_word_ix, span);
+                        source_map.insert(source_engine, half_word_ix, span, &inlined_at);
                     }
                     let read_range_upper_bound =
                         core::cmp::min(half_word_ix * 4 + std::mem::size_of_val(&op), buf.len());
@@ -187,6 +237,9 @@ fn to_bytecode_mut(
         CompiledBytecode {
             bytecode: buf,
             config_const_offsets: config_offsets,
+            function_sizes: vec![],
+            gas_estimates: vec![],
+            debug_info: DebugInfo::default(),
         },
         vec![],
         errors,
@@ -194,14 +247,16 @@ fn to_bytecode_mut(
 }
 
 /// Checks for disallowed opcodes in non-contract code.
-/// i.e., if this is a script or predicate, we can't use certain contract opcodes.
+/// i.e., if this is a script, we can't use certain contract opcodes. Predicates are verified
+/// when they're compiled, where the functions their opcodes are in are known.
 /// See https://github.com/FuelLabs/sway/issues/350 for details.
 pub fn check_invalid_opcodes(asm: &FinalizedAsm) -> CompileResult<()> {
     match &asm.program_section {
         InstructionSet::Fuel { ops } => match asm.program_kind {
-            ProgramKind::Contract | ProgramKind::Library => ok((), vec![], vec![]),
+            ProgramKind::Contract | ProgramKind::Library | ProgramKind::Predicate => {
+                ok((), vec![], vec![])
+            }
             ProgramKind::Script => checks::check_script_opcodes(&ops[..]),
-            ProgramKind::Predicate => checks::check_predicate_opcodes(&ops[..]),
         },
         InstructionSet::Evm { ops: _ } => ok((), vec![], vec![]),
         InstructionSet::MidenVM { ops: _ } => ok((), vec![], vec![]),
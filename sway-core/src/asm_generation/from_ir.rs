@@ -1,20 +1,25 @@
 use super::{
-    asm_builder::{AsmBuilder, AsmBuilderResult},
-    evm::EvmAsmBuilder,
-    finalized_asm::{check_invalid_opcodes, FinalizedAsm},
-    fuel::{
-        data_section::{DataId, DataSection},
-        fuel_asm_builder::FuelAsmBuilder,
-        register_sequencer::RegisterSequencer,
-    },
-    programs::{AbstractEntry, AbstractProgram, FinalProgram, ProgramKind},
-    MidenVMAsmBuilder,
+    backend::backend_for,
+    finalized_asm::{check_invalid_opcodes, FinalizedAsm, FunctionSize},
+    fuel::{checks, data_section::DataId},
+    programs::{FinalProgram, ProgramKind},
 };
 
-use crate::{err, ok, BuildConfig, BuildTarget, CompileResult, CompileWarning};
+use crate::{
+    asm_lang::Label,
+    debug_info::{DebugFunction, DebugInfo, DebugVariable},
+    err,
+    metadata::MetadataManager,
+    ok,
+    source_map::LocationRange,
+    BuildConfig, BuildTarget, CompileResult, CompileWarning,
+};
 
 use sway_error::error::CompileError;
 use sway_ir::*;
+use sway_types::{constants::DEFAULT_ENTRY_POINT_FN_NAME, Span};
+
+use std::{collections::HashMap, fmt::Write};
 
 pub fn compile_ir_to_asm(
     ir: &Context,
@@ -30,8 +35,8 @@ pub fn compile_ir_to_asm(
     let mut errors: Vec<CompileError> = Vec::new();
 
     let module = ir.module_iter().next().unwrap();
-    let final_program = check!(
-        compile_module_to_asm(RegisterSequencer::new(), ir, module, build_config),
+    let (final_program, function_sizes, debug_info) = check!(
+        compile_module_to_asm(ir, module, build_config),
         return err(warnings, errors),
         warnings,
         errors
@@ -45,7 +50,9 @@ pub fn compile_ir_to_asm(
         println!("{final_program}");
     }
 
-    let final_asm = final_program.finalize();
+    let mut final_asm = final_program.finalize();
+    final_asm.function_sizes = function_sizes;
+    final_asm.debug_info = debug_info;
 
     check!(
         check_invalid_opcodes(&final_asm),
@@ -58,11 +65,10 @@ pub fn compile_ir_to_asm(
 }
 
 fn compile_module_to_asm(
-    reg_seqr: RegisterSequencer,
     context: &Context,
     module: Module,
     build_config: Option<&BuildConfig>,
-) -> CompileResult<FinalProgram> {
+) -> CompileResult<(FinalProgram, Vec<FunctionSize>, DebugInfo)> {
     let kind = match module.get_kind(context) {
         Kind::Contract => ProgramKind::Contract,
         Kind::Library => ProgramKind::Library,
@@ -70,30 +76,28 @@ fn compile_module_to_asm(
         Kind::Script => ProgramKind::Script,
     };
 
-    let build_target = match build_config {
-        Some(cfg) => cfg.build_target,
-        None => BuildTarget::default(),
-    };
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
 
-    let mut builder: Box<dyn AsmBuilder> = match build_target {
-        BuildTarget::Fuel => Box::new(FuelAsmBuilder::new(
-            kind,
-            DataSection::default(),
-            reg_seqr,
-            context,
-        )),
-        BuildTarget::EVM => Box::new(EvmAsmBuilder::new(kind, context)),
-        BuildTarget::MidenVM => Box::new(MidenVMAsmBuilder::new(kind, context)),
+    let backend = match build_config {
+        Some(cfg) => backend_for(cfg.build_target, cfg.vm_version.as_deref()),
+        None => backend_for(BuildTarget::default(), None),
     };
+    let (backend, vm_version) = match backend {
+        Ok(backend) => backend,
+        Err(error) => {
+            errors.push(error);
+            return err(warnings, errors);
+        }
+    };
+    let mut builder = backend.asm_builder(kind, vm_version, context);
 
     // Pre-create labels for all functions before we generate other code, so we can call them
     // before compiling them if needed.
-    for func in module.function_iter(context) {
-        builder.func_to_labels(&func);
-    }
-
-    let mut warnings = Vec::new();
-    let mut errors = Vec::new();
+    let func_start_labels = module
+        .function_iter(context)
+        .map(|func| (func, builder.func_to_labels(&func).0))
+        .collect::<Vec<_>>();
 
     for function in module.function_iter(context) {
         check!(
@@ -103,68 +107,307 @@ fn compile_module_to_asm(
             errors
         );
     }
+    let variables = module
+        .function_iter(context)
+        .map(|func| (func, builder.debug_variables(func)))
+        .collect::<HashMap<_, _>>();
 
-    // Get the compiled result and massage a bit for the AbstractProgram.
-    let result = builder.finalize();
-    let final_program = match result {
-        AsmBuilderResult::Fuel(result) => {
-            let (data_section, reg_seqr, entries, non_entries) = result;
-            let entries = entries
-                .into_iter()
-                .map(|(func, label, ops, test_decl_ref)| {
-                    let selector = func.get_selector(context);
-                    let name = func.get_name(context).to_string();
-                    AbstractEntry {
-                        test_decl_ref,
-                        selector,
-                        label,
-                        ops,
-                        name,
-                    }
-                })
-                .collect();
-
-            let abstract_program =
-                AbstractProgram::new(kind, data_section, entries, non_entries, reg_seqr);
-
-            if build_config
-                .map(|cfg| cfg.print_intermediate_asm)
-                .unwrap_or(false)
-            {
-                println!(";; --- ABSTRACT VIRTUAL PROGRAM ---\n");
-                println!("{abstract_program}\n");
-            }
+    let final_program = check!(
+        backend.finalize_program(kind, context, builder.finalize(), build_config),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
 
-            let allocated_program = check!(
-                CompileResult::from(abstract_program.into_allocated_program()),
-                return err(warnings, errors),
-                warnings,
-                errors
-            );
-
-            if build_config
-                .map(|cfg| cfg.print_intermediate_asm)
-                .unwrap_or(false)
-            {
-                println!(";; --- ABSTRACT ALLOCATED PROGRAM ---\n");
-                println!("{allocated_program}");
-            }
+    if let Some(pattern) = build_config.and_then(|cfg| cfg.dump_fn.as_deref()) {
+        print!(
+            "{}",
+            function_dumps(context, &func_start_labels, &final_program, pattern)
+        );
+    }
+    if kind == ProgramKind::Predicate {
+        check!(
+            verify_predicate(context, &func_start_labels, &final_program),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+    }
 
-            check!(
-                CompileResult::from(allocated_program.into_final_program()),
-                return err(warnings, errors),
-                warnings,
-                errors
-            )
-        }
-        AsmBuilderResult::Evm(result) => FinalProgram::Evm {
-            ops: result.ops,
-            abi: result.abi,
-        },
-        AsmBuilderResult::MidenVM(result) => FinalProgram::MidenVM { ops: result.ops },
+    let function_sizes = function_sizes(context, &func_start_labels, &final_program);
+    let debug_info = debug_info(context, &func_start_labels, variables, &final_program);
+
+    ok(
+        (final_program, function_sizes, debug_info),
+        warnings,
+        errors,
+    )
+}
+
+/// The range of bytes of the function starting at `start_label` in the bytecode.  A function
+/// ends where the next one starts, or with the program.
+fn function_range(
+    func_start_labels: &[(Function, Label)],
+    label_offsets: &HashMap<Label, u64>,
+    start_label: &Label,
+) -> Option<(u64, u64)> {
+    let start = label_offsets.get(start_label).map(|offs| offs * 4)?;
+    let end = func_start_labels
+        .iter()
+        .filter_map(|(_, label)| label_offsets.get(label).map(|offs| offs * 4))
+        .filter(|offset| *offset > start)
+        .min()
+        .unwrap_or(u64::MAX);
+    Some((start, end))
+}
+
+/// Verifies that a predicate can be spent from: that it has none of the instructions predicates
+/// can't execute, pointing at the source they come from, and that it isn't larger than the VM
+/// allows.
+fn verify_predicate(
+    context: &Context,
+    func_start_labels: &[(Function, Label)],
+    final_program: &FinalProgram,
+) -> CompileResult<()> {
+    let FinalProgram::Fuel {
+        data_section,
+        ops,
+        label_offsets,
+        ..
+    } = final_program
+    else {
+        return ok((), vec![], vec![]);
     };
 
-    ok(final_program, warnings, errors)
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    let mut md_mgr = MetadataManager::default();
+    let func_spans = func_start_labels
+        .iter()
+        .filter_map(|(func, start_label)| {
+            let range = function_range(func_start_labels, label_offsets, start_label)?;
+            let span = md_mgr.md_to_span(context, func.get_metadata(context))?;
+            Some((*func, range, span))
+        })
+        .collect::<Vec<_>>();
+
+    // The instructions generated by the compiler are reported at the function they're in.
+    let mut offset = 0;
+    let fallback_spans = ops
+        .iter()
+        .map(|op| {
+            let span = func_spans
+                .iter()
+                .find(|(_, (start, end), _)| offset >= *start && offset < *end)
+                .map(|(_, _, span)| span.clone());
+            offset += op.size_in_bytes(data_section);
+            span
+        })
+        .collect::<Vec<_>>();
+    check!(
+        checks::check_predicate_opcodes(ops, &fallback_spans),
+        (),
+        warnings,
+        errors
+    );
+
+    // The instructions, the word pointing at the data section, and the data section.
+    let size = offset + 4 + data_section.serialize_to_bytes().len() as u64;
+    let entry_span = func_spans
+        .iter()
+        .find(|(func, ..)| {
+            func.is_entry(context) && func.get_name(context) == DEFAULT_ENTRY_POINT_FN_NAME
+        })
+        .map_or_else(Span::dummy, |(_, _, span)| span.clone());
+    check!(
+        checks::check_predicate_size(size, entry_span),
+        (),
+        warnings,
+        errors
+    );
+
+    if errors.is_empty() {
+        ok((), warnings, errors)
+    } else {
+        err(warnings, errors)
+    }
+}
+
+/// The size of each function in the bytecode, largest first.
+fn function_sizes(
+    context: &Context,
+    func_start_labels: &[(Function, Label)],
+    final_program: &FinalProgram,
+) -> Vec<FunctionSize> {
+    let FinalProgram::Fuel {
+        data_section,
+        ops,
+        label_offsets,
+        ..
+    } = final_program
+    else {
+        return vec![];
+    };
+    let program_size = ops
+        .iter()
+        .map(|op| op.size_in_bytes(data_section))
+        .sum::<u64>();
+
+    let mut sizes = func_start_labels
+        .iter()
+        .filter_map(|(func, start_label)| {
+            let (start, end) = function_range(func_start_labels, label_offsets, start_label)?;
+            let bytes = end.min(program_size) - start;
+            Some(FunctionSize {
+                name: func.get_name(context).to_string(),
+                signature: function_signature(context, *func),
+                bytes,
+                instructions: bytes / 4,
+            })
+        })
+        .collect::<Vec<_>>();
+    sizes.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    sizes
+}
+
+/// The debug info of each function, in the order they appear in the bytecode.
+fn debug_info(
+    context: &Context,
+    func_start_labels: &[(Function, Label)],
+    mut variables: HashMap<Function, (Vec<DebugVariable>, Vec<DebugVariable>)>,
+    final_program: &FinalProgram,
+) -> DebugInfo {
+    let FinalProgram::Fuel {
+        data_section,
+        ops,
+        label_offsets,
+        ..
+    } = final_program
+    else {
+        return DebugInfo::default();
+    };
+    let program_size = ops
+        .iter()
+        .map(|op| op.size_in_bytes(data_section))
+        .sum::<u64>();
+
+    let mut md_mgr = MetadataManager::default();
+    let mut functions = func_start_labels
+        .iter()
+        .filter_map(|(func, start_label)| {
+            let (start, end) = function_range(func_start_labels, label_offsets, start_label)?;
+            let span = md_mgr.md_to_span(context, func.get_metadata(context));
+            let (args, locals) = variables.remove(func).unwrap_or_default();
+            Some(DebugFunction {
+                name: func.get_name(context).to_string(),
+                signature: function_signature(context, *func),
+                path: span
+                    .as_ref()
+                    .and_then(|span| span.source_id())
+                    .map(|source_id| context.source_engine().get_path(source_id)),
+                range: span.map(|span| LocationRange {
+                    start: span.start(),
+                    end: span.end(),
+                }),
+                start: start / 4,
+                end: end.min(program_size) / 4,
+                args,
+                locals,
+            })
+        })
+        .collect::<Vec<_>>();
+    functions.sort_by_key(|function| function.start);
+    DebugInfo { functions }
+}
+
+/// The signature of `func` in the IR, e.g. `transfer(u64, b256) -> ()`.
+fn function_signature(context: &Context, func: Function) -> String {
+    let args = func
+        .args_iter(context)
+        .map(|(_, arg)| {
+            arg.get_type(context)
+                .map_or_else(|| "?".to_string(), |ty| ty.as_string(context))
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "{}({}) -> {}",
+        func.get_name(context),
+        args.join(", "),
+        func.get_return_type(context).as_string(context)
+    )
+}
+
+/// The IR, the finalized ASM and the range in the bytecode of each function whose name matches
+/// `pattern`.
+fn function_dumps(
+    context: &Context,
+    func_start_labels: &[(Function, Label)],
+    final_program: &FinalProgram,
+    pattern: &str,
+) -> String {
+    let mut dumps = String::new();
+    for (func, start_label) in func_start_labels {
+        let name = func.get_name(context);
+        if !matches_pattern(name, pattern) {
+            continue;
+        }
+
+        let _ = writeln!(dumps, ";; --- IR of {name} ---\n");
+        let _ = writeln!(
+            dumps,
+            "{}",
+            sway_ir::printer::function_to_string(context, *func)
+        );
+
+        let FinalProgram::Fuel {
+            data_section,
+            ops,
+            label_offsets,
+            ..
+        } = final_program
+        else {
+            continue;
+        };
+        let Some((start, end)) = function_range(func_start_labels, label_offsets, start_label)
+        else {
+            continue;
+        };
+
+        let mut offset = 0;
+        let mut func_ops = Vec::new();
+        for op in ops {
+            if offset >= start && offset < end {
+                func_ops.push(op.to_string());
+            }
+            offset += op.size_in_bytes(data_section);
+        }
+        let _ = writeln!(
+            dumps,
+            ";; --- ASM of {name}, at bytes 0x{start:x}..0x{:x} of the bytecode ---\n",
+            end.min(offset)
+        );
+        let _ = writeln!(dumps, "{}\n", func_ops.join("\n"));
+    }
+
+    if dumps.is_empty() {
+        let _ = writeln!(dumps, ";; --- no function matches `{pattern}` ---\n");
+    }
+    dumps
+}
+
+/// Whether `name` matches `pattern`, in which `*` matches any sequence of characters.
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return name == pattern;
+    };
+    let Some(name) = name.strip_prefix(prefix) else {
+        return false;
+    };
+    name.char_indices()
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(name.len()))
+        .any(|idx| matches_pattern(&name[idx..], rest))
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -209,3 +452,117 @@ pub(crate) fn ir_type_str_size_in_bytes(context: &Context, ty: &Type) -> u64 {
         _ => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sway_types::SourceEngine;
+
+    const IR: &str = r#"
+script {
+    entry fn main() -> u64 {
+        entry():
+        v0 = call one_0()
+        v1 = call two_1()
+        v2 = add v0, v1
+        ret u64 v2
+    }
+
+    fn one_0() -> u64 {
+        entry():
+        v0 = const u64 1
+        ret u64 v0
+    }
+
+    fn two_1() -> u64 {
+        entry():
+        v0 = const u64 2
+        ret u64 v0
+    }
+}
+"#;
+
+    /// The dumps of the functions of `IR` matching `pattern`, and the size of its instructions.
+    fn dumps_of(pattern: &str) -> (String, u64) {
+        let source_engine = SourceEngine::default();
+        let context = parse(IR, &source_engine).unwrap();
+        let module = context.module_iter().next().unwrap();
+
+        let (backend, vm_version) = backend_for(BuildTarget::Fuel, None).unwrap();
+        let mut builder = backend.asm_builder(ProgramKind::Script, vm_version, &context);
+        let func_start_labels = module
+            .function_iter(&context)
+            .map(|func| (func, builder.func_to_labels(&func).0))
+            .collect::<Vec<_>>();
+        for function in module.function_iter(&context) {
+            builder.compile_function(function).value.unwrap();
+        }
+        let final_program = backend
+            .finalize_program(ProgramKind::Script, &context, builder.finalize(), None)
+            .value
+            .unwrap();
+
+        let FinalProgram::Fuel {
+            data_section, ops, ..
+        } = &final_program
+        else {
+            unreachable!();
+        };
+        let size = ops.iter().map(|op| op.size_in_bytes(data_section)).sum();
+        let dumps = function_dumps(&context, &func_start_labels, &final_program, pattern);
+        (dumps, size)
+    }
+
+    #[test]
+    fn patterns_match_names() {
+        assert!(matches_pattern("main", "main"));
+        assert!(!matches_pattern("main", "mai"));
+        assert!(matches_pattern("main", "*"));
+        assert!(matches_pattern("transfer_3", "transfer*"));
+        assert!(matches_pattern("transfer_3", "*_3"));
+        assert!(matches_pattern("transfer_3", "t*s*3"));
+        assert!(!matches_pattern("transfer_3", "*_4"));
+        assert!(!matches_pattern("mint_3", "transfer*"));
+    }
+
+    #[test]
+    fn only_matching_functions_are_dumped() {
+        let (dumps, _) = dumps_of("one*");
+
+        assert!(dumps.contains(";; --- IR of one_0 ---"));
+        assert!(dumps.contains("fn one_0() -> u64"));
+        assert!(dumps.contains(";; --- ASM of one_0, at bytes 0x"));
+        assert!(!dumps.contains("two_1 ---"));
+        assert!(!dumps.contains("main ---"));
+    }
+
+    #[test]
+    fn function_ranges_cover_the_bytecode_after_the_prologue() {
+        let (dumps, size) = dumps_of("*");
+
+        let mut ranges = dumps
+            .lines()
+            .filter_map(|line| line.split_once(", at bytes 0x"))
+            .map(|(_, range)| {
+                let (start, end) = range.split_once("..0x").unwrap();
+                let end = end.split_once(' ').unwrap().0;
+                (
+                    u64::from_str_radix(start, 16).unwrap(),
+                    u64::from_str_radix(end, 16).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        ranges.sort();
+
+        assert_eq!(ranges.len(), 3);
+        assert!(ranges[0].0 > 0);
+        assert!(ranges.windows(2).all(|pair| pair[0].1 == pair[1].0));
+        assert_eq!(ranges.last().unwrap().1, size);
+    }
+
+    #[test]
+    fn unmatched_patterns_are_reported() {
+        let (dumps, _) = dumps_of("three*");
+        assert_eq!(dumps, ";; --- no function matches `three*` ---\n\n");
+    }
+}
@@ -101,6 +101,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "save base stack value".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                     new_ops.push(AllocatedAbstractOp {
                         opcode: Either::Left(AllocatedOpcode::CFEI(
@@ -108,6 +109,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "reserve space for saved registers".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
 
                     regs.into_iter().enumerate().for_each(|(idx, reg)| {
@@ -120,6 +122,7 @@ impl AllocatedAbstractInstructionSet {
                             opcode: Either::Left(store_op),
                             comment: format!("save {reg}"),
                             owning_span: None,
+                            inlined_at: Vec::new(),
                         });
                     })
                 }
@@ -142,6 +145,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "save base stack value".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
 
                     regs.into_iter().enumerate().for_each(|(idx, reg)| {
@@ -154,6 +158,7 @@ impl AllocatedAbstractInstructionSet {
                             opcode: Either::Left(load_op),
                             comment: format!("restore {reg}"),
                             owning_span: None,
+                            inlined_at: Vec::new(),
                         });
                     });
 
@@ -163,6 +168,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "recover space from saved registers".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                 }
 
@@ -192,11 +198,13 @@ impl AllocatedAbstractInstructionSet {
                 opcode,
                 comment,
                 owning_span,
+                inlined_at,
             } = op.clone();
             match opcode {
                 Either::Left(op) => realized_ops.push(RealizedOp {
                     opcode: op,
                     owning_span,
+                    inlined_at,
                     comment,
                 }),
                 Either::Right(org_op) => match org_op {
@@ -220,6 +228,7 @@ impl AllocatedAbstractInstructionSet {
                                         VirtualImmediate18::new_unchecked(0, "unreachable()"),
                                     ),
                                     owning_span,
+                                    inlined_at,
                                     comment,
                                 });
                             }
@@ -230,6 +239,7 @@ impl AllocatedAbstractInstructionSet {
                                         imm(),
                                     ),
                                     owning_span,
+                                    inlined_at,
                                     comment,
                                 });
                             }
@@ -240,6 +250,7 @@ impl AllocatedAbstractInstructionSet {
                                         imm(),
                                     ),
                                     owning_span,
+                                    inlined_at,
                                     comment,
                                 });
                             }
@@ -266,6 +277,7 @@ impl AllocatedAbstractInstructionSet {
                                         VirtualImmediate12::new_unchecked(0, "unreachable()"),
                                     ),
                                     owning_span,
+                                    inlined_at,
                                     comment,
                                 });
                             }
@@ -277,6 +289,7 @@ impl AllocatedAbstractInstructionSet {
                                         imm(),
                                     ),
                                     owning_span,
+                                    inlined_at,
                                     comment,
                                 });
                             }
@@ -288,6 +301,7 @@ impl AllocatedAbstractInstructionSet {
                                         imm(),
                                     ),
                                     owning_span,
+                                    inlined_at,
                                     comment,
                                 });
                             }
@@ -306,6 +320,7 @@ impl AllocatedAbstractInstructionSet {
                                 AllocatedRegister::Constant(ConstantRegister::InstructionStart),
                             ),
                             owning_span: owning_span.clone(),
+                            inlined_at: inlined_at.clone(),
                             comment: "Get current instruction offset from Instruction start".into(),
                         });
                         realized_ops.push(RealizedOp {
@@ -315,11 +330,13 @@ impl AllocatedAbstractInstructionSet {
                                 VirtualImmediate12 { value: 2 },
                             ),
                             owning_span: owning_span.clone(),
+                            inlined_at: inlined_at.clone(),
                             comment: "Current instruction offset in 32b words".into(),
                         });
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::ADDI(r1.clone(), r1, imm),
                             owning_span,
+                            inlined_at,
                             comment,
                         });
                     }
@@ -327,6 +344,7 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::DataSectionOffsetPlaceholder,
                             owning_span: None,
+                            inlined_at: Vec::new(),
                             comment: String::new(),
                         });
                     }
@@ -350,6 +368,7 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::LWDataId(r1, data_id),
                             owning_span,
+                            inlined_at,
                             comment,
                         });
                     }
@@ -541,6 +560,7 @@ impl AllocatedAbstractInstructionSet {
                                 opcode: Either::Left(AllocatedOpcode::NOOP),
                                 comment: "NOP for self loop".into(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                             new_ops.push(op);
                             modified = true;
@@ -555,6 +575,7 @@ impl AllocatedAbstractInstructionSet {
                                 )),
                                 comment: String::new(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
 
                             // Jump to $tmp.
@@ -584,6 +605,7 @@ impl AllocatedAbstractInstructionSet {
                                 opcode: Either::Left(AllocatedOpcode::NOOP),
                                 comment: "NOP for self loop".into(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                             new_ops.push(op);
                             modified = true;
@@ -598,6 +620,7 @@ impl AllocatedAbstractInstructionSet {
                                 )),
                                 comment: String::new(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
 
                             // JNZB/JNZF r1 $tmp.
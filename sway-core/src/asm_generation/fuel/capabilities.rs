@@ -0,0 +1,263 @@
+//! Checks that programs only use the opcodes available in the revision of the Fuel VM that code
+//! is generated for.
+//!
+//! Opcodes are used by ASM blocks and by the intrinsics lowered to VM instructions, either
+//! directly or through the functions of dependencies, e.g. of `std`. Uses within the package
+//! being compiled are reported where the opcode is used, and uses within dependencies are
+//! reported at the calls into them, so that the error points at code the user can change.
+//!
+//! The opcodes the compiler generates itself, e.g. the register based jumps replacing jumps too far
+//! for an immediate, are checked in the final program.
+
+use crate::{asm_lang::allocated_ops::AllocatedOp, metadata::MetadataManager, Engines};
+
+use sway_error::{error::CompileError, handler::Handler};
+use sway_ir::{Context, FuelVmInstruction, Function, Instruction};
+use sway_types::span::Span;
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+/// The opcodes that are not available in every supported revision of the Fuel VM, by the
+/// revision that introduced them.
+const OPCODE_REVISIONS: &[(&str, &[&str])] = &[("0.26", &["jmp", "jne", "scwq"])];
+
+/// The opcodes used by a function, directly or through the functions it calls, that require a
+/// newer revision of the VM, memoised by function.
+type Memos = HashMap<Function, Option<(&'static str, &'static str)>>;
+
+/// Reports the opcodes used by the package's functions that are not available in `vm_version`.
+///
+/// Designed to be called _prior_ to inlining, so that the calls into dependencies are intact.
+/// Functions whose source is not within `package_dir` are considered to be dependencies.
+pub(crate) fn check_vm_capabilities(
+    handler: &Handler,
+    engines: &Engines,
+    context: &Context,
+    vm_version: &str,
+    package_dir: &Path,
+) {
+    let mut md_mgr = MetadataManager::default();
+    let mut memos = Memos::new();
+    let is_in_package = |md_mgr: &mut MetadataManager, function: &Function| {
+        md_mgr
+            .md_to_span(context, function.get_metadata(context))
+            .and_then(|span| span.source_id().map(|id| engines.se().get_path(id)))
+            .map_or(true, |path| path.starts_with(package_dir))
+    };
+
+    for function in context
+        .module_iter()
+        .flat_map(|module| module.function_iter(context))
+    {
+        if !is_in_package(&mut md_mgr, &function) {
+            continue;
+        }
+        for (_block, ins_value) in function.instruction_iter(context) {
+            let span = md_mgr
+                .val_to_span(context, ins_value)
+                .unwrap_or_else(Span::dummy);
+            match ins_value.get_instruction(context) {
+                Some(Instruction::AsmBlock(asm_block, _args)) => {
+                    for asm_op in &asm_block.get_content(context).body {
+                        let opcode = asm_op.name.as_str();
+                        if let Some(required_vm_version) = unavailable_in(opcode, vm_version) {
+                            handler.emit_err(CompileError::OpcodeRequiresVmVersion {
+                                opcode: opcode.to_string(),
+                                required_vm_version: required_vm_version.to_string(),
+                                vm_version: vm_version.to_string(),
+                                span: md_mgr
+                                    .md_to_span(context, asm_op.metadata)
+                                    .unwrap_or_else(|| span.clone()),
+                            });
+                        }
+                    }
+                }
+                Some(Instruction::FuelVm(instruction)) => {
+                    let opcode = fuel_vm_instruction_opcode(instruction);
+                    if let Some(required_vm_version) = unavailable_in(opcode, vm_version) {
+                        handler.emit_err(CompileError::OpcodeRequiresVmVersion {
+                            opcode: opcode.to_string(),
+                            required_vm_version: required_vm_version.to_string(),
+                            vm_version: vm_version.to_string(),
+                            span,
+                        });
+                    }
+                }
+                Some(Instruction::Call(callee, _args)) if !is_in_package(&mut md_mgr, callee) => {
+                    if let Some((opcode, required_vm_version)) =
+                        unavailable_opcode_used_by(context, &mut memos, callee, vm_version)
+                    {
+                        handler.emit_err(CompileError::CallRequiresVmVersion {
+                            fn_name: callee.get_name(context).to_string(),
+                            opcode: opcode.to_string(),
+                            required_vm_version: required_vm_version.to_string(),
+                            vm_version: vm_version.to_string(),
+                            span,
+                        });
+                    }
+                }
+                _otherwise => (),
+            }
+        }
+    }
+}
+
+/// Reports the opcodes of the final program that are not available in `vm_version`, once for each
+/// source they were generated for.
+pub(crate) fn check_final_ops(ops: &[AllocatedOp], vm_version: &str) -> Vec<CompileError> {
+    let mut reported = HashSet::new();
+    ops.iter()
+        .filter_map(|op| {
+            let opcode = op.opcode.to_string();
+            let opcode = opcode.split_whitespace().next()?;
+            let required_vm_version = unavailable_in(opcode, vm_version)?;
+            let span = op.owning_span.clone().unwrap_or_else(Span::dummy);
+            reported
+                .insert((opcode.to_string(), span.clone()))
+                .then(|| CompileError::OpcodeRequiresVmVersion {
+                    opcode: opcode.to_string(),
+                    required_vm_version: required_vm_version.to_string(),
+                    vm_version: vm_version.to_string(),
+                    span,
+                })
+        })
+        .collect()
+}
+
+/// Returns an opcode used by `function`, directly or through the functions it calls, that is not
+/// available in `vm_version`, along with the revision that introduced it.
+fn unavailable_opcode_used_by(
+    context: &Context,
+    memos: &mut Memos,
+    function: &Function,
+    vm_version: &str,
+) -> Option<(&'static str, &'static str)> {
+    if let Some(memo) = memos.get(function) {
+        return *memo;
+    }
+    // Functions can be recursive, so mark this one as visited before looking at its calls.
+    memos.insert(*function, None);
+
+    let unavailable = function
+        .instruction_iter(context)
+        .find_map(
+            |(_block, ins_value)| match ins_value.get_instruction(context)? {
+                Instruction::AsmBlock(asm_block, _args) => asm_block
+                    .get_content(context)
+                    .body
+                    .iter()
+                    .find_map(|asm_op| {
+                        let opcode = known_opcode(asm_op.name.as_str())?;
+                        Some((opcode, unavailable_in(opcode, vm_version)?))
+                    }),
+                Instruction::FuelVm(instruction) => {
+                    let opcode = fuel_vm_instruction_opcode(instruction);
+                    Some((opcode, unavailable_in(opcode, vm_version)?))
+                }
+                Instruction::Call(callee, _args) => {
+                    unavailable_opcode_used_by(context, memos, callee, vm_version)
+                }
+                _otherwise => None,
+            },
+        );
+    memos.insert(*function, unavailable);
+    unavailable
+}
+
+/// Returns the revision that introduced `opcode`, if it is newer than `vm_version`.
+pub(crate) fn unavailable_in(opcode: &str, vm_version: &str) -> Option<&'static str> {
+    OPCODE_REVISIONS
+        .iter()
+        .find(|(_, opcodes)| opcodes.contains(&opcode))
+        .map(|(revision, _)| *revision)
+        .filter(|revision| is_older(vm_version, revision))
+}
+
+/// Returns the static name of `opcode` if it is one of the [OPCODE_REVISIONS].
+fn known_opcode(opcode: &str) -> Option<&'static str> {
+    OPCODE_REVISIONS
+        .iter()
+        .flat_map(|(_, opcodes)| opcodes.iter())
+        .find(|known| **known == opcode)
+        .copied()
+}
+
+/// The opcode that a VM instruction of the IR is lowered to.
+fn fuel_vm_instruction_opcode(instruction: &FuelVmInstruction) -> &'static str {
+    match instruction {
+        FuelVmInstruction::Gtf { .. } => "gtf",
+        FuelVmInstruction::Log { .. } => "logd",
+        FuelVmInstruction::ReadRegister(_) => "move",
+        FuelVmInstruction::Revert(_) => "rvrt",
+        FuelVmInstruction::Smo { .. } => "smo",
+        FuelVmInstruction::StateClear { .. } => "scwq",
+        FuelVmInstruction::StateLoadQuadWord { .. } => "srwq",
+        FuelVmInstruction::StateLoadWord(_) => "srw",
+        FuelVmInstruction::StateStoreQuadWord { .. } => "swwq",
+        FuelVmInstruction::StateStoreWord { .. } => "sww",
+    }
+}
+
+/// Whether the VM revision `a` is older than `b`, comparing their numeric components.
+fn is_older(a: &str, b: &str) -> bool {
+    let components = |revision: &str| {
+        revision
+            .split('.')
+            .map(|component| component.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>()
+    };
+    components(a) < components(b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn opcodes_are_checked_against_the_vm_version() {
+        assert_eq!(unavailable_in("scwq", "0.25"), Some("0.26"));
+        assert_eq!(unavailable_in("scwq", "0.26"), None);
+        assert_eq!(unavailable_in("scwq", "0.34"), None);
+        assert_eq!(unavailable_in("add", "0.25"), None);
+        assert!(is_older("0.9", "0.26"));
+        assert!(!is_older("0.34", "0.26"));
+    }
+
+    #[test]
+    fn generated_opcodes_are_reported_once_per_source() {
+        use crate::asm_lang::{
+            allocated_ops::{AllocatedOpcode, AllocatedRegister},
+            ConstantRegister,
+        };
+
+        let op = |opcode, owning_span| AllocatedOp {
+            opcode,
+            comment: String::new(),
+            owning_span,
+            inlined_at: vec![],
+        };
+        let reta = || AllocatedRegister::Constant(ConstantRegister::CallReturnAddress);
+        let zero = || AllocatedRegister::Constant(ConstantRegister::Zero);
+        let span = Span::from_string("fn f() {}".to_string());
+        let ops = [
+            op(AllocatedOpcode::ADD(reta(), zero(), zero()), None),
+            op(AllocatedOpcode::JMP(reta()), Some(span.clone())),
+            op(AllocatedOpcode::JMP(reta()), Some(span)),
+            op(AllocatedOpcode::JNE(zero(), zero(), reta()), None),
+        ];
+
+        let errors = check_final_ops(&ops, "0.25");
+        let opcodes = errors
+            .iter()
+            .map(|error| match error {
+                CompileError::OpcodeRequiresVmVersion { opcode, .. } => opcode.as_str(),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(opcodes, ["jmp", "jne"]);
+        assert!(check_final_ops(&ops, "0.34").is_empty());
+    }
+}
@@ -60,6 +60,10 @@ pub(crate) fn check_script_opcodes(ops: &[AllocatedOp]) -> CompileResult<()> {
     }
 }
 
+/// The largest predicate the VM verifies, in bytes, which is the `max_predicate_length` of the
+/// default consensus parameters.
+pub(crate) const MAX_PREDICATE_LENGTH: u64 = 1024 * 1024;
+
 /// Checks if an opcode is one that cannot be executed from within a predicate.
 /// If so, throw an error.
 ///
@@ -73,19 +77,27 @@ pub(crate) fn check_script_opcodes(ops: &[AllocatedOp]) -> CompileResult<()> {
 /// }
 /// ```
 ///
-/// Jumping backwards is not allowed in predicates so JMP and JNE are not allowed and
-/// the function verifies that the immediate of JI, JNEI, JNZI is greater than the opcode offset.
+/// The opcodes the compiler generates have no span of their own, so they're reported at the span
+/// of the function they're in, from `fallback_spans`, which has one for each of the `ops`.
 ///
 /// See: https://fuellabs.github.io/fuel-specs/master/vm/index.html?highlight=predicate#predicate-verification
-pub(crate) fn check_predicate_opcodes(ops: &[AllocatedOp]) -> CompileResult<()> {
+pub(crate) fn check_predicate_opcodes(
+    ops: &[AllocatedOp],
+    fallback_spans: &[Option<Span>],
+) -> CompileResult<()> {
     use AllocatedOpcode::*;
     let mut errors = vec![];
 
-    for op in ops.iter() {
+    for (op, fallback_span) in ops.iter().zip(fallback_spans) {
+        let span = op
+            .owning_span
+            .clone()
+            .or_else(|| fallback_span.clone())
+            .unwrap_or_else(|| get_op_span(op));
         let invalid_opcode = |name_str: &str, errors: &mut Vec<CompileError>| {
             errors.push(CompileError::InvalidOpcodeFromPredicate {
                 opcode: name_str.to_string(),
-                span: get_op_span(op),
+                span: span.clone(),
             });
         };
         match op.opcode.clone() {
@@ -99,15 +111,14 @@ pub(crate) fn check_predicate_opcodes(ops: &[AllocatedOp]) -> CompileResult<()>
             CROO(..) => invalid_opcode("CROO", &mut errors),
             CSIZ(..) => invalid_opcode("CSIZ", &mut errors),
             GM(_, VirtualImmediate18 { value: 1..=2 }) => {
-                errors.push(CompileError::GMFromExternalContext {
-                    span: get_op_span(op),
-                });
+                errors.push(CompileError::GMFromExternalContext { span });
             }
             LDC(..) => invalid_opcode("LDC", &mut errors),
             LOG(..) => invalid_opcode("LOG", &mut errors),
             LOGD(..) => invalid_opcode("LOGD", &mut errors),
             MINT(..) => invalid_opcode("MINT", &mut errors),
             RETD(..) => invalid_opcode("RETD", &mut errors),
+            SCWQ(..) => invalid_opcode("SCWQ", &mut errors),
             SMO(..) => invalid_opcode("SMO", &mut errors),
             SRW(..) => invalid_opcode("SRW", &mut errors),
             SRWQ(..) => invalid_opcode("SRWQ", &mut errors),
@@ -129,6 +140,23 @@ pub(crate) fn check_predicate_opcodes(ops: &[AllocatedOp]) -> CompileResult<()>
     }
 }
 
+/// Checks that a predicate of `size` bytes isn't larger than the VM allows, which would only
+/// be found out when spending from it. The error points at `entry_span`, the predicate's `main`.
+pub(crate) fn check_predicate_size(size: u64, entry_span: Span) -> CompileResult<()> {
+    if size > MAX_PREDICATE_LENGTH {
+        err(
+            vec![],
+            vec![CompileError::PredicateTooLarge {
+                size,
+                max_size: MAX_PREDICATE_LENGTH,
+                span: entry_span,
+            }],
+        )
+    } else {
+        ok((), vec![], vec![])
+    }
+}
+
 fn get_op_span(op: &AllocatedOp) -> Span {
     let default_span =
         sway_types::span::Span::new("no span found for opcode".into(), 0, 1, None).unwrap();
@@ -136,3 +164,69 @@ fn get_op_span(op: &AllocatedOp) -> Span {
         .clone()
         .unwrap_or_else(|| default_span.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::{allocated_ops::AllocatedRegister, ConstantRegister};
+
+    fn op(opcode: AllocatedOpcode, owning_span: Option<Span>) -> AllocatedOp {
+        AllocatedOp {
+            opcode,
+            comment: String::new(),
+            owning_span,
+            inlined_at: vec![],
+        }
+    }
+
+    fn zero() -> AllocatedRegister {
+        AllocatedRegister::Constant(ConstantRegister::Zero)
+    }
+
+    #[test]
+    fn scwq_is_invalid_in_predicates() {
+        let span = Span::from_string("asm() { scwq zero zero zero; }".to_string());
+        let ops = [op(
+            AllocatedOpcode::SCWQ(zero(), zero(), zero()),
+            Some(span),
+        )];
+
+        let result = check_predicate_opcodes(&ops, &[None]);
+        assert!(matches!(
+            result.errors.as_slice(),
+            [CompileError::InvalidOpcodeFromPredicate { opcode, .. }] if opcode == "SCWQ"
+        ));
+    }
+
+    #[test]
+    fn generated_opcodes_are_reported_at_the_fallback_span() {
+        let own_span = Span::from_string("asm() { log zero zero zero zero; }".to_string());
+        let fn_span = Span::from_string("fn log_it() {}".to_string());
+        let log = || AllocatedOpcode::LOG(zero(), zero(), zero(), zero());
+        let ops = [op(log(), Some(own_span.clone())), op(log(), None)];
+
+        let result = check_predicate_opcodes(&ops, &[Some(fn_span.clone()), Some(fn_span.clone())]);
+        let spans = result
+            .errors
+            .iter()
+            .map(|error| match error {
+                CompileError::InvalidOpcodeFromPredicate { span, .. } => span.as_str(),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(spans, [own_span.as_str(), fn_span.as_str()]);
+    }
+
+    #[test]
+    fn predicates_larger_than_the_vm_allows_are_rejected() {
+        let span = Span::from_string("fn main() -> bool { true }".to_string());
+
+        assert!(check_predicate_size(MAX_PREDICATE_LENGTH, span.clone()).is_ok());
+        let result = check_predicate_size(MAX_PREDICATE_LENGTH + 1, span);
+        assert!(matches!(
+            result.errors.as_slice(),
+            [CompileError::PredicateTooLarge { size, max_size, .. }]
+                if *size == MAX_PREDICATE_LENGTH + 1 && *max_size == MAX_PREDICATE_LENGTH
+        ));
+    }
+}
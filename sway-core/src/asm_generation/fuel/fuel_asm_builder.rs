@@ -11,6 +11,7 @@ use crate::{
         ProgramKind,
     },
     asm_lang::{virtual_register::*, Label, Op, VirtualImmediate12, VirtualImmediate18, VirtualOp},
+    debug_info::{DebugType, DebugVariable, VariableLocation},
     decl_engine::DeclRefFunction,
     error::*,
     metadata::MetadataManager,
@@ -54,6 +55,9 @@ pub struct FuelAsmBuilder<'ir, 'eng> {
     // IR context we're compiling.
     pub(super) context: &'ir Context<'eng>,
 
+    // The revision of the VM that code is generated for, the newest if `None`.
+    pub(super) vm_version: Option<String>,
+
     // Metadata manager for converting metadata to Spans, etc.
     pub(super) md_mgr: MetadataManager,
 
@@ -90,6 +94,10 @@ impl<'ir, 'eng> AsmBuilder for FuelAsmBuilder<'ir, 'eng> {
     fn finalize(&self) -> AsmBuilderResult {
         self.finalize()
     }
+
+    fn debug_variables(&self, function: Function) -> (Vec<DebugVariable>, Vec<DebugVariable>) {
+        self.debug_variables(function)
+    }
 }
 
 impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
@@ -98,6 +106,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         data_section: DataSection,
         reg_seqr: RegisterSequencer,
         context: &'ir Context<'eng>,
+        vm_version: Option<&str>,
     ) -> Self {
         FuelAsmBuilder {
             program_kind,
@@ -110,6 +119,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             return_ctxs: Vec::new(),
             locals_ctxs: Vec::new(),
             context,
+            vm_version: vm_version.map(str::to_string),
             md_mgr: MetadataManager::default(),
             entries: Vec::new(),
             non_entries: Vec::new(),
@@ -136,6 +146,70 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         ))
     }
 
+    /// The args of the compiled `function`, where they are when it's entered, and its locals,
+    /// where they are once its prologue has run.  Locals introduced by the compiler are skipped.
+    pub fn debug_variables(&self, function: Function) -> (Vec<DebugVariable>, Vec<DebugVariable>) {
+        let args = if function.is_entry(self.context) {
+            vec![]
+        } else {
+            function
+                .args_iter(self.context)
+                .zip(ConstantRegister::ARG_REGS)
+                .filter_map(|((name, arg_val), arg_reg)| {
+                    let ty = arg_val.get_type(self.context)?;
+                    let register = arg_reg.to_reg_id().to_u8();
+                    let location =
+                        if self.is_copy_type(&ty) || ty.get_pointee_type(self.context).is_some() {
+                            VariableLocation::Register { register }
+                        } else {
+                            VariableLocation::RegisterPointer { register }
+                        };
+                    Some(DebugVariable {
+                        name: name.clone(),
+                        ty: DebugType::from_ir(self.context, &ty),
+                        location,
+                    })
+                })
+                .collect()
+        };
+
+        let locals = function
+            .locals_iter(self.context)
+            .filter(|(name, _)| !name.starts_with("__"))
+            .filter_map(|(name, ptr)| {
+                let location = match self.ptr_map.get(ptr)? {
+                    Storage::Stack(word_offset) => VariableLocation::Stack {
+                        register: compiler_constants::LOCALS_BASE,
+                        offset: word_offset * 8,
+                    },
+                    Storage::Data(data_id) => VariableLocation::DataSection {
+                        offset: self.data_section.data_id_to_offset(data_id) as u64,
+                    },
+                };
+                Some(DebugVariable {
+                    name: name.clone(),
+                    ty: DebugType::from_ir(self.context, &ptr.get_inner_type(self.context)),
+                    location,
+                })
+            })
+            .collect();
+
+        (args, locals)
+    }
+
+    /// Record the call sites `instr_val` was inlined at in the ops compiled from it, which start
+    /// at `ops_start` in the current bytecode.
+    pub(super) fn record_inlined_at(&mut self, instr_val: &Value, ops_start: usize) {
+        let inlined_at = self.md_mgr.val_to_inlined_at(self.context, *instr_val);
+        if !inlined_at.is_empty() {
+            for op in &mut self.cur_bytecode[ops_start..] {
+                if op.owning_span.is_some() {
+                    op.inlined_at = inlined_at.clone();
+                }
+            }
+        }
+    }
+
     pub(super) fn compile_instruction(
         &mut self,
         instr_val: &Value,
@@ -316,6 +390,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "copy const asm init to GP reg".into(),
                                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                                inlined_at: Vec::new(),
                             });
                             const_copy
                         }
@@ -332,9 +407,17 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             })
         };
 
+        // Labels are local to the asm block, so each one declared in it gets a fresh label.
+        let asm_block = asm.get_content(self.context);
+        let asm_label_map = asm_block
+            .body
+            .iter()
+            .filter_map(|op| op.label.as_ref())
+            .map(|label| (label.as_str(), self.reg_seqr.get_label()))
+            .collect::<HashMap<_, _>>();
+
         // For each opcode in the asm expression, attempt to parse it into an opcode and
         // replace references to the above registers with the newly allocated ones.
-        let asm_block = asm.get_content(self.context);
         for op in &asm_block.body {
             let replaced_registers = op
                 .args
@@ -360,11 +443,55 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 })
                 .collect::<Vec<VirtualRegister>>();
 
-            // Parse the actual op and registers.
             let op_span = self
                 .md_mgr
                 .md_to_span(self.context, op.metadata)
                 .unwrap_or_else(Span::dummy);
+            if let Some(label) = &op.label {
+                inline_ops.push(Op::jump_label(
+                    asm_label_map[label.as_str()],
+                    op_span.clone(),
+                ));
+            }
+
+            // Jumps to the block's labels become organizational ops, which are resolved once
+            // the final code layout is known.
+            let target_label = op
+                .immediate
+                .as_ref()
+                .and_then(|imm| asm_label_map.get(imm.as_str()))
+                .copied();
+            if let Some(target_label) = target_label {
+                match (op.name.as_str(), replaced_registers.as_slice()) {
+                    ("ji", []) => {
+                        inline_ops.push(Op::jump_to_label_comment(target_label, "asm block"));
+                        continue;
+                    }
+                    ("jnzi", [cond]) => {
+                        inline_ops.push(Op::jump_if_not_zero(cond.clone(), target_label));
+                        continue;
+                    }
+                    ("jnei", [lhs, rhs]) => {
+                        let diff = self.reg_seqr.next();
+                        inline_ops.push(Op {
+                            opcode: Either::Left(VirtualOp::XOR(
+                                diff.clone(),
+                                lhs.clone(),
+                                rhs.clone(),
+                            )),
+                            comment: "asm block jnei".into(),
+                            owning_span: Some(op_span.clone()),
+                            inlined_at: Vec::new(),
+                        });
+                        inline_ops.push(Op::jump_if_not_zero(diff, target_label));
+                        continue;
+                    }
+                    // Anything else is reported as an invalid immediate below.
+                    _ => (),
+                }
+            }
+
+            // Parse the actual op and registers.
             let opcode = check!(
                 Op::parse_opcode(
                     &op.name,
@@ -381,6 +508,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: either::Either::Left(opcode),
                 comment: "asm block".into(),
                 owning_span: Some(op_span),
+                inlined_at: Vec::new(),
             });
         }
 
@@ -407,6 +535,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: Either::Left(VirtualOp::MOVE(instr_reg.clone(), ret_reg)),
                 comment: "return value from inline asm".into(),
                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                inlined_at: Vec::new(),
             });
             self.reg_map.insert(*instr_val, instr_reg);
         }
@@ -434,6 +563,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "convert to inversed boolean".into(),
                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                inlined_at: Vec::new(),
             });
             self.cur_bytecode.push(Op {
                 opcode: Either::Left(VirtualOp::XORI(
@@ -443,6 +573,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "invert boolean".into(),
                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                inlined_at: Vec::new(),
             });
             res_reg
         } else {
@@ -469,6 +600,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode,
             comment: String::new(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, res_reg);
@@ -501,6 +633,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode,
             comment: String::new(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, res_reg);
@@ -534,6 +667,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::EQ(res_reg.clone(), lhs_reg, rhs_reg)),
                     comment,
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
             }
             Predicate::LessThan => {
@@ -541,6 +675,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::LT(res_reg.clone(), lhs_reg, rhs_reg)),
                     comment,
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
             }
             Predicate::GreaterThan => {
@@ -548,6 +683,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::GT(res_reg.clone(), lhs_reg, rhs_reg)),
                     comment,
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
             }
         }
@@ -633,6 +769,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "call external contract".into(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         // now, move the return value of the contract call to the return register.
@@ -766,6 +903,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "get offset to array element".into(),
                                 owning_span: owning_span.clone(),
+                                inlined_at: Vec::new(),
                             });
                             self.cur_bytecode.push(Op {
                                 opcode: Either::Left(VirtualOp::ADD(
@@ -775,6 +913,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "add to array base".into(),
                                 owning_span: owning_span.clone(),
+                                inlined_at: Vec::new(),
                             });
                             let member_type =
                                 elem_ty.get_array_elem_type(self.context).ok_or_else(|| {
@@ -850,6 +989,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             )),
                             comment: "get byte offset to local from base".into(),
                             owning_span: owning_span.clone(),
+                            inlined_at: Vec::new(),
                         });
                         self.cur_bytecode.push(Op {
                             opcode: Either::Left(VirtualOp::ADD(
@@ -859,6 +999,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             )),
                             comment: "get absolute byte offset to local".into(),
                             owning_span,
+                            inlined_at: Vec::new(),
                         });
                     } else {
                         self.immediate_to_reg(
@@ -879,6 +1020,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::LWDataId(instr_reg.clone(), data_id.clone())),
                     comment: "get local constant".into(),
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
                 self.reg_map.insert(*instr_val, instr_reg);
 
@@ -911,6 +1053,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get transaction field".into(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
         self.reg_map.insert(*instr_val, instr_reg);
         Ok(())
@@ -939,6 +1082,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "load value".into(),
                 owning_span,
+                inlined_at: Vec::new(),
             });
 
             self.reg_map.insert(*instr_val, instr_reg);
@@ -963,22 +1107,37 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         let dst_reg = self.value_to_register(dst_val_ptr)?;
         let src_reg = self.value_to_register(src_val_ptr)?;
 
+        // Values which fit in the immediate of `mcpi` are copied without a length register.
+        if byte_len <= compiler_constants::TWELVE_BITS {
+            self.cur_bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::MCPI(
+                    dst_reg,
+                    src_reg,
+                    VirtualImmediate12 {
+                        value: byte_len as u16,
+                    },
+                )),
+                comment: "copy memory with mem_copy".into(),
+                owning_span,
+                inlined_at: Vec::new(),
+            });
+            return Ok(());
+        }
+
         let len_reg = self.reg_seqr.next();
-        self.cur_bytecode.push(Op {
-            opcode: Either::Left(VirtualOp::MOVI(
-                len_reg.clone(),
-                VirtualImmediate18 {
-                    value: byte_len as u32,
-                },
-            )),
-            comment: "get length for mcp".into(),
-            owning_span: owning_span.clone(),
-        });
+        self.immediate_to_reg(
+            byte_len,
+            len_reg.clone(),
+            None,
+            "get length for mcp",
+            owning_span.clone(),
+        );
 
         self.cur_bytecode.push(Op {
             opcode: Either::Left(VirtualOp::MCP(dst_reg, src_reg, len_reg)),
             comment: "copy memory with mem_copy".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         Ok(())
@@ -1019,6 +1178,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         if !log_ty.is_ptr(self.context) {
             self.cur_bytecode.push(Op {
                 owning_span,
+                inlined_at: Vec::new(),
                 opcode: Either::Left(VirtualOp::LOG(
                     log_val_reg,
                     log_id_reg,
@@ -1044,6 +1204,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
             self.cur_bytecode.push(Op {
                 owning_span,
+                inlined_at: Vec::new(),
                 opcode: Either::Left(VirtualOp::LOGD(
                     VirtualRegister::Constant(ConstantRegister::Zero),
                     log_id_reg,
@@ -1081,6 +1242,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "move register into abi function".to_owned(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, instr_reg);
@@ -1102,6 +1264,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     ConstantRegister::Zero,
                 ))),
                 owning_span,
+                inlined_at: Vec::new(),
                 comment: "returning unit as zero".into(),
             });
         } else {
@@ -1110,6 +1273,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             if !ret_type.is_ptr(self.context) && !ret_type.is_slice(self.context) {
                 self.cur_bytecode.push(Op {
                     owning_span,
+                    inlined_at: Vec::new(),
                     opcode: Either::Left(VirtualOp::RET(ret_reg)),
                     comment: "".into(),
                 });
@@ -1128,6 +1292,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             VirtualImmediate12 { value: 1 },
                         )),
                         owning_span: owning_span.clone(),
+                        inlined_at: Vec::new(),
                         comment: "load size of returned slice".into(),
                     });
                     self.cur_bytecode.push(Op {
@@ -1137,6 +1302,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             VirtualImmediate12 { value: 0 },
                         )),
                         owning_span: owning_span.clone(),
+                        inlined_at: Vec::new(),
                         comment: "load ptr of returned slice".into(),
                     });
                 } else {
@@ -1154,6 +1320,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 }
                 self.cur_bytecode.push(Op {
                     owning_span,
+                    inlined_at: Vec::new(),
                     opcode: Either::Left(VirtualOp::RETD(ret_reg, size_reg)),
                     comment: "".into(),
                 });
@@ -1173,6 +1340,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
         self.cur_bytecode.push(Op {
             owning_span,
+            inlined_at: Vec::new(),
             opcode: Either::Left(VirtualOp::RVRT(revert_reg)),
             comment: "".into(),
         });
@@ -1196,6 +1364,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
         self.cur_bytecode.push(Op {
             owning_span,
+            inlined_at: Vec::new(),
             opcode: Either::Left(VirtualOp::SMO(
                 recipient_reg,
                 message_reg,
@@ -1244,6 +1413,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "clear a sequence of storage slots".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, was_slot_set_reg);
@@ -1298,6 +1468,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             }),
             comment: "access a sequence of storage slots".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, was_slot_set_reg);
@@ -1331,6 +1502,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode: Either::Left(VirtualOp::SRW(load_reg.clone(), was_slot_set_reg, key_reg)),
             comment: "single word state access".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, load_reg);
@@ -1368,6 +1540,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode: Either::Left(VirtualOp::SWW(key_reg, was_slot_set_reg.clone(), store_reg)),
             comment: "single word state access".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, was_slot_set_reg);
@@ -1410,6 +1583,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "store value".into(),
                 owning_span,
+                inlined_at: Vec::new(),
             });
 
             Ok(())
@@ -1464,6 +1638,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: either::Either::Left(VirtualOp::LWDataId(reg.clone(), data_id.clone())),
                     comment: "literal instantiation".into(),
                     owning_span: span,
+                    inlined_at: Vec::new(),
                 });
                 (reg, Some(data_id))
             }
@@ -1549,6 +1724,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: comment.into(),
                 owning_span: span,
+                inlined_at: Vec::new(),
             });
         } else if imm <= compiler_constants::EIGHTEEN_BITS {
             let comment = comment.into();
@@ -1559,12 +1735,14 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: comment.clone(),
                 owning_span: span.clone(),
+                inlined_at: Vec::new(),
             });
             if let Some(base_reg) = base {
                 self.cur_bytecode.push(Op {
                     opcode: Either::Left(VirtualOp::ADD(reg.clone(), base_reg.clone(), reg)),
                     comment,
                     owning_span: span,
+                    inlined_at: Vec::new(),
                 });
             }
         } else {
@@ -1575,6 +1753,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             self.cur_bytecode.push(Op {
                 opcode: Either::Left(VirtualOp::LWDataId(reg.clone(), data_id)),
                 owning_span: span.clone(),
+                inlined_at: Vec::new(),
                 comment: comment.clone(),
             });
             if let Some(base_reg) = base {
@@ -1582,6 +1761,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::ADD(reg.clone(), base_reg.clone(), reg)),
                     comment,
                     owning_span: span,
+                    inlined_at: Vec::new(),
                 });
             }
         }
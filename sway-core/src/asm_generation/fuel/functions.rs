@@ -1,7 +1,10 @@
 use crate::{
     asm_generation::{
         from_ir::*,
-        fuel::{compiler_constants, data_section::Entry, fuel_asm_builder::FuelAsmBuilder},
+        fuel::{
+            capabilities, compiler_constants, data_section::Entry,
+            fuel_asm_builder::FuelAsmBuilder, stack_slots,
+        },
         ProgramKind,
     },
     asm_lang::{
@@ -19,7 +22,7 @@ use sway_ir::*;
 
 use either::Either;
 use sway_error::error::CompileError;
-use sway_types::Ident;
+use sway_types::{Ident, Span};
 
 use super::data_section::DataId;
 
@@ -63,6 +66,23 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         function: &Function,
         args: &[Value],
     ) -> Result<(), CompileError> {
+        // Returning from the call jumps to the return address in $reta, which the older revisions
+        // of the VM can't do.
+        if let Some(vm_version) = &self.vm_version {
+            if let Some(required_vm_version) = capabilities::unavailable_in("jmp", vm_version) {
+                return Err(CompileError::CallRequiresVmVersion {
+                    fn_name: function.get_name(self.context).to_string(),
+                    opcode: "jmp".to_string(),
+                    required_vm_version: required_vm_version.to_string(),
+                    vm_version: vm_version.clone(),
+                    span: self
+                        .md_mgr
+                        .val_to_span(self.context, *instr_val)
+                        .unwrap_or_else(Span::dummy),
+                });
+            }
+        }
+
         // Put the args into the args registers.
         for (idx, arg_val) in args.iter().enumerate() {
             if idx < compiler_constants::NUM_ARG_REGISTERS as usize {
@@ -96,6 +116,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode: Either::Right(OrganizationalOp::Call(fn_label)),
             comment: format!("call {}", function.get_name(self.context)),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.cur_bytecode.push(Op::unowned_jump_label(ret_label));
 
@@ -108,6 +129,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "copy the return value".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.reg_map.insert(*instr_val, ret_reg);
 
@@ -191,6 +213,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: Either::Right(OrganizationalOp::PushAll(start_label)),
                 comment: "save all regs".to_owned(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
         }
 
@@ -238,12 +261,14 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             self.cur_bytecode.push(Op::unowned_jump_label(label));
 
             for instr_val in block.instruction_iter(self.context) {
+                let ops_start = self.cur_bytecode.len();
                 check!(
                     self.compile_instruction(&instr_val, func_is_entry),
                     return err(warnings, errors),
                     warnings,
                     errors
                 );
+                self.record_inlined_at(&instr_val, ops_start);
             }
         }
 
@@ -271,6 +296,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: Either::Right(OrganizationalOp::PopAll(start_label)),
                 comment: "restore all regs".to_owned(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
 
             // Jump to the return address.
@@ -355,6 +381,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "load main fn parameter".into(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                         }
                     }
@@ -399,6 +426,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: format!("get offset for arg {name}"),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                             self.cur_bytecode.push(Op {
                                 opcode: Either::Left(VirtualOp::LW(
@@ -408,6 +436,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: format!("get arg {name}"),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                         } else {
                             self.cur_bytecode.push(Op {
@@ -420,6 +449,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: format!("get arg {name}"),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                         }
                     } else {
@@ -452,6 +482,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "base register for method parameter".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 
@@ -467,6 +498,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "base register for main fn parameter".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 
@@ -486,6 +518,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get predicate index".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Find the type of the "Input" using `GTF`. The returned value is one of three possible
@@ -505,6 +538,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get input type".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Label to jump to if the input type is *not* zero, i.e. not "coin". Then do the jump.
@@ -526,6 +560,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get input coin predicate data pointer".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Now that we have the actual pointer, we can jump to the success label to continue
@@ -547,6 +582,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "register containing 2".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.cur_bytecode.push(Op {
             opcode: either::Either::Left(VirtualOp::EQ(
@@ -556,6 +592,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "input type is message(2)".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Invert `input_type_is_message` to use in `jnzi`
@@ -568,6 +605,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "input type is not message(2)".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Label to jump to if the input type is *not* 2, i.e. not "message" (and not "coin" since
@@ -590,6 +628,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "input message predicate data pointer".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.cur_bytecode.push(Op::jump_to_label(success_label));
 
@@ -604,6 +643,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 ConstantRegister::Zero,
             ))),
             owning_span: None,
+            inlined_at: Vec::new(),
             comment: "return false".into(),
         });
 
@@ -626,51 +666,62 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         // Otherwise they go in runtime allocated space, either a register or on the stack.
         //
         // Stack offsets are in words to both enforce alignment and simplify use with LW/SW.
-        let (stack_base, init_mut_vars) = function.locals_iter(self.context).fold(
-            (0, Vec::new()),
-            |(stack_base, mut init_mut_vars), (_name, ptr)| {
-                if let (false, Some(constant)) = (
-                    ptr.is_mutable(self.context),
-                    ptr.get_initializer(self.context),
-                ) {
-                    let data_id = self.data_section.insert_data_value(Entry::from_constant(
+        let mut stack_locals = Vec::new();
+        for (_name, ptr) in function.locals_iter(self.context) {
+            if let (false, Some(constant)) = (
+                ptr.is_mutable(self.context),
+                ptr.get_initializer(self.context),
+            ) {
+                let data_id = self.data_section.insert_data_value(Entry::from_constant(
+                    self.context,
+                    constant,
+                    None,
+                ));
+                self.ptr_map.insert(*ptr, Storage::Data(data_id));
+            } else {
+                let ptr_ty = ptr.get_inner_type(self.context);
+                let var_size = match ptr_ty.get_content(self.context) {
+                    TypeContent::Unit
+                    | TypeContent::Bool
+                    | TypeContent::Uint(_)
+                    | TypeContent::Pointer(_) => 1,
+                    TypeContent::Slice => 2,
+                    TypeContent::B256 => 4,
+                    TypeContent::String(n) => size_bytes_round_up_to_word_alignment!(n),
+                    TypeContent::Array(..) | TypeContent::Struct(_) | TypeContent::Union(_) => {
+                        size_bytes_in_words!(ir_type_size_in_bytes(self.context, &ptr_ty))
+                    }
+                };
+
+                let init_data_id = ptr.get_initializer(self.context).map(|constant| {
+                    self.data_section.insert_data_value(Entry::from_constant(
                         self.context,
                         constant,
                         None,
-                    ));
-                    self.ptr_map.insert(*ptr, Storage::Data(data_id));
-                    (stack_base, init_mut_vars)
-                } else {
-                    self.ptr_map.insert(*ptr, Storage::Stack(stack_base));
-
-                    let ptr_ty = ptr.get_inner_type(self.context);
-                    let var_size = match ptr_ty.get_content(self.context) {
-                        TypeContent::Unit
-                        | TypeContent::Bool
-                        | TypeContent::Uint(_)
-                        | TypeContent::Pointer(_) => 1,
-                        TypeContent::Slice => 2,
-                        TypeContent::B256 => 4,
-                        TypeContent::String(n) => size_bytes_round_up_to_word_alignment!(n),
-                        TypeContent::Array(..) | TypeContent::Struct(_) | TypeContent::Union(_) => {
-                            size_bytes_in_words!(ir_type_size_in_bytes(self.context, &ptr_ty))
-                        }
-                    };
-
-                    if let Some(constant) = ptr.get_initializer(self.context) {
-                        let data_id = self.data_section.insert_data_value(Entry::from_constant(
-                            self.context,
-                            constant,
-                            None,
-                        ));
+                    ))
+                });
 
-                        init_mut_vars.push((stack_base, var_size, data_id));
-                    }
+                stack_locals.push((*ptr, var_size, init_data_id));
+            }
+        }
 
-                    (stack_base + var_size, init_mut_vars)
-                }
-            },
+        // Locals which are never live at the same time share their stack slots.
+        let (stack_offsets, stack_base) = stack_slots::assign_stack_slots(
+            self.context,
+            function,
+            &stack_locals
+                .iter()
+                .map(|(ptr, var_size, _)| (*ptr, *var_size))
+                .collect::<Vec<_>>(),
         );
+        let mut init_mut_vars = Vec::new();
+        for (ptr, var_size, init_data_id) in stack_locals {
+            let stack_offset = stack_offsets[&ptr];
+            self.ptr_map.insert(ptr, Storage::Stack(stack_offset));
+            if let Some(data_id) = init_data_id {
+                init_mut_vars.push((stack_offset, var_size, data_id));
+            }
+        }
 
         // Reserve space on the stack (in bytes) for all our locals which require it.  Firstly save
         // the current $sp.
@@ -692,6 +743,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             })),
             comment: format!("allocate {locals_size} bytes for locals"),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         (locals_size, locals_base_reg, init_mut_vars)
     }
@@ -714,6 +766,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "load initializer from data section".to_owned(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
 
             // Get the stack offset in bytes rather than words.
@@ -732,6 +785,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "calc local variable address".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             } else {
                 assert!(var_stack_off_bytes <= compiler_constants::EIGHTEEN_BITS);
@@ -745,6 +799,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "stack offset of local variable into register".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
                 self.cur_bytecode.push(Op {
                     opcode: Either::Left(VirtualOp::ADD(
@@ -754,6 +809,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "calc local variable address".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             }
 
@@ -767,6 +823,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "store initializer to local variable".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             } else {
                 // Initialise by reference.
@@ -782,6 +839,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "copy initializer from data section to local variable".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             }
         }
@@ -803,6 +861,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             })),
             comment: format!("free {locals_size} bytes for locals"),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 
@@ -0,0 +1,458 @@
+//! A static estimate of the gas used by each entry point of a program, found by taking the most
+//! expensive path through the control flow graph of its allocated ASM.
+//!
+//! The estimate is an upper bound of the gas used by the instructions of the program itself, as
+//! long as the program has no loops or recursion.  The cost of some instructions depends on the
+//! size of their operands, e.g. `MCP`, or on another contract, e.g. `CALL`; only their base cost
+//! is included, and the estimate is marked as having dynamic costs.
+
+use crate::{
+    asm_generation::finalized_asm::GasEstimate,
+    asm_lang::{
+        allocated_ops::{AllocatedOpcode, AllocatedRegister},
+        ControlFlowOp, Label,
+    },
+    decl_engine::DeclRefFunction,
+};
+
+use super::allocated_abstract_instruction_set::AllocatedAbstractInstructionSet;
+
+use fuel_vm::gas::{DependentCost, GasCosts};
+
+use either::Either;
+use std::collections::{HashMap, HashSet};
+
+/// The gas used along the most expensive path through some code, or `None` if it can't be
+/// bounded, e.g. as it loops.
+type Gas = Option<GasCost>;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct GasCost {
+    gas: u64,
+    dynamic: bool,
+}
+
+impl GasCost {
+    fn add(self, other: GasCost) -> GasCost {
+        GasCost {
+            gas: self.gas.saturating_add(other.gas),
+            dynamic: self.dynamic || other.dynamic,
+        }
+    }
+
+    fn max(self, other: GasCost) -> GasCost {
+        GasCost {
+            gas: self.gas.max(other.gas),
+            dynamic: self.dynamic || other.dynamic,
+        }
+    }
+}
+
+/// Estimates the gas used by each of the `entries` which isn't a test, given all the `functions`
+/// of the program, using the default gas costs of the VM.
+pub(crate) fn estimate_entries_gas(
+    functions: &[AllocatedAbstractInstructionSet],
+    entries: &[(Option<[u8; 4]>, Label, String, Option<DeclRefFunction>)],
+) -> Vec<GasEstimate> {
+    let mut estimator = GasEstimator::new(functions);
+    entries
+        .iter()
+        .filter(|(_, _, _, test_decl_ref)| test_decl_ref.is_none())
+        .filter_map(|(_, label, name, _)| {
+            let function = *estimator.function_labels.get(label)?;
+            let gas = estimator.function_gas(function);
+            Some(GasEstimate {
+                name: name.clone(),
+                max_gas: gas.map(|gas| gas.gas),
+                has_dynamic_costs: gas.map_or(false, |gas| gas.dynamic),
+            })
+        })
+        .collect()
+}
+
+struct GasEstimator<'a> {
+    functions: &'a [AllocatedAbstractInstructionSet],
+    /// The index of each function by the label it starts with.
+    function_labels: HashMap<Label, usize>,
+    costs: GasCosts,
+    function_gas: HashMap<usize, Gas>,
+    /// The functions whose gas is being estimated, to detect recursion.
+    in_progress: HashSet<usize>,
+}
+
+/// A sequence of ops which is only entered at its start and only left at its end.
+struct Block {
+    ops: std::ops::Range<usize>,
+    successors: Vec<usize>,
+}
+
+impl<'a> GasEstimator<'a> {
+    fn new(functions: &'a [AllocatedAbstractInstructionSet]) -> Self {
+        let function_labels = functions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, function)| {
+                function.ops.iter().find_map(|op| match op.opcode {
+                    Either::Right(ControlFlowOp::Label(label)) => Some((label, idx)),
+                    _ => None,
+                })
+            })
+            .collect();
+        GasEstimator {
+            functions,
+            function_labels,
+            costs: GasCosts::default(),
+            function_gas: HashMap::new(),
+            in_progress: HashSet::new(),
+        }
+    }
+
+    fn function_gas(&mut self, function: usize) -> Gas {
+        if let Some(gas) = self.function_gas.get(&function) {
+            return *gas;
+        }
+        if !self.in_progress.insert(function) {
+            // Recursion can't be bounded.
+            return None;
+        }
+        let gas = self.most_expensive_path(function);
+        self.in_progress.remove(&function);
+        self.function_gas.insert(function, gas);
+        gas
+    }
+
+    /// The gas used along the most expensive path through the blocks of `function`, from its
+    /// first block.
+    fn most_expensive_path(&mut self, function: usize) -> Gas {
+        let blocks = blocks(&self.functions[function]);
+        if blocks.is_empty() {
+            return Some(GasCost::default());
+        }
+
+        const UNVISITED: u8 = 0;
+        const ON_PATH: u8 = 1;
+        const DONE: u8 = 2;
+        let mut state = vec![UNVISITED; blocks.len()];
+        let mut path_gas: Vec<Gas> = vec![None; blocks.len()];
+
+        // An iterative depth first search, as functions can have many blocks.
+        let mut stack = vec![(0, 0)];
+        state[0] = ON_PATH;
+        while let Some((block, next_successor)) = stack.last_mut() {
+            let block = *block;
+            if let Some(&successor) = blocks[block].successors.get(*next_successor) {
+                *next_successor += 1;
+                match state[successor] {
+                    // A loop, whose iterations can't be bounded.
+                    ON_PATH => return None,
+                    UNVISITED => {
+                        state[successor] = ON_PATH;
+                        stack.push((successor, 0));
+                    }
+                    _ => (),
+                }
+                continue;
+            }
+            stack.pop();
+            state[block] = DONE;
+            let own_gas = self.block_gas(function, &blocks[block]);
+            let successors_gas = blocks[block].successors.iter().try_fold(
+                None,
+                |max: Option<GasCost>, successor| {
+                    let gas = path_gas[*successor]?;
+                    Some(Some(max.map_or(gas, |max| max.max(gas))))
+                },
+            );
+            path_gas[block] = match (own_gas, successors_gas) {
+                (Some(own_gas), Some(successors_gas)) => {
+                    Some(own_gas.add(successors_gas.unwrap_or_default()))
+                }
+                _ => None,
+            };
+        }
+        path_gas[0]
+    }
+
+    /// The gas used by the ops of `block`, including that of the functions it calls.
+    fn block_gas(&mut self, function: usize, block: &Block) -> Gas {
+        let functions = self.functions;
+        let mut gas = GasCost::default();
+        for op in &functions[function].ops[block.ops.clone()] {
+            let op_gas = match &op.opcode {
+                Either::Left(opcode) => opcode_gas(opcode, &self.costs)?,
+                Either::Right(ControlFlowOp::Call(label)) => {
+                    let callee = *self.function_labels.get(label)?;
+                    GasCost {
+                        gas: self.costs.ji,
+                        dynamic: false,
+                    }
+                    .add(self.function_gas(callee)?)
+                }
+                Either::Right(ControlFlowOp::Jump(_) | ControlFlowOp::JumpIfNotZero(..)) => {
+                    GasCost {
+                        gas: self.costs.jnzi,
+                        dynamic: false,
+                    }
+                }
+                // Saving the return address takes a `SUB`, a `SRLI` and an `ADDI`.
+                Either::Right(ControlFlowOp::SaveRetAddr(..)) => GasCost {
+                    gas: self.costs.sub + self.costs.srli + self.costs.addi,
+                    dynamic: false,
+                },
+                Either::Right(ControlFlowOp::LoadLabel(..)) => GasCost {
+                    gas: self.costs.lw,
+                    dynamic: false,
+                },
+                Either::Right(_) => GasCost::default(),
+            };
+            gas = gas.add(op_gas);
+        }
+        Some(gas)
+    }
+}
+
+/// Splits the ops of `function` into blocks, the first of which is the entry of the function.
+fn blocks(function: &AllocatedAbstractInstructionSet) -> Vec<Block> {
+    let ops = &function.ops;
+    if ops.is_empty() {
+        return vec![];
+    }
+
+    // A block starts at each label and after each op which may jump elsewhere.
+    let mut starts = vec![0];
+    for (idx, op) in ops.iter().enumerate() {
+        match &op.opcode {
+            Either::Right(ControlFlowOp::Label(_)) if idx != 0 => starts.push(idx),
+            opcode if ends_block(opcode) && idx + 1 < ops.len() => starts.push(idx + 1),
+            _ => (),
+        }
+    }
+    starts.dedup();
+
+    let label_blocks = starts
+        .iter()
+        .enumerate()
+        .filter_map(|(block, start)| match ops[*start].opcode {
+            Either::Right(ControlFlowOp::Label(label)) => Some((label, block)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let ends = starts
+        .iter()
+        .skip(1)
+        .copied()
+        .chain(std::iter::once(ops.len()));
+    starts
+        .iter()
+        .zip(ends)
+        .enumerate()
+        .map(|(block, (start, end))| {
+            let next = (end < ops.len()).then_some(block + 1);
+            let successors = match ops.get(end.wrapping_sub(1)).map(|op| &op.opcode) {
+                Some(Either::Right(ControlFlowOp::Jump(label))) => {
+                    label_blocks.get(label).copied().into_iter().collect()
+                }
+                Some(Either::Right(ControlFlowOp::JumpIfNotZero(_, label))) => next
+                    .into_iter()
+                    .chain(label_blocks.get(label).copied())
+                    .collect(),
+                // Returns, and the end of the program.
+                Some(Either::Left(
+                    AllocatedOpcode::JMP(_)
+                    | AllocatedOpcode::RET(_)
+                    | AllocatedOpcode::RETD(..)
+                    | AllocatedOpcode::RVRT(_),
+                )) => vec![],
+                _ => next.into_iter().collect(),
+            };
+            Block {
+                ops: start..end,
+                successors,
+            }
+        })
+        .collect()
+}
+
+fn ends_block(opcode: &Either<AllocatedOpcode, ControlFlowOp<AllocatedRegister>>) -> bool {
+    matches!(
+        opcode,
+        Either::Right(ControlFlowOp::Jump(_) | ControlFlowOp::JumpIfNotZero(..))
+            | Either::Left(
+                AllocatedOpcode::JMP(_)
+                    | AllocatedOpcode::RET(_)
+                    | AllocatedOpcode::RETD(..)
+                    | AllocatedOpcode::RVRT(_)
+            )
+    )
+}
+
+/// The gas used by `opcode`, or `None` if it's a jump to an offset, which only appears in ASM
+/// blocks and can't be followed.
+fn opcode_gas(opcode: &AllocatedOpcode, costs: &GasCosts) -> Gas {
+    use AllocatedOpcode::*;
+    let fixed = |gas: u64| {
+        Some(GasCost {
+            gas,
+            dynamic: false,
+        })
+    };
+    let dependent = |cost: &DependentCost| {
+        Some(GasCost {
+            gas: cost.base,
+            dynamic: true,
+        })
+    };
+    match opcode {
+        /* Arithmetic/Logic (ALU) Instructions */
+        ADD(..) => fixed(costs.add),
+        ADDI(..) => fixed(costs.addi),
+        AND(..) => fixed(costs.and),
+        ANDI(..) => fixed(costs.andi),
+        DIV(..) => fixed(costs.div),
+        DIVI(..) => fixed(costs.divi),
+        EQ(..) => fixed(costs.eq),
+        EXP(..) => fixed(costs.exp),
+        EXPI(..) => fixed(costs.expi),
+        GT(..) => fixed(costs.gt),
+        LT(..) => fixed(costs.lt),
+        MLOG(..) => fixed(costs.mlog),
+        MOD(..) => fixed(costs.mod_op),
+        MODI(..) => fixed(costs.modi),
+        MOVE(..) => fixed(costs.move_op),
+        MOVI(..) => fixed(costs.movi),
+        MROO(..) => fixed(costs.mroo),
+        MUL(..) => fixed(costs.mul),
+        MULI(..) => fixed(costs.muli),
+        NOOP => fixed(costs.noop),
+        NOT(..) => fixed(costs.not),
+        OR(..) => fixed(costs.or),
+        ORI(..) => fixed(costs.ori),
+        SLL(..) => fixed(costs.sll),
+        SLLI(..) => fixed(costs.slli),
+        SRL(..) => fixed(costs.srl),
+        SRLI(..) => fixed(costs.srli),
+        SUB(..) => fixed(costs.sub),
+        SUBI(..) => fixed(costs.subi),
+        XOR(..) => fixed(costs.xor),
+        XORI(..) => fixed(costs.xori),
+
+        /* Conrol Flow Instructions */
+        JMP(..) => fixed(costs.jmp),
+        RET(..) => fixed(costs.ret),
+        JI(..) | JNE(..) | JNEI(..) | JNZI(..) | JMPB(..) | JMPF(..) | JNZB(..) | JNZF(..) => None,
+
+        /* Memory Instructions */
+        ALOC(..) => fixed(costs.aloc),
+        CFEI(..) => fixed(costs.cfei),
+        CFSI(..) => fixed(costs.cfsi),
+        LB(..) => fixed(costs.lb),
+        LW(..) => fixed(costs.lw),
+        MCL(..) => dependent(&costs.mcl),
+        MCLI(..) => dependent(&costs.mcli),
+        MCP(..) => dependent(&costs.mcp),
+        MCPI(..) => fixed(costs.mcpi),
+        MEQ(..) => dependent(&costs.meq),
+        SB(..) => fixed(costs.sb),
+        SW(..) => fixed(costs.sw),
+
+        /* Contract Instructions */
+        BAL(..) => fixed(costs.bal),
+        BHEI(..) => fixed(costs.bhei),
+        BHSH(..) => fixed(costs.bhsh),
+        BURN(..) => fixed(costs.burn),
+        CALL(..) => dependent(&costs.call),
+        CB(..) => fixed(costs.cb),
+        CCP(..) => dependent(&costs.ccp),
+        CROO(..) => fixed(costs.croo),
+        CSIZ(..) => dependent(&costs.csiz),
+        LDC(..) => dependent(&costs.ldc),
+        LOG(..) => fixed(costs.log),
+        LOGD(..) => dependent(&costs.logd),
+        MINT(..) => fixed(costs.mint),
+        RETD(..) => dependent(&costs.retd),
+        RVRT(..) => fixed(costs.rvrt),
+        SMO(..) => dependent(&costs.smo),
+        SCWQ(..) => fixed(costs.scwq),
+        SRW(..) => fixed(costs.srw),
+        SRWQ(..) => dependent(&costs.srwq),
+        SWW(..) => fixed(costs.sww),
+        SWWQ(..) => fixed(costs.swwq),
+        TIME(..) => fixed(costs.time),
+        TR(..) => fixed(costs.tr),
+        TRO(..) => fixed(costs.tro),
+
+        /* Cryptographic Instructions */
+        ECR(..) => fixed(costs.ecr),
+        K256(..) => fixed(costs.k256),
+        S256(..) => fixed(costs.s256),
+
+        /* Other Instructions */
+        FLAG(..) => fixed(costs.flag),
+        GM(..) => fixed(costs.gm),
+        GTF(..) => fixed(costs.gtf),
+
+        /* Non-VM Instructions */
+        DataSectionRegisterLoadPlaceholder | LWDataId(..) => fixed(costs.lw),
+        BLOB(..) | DataSectionOffsetPlaceholder | Undefined => fixed(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::{AllocatedAbstractOp, ConstantRegister};
+
+    fn op(
+        opcode: Either<AllocatedOpcode, ControlFlowOp<AllocatedRegister>>,
+    ) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode,
+            comment: String::new(),
+            owning_span: None,
+            inlined_at: Vec::new(),
+        }
+    }
+
+    fn reg() -> AllocatedRegister {
+        AllocatedRegister::Allocated(0)
+    }
+
+    fn estimate(ops: Vec<AllocatedAbstractOp>) -> GasEstimate {
+        let function = AllocatedAbstractInstructionSet { ops };
+        let entries = [(None, Label(0), "main".to_string(), None)];
+        estimate_entries_gas(&[function], &entries).remove(0)
+    }
+
+    #[test]
+    fn most_expensive_branch() {
+        let costs = GasCosts::default();
+        let estimate = estimate(vec![
+            op(Either::Right(ControlFlowOp::Label(Label(0)))),
+            op(Either::Right(ControlFlowOp::JumpIfNotZero(reg(), Label(1)))),
+            op(Either::Left(AllocatedOpcode::MUL(reg(), reg(), reg()))),
+            op(Either::Left(AllocatedOpcode::MUL(reg(), reg(), reg()))),
+            op(Either::Right(ControlFlowOp::Label(Label(1)))),
+            op(Either::Left(AllocatedOpcode::RET(
+                AllocatedRegister::Constant(ConstantRegister::Zero),
+            ))),
+        ]);
+        assert_eq!(
+            estimate.max_gas,
+            Some(costs.jnzi + 2 * costs.mul + costs.ret)
+        );
+        assert!(!estimate.has_dynamic_costs);
+    }
+
+    #[test]
+    fn loops_are_unbounded() {
+        let estimate = estimate(vec![
+            op(Either::Right(ControlFlowOp::Label(Label(0)))),
+            op(Either::Right(ControlFlowOp::Label(Label(1)))),
+            op(Either::Left(AllocatedOpcode::MCP(reg(), reg(), reg()))),
+            op(Either::Right(ControlFlowOp::JumpIfNotZero(reg(), Label(1)))),
+            op(Either::Left(AllocatedOpcode::RET(reg()))),
+        ]);
+        assert_eq!(estimate.max_gas, None);
+    }
+}
@@ -4,8 +4,11 @@ pub(crate) mod register_allocator;
 
 pub(super) mod abstract_instruction_set;
 pub(super) mod allocated_abstract_instruction_set;
+pub(super) mod capabilities;
 pub(super) mod checks;
 pub(super) mod fuel_asm_builder;
+pub(super) mod gas_estimation;
 pub(super) mod register_sequencer;
 
 mod functions;
+mod stack_slots;
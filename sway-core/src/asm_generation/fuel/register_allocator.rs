@@ -753,6 +753,7 @@ pub(crate) fn allocate_registers(
             opcode: op.allocate_registers(&pool),
             comment: op.comment.clone(),
             owning_span: op.owning_span.clone(),
+            inlined_at: op.inlined_at.clone(),
         })
     }
 
@@ -857,6 +858,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 })),
                 comment: op.comment.clone() + &format!(" and {spills_size} bytes for spills"),
                 owning_span: op.owning_span.clone(),
+                inlined_at: op.inlined_at.clone(),
             });
         } else if matches!(cfs_idx_opt, Some(cfs_idx) if cfs_idx == op_idx) {
             // This is the CFS instruction, use the new stack size.
@@ -866,6 +868,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 })),
                 comment: op.comment.clone() + &format!(" and {spills_size} bytes for spills"),
                 owning_span: op.owning_span.clone(),
+                inlined_at: op.inlined_at.clone(),
             });
         } else {
             // For every other instruction:
@@ -890,6 +893,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Set offset".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_mov_instr);
                     let offset_add_instr = Op {
@@ -900,6 +904,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Add offset to stack base".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_add_instr);
                     (
@@ -926,6 +931,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Offset computation".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_upper_mov_instr);
                     let offset_upper_shift_instr = Op {
@@ -936,6 +942,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Offset computation".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_upper_shift_instr);
                     let offset_add_instr = Op {
@@ -946,6 +953,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Offset computation".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_add_instr);
                     (
@@ -975,6 +983,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Refilling from spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                 } else {
                     let (offset_reg, offset_imm_word) =
@@ -988,6 +997,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Refilling from spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     spilled.push(lw);
                 }
@@ -1013,6 +1023,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                 } else {
                     let (offset_reg, offset_imm_word) =
@@ -1026,6 +1037,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     spilled.push(sw);
                 }
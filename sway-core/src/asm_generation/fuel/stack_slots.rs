@@ -0,0 +1,224 @@
+//! Sharing of stack slots between the locals of a function.
+//!
+//! The locals which aren't promoted to registers each need space in the stack frame of their
+//! function, which is allocated, and zeroed by the VM, on every call. Locals which are never live
+//! at the same time share their space, by coloring the intervals in which they're live.
+//!
+//! The intervals are computed over the instructions numbered in reverse post order, from the first
+//! to the last instruction accessing a local, and widened to the whole of the loops they overlap,
+//! so that they cover every path from a write of a local to a read of it. Callees only access the
+//! locals they're passed pointers to while they're called. Locals whose address may be kept past
+//! the instructions accessing them, e.g. cast to an integer or passed to ASM blocks, and locals
+//! whose layout has padding, which would be left with the bytes of a previous local, keep their
+//! space for the whole function.
+
+use std::collections::{HashMap, HashSet};
+
+use sway_ir::{dominator::compute_post_order, *};
+
+/// The first and last instructions between which a local is live.
+type Interval = (usize, usize);
+
+/// Assigns each of the `locals` of `function`, with their sizes in words, a word offset from the
+/// locals base. Returns the offsets and the size of the locals in words.
+pub(super) fn assign_stack_slots(
+    context: &Context,
+    function: Function,
+    locals: &[(LocalVar, u64)],
+) -> (HashMap<LocalVar, u64>, u64) {
+    let intervals = live_intervals(context, function);
+    let (offsets, size) = color(
+        &locals
+            .iter()
+            .map(|(local, size)| (*size, intervals.get(local).copied()))
+            .collect::<Vec<_>>(),
+    );
+    let offsets = locals
+        .iter()
+        .zip(offsets)
+        .map(|((local, _), offset)| (*local, offset))
+        .collect();
+    (offsets, size)
+}
+
+/// Assigns each of the `locals`, with their sizes in words and the intervals in which they're
+/// live, the lowest word offset at which they don't overlap another local live at the same time.
+/// Locals which are never accessed have no interval. Returns the offsets and the size in words.
+fn color(locals: &[(u64, Option<Interval>)]) -> (Vec<u64>, u64) {
+    let mut offsets: Vec<u64> = Vec::with_capacity(locals.len());
+    let mut size = 0;
+    for (idx, (local_size, interval)) in locals.iter().enumerate() {
+        // The words taken by the locals already assigned which are live at the same time.
+        let taken: Vec<(u64, u64)> = locals[..idx]
+            .iter()
+            .zip(&offsets)
+            .filter(|((_, other), _)| match (interval, other) {
+                (Some((lo, hi)), Some((other_lo, other_hi))) => lo <= other_hi && other_lo <= hi,
+                _ => false,
+            })
+            .map(|((other_size, _), offset)| (*offset, offset + other_size))
+            .collect();
+        let offset = std::iter::once(0)
+            .chain(taken.iter().map(|(_, end)| *end))
+            .filter(|offset| {
+                taken
+                    .iter()
+                    .all(|(start, end)| offset + local_size <= *start || offset >= end)
+            })
+            .min()
+            .expect("the end of the last taken words is free");
+        size = size.max(offset + local_size);
+        offsets.push(offset);
+    }
+    (offsets, size)
+}
+
+/// The intervals in which the locals of `function` are live, for those which are accessed.
+fn live_intervals(context: &Context, function: Function) -> HashMap<LocalVar, Interval> {
+    let mut intervals: HashMap<LocalVar, Interval> = HashMap::new();
+    let mut access = |local: LocalVar, idx: usize| {
+        intervals.entry(local).or_insert((idx, idx)).1 = idx;
+    };
+
+    // The locals the pointers to them, or to their elements, point into.
+    let mut pointers: HashMap<Value, LocalVar> = HashMap::new();
+    let mut pinned = HashSet::new();
+    let mut block_ranges: HashMap<Block, Interval> = HashMap::new();
+    let po = compute_post_order(context, &function);
+    let mut idx = 0;
+    for block in po.po_to_block.iter().rev() {
+        let block_start = idx;
+        for inst_val in block.instruction_iter(context) {
+            let Some(inst) = inst_val.get_instruction(context) else {
+                continue;
+            };
+            match inst {
+                Instruction::GetLocal(local) => {
+                    pointers.insert(inst_val, *local);
+                    access(*local, idx);
+                }
+                Instruction::GetElemPtr { base, .. } | Instruction::CastPtr(base, _)
+                    if pointers.contains_key(base) =>
+                {
+                    let local = pointers[base];
+                    pointers.insert(inst_val, local);
+                    access(local, idx);
+                }
+                _ => {
+                    for operand in inst.get_operands() {
+                        if let Some(local) = pointers.get(&operand) {
+                            access(*local, idx);
+                            if keeps_address(context, inst_val, inst, operand) {
+                                pinned.insert(*local);
+                            }
+                        }
+                    }
+                }
+            }
+            idx += 1;
+        }
+        block_ranges.insert(*block, (block_start, idx.saturating_sub(1)));
+    }
+
+    // The initializers of locals are stored in them before the first instruction.
+    for (_, local) in function.locals_iter(context) {
+        if local.get_initializer(context).is_some() {
+            intervals.entry(*local).or_insert((0, 0)).0 = 0;
+        }
+        if has_padding(context, local.get_inner_type(context)) {
+            pinned.insert(*local);
+        }
+    }
+    for local in pinned {
+        intervals.insert(local, (0, usize::MAX));
+    }
+
+    // The loops, from the block a back edge goes to, to the end of the block it comes from.
+    let loops: Vec<Interval> = block_ranges
+        .iter()
+        .flat_map(|(block, (block_start, block_end))| {
+            let successors = match block.get_terminator(context) {
+                Some(Instruction::Branch(to)) => vec![to.block],
+                Some(Instruction::ConditionalBranch {
+                    true_block,
+                    false_block,
+                    ..
+                }) => vec![true_block.block, false_block.block],
+                _ => vec![],
+            };
+            successors
+                .into_iter()
+                .filter_map(|succ| block_ranges.get(&succ))
+                .filter(|(succ_start, _)| succ_start <= block_start)
+                .map(|(succ_start, _)| (*succ_start, *block_end))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    for interval in intervals.values_mut() {
+        let mut widened = true;
+        while widened {
+            widened = false;
+            for (loop_start, loop_end) in &loops {
+                let overlaps = *loop_start <= interval.1 && interval.0 <= *loop_end;
+                if overlaps && (*loop_start < interval.0 || interval.1 < *loop_end) {
+                    interval.0 = interval.0.min(*loop_start);
+                    interval.1 = interval.1.max(*loop_end);
+                    widened = true;
+                }
+            }
+        }
+    }
+    intervals
+}
+
+/// Whether `inst` may keep the address of the local `operand` points into past itself.
+fn keeps_address(context: &Context, inst_val: Value, inst: &Instruction, operand: Value) -> bool {
+    match inst {
+        Instruction::Load(_)
+        | Instruction::MemCopyBytes { .. }
+        | Instruction::MemCopyVal { .. }
+        | Instruction::ContractCall { .. }
+        | Instruction::FuelVm(_) => false,
+        Instruction::Store { stored_val, .. } => *stored_val == operand,
+        Instruction::Call(..) => inst_val
+            .get_type(context)
+            .map_or(false, |ty| ty.is_ptr(context)),
+        _ => true,
+    }
+}
+
+/// Whether values of `ty` have bytes which aren't written when they're stored.
+fn has_padding(context: &Context, ty: Type) -> bool {
+    match ty.get_content(context) {
+        TypeContent::String(n) => n % 8 != 0,
+        TypeContent::Union(_) => true,
+        TypeContent::Array(elem_ty, _) => has_padding(context, *elem_ty),
+        TypeContent::Struct(field_tys) => field_tys.iter().any(|ty| has_padding(context, *ty)),
+        TypeContent::Unit
+        | TypeContent::Bool
+        | TypeContent::Uint(_)
+        | TypeContent::B256
+        | TypeContent::Slice
+        | TypeContent::Pointer(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locals_live_at_different_times_share_slots() {
+        // `a` and `c` overlap, `b` is only live after `a`, and `d` is never accessed.
+        let locals = [
+            (2, Some((0, 4))),
+            (3, Some((5, 9))),
+            (1, Some((3, 7))),
+            (4, None),
+        ];
+        assert_eq!(color(&locals), (vec![0, 0, 3, 0], 4));
+
+        let pinned = [(2, Some((0, usize::MAX))), (1, Some((3, 3)))];
+        assert_eq!(color(&pinned), (vec![0, 2], 3));
+    }
+}
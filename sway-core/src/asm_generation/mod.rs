@@ -1,6 +1,7 @@
 pub mod abi;
 pub use abi::*;
 pub mod asm_builder;
+pub mod backend;
 pub mod evm;
 pub use evm::*;
 mod miden_vm;
@@ -11,6 +12,7 @@ mod instruction_set;
 mod programs;
 
 mod finalized_asm;
-pub use finalized_asm::{CompiledBytecode, FinalizedAsm, FinalizedEntry};
+pub use finalized_asm::{CompiledBytecode, FinalizedAsm, FinalizedEntry, FunctionSize, GasEstimate};
 
+pub(crate) use instruction_set::InstructionSet;
 pub(crate) use programs::ProgramKind;
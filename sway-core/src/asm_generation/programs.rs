@@ -9,10 +9,13 @@ use super::fuel::{
 };
 
 use crate::{
+    asm_generation::finalized_asm::GasEstimate,
     asm_lang::{allocated_ops::AllocatedOp, Label},
     decl_engine::DeclRefFunction,
 };
 
+use std::collections::HashMap;
+
 type SelectorOpt = Option<[u8; 4]>;
 type FnName = String;
 type ImmOffset = u64;
@@ -64,6 +67,10 @@ pub(super) enum FinalProgram {
         data_section: DataSection,
         ops: Vec<AllocatedOp>,
         entries: Vec<(SelectorOpt, ImmOffset, FnName, Option<DeclRefFunction>)>,
+        /// The instruction offset of each label, e.g. of the start of each function.
+        label_offsets: HashMap<Label, ImmOffset>,
+        /// The estimated gas used by each entry point which isn't a test.
+        gas_estimates: Vec<GasEstimate>,
     },
     Evm {
         ops: Vec<etk_asm::ops::AbstractOp>,
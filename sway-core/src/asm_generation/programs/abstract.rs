@@ -110,29 +110,34 @@ impl AbstractProgram {
                     opcode: Either::Right(ControlFlowOp::Jump(label)),
                     comment: String::new(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 1.5
                 AllocatedAbstractOp {
                     opcode: Either::Left(AllocatedOpcode::NOOP),
                     comment: "".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 2 -- full word u64 placeholder
                 AllocatedAbstractOp {
                     opcode: Either::Right(ControlFlowOp::DataSectionOffsetPlaceholder),
                     comment: "data section offset".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 AllocatedAbstractOp {
                     opcode: Either::Right(ControlFlowOp::Label(label)),
                     comment: "end of metadata".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 3 -- load the data offset into $ds
                 AllocatedAbstractOp {
                     opcode: Either::Left(AllocatedOpcode::DataSectionRegisterLoadPlaceholder),
                     comment: "".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 3.5 -- add $ds $ds $is
                 AllocatedAbstractOp {
@@ -143,6 +148,7 @@ impl AbstractProgram {
                     )),
                     comment: "".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
             ]
             .to_vec(),
@@ -164,6 +170,7 @@ impl AbstractProgram {
             opcode: Either::Right(ControlFlowOp::Comment),
             comment: "Begin contract ABI selector switch".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Load the selector from the call frame.
@@ -178,6 +185,7 @@ impl AbstractProgram {
             )),
             comment: "load input function selector".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Add a 'case' for each entry with a selector.
@@ -200,6 +208,7 @@ impl AbstractProgram {
                 opcode: Either::Left(AllocatedOpcode::LWDataId(PROG_SELECTOR_REG, data_label)),
                 comment: "load fn selector for comparison".into(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
 
             // Compare with the input selector.
@@ -211,6 +220,7 @@ impl AbstractProgram {
                 )),
                 comment: "function selector comparison".into(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
 
             // Jump to the function label if the selector was equal.
@@ -219,6 +229,7 @@ impl AbstractProgram {
                 opcode: Either::Right(ControlFlowOp::JumpIfNotZero(CMP_RESULT_REG, entry.label)),
                 comment: "jump to selected function".into(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
         }
 
@@ -233,6 +244,7 @@ impl AbstractProgram {
             )),
             comment: "special code for mismatched selector".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         asm_buf.ops.push(AllocatedAbstractOp {
             opcode: Either::Left(AllocatedOpcode::RVRT(AllocatedRegister::Constant(
@@ -240,6 +252,7 @@ impl AbstractProgram {
             ))),
             comment: "revert if no selectors matched".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 }
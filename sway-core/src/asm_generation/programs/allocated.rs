@@ -1,9 +1,17 @@
 use super::{AllocatedProgram, FinalProgram};
 
-use crate::asm_generation::fuel::allocated_abstract_instruction_set::AllocatedAbstractInstructionSet;
+use crate::asm_generation::fuel::{
+    allocated_abstract_instruction_set::AllocatedAbstractInstructionSet,
+    gas_estimation::estimate_entries_gas,
+};
+
+use std::collections::HashMap;
 
 impl AllocatedProgram {
     pub(crate) fn into_final_program(mut self) -> Result<FinalProgram, crate::CompileError> {
+        // Estimate the gas of the entries while the functions are still apart.
+        let gas_estimates = estimate_entries_gas(&self.functions, &self.entries);
+
         // Concat the prologue and all the functions together.
         let abstract_ops = AllocatedAbstractInstructionSet {
             ops: std::iter::once(self.prologue.ops)
@@ -12,19 +20,19 @@ impl AllocatedProgram {
                 .collect(),
         };
 
-        let (realized_ops, mut label_offsets) =
-            abstract_ops.realize_labels(&mut self.data_section)?;
+        let (realized_ops, label_offsets) = abstract_ops.realize_labels(&mut self.data_section)?;
         let ops = realized_ops.pad_to_even();
+        let label_offsets = label_offsets
+            .into_iter()
+            .map(|(label, block)| (label, block.offs))
+            .collect::<HashMap<_, _>>();
 
         // Collect the entry point offsets.
         let entries = self
             .entries
             .into_iter()
             .map(|(selector, label, name, test_decl_ref)| {
-                let offset = label_offsets
-                    .remove(&label)
-                    .expect("no offset for entry")
-                    .offs;
+                let offset = *label_offsets.get(&label).expect("no offset for entry");
                 (selector, offset, name, test_decl_ref)
             })
             .collect();
@@ -34,6 +42,8 @@ impl AllocatedProgram {
             data_section: self.data_section,
             ops,
             entries,
+            label_offsets,
+            gas_estimates,
         })
     }
 }
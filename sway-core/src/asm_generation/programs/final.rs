@@ -4,6 +4,7 @@ use crate::{
     asm_generation::{
         fuel::data_section::DataSection, instruction_set::InstructionSet, ProgramABI,
     },
+    debug_info::DebugInfo,
     FinalizedAsm, FinalizedEntry,
 };
 
@@ -15,6 +16,8 @@ impl FinalProgram {
                 data_section,
                 ops,
                 entries,
+                gas_estimates,
+                ..
             } => FinalizedAsm {
                 data_section,
                 program_section: InstructionSet::Fuel { ops },
@@ -29,6 +32,9 @@ impl FinalProgram {
                     })
                     .collect(),
                 abi: None,
+                function_sizes: vec![],
+                gas_estimates,
+                debug_info: DebugInfo::default(),
             },
             FinalProgram::Evm { ops, abi } => FinalizedAsm {
                 data_section: DataSection {
@@ -38,6 +44,9 @@ impl FinalProgram {
                 program_kind: super::ProgramKind::Script,
                 entries: vec![],
                 abi: Some(ProgramABI::Evm(abi)),
+                function_sizes: vec![],
+                gas_estimates: vec![],
+                debug_info: DebugInfo::default(),
             },
             FinalProgram::MidenVM { ops } => FinalizedAsm {
                 data_section: DataSection {
@@ -48,6 +57,9 @@ impl FinalProgram {
                 program_kind: super::ProgramKind::Script,
                 entries: vec![],
                 abi: None, /* TODO? */
+                function_sizes: vec![],
+                gas_estimates: vec![],
+                debug_info: DebugInfo::default(),
             },
         }
     }
@@ -440,6 +440,8 @@ pub struct AllocatedOp {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The call sites the op was inlined at, innermost first.
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 impl fmt::Display for AllocatedOp {
@@ -461,6 +463,23 @@ impl fmt::Display for AllocatedOp {
 type DoubleWideData = [u8; 8];
 
 impl AllocatedOp {
+    /// The number of bytes this op takes up in the bytecode.
+    ///
+    /// Some `LW`s are expanded into two ops, to allow for data larger than one word.
+    pub(crate) fn size_in_bytes(&self, data_section: &DataSection) -> u64 {
+        match &self.opcode {
+            AllocatedOpcode::LWDataId(_reg, data_label)
+                if !data_section
+                    .has_copy_type(data_label)
+                    .expect("data label references non existent data -- internal error") =>
+            {
+                8
+            }
+            AllocatedOpcode::BLOB(count) => count.value as u64 * 4,
+            _ => 4,
+        }
+    }
+
     pub(crate) fn to_fuel_asm(
         &self,
         offset_to_data_section: u64,
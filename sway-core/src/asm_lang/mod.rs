@@ -46,6 +46,8 @@ pub(crate) struct Op {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The call sites the op was inlined at, innermost first.
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 #[derive(Clone, Debug)]
@@ -54,6 +56,8 @@ pub(crate) struct AllocatedAbstractOp {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The call sites the op was inlined at, innermost first.
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 #[derive(Clone, Debug)]
@@ -62,6 +66,8 @@ pub(crate) struct RealizedOp {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The call sites the op was inlined at, innermost first.
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 impl Op {
@@ -77,6 +83,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::SW(destination_address, value_to_write, offset)),
             comment: String::new(),
             owning_span: Some(span),
+            inlined_at: Vec::new(),
         }
     }
     /// Write value in given [VirtualRegister] `value_to_write` to given memory address that is held within the
@@ -92,6 +99,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::SW(destination_address, value_to_write, offset)),
             comment: comment.into(),
             owning_span: Some(span),
+            inlined_at: Vec::new(),
         }
     }
     /// Moves the stack pointer by the given amount (i.e. allocates stack memory)
@@ -102,6 +110,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::CFEI(size_to_allocate_in_bytes)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
     pub(crate) fn unowned_new_with_comment(opcode: VirtualOp, comment: impl Into<String>) -> Self {
@@ -109,6 +118,7 @@ impl Op {
             opcode: Either::Left(opcode),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
     pub(crate) fn new(opcode: VirtualOp, owning_span: Span) -> Self {
@@ -116,6 +126,7 @@ impl Op {
             opcode: Either::Left(opcode),
             comment: String::new(),
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
     pub(crate) fn new_with_comment(
@@ -128,6 +139,7 @@ impl Op {
             opcode: Either::Left(opcode),
             comment,
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
 
@@ -137,6 +149,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: String::new(),
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
     /// Loads the data from [DataId] `data` into [VirtualRegister] `reg`.
@@ -149,6 +162,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::LWDataId(reg, data)),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -159,6 +173,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -173,6 +188,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: comment.into(),
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
 
@@ -182,6 +198,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -196,6 +213,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::SaveRetAddr(reg, label)),
             comment: comment.into(),
             owning_span,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -210,6 +228,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::MOVE(r1, r2)),
             comment: comment.into(),
             owning_span,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -218,6 +237,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Comment),
             comment: comm.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -226,6 +246,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Jump(label)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -234,6 +255,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Jump(label)),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -243,6 +265,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::JumpIfNotZero(reg0, label)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -256,6 +279,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::JMP(reg)),
             comment: comment.into(),
             owning_span,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -1052,6 +1076,7 @@ impl Op {
             },
             comment: self.comment.clone(),
             owning_span: self.owning_span.clone(),
+            inlined_at: self.inlined_at.clone(),
         }
     }
 
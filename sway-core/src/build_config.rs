@@ -1,7 +1,14 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
 use serde::{Deserialize, Serialize};
-use strum::EnumString;
+use strum::{Display, EnumIter, EnumString};
+use sway_error::warning::Warning;
 
 #[derive(
     Clone,
@@ -14,6 +21,7 @@ use strum::EnumString;
     Serialize,
     Deserialize,
     clap::ValueEnum,
+    Display,
     EnumString,
 )]
 pub enum BuildTarget {
@@ -32,11 +40,222 @@ pub enum BuildTarget {
     MidenVM,
 }
 
+/// Identifies what code is generated for: a VM and, optionally, the revision of it, e.g. `fuel`
+/// or `fuel-0.34`.
+///
+/// The revision determines the opcode set that the generated code may use. Without one, code is
+/// generated for the newest revision of the VM supported by the compiler.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Target {
+    pub build_target: BuildTarget,
+    pub vm_version: Option<String>,
+}
+
+impl Target {
+    pub fn new(build_target: BuildTarget, vm_version: Option<String>) -> Self {
+        Self {
+            build_target,
+            vm_version,
+        }
+    }
+}
+
+impl From<BuildTarget> for Target {
+    fn from(build_target: BuildTarget) -> Self {
+        Self::new(build_target, None)
+    }
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (build_target, vm_version) = match s.split_once('-') {
+            Some((build_target, vm_version)) => (build_target, Some(vm_version)),
+            None => (s, None),
+        };
+        let build_target = BuildTarget::from_str(build_target)
+            .map_err(|_| format!("unknown target VM `{build_target}` in target `{s}`"))?;
+        match vm_version {
+            Some("") => Err(format!("missing VM revision in target `{s}`")),
+            vm_version => Ok(Self::new(build_target, vm_version.map(str::to_string))),
+        }
+    }
+}
+
+impl TryFrom<String> for Target {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+impl From<Target> for String {
+    fn from(target: Target) -> Self {
+        target.to_string()
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.vm_version {
+            Some(vm_version) => write!(f, "{}-{vm_version}", self.build_target),
+            None => write!(f, "{}", self.build_target),
+        }
+    }
+}
+
+/// Language features that are still in development, and so have to be explicitly enabled, either
+/// with `experimental` in the `[project]` section of the package manifest or with `--experimental`.
+///
+/// Using the syntax of a feature that is not enabled results in an error naming the feature.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+    Display,
+    EnumIter,
+    EnumString,
+)]
+pub enum ExperimentalFeature {
+    /// `if let` conditions chained with `&&`, e.g. `if let Some(x) = a && x > 0 { .. }`.
+    #[serde(rename = "if_let_chains")]
+    #[clap(name = "if_let_chains")]
+    #[strum(serialize = "if_let_chains")]
+    IfLetChains,
+}
+
+impl ExperimentalFeature {
+    /// Describes the syntax enabled by the feature, for diagnostics.
+    pub fn description(&self) -> &'static str {
+        match self {
+            ExperimentalFeature::IfLetChains => "`if let` chains",
+        }
+    }
+}
+
+/// How the warnings of a lint are reported, see [sway_error::warning::Warning::lint_name].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum LintLevel {
+    /// The warnings are not reported.
+    Allow,
+    /// The warnings are reported as warnings.
+    Warn,
+    /// The warnings are reported as errors, failing the build.
+    Deny,
+}
+
+/// How much the IR of programs is optimized, from not at all to the most.
+///
+/// `O2` follows the optimizations of `O1` with another round of inlining and simplifications,
+/// trading compilation time for smaller and cheaper code.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum OptLevel {
+    /// No optimizations, only the transformations required for code generation.
+    O0,
+    #[default]
+    O1,
+    O2,
+}
+
+impl TryFrom<u8> for OptLevel {
+    type Error = String;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        match level {
+            0 => Ok(OptLevel::O0),
+            1 => Ok(OptLevel::O1),
+            2 => Ok(OptLevel::O2),
+            _ => Err(format!(
+                "unknown optimization level `{level}`, expected one of: 0, 1, 2"
+            )),
+        }
+    }
+}
+
+impl From<OptLevel> for u8 {
+    fn from(level: OptLevel) -> Self {
+        level as u8
+    }
+}
+
+/// The heuristics deciding which function calls are inlined, see [BuildConfig::inlining].
+///
+/// Functions marked `#[inline(always)]` are inlined and those marked `#[inline(never)]` aren't,
+/// regardless of these.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct Inlining {
+    /// Inline functions with at most this many IR instructions.
+    pub max_instructions: usize,
+    /// Only inline small functions if they also have at most this many IR blocks.
+    pub max_blocks: Option<usize>,
+    /// Inline functions which are called only once, regardless of their size.
+    pub single_call_sites: bool,
+    /// Don't inline functions into others if it nests inlined code deeper than this.
+    pub max_depth: Option<usize>,
+}
+
+impl Default for Inlining {
+    fn default() -> Self {
+        let params = sway_ir::InlineParams::default();
+        Self {
+            max_instructions: params.max_instrs,
+            max_blocks: params.max_blocks,
+            single_call_sites: params.single_call_sites,
+            max_depth: params.max_depth,
+        }
+    }
+}
+
+impl From<Inlining> for sway_ir::InlineParams {
+    fn from(inlining: Inlining) -> Self {
+        Self {
+            max_instrs: inlining.max_instructions,
+            max_blocks: inlining.max_blocks,
+            single_call_sites: inlining.single_call_sites,
+            max_depth: inlining.max_depth,
+            inline_all: false,
+        }
+    }
+}
+
+/// What the arithmetic operators `+`, `-` and `*` do when their result doesn't fit in its type,
+/// see [BuildConfig::overflow].
+///
+/// The `__wrapping_*` and `__checked_*` intrinsics always wrap and check, regardless of it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverflowBehavior {
+    /// The VM panics on overflows of `u64`, and the smaller integer types revert.
+    #[default]
+    Panic,
+    /// The result wraps around, modulo the range of its type.
+    Wrap,
+    /// The operation reverts with `FAILED_OVERFLOW_SIGNAL`.
+    Checked,
+}
+
 /// Configuration for the overall build and compilation process.
 #[derive(Clone)]
 pub struct BuildConfig {
     // Build target for code generation.
     pub(crate) build_target: BuildTarget,
+    // The revision of the build target's VM to generate code for, the newest if `None`.
+    pub(crate) vm_version: Option<String>,
     // The canonical file path to the root module.
     // E.g. `/home/user/project/src/main.sw`.
     pub(crate) canonical_root_module: Arc<PathBuf>,
@@ -45,7 +264,27 @@ pub struct BuildConfig {
     pub(crate) print_intermediate_asm: bool,
     pub(crate) print_finalized_asm: bool,
     pub(crate) print_ir: bool,
+    pub(crate) print_smt: bool,
+    pub(crate) dump_fn: Option<String>,
     pub(crate) include_tests: bool,
+    // Whether the conditions of the specification attributes are checked at runtime.
+    pub(crate) check_specifications: bool,
+    // What the arithmetic operators do when they overflow.
+    pub(crate) overflow: OverflowBehavior,
+    // Module sources by path, used instead of the file system when compiling in memory.
+    pub(crate) in_memory_sources: Option<Arc<HashMap<PathBuf, Arc<str>>>>,
+    pub(crate) experimental: HashSet<ExperimentalFeature>,
+    // The levels of the lints configured explicitly, by lint name.
+    pub(crate) lint_levels: HashMap<String, LintLevel>,
+    // Whether the lints without an explicit level are denied rather than warned about.
+    pub(crate) deny_warnings: bool,
+    pub(crate) optimization_level: OptLevel,
+    // The IR passes to run instead of those of the optimization level, by name.
+    pub(crate) ir_passes: Option<Vec<String>>,
+    // Whether the IR is verified after each pass which modifies it, as it is in debug builds.
+    pub(crate) verify_ir: bool,
+    pub(crate) inlining: Inlining,
+    pub(crate) cse_storage_reads: bool,
     pub time_phases: bool,
     pub metrics_outfile: Option<String>,
 }
@@ -83,18 +322,77 @@ impl BuildConfig {
         };
         Self {
             build_target,
+            vm_version: None,
             canonical_root_module: Arc::new(canonical_root_module),
             print_dca_graph: None,
             print_dca_graph_url_format: None,
             print_intermediate_asm: false,
             print_finalized_asm: false,
             print_ir: false,
+            print_smt: false,
+            dump_fn: None,
             include_tests: false,
+            check_specifications: false,
+            overflow: OverflowBehavior::default(),
+            in_memory_sources: None,
+            experimental: HashSet::new(),
+            lint_levels: HashMap::new(),
+            deny_warnings: false,
+            optimization_level: OptLevel::default(),
+            ir_passes: None,
+            verify_ir: false,
+            inlining: Inlining::default(),
+            cse_storage_reads: false,
             time_phases: false,
             metrics_outfile: None,
         }
     }
 
+    /// Construct a `BuildConfig` for compiling module sources held in memory instead of files.
+    ///
+    /// The `root_module` is the virtual path of the root module within `sources`, e.g.
+    /// `src/main.sw`. Submodules are looked up in `sources` relative to it, following the same
+    /// layout as on disk, so `mod foo;` in `src/main.sw` refers to `src/foo.sw`.
+    pub fn root_from_in_memory_sources(
+        root_module: PathBuf,
+        sources: HashMap<PathBuf, Arc<str>>,
+        build_target: BuildTarget,
+    ) -> Self {
+        Self {
+            build_target,
+            vm_version: None,
+            canonical_root_module: Arc::new(root_module),
+            print_dca_graph: None,
+            print_dca_graph_url_format: None,
+            print_intermediate_asm: false,
+            print_finalized_asm: false,
+            print_ir: false,
+            print_smt: false,
+            dump_fn: None,
+            include_tests: false,
+            check_specifications: false,
+            overflow: OverflowBehavior::default(),
+            in_memory_sources: Some(Arc::new(sources)),
+            experimental: HashSet::new(),
+            lint_levels: HashMap::new(),
+            deny_warnings: false,
+            optimization_level: OptLevel::default(),
+            ir_passes: None,
+            verify_ir: false,
+            inlining: Inlining::default(),
+            cse_storage_reads: false,
+            time_phases: false,
+            metrics_outfile: None,
+        }
+    }
+
+    /// The revision of the build target's VM to generate code for, e.g. `0.34` for the Fuel VM.
+    ///
+    /// Default: `None`, i.e. the newest revision supported by the compiler.
+    pub fn vm_version(self, vm_version: Option<String>) -> Self {
+        Self { vm_version, ..self }
+    }
+
     pub fn print_dca_graph(self, a: Option<String>) -> Self {
         Self {
             print_dca_graph: a,
@@ -109,6 +407,14 @@ impl BuildConfig {
         }
     }
 
+    /// Print the IR, the finalized ASM and the range in the bytecode of each function whose name
+    /// matches this pattern, in which `*` matches any sequence of characters.
+    ///
+    /// Default: `None`
+    pub fn dump_fn(self, dump_fn: Option<String>) -> Self {
+        Self { dump_fn, ..self }
+    }
+
     pub fn print_intermediate_asm(self, a: bool) -> Self {
         Self {
             print_intermediate_asm: a,
@@ -130,6 +436,14 @@ impl BuildConfig {
         }
     }
 
+    /// Print the pure functions of the optimized IR in SMT-LIB 2, along with their path
+    /// conditions, for formal-methods tools to check their assertions. See [sway_ir::smt].
+    ///
+    /// Default: `false`
+    pub fn print_smt(self, print_smt: bool) -> Self {
+        Self { print_smt, ..self }
+    }
+
     pub fn time_phases(self, a: bool) -> Self {
         Self {
             time_phases: a,
@@ -156,7 +470,129 @@ impl BuildConfig {
         }
     }
 
+    /// Whether the conditions of the `#[requires(..)]` and `#[ensures(..)]` attributes of the
+    /// functions are checked at runtime, reverting if they don't hold. They always are when
+    /// [BuildConfig::include_tests] is set, and they're type checked regardless.
+    ///
+    /// Default: `false`
+    pub fn check_specifications(self, check_specifications: bool) -> Self {
+        Self {
+            check_specifications,
+            ..self
+        }
+    }
+
+    /// The [ExperimentalFeature]s to enable.
+    ///
+    /// Default: none
+    pub fn experimental(self, experimental: impl IntoIterator<Item = ExperimentalFeature>) -> Self {
+        Self {
+            experimental: experimental.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// The levels of individual lints, by lint name, overriding [BuildConfig::deny_warnings].
+    ///
+    /// Default: none, i.e. the lints of [Warning::ALLOWED_BY_DEFAULT] are allowed and all others
+    /// are warned about
+    pub fn lint_levels(self, lint_levels: impl IntoIterator<Item = (String, LintLevel)>) -> Self {
+        Self {
+            lint_levels: lint_levels.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Whether to report the warnings of all lints without an explicit level as errors.
+    ///
+    /// Default: `false`
+    pub fn deny_warnings(self, deny_warnings: bool) -> Self {
+        Self {
+            deny_warnings,
+            ..self
+        }
+    }
+
+    /// How much to optimize the IR.
+    ///
+    /// Default: [OptLevel::O1]
+    pub fn optimization_level(self, optimization_level: OptLevel) -> Self {
+        Self {
+            optimization_level,
+            ..self
+        }
+    }
+
+    /// The IR passes to run, by name and in order, instead of those of the
+    /// [BuildConfig::optimization_level], e.g. to find the pass which miscompiles a program.
+    ///
+    /// The transformations required for code generation are run regardless.
+    ///
+    /// Default: `None`
+    pub fn ir_passes(self, ir_passes: Option<Vec<String>>) -> Self {
+        Self { ir_passes, ..self }
+    }
+
+    /// Whether the IR is verified after each pass which modifies it, to find the pass which
+    /// produces invalid IR.  It always is when the compiler itself is a debug build.
+    ///
+    /// Default: `false`
+    pub fn verify_ir(self, verify_ir: bool) -> Self {
+        Self { verify_ir, ..self }
+    }
+
+    /// The heuristics deciding which function calls are inlined.
+    ///
+    /// Default: [Inlining::default]
+    pub fn inlining(self, inlining: Inlining) -> Self {
+        Self { inlining, ..self }
+    }
+
+    /// What the arithmetic operators `+`, `-` and `*` do when their result overflows.
+    ///
+    /// Default: [OverflowBehavior::Panic]
+    pub fn overflow(self, overflow: OverflowBehavior) -> Self {
+        Self { overflow, ..self }
+    }
+
+    /// Whether common subexpression elimination also deduplicates reads of the same storage slot
+    /// within a block, when nothing in between may write to storage.
+    ///
+    /// Default: `false`
+    pub fn cse_storage_reads(self, cse_storage_reads: bool) -> Self {
+        Self {
+            cse_storage_reads,
+            ..self
+        }
+    }
+
+    /// The level at which the warnings of the lint `lint_name` are reported.
+    ///
+    /// Lints which are allowed by default stay allowed with [BuildConfig::deny_warnings].
+    pub(crate) fn lint_level(&self, lint_name: &str) -> LintLevel {
+        match self.lint_levels.get(lint_name) {
+            Some(level) => *level,
+            None if Warning::ALLOWED_BY_DEFAULT.contains(&lint_name) => LintLevel::Allow,
+            None if self.deny_warnings => LintLevel::Deny,
+            None => LintLevel::Warn,
+        }
+    }
+
     pub fn canonical_root_module(&self) -> Arc<PathBuf> {
         self.canonical_root_module.clone()
     }
+
+    /// Reads the source of the module at `path`, from memory if the config was constructed with
+    /// [BuildConfig::root_from_in_memory_sources] and from the file system otherwise.
+    pub(crate) fn read_module_source(&self, path: &Path) -> std::io::Result<Arc<str>> {
+        match &self.in_memory_sources {
+            Some(sources) => sources.get(path).cloned().ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "no in-memory source was provided for this path",
+                )
+            }),
+            None => std::fs::read_to_string(path).map(Arc::from),
+        }
+    }
 }
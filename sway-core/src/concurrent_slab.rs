@@ -1,12 +1,165 @@
-use std::{fmt, sync::RwLock};
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    fmt,
+    sync::{
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+        OnceLock,
+    },
+};
 
-use sway_types::{Named, Spanned};
+use sway_types::{Named, SourceId, Spanned};
 
 use crate::{decl_engine::*, engine_threading::*, type_system::*};
 
-#[derive(Debug)]
+/// When and for which module an entry of a slab was created.
+///
+/// Each compilation with the same [Engines] starts a new generation, so that once a module has
+/// been compiled again, the entries created for it in previous generations can be collected by
+/// [Engines::gc].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Provenance {
+    /// The last generation the entry was used in.
+    pub(crate) generation: u32,
+    /// The generation the entry was created in.
+    pub(crate) created: u32,
+    /// The module being compiled when the entry was created, or `None` if it was created outside
+    /// of any module or is shared between modules, as interned types are.
+    pub(crate) source_id: Option<SourceId>,
+}
+
+/// The message of the panic when an entry is used after it was collected.
+const COLLECTED: &str = "the entry was collected by `Engines::gc`";
+
+thread_local! {
+    /// The module being compiled by this thread, which the entries it creates are attributed to.
+    static CURRENT_MODULE: Cell<Option<SourceId>> = const { Cell::new(None) };
+}
+
+impl Provenance {
+    /// The provenance of the entries created by this thread in `generation`.
+    pub(crate) fn current(generation: u32) -> Self {
+        Provenance {
+            generation,
+            created: generation,
+            source_id: CURRENT_MODULE.with(Cell::get),
+        }
+    }
+}
+
+/// Attributes the entries created by this thread to the module `source_id`, and returns the
+/// module they were attributed to before.
+pub(crate) fn set_current_module(source_id: Option<SourceId>) -> Option<SourceId> {
+    CURRENT_MODULE.with(|current_module| current_module.replace(source_id))
+}
+
+/// The number of elements of the first bucket of a [Chunks]. Each of the next buckets is twice as
+/// large as the one before it, so that the elements never have to move to make room.
+const FIRST_BUCKET_LEN: usize = 32;
+
+/// The number of buckets needed to hold any index.
+const BUCKETS: usize = (usize::BITS - FIRST_BUCKET_LEN.trailing_zeros()) as usize;
+
+/// An append only sequence of elements, which never move once they've been added, so that they
+/// can be referred to without locking anything.
+struct Chunks<E> {
+    /// The buckets of elements, which are allocated as the sequence grows.
+    buckets: [OnceLock<Box<[E]>>; BUCKETS],
+    /// The number of indices reserved so far, including those of the elements being added.
+    len: AtomicUsize,
+}
+
+impl<E> Default for Chunks<E> {
+    fn default() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| OnceLock::new()),
+            len: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// The bucket of the element at `index`, and the offset of the element in it.
+fn bucket_and_offset(index: usize) -> (usize, usize) {
+    let shifted = index + FIRST_BUCKET_LEN;
+    let bucket_start = 1 << (usize::BITS - 1 - shifted.leading_zeros());
+    let bucket = (bucket_start / FIRST_BUCKET_LEN).trailing_zeros() as usize;
+    (bucket, shifted - bucket_start)
+}
+
+impl<E: Default> Chunks<E> {
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// The element at `index`, unless its bucket is still being allocated.
+    fn get(&self, index: usize) -> Option<&E> {
+        let (bucket, offset) = bucket_and_offset(index);
+        self.buckets[bucket].get().map(|elements| &elements[offset])
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut E> {
+        let (bucket, offset) = bucket_and_offset(index);
+        self.buckets[bucket]
+            .get_mut()
+            .map(|elements| &mut elements[offset])
+    }
+
+    /// Reserves the next index, and returns it along with its element, still the default one.
+    fn push(&self) -> (usize, &E) {
+        let index = self.len.fetch_add(1, Ordering::AcqRel);
+        let (bucket, offset) = bucket_and_offset(index);
+        let elements = self.buckets[bucket].get_or_init(|| {
+            (0..FIRST_BUCKET_LEN << bucket)
+                .map(|_| E::default())
+                .collect()
+        });
+        (index, &elements[offset])
+    }
+}
+
+/// An entry of a slab, which refers to its current value among the values of the slab.
+#[derive(Default)]
+struct Entry {
+    /// One more than the index of the current value among the values of the slab, or zero while
+    /// the entry is being inserted and once it has been collected.
+    value: AtomicUsize,
+    /// The last generation the entry was used in.
+    generation: AtomicU32,
+    /// The generation the entry was created in and the module it was created for, set once the
+    /// entry has been inserted.
+    origin: OnceLock<(u32, Option<SourceId>)>,
+}
+
+impl Entry {
+    fn provenance(&self) -> Option<Provenance> {
+        let (created, source_id) = *self.origin.get()?;
+        Some(Provenance {
+            generation: self.generation.load(Ordering::Relaxed),
+            created,
+            source_id,
+        })
+    }
+}
+
+/// An append only collection of entries, which can be read without taking any lock.
+///
+/// The values of the entries are never modified in place: inserting or replacing a value adds it
+/// to the values of the slab, and atomically points the entry at it. Reading an entry therefore
+/// only loads the index of its current value, and inserting and replacing entries only contend
+/// with each other on the same entry. The values which are no longer pointed at, as they were
+/// replaced or their entries collected, are dropped by [ConcurrentSlab::collect], which has the
+/// slab to itself.
 pub(crate) struct ConcurrentSlab<T> {
-    inner: RwLock<Vec<T>>,
+    entries: Chunks<Entry>,
+    values: Chunks<OnceLock<T>>,
+}
+
+impl<T> fmt::Debug for ConcurrentSlab<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrentSlab")
+            .field("len", &self.entries.len())
+            .finish()
+    }
 }
 
 impl<T> Clone for ConcurrentSlab<T>
@@ -14,24 +167,144 @@ where
     T: Clone,
 {
     fn clone(&self) -> Self {
-        let inner = self.inner.read().unwrap();
-        Self {
-            inner: RwLock::new(inner.clone()),
+        let slab = Self::default();
+        for index in 0..self.entries.len() {
+            let (_, clone) = slab.entries.push();
+            let Some(entry) = self.entries.get(index) else {
+                continue;
+            };
+            if let Some(value) = self.value(entry.value.load(Ordering::Acquire)) {
+                clone
+                    .value
+                    .store(slab.push_value(value.clone()), Ordering::Release);
+            }
+            if let Some(provenance) = entry.provenance() {
+                clone
+                    .generation
+                    .store(provenance.generation, Ordering::Relaxed);
+                let _ = clone.origin.set((provenance.created, provenance.source_id));
+            }
         }
+        slab
     }
 }
 
 impl<T> Default for ConcurrentSlab<T> {
     fn default() -> Self {
         Self {
-            inner: Default::default(),
+            entries: Chunks::default(),
+            values: Chunks::default(),
         }
     }
 }
 
 impl<T> ConcurrentSlab<T> {
-    pub fn with_slice<R>(&self, run: impl FnOnce(&[T]) -> R) -> R {
-        run(&self.inner.read().unwrap())
+    fn entry(&self, index: usize) -> &Entry {
+        self.entries.get(index).expect("index out of bounds")
+    }
+
+    /// The entries of the slab along with their indices.
+    fn entries(&self) -> impl Iterator<Item = (usize, &Entry)> {
+        (0..self.entries.len())
+            .filter_map(|index| self.entries.get(index).map(|entry| (index, entry)))
+    }
+
+    /// Adds `value` to the values of the slab, returning what entries pointing at it hold.
+    fn push_value(&self, value: T) -> usize {
+        let (index, slot) = self.values.push();
+        let _ = slot.set(value);
+        index + 1
+    }
+
+    /// The value an entry holding `value` points at, if any.
+    fn value(&self, value: usize) -> Option<&T> {
+        let index = value.checked_sub(1)?;
+        self.values.get(index).and_then(OnceLock::get)
+    }
+
+    /// The current value of the entry at `index`.
+    fn current(&self, index: usize) -> (usize, &T) {
+        let value = self.entry(index).value.load(Ordering::Acquire);
+        (value, self.value(value).expect(COLLECTED))
+    }
+
+    /// Runs `run` on the values of the entries, by index, where those which have been collected
+    /// are `None`.
+    pub fn with_values<R>(&self, run: impl FnOnce(&[Option<&T>]) -> R) -> R {
+        let values = (0..self.entries.len())
+            .map(|index| {
+                let entry = self.entries.get(index)?;
+                self.value(entry.value.load(Ordering::Acquire))
+            })
+            .collect::<Vec<_>>();
+        run(&values)
+    }
+
+    /// The number of entries whose values haven't been collected.
+    pub fn live_count(&self) -> usize {
+        self.entries()
+            .filter(|(_, entry)| entry.value.load(Ordering::Acquire) != 0)
+            .count()
+    }
+
+    /// The provenance of the entry at `index`.
+    pub fn provenance(&self, index: usize) -> Provenance {
+        self.entry(index).provenance().expect("index out of bounds")
+    }
+
+    /// Moves the entry at `index` to `generation`, as it's still in use.
+    pub fn touch(&self, index: usize, generation: u32) {
+        self.entry(index)
+            .generation
+            .fetch_max(generation, Ordering::Relaxed);
+    }
+
+    /// The modules for which the entries of the generations before `generation` were created,
+    /// among those which haven't been collected.
+    pub fn sources_before(&self, generation: u32) -> HashSet<SourceId> {
+        self.entries()
+            .filter(|(_, entry)| entry.value.load(Ordering::Acquire) != 0)
+            .filter_map(|(_, entry)| {
+                let provenance = entry.provenance()?;
+                (provenance.generation < generation)
+                    .then_some(provenance.source_id)
+                    .flatten()
+            })
+            .collect()
+    }
+
+    /// Drops the values of the entries whose provenance is `collectible`, along with the values
+    /// which have been replaced since the last collection, and returns the indices of the
+    /// collected entries. The indices aren't reused, so that any left over id of a collected entry
+    /// can't refer to another.
+    pub fn collect(&mut self, collectible: &dyn Fn(&Provenance) -> bool) -> HashSet<usize> {
+        let mut collected = HashSet::new();
+        let mut current_values = HashSet::new();
+        for index in 0..self.entries.len() {
+            let Some(entry) = self.entries.get_mut(index) else {
+                continue;
+            };
+            let (Some(provenance), value) = (entry.provenance(), *entry.value.get_mut()) else {
+                continue;
+            };
+            if value == 0 {
+                continue;
+            }
+            if collectible(&provenance) {
+                *entry.value.get_mut() = 0;
+                collected.insert(index);
+            } else {
+                current_values.insert(value - 1);
+            }
+        }
+        for index in 0..self.values.len() {
+            if !current_values.contains(&index) {
+                if let Some(slot) = self.values.get_mut(index) {
+                    slot.take();
+                }
+            }
+        }
+        collected
     }
 }
 
@@ -59,16 +332,19 @@ impl<T> ConcurrentSlab<T>
 where
     T: Clone,
 {
-    pub fn insert(&self, value: T) -> usize {
-        let mut inner = self.inner.write().unwrap();
-        let ret = inner.len();
-        inner.push(value);
-        ret
+    pub fn insert(&self, value: T, provenance: Provenance) -> usize {
+        let value = self.push_value(value);
+        let (index, entry) = self.entries.push();
+        entry
+            .generation
+            .store(provenance.generation, Ordering::Relaxed);
+        let _ = entry.origin.set((provenance.created, provenance.source_id));
+        entry.value.store(value, Ordering::Release);
+        index
     }
 
     pub fn get(&self, index: usize) -> T {
-        let inner = self.inner.read().unwrap();
-        inner[index].clone()
+        self.current(index).1.clone()
     }
 }
 
@@ -80,23 +356,23 @@ impl ConcurrentSlab<TypeInfo> {
         new_value: TypeInfo,
         engines: &Engines,
     ) -> Option<TypeInfo> {
-        let index = index.index();
-        // The comparison below ends up calling functions in the slab, which
-        // can lead to deadlocks if we used a single read/write lock.
-        // So we split the operation: we do the read only operations with
-        // a single scoped read lock below, and only after the scope do
-        // we get a write lock for writing into the slab.
-        {
-            let inner = self.inner.read().unwrap();
-            let actual_prev_value = &inner[index];
-            if !actual_prev_value.eq(prev_value, engines) {
-                return Some(actual_prev_value.clone());
-            }
+        // The comparison calls back into the engines, so it must not hold anything, and the entry
+        // is only pointed at the new value if it still holds the value that was compared.
+        let (current, actual_prev_value) = self.current(index.index());
+        if !actual_prev_value.eq(prev_value, engines) {
+            return Some(actual_prev_value.clone());
+        }
+        let new_value = self.push_value(new_value);
+        match self.entry(index.index()).value.compare_exchange(
+            current,
+            new_value,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => None,
+            // Another thread replaced the value in the meantime.
+            Err(replaced) => Some(self.value(replaced).expect(COLLECTED).clone()),
         }
-
-        let mut inner = self.inner.write().unwrap();
-        inner[index] = new_value;
-        None
     }
 }
 
@@ -106,8 +382,55 @@ where
     T: Named + Spanned,
 {
     pub fn replace(&self, index: DeclId<T>, new_value: T) -> Option<T> {
-        let mut inner = self.inner.write().unwrap();
-        inner[index.inner()] = new_value;
+        let entry = self.entry(index.inner());
+        // Entries are only collected while nothing else uses the slab.
+        assert!(entry.value.load(Ordering::Acquire) != 0, "{COLLECTED}");
+        entry
+            .value
+            .store(self.push_value(new_value), Ordering::Release);
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_double_in_size() {
+        assert_eq!(bucket_and_offset(0), (0, 0));
+        assert_eq!(bucket_and_offset(31), (0, 31));
+        assert_eq!(bucket_and_offset(32), (1, 0));
+        assert_eq!(bucket_and_offset(95), (1, 63));
+        assert_eq!(bucket_and_offset(96), (2, 0));
+        assert_eq!(
+            bucket_and_offset(usize::MAX - 32),
+            (BUCKETS - 1, usize::MAX >> 1)
+        );
+    }
+
+    #[test]
+    fn concurrent_inserts_get_distinct_indices() {
+        let slab = ConcurrentSlab::default();
+        let provenance = Provenance::current(0);
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let slab = &slab;
+                scope.spawn(move || {
+                    for i in 0..1000 {
+                        let value = thread * 1000 + i;
+                        assert_eq!(slab.get(slab.insert(value, provenance)), value);
+                    }
+                });
+            }
+        });
+        slab.with_values(|values| {
+            let mut values = values
+                .iter()
+                .map(|value| *value.unwrap())
+                .collect::<Vec<_>>();
+            values.sort_unstable();
+            assert_eq!(values, (0..4000).collect::<Vec<_>>());
+        });
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
         ty::{self, TyImplItem},
         CallPath, Visibility,
     },
-    transform::{self, AttributesMap},
+    transform::AttributesMap,
     type_system::TypeInfo,
     Engines, TypeArgument, TypeEngine, TypeId,
 };
@@ -144,20 +144,25 @@ impl<'cfg> ControlFlowGraph<'cfg> {
 
         let dead_function_contains_span = |span: &Span| -> bool {
             dead_nodes.iter().any(|x| {
-                if let ControlFlowGraphNode::ProgramNode {
-                    node:
-                        ty::TyAstNode {
-                            span: function_span,
-                            content:
-                                ty::TyAstNodeContent::Declaration(ty::TyDecl::FunctionDecl { .. }),
-                        },
-                    ..
-                } = &self.graph[*x]
-                {
-                    function_span.end() >= span.end() && function_span.start() <= span.start()
-                } else {
-                    false
-                }
+                let function_span = match &self.graph[*x] {
+                    ControlFlowGraphNode::ProgramNode {
+                        node:
+                            ty::TyAstNode {
+                                span: function_span,
+                                content:
+                                    ty::TyAstNodeContent::Declaration(ty::TyDecl::FunctionDecl {
+                                        ..
+                                    }),
+                            },
+                        ..
+                    } => function_span,
+                    ControlFlowGraphNode::MethodDeclaration {
+                        span: function_span,
+                        ..
+                    } => function_span,
+                    _ => return false,
+                };
+                function_span.end() >= span.end() && function_span.start() <= span.start()
             })
         };
 
@@ -203,9 +208,9 @@ impl<'cfg> ControlFlowGraph<'cfg> {
                             ..
                         } if !is_public => Some(priv_enum_var_warn(variant_name)),
                         ControlFlowGraphNode::EnumVariant { .. } => None,
-                        ControlFlowGraphNode::MethodDeclaration { span, .. } => {
+                        ControlFlowGraphNode::MethodDeclaration { method_name, .. } => {
                             Some(CompileWarning {
-                                span: span.clone(),
+                                span: method_name.span(),
                                 warning_content: Warning::DeadMethod,
                             })
                         }
@@ -225,7 +230,9 @@ impl<'cfg> ControlFlowGraph<'cfg> {
                         ControlFlowGraphNode::FunctionParameter { param_name, .. } => {
                             Some(CompileWarning {
                                 span: param_name.span(),
-                                warning_content: Warning::DeadDeclaration,
+                                warning_content: Warning::UnusedVariable {
+                                    variable_name: param_name.clone(),
+                                },
                             })
                         }
                     }
@@ -638,7 +645,8 @@ fn connect_impl_trait<'eng: 'cfg, 'cfg>(
     let decl_engine = engines.de();
     // If trait_decl_ref is None then the impl trait is an impl self.
     // Impl self does not have any trait to point to.
-    if trait_decl_ref.is_some() {
+    let is_impl_self = trait_decl_ref.is_none();
+    if !is_impl_self {
         let trait_decl_node = graph.namespace.find_trait(trait_name).cloned();
         match trait_decl_node {
             None => {
@@ -693,6 +701,11 @@ fn connect_impl_trait<'eng: 'cfg, 'cfg>(
                             // thus we can return true directly.
                             true
                         }
+                    } else if is_impl_self {
+                        // Only public methods of an impl self are reachable from outside of a
+                        // library or contract, private ones must be called from within it.
+                        matches!(tree_type, TreeType::Library { .. } | TreeType::Contract)
+                            && fn_decl.visibility.is_public()
                     } else {
                         matches!(tree_type, TreeType::Library { .. } | TreeType::Contract)
                     };
@@ -2039,7 +2052,9 @@ fn construct_dead_code_warning_from_node(
             if decl.name.span().source_id().is_some() {
                 CompileWarning {
                     span: decl.name.span(),
-                    warning_content: Warning::DeadDeclaration,
+                    warning_content: Warning::UnusedVariable {
+                        variable_name: decl.name.clone(),
+                    },
                 }
             } else if span.source_id().is_some() {
                 CompileWarning {
@@ -2196,20 +2211,7 @@ fn connect_call_path_decl<'eng: 'cfg, 'cfg>(
 /// Checks [AttributesMap] for `#[allow(dead_code)]` usage, if so returns true
 /// otherwise returns false.
 fn allow_dead_code(attributes: AttributesMap) -> bool {
-    fn allow_dead_code_helper(attributes: AttributesMap) -> Option<bool> {
-        Some(
-            attributes
-                .get(&transform::AttributeKind::Allow)?
-                .last()?
-                .args
-                .first()?
-                .name
-                .as_str()
-                == ALLOW_DEAD_CODE_NAME,
-        )
-    }
-
-    allow_dead_code_helper(attributes).unwrap_or_default()
+    attributes.allows(ALLOW_DEAD_CODE_NAME)
 }
 
 /// Returns true when the given `node` contains the attribute `#[allow(dead_code)]`
@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use sway_ir::{Context, Type, TypeContent};
+
+use crate::source_map::LocationRange;
+
+/// Debug info for a compiled program, describing its functions and their variables so that a
+/// debugger can show them by name.
+///
+/// Instruction offsets count instructions from the start of the bytecode, as in the
+/// [SourceMap](crate::source_map::SourceMap).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugInfo {
+    /// The functions of the program, in the order they appear in the bytecode.
+    pub functions: Vec<DebugFunction>,
+}
+
+impl DebugInfo {
+    /// The function whose instructions include the one at `pc`, if any.
+    pub fn function_at(&self, pc: u64) -> Option<&DebugFunction> {
+        self.functions
+            .iter()
+            .find(|function| function.start <= pc && pc < function.end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugFunction {
+    /// The name of the function.
+    pub name: String,
+    /// The signature of the function in the IR, e.g. `transfer(u64, b256) -> ()`.
+    pub signature: String,
+    /// The source file the function is declared in.
+    pub path: Option<PathBuf>,
+    /// The range of the declaration of the function in its source file.
+    pub range: Option<LocationRange>,
+    /// The offset of the first instruction of the function.
+    pub start: u64,
+    /// The offset of the instruction following the last one of the function.
+    pub end: u64,
+    /// The arguments of the function, where they are when it's entered.  Entry points receive
+    /// their arguments from the transaction rather than in registers, so they have none.
+    pub args: Vec<DebugVariable>,
+    /// The local variables of the function.
+    pub locals: Vec<DebugVariable>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: String,
+    pub ty: DebugType,
+    pub location: VariableLocation,
+}
+
+/// Where the value of a variable is at runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VariableLocation {
+    /// In memory, `offset` bytes past the address in the register `register`, which holds the
+    /// base of the locals of the function once its prologue has run.
+    Stack { register: u8, offset: u64 },
+    /// A constant, `offset` bytes from the start of the data section.
+    DataSection { offset: u64 },
+    /// In the register `register`.
+    Register { register: u8 },
+    /// In memory, at the address in the register `register`.  Values which don't fit in a
+    /// register are passed by reference.
+    RegisterPointer { register: u8 },
+}
+
+/// The layout of the value of a variable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DebugType {
+    Unit,
+    Bool,
+    Uint {
+        bits: u8,
+    },
+    B256,
+    String {
+        len: u64,
+    },
+    Slice,
+    Pointer {
+        pointee: Box<DebugType>,
+    },
+    Array {
+        elem: Box<DebugType>,
+        len: u64,
+    },
+    Struct {
+        fields: Vec<DebugField>,
+    },
+    /// An enum's value, which holds any one of the variants.
+    Union {
+        variants: Vec<DebugType>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugField {
+    /// The offset in bytes of the field from the start of the struct.
+    pub offset: u64,
+    pub ty: DebugType,
+}
+
+impl DebugType {
+    pub(crate) fn from_ir(context: &Context, ty: &Type) -> Self {
+        match ty.get_content(context) {
+            TypeContent::Unit => DebugType::Unit,
+            TypeContent::Bool => DebugType::Bool,
+            TypeContent::Uint(bits) => DebugType::Uint { bits: *bits },
+            TypeContent::B256 => DebugType::B256,
+            TypeContent::String(len) => DebugType::String { len: *len },
+            TypeContent::Slice => DebugType::Slice,
+            TypeContent::Pointer(pointee) => DebugType::Pointer {
+                pointee: Box::new(DebugType::from_ir(context, pointee)),
+            },
+            TypeContent::Array(elem, len) => DebugType::Array {
+                elem: Box::new(DebugType::from_ir(context, elem)),
+                len: *len,
+            },
+            TypeContent::Struct(field_tys) => {
+                let mut offset = 0;
+                let fields = field_tys
+                    .iter()
+                    .map(|field_ty| {
+                        let field = DebugField {
+                            offset,
+                            ty: DebugType::from_ir(context, field_ty),
+                        };
+                        offset += field_ty.size_in_bytes(context);
+                        field
+                    })
+                    .collect();
+                DebugType::Struct { fields }
+            }
+            TypeContent::Union(variant_tys) => DebugType::Union {
+                variants: variant_tys
+                    .iter()
+                    .map(|variant_ty| DebugType::from_ir(context, variant_ty))
+                    .collect(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sway_types::SourceEngine;
+
+    #[test]
+    fn struct_fields_are_laid_out_in_order() {
+        let source_engine = SourceEngine::default();
+        let mut context = Context::new(&source_engine);
+        let bool_ty = Type::get_bool(&context);
+        let b256_ty = Type::get_b256(&context);
+        let u64_ty = Type::get_uint64(&context);
+        let array_ty = Type::new_array(&mut context, u64_ty, 3);
+        let struct_ty = Type::new_struct(&mut context, vec![bool_ty, b256_ty, array_ty]);
+
+        let field_offsets = match DebugType::from_ir(&context, &struct_ty) {
+            DebugType::Struct { fields } => {
+                fields.iter().map(|field| field.offset).collect::<Vec<_>>()
+            }
+            ty => panic!("expected a struct, found {ty:?}"),
+        };
+        assert_eq!(field_offsets, [0, 8, 40]);
+    }
+}
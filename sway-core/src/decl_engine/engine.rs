@@ -1,18 +1,24 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
-    sync::RwLock,
+    hash::Hash,
+    iter,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        RwLock,
+    },
 };
 
-use sway_types::{Named, Spanned};
+use sway_types::{Named, SourceId, Span, Spanned};
 
 use crate::{
-    concurrent_slab::ConcurrentSlab,
+    concurrent_slab::{ConcurrentSlab, Provenance},
     decl_engine::*,
     engine_threading::*,
     language::ty::{
         self, TyAbiDecl, TyConstantDecl, TyEnumDecl, TyFunctionDecl, TyImplTrait, TyStorageDecl,
         TyStructDecl, TyTraitDecl, TyTraitFn, TyTypeAliasDecl,
     },
+    type_system::TypeId,
 };
 
 /// Used inside of type inference to store declarations.
@@ -30,6 +36,11 @@ pub struct DeclEngine {
     type_alias_slab: ConcurrentSlab<TyTypeAliasDecl>,
 
     parents: RwLock<HashMap<AssociatedItemDeclId, Vec<AssociatedItemDeclId>>>,
+    generation: AtomicU32,
+
+    function_instances: Instances<Span, DeclRefFunction>,
+    struct_instances: Instances<DeclId<TyStructDecl>, DeclRefStruct>,
+    enum_instances: Instances<DeclId<TyEnumDecl>, DeclRefEnum>,
 }
 
 pub trait DeclEngineGet<I, U> {
@@ -87,7 +98,7 @@ macro_rules! decl_engine_insert {
                 let span = decl.span();
                 DeclRef::new(
                     decl.name().clone(),
-                    DeclId::new(self.$slab.insert(decl)),
+                    DeclId::new(self.$slab.insert(decl, self.provenance())),
                     span,
                 )
             }
@@ -188,6 +199,98 @@ impl DeclEngine {
         acc_parents.values().cloned().collect()
     }
 
+    pub(crate) fn provenance(&self) -> Provenance {
+        Provenance::current(self.generation.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set_generation(&self, generation: u32) {
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+
+    /// The provenance of the struct declaration `id`.
+    pub(crate) fn struct_provenance(&self, id: &DeclId<TyStructDecl>) -> Provenance {
+        self.struct_slab.provenance(id.inner())
+    }
+
+    /// The provenance of the enum declaration `id`.
+    pub(crate) fn enum_provenance(&self, id: &DeclId<TyEnumDecl>) -> Provenance {
+        self.enum_slab.provenance(id.inner())
+    }
+
+    /// Drops the declarations whose provenance is `collectible`, returning how many were.
+    pub(crate) fn collect(&mut self, collectible: &dyn Fn(&Provenance) -> bool) -> usize {
+        let functions = self.function_slab.collect(collectible);
+        let trait_fns = self.trait_fn_slab.collect(collectible);
+        let constants = self.constant_slab.collect(collectible);
+        let others = [
+            self.trait_slab.collect(collectible),
+            self.impl_trait_slab.collect(collectible),
+            self.struct_slab.collect(collectible),
+            self.storage_slab.collect(collectible),
+            self.abi_slab.collect(collectible),
+            self.enum_slab.collect(collectible),
+            self.type_alias_slab.collect(collectible),
+        ];
+
+        let is_collected = |id: &AssociatedItemDeclId| match id {
+            AssociatedItemDeclId::TraitFn(id) => trait_fns.contains(&id.inner()),
+            AssociatedItemDeclId::Function(id) => functions.contains(&id.inner()),
+            AssociatedItemDeclId::Constant(id) => constants.contains(&id.inner()),
+        };
+        let mut parents = self.parents.write().unwrap();
+        parents.retain(|id, _| !is_collected(id));
+        for id_parents in parents.values_mut() {
+            id_parents.retain(|parent| !is_collected(parent));
+        }
+
+        self.function_instances.collect(collectible);
+        self.struct_instances.collect(collectible);
+        self.enum_instances.collect(collectible);
+
+        functions.len()
+            + trait_fns.len()
+            + constants.len()
+            + others.iter().map(HashSet::len).sum::<usize>()
+    }
+
+    /// The number of declarations which haven't been collected.
+    pub fn live_count(&self) -> usize {
+        [
+            self.function_slab.live_count(),
+            self.trait_slab.live_count(),
+            self.trait_fn_slab.live_count(),
+            self.impl_trait_slab.live_count(),
+            self.struct_slab.live_count(),
+            self.storage_slab.live_count(),
+            self.abi_slab.live_count(),
+            self.constant_slab.live_count(),
+            self.enum_slab.live_count(),
+            self.type_alias_slab.live_count(),
+        ]
+        .into_iter()
+        .sum()
+    }
+
+    /// The modules for which the declarations of the generations before `generation` were
+    /// created.
+    pub(crate) fn sources_before(&self, generation: u32) -> HashSet<SourceId> {
+        [
+            self.function_slab.sources_before(generation),
+            self.trait_slab.sources_before(generation),
+            self.trait_fn_slab.sources_before(generation),
+            self.impl_trait_slab.sources_before(generation),
+            self.struct_slab.sources_before(generation),
+            self.storage_slab.sources_before(generation),
+            self.abi_slab.sources_before(generation),
+            self.constant_slab.sources_before(generation),
+            self.enum_slab.sources_before(generation),
+            self.type_alias_slab.sources_before(generation),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     pub(crate) fn register_parent<I>(
         &self,
         index: AssociatedItemDeclId,
@@ -202,6 +305,118 @@ impl DeclEngine {
             .or_insert_with(|| vec![parent]);
     }
 
+    /// Inserts `decl`, an instance of the function declared at `decl.span`, unless an instance of
+    /// it with the same types was already inserted, in which case that one is returned.
+    ///
+    /// The types of an instance are those of its type parameters, parameters and return type,
+    /// along with `self_type`, the type a method is called on.
+    pub(crate) fn insert_function_instance(
+        &self,
+        engines: &Engines,
+        decl: TyFunctionDecl,
+        self_type: Option<TypeId>,
+        parent: AssociatedItemDeclId,
+    ) -> DeclRefFunction {
+        let insert = |decl: TyFunctionDecl| self.insert(decl).with_parent(self, parent);
+        let type_args = self_type
+            .into_iter()
+            .chain(decl.type_parameters.iter().map(|param| param.type_id))
+            .chain(
+                decl.parameters
+                    .iter()
+                    .map(|param| param.type_argument.type_id),
+            )
+            .chain(iter::once(decl.return_type.type_id))
+            .collect();
+        let generic = decl.span.clone();
+        self.insert_instance(
+            engines,
+            &self.function_instances,
+            generic,
+            type_args,
+            decl,
+            insert,
+        )
+    }
+
+    /// Inserts `decl`, the instance of the struct `generic` with the type arguments of its type
+    /// parameters, unless an instance of `generic` with the same type arguments was already
+    /// inserted, in which case that one is returned.
+    pub(crate) fn insert_struct_instance(
+        &self,
+        engines: &Engines,
+        generic: DeclId<TyStructDecl>,
+        decl: TyStructDecl,
+    ) -> DeclRefStruct {
+        let type_args = decl
+            .type_parameters
+            .iter()
+            .map(|param| param.type_id)
+            .collect();
+        self.insert_instance(
+            engines,
+            &self.struct_instances,
+            generic,
+            type_args,
+            decl,
+            |decl| self.insert(decl),
+        )
+    }
+
+    /// Inserts `decl`, the instance of the enum `generic` with the type arguments of its type
+    /// parameters, unless an instance of `generic` with the same type arguments was already
+    /// inserted, in which case that one is returned.
+    pub(crate) fn insert_enum_instance(
+        &self,
+        engines: &Engines,
+        generic: DeclId<TyEnumDecl>,
+        decl: TyEnumDecl,
+    ) -> DeclRefEnum {
+        let type_args = decl
+            .type_parameters
+            .iter()
+            .map(|param| param.type_id)
+            .collect();
+        self.insert_instance(
+            engines,
+            &self.enum_instances,
+            generic,
+            type_args,
+            decl,
+            |decl| self.insert(decl),
+        )
+    }
+
+    /// Only instances whose types are all known are shared, as the types still to be inferred
+    /// differ between the uses of a declaration.
+    fn insert_instance<K, T>(
+        &self,
+        engines: &Engines,
+        instances: &Instances<K, DeclRef<DeclId<T>>>,
+        generic: K,
+        type_args: Vec<TypeId>,
+        decl: T,
+        insert: impl FnOnce(T) -> DeclRef<DeclId<T>>,
+    ) -> DeclRef<DeclId<T>>
+    where
+        K: Hash + Eq + Clone,
+    {
+        let type_engine = engines.te();
+        if !type_args
+            .iter()
+            .all(|type_id| type_engine.is_concrete(engines, *type_id))
+        {
+            return insert(decl);
+        }
+        let provenance = self.provenance();
+        if let Some(instance) = instances.get(engines, provenance, generic.clone(), &type_args) {
+            return instance;
+        }
+        let instance = insert(decl);
+        instances.insert(provenance, generic, type_args, instance.clone());
+        instance
+    }
+
     /// Friendly helper method for calling the `get` method from the
     /// implementation of [DeclEngineGet] for [DeclEngine]
     ///
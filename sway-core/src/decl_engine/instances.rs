@@ -0,0 +1,73 @@
+use std::{collections::HashMap, hash::Hash, sync::RwLock};
+
+use crate::{concurrent_slab::Provenance, engine_threading::*, type_system::*};
+
+/// The instances of generic declarations, by the declaration they were monomorphized from and
+/// their type arguments, so that the uses of a declaration with the same type arguments share one
+/// instance of it rather than each having its own.
+///
+/// Instances are only shared within the module and the generation they were created in, so that
+/// collecting the declarations of a module never leaves the other modules referring to them.
+#[derive(Debug)]
+pub(crate) struct Instances<K, V> {
+    instances: RwLock<HashMap<(Provenance, K), Vec<(Vec<TypeId>, V)>>>,
+}
+
+impl<K, V> Default for Instances<K, V> {
+    fn default() -> Self {
+        Instances {
+            instances: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, V> Instances<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    /// The instance of `generic` with the type arguments `type_args`, if there is one.
+    pub(crate) fn get(
+        &self,
+        engines: &Engines,
+        provenance: Provenance,
+        generic: K,
+        type_args: &[TypeId],
+    ) -> Option<V> {
+        let type_engine = engines.te();
+        let instances = self.instances.read().unwrap();
+        instances
+            .get(&(provenance, generic))?
+            .iter()
+            .find(|(instance_type_args, _)| {
+                instance_type_args.len() == type_args.len()
+                    && instance_type_args.iter().zip(type_args).all(|(a, b)| {
+                        a == b || type_engine.get(*a).eq(&type_engine.get(*b), engines)
+                    })
+            })
+            .map(|(_, instance)| instance.clone())
+    }
+
+    pub(crate) fn insert(
+        &self,
+        provenance: Provenance,
+        generic: K,
+        type_args: Vec<TypeId>,
+        instance: V,
+    ) {
+        self.instances
+            .write()
+            .unwrap()
+            .entry((provenance, generic))
+            .or_default()
+            .push((type_args, instance));
+    }
+
+    /// Drops the instances whose provenance is `collectible`.
+    pub(crate) fn collect(&self, collectible: &dyn Fn(&Provenance) -> bool) {
+        self.instances
+            .write()
+            .unwrap()
+            .retain(|(provenance, _), _| !collectible(provenance));
+    }
+}
@@ -12,6 +12,7 @@ pub mod associated_item_decl_id;
 #[allow(clippy::module_inception)]
 pub(crate) mod engine;
 pub mod id;
+pub(crate) mod instances;
 pub(crate) mod interface_decl_id;
 pub(crate) mod mapping;
 pub(crate) mod r#ref;
@@ -23,6 +24,7 @@ use std::collections::BTreeMap;
 pub(crate) use associated_item_decl_id::*;
 pub use engine::*;
 pub(crate) use id::*;
+pub(crate) use instances::*;
 pub use interface_decl_id::*;
 pub(crate) use mapping::*;
 pub use r#ref::*;
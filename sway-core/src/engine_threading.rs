@@ -1,12 +1,19 @@
 use std::{
     cmp::Ordering,
+    collections::HashSet,
     fmt,
     hash::{BuildHasher, Hash, Hasher},
+    path::Path,
 };
 
-use sway_types::SourceEngine;
+use sway_types::{SourceEngine, SourceId};
 
-use crate::{decl_engine::DeclEngine, query_engine::QueryEngine, type_system::TypeEngine};
+use crate::{
+    concurrent_slab::{set_current_module, Provenance},
+    decl_engine::DeclEngine,
+    query_engine::QueryEngine,
+    type_system::TypeEngine,
+};
 
 #[derive(Debug, Default)]
 pub struct Engines {
@@ -47,6 +54,71 @@ impl Engines {
         &self.source_engine
     }
 
+    /// Starts a new generation of the entries of the type and declaration engines, to be called
+    /// before compiling again with the same engines, and returns it.
+    ///
+    /// The entries of the previous generations remain valid until they're collected by
+    /// [Engines::gc], once the modules they were created for have been compiled again.
+    pub fn next_generation(&self) -> u32 {
+        let generation = self.type_engine.provenance().generation + 1;
+        self.type_engine.set_generation(generation);
+        self.decl_engine.set_generation(generation);
+        generation
+    }
+
+    /// Collects the entries of the type and declaration engines from previous generations which
+    /// were created for the modules whose paths match `module_filter`, freeing their memory, and
+    /// returns how many were collected.
+    ///
+    /// The matching modules, and all the modules depending on them, must have been compiled again
+    /// in the current generation, as nothing may use the ids of the collected entries anymore.
+    /// The entries shared between modules, e.g. the interned types which don't refer to any
+    /// declaration, and those created outside of any module are only collected along with those
+    /// of every module, i.e. when `module_filter` matches all the modules with entries from
+    /// previous generations.
+    ///
+    /// Reading the engines doesn't take any lock, so collecting them needs them to itself.
+    pub fn gc(&mut self, module_filter: impl Fn(&Path) -> bool) -> usize {
+        let generation = self.type_engine.provenance().generation;
+        let mut sources = self.type_engine.sources_before(generation);
+        sources.extend(self.decl_engine.sources_before(generation));
+        let collected_sources = sources
+            .iter()
+            .filter(|source_id| module_filter(&self.source_engine.get_path(source_id)))
+            .copied()
+            .collect::<HashSet<_>>();
+        let collect_shared = collected_sources.len() == sources.len();
+        let collectible = |provenance: &Provenance| {
+            provenance.generation < generation
+                && match provenance.source_id {
+                    Some(source_id) => collected_sources.contains(&source_id),
+                    None => collect_shared,
+                }
+        };
+        self.type_engine.collect(&collectible) + self.decl_engine.collect(&collectible)
+    }
+
+    /// Collects the entries created in the current generation, once its compilation has failed
+    /// and nothing refers to them, and returns how many were collected.
+    ///
+    /// The entries of previous generations are kept, even those the failed compilation used.
+    pub fn discard_generation(&mut self) -> usize {
+        let generation = self.type_engine.provenance().generation;
+        let collectible = |provenance: &Provenance| provenance.created == generation;
+        self.type_engine.collect(&collectible) + self.decl_engine.collect(&collectible)
+    }
+
+    /// Attributes the entries created by this thread in the type and declaration engines to the
+    /// module `source_id` until the returned scope is dropped, so that they can be collected once
+    /// the module is compiled again.
+    ///
+    /// The module is tracked per thread, so that modules can be compiled on several threads.
+    pub(crate) fn enter_module(&self, source_id: Option<SourceId>) -> ModuleScope {
+        ModuleScope {
+            previous: set_current_module(source_id),
+        }
+    }
+
     /// Helps out some `thing: T` by adding `self` as context.
     pub fn help_out<T>(&self, thing: T) -> WithEngines<'_, T> {
         WithEngines {
@@ -56,6 +128,18 @@ impl Engines {
     }
 }
 
+/// The module the entries of the engines are created for by this thread, until it's dropped.
+pub(crate) struct ModuleScope {
+    /// The module they were created for before.
+    previous: Option<SourceId>,
+}
+
+impl Drop for ModuleScope {
+    fn drop(&mut self) {
+        set_current_module(self.previous);
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct WithEngines<'a, T> {
     pub thing: T,
@@ -234,3 +318,264 @@ where
         state.finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        path::PathBuf,
+        sync::{
+            atomic::{AtomicUsize, Ordering as AtomicOrdering},
+            Arc,
+        },
+    };
+
+    use sway_types::{integer_bits::IntegerBits, Ident, Span};
+
+    use super::*;
+    use crate::{
+        decl_engine::{DeclEngineInsert, DeclRefStruct},
+        language::{ty, CallPath, Visibility},
+        transform::AttributesMap,
+        TypeArgument, TypeInfo,
+    };
+
+    #[test]
+    fn gc_collects_stale_entries_of_compiled_modules() {
+        let mut engines = Engines::default();
+        let a = engines.se().get_source_id(&PathBuf::from("a.sw"));
+        let b = engines.se().get_source_id(&PathBuf::from("b.sw"));
+        let u64_type = TypeInfo::UnsignedInteger(IntegerBits::SixtyFour);
+
+        let (stale_a, shared) = {
+            let _module_scope = engines.enter_module(Some(a));
+            let stale_a = engines.te().insert(&engines, TypeInfo::Unknown);
+            let shared = engines.te().insert(&engines, u64_type.clone());
+            (stale_a, shared)
+        };
+        let stale_b = {
+            let _module_scope = engines.enter_module(Some(b));
+            engines.te().insert(&engines, TypeInfo::Unknown)
+        };
+
+        // Only `a.sw` is compiled again.
+        engines.next_generation();
+        let fresh_a = {
+            let _module_scope = engines.enter_module(Some(a));
+            assert_eq!(engines.te().insert(&engines, u64_type.clone()), shared);
+            engines.te().insert(&engines, TypeInfo::Unknown)
+        };
+        assert_ne!(fresh_a, stale_a);
+
+        assert_eq!(engines.gc(|path| path.ends_with("a.sw")), 1);
+        assert!(catch_unwind(AssertUnwindSafe(|| engines.te().get(stale_a))).is_err());
+        assert!(matches!(engines.te().get(stale_b), TypeInfo::Unknown));
+        assert!(matches!(engines.te().get(fresh_a), TypeInfo::Unknown));
+        assert!(matches!(
+            engines.te().get(shared),
+            TypeInfo::UnsignedInteger(IntegerBits::SixtyFour)
+        ));
+
+        // `b.sw` is the last module with stale entries.
+        assert_eq!(engines.gc(|_| true), 1);
+        assert!(catch_unwind(AssertUnwindSafe(|| engines.te().get(stale_b))).is_err());
+    }
+
+    /// Declares `struct S { x: u64 }` at `span`, as compiling a module declaring it does.
+    fn declare_struct(engines: &Engines, span: Span) -> DeclRefStruct {
+        let u64_type = engines
+            .te()
+            .insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour));
+        engines.de().insert(ty::TyStructDecl {
+            call_path: CallPath {
+                prefixes: vec![],
+                suffix: Ident::new_no_span("S".to_string()),
+                is_absolute: false,
+            },
+            fields: vec![ty::TyStructField {
+                name: Ident::new_no_span("x".to_string()),
+                span: Span::dummy(),
+                type_argument: TypeArgument::from(u64_type),
+                attributes: AttributesMap::default(),
+            }],
+            type_parameters: vec![],
+            visibility: Visibility::Public,
+            span,
+            attributes: AttributesMap::default(),
+        })
+    }
+
+    #[test]
+    fn gc_collects_interned_types_with_their_declarations() {
+        let mut engines = Engines::default();
+        let a = engines.se().get_source_id(&PathBuf::from("a.sw"));
+        let b = engines.se().get_source_id(&PathBuf::from("b.sw"));
+
+        // `a.sw` declares the struct, and `b.sw` uses it.
+        let (stale_decl, stale_type) = {
+            let _module_scope = engines.enter_module(Some(a));
+            let decl = declare_struct(&engines, Span::dummy());
+            let struct_type = engines
+                .te()
+                .insert(&engines, TypeInfo::Struct(decl.clone()));
+            (decl, struct_type)
+        };
+        {
+            let _module_scope = engines.enter_module(Some(b));
+            assert_eq!(
+                engines
+                    .te()
+                    .insert(&engines, TypeInfo::Struct(stale_decl.clone())),
+                stale_type
+            );
+        }
+
+        // Both modules are compiled again. The new declaration is equal to the previous one, but
+        // its type must not be the previous type, which refers to the previous declaration.
+        engines.next_generation();
+        let (fresh_decl, fresh_type) = {
+            let _module_scope = engines.enter_module(Some(a));
+            let decl = declare_struct(&engines, Span::dummy());
+            let struct_type = engines
+                .te()
+                .insert(&engines, TypeInfo::Struct(decl.clone()));
+            (decl, struct_type)
+        };
+        assert_ne!(fresh_type, stale_type);
+        {
+            let _module_scope = engines.enter_module(Some(b));
+            assert_eq!(
+                engines
+                    .te()
+                    .insert(&engines, TypeInfo::Struct(fresh_decl.clone())),
+                fresh_type
+            );
+        }
+
+        // The previous declaration is collected along with its type.
+        assert_eq!(engines.gc(|path| path.ends_with("a.sw")), 2);
+        assert!(catch_unwind(AssertUnwindSafe(|| engines.de().get_struct(&stale_decl))).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| engines.te().get(stale_type))).is_err());
+        assert_eq!(
+            engines
+                .te()
+                .insert(&engines, TypeInfo::Struct(fresh_decl.clone())),
+            fresh_type
+        );
+        assert_eq!(engines.de().get_struct(&fresh_decl).fields.len(), 1);
+    }
+
+    #[test]
+    fn failed_generations_are_discarded() {
+        let mut engines = Engines::default();
+        let a = engines.se().get_source_id(&PathBuf::from("a.sw"));
+        let u64_type = TypeInfo::UnsignedInteger(IntegerBits::SixtyFour);
+
+        let (kept, shared) = {
+            let _module_scope = engines.enter_module(Some(a));
+            let kept = engines.te().insert(&engines, TypeInfo::Unknown);
+            let shared = engines.te().insert(&engines, u64_type.clone());
+            (kept, shared)
+        };
+
+        // The compilation of the next generation uses the shared type, and fails.
+        engines.next_generation();
+        let discarded = {
+            let _module_scope = engines.enter_module(Some(a));
+            assert_eq!(engines.te().insert(&engines, u64_type.clone()), shared);
+            engines.te().insert(&engines, TypeInfo::Unknown)
+        };
+        assert_eq!(engines.discard_generation(), 1);
+        assert!(catch_unwind(AssertUnwindSafe(|| engines.te().get(discarded))).is_err());
+        assert!(matches!(engines.te().get(kept), TypeInfo::Unknown));
+        assert!(matches!(
+            engines.te().get(shared),
+            TypeInfo::UnsignedInteger(IntegerBits::SixtyFour)
+        ));
+    }
+
+    #[test]
+    fn types_are_read_and_unified_from_several_threads() {
+        let engines = Engines::default();
+        let unknowns = (0..500)
+            .map(|_| engines.te().insert(&engines, TypeInfo::Unknown))
+            .collect::<Vec<_>>();
+        let unified = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            // Each of these threads unifies every unknown type with another integer type, which
+            // only the first of them to get to it succeeds at.
+            for bits in [
+                IntegerBits::Eight,
+                IntegerBits::Sixteen,
+                IntegerBits::ThirtyTwo,
+                IntegerBits::SixtyFour,
+            ] {
+                let (engines, unknowns, unified) = (&engines, &unknowns, &unified);
+                scope.spawn(move || {
+                    let int_type = engines
+                        .te()
+                        .insert(engines, TypeInfo::UnsignedInteger(bits));
+                    for &unknown in unknowns {
+                        let (_, errors) = engines.te().unify(
+                            engines,
+                            unknown,
+                            int_type,
+                            &Span::dummy(),
+                            "",
+                            None,
+                        );
+                        if errors.is_empty() {
+                            unified.fetch_add(1, AtomicOrdering::Relaxed);
+                        }
+                    }
+                });
+            }
+            // While these threads keep reading them.
+            for _ in 0..4 {
+                let (engines, unknowns) = (&engines, &unknowns);
+                scope.spawn(move || {
+                    for &unknown in unknowns {
+                        assert!(matches!(
+                            engines.te().get(unknown),
+                            TypeInfo::Unknown | TypeInfo::UnsignedInteger(_)
+                        ));
+                    }
+                });
+            }
+        });
+        assert_eq!(unified.into_inner(), unknowns.len());
+        assert!(unknowns
+            .iter()
+            .all(|&unknown| matches!(engines.te().get(unknown), TypeInfo::UnsignedInteger(_))));
+    }
+
+    #[test]
+    fn gc_frees_the_source_texts_of_collected_entries() {
+        let mut engines = Engines::default();
+        let a = engines.se().get_source_id(&PathBuf::from("a.sw"));
+        let src: Arc<str> = Arc::from("struct S { x: u64 }");
+        let stale_text = Arc::downgrade(&src);
+        {
+            let _module_scope = engines.enter_module(Some(a));
+            let span = engines.se().set_source_text(a, src).with_range(0, 19);
+            declare_struct(&engines, span.unwrap());
+        }
+
+        // `a.sw` is edited and compiled again.
+        engines.next_generation();
+        let fresh_span = {
+            let _module_scope = engines.enter_module(Some(a));
+            let src = Arc::from("struct S { x: u64 }\n");
+            let span = engines.se().set_source_text(a, src).with_range(0, 19);
+            declare_struct(&engines, span.clone().unwrap());
+            span.unwrap()
+        };
+
+        // The previous text is only pointed into by the previous declaration.
+        assert!(stale_text.upgrade().is_some());
+        assert_eq!(engines.gc(|path| path.ends_with("a.sw")), 1);
+        assert!(stale_text.upgrade().is_none());
+        let current = engines.se().get_source_text(&a).unwrap();
+        assert!(Arc::ptr_eq(current.src(), fresh_span.src()));
+    }
+}
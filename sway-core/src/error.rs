@@ -1,7 +1,7 @@
 //! Tools related to handling/recovering from Sway compile errors and reporting them to the user.
 
 use crate::language::parsed::VariableDeclaration;
-use sway_error::error::CompileError;
+use sway_error::error::{CompileError, ErrorContext};
 use sway_error::handler::{ErrorEmitted, Handler};
 use sway_error::warning::CompileWarning;
 
@@ -181,6 +181,20 @@ impl<T> CompileResult<T> {
         }
     }
 
+    /// Adds the frame of context returned by `frame` to all of the errors, as the outermost frame
+    /// of context about where they surfaced.
+    pub fn with_context(mut self, frame: impl FnOnce() -> ErrorContext) -> Self {
+        if !self.errors.is_empty() {
+            let frame = frame();
+            self.errors = self
+                .errors
+                .into_iter()
+                .map(|error| error.with_context(frame.clone()))
+                .collect();
+        }
+        self
+    }
+
     pub fn unwrap(self, warnings: &mut Vec<CompileWarning>, errors: &mut Vec<CompileError>) -> T {
         let panic_msg = format!("Unwrapped an err {:?}", self.errors);
         self.unwrap_or_else(warnings, errors, || panic!("{}", panic_msg))
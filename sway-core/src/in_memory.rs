@@ -0,0 +1,279 @@
+//! Compilation of programs whose module sources are held in memory rather than on disk.
+//!
+//! This is the entry point for embedding the compiler, e.g. in web playgrounds or in test
+//! frameworks that generate Sway code on the fly.
+
+use crate::{
+    abi_generation::{
+        evm_abi,
+        fuel_abi::{self, AbiContext},
+    },
+    asm_generation::ProgramABI,
+    asm_to_bytecode, ast_to_asm, compile_to_ast,
+    error::*,
+    namespace,
+    source_map::SourceMap,
+    BuildConfig, BuildTarget, CompiledBytecode, Engines,
+};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use sway_error::error::CompileError;
+use sway_types::Span;
+use sway_utils::PerformanceData;
+
+/// A handle used to cancel an in-memory compilation, e.g. from another thread.
+///
+/// Cancellation is checked between compilation phases, so a cancelled compilation stops as soon
+/// as the phase it is in completes.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests that every compilation using this token, or a clone of it, stops.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Returned instead of the compilation result when the compilation was cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+/// The output of a successful in-memory compilation.
+pub struct CompiledSources {
+    pub bytecode: CompiledBytecode,
+    pub abi: ProgramABI,
+    /// Maps the bytecode back to locations within the given sources.
+    pub source_map: SourceMap,
+}
+
+/// Compiles the program whose modules are given by `sources`, a map of virtual file paths to
+/// module sources, without accessing the file system.
+///
+/// The `root_module` is the path of the program's root module within `sources`. Submodules
+/// declared with `mod` are looked up relative to it, following the same layout as on disk.
+/// Dependencies, such as `core` and `std`, must already be part of the `initial_namespace`.
+///
+/// Diagnostics are returned as the warnings and errors of the [CompileResult], with their spans
+/// pointing into the given sources. If `cancellation` is cancelled before the compilation
+/// completes, [Cancelled] is returned instead.
+///
+/// # Example
+/// ```ignore
+/// # use sway_core::{compile_sources_to_bytecode, namespace, BuildTarget, CancellationToken, Engines};
+/// # fn main() {
+///     let sources = [("src/main.sw".into(), "script; fn main() -> bool { true }".into())];
+///     let result = compile_sources_to_bytecode(
+///         &Engines::default(),
+///         sources.into_iter().collect(),
+///         "src/main.sw".into(),
+///         namespace::Module::default(),
+///         BuildTarget::Fuel,
+///         "playground",
+///         &CancellationToken::new(),
+///     );
+/// # }
+/// ```
+pub fn compile_sources_to_bytecode(
+    engines: &Engines,
+    sources: HashMap<PathBuf, Arc<str>>,
+    root_module: PathBuf,
+    initial_namespace: namespace::Module,
+    build_target: BuildTarget,
+    package_name: &str,
+    cancellation: &CancellationToken,
+) -> Result<CompileResult<CompiledSources>, Cancelled> {
+    let check_cancelled = || {
+        if cancellation.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    };
+    check_cancelled()?;
+
+    let root_source = match sources.get(&root_module) {
+        Some(source) => source.clone(),
+        None => {
+            return Ok(err(
+                vec![],
+                vec![CompileError::FileCouldNotBeRead {
+                    span: Span::dummy(),
+                    file_path: root_module.to_string_lossy().to_string(),
+                    stringified_error: "no in-memory source was provided for this path".to_string(),
+                }],
+            ))
+        }
+    };
+    let build_config = BuildConfig::root_from_in_memory_sources(root_module, sources, build_target);
+    let mut metrics = PerformanceData::default();
+
+    let ast_res = compile_to_ast(
+        engines,
+        root_source,
+        initial_namespace,
+        Some(&build_config),
+        package_name,
+        &mut metrics,
+    );
+    check_cancelled()?;
+    let typed_program = match ast_res
+        .value
+        .as_ref()
+        .and_then(|programs| programs.typed.as_ref())
+    {
+        Some(typed_program) if ast_res.errors.is_empty() => typed_program,
+        _ => return Ok(err(ast_res.warnings, ast_res.errors)),
+    };
+
+    let asm_res = ast_to_asm(engines, &ast_res, &build_config, &mut metrics);
+    check_cancelled()?;
+
+    let abi = match build_target {
+        BuildTarget::Fuel => ProgramABI::Fuel(fuel_abi::generate_program_abi(
+            &mut AbiContext {
+                program: typed_program,
+                abi_with_callpaths: false,
+            },
+            engines.te(),
+            engines.de(),
+            &mut vec![],
+        )),
+        BuildTarget::EVM => {
+            // Include the internal constructors generated by the ASM backend, as `forc` does.
+            let mut ops = match &asm_res.value {
+                Some(asm) => match &asm.0.abi {
+                    Some(ProgramABI::Evm(ops)) => ops.clone(),
+                    _ => vec![],
+                },
+                None => vec![],
+            };
+            ops.extend(evm_abi::generate_abi_program(typed_program, engines));
+            ProgramABI::Evm(ops)
+        }
+        BuildTarget::MidenVM => ProgramABI::MidenVM(()),
+    };
+
+    let mut source_map = SourceMap::new();
+    let bc_res = asm_to_bytecode(asm_res, &mut source_map, engines.se());
+    check_cancelled()?;
+    Ok(bc_res.map(|bytecode| CompiledSources {
+        bytecode,
+        abi,
+        source_map,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(
+        sources: &[(&str, &str)],
+        cancellation: &CancellationToken,
+    ) -> Result<CompileResult<CompiledSources>, Cancelled> {
+        compile_sources_to_bytecode(
+            &Engines::default(),
+            sources
+                .iter()
+                .map(|(path, source)| (PathBuf::from(path), Arc::from(*source)))
+                .collect(),
+            PathBuf::from("src/main.sw"),
+            namespace::Module::default(),
+            BuildTarget::Fuel,
+            "test",
+            cancellation,
+        )
+    }
+
+    #[test]
+    fn compiles_submodules_from_memory() {
+        let res = compile(
+            &[
+                (
+                    "src/main.sw",
+                    "script; mod utils; fn main() -> u64 { utils::one() }",
+                ),
+                ("src/utils.sw", "library; pub fn one() -> u64 { 1 }"),
+            ],
+            &CancellationToken::new(),
+        )
+        .unwrap();
+        assert!(res.errors.is_empty(), "{:#?}", res.errors);
+        let compiled = res.value.unwrap();
+        assert!(!compiled.bytecode.bytecode.is_empty());
+        assert!(matches!(compiled.abi, ProgramABI::Fuel(_)));
+    }
+
+    #[test]
+    fn reports_missing_submodule() {
+        let res = compile(
+            &[("src/main.sw", "script; mod utils; fn main() {}")],
+            &CancellationToken::new(),
+        )
+        .unwrap();
+        assert!(res.value.is_none());
+        assert!(matches!(
+            res.errors.as_slice(),
+            [CompileError::FileCouldNotBeRead { file_path, .. }] if file_path == "src/utils.sw"
+        ));
+    }
+
+    #[test]
+    fn leaves_out_branches_not_taken() {
+        // The `else` branch would warn about `unused`, log a `u32` and fail to find `evm_only`.
+        let res = compile(
+            &[(
+                "src/main.sw",
+                r#"
+                script;
+                const ON_FUEL: bool = true;
+                fn main() -> u64 {
+                    if ON_FUEL {
+                        __log(true);
+                        42
+                    } else {
+                        let unused = 0u8;
+                        __log(7u32);
+                        evm_only()
+                    }
+                }
+                "#,
+            )],
+            &CancellationToken::new(),
+        )
+        .unwrap();
+        assert!(res.errors.is_empty(), "{:#?}", res.errors);
+        assert!(res.warnings.is_empty(), "{:#?}", res.warnings);
+        let compiled = res.value.unwrap();
+        let ProgramABI::Fuel(abi) = compiled.abi else {
+            panic!("expected a Fuel ABI");
+        };
+        assert_eq!(
+            abi.logged_types.map(|logged_types| logged_types.len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let res = compile(&[("src/main.sw", "script; fn main() {}")], &cancellation);
+        assert_eq!(res.err(), Some(Cancelled));
+    }
+}
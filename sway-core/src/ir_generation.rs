@@ -13,11 +13,18 @@ use sway_types::span::Span;
 
 pub(crate) use purity::{check_function_purity, PurityEnv};
 
-use crate::{language::ty, Engines};
+use crate::{build_config::OverflowBehavior, language::ty, Engines};
 
+/// Compiles `program` to IR, along with its tests if `include_tests` is set.
+///
+/// The conditions of the specification attributes, `#[requires(..)]` and `#[ensures(..)]`, are
+/// only checked at runtime if `check_specifications` is set, and `overflow` decides what the
+/// arithmetic operators do when they overflow.
 pub fn compile_program<'eng>(
     program: &ty::TyProgram,
     include_tests: bool,
+    check_specifications: bool,
+    overflow: OverflowBehavior,
     engines: &'eng Engines,
 ) -> Result<Context<'eng>, CompileError> {
     let declaration_engine = engines.de();
@@ -66,6 +73,8 @@ pub fn compile_program<'eng>(
             &logged_types,
             &messages_types,
             &test_fns,
+            check_specifications,
+            overflow,
         ),
         ty::TyProgramKind::Predicate { main_function } => compile::compile_predicate(
             engines,
@@ -76,6 +85,8 @@ pub fn compile_program<'eng>(
             &logged_types,
             &messages_types,
             &test_fns,
+            check_specifications,
+            overflow,
         ),
         ty::TyProgramKind::Contract { abi_entries } => compile::compile_contract(
             &mut ctx,
@@ -86,6 +97,8 @@ pub fn compile_program<'eng>(
             &messages_types,
             &test_fns,
             engines,
+            check_specifications,
+            overflow,
         ),
         ty::TyProgramKind::Library { .. } => compile::compile_library(
             engines,
@@ -95,6 +108,8 @@ pub fn compile_program<'eng>(
             &logged_types,
             &messages_types,
             &test_fns,
+            check_specifications,
+            overflow,
         ),
     }?;
 
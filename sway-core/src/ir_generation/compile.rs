@@ -1,5 +1,6 @@
 use crate::{
-    decl_engine::DeclRefFunction,
+    build_config::OverflowBehavior,
+    decl_engine::{DeclId, DeclRefFunction},
     language::{ty, Visibility},
     metadata::MetadataManager,
     semantic_analysis::namespace,
@@ -14,7 +15,7 @@ use super::{
     function::FnCompiler,
 };
 
-use sway_error::error::CompileError;
+use sway_error::error::{CompileError, ErrorContext};
 use sway_ir::{metadata::combine as md_combine, *};
 use sway_types::Spanned;
 
@@ -30,6 +31,8 @@ pub(super) fn compile_script(
     logged_types_map: &HashMap<TypeId, LogId>,
     messages_types_map: &HashMap<TypeId, MessageId>,
     test_fns: &[(ty::TyFunctionDecl, DeclRefFunction)],
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Module, CompileError> {
     let module = Module::new(context, Kind::Script);
     let mut md_mgr = MetadataManager::default();
@@ -52,6 +55,8 @@ pub(super) fn compile_script(
         logged_types_map,
         messages_types_map,
         None,
+        check_specifications,
+        overflow,
     )?;
     compile_tests(
         engines,
@@ -61,6 +66,8 @@ pub(super) fn compile_script(
         logged_types_map,
         messages_types_map,
         test_fns,
+        check_specifications,
+        overflow,
     )?;
 
     Ok(module)
@@ -76,6 +83,8 @@ pub(super) fn compile_predicate(
     logged_types: &HashMap<TypeId, LogId>,
     messages_types: &HashMap<TypeId, MessageId>,
     test_fns: &[(ty::TyFunctionDecl, DeclRefFunction)],
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Module, CompileError> {
     let module = Module::new(context, Kind::Predicate);
     let mut md_mgr = MetadataManager::default();
@@ -98,6 +107,8 @@ pub(super) fn compile_predicate(
         &HashMap::new(),
         &HashMap::new(),
         None,
+        check_specifications,
+        overflow,
     )?;
     compile_tests(
         engines,
@@ -107,6 +118,8 @@ pub(super) fn compile_predicate(
         logged_types,
         messages_types,
         test_fns,
+        check_specifications,
+        overflow,
     )?;
 
     Ok(module)
@@ -122,6 +135,8 @@ pub(super) fn compile_contract(
     messages_types_map: &HashMap<TypeId, MessageId>,
     test_fns: &[(ty::TyFunctionDecl, DeclRefFunction)],
     engines: &Engines,
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Module, CompileError> {
     let module = Module::new(context, Kind::Contract);
     let mut md_mgr = MetadataManager::default();
@@ -144,6 +159,8 @@ pub(super) fn compile_contract(
             logged_types_map,
             messages_types_map,
             engines,
+            check_specifications,
+            overflow,
         )?;
     }
     compile_tests(
@@ -154,6 +171,8 @@ pub(super) fn compile_contract(
         logged_types_map,
         messages_types_map,
         test_fns,
+        check_specifications,
+        overflow,
     )?;
 
     Ok(module)
@@ -168,6 +187,8 @@ pub(super) fn compile_library(
     logged_types_map: &HashMap<TypeId, LogId>,
     messages_types_map: &HashMap<TypeId, MessageId>,
     test_fns: &[(ty::TyFunctionDecl, DeclRefFunction)],
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Module, CompileError> {
     let module = Module::new(context, Kind::Library);
     let mut md_mgr = MetadataManager::default();
@@ -189,6 +210,8 @@ pub(super) fn compile_library(
         logged_types_map,
         messages_types_map,
         test_fns,
+        check_specifications,
+        overflow,
     )?;
 
     Ok(module)
@@ -311,6 +334,9 @@ pub(super) fn compile_function(
     messages_types_map: &HashMap<TypeId, MessageId>,
     is_entry: bool,
     test_decl_ref: Option<DeclRefFunction>,
+    compilation_stack: &[(DeclId<ty::TyFunctionDecl>, Function)],
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Option<Function>, CompileError> {
     // Currently monomorphization of generics is inlined into main() and the functions with generic
     // args are still present in the AST declarations, but they can be ignored.
@@ -328,8 +354,12 @@ pub(super) fn compile_function(
             logged_types_map,
             messages_types_map,
             test_decl_ref,
+            compilation_stack,
+            check_specifications,
+            overflow,
         )
         .map(Some)
+        .map_err(|err| err.with_context(ir_generation_context(ast_fn_decl)))
     }
 }
 
@@ -343,6 +373,8 @@ pub(super) fn compile_entry_function(
     logged_types_map: &HashMap<TypeId, LogId>,
     messages_types_map: &HashMap<TypeId, MessageId>,
     test_decl_ref: Option<DeclRefFunction>,
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Function, CompileError> {
     let is_entry = true;
     compile_function(
@@ -355,6 +387,9 @@ pub(super) fn compile_entry_function(
         messages_types_map,
         is_entry,
         test_decl_ref,
+        &[],
+        check_specifications,
+        overflow,
     )
     .map(|f| f.expect("entry point should never contain generics"))
 }
@@ -368,6 +403,8 @@ pub(super) fn compile_tests(
     logged_types_map: &HashMap<TypeId, LogId>,
     messages_types_map: &HashMap<TypeId, MessageId>,
     test_fns: &[(ty::TyFunctionDecl, DeclRefFunction)],
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Vec<Function>, CompileError> {
     test_fns
         .iter()
@@ -381,6 +418,8 @@ pub(super) fn compile_tests(
                 logged_types_map,
                 messages_types_map,
                 Some(decl_ref.clone()),
+                check_specifications,
+                overflow,
             )
         })
         .collect()
@@ -398,6 +437,9 @@ fn compile_fn(
     logged_types_map: &HashMap<TypeId, LogId>,
     messages_types_map: &HashMap<TypeId, MessageId>,
     test_decl_ref: Option<DeclRefFunction>,
+    compilation_stack: &[(DeclId<ty::TyFunctionDecl>, Function)],
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Function, CompileError> {
     let type_engine = engines.te();
     let decl_engine = engines.de();
@@ -475,6 +517,13 @@ fn compile_fn(
         metadata,
     );
 
+    // Entry functions can't be called, so only other functions can be the target of recursive
+    // calls made while compiling the body.
+    let mut compilation_stack = compilation_stack.to_vec();
+    if let Some(recursive_ref) = ast_fn_decl.recursive_ref.filter(|_| !is_entry) {
+        compilation_stack.push((recursive_ref, func));
+    }
+
     let mut compiler = FnCompiler::new(
         engines,
         context,
@@ -482,7 +531,15 @@ fn compile_fn(
         func,
         logged_types_map,
         messages_types_map,
+        compilation_stack,
+        check_specifications,
+        overflow,
     );
+    compiler.compile_param_validation(context, md_mgr, &ast_fn_decl.parameters)?;
+    if check_specifications {
+        compiler.compile_requires(context, md_mgr, &ast_fn_decl.requires)?;
+        compiler.ensures = ast_fn_decl.ensures.clone();
+    }
     let mut ret_val = compiler.compile_code_block(context, md_mgr, body)?;
 
     // Special case: sometimes the returned value at the end of the function block is hacked
@@ -515,6 +572,7 @@ fn compile_fn(
         if ret_type.is_unit(context) {
             ret_val = Constant::get_unit(context);
         }
+        compiler.compile_ensures(context, md_mgr, ret_val)?;
         compiler.current_block.ins(context).ret(ret_val, ret_type);
     }
     Ok(func)
@@ -529,6 +587,8 @@ fn compile_abi_method(
     logged_types_map: &HashMap<TypeId, LogId>,
     messages_types_map: &HashMap<TypeId, MessageId>,
     engines: &Engines,
+    check_specifications: bool,
+    overflow: OverflowBehavior,
 ) -> Result<Function, CompileError> {
     // Use the error from .to_fn_selector_value() if possible, else make an CompileError::Internal.
     let get_selector_result = ast_fn_decl.to_fn_selector_value(engines);
@@ -565,5 +625,16 @@ fn compile_abi_method(
         logged_types_map,
         messages_types_map,
         None,
+        &[],
+        check_specifications,
+        overflow,
+    )
+    .map_err(|err| err.with_context(ir_generation_context(ast_fn_decl)))
+}
+
+fn ir_generation_context(ast_fn_decl: &ty::TyFunctionDecl) -> ErrorContext {
+    ErrorContext::new(
+        "generating IR for",
+        format!("function `{}`", ast_fn_decl.name),
     )
 }
@@ -268,6 +268,15 @@ fn const_eval_typed_expr(
             call_path,
             ..
         } => {
+            let function_decl = lookup.engines.de().get_function(fn_ref);
+            // The body of a recursive call isn't available here, and evaluating it could recurse
+            // without bound anyway.
+            if function_decl.is_recursive_ref {
+                return Err(ConstEvalError::CannotBeEvaluatedToConst {
+                    span: call_path.span(),
+                });
+            }
+
             let mut actuals_const: Vec<_> = vec![];
 
             for arg in arguments {
@@ -290,7 +299,6 @@ fn const_eval_typed_expr(
                 known_consts.push(name.clone(), cval);
             }
 
-            let function_decl = lookup.engines.de().get_function(fn_ref);
             let res = const_eval_codeblock(lookup, known_consts, &function_decl.body);
 
             for (name, _) in arguments {
@@ -730,6 +738,12 @@ fn const_eval_intrinsic(
         sway_ast::Intrinsic::Add
         | sway_ast::Intrinsic::Sub
         | sway_ast::Intrinsic::Mul
+        | sway_ast::Intrinsic::WrappingAdd
+        | sway_ast::Intrinsic::WrappingSub
+        | sway_ast::Intrinsic::WrappingMul
+        | sway_ast::Intrinsic::CheckedAdd
+        | sway_ast::Intrinsic::CheckedSub
+        | sway_ast::Intrinsic::CheckedMul
         | sway_ast::Intrinsic::Div
         | sway_ast::Intrinsic::And
         | sway_ast::Intrinsic::Or
@@ -745,11 +759,19 @@ fn const_eval_intrinsic(
                 panic!("Type checker allowed incorrect args to binary op");
             };
 
-            // All arithmetic is done as if it were u64
+            // All arithmetic is done as if it were u64, except that the wrapping and checked
+            // operations wrap around and overflow the range of the type.
+            let max = u64::MAX >> (64 - ty.get_uint_width(lookup.context).unwrap_or(64));
             let result = match intrinsic.kind {
                 Intrinsic::Add => arg1.checked_add(*arg2),
                 Intrinsic::Sub => arg1.checked_sub(*arg2),
                 Intrinsic::Mul => arg1.checked_mul(*arg2),
+                Intrinsic::WrappingAdd => Some(arg1.wrapping_add(*arg2) & max),
+                Intrinsic::WrappingSub => Some(arg1.wrapping_sub(*arg2) & max),
+                Intrinsic::WrappingMul => Some(arg1.wrapping_mul(*arg2) & max),
+                Intrinsic::CheckedAdd => arg1.checked_add(*arg2).filter(|res| *res <= max),
+                Intrinsic::CheckedSub => arg1.checked_sub(*arg2),
+                Intrinsic::CheckedMul => arg1.checked_mul(*arg2).filter(|res| *res <= max),
                 Intrinsic::Div => arg1.checked_div(*arg2),
                 Intrinsic::And => Some(arg1.bitand(arg2)),
                 Intrinsic::Or => Some(arg1.bitor(*arg2)),
@@ -890,7 +912,12 @@ fn const_eval_intrinsic(
         | sway_ast::Intrinsic::StateStoreQuad
         | sway_ast::Intrinsic::Log
         | sway_ast::Intrinsic::Revert
-        | sway_ast::Intrinsic::Smo => Err(ConstEvalError::CannotBeEvaluatedToConst {
+        | sway_ast::Intrinsic::Smo
+        | sway_ast::Intrinsic::Gas
+        | sway_ast::Intrinsic::Realloc
+        | sway_ast::Intrinsic::HeapStats
+        | sway_ast::Intrinsic::Memcpy
+        | sway_ast::Intrinsic::Memcmp => Err(ConstEvalError::CannotBeEvaluatedToConst {
             span: intrinsic.span.clone(),
         }),
         sway_ast::Intrinsic::Not => {
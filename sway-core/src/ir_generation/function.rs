@@ -7,7 +7,10 @@ use super::{
 };
 use crate::{
     asm_generation::from_ir::{ir_type_size_in_bytes, ir_type_str_size_in_bytes},
+    build_config::OverflowBehavior,
+    decl_engine::DeclId,
     engine_threading::*,
+    fuel_prelude::fuel_vm::consts::VM_MAX_RAM,
     ir_generation::const_eval::{
         compile_constant_expression, compile_constant_expression_to_constant,
     },
@@ -66,10 +69,21 @@ pub(crate) struct FnCompiler<'eng> {
     current_fn_param: Option<ty::TyFunctionParameter>,
     lexical_map: LexicalMap,
     recreated_fns: HashMap<(Span, Vec<TypeId>, Vec<TypeId>), Function>,
+    // The functions being compiled, from the outermost caller down to this function, by the ID of
+    // the declaration through which they call themselves.  Recursive calls are made to these
+    // rather than compiling the callee again.
+    compilation_stack: Vec<(DeclId<ty::TyFunctionDecl>, Function)>,
     // This is a map from the type IDs of a logged type and the ID of the corresponding log
     logged_types_map: HashMap<TypeId, LogId>,
     // This is a map from the type IDs of a message data type and the ID of the corresponding smo
     messages_types_map: HashMap<TypeId, MessageId>,
+    // Whether the conditions of the specification attributes are checked at runtime.
+    check_specifications: bool,
+    // What the arithmetic operators do when they overflow.
+    overflow: OverflowBehavior,
+    // The conditions of the `#[ensures(..)]` attributes checked before each return, if they're
+    // checked at runtime.
+    pub(super) ensures: Vec<ty::TyExpression>,
 }
 
 impl<'eng> FnCompiler<'eng> {
@@ -81,6 +95,9 @@ impl<'eng> FnCompiler<'eng> {
         function: Function,
         logged_types_map: &HashMap<TypeId, LogId>,
         messages_types_map: &HashMap<TypeId, MessageId>,
+        compilation_stack: Vec<(DeclId<ty::TyFunctionDecl>, Function)>,
+        check_specifications: bool,
+        overflow: OverflowBehavior,
     ) -> Self {
         let lexical_map = LexicalMap::from_iter(
             function
@@ -96,9 +113,13 @@ impl<'eng> FnCompiler<'eng> {
             block_to_continue_to: None,
             lexical_map,
             recreated_fns: HashMap::new(),
+            compilation_stack,
             current_fn_param: None,
             logged_types_map: logged_types_map.clone(),
             messages_types_map: messages_types_map.clone(),
+            check_specifications,
+            overflow,
+            ensures: Vec::new(),
         }
     }
 
@@ -285,7 +306,30 @@ impl<'eng> FnCompiler<'eng> {
                     )
                 } else {
                     let function_decl = self.engines.de().get_function(fn_ref);
-                    self.compile_fn_call(context, md_mgr, arguments, &function_decl, span_md_idx)
+                    // Calls of memoized functions are replaced by their result when it can be
+                    // computed at compile time, which needs all of the arguments to be constant.
+                    if function_decl.is_memoized() {
+                        if let Ok(result) = compile_constant_expression_to_constant(
+                            self.engines,
+                            context,
+                            md_mgr,
+                            self.module,
+                            None,
+                            None,
+                            ast_expr,
+                        ) {
+                            return Ok(Value::new_constant(context, result)
+                                .add_metadatum(context, span_md_idx));
+                        }
+                    }
+                    self.compile_fn_call(
+                        context,
+                        md_mgr,
+                        arguments,
+                        fn_ref.id(),
+                        &function_decl,
+                        span_md_idx,
+                    )
                 }
             }
             ty::TyExpressionVariant::LazyOperator { op, lhs, rhs } => {
@@ -552,6 +596,40 @@ impl<'eng> FnCompiler<'eng> {
                 let val = matches!(engines.te().get_unaliased(targ.type_id), TypeInfo::Str(_));
                 Ok(Constant::get_bool(context, val))
             }
+            Intrinsic::Eq
+                if !engines
+                    .te()
+                    .get_unaliased(arguments[0].return_type)
+                    .is_copy_type() =>
+            {
+                // Strings, arrays and structs are compared by their memory, which for strings
+                // excludes the padding after them.
+                let lhs = &arguments[0];
+                let ir_type = convert_resolved_typeid(
+                    engines.te(),
+                    engines.de(),
+                    context,
+                    &lhs.return_type,
+                    &lhs.span,
+                )?;
+                let len = if ir_type.is_string(context) {
+                    ir_type_str_size_in_bytes(context, &ir_type)
+                } else {
+                    ir_type_size_in_bytes(context, &ir_type)
+                };
+                let lhs_ptr = self.compile_expression_to_ptr(context, md_mgr, lhs)?;
+                let rhs_ptr = self.compile_expression_to_ptr(context, md_mgr, &arguments[1])?;
+                let len = Constant::get_uint(context, 64, len);
+                let span_md_idx = md_mgr.span_to_md(context, &span);
+                Ok(self.compile_mem_op(
+                    context,
+                    Intrinsic::Memcmp,
+                    lhs_ptr,
+                    rhs_ptr,
+                    len,
+                    span_md_idx,
+                ))
+            }
             Intrinsic::Eq | Intrinsic::Gt | Intrinsic::Lt => {
                 let lhs = &arguments[0];
                 let rhs = &arguments[1];
@@ -797,10 +875,34 @@ impl<'eng> FnCompiler<'eng> {
                 let rhs = &arguments[1];
                 let lhs_value = self.compile_expression_to_value(context, md_mgr, lhs)?;
                 let rhs_value = self.compile_expression_to_value(context, md_mgr, rhs)?;
-                Ok(self
-                    .current_block
-                    .ins(context)
-                    .binary_op(op, lhs_value, rhs_value))
+                let overflow = match op {
+                    BinaryOpKind::Add | BinaryOpKind::Sub | BinaryOpKind::Mul => self.overflow,
+                    _ => OverflowBehavior::Panic,
+                };
+                self.compile_arithmetic_op(
+                    context, md_mgr, op, lhs_value, rhs_value, overflow, &span,
+                )
+            }
+            Intrinsic::WrappingAdd
+            | Intrinsic::WrappingSub
+            | Intrinsic::WrappingMul
+            | Intrinsic::CheckedAdd
+            | Intrinsic::CheckedSub
+            | Intrinsic::CheckedMul => {
+                let (op, overflow) = match kind {
+                    Intrinsic::WrappingAdd => (BinaryOpKind::Add, OverflowBehavior::Wrap),
+                    Intrinsic::WrappingSub => (BinaryOpKind::Sub, OverflowBehavior::Wrap),
+                    Intrinsic::WrappingMul => (BinaryOpKind::Mul, OverflowBehavior::Wrap),
+                    Intrinsic::CheckedAdd => (BinaryOpKind::Add, OverflowBehavior::Checked),
+                    Intrinsic::CheckedSub => (BinaryOpKind::Sub, OverflowBehavior::Checked),
+                    Intrinsic::CheckedMul => (BinaryOpKind::Mul, OverflowBehavior::Checked),
+                    _ => unreachable!(),
+                };
+                let lhs_value = self.compile_expression_to_value(context, md_mgr, &arguments[0])?;
+                let rhs_value = self.compile_expression_to_value(context, md_mgr, &arguments[1])?;
+                self.compile_arithmetic_op(
+                    context, md_mgr, op, lhs_value, rhs_value, overflow, &span,
+                )
             }
             Intrinsic::Revert => {
                 let revert_code_val =
@@ -951,9 +1053,147 @@ impl<'eng> FnCompiler<'eng> {
                     .ins(context)
                     .unary_op(UnaryOpKind::Not, value))
             }
+            Intrinsic::Gas => {
+                let span_md_idx = md_mgr.span_to_md(context, &span);
+                Ok(self
+                    .current_block
+                    .ins(context)
+                    .read_register(sway_ir::Register::Cgas)
+                    .add_metadatum(context, span_md_idx))
+            }
+            Intrinsic::Realloc => {
+                let span_md_idx = md_mgr.span_to_md(context, &span);
+                let ptr = self.compile_expression_to_value(context, md_mgr, &arguments[0])?;
+                let size = self.compile_expression_to_value(context, md_mgr, &arguments[1])?;
+                let new_size = self.compile_expression_to_value(context, md_mgr, &arguments[2])?;
+
+                // The allocation is only ever grown, otherwise `ptr` is returned as is.
+                let grows = self
+                    .current_block
+                    .ins(context)
+                    .cmp(Predicate::GreaterThan, new_size, size)
+                    .add_metadatum(context, span_md_idx);
+                let grow_block = self.function.create_block(context, None);
+                let final_block = self.function.create_block(context, None);
+                let ptr_ty = Type::get_uint64(context);
+                let result_arg_idx = final_block.new_arg(context, ptr_ty);
+                self.current_block
+                    .ins(context)
+                    .conditional_branch(grows, grow_block, final_block, vec![], vec![ptr])
+                    .add_metadatum(context, span_md_idx);
+
+                // Allocate `new_size` bytes and copy the `size` bytes at `ptr` into them.
+                let ident = |name: &str| Ident::new_no_span(name.to_string());
+                let args = [
+                    ("ptr", Some(ptr)),
+                    ("size", Some(size)),
+                    ("new_size", Some(new_size)),
+                    ("new_ptr", None),
+                ]
+                .map(|(name, initializer)| AsmArg {
+                    name: ident(name),
+                    initializer,
+                });
+                let body = [
+                    ("aloc", vec!["new_size"]),
+                    ("move", vec!["new_ptr", "hp"]),
+                    ("mcp", vec!["new_ptr", "ptr", "size"]),
+                ]
+                .map(|(op_name, op_args)| AsmInstruction {
+                    label: None,
+                    name: ident(op_name),
+                    args: op_args.into_iter().map(ident).collect(),
+                    immediate: None,
+                    metadata: span_md_idx,
+                });
+                let new_ptr = grow_block
+                    .ins(context)
+                    .asm_block(args.to_vec(), body.to_vec(), ptr_ty, Some(ident("new_ptr")))
+                    .add_metadatum(context, span_md_idx);
+                grow_block
+                    .ins(context)
+                    .branch(final_block, vec![new_ptr])
+                    .add_metadatum(context, span_md_idx);
+
+                self.current_block = final_block;
+                Ok(final_block.get_arg(context, result_arg_idx).unwrap())
+            }
+            Intrinsic::HeapStats => {
+                let span_md_idx = md_mgr.span_to_md(context, &span);
+                let heap_ptr = self
+                    .current_block
+                    .ins(context)
+                    .read_register(sway_ir::Register::Hp)
+                    .add_metadatum(context, span_md_idx);
+                // The heap grows downwards from the top of the VM's memory.
+                let heap_top = Constant::get_uint(context, 64, VM_MAX_RAM);
+                let allocated = self
+                    .current_block
+                    .ins(context)
+                    .binary_op(BinaryOpKind::Sub, heap_top, heap_ptr)
+                    .add_metadatum(context, span_md_idx);
+                let u64_ty = Type::get_uint64(context);
+                self.compile_tuple_from_values(
+                    context,
+                    vec![heap_ptr, allocated],
+                    vec![u64_ty, u64_ty],
+                    span_md_idx,
+                )
+            }
+            Intrinsic::Memcpy | Intrinsic::Memcmp => {
+                let span_md_idx = md_mgr.span_to_md(context, &span);
+                let lhs = self.compile_expression_to_value(context, md_mgr, &arguments[0])?;
+                let rhs = self.compile_expression_to_value(context, md_mgr, &arguments[1])?;
+                let len = self.compile_expression_to_value(context, md_mgr, &arguments[2])?;
+                Ok(self.compile_mem_op(context, kind, lhs, rhs, len, span_md_idx))
+            }
         }
     }
 
+    /// Copies the `len` bytes at `rhs` to `lhs` for [Intrinsic::Memcpy], or compares the `len`
+    /// bytes at `lhs` and `rhs` for [Intrinsic::Memcmp]. Both are single instructions of the VM.
+    fn compile_mem_op(
+        &mut self,
+        context: &mut Context,
+        kind: Intrinsic,
+        lhs: Value,
+        rhs: Value,
+        len: Value,
+        span_md_idx: Option<MetadataIndex>,
+    ) -> Value {
+        let ident = |name: &str| Ident::new_no_span(name.to_string());
+        let (op_name, ret_ty, ret_name) = match kind {
+            Intrinsic::Memcpy => ("mcp", Type::get_unit(context), None),
+            _ => ("meq", Type::get_bool(context), Some(ident("res"))),
+        };
+        let mut args = vec![("lhs", Some(lhs)), ("rhs", Some(rhs)), ("len", Some(len))];
+        let op_args = match kind {
+            Intrinsic::Memcpy => vec!["lhs", "rhs", "len"],
+            _ => {
+                args.push(("res", None));
+                vec!["res", "lhs", "rhs", "len"]
+            }
+        };
+        let args = args
+            .into_iter()
+            .map(|(name, initializer)| AsmArg {
+                name: ident(name),
+                initializer,
+            })
+            .collect();
+        let body = vec![AsmInstruction {
+            label: None,
+            name: ident(op_name),
+            args: op_args.into_iter().map(ident).collect(),
+            immediate: None,
+            metadata: span_md_idx,
+        }];
+        self.current_block
+            .ins(context)
+            .asm_block(args, body, ret_ty, ret_name)
+            .add_metadatum(context, span_md_idx)
+    }
+
     fn compile_return_statement(
         &mut self,
         context: &mut Context,
@@ -970,6 +1210,8 @@ impl<'eng> FnCompiler<'eng> {
             return Ok(ret_value);
         }
 
+        self.compile_ensures(context, md_mgr, ret_value)?;
+
         let span_md_idx = md_mgr.span_to_md(context, &ast_expr.span);
         ret_value
             .get_type(context)
@@ -987,6 +1229,304 @@ impl<'eng> FnCompiler<'eng> {
             })
     }
 
+    /// Compiles the binary `op`, doing what `overflow` says when it's an addition, subtraction or
+    /// multiplication whose result doesn't fit in the type of the operands.
+    ///
+    /// Both wrapping and checking set the VM's `F_WRAPPING` flag around the operation, so that it
+    /// wraps around 64 bits and sets `$of` rather than panicking. The narrower integer types are
+    /// operated on in 64 bits, so they're masked or compared to their max as well.
+    #[allow(clippy::too_many_arguments)]
+    fn compile_arithmetic_op(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        op: BinaryOpKind,
+        lhs: Value,
+        rhs: Value,
+        overflow: OverflowBehavior,
+        span: &Span,
+    ) -> Result<Value, CompileError> {
+        // The VM flag making arithmetic operations wrap on overflow.
+        const F_WRAPPING: u64 = 0b10;
+
+        let op_name = match (overflow, op) {
+            (OverflowBehavior::Panic, _) => None,
+            (_, BinaryOpKind::Add) => Some("add"),
+            (_, BinaryOpKind::Sub) => Some("sub"),
+            (_, BinaryOpKind::Mul) => Some("mul"),
+            _ => None,
+        };
+        let Some(op_name) = op_name else {
+            return Ok(self.current_block.ins(context).binary_op(op, lhs, rhs));
+        };
+        let ty = lhs.get_type(context).ok_or_else(|| {
+            CompileError::Internal(
+                "Unable to determine the type of an arithmetic operand.",
+                span.clone(),
+            )
+        })?;
+        let nbits = ty.get_uint_width(context).ok_or_else(|| {
+            CompileError::Internal(
+                "Only unsigned integers can overflow arithmetic operations.",
+                span.clone(),
+            )
+        })?;
+
+        let span_md_idx = md_mgr.span_to_md(context, span);
+        let ident = |name: &str| Ident::new_no_span(name.to_string());
+        let mut args = vec![
+            ("lhs", Some(lhs)),
+            ("rhs", Some(rhs)),
+            ("flags", None),
+            (
+                "wrapping",
+                Some(Constant::get_uint(context, 64, F_WRAPPING)),
+            ),
+            ("res", None),
+        ];
+        let mut body = vec![
+            ("move", vec!["flags", "flag"]),
+            ("or", vec!["wrapping", "wrapping", "flags"]),
+            ("flag", vec!["wrapping"]),
+            (op_name, vec!["res", "lhs", "rhs"]),
+        ];
+        if overflow == OverflowBehavior::Checked {
+            args.push(("ovf", None));
+            body.push(("move", vec!["ovf", "of"]));
+        }
+        body.push(("flag", vec!["flags"]));
+        if nbits < 64 {
+            let max = u64::MAX >> (64 - nbits);
+            args.push(("max", Some(Constant::get_uint(context, 64, max))));
+            match overflow {
+                OverflowBehavior::Wrap => body.push(("and", vec!["res", "res", "max"])),
+                _ => body.extend([
+                    ("gt", vec!["res", "res", "max"]),
+                    ("or", vec!["ovf", "ovf", "res"]),
+                ]),
+            }
+        }
+        let args = args
+            .into_iter()
+            .map(|(name, initializer)| AsmArg {
+                name: ident(name),
+                initializer,
+            })
+            .collect();
+        let body = body
+            .into_iter()
+            .map(|(op_name, op_args)| AsmInstruction {
+                label: None,
+                name: ident(op_name),
+                args: op_args.into_iter().map(ident).collect(),
+                immediate: None,
+                metadata: span_md_idx,
+            })
+            .collect();
+
+        if overflow == OverflowBehavior::Wrap {
+            return Ok(self
+                .current_block
+                .ins(context)
+                .asm_block(args, body, ty, Some(ident("res")))
+                .add_metadatum(context, span_md_idx));
+        }
+
+        // The operation is checked, then done again without the flag, now that it can't overflow.
+        let ovf = self
+            .current_block
+            .ins(context)
+            .asm_block(args, body, Type::get_uint64(context), Some(ident("ovf")))
+            .add_metadatum(context, span_md_idx);
+        let zero = Constant::get_uint(context, 64, 0);
+        let fits = self
+            .current_block
+            .ins(context)
+            .cmp(Predicate::Equal, ovf, zero)
+            .add_metadatum(context, span_md_idx);
+        let fits_block = self.function.create_block(context, None);
+        let overflows_block = self.function.create_block(context, None);
+        self.current_block
+            .ins(context)
+            .conditional_branch(fits, fits_block, overflows_block, vec![], vec![])
+            .add_metadatum(context, span_md_idx);
+
+        let revert_code = Constant::get_uint(context, 64, constants::FAILED_OVERFLOW_SIGNAL);
+        overflows_block
+            .ins(context)
+            .revert(revert_code)
+            .add_metadatum(context, span_md_idx);
+
+        self.current_block = fits_block;
+        Ok(self
+            .current_block
+            .ins(context)
+            .binary_op(op, lhs, rhs)
+            .add_metadatum(context, span_md_idx))
+    }
+
+    /// Reverts with [constants::FAILED_REQUIRES_SIGNAL] unless all the `conditions` of the
+    /// `#[requires(..)]` attributes of the function hold.
+    pub(super) fn compile_requires(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        conditions: &[ty::TyExpression],
+    ) -> Result<(), CompileError> {
+        for condition in conditions {
+            self.compile_specification_condition(
+                context,
+                md_mgr,
+                condition,
+                constants::FAILED_REQUIRES_SIGNAL,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Reverts with [constants::FAILED_ENSURES_SIGNAL] unless all the conditions of the
+    /// `#[ensures(..)]` attributes of the function hold, with `result` bound to `ret_value`.
+    pub(super) fn compile_ensures(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        ret_value: Value,
+    ) -> Result<(), CompileError> {
+        if self.ensures.is_empty() {
+            return Ok(());
+        }
+        let ret_type = ret_value.get_type(context).ok_or_else(|| {
+            CompileError::Internal(
+                "Unable to determine the type of the returned value.",
+                Span::dummy(),
+            )
+        })?;
+        let conditions = self.ensures.clone();
+        self.compile_with_new_scope(|fn_compiler| {
+            let local_name = fn_compiler
+                .lexical_map
+                .insert(constants::ENSURES_RESULT_NAME.to_owned());
+            let local_var = fn_compiler
+                .function
+                .new_local_var(context, local_name, ret_type, None, false)
+                .map_err(|ir_error| {
+                    CompileError::InternalOwned(ir_error.to_string(), Span::dummy())
+                })?;
+            if ir_type_size_in_bytes(context, &ret_type) > 0 {
+                let local_ptr = fn_compiler.current_block.ins(context).get_local(local_var);
+                fn_compiler
+                    .current_block
+                    .ins(context)
+                    .store(local_ptr, ret_value);
+            }
+            for condition in &conditions {
+                fn_compiler.compile_specification_condition(
+                    context,
+                    md_mgr,
+                    condition,
+                    constants::FAILED_ENSURES_SIGNAL,
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Reverts with [constants::FAILED_PARAM_SIGNAL] unless the arguments are within the bounds
+    /// of the `#[param(..)]` attributes of their parameters.
+    pub(super) fn compile_param_validation(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        parameters: &[ty::TyFunctionParameter],
+    ) -> Result<(), CompileError> {
+        for param in parameters {
+            let Some(validation) = &param.validation else {
+                continue;
+            };
+            let arg = self
+                .function
+                .get_arg(context, param.name.as_str())
+                .ok_or_else(|| {
+                    CompileError::InternalOwned(
+                        format!("Unable to resolve parameter '{}'.", param.name.as_str()),
+                        param.name.span(),
+                    )
+                })?;
+            let nbits = arg
+                .get_type(context)
+                .and_then(|ty| ty.get_uint_width(context))
+                .ok_or_else(|| {
+                    CompileError::Internal(
+                        "Only the arguments of unsigned integer parameters can be validated.",
+                        validation.span.clone(),
+                    )
+                })?;
+
+            let span_md_idx = md_mgr.span_to_md(context, &validation.span);
+            let bounds = [
+                (validation.min, Predicate::LessThan),
+                (validation.max, Predicate::GreaterThan),
+            ];
+            for (bound, out_of_bounds) in bounds {
+                let Some(bound) = bound else {
+                    continue;
+                };
+                let bound = Constant::get_uint(context, nbits, bound);
+                let out_of_bounds = self
+                    .current_block
+                    .ins(context)
+                    .cmp(out_of_bounds, arg, bound)
+                    .add_metadatum(context, span_md_idx);
+
+                let fails_block = self.function.create_block(context, None);
+                let holds_block = self.function.create_block(context, None);
+                self.current_block
+                    .ins(context)
+                    .conditional_branch(out_of_bounds, fails_block, holds_block, vec![], vec![])
+                    .add_metadatum(context, span_md_idx);
+
+                let revert_code = Constant::get_uint(context, 64, constants::FAILED_PARAM_SIGNAL);
+                fails_block
+                    .ins(context)
+                    .revert(revert_code)
+                    .add_metadatum(context, span_md_idx);
+
+                self.current_block = holds_block;
+            }
+        }
+        Ok(())
+    }
+
+    fn compile_specification_condition(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        condition: &ty::TyExpression,
+        revert_code: u64,
+    ) -> Result<(), CompileError> {
+        let cond_value = self.compile_expression_to_value(context, md_mgr, condition)?;
+        if cond_value.is_diverging(context) {
+            return Ok(());
+        }
+
+        let span_md_idx = md_mgr.span_to_md(context, &condition.span);
+        let holds_block = self.function.create_block(context, None);
+        let fails_block = self.function.create_block(context, None);
+        self.current_block
+            .ins(context)
+            .conditional_branch(cond_value, holds_block, fails_block, vec![], vec![])
+            .add_metadatum(context, span_md_idx);
+
+        let revert_code = Constant::get_uint(context, 64, revert_code);
+        fails_block
+            .ins(context)
+            .revert(revert_code)
+            .add_metadatum(context, span_md_idx);
+
+        self.current_block = holds_block;
+        Ok(())
+    }
+
     fn compile_lazy_op(
         &mut self,
         context: &mut Context,
@@ -1324,6 +1864,7 @@ impl<'eng> FnCompiler<'eng> {
         context: &mut Context,
         md_mgr: &mut MetadataManager,
         ast_args: &[(Ident, ty::TyExpression)],
+        callee_id: &DeclId<ty::TyFunctionDecl>,
         callee: &ty::TyFunctionDecl,
         span_md_idx: Option<MetadataIndex>,
     ) -> Result<Value, CompileError> {
@@ -1355,6 +1896,19 @@ impl<'eng> FnCompiler<'eng> {
         );
         let new_callee = match self.recreated_fns.get(&fn_key).copied() {
             Some(func) => func,
+            // A function calling itself only refers to its signature, the body is the one which is
+            // currently being compiled further up the stack.
+            None if callee.is_recursive_ref => self
+                .compilation_stack
+                .iter()
+                .rev()
+                .find_map(|(id, func)| (id == callee_id).then_some(*func))
+                .ok_or_else(|| {
+                    CompileError::Internal(
+                        "Recursive call to a function which is not being compiled.",
+                        callee.span(),
+                    )
+                })?,
             None => {
                 let callee_fn_decl = ty::TyFunctionDecl {
                     type_parameters: Vec::new(),
@@ -1377,6 +1931,9 @@ impl<'eng> FnCompiler<'eng> {
                     &self.messages_types_map,
                     is_entry,
                     None,
+                    &self.compilation_stack,
+                    self.check_specifications,
+                    self.overflow,
                 )?
                 .unwrap();
                 self.recreated_fns.insert(fn_key, new_func);
@@ -2312,38 +2869,48 @@ impl<'eng> FnCompiler<'eng> {
                 init_types.push(init_type);
             }
 
-            let tuple_type = Type::new_struct(context, init_types.clone());
-            let temp_name = self.lexical_map.insert_anon();
-            let tuple_var = self
-                .function
-                .new_local_var(context, temp_name, tuple_type, None, false)
-                .map_err(|ir_error| {
-                    CompileError::InternalOwned(ir_error.to_string(), Span::dummy())
-                })?;
-            let tuple_val = self
-                .current_block
-                .ins(context)
-                .get_local(tuple_var)
-                .add_metadatum(context, span_md_idx);
+            self.compile_tuple_from_values(context, init_values, init_types, span_md_idx)
+        }
+    }
 
-            init_values
-                .into_iter()
-                .zip(init_types.into_iter())
-                .enumerate()
-                .for_each(|(insert_idx, (field_val, field_type))| {
-                    let gep_val = self
-                        .current_block
-                        .ins(context)
-                        .get_elem_ptr_with_idx(tuple_val, field_type, insert_idx as u64)
-                        .add_metadatum(context, span_md_idx);
-                    self.current_block
-                        .ins(context)
-                        .store(gep_val, field_val)
-                        .add_metadatum(context, span_md_idx);
-                });
+    /// Stores the already compiled `init_values` of the given types into a new local tuple,
+    /// returning a pointer to it.
+    fn compile_tuple_from_values(
+        &mut self,
+        context: &mut Context,
+        init_values: Vec<Value>,
+        init_types: Vec<Type>,
+        span_md_idx: Option<MetadataIndex>,
+    ) -> Result<Value, CompileError> {
+        let tuple_type = Type::new_struct(context, init_types.clone());
+        let temp_name = self.lexical_map.insert_anon();
+        let tuple_var = self
+            .function
+            .new_local_var(context, temp_name, tuple_type, None, false)
+            .map_err(|ir_error| CompileError::InternalOwned(ir_error.to_string(), Span::dummy()))?;
+        let tuple_val = self
+            .current_block
+            .ins(context)
+            .get_local(tuple_var)
+            .add_metadatum(context, span_md_idx);
 
-            Ok(tuple_val)
-        }
+        init_values
+            .into_iter()
+            .zip(init_types.into_iter())
+            .enumerate()
+            .for_each(|(insert_idx, (field_val, field_type))| {
+                let gep_val = self
+                    .current_block
+                    .ins(context)
+                    .get_elem_ptr_with_idx(tuple_val, field_type, insert_idx as u64)
+                    .add_metadatum(context, span_md_idx);
+                self.current_block
+                    .ins(context)
+                    .store(gep_val, field_val)
+                    .add_metadatum(context, span_md_idx);
+            });
+
+        Ok(tuple_val)
     }
 
     fn compile_tuple_elem_expr(
@@ -2472,7 +3039,9 @@ impl<'eng> FnCompiler<'eng> {
                      op_args,
                      immediate,
                      span,
+                     label,
                  }| AsmInstruction {
+                    label: label.clone(),
                     name: op_name.clone(),
                     args: op_args.clone(),
                     immediate: immediate.clone(),
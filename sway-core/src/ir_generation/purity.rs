@@ -4,6 +4,7 @@ use crate::{
         Purity::{self, *},
     },
     metadata::{MetadataManager, StorageOperation},
+    semantic_analysis::storage_effects::StorageEffects,
 };
 
 use sway_error::warning::{CompileWarning, Warning};
@@ -13,9 +14,20 @@ use sway_types::span::Span;
 
 use std::collections::HashMap;
 
-#[derive(Default)]
 pub(crate) struct PurityEnv {
     memos: HashMap<Function, (bool, bool)>,
+    /// The storage fields accessed by the functions, by the span of their declaration, which the
+    /// warnings about unneeded attributes mention.
+    storage_effects: HashMap<Span, StorageEffects>,
+}
+
+impl PurityEnv {
+    pub(crate) fn new(storage_effects: HashMap<Span, StorageEffects>) -> Self {
+        PurityEnv {
+            memos: HashMap::new(),
+            storage_effects,
+        }
+    }
 }
 
 /// Analyses purity annotations on functions.
@@ -100,10 +112,20 @@ pub(crate) fn check_function_purity(
             span,
         });
     };
+    // The storage fields accessed are only mentioned if they agree with the IR, which is what the
+    // attributes are checked against.
+    let effects = env
+        .storage_effects
+        .get(&span)
+        .filter(|effects| {
+            !effects.reads.is_empty() == reads && !effects.writes.is_empty() == writes
+        })
+        .map(StorageEffects::describe);
     let warn = |span, purity: Purity| {
         handler.emit_warn(CompileWarning {
             warning_content: Warning::DeadStorageDeclarationForFunction {
                 unneeded_attrib: purity.to_attribute_syntax(),
+                effects: effects.clone(),
             },
             span,
         });
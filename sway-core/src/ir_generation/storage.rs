@@ -5,7 +5,11 @@ use crate::{
         fuel_tx::StorageSlot,
         fuel_types::{Bytes32, Bytes8},
     },
+    language::ty,
+    Engines, TypeId, TypeInfo,
 };
+
+use super::convert::convert_resolved_typeid_no_span;
 use sway_ir::{
     constant::{Constant, ConstantValue},
     context::Context,
@@ -16,7 +20,7 @@ use sway_types::state::StateIndex;
 /// Hands out storage keys using a state index and a list of subfield indices.
 /// Basically returns sha256("storage_<state_index>_<idx1>_<idx2>_..")
 ///
-pub(super) fn get_storage_key<T>(ix: &StateIndex, indices: &[T]) -> Bytes32
+pub(crate) fn get_storage_key<T>(ix: &StateIndex, indices: &[T]) -> Bytes32
 where
     T: std::fmt::Display,
 {
@@ -47,6 +51,45 @@ pub(super) fn add_to_b256(x: Bytes32, y: u64) -> Bytes32 {
     Bytes32::from(res)
 }
 
+/// The layout of the fields of the struct of type `type_id` stored from `key`, `offset` bytes
+/// into it, and of their own fields, recursively.
+///
+/// Structs are packed into successive storage slots from their key, so each field is in the slot
+/// `key + offset / 32`, `offset % 32` bytes into it, where `offset` is its byte offset in the
+/// packed struct.
+pub(crate) fn get_struct_fields_layout(
+    engines: &Engines,
+    context: &mut Context,
+    type_id: TypeId,
+    key: Bytes32,
+    offset: u64,
+) -> Vec<ty::StorageFieldLayout> {
+    let type_engine = engines.te();
+    let decl_engine = engines.de();
+    let TypeInfo::Struct(decl_ref) = type_engine.get_unaliased(type_id) else {
+        return vec![];
+    };
+    let mut field_offset = offset;
+    let mut fields = vec![];
+    for field in decl_engine.get_struct(&decl_ref).fields {
+        let field_type_id = field.type_argument.type_id;
+        let Ok(field_type) =
+            convert_resolved_typeid_no_span(type_engine, decl_engine, context, &field_type_id)
+        else {
+            return vec![];
+        };
+        fields.push(ty::StorageFieldLayout {
+            name: field.name.as_str().to_string(),
+            type_name: engines.help_out(field_type_id).to_string(),
+            key: add_to_b256(key, field_offset / 32),
+            offset: field_offset % 32,
+            fields: get_struct_fields_layout(engines, context, field_type_id, key, field_offset),
+        });
+        field_offset += ir_type_size_in_bytes(context, &field_type);
+    }
+    fields
+}
+
 /// Given a constant value `constant`, a type `ty`, a state index, and a vector of subfield
 /// indices, serialize the constant into a vector of storage slots. The keys (slots) are
 /// generated using the state index and the subfield indices which are recursively built. The
@@ -8,6 +8,7 @@ pub struct AsmOp {
     pub(crate) op_args: Vec<Ident>,
     pub(crate) span: Span,
     pub(crate) immediate: Option<Ident>,
+    pub(crate) label: Option<Ident>,
 }
 
 impl Hash for AsmOp {
@@ -17,6 +18,7 @@ impl Hash for AsmOp {
         if let Some(immediate) = self.immediate.clone() {
             immediate.hash(state);
         }
+        self.label.hash(state);
     }
 }
 
@@ -24,6 +26,7 @@ impl PartialEq for AsmOp {
     fn eq(&self, other: &Self) -> bool {
         self.op_name == other.op_name
             && self.op_args == other.op_args
+            && self.label == other.label
             && if let (Some(l), Some(r)) = (self.immediate.clone(), other.immediate.clone()) {
                 l == r
             } else {
@@ -15,7 +15,8 @@ pub enum Literal {
     U16(u16),
     U32(u32),
     U64(u64),
-    String(span::Span),
+    /// A string literal's source span and its contents, with escapes resolved.
+    String(span::Span, String),
     Numeric(u64),
     Boolean(bool),
     B256([u8; 32]),
@@ -45,9 +46,9 @@ impl Hash for Literal {
                 state.write_u8(5);
                 x.hash(state);
             }
-            String(inner) => {
+            String(_, value) => {
                 state.write_u8(6);
-                inner.as_str().hash(state);
+                value.hash(state);
             }
             Boolean(x) => {
                 state.write_u8(7);
@@ -68,7 +69,7 @@ impl PartialEq for Literal {
             (Self::U16(l0), Self::U16(r0)) => l0 == r0,
             (Self::U32(l0), Self::U32(r0)) => l0 == r0,
             (Self::U64(l0), Self::U64(r0)) => l0 == r0,
-            (Self::String(l0), Self::String(r0)) => *l0.as_str() == *r0.as_str(),
+            (Self::String(_, l0), Self::String(_, r0)) => l0 == r0,
             (Self::Numeric(l0), Self::Numeric(r0)) => l0 == r0,
             (Self::Boolean(l0), Self::Boolean(r0)) => l0 == r0,
             (Self::B256(l0), Self::B256(r0)) => l0 == r0,
@@ -85,7 +86,7 @@ impl fmt::Display for Literal {
             Literal::U32(content) => content.to_string(),
             Literal::U64(content) => content.to_string(),
             Literal::Numeric(content) => content.to_string(),
-            Literal::String(content) => content.as_str().to_string(),
+            Literal::String(_, value) => value.clone(),
             Literal::Boolean(content) => content.to_string(),
             Literal::B256(content) => content
                 .iter()
@@ -124,9 +125,20 @@ impl Literal {
         }
     }
 
+    /// The value of this literal if it is an unsigned integer, widened to `u64`.
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            Literal::U8(value) => Some(*value as u64),
+            Literal::U16(value) => Some(*value as u64),
+            Literal::U32(value) => Some(*value as u64),
+            Literal::U64(value) | Literal::Numeric(value) => Some(*value),
+            _ => None,
+        }
+    }
+
     pub(crate) fn to_typeinfo(&self) -> TypeInfo {
         match self {
-            Literal::String(s) => TypeInfo::Str(Length::new(s.as_str().len(), s.clone())),
+            Literal::String(s, value) => TypeInfo::Str(Length::new(value.len(), s.clone())),
             Literal::Numeric(_) => TypeInfo::Numeric,
             Literal::U8(_) => TypeInfo::UnsignedInteger(IntegerBits::Eight),
             Literal::U16(_) => TypeInfo::UnsignedInteger(IntegerBits::Sixteen),
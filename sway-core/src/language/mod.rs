@@ -5,6 +5,7 @@ mod lazy_op;
 pub mod lexed;
 mod literal;
 mod module;
+mod param_validation;
 pub mod parsed;
 pub mod programs;
 mod purity;
@@ -17,6 +18,7 @@ pub use inline::*;
 pub use lazy_op::*;
 pub use literal::*;
 pub use module::*;
+pub use param_validation::*;
 pub use programs::*;
 pub use purity::*;
 pub use visibility::*;
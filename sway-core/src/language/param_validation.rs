@@ -0,0 +1,22 @@
+use sway_types::{constants::*, Span};
+
+/// The validation of the arguments of an ABI method parameter, given by its `#[param(..)]`
+/// attribute, e.g. `#[param(min = 1, max = 100)]`. The bounds are inclusive.
+///
+/// Calls of the method revert with [FAILED_PARAM_SIGNAL] when an argument is out of bounds, and
+/// the bounds are exported in the JSON ABI so that SDKs can check the arguments before calling it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParamValidation {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub span: Span,
+}
+
+impl ParamValidation {
+    /// The arguments of the `#[param(..)]` attribute, e.g. `["min = 1", "max = 100"]`.
+    pub fn to_attribute_args(&self) -> Vec<String> {
+        let min = self.min.map(|min| format!("{PARAM_MIN_ARG_NAME} = {min}"));
+        let max = self.max.map(|max| format!("{PARAM_MAX_ARG_NAME} = {max}"));
+        min.into_iter().chain(max).collect()
+    }
+}
@@ -18,6 +18,11 @@ pub struct FunctionDeclaration {
     pub return_type: TypeArgument,
     pub type_parameters: Vec<TypeParameter>,
     pub where_clause: Vec<(Ident, Vec<TraitConstraint>)>,
+    /// The conditions of the `#[requires(..)]` attributes, which hold when the function is called.
+    pub requires: Vec<Expression>,
+    /// The conditions of the `#[ensures(..)]` attributes, which hold when the function returns,
+    /// with `result` bound to the returned value.
+    pub ensures: Vec<Expression>,
 }
 
 #[derive(Debug, Clone)]
@@ -27,6 +32,8 @@ pub struct FunctionParameter {
     pub is_mutable: bool,
     pub mutability_span: Span,
     pub type_argument: TypeArgument,
+    /// The validation of the arguments given by a `#[param(..)]` attribute.
+    pub validation: Option<ParamValidation>,
 }
 
 impl EqWithEngines for FunctionParameter {}
@@ -37,14 +44,22 @@ impl PartialEqWithEngines for FunctionParameter {
             && self.is_mutable == other.is_mutable
             && self.mutability_span == other.mutability_span
             && self.type_argument.eq(&other.type_argument, engines)
+            && self.validation == other.validation
     }
 }
 
 impl FunctionDeclaration {
-    /// Checks if this `FunctionDeclaration` is a test.
+    /// Checks if this `FunctionDeclaration` is a test, or a function run before or after each
+    /// test.
     pub(crate) fn is_test(&self) -> bool {
-        self.attributes
-            .keys()
-            .any(|k| matches!(k, AttributeKind::Test))
+        self.attributes.keys().any(|k| {
+            matches!(
+                k,
+                AttributeKind::Test
+                    | AttributeKind::BeforeEach
+                    | AttributeKind::AfterEach
+                    | AttributeKind::Invariant
+            )
+        })
     }
 }
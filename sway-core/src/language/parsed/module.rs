@@ -29,3 +29,13 @@ pub struct ParseSubmodule {
     pub mod_name_span: Span,
     pub visibility: Visibility,
 }
+
+impl ParseModule {
+    /// Excludes all test functions from this module and its submodules.
+    pub(crate) fn exclude_tests(&mut self) {
+        self.tree.exclude_tests();
+        for (_, submodule) in &mut self.submodules {
+            submodule.module.exclude_tests();
+        }
+    }
+}
@@ -42,8 +42,8 @@ impl std::fmt::Display for TreeType {
 }
 
 impl ParseProgram {
-    /// Excludes all test functions from the parse tree.
+    /// Excludes all test functions from the parse tree, including those of submodules.
     pub(crate) fn exclude_tests(&mut self) {
-        self.root.tree.exclude_tests()
+        self.root.exclude_tests()
     }
 }
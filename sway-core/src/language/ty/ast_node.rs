@@ -10,7 +10,6 @@ use crate::{
     engine_threading::*,
     error::*,
     language::{parsed::TreeType, ty::*, Visibility},
-    transform::AttributeKind,
     type_system::*,
     types::*,
 };
@@ -202,10 +201,7 @@ impl TyAstNode {
                         decl_id, ..
                     })),
                 ..
-            } => {
-                let TyFunctionDecl { attributes, .. } = decl_engine.get_function(decl_id);
-                attributes.contains_key(&AttributeKind::Test)
-            }
+            } => decl_engine.get_function(decl_id).is_test(),
             _ => false,
         }
     }
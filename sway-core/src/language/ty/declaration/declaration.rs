@@ -521,6 +521,7 @@ impl CollectTypesMetadata for TyDecl {
                     warnings,
                     errors
                 ));
+                ctx.forget_argument(&decl.name);
                 body
             }
             TyDecl::FunctionDecl(FunctionDecl { decl_id, .. }) => {
@@ -9,7 +9,7 @@ use crate::{
     decl_engine::*,
     engine_threading::*,
     error::*,
-    language::{parsed, ty::*, Inline, Purity, Visibility},
+    language::{parsed, ty::*, Inline, ParamValidation, Purity, Visibility},
     transform,
     type_system::*,
     types::*,
@@ -35,6 +35,15 @@ pub struct TyFunctionDecl {
     pub is_contract_call: bool,
     pub purity: Purity,
     pub where_clause: Vec<(Ident, Vec<TraitConstraint>)>,
+    /// whether this is the declaration through which a function calls itself. It only carries the
+    /// signature; the body is that of the function being declared.
+    pub is_recursive_ref: bool,
+    /// The declaration through which this function calls itself, if it may.
+    pub recursive_ref: Option<DeclId<TyFunctionDecl>>,
+    /// The conditions of the `#[requires(..)]` attributes, checked when the function is called.
+    pub requires: Vec<TyExpression>,
+    /// The conditions of the `#[ensures(..)]` attributes, checked when the function returns.
+    pub ensures: Vec<TyExpression>,
 }
 
 impl Named for TyFunctionDecl {
@@ -64,6 +73,8 @@ impl PartialEqWithEngines for TyFunctionDecl {
             && self.visibility == other.visibility
             && self.is_contract_call == other.is_contract_call
             && self.purity == other.purity
+            && self.requires.eq(&other.requires, engines)
+            && self.ensures.eq(&other.ensures, engines)
     }
 }
 
@@ -78,12 +89,16 @@ impl HashWithEngines for TyFunctionDecl {
             visibility,
             is_contract_call,
             purity,
+            requires,
+            ensures,
             // these fields are not hashed because they aren't relevant/a
             // reliable source of obj v. obj distinction
             span: _,
             attributes: _,
             implementing_type: _,
             where_clause: _,
+            is_recursive_ref: _,
+            recursive_ref: _,
         } = self;
         name.hash(state);
         body.hash(state, engines);
@@ -93,6 +108,8 @@ impl HashWithEngines for TyFunctionDecl {
         visibility.hash(state);
         is_contract_call.hash(state);
         purity.hash(state);
+        requires.hash(state, engines);
+        ensures.hash(state, engines);
     }
 }
 
@@ -106,6 +123,10 @@ impl SubstTypes for TyFunctionDecl {
             .for_each(|x| x.subst(type_mapping, engines));
         self.return_type.subst(type_mapping, engines);
         self.body.subst(type_mapping, engines);
+        self.requires
+            .iter_mut()
+            .chain(self.ensures.iter_mut())
+            .for_each(|x| x.subst(type_mapping, engines));
     }
 }
 
@@ -119,12 +140,20 @@ impl ReplaceSelfType for TyFunctionDecl {
             .for_each(|x| x.replace_self_type(engines, self_type));
         self.return_type.replace_self_type(engines, self_type);
         self.body.replace_self_type(engines, self_type);
+        self.requires
+            .iter_mut()
+            .chain(self.ensures.iter_mut())
+            .for_each(|x| x.replace_self_type(engines, self_type));
     }
 }
 
 impl ReplaceDecls for TyFunctionDecl {
     fn replace_decls_inner(&mut self, decl_mapping: &DeclMapping, engines: &Engines) {
         self.body.replace_decls(decl_mapping, engines);
+        self.requires
+            .iter_mut()
+            .chain(self.ensures.iter_mut())
+            .for_each(|x| x.replace_decls(decl_mapping, engines));
     }
 }
 
@@ -192,6 +221,14 @@ impl CollectTypesMetadata for TyFunctionDecl {
                 errors
             ));
         }
+        for condition in self.requires.iter().chain(self.ensures.iter()) {
+            body.append(&mut check!(
+                condition.collect_types_metadata(ctx),
+                return err(warnings, errors),
+                warnings,
+                errors
+            ));
+        }
         body.append(&mut check!(
             self.return_type.type_id.collect_types_metadata(ctx),
             return err(warnings, errors),
@@ -250,6 +287,10 @@ impl TyFunctionDecl {
             return_type,
             type_parameters: Default::default(),
             where_clause,
+            is_recursive_ref: false,
+            recursive_ref: None,
+            requires: Default::default(),
+            ensures: Default::default(),
         }
     }
 
@@ -330,10 +371,42 @@ impl TyFunctionDecl {
         self.name.as_str() == sway_types::constants::DEFAULT_ENTRY_POINT_FN_NAME
     }
 
-    /// Whether or not this function is a unit test, i.e. decorated with `#[test]`.
+    /// Whether or not this function is a unit test, i.e. decorated with `#[test]`, or run before
+    /// or after each of them, i.e. decorated with `#[before_each]`, `#[after_each]` or
+    /// `#[invariant]`.
     pub fn is_test(&self) -> bool {
         self.attributes
             .contains_key(&transform::AttributeKind::Test)
+            || self.is_test_hook().is_some()
+    }
+
+    /// Whether this function is run before or after each unit test, and which.
+    pub fn is_test_hook(&self) -> Option<TestHook> {
+        if self
+            .attributes
+            .contains_key(&transform::AttributeKind::BeforeEach)
+        {
+            Some(TestHook::BeforeEach)
+        } else if self
+            .attributes
+            .contains_key(&transform::AttributeKind::AfterEach)
+        {
+            Some(TestHook::AfterEach)
+        } else if self
+            .attributes
+            .contains_key(&transform::AttributeKind::Invariant)
+        {
+            Some(TestHook::Invariant)
+        } else {
+            None
+        }
+    }
+
+    /// Whether or not calls of this function with constant arguments are folded into their
+    /// results, i.e. it is decorated with `#[memoize]`.
+    pub fn is_memoized(&self) -> bool {
+        self.attributes
+            .contains_key(&transform::AttributeKind::Memoize)
     }
 
     pub fn inline(&self) -> Option<Inline> {
@@ -365,6 +438,9 @@ pub struct TyFunctionParameter {
     pub is_mutable: bool,
     pub mutability_span: Span,
     pub type_argument: TypeArgument,
+    /// The validation of the arguments given by a `#[param(..)]` attribute, checked when an ABI
+    /// method is called.
+    pub validation: Option<ParamValidation>,
 }
 
 impl EqWithEngines for TyFunctionParameter {}
@@ -374,6 +450,7 @@ impl PartialEqWithEngines for TyFunctionParameter {
             && self.type_argument.eq(&other.type_argument, engines)
             && self.is_reference == other.is_reference
             && self.is_mutable == other.is_mutable
+            && self.validation == other.validation
     }
 }
 
@@ -384,6 +461,7 @@ impl HashWithEngines for TyFunctionParameter {
             is_reference,
             is_mutable,
             type_argument,
+            validation,
             // these fields are not hashed because they aren't relevant/a
             // reliable source of obj v. obj distinction
             mutability_span: _,
@@ -392,6 +470,7 @@ impl HashWithEngines for TyFunctionParameter {
         type_argument.hash(state, engines);
         is_reference.hash(state);
         is_mutable.hash(state);
+        validation.hash(state);
     }
 }
 
@@ -415,6 +494,18 @@ impl TyFunctionParameter {
     }
 }
 
+/// The kinds of functions run around each unit test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestHook {
+    /// Run before each test, decorated with `#[before_each]`.
+    BeforeEach,
+    /// Run after each test, decorated with `#[after_each]`.
+    AfterEach,
+    /// Run after each test and its `#[after_each]` functions, failing the test if it reverts,
+    /// decorated with `#[invariant]`.
+    Invariant,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TyFunctionSig {
     pub return_type: TypeId,
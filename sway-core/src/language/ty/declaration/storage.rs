@@ -1,11 +1,12 @@
 use std::hash::{Hash, Hasher};
 
+use serde::{Deserialize, Serialize};
 use sway_error::error::CompileError;
 use sway_types::{state::StateIndex, Ident, Named, Span, Spanned};
 
 use crate::{
-    decl_engine::DeclEngine, engine_threading::*, error::*, language::ty::*, transform,
-    type_system::*,
+    decl_engine::DeclEngine, engine_threading::*, error::*, fuel_prelude::fuel_types::Bytes32,
+    language::ty::*, transform, type_system::*,
 };
 
 #[derive(Clone, Debug)]
@@ -16,6 +17,26 @@ pub struct TyStorageDecl {
     pub storage_keyword: Ident,
 }
 
+/// The name, type and storage key of a single storage field, as written to the storage layout
+/// artifact of a contract.
+///
+/// The key of a `StorageMap` is the base key which the keys of its entries are derived from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageFieldLayout {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// The slot the value starts in.
+    pub key: Bytes32,
+    /// The byte offset of the value in its slot, which is only non-zero for the fields of
+    /// structs.
+    #[serde(default)]
+    pub offset: u64,
+    /// The layout of the fields of a struct.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<StorageFieldLayout>,
+}
+
 impl Named for TyStorageDecl {
     fn name(&self) -> &Ident {
         &self.storage_keyword
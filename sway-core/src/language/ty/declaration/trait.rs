@@ -100,6 +100,15 @@ impl PartialEqWithEngines for TyTraitInterfaceItem {
     }
 }
 
+impl Spanned for TyTraitItem {
+    fn span(&self) -> Span {
+        match self {
+            TyTraitItem::Fn(decl_ref) => decl_ref.span(),
+            TyTraitItem::Constant(decl_ref) => decl_ref.span(),
+        }
+    }
+}
+
 impl EqWithEngines for TyTraitItem {}
 impl PartialEqWithEngines for TyTraitItem {
     fn eq(&self, other: &Self, engines: &Engines) -> bool {
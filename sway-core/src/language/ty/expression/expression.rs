@@ -6,7 +6,7 @@ use crate::{
     decl_engine::*,
     engine_threading::*,
     error::*,
-    language::{ty::*, Literal},
+    language::{ty::*, LazyOp, Literal},
     type_system::*,
     types::*,
 };
@@ -129,6 +129,7 @@ impl CollectTypesMetadata for TyExpression {
                 for type_parameter in function_decl.type_parameters {
                     ctx.call_site_insert(type_parameter.type_id, call_path.span())
                 }
+                ctx.call_push(self.span.clone(), &function_decl.parameters, arguments);
 
                 for content in function_decl.body.contents.iter() {
                     res.append(&mut check!(
@@ -138,6 +139,7 @@ impl CollectTypesMetadata for TyExpression {
                         errors
                     ));
                 }
+                ctx.call_pop();
                 ctx.call_site_pop();
             }
             Tuple { fields } => {
@@ -557,4 +559,29 @@ impl TyExpression {
     pub(crate) fn extract_literal_value(&self) -> Option<Literal> {
         self.expression.extract_literal_value()
     }
+
+    /// Returns the value of `self` if it is a boolean known at compile time, i.e. a literal, a
+    /// constant, or `&&` and `||` of those.
+    ///
+    /// Configurable constants are never known at compile time, as their values can be changed
+    /// when the program is deployed.
+    pub(crate) fn static_bool_value(&self) -> Option<bool> {
+        match &self.expression {
+            TyExpressionVariant::Literal(Literal::Boolean(value)) => Some(*value),
+            TyExpressionVariant::ConstantExpression { const_decl, .. }
+                if !const_decl.is_configurable =>
+            {
+                const_decl.value.as_ref()?.static_bool_value()
+            }
+            // The right hand side only needs to be known if it isn't short-circuited.
+            TyExpressionVariant::LazyOperator { op, lhs, rhs } => {
+                match (op, lhs.static_bool_value()?) {
+                    (LazyOp::And, false) => Some(false),
+                    (LazyOp::Or, true) => Some(true),
+                    (LazyOp::And, true) | (LazyOp::Or, false) => rhs.static_bool_value(),
+                }
+            }
+            _ => None,
+        }
+    }
 }
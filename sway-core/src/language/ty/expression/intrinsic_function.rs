@@ -120,10 +120,12 @@ impl CollectTypesMetadata for TyIntrinsicFunctionKind {
 
         match self.kind {
             Intrinsic::Log => {
+                let log_id = LogId::new(ctx.log_id_counter());
                 types_metadata.push(TypeMetadata::LoggedType(
-                    LogId::new(ctx.log_id_counter()),
+                    log_id,
                     self.arguments[0].return_type,
                 ));
+                ctx.record_call_log(log_id);
                 *ctx.log_id_counter_mut() += 1;
             }
             Intrinsic::Smo => {
@@ -133,6 +135,18 @@ impl CollectTypesMetadata for TyIntrinsicFunctionKind {
                 ));
                 *ctx.message_id_counter_mut() += 1;
             }
+            Intrinsic::Revert => {
+                if let Some((code, name)) = ctx.known_u64(&self.arguments[0]) {
+                    types_metadata.push(TypeMetadata::RevertCode(RevertCode {
+                        code,
+                        name,
+                        call_site: ctx
+                            .outermost_call_span()
+                            .unwrap_or_else(|| self.span.clone()),
+                        log_id: ctx.last_call_log(),
+                    }));
+                }
+            }
             _ => {}
         }
 
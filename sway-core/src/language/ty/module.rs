@@ -67,6 +67,32 @@ impl TyModule {
             None
         })
     }
+
+    /// All free functions and methods of trait implementations within this module, not including
+    /// those of its submodules.
+    pub fn functions(&self, decl_engine: &DeclEngine) -> Vec<TyFunctionDecl> {
+        self.all_nodes
+            .iter()
+            .flat_map(|node| match &node.content {
+                TyAstNodeContent::Declaration(TyDecl::FunctionDecl(FunctionDecl {
+                    decl_id,
+                    ..
+                })) => vec![decl_engine.get_function(decl_id)],
+                TyAstNodeContent::Declaration(TyDecl::ImplTrait(ImplTrait { decl_id, .. })) => {
+                    decl_engine
+                        .get_impl_trait(decl_id)
+                        .items
+                        .iter()
+                        .filter_map(|item| match item {
+                            TyImplItem::Fn(fn_decl) => Some(decl_engine.get_function(fn_decl.id())),
+                            TyImplItem::Constant(_) => None,
+                        })
+                        .collect()
+                }
+                _ => vec![],
+            })
+            .collect()
+    }
 }
 
 impl<'module> Iterator for SubmodulesRecursive<'module> {
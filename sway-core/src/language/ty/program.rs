@@ -18,8 +18,10 @@ pub struct TyProgram {
     pub declarations: Vec<TyDecl>,
     pub configurables: Vec<TyConstantDecl>,
     pub storage_slots: Vec<StorageSlot>,
+    pub storage_layout: Vec<StorageFieldLayout>,
     pub logged_types: Vec<(LogId, TypeId)>,
     pub messages_types: Vec<(MessageId, TypeId)>,
+    pub revert_codes: Vec<RevertCode>,
 }
 
 impl TyProgram {
@@ -10,7 +10,9 @@ mod asm_lang;
 mod build_config;
 mod concurrent_slab;
 mod control_flow_analysis;
+pub mod debug_info;
 pub mod decl_engine;
+mod in_memory;
 pub mod ir_generation;
 pub mod language;
 mod metadata;
@@ -25,9 +27,13 @@ use crate::ir_generation::check_function_purity;
 use crate::{error::*, source_map::SourceMap};
 pub use asm_generation::from_ir::compile_ir_to_asm;
 use asm_generation::FinalizedAsm;
-pub use asm_generation::{CompiledBytecode, FinalizedEntry};
-pub use build_config::{BuildConfig, BuildTarget};
+pub use asm_generation::{CompiledBytecode, FinalizedEntry, FunctionSize, GasEstimate};
+pub use build_config::{
+    BuildConfig, BuildTarget, ExperimentalFeature, Inlining, LintLevel, OptLevel, OverflowBehavior,
+    Target,
+};
 use control_flow_analysis::ControlFlowGraph;
+pub use in_memory::{compile_sources_to_bytecode, CancellationToken, Cancelled, CompiledSources};
 use metadata::MetadataManager;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -35,13 +41,14 @@ use std::sync::Arc;
 use sway_ast::AttributeDecl;
 use sway_error::handler::{ErrorEmitted, Handler};
 use sway_ir::{
-    create_o1_pass_group, register_known_passes, Context, Kind, Module, PassManager,
-    ARGDEMOTION_NAME, CONSTDEMOTION_NAME, DCE_NAME, MEMCPYOPT_NAME, MISCDEMOTION_NAME,
-    MODULEPRINTER_NAME, RETDEMOTION_NAME,
+    create_named_pass_group, create_opt_level_pass_group, register_known_passes, Context, Kind,
+    Module, PassManager, ARGDEMOTION_NAME, CONSTDEMOTION_NAME, DCE_NAME, FN_DEDUP_NAME,
+    FUNC_DCE_NAME, HEAP2STACK_NAME, INLINE_MODULE_NAME, MEMCPYOPT_NAME, MISCDEMOTION_NAME,
+    MODULEPRINTER_NAME, OUTLINE_NAME, RETDEMOTION_NAME, SMTPRINTER_NAME,
 };
 use sway_types::constants::DOC_COMMENT_ATTRIBUTE_NAME;
 use sway_types::SourceEngine;
-use sway_utils::{time_expr, PerformanceData, PerformanceMetric};
+use sway_utils::{time_expr, PassMetric, PerformanceData, PerformanceMetric};
 use transform::{Attribute, AttributeArg, AttributeKind, AttributesMap};
 use types::*;
 
@@ -64,6 +71,9 @@ pub mod fuel_prelude {
 
 pub use engine_threading::Engines;
 
+/// The version of the compiler.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /// Given an input `Arc<str>` and an optional [BuildConfig], parse the input into a [lexed::LexedProgram] and [parsed::ParseProgram].
 ///
 /// # Example
@@ -92,7 +102,7 @@ pub fn parse(
             input,
             config.canonical_root_module(),
             None,
-            config.build_target,
+            config,
         )
         .map(|(kind, lexed, parsed)| {
             let lexed = lexed::LexedProgram {
@@ -211,7 +221,7 @@ fn parse_submodules(
     module_name: Option<&str>,
     module: &sway_ast::Module,
     module_dir: &Path,
-    build_target: BuildTarget,
+    config: &BuildConfig,
 ) -> Submodules {
     // Assume the happy path, so there'll be as many submodules as dependencies, but no more.
     let mut lexed_submods = Vec::with_capacity(module.submodules().count());
@@ -221,8 +231,8 @@ fn parse_submodules(
         // Read the source code from the dependency.
         // If we cannot, record as an error, but continue with other files.
         let submod_path = Arc::new(module_path(module_dir, module_name, submod));
-        let submod_str: Arc<str> = match std::fs::read_to_string(&*submod_path) {
-            Ok(s) => Arc::from(s),
+        let submod_str: Arc<str> = match config.read_module_source(&submod_path) {
+            Ok(s) => s,
             Err(e) => {
                 handler.emit_err(CompileError::FileCouldNotBeRead {
                     span: submod.name.span(),
@@ -239,7 +249,7 @@ fn parse_submodules(
             submod_str.clone(),
             submod_path.clone(),
             Some(submod.name.as_str()),
-            build_target,
+            config,
         ) {
             if !matches!(kind, parsed::TreeType::Library) {
                 let source_id = engines.se().get_source_id(submod_path.as_ref());
@@ -278,12 +288,14 @@ fn parse_module_tree(
     src: Arc<str>,
     path: Arc<PathBuf>,
     module_name: Option<&str>,
-    build_target: BuildTarget,
+    config: &BuildConfig,
 ) -> Result<(parsed::TreeType, lexed::LexedModule, parsed::ParseModule), ErrorEmitted> {
     // Parse this module first.
     let module_dir = path.parent().expect("module file has no parent directory");
     let source_id = engines.se().get_source_id(&path.clone());
-    let module = sway_parse::parse_file(handler, src.clone(), Some(source_id))?;
+    let src_span = engines.se().set_source_text(source_id, src.clone());
+    let _module_scope = engines.enter_module(Some(source_id));
+    let module = sway_parse::parse_file(handler, src, Some(source_id))?;
 
     // Parse all submodules before converting to the `ParseTree`.
     // This always recovers on parse errors for the file itself by skipping that file.
@@ -293,16 +305,15 @@ fn parse_module_tree(
         module_name,
         &module.value,
         module_dir,
-        build_target,
+        config,
     );
 
     // Convert from the raw parsed module to the `ParseTree` ready for type-check.
-    let (kind, tree) = to_parsed_lang::convert_parse_tree(
-        &mut to_parsed_lang::Context::new(build_target),
-        handler,
-        engines,
-        module.value.clone(),
-    )?;
+    let mut context =
+        to_parsed_lang::Context::new(config.build_target, config.experimental.clone());
+    context.set_include_tests(config.include_tests);
+    let (kind, tree) =
+        to_parsed_lang::convert_parse_tree(&mut context, handler, engines, module.value.clone())?;
     let module_kind_span = module.value.kind.span();
     let attributes = module_attrs_to_map(handler, &module.attribute_list)?;
 
@@ -310,9 +321,8 @@ fn parse_module_tree(
         tree: module.value,
         submodules: submodules.lexed,
     };
-    let source_id = engines.se().get_source_id(&path.clone());
     let parsed = parsed::ParseModule {
-        span: span::Span::new(src, 0, 0, Some(source_id)).unwrap(),
+        span: src_span,
         module_kind_span,
         tree,
         submodules: submodules.parsed,
@@ -360,6 +370,11 @@ pub fn parsed_to_ast(
         None => return err(warnings, errors),
     };
 
+    // Default the integer locals whose width wasn't inferred to `u64`
+    let numeric_fallback_warnings =
+        semantic_analysis::numeric_fallback::analyze_program(engines, &typed_program);
+    warnings.extend(numeric_fallback_warnings);
+
     // Collect information about the types used in this program
     let CompileResult {
         value: types_metadata_result,
@@ -387,6 +402,15 @@ pub fn parsed_to_ast(
             _ => None,
         }));
 
+    // The same revert is reached once per call to the function containing it.
+    for m in types_metadata.iter() {
+        if let TypeMetadata::RevertCode(revert_code) = m {
+            if !typed_program.revert_codes.contains(revert_code) {
+                typed_program.revert_codes.push(revert_code.clone());
+            }
+        }
+    }
+
     let (print_graph, print_graph_url_format) = match build_config {
         Some(cfg) => (
             cfg.print_dca_graph.clone(),
@@ -419,6 +443,11 @@ pub fn parsed_to_ast(
         semantic_analysis::cei_pattern_analysis::analyze_program(engines, &typed_program);
     warnings.extend(cei_analysis_warnings);
 
+    // Borrow analysis of `ref mut` locals and storage handles
+    let borrow_analysis_warnings =
+        semantic_analysis::borrow_analysis::analyze_program(engines, &typed_program);
+    warnings.extend(borrow_analysis_warnings);
+
     // Check that all storage initializers can be evaluated at compile time.
     let typed_wiss_res = typed_program.get_typed_program_with_initialized_storage_slots(
         engines,
@@ -505,6 +534,16 @@ pub fn compile_to_ast(
     errors.extend(typed_res.errors);
     warnings.extend(typed_res.warnings);
 
+    // Unused imports are reported at their `use` statements, which only the lexed program keeps.
+    if let Some(typed_program) = &typed_res.value {
+        warnings.extend(semantic_analysis::unused_import_analysis::analyze_program(
+            &lexed_program,
+            typed_program,
+        ));
+    }
+
+    let warnings = apply_lint_levels(build_config, warnings, &mut errors);
+
     ok(
         Programs::new(lexed_program, parsed_program, typed_res.value),
         dedup_unsorted(warnings),
@@ -512,6 +551,42 @@ pub fn compile_to_ast(
     )
 }
 
+/// Drops the warnings of allowed lints and turns those of denied lints into errors, returning
+/// the warnings that remain.
+fn apply_lint_levels(
+    build_config: Option<&BuildConfig>,
+    warnings: Vec<CompileWarning>,
+    errors: &mut Vec<CompileError>,
+) -> Vec<CompileWarning> {
+    let Some(build_config) = build_config else {
+        return warnings
+            .into_iter()
+            .filter(|warning| {
+                let lint_name = warning.warning_content.lint_name();
+                !sway_error::warning::Warning::ALLOWED_BY_DEFAULT.contains(&lint_name)
+            })
+            .collect();
+    };
+    warnings
+        .into_iter()
+        .filter(|warning| {
+            let lint_name = warning.warning_content.lint_name();
+            match build_config.lint_level(lint_name) {
+                LintLevel::Allow => false,
+                LintLevel::Warn => true,
+                LintLevel::Deny => {
+                    errors.push(CompileError::DeniedWarning {
+                        warning: warning.to_friendly_warning_string(),
+                        lint: lint_name.to_string(),
+                        span: warning.span(),
+                    });
+                    false
+                }
+            }
+        })
+        .collect()
+}
+
 /// Given input Sway source code, try compiling to a `CompiledAsm`,
 /// containing the asm in opcode form (not raw bytes/bytecode).
 pub fn compile_to_asm(
@@ -530,7 +605,7 @@ pub fn compile_to_asm(
         package_name,
         metrics,
     );
-    ast_to_asm(engines, &ast_res, &build_config)
+    ast_to_asm(engines, &ast_res, &build_config, metrics)
 }
 
 /// Given an AST compilation result, try compiling to a `CompiledAsm`,
@@ -539,6 +614,7 @@ pub fn ast_to_asm(
     engines: &Engines,
     ast_res: &CompileResult<Programs>,
     build_config: &BuildConfig,
+    metrics: &mut PerformanceData,
 ) -> CompileResult<CompiledAsm> {
     let programs = match ast_res.value.as_ref() {
         Some(programs) => programs,
@@ -552,8 +628,16 @@ pub fn ast_to_asm(
 
     let mut errors = ast_res.errors.clone();
     let mut warnings = ast_res.warnings.clone();
+    // The warnings of the earlier phases have had the lint levels applied by `compile_to_ast`
+    // already, those of IR generation and codegen are subject to them too.
+    let mut asm_res = compile_ast_to_ir_to_asm(engines, typed_program, build_config, metrics);
+    asm_res.warnings = apply_lint_levels(
+        Some(build_config),
+        std::mem::take(&mut asm_res.warnings),
+        &mut asm_res.errors,
+    );
     let asm = check!(
-        compile_ast_to_ir_to_asm(engines, typed_program, build_config),
+        asm_res,
         return deduped_err(warnings, errors),
         warnings,
         errors
@@ -565,6 +649,7 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     engines: &Engines,
     program: &ty::TyProgram,
     build_config: &BuildConfig,
+    metrics: &mut PerformanceData,
 ) -> CompileResult<FinalizedAsm> {
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
@@ -580,11 +665,19 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     // errors and then hold as a runtime invariant that none of the types will be unresolved in the
     // IR phase.
 
-    let mut ir = match ir_generation::compile_program(program, build_config.include_tests, engines)
-    {
+    let check_specifications = build_config.check_specifications || build_config.include_tests;
+    let mut ir = match ir_generation::compile_program(
+        program,
+        build_config.include_tests,
+        check_specifications,
+        build_config.overflow,
+        engines,
+    ) {
         Ok(ir) => ir,
         Err(e) => return err(warnings, vec![e]),
     };
+    ir.inline_params = build_config.inlining.into();
+    ir.cse_storage_reads = build_config.cse_storage_reads;
 
     // Find all the entry points for purity checking and DCE.
     let entry_point_functions: Vec<::sway_ir::Function> = ir
@@ -596,7 +689,8 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     // Do a purity check on the _unoptimised_ IR.
     {
         let handler = Handler::default();
-        let mut env = ir_generation::PurityEnv::default();
+        let storage_effects = semantic_analysis::storage_effects::analyze_program(engines, program);
+        let mut env = ir_generation::PurityEnv::new(storage_effects);
         let mut md_mgr = metadata::MetadataManager::default();
         for entry_point in &entry_point_functions {
             check_function_purity(&handler, &mut env, &ir, &mut md_mgr, entry_point);
@@ -606,15 +700,101 @@ pub(crate) fn compile_ast_to_ir_to_asm(
         errors.extend(e);
     }
 
+    // Check that the _unoptimised_ IR only uses what the targeted VM revision supports, before
+    // inlining hides which calls into dependencies lead to the unsupported opcodes.
+    let backend = asm_generation::backend::backend_for(
+        build_config.build_target,
+        build_config.vm_version.as_deref(),
+    )
+    .ok();
+    if let Some((backend, vm_version)) = backend {
+        let handler = Handler::default();
+        let root_module = build_config.canonical_root_module();
+        let package_dir = root_module.parent().unwrap_or(root_module.as_path());
+        backend.check_capabilities(&handler, engines, &ir, vm_version, package_dir);
+        let (e, w) = handler.consume();
+        warnings.extend(w);
+        // The generated code would use the same opcodes, which codegen would report again.
+        if !e.is_empty() {
+            errors.extend(e);
+            return err(warnings, errors);
+        }
+    }
+
+    let final_asm = check!(
+        optimize_ir_to_asm(&mut ir, build_config, metrics),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+
+    ok(final_asm, warnings, errors)
+}
+
+/// Runs the optimizations and target specific transforms configured by `build_config` on `ir`,
+/// and compiles the result to a [FinalizedAsm].
+fn optimize_ir_to_asm(
+    ir: &mut Context,
+    build_config: &BuildConfig,
+    metrics: &mut PerformanceData,
+) -> CompileResult<FinalizedAsm> {
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+    let backend = asm_generation::backend::backend_for(
+        build_config.build_target,
+        build_config.vm_version.as_deref(),
+    )
+    .ok();
+
     // Initialize the pass manager and register known passes.
     let mut pass_mgr = PassManager::default();
     register_known_passes(&mut pass_mgr);
-    let mut pass_group = create_o1_pass_group();
+    pass_mgr.verify_after_each_pass(cfg!(debug_assertions) || build_config.verify_ir);
+    pass_mgr.time_passes(build_config.metrics_outfile.is_some());
+
+    // The optimizations are either those of the optimization level or the passes chosen by name,
+    // in which case none of the target specific optimizations are added to them either.
+    let (mut pass_group, optimize) = match &build_config.ir_passes {
+        Some(names) => match create_named_pass_group(&pass_mgr, names.iter().map(String::as_str)) {
+            Ok(pass_group) => (pass_group, false),
+            Err(name) => {
+                errors.push(CompileError::UnknownIrPass {
+                    name: name.to_string(),
+                    valid_passes: pass_mgr
+                        .pass_names()
+                        .into_iter()
+                        .map(str::to_string)
+                        .collect(),
+                    span: span::Span::dummy(),
+                });
+                return err(warnings, errors);
+            }
+        },
+        None => (
+            create_opt_level_pass_group(build_config.optimization_level.into()),
+            build_config.optimization_level > OptLevel::O0,
+        ),
+    };
+
+    // Export the pure functions before the target specific transforms demote their arguments and
+    // return values to memory.
+    if build_config.print_smt {
+        pass_group.append_pass(SMTPRINTER_NAME);
+    }
 
     // Target specific transforms should be moved into something more configured.
+    let can_return_from_calls = backend.map_or(true, |(backend, vm_version)| {
+        backend.can_return_from_calls(vm_version)
+    });
     if build_config.build_target == BuildTarget::Fuel {
         // FuelVM target specific transforms.
         //
+        // Move small heap allocations which don't escape their function, now that the allocator
+        // has been inlined, to the stack.
+        if optimize {
+            pass_group.append_pass(HEAP2STACK_NAME);
+        }
+
         // Demote large by-value constants, arguments and return values to by-reference values
         // using temporaries.
         pass_group.append_pass(CONSTDEMOTION_NAME);
@@ -622,11 +802,30 @@ pub(crate) fn compile_ast_to_ir_to_asm(
         pass_group.append_pass(RETDEMOTION_NAME);
         pass_group.append_pass(MISCDEMOTION_NAME);
 
-        // Convert loads and stores to mem_copys where possible.
-        pass_group.append_pass(MEMCPYOPT_NAME);
+        if optimize {
+            // Convert loads and stores to mem_copys where possible.
+            pass_group.append_pass(MEMCPYOPT_NAME);
+
+            // Run a DCE and simplify-cfg to clean up any obsolete instructions.
+            pass_group.append_pass(DCE_NAME);
+
+            // Merge the functions which have ended up identical, e.g. generic instantiations for
+            // types with the same layout, to reduce the size of the bytecode.
+            pass_group.append_pass(FN_DEDUP_NAME);
 
-        // Run a DCE and simplify-cfg to clean up any obsolete instructions.
-        pass_group.append_pass(DCE_NAME);
+            // Outline the instruction sequences repeated across what's left.
+            if can_return_from_calls {
+                pass_group.append_pass(OUTLINE_NAME);
+            }
+        }
+
+        // The VM revisions which can't return from calls have every call inlined instead, and the
+        // functions left uncalled removed. Only recursive calls remain, which codegen reports.
+        if !can_return_from_calls {
+            ir.inline_params.inline_all = true;
+            pass_group.append_pass(INLINE_MODULE_NAME);
+            pass_group.append_pass(FUNC_DCE_NAME);
+        }
         // XXX Oh no, if we add simplifycfg here it unearths a bug in the register allocator which
         // manifests in the `should_pass/language/while_loops` test.  Fixing the register allocator
         // is a very high priority but isn't a part of this change.
@@ -639,7 +838,7 @@ pub(crate) fn compile_ast_to_ir_to_asm(
 
     // Run the passes.
     let res = CompileResult::with_handler(|handler| {
-        if let Err(ir_error) = pass_mgr.run(&mut ir, &pass_group) {
+        if let Err(ir_error) = pass_mgr.run(ir, &pass_group) {
             Err(handler.emit_err(CompileError::InternalOwned(
                 ir_error.to_string(),
                 span::Span::dummy(),
@@ -648,10 +847,19 @@ pub(crate) fn compile_ast_to_ir_to_asm(
             Ok(())
         }
     });
+    if build_config.metrics_outfile.is_some() {
+        metrics
+            .passes
+            .extend(pass_mgr.timings().iter().map(|timing| PassMetric {
+                pass: timing.name.to_string(),
+                elapsed: timing.elapsed.as_secs_f64(),
+                runs: timing.runs,
+            }));
+    }
     check!(res, return err(warnings, errors), warnings, errors);
 
     let final_asm = check!(
-        compile_ir_to_asm(&ir, Some(build_config)),
+        compile_ir_to_asm(ir, Some(build_config)),
         return err(warnings, errors),
         warnings,
         errors
@@ -681,6 +889,33 @@ pub fn compile_to_bytecode(
     asm_to_bytecode(asm_res, source_map, engines.se())
 }
 
+/// Given the textual IR of a program, e.g. as printed by `--print-ir`, optimize and compile it to
+/// [CompiledBytecode] in the same way as the IR generated from Sway source.
+pub fn compile_ir_to_bytecode(
+    engines: &Engines,
+    ir_source: &str,
+    build_config: &BuildConfig,
+    source_map: &mut SourceMap,
+    metrics: &mut PerformanceData,
+) -> CompileResult<CompiledBytecode> {
+    let mut ir = match sway_ir::parse(ir_source, engines.se()) {
+        Ok(ir) => ir,
+        Err(ir_error) => {
+            return err(
+                vec![],
+                vec![CompileError::InvalidIr {
+                    error: ir_error.to_string(),
+                    span: span::Span::dummy(),
+                }],
+            )
+        }
+    };
+    ir.inline_params = build_config.inlining.into();
+    ir.cse_storage_reads = build_config.cse_storage_reads;
+    let asm_res = optimize_ir_to_asm(&mut ir, build_config, metrics).map(CompiledAsm);
+    asm_to_bytecode(asm_res, source_map, engines.se())
+}
+
 /// Given the assembly (opcodes), compile to [CompiledBytecode], containing the asm in bytecode form.
 pub fn asm_to_bytecode(
     CompileResult {
@@ -1002,3 +1237,362 @@ fn dedup_unsorted<T: PartialEq + std::hash::Hash>(mut data: Vec<T>) -> Vec<T> {
     data.truncate(write_index);
     data
 }
+
+#[test]
+fn test_lint_levels() {
+    use sway_error::warning::Warning;
+
+    let warnings = || {
+        vec![
+            CompileWarning {
+                span: span::Span::dummy(),
+                warning_content: Warning::DeadDeclaration,
+            },
+            CompileWarning {
+                span: span::Span::dummy(),
+                warning_content: Warning::UnusedVariable {
+                    variable_name: Ident::new_no_span("x".to_string()),
+                },
+            },
+            CompileWarning {
+                span: span::Span::dummy(),
+                warning_content: Warning::NumericFallback {
+                    name: Ident::new_no_span("y".to_string()),
+                },
+            },
+        ]
+    };
+    let lints = |build_config: &BuildConfig| {
+        let mut errors = vec![];
+        let warnings = apply_lint_levels(Some(build_config), warnings(), &mut errors);
+        let warned = warnings
+            .iter()
+            .map(|warning| warning.warning_content.lint_name().to_string())
+            .collect::<Vec<_>>();
+        let denied = errors
+            .iter()
+            .map(|error| match error {
+                CompileError::DeniedWarning { lint, .. } => lint.clone(),
+                error => panic!("unexpected error: {error}"),
+            })
+            .collect::<Vec<_>>();
+        (warned, denied)
+    };
+    let build_config = BuildConfig::root_from_in_memory_sources(
+        PathBuf::from("main.sw"),
+        HashMap::new(),
+        BuildTarget::Fuel,
+    );
+
+    // Everything but the lints allowed by default is warned about by default.
+    assert_eq!(
+        lints(&build_config),
+        (vec!["dead_code".into(), "unused_variables".into()], vec![])
+    );
+
+    // The explicit levels apply to their lints only.
+    let allowed = build_config
+        .clone()
+        .lint_levels([("dead_code".to_string(), LintLevel::Allow)]);
+    assert_eq!(lints(&allowed), (vec!["unused_variables".into()], vec![]));
+    let denied = build_config
+        .clone()
+        .lint_levels([("dead_code".to_string(), LintLevel::Deny)]);
+    assert_eq!(
+        lints(&denied),
+        (vec!["unused_variables".into()], vec!["dead_code".into()])
+    );
+
+    // Denying warnings denies the lints without an explicit level, the explicit levels win.
+    let deny_warnings = build_config.clone().deny_warnings(true);
+    assert_eq!(
+        lints(&deny_warnings),
+        (vec![], vec!["dead_code".into(), "unused_variables".into()])
+    );
+    let warned = deny_warnings
+        .clone()
+        .lint_levels([("unused_variables".to_string(), LintLevel::Warn)]);
+    assert_eq!(
+        lints(&warned),
+        (vec!["unused_variables".into()], vec!["dead_code".into()])
+    );
+    let allowed = deny_warnings
+        .clone()
+        .lint_levels([("dead_code".to_string(), LintLevel::Allow)]);
+    assert_eq!(lints(&allowed), (vec![], vec!["unused_variables".into()]));
+
+    // The lints allowed by default are only reported at an explicit level.
+    let warned = deny_warnings.lint_levels([("numeric_fallback".to_string(), LintLevel::Warn)]);
+    assert_eq!(
+        lints(&warned),
+        (
+            vec!["numeric_fallback".into()],
+            vec!["dead_code".into(), "unused_variables".into()]
+        )
+    );
+}
+
+#[test]
+fn test_ir_passes() {
+    let source: Arc<str> = Arc::from("script; fn one() -> u64 { 1 } fn main() -> u64 { one() }");
+    let build_config = BuildConfig::root_from_in_memory_sources(
+        PathBuf::from("src/main.sw"),
+        HashMap::from([(PathBuf::from("src/main.sw"), source.clone())]),
+        BuildTarget::Fuel,
+    );
+    let compile = |build_config: BuildConfig| {
+        compile_to_asm(
+            &Engines::default(),
+            source.clone(),
+            namespace::Module::default(),
+            build_config,
+            "test",
+            &mut PerformanceData::default(),
+        )
+    };
+
+    for level in [OptLevel::O0, OptLevel::O1, OptLevel::O2] {
+        let res = compile(build_config.clone().optimization_level(level));
+        assert!(res.errors.is_empty(), "{level:?}: {:?}", res.errors);
+    }
+
+    let named = build_config
+        .clone()
+        .ir_passes(Some(vec!["mem2reg".into(), "dce".into()]));
+    let res = compile(named);
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+    let unknown = build_config.ir_passes(Some(vec!["mem2reg".into(), "no_such_pass".into()]));
+    match compile(unknown).errors.as_slice() {
+        [CompileError::UnknownIrPass {
+            name, valid_passes, ..
+        }] => {
+            assert_eq!(name, "no_such_pass");
+            assert!(valid_passes.iter().any(|pass| pass == "mem2reg"));
+        }
+        errors => panic!("expected an unknown IR pass error, got {errors:?}"),
+    }
+}
+
+#[test]
+fn test_compile_ir_to_bytecode() {
+    let source: Arc<str> =
+        Arc::from("script; fn one() -> u64 { 1 } fn main() -> u64 { one() + 1 }");
+    let build_config = BuildConfig::root_from_in_memory_sources(
+        PathBuf::from("src/main.sw"),
+        HashMap::from([(PathBuf::from("src/main.sw"), source.clone())]),
+        BuildTarget::Fuel,
+    );
+    let engines = Engines::default();
+    let from_source = compile_to_bytecode(
+        &engines,
+        source.clone(),
+        namespace::Module::default(),
+        build_config.clone(),
+        &mut SourceMap::new(),
+        "test",
+        &mut PerformanceData::default(),
+    );
+    assert!(from_source.errors.is_empty(), "{:?}", from_source.errors);
+
+    // The IR printed before optimizing compiles to the same bytecode as the source.
+    let ast_res = compile_to_ast(
+        &engines,
+        source,
+        namespace::Module::default(),
+        Some(&build_config),
+        "test",
+        &mut PerformanceData::default(),
+    );
+    let typed_program = ast_res.value.and_then(|programs| programs.typed).unwrap();
+    let ir = ir_generation::compile_program(
+        &typed_program,
+        false,
+        false,
+        build_config.overflow,
+        &engines,
+    )
+    .unwrap();
+    let from_ir = compile_ir_to_bytecode(
+        &engines,
+        &sway_ir::printer::to_string(&ir),
+        &build_config,
+        &mut SourceMap::new(),
+        &mut PerformanceData::default(),
+    );
+    assert!(from_ir.errors.is_empty(), "{:?}", from_ir.errors);
+    assert_eq!(
+        from_ir.value.unwrap().bytecode,
+        from_source.value.unwrap().bytecode
+    );
+
+    let invalid = compile_ir_to_bytecode(
+        &engines,
+        "script { fn main() -> u64 {",
+        &build_config,
+        &mut SourceMap::new(),
+        &mut PerformanceData::default(),
+    );
+    assert!(matches!(
+        invalid.errors.as_slice(),
+        [CompileError::InvalidIr { .. }]
+    ));
+}
+
+#[test]
+fn test_storage_layout_of_nested_structs() {
+    let source: Arc<str> = Arc::from(
+        r#"
+        contract;
+
+        struct Inner {
+            a: u64,
+            b: b256,
+        }
+
+        struct Outer {
+            flag: bool,
+            inner: Inner,
+            c: u64,
+        }
+
+        storage {
+            counter: u64 = 0,
+            outer: Outer = Outer {
+                flag: false,
+                inner: Inner {
+                    a: 0,
+                    b: 0x0000000000000000000000000000000000000000000000000000000000000000,
+                },
+                c: 0,
+            },
+        }
+        "#,
+    );
+    let build_config = BuildConfig::root_from_in_memory_sources(
+        PathBuf::from("src/main.sw"),
+        HashMap::from([(PathBuf::from("src/main.sw"), source.clone())]),
+        BuildTarget::Fuel,
+    );
+    let engines = Engines::default();
+    let ast_res = compile_to_ast(
+        &engines,
+        source,
+        namespace::Module::default(),
+        Some(&build_config),
+        "test",
+        &mut PerformanceData::default(),
+    );
+    assert!(ast_res.errors.is_empty(), "{:?}", ast_res.errors);
+    let typed_program = ast_res.value.and_then(|programs| programs.typed).unwrap();
+
+    let [counter, outer] = typed_program.storage_layout.as_slice() else {
+        panic!(
+            "expected two storage fields, found {:?}",
+            typed_program.storage_layout
+        );
+    };
+    assert_eq!((counter.name.as_str(), counter.offset), ("counter", 0));
+    assert!(counter.fields.is_empty());
+    assert_eq!((outer.name.as_str(), outer.offset), ("outer", 0));
+    let field_names: Vec<_> = outer
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    assert_eq!(field_names, ["flag", "inner", "c"]);
+
+    // The 56 bytes of `Outer` are packed into two slots: `flag` and `inner.a` are the first 16
+    // bytes of the first, `inner.b` spans both of them and `c` follows it in the second.
+    let [flag, inner, c] = outer.fields.as_slice() else {
+        unreachable!()
+    };
+    assert_eq!((flag.key, flag.offset), (outer.key, 0));
+    assert_eq!((inner.key, inner.offset), (outer.key, 8));
+    let [a, b] = inner.fields.as_slice() else {
+        panic!("expected two fields of `inner`, found {:?}", inner.fields);
+    };
+    assert_eq!((a.name.as_str(), a.key, a.offset), ("a", outer.key, 8));
+    assert_eq!((b.name.as_str(), b.key, b.offset), ("b", outer.key, 16));
+    assert_ne!(c.key, outer.key);
+    assert_eq!(c.offset, 16);
+
+    // The second slot of `outer` is the one that `c` is in.
+    let slot_keys: std::collections::HashSet<_> = typed_program
+        .storage_slots
+        .iter()
+        .map(|slot| *slot.key())
+        .collect();
+    assert_eq!(
+        slot_keys,
+        std::collections::HashSet::from([counter.key, outer.key, c.key])
+    );
+}
+
+#[test]
+fn test_function_sizes() {
+    let source: Arc<str> = Arc::from(
+        "script; fn one() -> u64 { 1 } fn two() -> u64 { 2 } fn main() -> u64 { let _one = one(); two() }",
+    );
+    let build_config = BuildConfig::root_from_in_memory_sources(
+        PathBuf::from("src/main.sw"),
+        HashMap::from([(PathBuf::from("src/main.sw"), source.clone())]),
+        BuildTarget::Fuel,
+    )
+    .optimization_level(OptLevel::O0);
+    let res = compile_to_asm(
+        &Engines::default(),
+        source,
+        namespace::Module::default(),
+        build_config,
+        "test",
+        &mut PerformanceData::default(),
+    );
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    let CompiledAsm(asm) = res.value.unwrap();
+    let asm_generation::InstructionSet::Fuel { ops } = &asm.program_section else {
+        panic!("expected Fuel instructions");
+    };
+    let instruction_bytes = ops
+        .iter()
+        .map(|op| op.size_in_bytes(&asm.data_section))
+        .sum::<u64>();
+
+    let mut names: Vec<_> = asm
+        .function_sizes
+        .iter()
+        .map(|size| size.name.as_str())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["main", "one", "two"]);
+    assert!(asm
+        .function_sizes
+        .windows(2)
+        .all(|sizes| sizes[0].bytes >= sizes[1].bytes));
+    for size in &asm.function_sizes {
+        assert_eq!(size.bytes, size.instructions * 4, "{size:?}");
+        let function = asm
+            .debug_info
+            .functions
+            .iter()
+            .find(|function| function.name == size.name)
+            .unwrap();
+        assert_eq!(size.bytes, function.end - function.start, "{size:?}");
+    }
+
+    // The functions take up all of the instructions but the prologue, which precedes them.
+    let prologue_bytes = asm
+        .debug_info
+        .functions
+        .iter()
+        .map(|function| function.start)
+        .min()
+        .unwrap();
+    let function_bytes = asm
+        .function_sizes
+        .iter()
+        .map(|size| size.bytes)
+        .sum::<u64>();
+    assert!(prologue_bytes > 0);
+    assert_eq!(prologue_bytes + function_bytes, instruction_bytes);
+}
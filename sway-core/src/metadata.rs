@@ -6,7 +6,7 @@ use crate::{
 use sway_ir::{Context, MetadataIndex, Metadatum, Value};
 use sway_types::{SourceId, Span};
 
-use std::{collections::HashMap, path::PathBuf, rc::Rc, sync::Arc};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
 
 /// IR metadata needs to be consistent between IR generation (converting Spans, etc. to metadata)
 /// and ASM generation (converting the metadata back again).  Here we consolidate all of
@@ -19,7 +19,7 @@ use std::{collections::HashMap, path::PathBuf, rc::Rc, sync::Arc};
 #[derive(Default)]
 pub(crate) struct MetadataManager {
     md_span_cache: HashMap<MetadataIndex, Span>,
-    md_file_loc_cache: HashMap<MetadataIndex, (Arc<PathBuf>, Arc<str>)>,
+    md_file_loc_cache: HashMap<MetadataIndex, Span>,
     md_storage_op_cache: HashMap<MetadataIndex, StorageOperation>,
     md_inline_cache: HashMap<MetadataIndex, Inline>,
     md_test_decl_index_cache: HashMap<MetadataIndex, DeclId<TyFunctionDecl>>,
@@ -53,12 +53,10 @@ impl MetadataManager {
                     .get_content(context)
                     .unwrap_struct("span", 3)
                     .and_then(|fields| {
-                        let (path, src) = self.md_to_file_location(context, &fields[0])?;
+                        let file_span = self.md_to_file_location(context, &fields[0])?;
                         let start = fields[1].unwrap_integer()?;
                         let end = fields[2].unwrap_integer()?;
-                        let source_engine = context.source_engine();
-                        let source_id = source_engine.get_source_id(&path);
-                        let span = Span::new(src, start as usize, end as usize, Some(source_id))?;
+                        let span = file_span.with_range(start as usize, end as usize)?;
 
                         self.md_span_cache.insert(md_idx, span.clone());
 
@@ -68,6 +66,26 @@ impl MetadataManager {
         })
     }
 
+    /// The spans of the call sites a value was inlined at, innermost first.
+    pub(crate) fn md_to_inlined_at(
+        &mut self,
+        context: &Context,
+        md_idx: Option<MetadataIndex>,
+    ) -> Vec<Span> {
+        let mut call_sites = Vec::new();
+        let mut md_idx = md_idx;
+        while let Some(call_md_idx) = Self::for_each_md_idx(context, md_idx, |md_idx| {
+            md_idx
+                .get_content(context)
+                .unwrap_struct("inlined_at", 1)
+                .and_then(|fields| fields[0].unwrap_index())
+        }) {
+            call_sites.extend(self.md_to_span(context, Some(call_md_idx)));
+            md_idx = Some(call_md_idx);
+        }
+        call_sites
+    }
+
     pub(crate) fn md_to_test_decl_index(
         &mut self,
         context: &Context,
@@ -156,25 +174,30 @@ impl MetadataManager {
         })
     }
 
-    fn md_to_file_location(
-        &mut self,
-        context: &Context,
-        md: &Metadatum,
-    ) -> Option<(Arc<PathBuf>, Arc<str>)> {
+    /// An empty span at the start of the file the metadatum locates, which the spans in that file
+    /// are created from so that the file's text is only looked up once.
+    fn md_to_file_location(&mut self, context: &Context, md: &Metadatum) -> Option<Span> {
         md.unwrap_index().and_then(|md_idx| {
             self.md_file_loc_cache.get(&md_idx).cloned().or_else(|| {
-                // Create a new file location (path and src) and save it in the cache.
+                // Create a new file location and save it in the cache.
                 md_idx
                     .get_content(context)
                     .unwrap_source_id()
                     .and_then(|source_id| {
-                        let path_buf = context.source_engine.get_path(source_id);
-                        let src = std::fs::read_to_string(&path_buf).ok()?;
-                        let path_and_src = (Arc::new(path_buf), Arc::from(src));
-
-                        self.md_file_loc_cache.insert(md_idx, path_and_src.clone());
-
-                        Some(path_and_src)
+                        // The text compiled is held by the source engine, unless it's no longer
+                        // pointed into, e.g. when the IR was parsed rather than generated.
+                        let file_span = match context.source_engine.get_source_text(source_id) {
+                            Some(file_span) => file_span,
+                            None => {
+                                let path_buf = context.source_engine.get_path(source_id);
+                                let src = std::fs::read_to_string(path_buf).ok()?;
+                                Span::new(Arc::from(src), 0, 0, Some(*source_id))?
+                            }
+                        };
+
+                        self.md_file_loc_cache.insert(md_idx, file_span.clone());
+
+                        Some(file_span)
                     })
             })
         })
@@ -208,6 +231,10 @@ impl MetadataManager {
         self.md_to_span(context, value.get_metadata(context))
     }
 
+    pub(crate) fn val_to_inlined_at(&mut self, context: &Context, value: Value) -> Vec<Span> {
+        self.md_to_inlined_at(context, value.get_metadata(context))
+    }
+
     pub(crate) fn span_to_md(
         &mut self,
         context: &mut Context,
@@ -1,12 +1,18 @@
 //! Type checking for Sway.
 pub mod ast_node;
+pub(crate) mod borrow_analysis;
 pub(crate) mod cei_pattern_analysis;
 pub(crate) mod coins_analysis;
 mod module;
 pub mod namespace;
 mod node_dependencies;
+pub(crate) mod numeric_fallback;
 mod program;
+pub mod program_graphs;
+pub(crate) mod storage_effects;
 mod type_check_context;
+pub(crate) mod unused_import_analysis;
 pub use ast_node::*;
 pub use namespace::Namespace;
+pub(crate) use node_dependencies::refers_to_type;
 pub(crate) use type_check_context::TypeCheckContext;
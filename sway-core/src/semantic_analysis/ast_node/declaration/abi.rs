@@ -157,6 +157,7 @@ impl ty::TyAbiDecl {
                 errors.push(CompileError::MultipleDefinitionsOfName {
                     name: decl_name.clone(),
                     span: decl_name.span(),
+                    previous_span: ids.get(&decl_name).map(|previous| previous.span()),
                 })
             }
         }
@@ -183,6 +184,7 @@ impl ty::TyAbiDecl {
                 errors.push(CompileError::MultipleDefinitionsOfName {
                     name: method.name.clone(),
                     span: method.name.span(),
+                    previous_span: ids.get(&method.name).map(|previous| previous.span()),
                 })
             }
             new_items.push(TyTraitItem::Fn(ctx.engines.de().insert(method)));
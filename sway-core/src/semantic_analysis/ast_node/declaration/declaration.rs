@@ -1,10 +1,11 @@
+use sway_error::error::ErrorContext;
 use sway_types::{Named, Spanned};
 
 use crate::{
-    decl_engine::{DeclEngineInsert, DeclRef, ReplaceFunctionImplementingType},
+    decl_engine::{DeclEngineInsert, DeclEngineReplace, DeclRef, ReplaceFunctionImplementingType},
     error::*,
-    language::{parsed, ty},
-    semantic_analysis::TypeCheckContext,
+    language::{parsed, ty, CallPath},
+    semantic_analysis::{refers_to_type, TypeCheckContext},
     type_system::*,
     CompileResult,
 };
@@ -78,8 +79,10 @@ impl ty::TyDecl {
             }
             parsed::Declaration::ConstantDeclaration(decl) => {
                 let span = decl.span.clone();
+                let context =
+                    ErrorContext::new("type checking", format!("constant `{}`", decl.name));
                 let const_decl = check!(
-                    ty::TyConstantDecl::type_check(ctx.by_ref(), decl),
+                    ty::TyConstantDecl::type_check(ctx.by_ref(), decl).with_context(|| context),
                     return ok(ty::TyDecl::ErrorRecovery(span), warnings, errors),
                     warnings,
                     errors
@@ -95,12 +98,41 @@ impl ty::TyDecl {
             }
             parsed::Declaration::EnumDeclaration(decl) => {
                 let span = decl.span.clone();
+                let context = ErrorContext::new("type checking", format!("enum `{}`", decl.name));
+                // An enum referring to itself through a `Box` is declared before its variants are
+                // type checked, so that they find it, and completed after.
+                let forward_decl = if decl.type_parameters.is_empty()
+                    && decl.variants.iter().any(|variant| {
+                        refers_to_type(engines, &decl.name, &variant.type_argument, None)
+                    }) {
+                    let forward_decl = decl_engine.insert(ty::TyEnumDecl {
+                        call_path: CallPath::from(decl.name.clone()).to_fullpath(ctx.namespace),
+                        type_parameters: vec![],
+                        variants: vec![],
+                        span: decl.span.clone(),
+                        attributes: decl.attributes.clone(),
+                        visibility: decl.visibility,
+                    });
+                    check!(
+                        ctx.insert_symbol(decl.name.clone(), forward_decl.clone().into()),
+                        return err(warnings, errors),
+                        warnings,
+                        errors
+                    );
+                    Some(forward_decl)
+                } else {
+                    None
+                };
                 let enum_decl = check!(
-                    ty::TyEnumDecl::type_check(ctx.by_ref(), decl),
+                    ty::TyEnumDecl::type_check(ctx.by_ref(), decl).with_context(|| context),
                     return ok(ty::TyDecl::ErrorRecovery(span), warnings, errors),
                     warnings,
                     errors
                 );
+                if let Some(forward_decl) = forward_decl {
+                    decl_engine.replace(*forward_decl.id(), enum_decl);
+                    return ok(forward_decl.into(), warnings, errors);
+                }
                 let call_path = enum_decl.call_path.clone();
                 let decl: ty::TyDecl = decl_engine.insert(enum_decl).into();
                 check!(
@@ -128,8 +160,10 @@ impl ty::TyDecl {
             }
             parsed::Declaration::TraitDeclaration(trait_decl) => {
                 let span = trait_decl.span.clone();
+                let context =
+                    ErrorContext::new("type checking", format!("trait `{}`", trait_decl.name));
                 let mut trait_decl = check!(
-                    ty::TyTraitDecl::type_check(ctx.by_ref(), trait_decl),
+                    ty::TyTraitDecl::type_check(ctx.by_ref(), trait_decl).with_context(|| context),
                     return ok(ty::TyDecl::ErrorRecovery(span), warnings, errors),
                     warnings,
                     errors
@@ -174,8 +208,17 @@ impl ty::TyDecl {
             }
             parsed::Declaration::ImplTrait(impl_trait) => {
                 let span = impl_trait.block_span.clone();
+                let context = ErrorContext::new(
+                    "type checking",
+                    format!(
+                        "impl of `{}` for `{}`",
+                        impl_trait.trait_name,
+                        impl_trait.implementing_for.span.as_str()
+                    ),
+                );
                 let mut impl_trait = check!(
-                    ty::TyImplTrait::type_check_impl_trait(ctx.by_ref(), impl_trait),
+                    ty::TyImplTrait::type_check_impl_trait(ctx.by_ref(), impl_trait)
+                        .with_context(|| context),
                     return ok(ty::TyDecl::ErrorRecovery(span), warnings, errors),
                     warnings,
                     errors
@@ -222,8 +265,13 @@ impl ty::TyDecl {
             }
             parsed::Declaration::ImplSelf(impl_self) => {
                 let span = impl_self.block_span.clone();
+                let context = ErrorContext::new(
+                    "type checking",
+                    format!("impl for `{}`", impl_self.implementing_for.span.as_str()),
+                );
                 let mut impl_trait = check!(
-                    ty::TyImplTrait::type_check_impl_self(ctx.by_ref(), impl_self),
+                    ty::TyImplTrait::type_check_impl_self(ctx.by_ref(), impl_self)
+                        .with_context(|| context),
                     return ok(ty::TyDecl::ErrorRecovery(span), warnings, errors),
                     warnings,
                     errors
@@ -254,12 +302,40 @@ impl ty::TyDecl {
             }
             parsed::Declaration::StructDeclaration(decl) => {
                 let span = decl.span.clone();
+                let context = ErrorContext::new("type checking", format!("struct `{}`", decl.name));
+                // like enums, a struct referring to itself is declared before its fields
+                let forward_decl = if decl.type_parameters.is_empty()
+                    && decl.fields.iter().any(|field| {
+                        refers_to_type(engines, &decl.name, &field.type_argument, None)
+                    }) {
+                    let forward_decl = decl_engine.insert(ty::TyStructDecl {
+                        call_path: CallPath::from(decl.name.clone()).to_fullpath(ctx.namespace),
+                        type_parameters: vec![],
+                        fields: vec![],
+                        visibility: decl.visibility,
+                        span: decl.span.clone(),
+                        attributes: decl.attributes.clone(),
+                    });
+                    check!(
+                        ctx.insert_symbol(decl.name.clone(), forward_decl.clone().into()),
+                        return err(warnings, errors),
+                        warnings,
+                        errors
+                    );
+                    Some(forward_decl)
+                } else {
+                    None
+                };
                 let decl = check!(
-                    ty::TyStructDecl::type_check(ctx.by_ref(), decl),
+                    ty::TyStructDecl::type_check(ctx.by_ref(), decl).with_context(|| context),
                     return ok(ty::TyDecl::ErrorRecovery(span), warnings, errors),
                     warnings,
                     errors
                 );
+                if let Some(forward_decl) = forward_decl {
+                    decl_engine.replace(*forward_decl.id(), decl);
+                    return ok(forward_decl.into(), warnings, errors);
+                }
                 let call_path = decl.call_path.clone();
                 let decl: ty::TyDecl = decl_engine.insert(decl).into();
                 // insert the struct decl into namespace
@@ -273,8 +349,10 @@ impl ty::TyDecl {
             }
             parsed::Declaration::AbiDeclaration(abi_decl) => {
                 let span = abi_decl.span.clone();
+                let context =
+                    ErrorContext::new("type checking", format!("abi `{}`", abi_decl.name));
                 let mut abi_decl = check!(
-                    ty::TyAbiDecl::type_check(ctx.by_ref(), abi_decl),
+                    ty::TyAbiDecl::type_check(ctx.by_ref(), abi_decl).with_context(|| context),
                     return ok(ty::TyDecl::ErrorRecovery(span), warnings, errors),
                     warnings,
                     errors
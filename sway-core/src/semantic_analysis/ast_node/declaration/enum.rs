@@ -81,6 +81,16 @@ impl ty::TyEnumVariant {
             warnings,
             errors,
         );
+        // the bounds of a generic type the variant holds, e.g. `Pair<T>` where `T: Eq`, must be
+        // satisfied by its type arguments
+        check!(
+            type_argument
+                .type_id
+                .check_type_parameter_bounds(&ctx, &type_argument.span, vec![]),
+            (),
+            warnings,
+            errors
+        );
         ok(
             ty::TyEnumVariant {
                 name: variant.name.clone(),
@@ -89,7 +99,7 @@ impl ty::TyEnumVariant {
                 span: variant.span,
                 attributes: variant.attributes,
             },
-            vec![],
+            warnings,
             errors,
         )
     }
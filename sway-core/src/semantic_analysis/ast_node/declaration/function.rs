@@ -2,20 +2,32 @@ mod function_parameter;
 
 pub use function_parameter::*;
 use sway_error::{
-    error::CompileError,
+    error::{CompileError, ErrorContext},
     warning::{CompileWarning, Warning},
 };
 
 use crate::{
+    decl_engine::DeclEngineInsert,
     error::*,
     language::{parsed::*, ty, Visibility},
     semantic_analysis::*,
     type_system::*,
 };
-use sway_types::{style::is_snake_case, Spanned};
+use sway_types::{constants::ENSURES_RESULT_NAME, style::is_snake_case, Ident, Spanned};
 
 impl ty::TyFunctionDecl {
     pub fn type_check(
+        ctx: TypeCheckContext,
+        fn_decl: FunctionDeclaration,
+        is_method: bool,
+        is_in_impl_self: bool,
+    ) -> CompileResult<Self> {
+        let name = fn_decl.name.clone();
+        Self::type_check_function(ctx, fn_decl, is_method, is_in_impl_self)
+            .with_context(|| ErrorContext::new("type checking", format!("function `{name}`")))
+    }
+
+    fn type_check_function(
         mut ctx: TypeCheckContext,
         fn_decl: FunctionDeclaration,
         is_method: bool,
@@ -35,9 +47,12 @@ impl ty::TyFunctionDecl {
             visibility,
             purity,
             where_clause,
+            requires,
+            ensures,
         } = fn_decl;
 
         let type_engine = ctx.engines.te();
+        let decl_engine = ctx.engines.de();
         let engines = ctx.engines();
 
         // If functions aren't allowed in this location, return an error.
@@ -102,6 +117,42 @@ impl ty::TyFunctionDecl {
             errors,
         );
 
+        // Free functions which aren't generic may call themselves. Make the signature visible to
+        // the body, unless a parameter shadows the function name.
+        let recursive_ref = if !is_method
+            && new_type_parameters.is_empty()
+            && !new_parameters.iter().any(|param| param.name == name)
+        {
+            let recursive_ref = decl_engine.insert(ty::TyFunctionDecl {
+                name: name.clone(),
+                body: ty::TyCodeBlock { contents: vec![] },
+                parameters: new_parameters.clone(),
+                implementing_type: None,
+                span: span.clone(),
+                attributes: attributes.clone(),
+                return_type: return_type.clone(),
+                type_parameters: vec![],
+                visibility,
+                is_contract_call: false,
+                purity,
+                where_clause: where_clause.clone(),
+                is_recursive_ref: true,
+                recursive_ref: None,
+                requires: vec![],
+                ensures: vec![],
+            });
+            let id = *recursive_ref.id();
+            check!(
+                ctx.insert_symbol(name.clone(), recursive_ref.into()),
+                return err(warnings, errors),
+                warnings,
+                errors
+            );
+            Some(id)
+        } else {
+            None
+        };
+
         // type check the function body
         //
         // If there are no implicit block returns, then we do not want to type check them, so we
@@ -111,7 +162,8 @@ impl ty::TyFunctionDecl {
                 .by_ref()
                 .with_purity(purity)
                 .with_help_text("Function body's return type does not match up with its return type annotation.")
-                .with_type_annotation(return_type.type_id);
+                .with_type_annotation(return_type.type_id)
+                .with_return_type_annotation(return_type.type_id);
             check!(
                 ty::TyCodeBlock::type_check(ctx, body),
                 (
@@ -137,6 +189,45 @@ impl ty::TyFunctionDecl {
             errors
         );
 
+        // type check the specification, in which `result` is the value returned by the function
+        let requires = check!(
+            type_check_specification(ctx.by_ref(), requires),
+            vec![],
+            warnings,
+            errors
+        );
+        let ensures = if ensures.is_empty() {
+            vec![]
+        } else {
+            let mut ensures_namespace = ctx.namespace.clone();
+            let mut ctx = ctx.by_ref().scoped(&mut ensures_namespace);
+            let result =
+                Ident::new_with_override(ENSURES_RESULT_NAME.to_string(), return_type.span.clone());
+            let result_decl = ty::TyDecl::VariableDecl(Box::new(ty::TyVariableDecl {
+                name: result.clone(),
+                body: ty::TyExpression {
+                    expression: ty::TyExpressionVariant::FunctionParameter,
+                    return_type: return_type.type_id,
+                    span: return_type.span.clone(),
+                },
+                mutability: ty::VariableMutability::Immutable,
+                return_type: return_type.type_id,
+                type_ascription: return_type.clone(),
+            }));
+            check!(
+                ctx.insert_symbol(result, result_decl),
+                return err(warnings, errors),
+                warnings,
+                errors
+            );
+            check!(
+                type_check_specification(ctx, ensures),
+                vec![],
+                warnings,
+                errors
+            )
+        };
+
         let (visibility, is_contract_call) = if is_method {
             if is_in_impl_self {
                 (visibility, false)
@@ -169,12 +260,49 @@ impl ty::TyFunctionDecl {
             is_contract_call,
             purity,
             where_clause,
+            is_recursive_ref: false,
+            recursive_ref,
+            requires,
+            ensures,
         };
 
         ok(function_decl, warnings, errors)
     }
 }
 
+/// Type checks the conditions of the specification attributes of a function declaration, which
+/// are boolean expressions.
+fn type_check_specification(
+    mut ctx: TypeCheckContext,
+    conditions: Vec<Expression>,
+) -> CompileResult<Vec<ty::TyExpression>> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    let type_engine = ctx.engines.te();
+    let engines = ctx.engines();
+
+    let mut typed_conditions = vec![];
+    for condition in conditions {
+        let ctx = ctx
+            .by_ref()
+            .with_help_text("The condition of a specification attribute must be a boolean.")
+            .with_type_annotation(type_engine.insert(engines, TypeInfo::Boolean));
+        typed_conditions.push(check!(
+            ty::TyExpression::type_check(ctx, condition),
+            continue,
+            warnings,
+            errors
+        ));
+    }
+
+    if errors.is_empty() {
+        ok(typed_conditions, warnings, errors)
+    } else {
+        err(warnings, errors)
+    }
+}
+
 /// Unifies the types of the return statements and the return type of the
 /// function declaration.
 fn unify_return_statements(
@@ -231,6 +359,10 @@ fn test_function_selector_behavior() {
         visibility: Visibility::Public,
         is_contract_call: false,
         where_clause: vec![],
+        is_recursive_ref: false,
+        recursive_ref: None,
+        requires: vec![],
+        ensures: vec![],
     };
 
     let selector_text = match decl.to_selector_name(&engines).value {
@@ -255,6 +387,7 @@ fn test_function_selector_behavior() {
                     .te()
                     .insert(&engines, TypeInfo::Str(Length::new(5, Span::dummy())))
                     .into(),
+                validation: None,
             },
             ty::TyFunctionParameter {
                 name: Ident::new_no_span("baz".into()),
@@ -271,6 +404,7 @@ fn test_function_selector_behavior() {
                     span: Span::dummy(),
                     call_path_tree: None,
                 },
+                validation: None,
             },
         ],
         span: Span::dummy(),
@@ -280,6 +414,10 @@ fn test_function_selector_behavior() {
         visibility: Visibility::Public,
         is_contract_call: false,
         where_clause: vec![],
+        is_recursive_ref: false,
+        recursive_ref: None,
+        requires: vec![],
+        ensures: vec![],
     };
 
     let selector_text = match decl.to_selector_name(&engines).value {
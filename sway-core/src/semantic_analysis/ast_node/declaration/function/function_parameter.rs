@@ -7,7 +7,7 @@ use crate::{
 };
 
 use sway_error::error::CompileError;
-use sway_types::Spanned;
+use sway_types::{integer_bits::IntegerBits, Spanned};
 
 impl ty::TyFunctionParameter {
     pub(crate) fn type_check(
@@ -26,6 +26,7 @@ impl ty::TyFunctionParameter {
             is_mutable,
             mutability_span,
             mut type_argument,
+            validation,
         } = parameter;
 
         type_argument.type_id = check!(
@@ -64,6 +65,7 @@ impl ty::TyFunctionParameter {
             is_mutable,
             mutability_span,
             type_argument,
+            validation,
         };
 
         insert_into_namespace(ctx, &typed_parameter);
@@ -87,6 +89,7 @@ impl ty::TyFunctionParameter {
             is_mutable,
             mutability_span,
             mut type_argument,
+            validation,
         } = parameter;
 
         type_argument.type_id = check!(
@@ -101,12 +104,43 @@ impl ty::TyFunctionParameter {
             errors,
         );
 
+        // only the arguments of unsigned integer parameters can be validated, by bounds which
+        // are in the range of their type
+        if let Some(validation) = &validation {
+            match type_engine.get_unaliased(type_argument.type_id) {
+                TypeInfo::UnsignedInteger(bits) => {
+                    let type_max = match bits {
+                        IntegerBits::Eight => u8::MAX as u64,
+                        IntegerBits::Sixteen => u16::MAX as u64,
+                        IntegerBits::ThirtyTwo => u32::MAX as u64,
+                        IntegerBits::SixtyFour => u64::MAX,
+                    };
+                    let bounds = validation.min.into_iter().chain(validation.max);
+                    for bound in bounds.filter(|bound| *bound > type_max) {
+                        errors.push(CompileError::ParamBoundOutOfRange {
+                            param_name: name.clone(),
+                            bound,
+                            ty: engines.help_out(type_argument.type_id).to_string(),
+                            span: validation.span.clone(),
+                        });
+                    }
+                }
+                TypeInfo::ErrorRecovery => (),
+                _ => errors.push(CompileError::ParamValidationNotSupportedForType {
+                    param_name: name.clone(),
+                    ty: engines.help_out(type_argument.type_id).to_string(),
+                    span: type_argument.span.clone(),
+                }),
+            }
+        }
+
         let typed_parameter = ty::TyFunctionParameter {
             name,
             is_reference,
             is_mutable,
             mutability_span,
             type_argument,
+            validation,
         };
 
         ok(typed_parameter, warnings, errors)
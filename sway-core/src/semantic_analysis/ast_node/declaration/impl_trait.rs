@@ -420,8 +420,9 @@ fn type_check_trait_implementation(
     check!(
         ctx.namespace
             .implemented_traits
-            .check_if_trait_constraints_are_satisfied_for_type(
+            .check_if_supertraits_are_satisfied_for_type(
                 self_type,
+                trait_name,
                 &trait_supertraits
                     .iter()
                     .map(|x| x.into())
@@ -733,6 +734,11 @@ fn type_check_impl_method(
         .iter_mut()
         .zip(&mut impl_method.parameters)
     {
+        // the implementation of an ABI method validates its arguments as its signature does
+        if is_contract {
+            impl_method_param.validation = impl_method_signature_param.validation.clone();
+        }
+
         // TODO use trait constraints as part of the type here to
         // implement trait constraint solver */
         // Check if we have a non-ref mutable argument. That's not allowed.
@@ -904,10 +910,11 @@ fn type_check_const_decl(
     let const_name = const_decl.call_path.suffix.clone();
 
     // Ensure that there aren't multiple definitions of this constant
-    if impld_constant_ids.contains_key(&const_name) {
+    if let Some((previous, _)) = impld_constant_ids.get_key_value(&const_name) {
         errors.push(CompileError::MultipleDefinitionsOfConstant {
             name: const_name.clone(),
             span: const_name.span(),
+            previous_span: Some(previous.span()),
         });
         return err(warnings, errors);
     }
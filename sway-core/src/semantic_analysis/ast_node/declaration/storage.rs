@@ -2,7 +2,8 @@ use crate::{
     error::*,
     fuel_prelude::fuel_tx::StorageSlot,
     ir_generation::{
-        const_eval::compile_constant_expression_to_constant, storage::serialize_to_storage_slots,
+        const_eval::compile_constant_expression_to_constant,
+        storage::{get_storage_key, get_struct_fields_layout, serialize_to_storage_slots},
     },
     language::ty,
     metadata::MetadataManager,
@@ -43,6 +44,32 @@ impl ty::TyStorageDecl {
             false => err(vec![], errors),
         }
     }
+
+    /// The name, type and storage key of each field, in declaration order, along with the
+    /// layout of the fields of those which are structs.
+    pub(crate) fn get_storage_layout(&self, engines: &Engines) -> Vec<ty::StorageFieldLayout> {
+        let mut context = Context::new(engines.se());
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let key = get_storage_key::<usize>(&StateIndex::new(i), &[]);
+                ty::StorageFieldLayout {
+                    name: f.name.as_str().to_string(),
+                    type_name: engines.help_out(f.type_argument.type_id).to_string(),
+                    key,
+                    offset: 0,
+                    fields: get_struct_fields_layout(
+                        engines,
+                        &mut context,
+                        f.type_argument.type_id,
+                        key,
+                        0,
+                    ),
+                }
+            })
+            .collect()
+    }
 }
 
 impl ty::TyStorageField {
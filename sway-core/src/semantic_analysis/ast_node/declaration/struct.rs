@@ -82,6 +82,16 @@ impl ty::TyStructField {
             warnings,
             errors,
         );
+        // the bounds of a generic type the field holds, e.g. `Pair<T>` where `T: Eq`, must be
+        // satisfied by its type arguments
+        check!(
+            type_argument
+                .type_id
+                .check_type_parameter_bounds(&ctx, &type_argument.span, vec![]),
+            (),
+            warnings,
+            errors
+        );
         let field = ty::TyStructField {
             name: field.name,
             span: field.span,
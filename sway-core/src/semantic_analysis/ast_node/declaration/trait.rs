@@ -137,6 +137,7 @@ impl ty::TyTraitDecl {
                 errors.push(CompileError::MultipleDefinitionsOfName {
                     name: decl_name.clone(),
                     span: decl_name.span(),
+                    previous_span: ids.get(&decl_name).map(|previous| previous.span()),
                 })
             }
         }
@@ -104,6 +104,10 @@ impl ty::TyTraitFn {
             type_parameters: vec![],
             is_contract_call: matches!(abi_mode, AbiMode::ImplAbiFn(..)),
             where_clause: vec![],
+            is_recursive_ref: false,
+            recursive_ref: None,
+            requires: vec![],
+            ensures: vec![],
         }
     }
 }
@@ -58,6 +58,12 @@ impl ty::TyIntrinsicFunctionKind {
             Intrinsic::Add
             | Intrinsic::Sub
             | Intrinsic::Mul
+            | Intrinsic::WrappingAdd
+            | Intrinsic::WrappingSub
+            | Intrinsic::WrappingMul
+            | Intrinsic::CheckedAdd
+            | Intrinsic::CheckedSub
+            | Intrinsic::CheckedMul
             | Intrinsic::Div
             | Intrinsic::And
             | Intrinsic::Or
@@ -72,6 +78,14 @@ impl ty::TyIntrinsicFunctionKind {
             }
             Intrinsic::Smo => type_check_smo(ctx, kind, arguments, type_arguments, span),
             Intrinsic::Not => type_check_not(ctx, kind, arguments, type_arguments, span),
+            Intrinsic::Gas => type_check_gas(ctx, kind, arguments, type_arguments, span),
+            Intrinsic::Realloc => type_check_realloc(ctx, kind, arguments, type_arguments, span),
+            Intrinsic::HeapStats => {
+                type_check_heap_stats(ctx, kind, arguments, type_arguments, span)
+            }
+            Intrinsic::Memcpy | Intrinsic::Memcmp => {
+                type_check_mem_op(ctx, kind, arguments, type_arguments, span)
+            }
         }
     }
 }
@@ -128,6 +142,255 @@ fn type_check_not(
     )
 }
 
+/// Signature: `__gas() -> u64`
+/// Description: Returns the remaining gas in the current context.
+///              This is a wrapper around reading FuelVM's `$cgas` register.
+/// Constraints: None.
+fn type_check_gas(
+    ctx: TypeCheckContext,
+    kind: sway_ast::Intrinsic,
+    arguments: Vec<Expression>,
+    type_arguments: Vec<TypeArgument>,
+    span: Span,
+) -> CompileResult<(ty::TyIntrinsicFunctionKind, TypeId)> {
+    let type_engine = ctx.engines.te();
+    let engines = ctx.engines();
+
+    let warnings = vec![];
+    let mut errors = vec![];
+
+    if !arguments.is_empty() {
+        errors.push(CompileError::IntrinsicIncorrectNumArgs {
+            name: kind.to_string(),
+            expected: 0,
+            span,
+        });
+        return err(warnings, errors);
+    }
+    if !type_arguments.is_empty() {
+        errors.push(CompileError::IntrinsicIncorrectNumTArgs {
+            name: kind.to_string(),
+            expected: 0,
+            span,
+        });
+        return err(warnings, errors);
+    }
+
+    let return_type =
+        type_engine.insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour));
+    ok(
+        (
+            ty::TyIntrinsicFunctionKind {
+                kind,
+                arguments: vec![],
+                type_arguments: vec![],
+                span,
+            },
+            return_type,
+        ),
+        warnings,
+        errors,
+    )
+}
+
+/// Signature: `__realloc(ptr: raw_ptr, size: u64, new_size: u64) -> raw_ptr`
+/// Description: Moves the `size` bytes at `ptr` into a new heap allocation of `new_size` bytes
+///              and returns a pointer to it. If `new_size` is not larger than `size`, nothing is
+///              allocated and `ptr` is returned.
+/// Constraints: None.
+fn type_check_realloc(
+    mut ctx: TypeCheckContext,
+    kind: sway_ast::Intrinsic,
+    arguments: Vec<Expression>,
+    type_arguments: Vec<TypeArgument>,
+    span: Span,
+) -> CompileResult<(ty::TyIntrinsicFunctionKind, TypeId)> {
+    let type_engine = ctx.engines.te();
+    let engines = ctx.engines();
+
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    if arguments.len() != 3 {
+        errors.push(CompileError::IntrinsicIncorrectNumArgs {
+            name: kind.to_string(),
+            expected: 3,
+            span,
+        });
+        return err(warnings, errors);
+    }
+    if !type_arguments.is_empty() {
+        errors.push(CompileError::IntrinsicIncorrectNumTArgs {
+            name: kind.to_string(),
+            expected: 0,
+            span,
+        });
+        return err(warnings, errors);
+    }
+
+    let ptr_type = type_engine.insert(engines, TypeInfo::RawUntypedPtr);
+    let u64_type = type_engine.insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour));
+    let mut typed_arguments = Vec::with_capacity(arguments.len());
+    for (argument, argument_type) in arguments.into_iter().zip([ptr_type, u64_type, u64_type]) {
+        let ctx = ctx
+            .by_ref()
+            .with_help_text("Incorrect argument type")
+            .with_type_annotation(argument_type);
+        typed_arguments.push(check!(
+            ty::TyExpression::type_check(ctx, argument),
+            return err(warnings, errors),
+            warnings,
+            errors
+        ));
+    }
+
+    ok(
+        (
+            ty::TyIntrinsicFunctionKind {
+                kind,
+                arguments: typed_arguments,
+                type_arguments: vec![],
+                span,
+            },
+            ptr_type,
+        ),
+        warnings,
+        errors,
+    )
+}
+
+/// Signature: `__memcpy(dst: raw_ptr, src: raw_ptr, len: u64)`
+/// Description: Copies the `len` bytes at `src` to `dst`, with a single `mcp` instruction.
+/// Constraints: None.
+///
+/// Signature: `__memcmp(lhs: raw_ptr, rhs: raw_ptr, len: u64) -> bool`
+/// Description: Returns whether the `len` bytes at `lhs` and `rhs` are equal, with a single `meq`
+///              instruction.
+/// Constraints: None.
+fn type_check_mem_op(
+    mut ctx: TypeCheckContext,
+    kind: sway_ast::Intrinsic,
+    arguments: Vec<Expression>,
+    type_arguments: Vec<TypeArgument>,
+    span: Span,
+) -> CompileResult<(ty::TyIntrinsicFunctionKind, TypeId)> {
+    let type_engine = ctx.engines.te();
+    let engines = ctx.engines();
+
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    if arguments.len() != 3 {
+        errors.push(CompileError::IntrinsicIncorrectNumArgs {
+            name: kind.to_string(),
+            expected: 3,
+            span,
+        });
+        return err(warnings, errors);
+    }
+    if !type_arguments.is_empty() {
+        errors.push(CompileError::IntrinsicIncorrectNumTArgs {
+            name: kind.to_string(),
+            expected: 0,
+            span,
+        });
+        return err(warnings, errors);
+    }
+
+    let ptr_type = type_engine.insert(engines, TypeInfo::RawUntypedPtr);
+    let u64_type = type_engine.insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour));
+    let mut typed_arguments = Vec::with_capacity(arguments.len());
+    for (argument, argument_type) in arguments.into_iter().zip([ptr_type, ptr_type, u64_type]) {
+        let ctx = ctx
+            .by_ref()
+            .with_help_text("Incorrect argument type")
+            .with_type_annotation(argument_type);
+        typed_arguments.push(check!(
+            ty::TyExpression::type_check(ctx, argument),
+            return err(warnings, errors),
+            warnings,
+            errors
+        ));
+    }
+
+    let return_type = match kind {
+        Intrinsic::Memcmp => type_engine.insert(engines, TypeInfo::Boolean),
+        _ => type_engine.insert(engines, TypeInfo::Tuple(vec![])),
+    };
+    ok(
+        (
+            ty::TyIntrinsicFunctionKind {
+                kind,
+                arguments: typed_arguments,
+                type_arguments: vec![],
+                span,
+            },
+            return_type,
+        ),
+        warnings,
+        errors,
+    )
+}
+
+/// Signature: `__heap_stats() -> (raw_ptr, u64)`
+/// Description: Returns the current heap pointer, i.e. the address of the most recent heap
+///              allocation, along with the number of bytes allocated on the heap so far.
+///              This is a wrapper around reading FuelVM's `$hp` register.
+/// Constraints: None.
+fn type_check_heap_stats(
+    ctx: TypeCheckContext,
+    kind: sway_ast::Intrinsic,
+    arguments: Vec<Expression>,
+    type_arguments: Vec<TypeArgument>,
+    span: Span,
+) -> CompileResult<(ty::TyIntrinsicFunctionKind, TypeId)> {
+    let type_engine = ctx.engines.te();
+    let engines = ctx.engines();
+
+    let warnings = vec![];
+    let mut errors = vec![];
+
+    if !arguments.is_empty() {
+        errors.push(CompileError::IntrinsicIncorrectNumArgs {
+            name: kind.to_string(),
+            expected: 0,
+            span,
+        });
+        return err(warnings, errors);
+    }
+    if !type_arguments.is_empty() {
+        errors.push(CompileError::IntrinsicIncorrectNumTArgs {
+            name: kind.to_string(),
+            expected: 0,
+            span,
+        });
+        return err(warnings, errors);
+    }
+
+    let return_type = type_engine.insert(
+        engines,
+        TypeInfo::Tuple(vec![
+            type_engine.insert(engines, TypeInfo::RawUntypedPtr).into(),
+            type_engine
+                .insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour))
+                .into(),
+        ]),
+    );
+    ok(
+        (
+            ty::TyIntrinsicFunctionKind {
+                kind,
+                arguments: vec![],
+                type_arguments: vec![],
+                span,
+            },
+            return_type,
+        ),
+        warnings,
+        errors,
+    )
+}
+
 /// Signature: `__size_of_val<T>(val: T) -> u64`
 /// Description: Return the size of type `T` in bytes.
 /// Constraints: None.
@@ -303,7 +566,9 @@ fn type_check_is_reference_type(
 
 /// Signature: `__eq<T>(lhs: T, rhs: T) -> bool`
 /// Description: Returns whether `lhs` and `rhs` are equal.
-/// Constraints: `T` is `bool`, `u8`, `u16`, `u32`, `u64`, or `raw_ptr`.
+/// Constraints: `T` is `bool`, `u8`, `u16`, `u32`, `u64`, `raw_ptr`, a string array, or an
+///              array or struct of integers, `bool`s, `b256`s, or arrays of them, whose memory is
+///              compared.
 ///
 /// Signature: `__gt<T>(lhs: T, rhs: T) -> bool`
 /// Description: Returns whether `lhs` > `rhs`.
@@ -363,7 +628,8 @@ fn type_check_cmp(
     );
     let is_valid_arg_ty = matches!(arg_ty, TypeInfo::UnsignedInteger(_) | TypeInfo::Numeric)
         || (matches!(&kind, Intrinsic::Eq)
-            && matches!(arg_ty, TypeInfo::Boolean | TypeInfo::RawUntypedPtr));
+            && (matches!(arg_ty, TypeInfo::Boolean | TypeInfo::RawUntypedPtr)
+                || arg_ty.is_bytewise_comparable(type_engine, engines.de())));
     if !is_valid_arg_ty {
         errors.push(CompileError::IntrinsicUnsupportedArgType {
             name: kind.to_string(),
@@ -971,6 +1237,19 @@ fn type_check_log(
 /// Description: Multiplies `lhs` and `rhs` and returns the result.
 /// Constraints: `T` is an integer type, i.e. `u8`, `u16`, `u32`, `u64`.
 ///
+/// What `__add`, `__sub` and `__mul` do when the result overflows `T` depends on the `overflow`
+/// setting of the build profile.
+///
+/// Signature: `__wrapping_add<T>(lhs: T, rhs: T) -> T`, and `__wrapping_sub` and `__wrapping_mul`
+/// Description: Adds, subtracts or multiplies `lhs` and `rhs` and returns the result, wrapped
+/// around the range of `T` when it overflows.
+/// Constraints: `T` is an integer type, i.e. `u8`, `u16`, `u32`, `u64`.
+///
+/// Signature: `__checked_add<T>(lhs: T, rhs: T) -> T`, and `__checked_sub` and `__checked_mul`
+/// Description: Adds, subtracts or multiplies `lhs` and `rhs` and returns the result, reverting
+/// with `FAILED_OVERFLOW_SIGNAL` when it overflows.
+/// Constraints: `T` is an integer type, i.e. `u8`, `u16`, `u32`, `u64`.
+///
 /// Signature: `__div<T>(lhs: T, rhs: T) -> T`
 /// Description: Divides `lhs` and `rhs` and returns the result.
 /// Constraints: `T` is an integer type, i.e. `u8`, `u16`, `u32`, `u64`.
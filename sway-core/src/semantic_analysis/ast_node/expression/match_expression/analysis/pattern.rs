@@ -197,7 +197,7 @@ impl Pattern {
             Literal::B256(x) => Pattern::B256(x),
             Literal::Boolean(b) => Pattern::Boolean(b),
             Literal::Numeric(x) => Pattern::Numeric(Range::from_single(x)),
-            Literal::String(s) => Pattern::String(s.as_str().to_string()),
+            Literal::String(_, value) => Pattern::String(value.clone()),
         }
     }
 
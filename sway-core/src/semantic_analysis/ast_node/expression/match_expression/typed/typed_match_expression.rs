@@ -42,6 +42,17 @@ impl ty::TyMatchExpression {
             return err(warnings, errors);
         }
 
+        // A branch returning a numeric value before the branch which gives the match expression
+        // its width only made it numeric, so unify the numeric branches with it once more. A
+        // mismatch was already reported when the branch was type checked.
+        let type_engine = ctx.engines.te();
+        let decl_engine = ctx.engines.de();
+        for ty::TyMatchBranch { result, .. } in &typed_branches {
+            if type_engine.contains_numeric(decl_engine, result.return_type) {
+                let _ = ctx.unify_with_self(result.return_type, &result.span);
+            }
+        }
+
         let typed_exp = ty::TyMatchExpression {
             value_type_id: typed_value.return_type,
             branches: typed_branches,
@@ -170,8 +170,6 @@ impl ty::TyExpression {
             ExpressionKind::CodeBlock(contents) => {
                 Self::type_check_code_block(ctx.by_ref(), contents, span)
             }
-            // TODO if _condition_ is constant, evaluate it and compile this to an
-            // expression with only one branch
             ExpressionKind::If(IfExpression {
                 condition,
                 then,
@@ -298,20 +296,19 @@ impl ty::TyExpression {
                 Self::type_check_reassignment(ctx.by_ref(), lhs, *rhs, span)
             }
             ExpressionKind::Return(expr) => {
+                // Return statements do not necessarily follow the type annotation of their
+                // immediate surrounding context. Because a return statement is control flow
+                // that breaks out to the nearest function, we type check it against the return
+                // type of the surrounding function instead, or against "unknown" outside of a
+                // function body.
+                let return_type_annotation = ctx
+                    .return_type_annotation()
+                    .unwrap_or_else(|| type_engine.insert(engines, TypeInfo::Unknown));
                 let ctx = ctx
-                    // we use "unknown" here because return statements do not
-                    // necessarily follow the type annotation of their immediate
-                    // surrounding context. Because a return statement is control flow
-                    // that breaks out to the nearest function, we need to type check
-                    // it against the surrounding function.
-                    // That is impossible here, as we don't have that information. It
-                    // is the responsibility of the function declaration to type check
-                    // all return statements contained within it.
                     .by_ref()
-                    .with_type_annotation(type_engine.insert(engines, TypeInfo::Unknown))
+                    .with_type_annotation(return_type_annotation)
                     .with_help_text(
-                        "Returned value must match up with the function return type \
-                        annotation.",
+                        "Return statement must return the declared function return type.",
                     );
                 let mut warnings = vec![];
                 let mut errors = vec![];
@@ -391,7 +388,7 @@ impl ty::TyExpression {
     ) -> CompileResult<ty::TyExpression> {
         let type_engine = engines.te();
         let return_type = match &lit {
-            Literal::String(s) => TypeInfo::Str(Length::new(s.as_str().len(), s.clone())),
+            Literal::String(s, value) => TypeInfo::Str(Length::new(value.len(), s.clone())),
             Literal::Numeric(_) => TypeInfo::Numeric,
             Literal::U8(_) => TypeInfo::UnsignedInteger(IntegerBits::Eight),
             Literal::U16(_) => TypeInfo::UnsignedInteger(IntegerBits::Sixteen),
@@ -595,6 +592,41 @@ impl ty::TyExpression {
                 errors
             )
         };
+
+        // If the condition is known at compile time, e.g. a constant which is only `true` for some
+        // build targets, the branch which isn't taken is left out as if it were excluded with
+        // `#[cfg]`. It isn't type checked, so it neither warns nor logs types, and functions only
+        // called from it don't end up in the bytecode. Literal conditions are left as they are.
+        let static_condition = match condition.expression {
+            ty::TyExpressionVariant::Literal(_) => None,
+            _ => condition.static_bool_value(),
+        };
+        if let Some(value) = static_condition {
+            let unit = type_engine.insert(engines, TypeInfo::Tuple(vec![]));
+            let (taken, type_annotation) = match (value, r#else) {
+                (true, Some(_)) => (Some(then), ctx.type_annotation()),
+                (true, None) => (Some(then), unit),
+                (false, r#else) => (r#else, ctx.type_annotation()),
+            };
+            let exp = match taken {
+                Some(branch) => check!(
+                    ty::TyExpression::type_check(
+                        ctx.by_ref().with_type_annotation(type_annotation),
+                        branch.clone()
+                    ),
+                    ty::TyExpression::error(branch.span(), engines),
+                    warnings,
+                    errors
+                ),
+                None => ty::TyExpression {
+                    expression: ty::TyExpressionVariant::Tuple { fields: vec![] },
+                    return_type: unit,
+                    span,
+                },
+            };
+            return ok(exp, warnings, errors);
+        }
+
         let then = {
             let ctx = ctx
                 .by_ref()
@@ -727,7 +759,7 @@ impl ty::TyExpression {
     #[allow(clippy::too_many_arguments)]
     fn type_check_asm_expression(
         mut ctx: TypeCheckContext,
-        asm: AsmExpression,
+        mut asm: AsmExpression,
         span: Span,
     ) -> CompileResult<ty::TyExpression> {
         let mut warnings = vec![];
@@ -736,9 +768,17 @@ impl ty::TyExpression {
         let type_engine = ctx.engines.te();
         let engines = ctx.engines();
 
+        // Replace immediates naming constants with their values, leaving labels in place.
+        check!(
+            resolve_asm_immediates(&ctx, &mut asm),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+
         // Various checks that we can catch early to check that the assembly is valid. For now,
         // this includes two checks:
-        // 1. Check that no control flow opcodes are used.
+        // 1. Check that no control flow opcodes are used, other than jumps to local labels.
         // 2. Check that initialized registers are not reassigned in the `asm` block.
         check!(
             check_asm_block_validity(&asm),
@@ -1171,9 +1211,10 @@ impl ty::TyExpression {
                     if !type_arguments.to_vec().is_empty() {
                         return err(
                             vec![],
-                            vec![
-                                ConvertParseTreeError::MultipleGenericsNotSupported { span }.into()
-                            ],
+                            vec![CompileError::DuplicateTypeArguments {
+                                name: suffix,
+                                span: type_arguments.span(),
+                            }],
                         );
                     }
                     type_arguments = TypeArgs::Prefix(vec)
@@ -1892,7 +1933,11 @@ impl ty::TyExpression {
                                 errors
                             );
                             if !variable_decl.mutability.is_mutable() {
-                                errors.push(CompileError::AssignmentToNonMutable { name, span });
+                                errors.push(CompileError::AssignmentToNonMutable {
+                                    name,
+                                    span,
+                                    decl_span: variable_decl.name.span(),
+                                });
                                 return err(warnings, errors);
                             }
                             break (name, variable_decl.body.return_type);
@@ -2235,10 +2280,99 @@ mod tests {
     }
 }
 
+/// Checks whether an `asm` immediate is a literal of the form `i<digits>`.
+fn is_literal_asm_immediate(immediate: &Ident) -> bool {
+    immediate
+        .as_str()
+        .strip_prefix('i')
+        .map_or(false, |digits| {
+            !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+/// Returns the names of the labels declared in an `asm` block.
+fn asm_block_labels(asm: &AsmExpression) -> FxHashSet<String> {
+    asm.body
+        .iter()
+        .filter_map(|op| op.label.as_ref().map(|label| label.as_str().to_string()))
+        .collect()
+}
+
+/// Resolves the symbolic immediates of an `asm` block. Immediates naming a label declared in the
+/// block are kept as they are, and immediates naming an integer constant are replaced with the
+/// equivalent `i<value>` immediate.
+fn resolve_asm_immediates(ctx: &TypeCheckContext, asm: &mut AsmExpression) -> CompileResult<()> {
+    let mut errors = vec![];
+
+    let labels = asm_block_labels(asm);
+    let mut declared_labels = FxHashSet::default();
+
+    let decl_engine = ctx.engines.de();
+    for op in asm.body.iter_mut() {
+        if let Some(label) = &op.label {
+            if !declared_labels.insert(label.as_str().to_string()) {
+                errors.push(CompileError::DuplicateAsmLabel {
+                    name: label.clone(),
+                    span: label.span(),
+                });
+            }
+        }
+
+        let Some(immediate) = &op.immediate else {
+            continue;
+        };
+        if is_literal_asm_immediate(immediate) || labels.contains(immediate.as_str()) {
+            continue;
+        }
+
+        let const_decl = match ctx.namespace.resolve_symbol(immediate).value {
+            Some(ty::TyDecl::ConstantDecl(ty::ConstantDecl { decl_id, .. })) => {
+                decl_engine.get_constant(decl_id)
+            }
+            _ => {
+                errors.push(CompileError::UnknownAsmImmediate {
+                    name: immediate.clone(),
+                    span: immediate.span(),
+                });
+                continue;
+            }
+        };
+        let value = match const_decl.value.as_ref().map(|value| &value.expression) {
+            Some(ty::TyExpressionVariant::Literal(literal)) => literal.as_u64(),
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                op.immediate = Some(Ident::new_with_override(
+                    format!("i{value}"),
+                    immediate.span(),
+                ));
+            }
+            None => errors.push(CompileError::AsmImmediateConstantNotAnInteger {
+                name: immediate.clone(),
+                span: immediate.span(),
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        ok((), vec![], errors)
+    } else {
+        err(vec![], errors)
+    }
+}
+
 fn check_asm_block_validity(asm: &AsmExpression) -> CompileResult<()> {
     let mut errors = vec![];
     let mut warnings = vec![];
 
+    let labels = asm_block_labels(asm);
+    let targets_label = |op: &AsmOp| {
+        op.immediate
+            .as_ref()
+            .map_or(false, |immediate| labels.contains(immediate.as_str()))
+    };
+
     // Collect all asm block instructions in the form of `VirtualOp`s
     let mut opcodes = vec![];
     for op in &asm.body {
@@ -2248,12 +2382,22 @@ fn check_asm_block_validity(asm: &AsmExpression) -> CompileResult<()> {
             .map(|reg_name| VirtualRegister::Virtual(reg_name.to_string()))
             .collect::<Vec<VirtualRegister>>();
 
+        // Jump targets are resolved during code generation, so only check the rest of the
+        // instruction here.
+        let immediate = if targets_label(op) {
+            op.immediate
+                .as_ref()
+                .map(|label| Ident::new_with_override("i0".into(), label.span()))
+        } else {
+            op.immediate.clone()
+        };
+
         opcodes.push((
             check!(
                 crate::asm_lang::Op::parse_opcode(
                     &op.op_name,
                     &registers,
-                    &op.immediate,
+                    &immediate,
                     op.span.clone(),
                 ),
                 return err(warnings, errors),
@@ -2262,26 +2406,24 @@ fn check_asm_block_validity(asm: &AsmExpression) -> CompileResult<()> {
             ),
             op.op_name.clone(),
             op.span.clone(),
+            targets_label(op),
         ));
     }
 
-    // Check #1: Disallow control flow instructions
+    // Check #1: Disallow control flow instructions, apart from jumps to labels declared in the
+    // block.
     //
     errors.extend(
         opcodes
             .iter()
-            .filter(|op| {
-                matches!(
-                    op.0,
-                    VirtualOp::JMP(_)
-                        | VirtualOp::JI(_)
-                        | VirtualOp::JNE(..)
-                        | VirtualOp::JNEI(..)
-                        | VirtualOp::JNZI(..)
-                        | VirtualOp::RET(_)
-                        | VirtualOp::RETD(..)
-                        | VirtualOp::RVRT(..)
-                )
+            .filter(|op| match op.0 {
+                VirtualOp::JI(_) | VirtualOp::JNEI(..) | VirtualOp::JNZI(..) => !op.3,
+                VirtualOp::JMP(_)
+                | VirtualOp::JNE(..)
+                | VirtualOp::RET(_)
+                | VirtualOp::RETD(..)
+                | VirtualOp::RVRT(..) => true,
+                _ => false,
             })
             .map(|op| CompileError::DisallowedControlFlowInstruction {
                 name: op.1.to_string(),
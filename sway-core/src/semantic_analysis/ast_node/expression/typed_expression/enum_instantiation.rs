@@ -78,6 +78,12 @@ pub(crate) fn instantiate_enum(
             errors,
         ),
         ([single_expr], _) => {
+            let type_id = type_engine.insert(engines, TypeInfo::Enum(enum_ref.clone()));
+
+            // the annotation gives the type of the variant if the argument's type depends on it,
+            // e.g. in `let x: Option<Vec<u64>> = Some(Vec::new());`
+            ctx.propagate_type_annotation(type_id, &enum_variant_name.span());
+
             let enum_ctx = ctx
                 .by_ref()
                 .with_help_text("Enum instantiator must match its declared variant type.")
@@ -107,8 +113,6 @@ pub(crate) fn instantiate_enum(
             // we now know that the instantiator type matches the declared type, via the above tpe
             // check
 
-            let type_id = type_engine.insert(engines, TypeInfo::Enum(enum_ref.clone()));
-
             check!(
                 type_id.check_type_parameter_bounds(&ctx, &enum_variant_name.span(), vec![]),
                 return err(warnings, errors),
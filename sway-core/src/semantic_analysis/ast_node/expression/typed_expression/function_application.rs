@@ -1,11 +1,11 @@
 use crate::{
-    decl_engine::{DeclEngineInsert, DeclRefFunction, ReplaceDecls},
+    decl_engine::{DeclRefFunction, ReplaceDecls},
     error::*,
     language::{ty, *},
     semantic_analysis::{ast_node::*, TypeCheckContext},
 };
 use std::collections::HashMap;
-use sway_error::error::CompileError;
+use sway_error::error::{CompileError, ErrorContext};
 use sway_types::Spanned;
 
 #[allow(clippy::too_many_arguments)]
@@ -54,6 +54,9 @@ pub(crate) fn instantiate_function_application(
         errors
     );
 
+    // the annotation may give type arguments which the arguments don't, e.g. of `Vec::new()`
+    ctx.propagate_type_annotation(function_decl.return_type.type_id, &span);
+
     let typed_arguments = check!(
         type_check_arguments(ctx.by_ref(), arguments),
         return err(warnings, errors),
@@ -61,8 +64,29 @@ pub(crate) fn instantiate_function_application(
         errors
     );
 
-    let typed_arguments_with_names = check!(
-        unify_arguments_and_parameters(ctx.by_ref(), typed_arguments, &function_decl.parameters),
+    let mut typed_arguments_with_names = check!(
+        with_instantiation_context(
+            unify_arguments_and_parameters(
+                ctx.by_ref(),
+                typed_arguments,
+                &function_decl.parameters
+            ),
+            &function_decl,
+            engines
+        ),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+
+    check!(
+        infer_numeric_arguments(
+            ctx.by_ref(),
+            &mut typed_arguments_with_names,
+            &function_decl.parameters,
+            function_decl.return_type.type_id,
+            &span
+        ),
         return err(warnings, errors),
         warnings,
         errors
@@ -88,9 +112,18 @@ pub(crate) fn instantiate_function_application(
     );
     function_decl.replace_decls(&decl_mapping, engines);
     let return_type = function_decl.return_type.clone();
-    let new_decl_ref = decl_engine
-        .insert(function_decl)
-        .with_parent(decl_engine, (*function_decl_ref.id()).into());
+    // The declaration through which a function calls itself has no body to instantiate, and is
+    // found by its ID when generating IR.
+    let new_decl_ref = if function_decl.is_recursive_ref {
+        function_decl_ref
+    } else {
+        decl_engine.insert_function_instance(
+            engines,
+            function_decl,
+            None,
+            (*function_decl_ref.id()).into(),
+        )
+    };
 
     let exp = ty::TyExpression {
         expression: ty::TyExpressionVariant::FunctionApplication {
@@ -194,6 +227,110 @@ fn unify_arguments_and_parameters(
     }
 }
 
+/// Gives the numeric arguments the width of the parameters they were passed to, once all of the
+/// arguments have been unified with the parameters.
+///
+/// A numeric literal passed to a generic parameter before the argument which gives the parameter
+/// its width only makes the parameter numeric, and the return type of the call only gets its width
+/// from the type annotation after the trait constraints of the function have defaulted it to `u64`.
+/// So the return type is unified with the annotation first, and the numeric arguments with the
+/// parameters again, resolving the numeric literals to the integers they now are.
+pub(crate) fn infer_numeric_arguments(
+    mut ctx: TypeCheckContext,
+    arguments: &mut [(Ident, ty::TyExpression)],
+    parameters: &[ty::TyFunctionParameter],
+    return_type: TypeId,
+    span: &Span,
+) -> CompileResult<()> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    let type_engine = ctx.engines.te();
+    let decl_engine = ctx.engines.de();
+    let engines = ctx.engines();
+
+    // A mismatch with the annotation is reported when the whole call is unified with it.
+    if type_engine.contains_numeric(decl_engine, return_type) {
+        let _ = type_engine.unify_with_self(
+            engines,
+            return_type,
+            ctx.type_annotation(),
+            ctx.self_type(),
+            span,
+            "",
+            None,
+        );
+    }
+
+    for ((_, arg), param) in arguments.iter_mut().zip(parameters.iter()) {
+        // A mismatch with the parameter was reported when the argument was first unified with it.
+        if type_engine.contains_numeric(decl_engine, arg.return_type) {
+            let _ = type_engine.unify_with_self(
+                engines,
+                arg.return_type,
+                param.type_argument.type_id,
+                ctx.self_type(),
+                &arg.span,
+                "",
+                None,
+            );
+        }
+        if let ty::TyExpressionVariant::Literal(lit @ Literal::Numeric(_)) = &arg.expression {
+            if let TypeInfo::UnsignedInteger(_) = type_engine.get(arg.return_type) {
+                *arg = check!(
+                    ty::TyExpression::resolve_numeric_literal(
+                        ctx.by_ref(),
+                        lit.clone(),
+                        arg.span.clone(),
+                        arg.return_type
+                    ),
+                    continue,
+                    warnings,
+                    errors
+                );
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        ok((), warnings, errors)
+    } else {
+        err(warnings, errors)
+    }
+}
+
+/// Adds the type arguments inferred for a call of the generic function `function_decl` to the
+/// context of the errors of `result`, e.g. "while instantiating `get<u8, MyStruct>`, inferred from
+/// this call", so that a mismatch in the arguments shows which instance of the function was being
+/// called. The type arguments which haven't been inferred are shown as `_`.
+pub(crate) fn with_instantiation_context<T>(
+    result: CompileResult<T>,
+    function_decl: &ty::TyFunctionDecl,
+    engines: &Engines,
+) -> CompileResult<T> {
+    if function_decl.type_parameters.is_empty() {
+        return result;
+    }
+    result.with_context(|| {
+        let type_arguments = function_decl
+            .type_parameters
+            .iter()
+            .map(|type_param| match engines.te().get(type_param.type_id) {
+                TypeInfo::Unknown | TypeInfo::Placeholder(_) => "_".to_string(),
+                _ => engines.help_out(type_param.type_id).to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        ErrorContext::new(
+            "instantiating",
+            format!(
+                "`{}<{type_arguments}>`, inferred from this call",
+                function_decl.name
+            ),
+        )
+    })
+}
+
 pub(crate) fn check_function_arguments_arity(
     arguments_len: usize,
     function_decl: &ty::TyFunctionDecl,
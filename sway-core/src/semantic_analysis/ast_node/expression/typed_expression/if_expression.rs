@@ -103,6 +103,22 @@ pub(crate) fn instantiate_if_expression(
     } else {
         r#else_ret_ty
     };
+
+    // A numeric `then` branch only made the expected type of the `if` expression numeric, e.g. in
+    // `if c { 1 } else { x }`, so unify it with the type of the branches once more. A mismatch was
+    // already reported above.
+    if type_engine.contains_numeric(decl_engine, ctx.type_annotation()) {
+        let _ = type_engine.unify_with_self(
+            engines,
+            return_type,
+            ctx.type_annotation(),
+            ctx.self_type(),
+            &span,
+            "",
+            None,
+        );
+    }
+
     let exp = ty::TyExpression {
         expression: ty::TyExpressionVariant::IfExp {
             condition: Box::new(condition),
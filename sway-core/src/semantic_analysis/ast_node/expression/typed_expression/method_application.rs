@@ -5,7 +5,9 @@ use crate::{
     semantic_analysis::*,
     type_system::*,
 };
-use ast_node::typed_expression::check_function_arguments_arity;
+use ast_node::typed_expression::{
+    check_function_arguments_arity, infer_numeric_arguments, with_instantiation_context,
+};
 use std::collections::{HashMap, VecDeque};
 use sway_error::error::CompileError;
 use sway_types::{constants, integer_bits::IntegerBits};
@@ -43,12 +45,15 @@ pub(crate) fn type_check_method_application(
     }
 
     // resolve the method name to a typed function declaration and type_check
-    let (decl_ref, call_path_typeid) = check!(
-        resolve_method_name(ctx.by_ref(), &mut method_name_binding, args_buf.clone()),
-        return err(warnings, errors),
-        warnings,
-        errors
-    );
+    let is_eq_op = is_core_ops_call(&method_name_binding.inner, &["eq", "neq"]);
+    let resolved = resolve_method_name(ctx.by_ref(), &mut method_name_binding, args_buf.clone());
+    if resolved.value.is_none() && is_eq_op {
+        if let Some(exp) = bytewise_eq(&ctx, &method_name_binding.inner, &args_buf, &span) {
+            return ok(exp, warnings, errors);
+        }
+    }
+    let (decl_ref, call_path_typeid) =
+        check!(resolved, return err(warnings, errors), warnings, errors);
     let mut method = decl_engine.get_function(&decl_ref);
 
     // check the method visibility
@@ -345,9 +350,29 @@ pub(crate) fn type_check_method_application(
         errors
     );
 
+    // the annotation may give type arguments which the arguments don't, e.g. of `Vec::new()`
+    ctx.propagate_type_annotation(method.return_type.type_id, &span);
+
     // unify the types of the arguments with the types of the parameters from the function declaration
-    let typed_arguments_with_names = check!(
-        unify_arguments_and_parameters(ctx.by_ref(), args_buf, &method.parameters),
+    let mut typed_arguments_with_names = check!(
+        with_instantiation_context(
+            unify_arguments_and_parameters(ctx.by_ref(), args_buf, &method.parameters),
+            &method,
+            engines
+        ),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+
+    check!(
+        infer_numeric_arguments(
+            ctx.by_ref(),
+            &mut typed_arguments_with_names,
+            &method.parameters,
+            method.return_type.type_id,
+            &span
+        ),
         return err(warnings, errors),
         warnings,
         errors
@@ -373,9 +398,12 @@ pub(crate) fn type_check_method_application(
     );
     method.replace_decls(&decl_mapping, ctx.engines());
     let return_type = method.return_type.type_id;
-    let new_decl_ref = decl_engine
-        .insert(method)
-        .with_parent(decl_engine, (*decl_ref.id()).into());
+    let new_decl_ref = decl_engine.insert_function_instance(
+        engines,
+        method,
+        Some(call_path_typeid),
+        (*decl_ref.id()).into(),
+    );
 
     let exp = ty::TyExpression {
         expression: ty::TyExpressionVariant::FunctionApplication {
@@ -439,6 +467,176 @@ fn unify_arguments_and_parameters(
     }
 }
 
+/// Whether `method_name` is the method of one of the `ops` of `core::ops`, which the operators
+/// are desugared to.
+fn is_core_ops_call(method_name: &MethodName, ops: &[&str]) -> bool {
+    let MethodName::FromTrait { call_path } = method_name else {
+        return false;
+    };
+    call_path.prefixes.len() == 2
+        && call_path.prefixes[0].as_str() == "core"
+        && call_path.prefixes[1].as_str() == "ops"
+        && ops.contains(&call_path.suffix.as_str())
+}
+
+/// Lowers `==` and `!=` on values of a type which doesn't implement `Eq`, and whose values are
+/// equal exactly when their bytes are, to `__eq`, which compares their memory. `!=` compares the
+/// result to `false`.
+fn bytewise_eq(
+    ctx: &TypeCheckContext,
+    method_name: &MethodName,
+    arguments: &VecDeque<ty::TyExpression>,
+    span: &Span,
+) -> Option<ty::TyExpression> {
+    let type_engine = ctx.engines.te();
+    let decl_engine = ctx.engines.de();
+    let engines = ctx.engines();
+
+    let (Some(lhs), Some(rhs)) = (arguments.get(0), arguments.get(1)) else {
+        return None;
+    };
+    if !type_engine
+        .get(lhs.return_type)
+        .is_bytewise_comparable(type_engine, decl_engine)
+        || !UnifyCheck::non_dynamic_equality(engines).check(lhs.return_type, rhs.return_type)
+    {
+        return None;
+    }
+
+    let bool_type = type_engine.insert(engines, TypeInfo::Boolean);
+    let eq = |arguments| ty::TyExpression {
+        expression: ty::TyExpressionVariant::IntrinsicFunction(ty::TyIntrinsicFunctionKind {
+            kind: sway_ast::Intrinsic::Eq,
+            arguments,
+            type_arguments: vec![],
+            span: span.clone(),
+        }),
+        return_type: bool_type,
+        span: span.clone(),
+    };
+    let exp = eq(vec![lhs.clone(), rhs.clone()]);
+    Some(if is_core_ops_call(method_name, &["neq"]) {
+        let false_exp = ty::TyExpression {
+            expression: ty::TyExpressionVariant::Literal(Literal::Boolean(false)),
+            return_type: bool_type,
+            span: span.clone(),
+        };
+        eq(vec![exp, false_exp])
+    } else {
+        exp
+    })
+}
+
+/// Gives a numeric left operand of an operator the width of its right operand, or else of the
+/// result the operator is expected to have, e.g. `1 + x` where `x: u8`.
+///
+/// The method implementing the operator is looked up on the type of its left operand, which would
+/// otherwise decay to `u64` and mismatch the right operand.
+fn infer_numeric_operand_width(
+    ctx: &TypeCheckContext,
+    call_path: &CallPath,
+    arguments: &VecDeque<ty::TyExpression>,
+) {
+    // The operators whose operands are both of type `Self`, and those whose result is.
+    const SELF_OPERANDS: &[&str] = &[
+        "add",
+        "subtract",
+        "multiply",
+        "divide",
+        "modulo",
+        "binary_and",
+        "binary_xor",
+        "binary_or",
+        "eq",
+        "neq",
+        "lt",
+        "gt",
+        "le",
+        "ge",
+    ];
+    const SELF_RESULT: &[&str] = &[
+        "add",
+        "subtract",
+        "multiply",
+        "divide",
+        "modulo",
+        "binary_and",
+        "binary_xor",
+        "binary_or",
+        "lsh",
+        "rsh",
+    ];
+
+    let type_engine = ctx.engines.te();
+    let engines = ctx.engines();
+
+    let is_core_ops = call_path.prefixes.len() == 2
+        && call_path.prefixes[0].as_str() == "core"
+        && call_path.prefixes[1].as_str() == "ops";
+    let Some(lhs) = arguments.get(0) else {
+        return;
+    };
+    if !is_core_ops || !matches!(type_engine.get(lhs.return_type), TypeInfo::Numeric) {
+        return;
+    }
+    let op = call_path.suffix.as_str();
+    let is_integer =
+        |type_id: &TypeId| matches!(type_engine.get(*type_id), TypeInfo::UnsignedInteger(_));
+    let rhs_width = arguments
+        .get(1)
+        .map(|rhs| rhs.return_type)
+        .filter(|rhs| SELF_OPERANDS.contains(&op) && is_integer(rhs));
+    let result_width = Some(ctx.type_annotation())
+        .filter(|result| SELF_RESULT.contains(&op) && is_integer(result));
+    if let Some(width_from) = rhs_width.or(result_width) {
+        // Unifying a numeric type with an integer can't fail.
+        let _ = type_engine.unify(engines, lhs.return_type, width_from, &lhs.span, "", None);
+    }
+}
+
+/// Checks the type arguments given to a method, e.g. `x.foo::<u64>()`, against the type parameters
+/// of the method and completes them with those of the impl block it's declared in.
+///
+/// The type parameters of the impl block are appended to those of its methods, but they can't be
+/// given in the method's type arguments and are instead inferred from the method's arguments.
+fn method_type_arguments(
+    engines: &Engines,
+    func_decl: &ty::TyFunctionDecl,
+    mut type_arguments: Vec<TypeArgument>,
+) -> CompileResult<Vec<TypeArgument>> {
+    let type_engine = engines.te();
+    let Some(type_arguments_span) = type_arguments
+        .iter()
+        .map(|type_arg| type_arg.span.clone())
+        .reduce(Span::join)
+    else {
+        return ok(type_arguments, vec![], vec![]);
+    };
+    let (method_params, impl_params): (Vec<_>, Vec<_>) = func_decl
+        .type_parameters
+        .iter()
+        .partition(|type_param| !type_param.is_from_parent);
+    if method_params.is_empty() {
+        let error = CompileError::DoesNotTakeTypeArguments {
+            name: func_decl.name.clone(),
+            span: type_arguments_span,
+        };
+        return err(vec![], vec![error]);
+    }
+    if method_params.len() != type_arguments.len() {
+        let error = CompileError::IncorrectNumberOfTypeArguments {
+            given: type_arguments.len(),
+            expected: method_params.len(),
+            span: type_arguments_span,
+        };
+        return err(vec![], vec![error]);
+    }
+    type_arguments.extend(impl_params.into_iter().map(|type_param| {
+        TypeArgument::from(type_engine.insert(engines, TypeInfo::Placeholder(type_param.clone())))
+    }));
+    ok(type_arguments, vec![], vec![])
+}
+
 pub(crate) fn resolve_method_name(
     mut ctx: TypeCheckContext,
     method_name: &mut TypeBinding<MethodName>,
@@ -465,6 +663,17 @@ pub(crate) fn resolve_method_name(
                 errors
             );
 
+            // the type arguments given to the type, e.g. `Pair::<u64>::new()`, must satisfy the
+            // bounds of its type parameters
+            if !call_path_binding.type_arguments.to_vec().is_empty() {
+                check!(
+                    type_id.check_type_parameter_bounds(&ctx, &call_path_binding.span, vec![]),
+                    (),
+                    warnings,
+                    errors
+                );
+            }
+
             // find the module that the symbol is in
             let type_info_prefix = ctx
                 .namespace
@@ -513,6 +722,8 @@ pub(crate) fn resolve_method_name(
                 module_path
             };
 
+            infer_numeric_operand_width(&ctx, call_path, &arguments);
+
             // find the type of the first argument
             let type_id = arguments
                 .get(0)
@@ -604,10 +815,16 @@ pub(crate) fn resolve_method_name(
 
     // monomorphize the function declaration
     let method_name_span = method_name.span();
+    let mut type_arguments = check!(
+        method_type_arguments(engines, &func_decl, method_name.type_arguments.to_vec()),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
     check!(
         ctx.monomorphize(
             &mut func_decl,
-            method_name.type_arguments.to_vec_mut(),
+            &mut type_arguments,
             EnforceTypeArguments::No,
             &method_name_span,
         ),
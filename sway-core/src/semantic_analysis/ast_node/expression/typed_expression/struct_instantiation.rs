@@ -94,6 +94,10 @@ pub(crate) fn struct_instantiation(
         warnings,
         errors
     );
+    // the annotation gives the types of the fields if their types depend on it, e.g. in
+    // `let x: Wrapper<Vec<u64>> = Wrapper { inner: Vec::new() };`
+    ctx.propagate_type_annotation(type_id, &inner_span);
+
     let struct_decl = decl_engine.get_struct(&struct_ref);
     let struct_name = struct_decl.call_path.suffix;
     let struct_fields = struct_decl.fields;
@@ -0,0 +1,311 @@
+//! Borrow-style analysis of `ref mut` locals and storage handles.
+//!
+//! Sway copies values on assignment, but the heap-owning types of `std`, e.g. `Vec` and `Bytes`,
+//! only hold a pointer to their buffer, so their copies alias it. Mutating one of the copies can
+//! then reallocate or overwrite the buffer that the other copies still point to. To catch the
+//! common ways of running into this, we model within each function:
+//!
+//! - `ref mut` arguments, and storage handles passed to functions writing to storage, as
+//!   exclusive borrows of the place they refer to, and warn when another argument of the same
+//!   call aliases that place.
+//! - copies of locals of heap-owning types into other bindings, e.g. `let b = a;`, as moves, and
+//!   warn when the moved-from local is used again before being reassigned.
+//!
+//! The analysis runs on the typed AST rather than on the IR: the IR has already lowered `ref mut`
+//! parameters and storage handles to plain pointers and lost the bindings the warnings refer to,
+//! and Sway has no mid-level IR in between. It is a lint, not a borrow checker, and doesn't reject
+//! any program:
+//!
+//! - It is intra-procedural, so aliasing through the body of a called function isn't seen.
+//! - It is flow-insensitive across branches: moves within a nested block are forgotten at the end
+//!   of the block, so it never reports a use along a path on which the value was not moved.
+//! - Places are tracked by the local or storage field at their root, so two projections of the
+//!   same local, e.g. `a.x` and `a.y`, are considered to alias.
+
+use crate::{
+    language::{ty, Purity},
+    Engines, TypeId, TypeInfo,
+};
+use std::collections::{HashMap, HashSet};
+use sway_error::warning::{CompileWarning, Warning};
+use sway_types::{Ident, Span, Spanned};
+
+/// The root of the memory location that an expression refers to.
+#[derive(Clone, PartialEq, Eq)]
+enum Place {
+    /// A local variable or function parameter.
+    Local(String),
+    /// A storage field.
+    Storage(String),
+}
+
+impl Place {
+    fn name(&self) -> String {
+        match self {
+            Place::Local(name) => name.clone(),
+            Place::Storage(name) => format!("storage.{name}"),
+        }
+    }
+}
+
+pub(crate) fn analyze_program(engines: &Engines, prog: &ty::TyProgram) -> Vec<CompileWarning> {
+    let mut analysis = Analysis {
+        engines,
+        warnings: vec![],
+        moved: HashMap::new(),
+    };
+    let modules = std::iter::once(&prog.root).chain(
+        prog.root
+            .submodules_recursive()
+            .map(|(_, submodule)| &submodule.module),
+    );
+    for module in modules {
+        for fn_decl in module.functions(engines.de()) {
+            analysis.moved.clear();
+            analysis.code_block(&fn_decl.body);
+        }
+    }
+    analysis.warnings
+}
+
+struct Analysis<'eng> {
+    engines: &'eng Engines,
+    warnings: Vec<CompileWarning>,
+    /// The locals of heap-owning types that were copied into other bindings, with the names of
+    /// the bindings they were copied into.
+    moved: HashMap<String, String>,
+}
+
+impl<'eng> Analysis<'eng> {
+    fn code_block(&mut self, code_block: &ty::TyCodeBlock) {
+        // Moves within the block are only known to have happened by its end.
+        let outer_moved = self.moved.clone();
+        for ast_node in &code_block.contents {
+            match &ast_node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                    self.expression(&var_decl.body);
+                    // A new binding shadows any moved-from local with the same name.
+                    self.moved.remove(var_decl.name.as_str());
+                    self.record_move(&var_decl.body, var_decl.name.as_str());
+                }
+                ty::TyAstNodeContent::Declaration(_) => (),
+                ty::TyAstNodeContent::Expression(expr)
+                | ty::TyAstNodeContent::ImplicitReturnExpression(expr) => self.expression(expr),
+                ty::TyAstNodeContent::SideEffect(_) => (),
+            }
+        }
+        self.moved = outer_moved;
+    }
+
+    fn expression(&mut self, expr: &ty::TyExpression) {
+        use crate::ty::TyExpressionVariant::*;
+        match &expr.expression {
+            Literal(_)
+            | ConstantExpression { .. }
+            | FunctionParameter
+            | StorageAccess(_)
+            | Break
+            | Continue
+            | AbiName(_) => (),
+            VariableExpression { name, span, .. } => self.use_local(name.as_str(), span),
+            Reassignment(reassignment) => {
+                self.expression(&reassignment.rhs);
+                let lhs = reassignment.lhs_base_name.as_str();
+                if reassignment.lhs_indices.is_empty() {
+                    // Reassigning the whole local makes it usable again.
+                    self.moved.remove(lhs);
+                    self.record_move(&reassignment.rhs, lhs);
+                } else {
+                    self.use_local(lhs, &reassignment.lhs_base_name.span());
+                }
+            }
+            CodeBlock(code_block) => self.code_block(code_block),
+            LazyOperator { lhs, rhs, .. } => {
+                self.expression(lhs);
+                self.expression(rhs);
+            }
+            ArrayIndex { prefix, index } => {
+                self.expression(prefix);
+                self.expression(index);
+            }
+            FunctionApplication {
+                arguments, fn_ref, ..
+            } => {
+                for (_, arg) in arguments {
+                    self.expression(arg);
+                }
+                let fn_decl = self.engines.de().get_function(fn_ref);
+                self.call(&fn_decl, arguments);
+            }
+            IntrinsicFunction(intrinsic) => {
+                for arg in &intrinsic.arguments {
+                    self.expression(arg);
+                }
+            }
+            Tuple { fields: exprs }
+            | Array {
+                contents: exprs, ..
+            } => {
+                for expr in exprs {
+                    self.expression(expr);
+                }
+            }
+            StructExpression { fields, .. } => {
+                for field in fields {
+                    self.expression(&field.value);
+                }
+            }
+            StructFieldAccess { prefix: expr, .. }
+            | TupleElemAccess { prefix: expr, .. }
+            | Return(expr)
+            | EnumTag { exp: expr }
+            | UnsafeDowncast { exp: expr, .. }
+            | AbiCast { address: expr, .. } => self.expression(expr),
+            EnumInstantiation { contents, .. } => {
+                if let Some(expr) = contents {
+                    self.expression(expr);
+                }
+            }
+            MatchExp { desugared, .. } => self.expression(desugared),
+            IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                self.expression(condition);
+                self.expression(then);
+                if let Some(else_expr) = r#else {
+                    self.expression(else_expr);
+                }
+            }
+            WhileLoop { condition, body } => {
+                self.expression(condition);
+                self.code_block(body);
+            }
+            AsmExpression { registers, .. } => {
+                for initializer in registers.iter().filter_map(|reg| reg.initializer.as_ref()) {
+                    self.expression(initializer);
+                }
+            }
+        }
+    }
+
+    /// Warns about the arguments of a call that alias a place borrowed exclusively by another
+    /// argument.
+    ///
+    /// A `ref mut` argument borrows the local it refers to, and a storage handle borrows its
+    /// storage field if the function writes to storage. Another argument aliases the place if it
+    /// refers to it as well and is either borrowed itself or shares its heap buffer.
+    fn call(&mut self, fn_decl: &ty::TyFunctionDecl, arguments: &[(Ident, ty::TyExpression)]) {
+        let writes_storage = matches!(fn_decl.purity, Purity::Writes | Purity::ReadsWrites);
+        let is_borrowed =
+            |(param, arg): (&ty::TyFunctionParameter, &ty::TyExpression)| match place_of(arg) {
+                Some(Place::Local(_)) => param.is_reference && param.is_mutable,
+                Some(Place::Storage(_)) => writes_storage,
+                None => false,
+            };
+        let params_and_args = fn_decl
+            .parameters
+            .iter()
+            .zip(arguments.iter().map(|(_, arg)| arg))
+            .collect::<Vec<_>>();
+
+        let mut reported = HashSet::new();
+        for (i, &(param, arg)) in params_and_args.iter().enumerate() {
+            let Some(place) = place_of(arg).filter(|_| is_borrowed((param, arg))) else {
+                continue;
+            };
+            for (j, &(other_param, other_arg)) in params_and_args.iter().enumerate() {
+                let aliases = i != j
+                    && place_of(other_arg).as_ref() == Some(&place)
+                    && (is_borrowed((other_param, other_arg))
+                        || self.is_heap_owning(other_arg.return_type));
+                if aliases && reported.insert((i.min(j), i.max(j))) {
+                    self.warnings.push(CompileWarning {
+                        span: other_arg.span.clone(),
+                        warning_content: Warning::OverlappingMutableAccess {
+                            name: place.name(),
+                            param_name: param.name.clone(),
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    /// Records that the value of `expr` was copied into `binding`, if `expr` is a local of a
+    /// heap-owning type.
+    fn record_move(&mut self, expr: &ty::TyExpression, binding: &str) {
+        if let ty::TyExpressionVariant::VariableExpression { name, .. } = &expr.expression {
+            if name.as_str() != binding && self.is_heap_owning(expr.return_type) {
+                self.moved
+                    .insert(name.as_str().to_string(), binding.to_string());
+            }
+        }
+    }
+
+    /// Warns about a use of a moved-from local, once per move.
+    fn use_local(&mut self, name: &str, span: &Span) {
+        if let Some(moved_into) = self.moved.remove(name) {
+            self.warnings.push(CompileWarning {
+                span: span.clone(),
+                warning_content: Warning::UseAfterMove {
+                    name: name.to_string(),
+                    moved_into,
+                },
+            });
+        }
+    }
+
+    /// Whether values of the type own a heap buffer through a pointer, e.g. a `Vec` or `Bytes`.
+    ///
+    /// Raw pointers themselves are not considered to own what they point to.
+    fn is_heap_owning(&self, type_id: TypeId) -> bool {
+        !matches!(
+            self.engines.te().get(type_id),
+            TypeInfo::RawUntypedPtr | TypeInfo::RawUntypedSlice
+        ) && self.contains_pointer(type_id, &mut HashSet::new())
+    }
+
+    fn contains_pointer(&self, type_id: TypeId, visited: &mut HashSet<TypeId>) -> bool {
+        if !visited.insert(type_id) {
+            return false;
+        }
+        let decl_engine = self.engines.de();
+        match self.engines.te().get(type_id) {
+            TypeInfo::RawUntypedPtr | TypeInfo::RawUntypedSlice => true,
+            TypeInfo::Struct(decl_ref) => decl_engine
+                .get_struct(&decl_ref)
+                .fields
+                .iter()
+                .any(|field| self.contains_pointer(field.type_argument.type_id, visited)),
+            TypeInfo::Enum(decl_ref) => decl_engine
+                .get_enum(&decl_ref)
+                .variants
+                .iter()
+                .any(|variant| self.contains_pointer(variant.type_argument.type_id, visited)),
+            TypeInfo::Tuple(fields) => fields
+                .iter()
+                .any(|field| self.contains_pointer(field.type_id, visited)),
+            TypeInfo::Array(elem, _) => self.contains_pointer(elem.type_id, visited),
+            TypeInfo::Alias { ty, .. } => self.contains_pointer(ty.type_id, visited),
+            _ => false,
+        }
+    }
+}
+
+/// The place that `expr` refers to, if it is a local or a storage field, or a projection of one.
+fn place_of(expr: &ty::TyExpression) -> Option<Place> {
+    use crate::ty::TyExpressionVariant::*;
+    match &expr.expression {
+        VariableExpression { name, .. } => Some(Place::Local(name.as_str().to_string())),
+        StorageAccess(access) => access
+            .fields
+            .first()
+            .map(|field| Place::Storage(field.name.as_str().to_string())),
+        StructFieldAccess { prefix, .. }
+        | TupleElemAccess { prefix, .. }
+        | ArrayIndex { prefix, .. } => place_of(prefix),
+        _ => None,
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
 use std::collections::HashSet;
 use std::fmt;
 use sway_error::warning::{CompileWarning, Warning};
-use sway_types::{Ident, Span, Spanned};
+use sway_types::{constants::ALLOW_CEI_VIOLATION_NAME, Ident, Span, Spanned};
 
 #[derive(PartialEq, Eq, Hash, Clone)]
 enum Effect {
@@ -84,6 +84,10 @@ fn analyze_contract(engines: &Engines, ast_nodes: &[ty::TyAstNode]) -> Vec<Compi
     let decl_engine = engines.de();
     let mut warnings: Vec<CompileWarning> = vec![];
     for fn_decl in contract_entry_points(decl_engine, ast_nodes) {
+        // Functions annotated with `#[allow(cei_violation)]` are not analyzed.
+        if fn_decl.attributes.allows(ALLOW_CEI_VIOLATION_NAME) {
+            continue;
+        }
         analyze_code_block(engines, &fn_decl.body, &fn_decl.name, &mut warnings);
     }
     warnings
@@ -604,8 +608,9 @@ fn effects_of_intrinsic(intr: &sway_ast::Intrinsic) -> HashSet<Effect> {
         StateLoadWord | StateLoadQuad => HashSet::from([Effect::StorageRead]),
         Smo => HashSet::from([Effect::OutputMessage]),
         Revert | IsReferenceType | IsStrType | SizeOfType | SizeOfVal | SizeOfStr | Eq | Gt
-        | Lt | Gtf | AddrOf | Log | Add | Sub | Mul | Div | And | Or | Xor | Mod | Rsh | Lsh
-        | PtrAdd | PtrSub | Not => HashSet::new(),
+        | Lt | Gtf | AddrOf | Log | Add | Sub | Mul | WrappingAdd | WrappingSub | WrappingMul
+        | CheckedAdd | CheckedSub | CheckedMul | Div | And | Or | Xor | Mod | Rsh | Lsh
+        | PtrAdd | PtrSub | Not | Gas | Realloc | HeapStats | Memcpy | Memcmp => HashSet::new(),
     }
 }
 
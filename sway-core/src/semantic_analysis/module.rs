@@ -16,6 +16,8 @@ impl ty::TyModule {
             span,
             ..
         } = parsed;
+        let engines = ctx.engines;
+        let _module_scope = engines.enter_module(span.source_id().copied());
 
         // Type-check submodules first in order of declaration.
         let mut submodules_res = ok(vec![], vec![], vec![]);
@@ -16,7 +16,10 @@ use super::TraitMap;
 use sway_error::error::CompileError;
 use sway_types::{span::Span, Spanned};
 
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+};
 
 /// Is this a glob (`use foo::*;`) import?
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -28,6 +31,7 @@ pub(crate) enum GlobImport {
 pub(super) type SymbolMap = im::OrdMap<Ident, ty::TyDecl>;
 pub(super) type UseSynonyms = im::HashMap<Ident, (Vec<Ident>, GlobImport, ty::TyDecl, bool)>;
 pub(super) type UseAliases = im::HashMap<String, Ident>;
+pub(super) type UsedSynonyms = Arc<RwLock<HashSet<(PathBuf, Ident)>>>;
 
 /// The set of items that exist within some lexical scope via declaration or importing.
 #[derive(Clone, Debug, Default)]
@@ -45,6 +49,11 @@ pub struct Items {
     /// Aliases are introduced with syntax like `use foo::bar as baz;` syntax, where `baz` is an
     /// alias for `bar`.
     pub(crate) use_aliases: UseAliases,
+    /// The `use_synonyms` which name resolution has gone through, along with the path of the
+    /// module they are in.
+    ///
+    /// This is shared by all the clones of the items, so that a lookup made from any scope counts.
+    pub(crate) used_synonyms: UsedSynonyms,
     /// If there is a storage declaration (which are only valid in contracts), store it here.
     pub(crate) declared_storage: Option<DeclRefStorage>,
 }
@@ -131,6 +140,7 @@ impl Items {
                         errors.push(CompileError::MultipleDefinitionsOfConstant {
                             name: name.clone(),
                             span: name.span(),
+                            previous_span: Some(decl.span()),
                         })
                     }
                     // constant shadowing a constant within function body
@@ -155,6 +165,7 @@ impl Items {
                     ) => errors.push(CompileError::MultipleDefinitionsOfName {
                         name: name.clone(),
                         span: name.span(),
+                        previous_span: Some(decl.span()),
                     }),
                     // Generic parameter shadowing another generic parameter
                     (GenericTypeForFunctionScope { .. }, GenericTypeForFunctionScope { .. }, _) => {
@@ -176,6 +187,22 @@ impl Items {
         ok((), vec![], errors)
     }
 
+    /// Records that name resolution went through the synonym `symbol` of the module at `mod_path`.
+    pub(crate) fn mark_synonym_used(&self, mod_path: &Path, symbol: &Ident) {
+        self.used_synonyms
+            .write()
+            .unwrap()
+            .insert((mod_path.to_vec(), symbol.clone()));
+    }
+
+    /// Whether name resolution went through the synonym `symbol` of the module at `mod_path`.
+    pub(crate) fn is_synonym_used(&self, mod_path: &Path, symbol: &Ident) -> bool {
+        self.used_synonyms
+            .read()
+            .unwrap()
+            .contains(&(mod_path.to_vec(), symbol.clone()))
+    }
+
     pub(crate) fn check_symbol(&self, name: &Ident) -> Result<&ty::TyDecl, CompileError> {
         self.symbols
             .get(name)
@@ -213,6 +240,21 @@ impl Items {
             .get_impl_spans_for_type(engines, type_id)
     }
 
+    /// Returns every trait implemented for `type_id`, including "impl self"
+    /// blocks, with the items each impl block provides.
+    pub fn get_impls_for_type(&self, engines: &Engines, type_id: TypeId) -> Vec<TraitImplInfo> {
+        self.implemented_traits.get_impls_for_type(engines, type_id)
+    }
+
+    /// Returns every trait implemented for the type declared by `ty_decl`.
+    pub fn get_impls_for_decl(&self, engines: &Engines, ty_decl: &TyDecl) -> Vec<TraitImplInfo> {
+        ty_decl
+            .return_type(engines)
+            .value
+            .map(|type_id| self.get_impls_for_type(engines, type_id))
+            .unwrap_or_default()
+    }
+
     pub fn get_impl_spans_for_trait_name(&self, trait_name: &CallPath) -> Vec<Span> {
         self.implemented_traits
             .get_impl_spans_for_trait_name(trait_name)
@@ -11,6 +11,7 @@ pub use module::Module;
 pub use namespace::Namespace;
 pub use root::Root;
 pub(super) use trait_map::TraitMap;
+pub use trait_map::TraitImplInfo;
 
 use sway_types::Ident;
 
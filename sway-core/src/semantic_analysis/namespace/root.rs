@@ -114,7 +114,11 @@ impl Root {
                 .use_aliases
                 .get(symbol.as_str())
                 .unwrap_or(symbol);
-            match module.use_synonyms.get(symbol) {
+            let synonym = module.use_synonyms.get(symbol);
+            if synonym.is_some() {
+                module.mark_synonym_used(mod_path, symbol);
+            }
+            match synonym {
                 Some((_, _, decl @ ty::TyDecl::EnumVariantDecl { .. }, _)) => {
                     ok(decl, vec![], vec![])
                 }
@@ -1,13 +1,14 @@
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
+    collections::HashSet,
+    sync::{Arc, RwLock},
 };
 
 use sway_error::error::CompileError;
 use sway_types::{Ident, Span, Spanned};
 
 use crate::{
-    decl_engine::{DeclEngineGet, DeclEngineInsert},
+    decl_engine::{DeclEngineGet, DeclEngineInsert, DeclRefFunction},
     engine_threading::*,
     error::*,
     language::{
@@ -88,6 +89,31 @@ struct TraitEntry {
 /// Map of trait name and type to [TraitItems].
 type TraitImpls = Vec<TraitEntry>;
 
+/// A trait implemented for a type, as returned by [TraitMap::get_impls_for_type].
+///
+/// Note: "impl self" blocks are included, with the name of the type as the
+/// `trait_name` and no `trait_decl_span`.
+#[derive(Clone, Debug)]
+pub struct TraitImplInfo {
+    pub trait_name: CallPath,
+    /// The span of the trait declaration.
+    pub trait_decl_span: Option<Span>,
+    /// The span of the entire impl block.
+    pub impl_span: Span,
+    /// The items provided by the impl block, sorted by name.
+    pub items: Vec<ty::TyTraitItem>,
+}
+
+impl TraitImplInfo {
+    /// Returns the methods provided by the impl block.
+    pub fn methods(&self) -> impl Iterator<Item = &DeclRefFunction> {
+        self.items.iter().filter_map(|item| match item {
+            ty::TyTraitItem::Fn(decl_ref) => Some(decl_ref),
+            ty::TyTraitItem::Constant(_decl_ref) => None,
+        })
+    }
+}
+
 /// Map holding trait implementations for types.
 ///
 /// Note: "impl self" blocks are considered traits and are stored in the
@@ -95,6 +121,9 @@ type TraitImpls = Vec<TraitEntry>;
 #[derive(Clone, Debug, Default)]
 pub(crate) struct TraitMap {
     trait_impls: TraitImpls,
+    /// The obligations known to be satisfied by `trait_impls`, shared with
+    /// the clones of the map until either is extended.
+    solved: Arc<RwLock<HashSet<SolvedObligation>>>,
 }
 
 impl TraitMap {
@@ -127,14 +156,14 @@ impl TraitMap {
         for item in items.iter() {
             match item {
                 TyImplItem::Fn(decl_ref) => {
-                    if trait_items
-                        .insert(decl_ref.name().clone().to_string(), item.clone())
-                        .is_some()
+                    if let Some(previous) =
+                        trait_items.insert(decl_ref.name().clone().to_string(), item.clone())
                     {
                         // duplicate method name
                         errors.push(CompileError::MultipleDefinitionsOfName {
                             name: decl_ref.name().clone(),
                             span: decl_ref.span(),
+                            previous_span: Some(previous.span()),
                         });
                     }
                 }
@@ -289,7 +318,10 @@ impl TraitMap {
         };
         let entry = TraitEntry { key, value };
         let trait_impls: TraitImpls = vec![entry];
-        let trait_map = TraitMap { trait_impls };
+        let trait_map = TraitMap {
+            trait_impls,
+            ..Default::default()
+        };
 
         self.extend(trait_map, engines);
     }
@@ -397,7 +429,10 @@ impl TraitMap {
                     .value
                     .trait_items
                     .extend(oe.value.trait_items.into_iter()),
-                Err(pos) => self.trait_impls.insert(pos, oe),
+                Err(pos) => {
+                    self.trait_impls.insert(pos, oe);
+                    self.solved = Default::default();
+                }
             }
         }
     }
@@ -833,32 +868,48 @@ impl TraitMap {
         items
     }
 
-    pub(crate) fn get_trait_names_for_type(
+    /// Find the entries in `self` that are equivalent to `type_id` and return
+    /// the traits they implement, with their items.
+    ///
+    /// Notes:
+    /// - equivalency is defined (1) based on whether the types contains types
+    ///     that are dynamic and can change and (2) whether the types hold
+    ///     equivalency after (1) is fulfilled
+    /// - this method does not translate types from the found entries to the
+    ///     `type_id` (like in `filter_by_type()`). This is because the only
+    ///     entries that qualify as hits are equivalents of `type_id`
+    pub(crate) fn get_impls_for_type(
         &self,
         engines: &Engines,
         type_id: TypeId,
-    ) -> Vec<CallPath> {
+    ) -> Vec<TraitImplInfo> {
         let type_engine = engines.te();
         let unify_check = UnifyCheck::non_dynamic_equality(engines);
-        let mut trait_names = vec![];
+        let mut impls = vec![];
         // small performance gain in bad case
         if type_engine
             .get(type_id)
             .eq(&TypeInfo::ErrorRecovery, engines)
         {
-            return trait_names;
+            return impls;
         }
         for entry in self.trait_impls.iter() {
             if unify_check.check(type_id, entry.key.type_id) {
-                let trait_call_path = CallPath {
-                    prefixes: entry.key.name.prefixes.clone(),
-                    suffix: entry.key.name.suffix.name.clone(),
-                    is_absolute: entry.key.name.is_absolute,
-                };
-                trait_names.push(trait_call_path);
+                let mut items = entry.value.trait_items.iter().collect::<Vec<_>>();
+                items.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+                impls.push(TraitImplInfo {
+                    trait_name: CallPath {
+                        prefixes: entry.key.name.prefixes.clone(),
+                        suffix: entry.key.name.suffix.name.clone(),
+                        is_absolute: entry.key.name.is_absolute,
+                    },
+                    trait_decl_span: entry.key.trait_decl_span.clone(),
+                    impl_span: entry.value.impl_span.clone(),
+                    items: items.into_iter().map(|(_, item)| item.clone()).collect(),
+                });
             }
         }
-        trait_names
+        impls
     }
 
     /// Checks to see if the trait constraints are satisfied for a given type.
@@ -868,98 +919,319 @@ impl TraitMap {
         constraints: &[TraitConstraint],
         access_span: &Span,
         engines: &Engines,
+    ) -> CompileResult<()> {
+        self.check_obligations(type_id, constraints, vec![], access_span, engines)
+    }
+
+    /// Checks to see if the type `type_id`, for which the trait `trait_name`
+    /// is being implemented, implements the `supertraits` of that trait.
+    pub(crate) fn check_if_supertraits_are_satisfied_for_type(
+        &self,
+        type_id: TypeId,
+        trait_name: &CallPath,
+        supertraits: &[TraitConstraint],
+        access_span: &Span,
+        engines: &Engines,
+    ) -> CompileResult<()> {
+        let required_by = vec![(
+            engines.help_out(type_id).to_string(),
+            trait_name.suffix.to_string(),
+        )];
+        self.check_obligations(type_id, supertraits, required_by, access_span, engines)
+    }
+
+    /// Solves the obligations of `type_id` to implement each of the
+    /// `constraints`, reporting the innermost unsatisfied obligation of each,
+    /// along with the obligations which required it and `required_by`.
+    fn check_obligations(
+        &self,
+        type_id: TypeId,
+        constraints: &[TraitConstraint],
+        required_by: Vec<(String, String)>,
+        access_span: &Span,
+        engines: &Engines,
     ) -> CompileResult<()> {
         let mut warnings = vec![];
         let mut errors = vec![];
 
-        let type_engine = engines.te();
-        let _decl_engine = engines.de();
-        let unify_check = UnifyCheck::non_dynamic_equality(engines);
-
         // resolving trait constraits require a concrete type, we need to default numeric to u64
         check!(
-            type_engine.decay_numeric(engines, type_id, access_span),
+            engines.te().decay_numeric(engines, type_id, access_span),
             return err(warnings, errors),
             warnings,
             errors
         );
 
-        let all_impld_traits: BTreeMap<Ident, TypeId> = self
-            .trait_impls
-            .iter()
-            .filter_map(|e| {
-                let key = &e.key;
-                let suffix = &key.name.suffix;
-                let map_trait_type_id = type_engine.insert(
-                    engines,
-                    TypeInfo::Custom {
-                        call_path: suffix.name.clone().into(),
-                        type_arguments: if suffix.args.is_empty() {
-                            None
-                        } else {
-                            Some(suffix.args.to_vec())
-                        },
-                    },
-                );
-                if unify_check.check(type_id, key.type_id) {
-                    Some((suffix.name.clone(), map_trait_type_id))
-                } else {
+        let mut solver = TraitSolver::new(self, engines);
+        for constraint in constraints.iter() {
+            if let Err(unsatisfied) = solver.solve(type_id, constraint) {
+                errors.push(CompileError::TraitConstraintNotSatisfied {
+                    ty: engines.help_out(unsatisfied.type_id).to_string(),
+                    trait_name: unsatisfied.constraint.trait_name.suffix.to_string(),
+                    span: access_span.clone(),
+                    constraint_span: unsatisfied.constraint.trait_name.suffix.span(),
+                    required_by: unsatisfied
+                        .required_by
+                        .iter()
+                        .map(|(type_id, constraint)| {
+                            (
+                                engines.help_out(type_id).to_string(),
+                                constraint.trait_name.suffix.to_string(),
+                            )
+                        })
+                        .chain(required_by.iter().cloned())
+                        .collect(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            ok((), warnings, errors)
+        } else {
+            err(warnings, errors)
+        }
+    }
+
+    /// Returns the entries of `self` which implement the trait of `constraint`
+    /// for exactly the type `type_id`.
+    fn get_impls_for_obligation<'a>(
+        &'a self,
+        type_id: TypeId,
+        constraint: &'a TraitConstraint,
+        engines: &'a Engines,
+    ) -> impl Iterator<Item = &'a TraitEntry> {
+        let type_engine = engines.te();
+        let unify_check = UnifyCheck::non_dynamic_equality(engines);
+        let constraint_type_id = type_engine.insert(
+            engines,
+            TypeInfo::Custom {
+                call_path: constraint.trait_name.suffix.clone().into(),
+                type_arguments: if constraint.type_arguments.is_empty() {
                     None
+                } else {
+                    Some(constraint.type_arguments.clone())
+                },
+            },
+        );
+        self.trait_impls.iter().filter(move |entry| {
+            let suffix = &entry.key.name.suffix;
+            if suffix.name != constraint.trait_name.suffix
+                || !unify_check.check(type_id, entry.key.type_id)
+            {
+                return false;
+            }
+            let map_trait_type_id = type_engine.insert(
+                engines,
+                TypeInfo::Custom {
+                    call_path: suffix.name.clone().into(),
+                    type_arguments: if suffix.args.is_empty() {
+                        None
+                    } else {
+                        Some(suffix.args.to_vec())
+                    },
+                },
+            );
+            unify_check.check(constraint_type_id, map_trait_type_id)
+        })
+    }
+
+    /// Returns the obligations that the implementation `entry` of a trait for
+    /// the type `type_id` depends on.
+    ///
+    /// When a generic impl block such as `impl<T> Eq for Wrapper<T> where T: Eq`
+    /// applies to `Wrapper<u64>`, its items are re-inserted under
+    /// `Wrapper<u64>` by [TraitMap::insert_for_type], and the resulting
+    /// entry only holds if `u64` implements `Eq`. The constraints are found on
+    /// the generic entries of the same impl block, which `type_id` is a subset
+    /// of.
+    fn get_nested_obligations(
+        &self,
+        type_id: TypeId,
+        entry: &TraitEntry,
+        engines: &Engines,
+    ) -> Vec<(TypeId, TraitConstraint)> {
+        let type_engine = engines.te();
+        let decl_engine = engines.de();
+        let unify_check = UnifyCheck::constraint_subset(engines);
+        let mut obligations = vec![];
+        for generic_entry in self.trait_impls.iter().filter(|generic_entry| {
+            generic_entry.value.impl_span == entry.value.impl_span
+                && generic_entry.key.name.suffix.name == entry.key.name.suffix.name
+                && generic_entry.key.type_id != type_id
+                && unify_check.check(type_id, generic_entry.key.type_id)
+        }) {
+            let type_mapping = TypeSubstMap::from_superset_and_subset(
+                type_engine,
+                decl_engine,
+                generic_entry.key.type_id,
+                type_id,
+            );
+            let mut generics = type_engine
+                .get(generic_entry.key.type_id)
+                .extract_inner_types(engines);
+            generics.insert(generic_entry.key.type_id);
+            for generic in generics {
+                let TypeInfo::UnknownGeneric {
+                    trait_constraints, ..
+                } = type_engine.get(generic)
+                else {
+                    continue;
+                };
+                let Some(nested_type_id) = type_mapping.find_match(generic, engines) else {
+                    continue;
+                };
+                for mut constraint in trait_constraints.into_iter() {
+                    constraint.subst(&type_mapping, engines);
+                    obligations.push((nested_type_id, constraint));
                 }
-            })
-            .collect();
+            }
+        }
+        obligations
+    }
+}
+
+/// An obligation of a type to implement a trait which couldn't be satisfied.
+struct UnsatisfiedObligation {
+    type_id: TypeId,
+    constraint: TraitConstraint,
+    /// The obligations which required this one, innermost first.
+    required_by: Vec<(TypeId, TraitConstraint)>,
+}
+
+/// The type, trait name and trait type arguments of a solved obligation.
+type SolvedObligation = (TypeId, Ident, Vec<TypeId>);
+
+/// Solves the obligations of types to implement traits, given the impls of a
+/// [TraitMap].
+///
+/// An obligation is satisfied by a generic type whose constraints include the
+/// trait, or by an impl of the trait for the type whose own obligations, from
+/// the constraints and where clauses of its type parameters, are satisfied in
+/// turn. Obligations on types which can't change are cached in the
+/// [TraitMap].
+struct TraitSolver<'a> {
+    trait_map: &'a TraitMap,
+    engines: &'a Engines,
+    /// The obligations being solved, to break cycles between impls.
+    in_progress: Vec<(TypeId, TraitConstraint)>,
+}
 
-        let required_traits: BTreeMap<Ident, TypeId> = constraints
+impl<'a> TraitSolver<'a> {
+    fn new(trait_map: &'a TraitMap, engines: &'a Engines) -> Self {
+        TraitSolver {
+            trait_map,
+            engines,
+            in_progress: vec![],
+        }
+    }
+
+    fn solve(
+        &mut self,
+        type_id: TypeId,
+        constraint: &TraitConstraint,
+    ) -> Result<(), UnsatisfiedObligation> {
+        let engines = self.engines;
+        let type_engine = engines.te();
+
+        let solved_obligation = self.solved_obligation(type_id, constraint);
+        if let Some(solved_obligation) = &solved_obligation {
+            if self
+                .trait_map
+                .solved
+                .read()
+                .unwrap()
+                .contains(solved_obligation)
+            {
+                return Ok(());
+            }
+        }
+
+        match type_engine.get(type_id) {
+            // the error has already been reported
+            TypeInfo::ErrorRecovery => return Ok(()),
+            TypeInfo::UnknownGeneric {
+                trait_constraints, ..
+            } if trait_constraints
+                .iter()
+                .any(|c| c.trait_name.suffix == constraint.trait_name.suffix) =>
+            {
+                return Ok(())
+            }
+            _ => {}
+        }
+
+        // An obligation which depends on itself holds as long as the
+        // obligations it depends on along the way do.
+        if self
+            .in_progress
             .iter()
-            .map(|c| {
-                let TraitConstraint {
-                    trait_name: constraint_trait_name,
-                    type_arguments: constraint_type_arguments,
-                } = c;
-                let constraint_type_id = type_engine.insert(
-                    engines,
-                    TypeInfo::Custom {
-                        call_path: constraint_trait_name.suffix.clone().into(),
-                        type_arguments: if constraint_type_arguments.is_empty() {
-                            None
-                        } else {
-                            Some(constraint_type_arguments.clone())
-                        },
-                    },
-                );
-                (c.trait_name.suffix.clone(), constraint_type_id)
-            })
-            .collect();
+            .any(|(t, c)| *t == type_id && c.eq(constraint, engines))
+        {
+            return Ok(());
+        }
 
-        let relevant_impld_traits: BTreeMap<Ident, TypeId> = all_impld_traits
-            .into_iter()
-            .filter(|(impld_trait_name, impld_trait_type_id)| {
-                match required_traits.get(impld_trait_name) {
-                    Some(constraint_type_id) => {
-                        unify_check.check(*constraint_type_id, *impld_trait_type_id)
-                    }
-                    _ => false,
+        let impls = self
+            .trait_map
+            .get_impls_for_obligation(type_id, constraint, engines)
+            .collect::<Vec<_>>();
+        self.in_progress.push((type_id, constraint.clone()));
+        let mut result = Err(UnsatisfiedObligation {
+            type_id,
+            constraint: constraint.clone(),
+            required_by: vec![],
+        });
+        for entry in impls {
+            let nested_obligations = self
+                .trait_map
+                .get_nested_obligations(type_id, entry, engines);
+            match nested_obligations
+                .iter()
+                .try_for_each(|(nested_type_id, nested)| self.solve(*nested_type_id, nested))
+            {
+                Ok(()) => {
+                    result = Ok(());
+                    break;
                 }
-            })
-            .collect();
-
-        let required_traits_names: BTreeSet<Ident> = required_traits.keys().cloned().collect();
-        let relevant_impld_traits_names: BTreeSet<Ident> =
-            relevant_impld_traits.keys().cloned().collect();
-
-        for trait_name in required_traits_names.difference(&relevant_impld_traits_names) {
-            // TODO: use a better span
-            errors.push(CompileError::TraitConstraintNotSatisfied {
-                ty: engines.help_out(type_id).to_string(),
-                trait_name: trait_name.to_string(),
-                span: access_span.clone(),
-            });
+                Err(mut unsatisfied) => {
+                    unsatisfied.required_by.push((type_id, constraint.clone()));
+                    result = Err(unsatisfied);
+                }
+            }
         }
+        self.in_progress.pop();
 
-        if errors.is_empty() {
-            ok((), warnings, errors)
-        } else {
-            err(warnings, errors)
+        if let (Ok(()), Some(solved_obligation)) = (&result, solved_obligation) {
+            self.trait_map
+                .solved
+                .write()
+                .unwrap()
+                .insert(solved_obligation);
         }
+        result
+    }
+
+    /// Returns the key of the obligation of `type_id` to implement the trait of
+    /// `constraint` in the cache, if none of its types can change.
+    fn solved_obligation(
+        &self,
+        type_id: TypeId,
+        constraint: &TraitConstraint,
+    ) -> Option<SolvedObligation> {
+        let type_engine = self.engines.te();
+        let decl_engine = self.engines.de();
+        let type_ids = std::iter::once(type_id)
+            .chain(constraint.type_arguments.iter().map(|arg| arg.type_id))
+            .collect::<Vec<_>>();
+        if type_ids
+            .iter()
+            .any(|type_id| type_engine.get(*type_id).can_change(decl_engine))
+        {
+            return None;
+        }
+        Some((
+            type_id,
+            constraint.trait_name.suffix.clone(),
+            type_ids[1..].to_vec(),
+        ))
     }
 }
@@ -9,6 +9,7 @@ use crate::{
 };
 
 use sway_error::error::CompileError;
+use sway_types::constants::DEFAULT_ENTRY_POINT_FN_NAME;
 use sway_types::integer_bits::IntegerBits;
 use sway_types::Spanned;
 use sway_types::{ident::Ident, span::Span};
@@ -23,10 +24,11 @@ pub(crate) fn order_ast_nodes_by_dependency(
 ) -> CompileResult<Vec<AstNode>> {
     let type_engine = engines.te();
 
+    let std_box = StdBox::from_nodes(&nodes);
     let decl_dependencies = DependencyMap::from_iter(
         nodes
             .iter()
-            .filter_map(|node| Dependencies::gather_from_decl_node(engines, node)),
+            .filter_map(|node| Dependencies::gather_from_decl_node(engines, &std_box, node)),
     );
 
     // Check here for recursive calls now that we have a nice map of the dependencies to help us.
@@ -279,6 +281,7 @@ struct Dependencies {
 impl Dependencies {
     fn gather_from_decl_node(
         engines: &Engines,
+        std_box: &StdBox,
         node: &AstNode,
     ) -> Option<(DependentSymbol, Dependencies)> {
         let type_engine = engines.te();
@@ -289,13 +292,58 @@ impl Dependencies {
                     Dependencies {
                         deps: HashSet::new(),
                     }
-                    .gather_from_decl(engines, decl),
+                    .gather_from_decl_in_module(engines, std_box, decl),
                 )
             }),
             _ => None,
         }
     }
 
+    fn gather_from_decl_in_module(
+        self,
+        engines: &Engines,
+        std_box: &StdBox,
+        decl: &Declaration,
+    ) -> Self {
+        match decl {
+            // A type which isn't generic may refer to itself through std's `Box`, which only holds
+            // a pointer to it. Only generic types and types holding themselves are recursive.
+            Declaration::StructDeclaration(StructDeclaration {
+                name,
+                fields,
+                type_parameters,
+                ..
+            }) => {
+                let mut deps = self.gather_from_decl(engines, decl);
+                if type_parameters.is_empty()
+                    && !fields.iter().any(|field| {
+                        refers_to_type(engines, name, &field.type_argument, Some(std_box))
+                    })
+                {
+                    deps.deps.remove(&DependentSymbol::Symbol(name.clone()));
+                }
+                deps
+            }
+            Declaration::EnumDeclaration(EnumDeclaration {
+                name,
+                variants,
+                type_parameters,
+                ..
+            }) => {
+                let mut deps = self.gather_from_decl(engines, decl);
+                if type_parameters.is_empty()
+                    && !variants.iter().any(|variant| {
+                        refers_to_type(engines, name, &variant.type_argument, Some(std_box))
+                    })
+                {
+                    deps.deps.remove(&DependentSymbol::Symbol(name.clone()));
+                }
+                deps
+            }
+            _ => self.gather_from_decl(engines, decl),
+        }
+    }
+
     fn gather_from_decl(self, engines: &Engines, decl: &Declaration) -> Self {
         match decl {
             Declaration::VariableDeclaration(VariableDeclaration {
@@ -306,7 +354,18 @@ impl Dependencies {
                 .gather_from_type_argument(engines, type_ascription)
                 .gather_from_expr(engines, body),
             Declaration::ConstantDeclaration(decl) => self.gather_from_constant_decl(engines, decl),
-            Declaration::FunctionDeclaration(fn_decl) => self.gather_from_fn_decl(engines, fn_decl),
+            Declaration::FunctionDeclaration(fn_decl) => {
+                let mut deps = self.gather_from_fn_decl(engines, fn_decl);
+                // A function which isn't generic may call itself, which doesn't affect the order of
+                // declarations. Only `main` and generic functions must not be recursive.
+                if fn_decl.type_parameters.is_empty()
+                    && fn_decl.name.as_str() != DEFAULT_ENTRY_POINT_FN_NAME
+                {
+                    deps.deps
+                        .remove(&DependentSymbol::Fn(fn_decl.name.clone(), None));
+                }
+                deps
+            }
             Declaration::StructDeclaration(StructDeclaration {
                 fields,
                 type_parameters,
@@ -437,6 +496,8 @@ impl Dependencies {
             return_type,
             body,
             type_parameters,
+            requires,
+            ensures,
             ..
         } = fn_decl;
         self.gather_from_iter(parameters.iter(), |deps, param| {
@@ -445,6 +506,9 @@ impl Dependencies {
         .gather_from_type_argument(engines, return_type)
         .gather_from_block(engines, body)
         .gather_from_type_parameters(type_parameters)
+        .gather_from_iter(requires.iter().chain(ensures), |deps, condition| {
+            deps.gather_from_expr(engines, condition)
+        })
     }
 
     fn gather_from_expr(self, engines: &Engines, expr: &Expression) -> Self {
@@ -724,6 +788,127 @@ impl Dependencies {
     }
 }
 
+/// Whether `type_argument` refers to the type `name`. Given the module's `std_box`, the type
+/// arguments of std's `Box` aren't looked into: a type holding a `Box<T>` doesn't hold a `T`, only
+/// a pointer to it.
+pub(crate) fn refers_to_type(
+    engines: &Engines,
+    name: &Ident,
+    type_argument: &TypeArgument,
+    std_box: Option<&StdBox>,
+) -> bool {
+    match engines.te().get(type_argument.type_id) {
+        TypeInfo::Custom {
+            call_path,
+            type_arguments,
+        } => {
+            call_path.suffix == *name
+                || (!std_box.map_or(false, |std_box| std_box.is(&call_path))
+                    && type_arguments
+                        .iter()
+                        .flatten()
+                        .any(|type_argument| refers_to_type(engines, name, type_argument, std_box)))
+        }
+        TypeInfo::Tuple(elems) => elems
+            .iter()
+            .any(|elem| refers_to_type(engines, name, elem, std_box)),
+        TypeInfo::Array(elem_type, _) => refers_to_type(engines, name, &elem_type, std_box),
+        TypeInfo::Alias { ty, .. } => refers_to_type(engines, name, &ty, std_box),
+        _ => false,
+    }
+}
+
+/// The names under which a module refers to `std::boxed::Box` and its module, from its `use`
+/// statements. A name the module declares itself shadows them.
+pub(crate) struct StdBox {
+    box_names: HashSet<String>,
+    module_names: HashSet<String>,
+}
+
+impl StdBox {
+    const MODULE_PATH: [&'static str; 2] = ["std", "boxed"];
+    const NAME: &'static str = "Box";
+
+    fn from_nodes(nodes: &[AstNode]) -> Self {
+        let mut std_box = StdBox {
+            box_names: HashSet::new(),
+            module_names: HashSet::new(),
+        };
+        let is_path = |call_path: &[Ident], path: &[&str]| {
+            call_path.len() == path.len()
+                && call_path.iter().zip(path).all(|(l, r)| l.as_str() == *r)
+        };
+        for node in nodes {
+            if let AstNodeContent::UseStatement(UseStatement {
+                call_path,
+                import_type,
+                alias,
+                ..
+            }) = &node.content
+            {
+                let alias = alias.as_ref().map(|alias| alias.as_str().to_string());
+                match import_type {
+                    ImportType::Item(item)
+                        if item.as_str() == Self::NAME
+                            && is_path(call_path, &Self::MODULE_PATH) =>
+                    {
+                        std_box
+                            .box_names
+                            .insert(alias.unwrap_or_else(|| Self::NAME.to_string()));
+                    }
+                    ImportType::Item(item)
+                        if item.as_str() == Self::MODULE_PATH[1]
+                            && is_path(call_path, &Self::MODULE_PATH[..1]) =>
+                    {
+                        std_box
+                            .module_names
+                            .insert(alias.unwrap_or_else(|| item.as_str().to_string()));
+                    }
+                    ImportType::SelfImport(_) if is_path(call_path, &Self::MODULE_PATH) => {
+                        std_box
+                            .module_names
+                            .insert(alias.unwrap_or_else(|| Self::MODULE_PATH[1].to_string()));
+                    }
+                    ImportType::Star if is_path(call_path, &Self::MODULE_PATH) => {
+                        std_box.box_names.insert(Self::NAME.to_string());
+                    }
+                    _ => {}
+                }
+            }
+        }
+        for node in nodes {
+            if let AstNodeContent::Declaration(decl) = &node.content {
+                let name = match decl {
+                    Declaration::StructDeclaration(decl) => &decl.name,
+                    Declaration::EnumDeclaration(decl) => &decl.name,
+                    Declaration::TypeAliasDeclaration(decl) => &decl.name,
+                    _ => continue,
+                };
+                std_box.box_names.remove(name.as_str());
+            }
+        }
+        std_box
+    }
+
+    fn is(&self, call_path: &CallPath) -> bool {
+        match call_path.prefixes.as_slice() {
+            [] => self.box_names.contains(call_path.suffix.as_str()),
+            [module] => {
+                call_path.suffix.as_str() == Self::NAME
+                    && self.module_names.contains(module.as_str())
+            }
+            prefixes => {
+                call_path.suffix.as_str() == Self::NAME
+                    && prefixes.len() == Self::MODULE_PATH.len()
+                    && prefixes
+                        .iter()
+                        .zip(Self::MODULE_PATH)
+                        .all(|(l, r)| l.as_str() == r)
+            }
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // Most declarations can be uniquely identified by a name str.  ImplSelf and ImplTrait don't have a
 // name of their own though.  They can be identified as being an impl of another type, so we make
@@ -0,0 +1,155 @@
+// Defaulting of integer locals whose width was never inferred.
+//
+// Integer literals start out with the `Numeric` type, which takes the width of whatever the
+// literal is unified with. A local initialized with a literal, e.g. `let x = 0;`, whose uses never
+// constrain it, e.g. because it's only passed to generic functions, is still `Numeric` once its
+// function is type checked. Such locals default to `u64`, so that the rest of the compiler only
+// sees concrete integer types. This is what was intended most of the time, so the warning about it
+// is allowed by default.
+
+use crate::{language::ty, Engines};
+use sway_error::warning::{CompileWarning, Warning};
+use sway_types::Spanned;
+
+pub(crate) fn analyze_program(engines: &Engines, prog: &ty::TyProgram) -> Vec<CompileWarning> {
+    let mut analysis = Analysis {
+        engines,
+        warnings: vec![],
+    };
+    let modules = std::iter::once(&prog.root).chain(
+        prog.root
+            .submodules_recursive()
+            .map(|(_, submodule)| &submodule.module),
+    );
+    for module in modules {
+        for fn_decl in module.functions(engines.de()) {
+            analysis.code_block(&fn_decl.body);
+        }
+    }
+    analysis.warnings
+}
+
+struct Analysis<'eng> {
+    engines: &'eng Engines,
+    warnings: Vec<CompileWarning>,
+}
+
+impl<'eng> Analysis<'eng> {
+    fn code_block(&mut self, code_block: &ty::TyCodeBlock) {
+        for ast_node in &code_block.contents {
+            match &ast_node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                    self.expression(&var_decl.body);
+                    self.variable_decl(var_decl);
+                }
+                ty::TyAstNodeContent::Declaration(_) => (),
+                ty::TyAstNodeContent::Expression(expr)
+                | ty::TyAstNodeContent::ImplicitReturnExpression(expr) => self.expression(expr),
+                ty::TyAstNodeContent::SideEffect(_) => (),
+            }
+        }
+    }
+
+    fn variable_decl(&mut self, var_decl: &ty::TyVariableDecl) {
+        let type_engine = self.engines.te();
+        let decl_engine = self.engines.de();
+        if !type_engine.contains_numeric(decl_engine, var_decl.return_type) {
+            return;
+        }
+        let span = var_decl.name.span();
+        for type_id in [var_decl.return_type, var_decl.body.return_type] {
+            // Decaying only fails on a mismatch, which can't happen with a numeric type.
+            let _ = type_engine.decay_numeric(self.engines, type_id, &span);
+        }
+        // Locals named with a leading `_` are either meant to be unused or introduced by
+        // desugaring, e.g. of `match` expressions, which report through the variables they are
+        // initialized from.
+        if !var_decl.name.as_str().starts_with('_') {
+            self.warnings.push(CompileWarning {
+                span,
+                warning_content: Warning::NumericFallback {
+                    name: var_decl.name.clone(),
+                },
+            });
+        }
+    }
+
+    fn expression(&mut self, expr: &ty::TyExpression) {
+        use crate::ty::TyExpressionVariant::*;
+        match &expr.expression {
+            Literal(_)
+            | ConstantExpression { .. }
+            | VariableExpression { .. }
+            | FunctionParameter
+            | StorageAccess(_)
+            | Break
+            | Continue
+            | AbiName(_) => (),
+            Reassignment(reassignment) => self.expression(&reassignment.rhs),
+            CodeBlock(code_block) => self.code_block(code_block),
+            LazyOperator { lhs, rhs, .. } => {
+                self.expression(lhs);
+                self.expression(rhs);
+            }
+            ArrayIndex { prefix, index } => {
+                self.expression(prefix);
+                self.expression(index);
+            }
+            FunctionApplication { arguments, .. } => {
+                for (_, arg) in arguments {
+                    self.expression(arg);
+                }
+            }
+            IntrinsicFunction(intrinsic) => {
+                for arg in &intrinsic.arguments {
+                    self.expression(arg);
+                }
+            }
+            Tuple { fields: exprs }
+            | Array {
+                contents: exprs, ..
+            } => {
+                for expr in exprs {
+                    self.expression(expr);
+                }
+            }
+            StructExpression { fields, .. } => {
+                for field in fields {
+                    self.expression(&field.value);
+                }
+            }
+            StructFieldAccess { prefix: expr, .. }
+            | TupleElemAccess { prefix: expr, .. }
+            | Return(expr)
+            | EnumTag { exp: expr }
+            | UnsafeDowncast { exp: expr, .. }
+            | AbiCast { address: expr, .. } => self.expression(expr),
+            EnumInstantiation { contents, .. } => {
+                if let Some(expr) = contents {
+                    self.expression(expr);
+                }
+            }
+            MatchExp { desugared, .. } => self.expression(desugared),
+            IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                self.expression(condition);
+                self.expression(then);
+                if let Some(else_expr) = r#else {
+                    self.expression(else_expr);
+                }
+            }
+            WhileLoop { condition, body } => {
+                self.expression(condition);
+                self.code_block(body);
+            }
+            AsmExpression { registers, .. } => {
+                for initializer in registers.iter().filter_map(|reg| reg.initializer.as_ref()) {
+                    self.expression(initializer);
+                }
+            }
+        }
+    }
+}
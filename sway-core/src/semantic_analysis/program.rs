@@ -34,8 +34,10 @@ impl ty::TyProgram {
                 declarations,
                 configurables,
                 storage_slots: vec![],
+                storage_layout: vec![],
                 logged_types: vec![],
                 messages_types: vec![],
+                revert_codes: vec![],
             })
         })
     }
@@ -74,9 +76,11 @@ impl ty::TyProgram {
                         // Sort the slots to standardize the output. Not strictly required by the
                         // spec.
                         storage_slots.sort();
+                        let storage_layout = decl.get_storage_layout(engines);
                         ok(
                             Self {
                                 storage_slots,
+                                storage_layout,
                                 ..self
                             },
                             warnings,
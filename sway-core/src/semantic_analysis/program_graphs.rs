@@ -0,0 +1,907 @@
+//! The control-flow graph of each function of a typed program, and the call graph of the
+//! program, for tools which audit or visualize programs without parsing Sway.
+//!
+//! The control-flow graphs have a node per statement and per condition of `if` expressions and
+//! `while` loops. `match` expressions appear as the chain of `if` expressions they're desugared
+//! to, and the lazy operators `&&` and `||` aren't split into branches.
+
+use std::{collections::HashMap, fmt::Write, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sway_types::Span;
+
+use crate::{decl_engine::*, language::ty, source_map::LocationRange, Engines};
+
+/// The longest label of a node, in characters, past which it's cut short.
+const MAX_LABEL_LENGTH: usize = 48;
+
+/// The index of the entry node of every function graph.
+const ENTRY: usize = 0;
+/// The index of the exit node of every function graph.
+const EXIT: usize = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgramGraphs {
+    /// The control-flow graph of each function of the program, methods included.
+    pub functions: Vec<FunctionGraph>,
+    pub call_graph: CallGraph,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionGraph {
+    /// The name of the function, prefixed by the type implementing it for methods.
+    pub name: String,
+    /// The source file the function is declared in.
+    pub path: Option<PathBuf>,
+    /// The range of the declaration of the function in its source file.
+    pub range: Option<LocationRange>,
+    /// The nodes of the graph, of which the first is the entry and the second the exit of the
+    /// function.
+    pub nodes: Vec<CfgNode>,
+    pub edges: Vec<CfgEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CfgNode {
+    pub kind: CfgNodeKind,
+    /// The first line of the source of the node.
+    pub label: String,
+    pub range: Option<LocationRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CfgNodeKind {
+    Entry,
+    Exit,
+    Statement,
+    /// The condition of an `if` expression, followed by a `true` and a `false` edge.
+    Branch,
+    /// The condition of a `while` loop, followed by a `true` edge into its body.
+    Loop,
+    Return,
+    Break,
+    Continue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CfgEdge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: CfgEdgeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CfgEdgeKind {
+    Next,
+    True,
+    False,
+}
+
+/// The functions of the program, followed by those of its dependencies which it calls, and the
+/// calls between them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallGraph {
+    pub functions: Vec<CallGraphFunction>,
+    pub calls: Vec<Call>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallGraphFunction {
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub range: Option<LocationRange>,
+    /// Whether the function is declared outside of the program, and so has no control-flow
+    /// graph.
+    pub external: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Call {
+    /// The index of the calling function in the call graph.
+    pub caller: usize,
+    /// The index of the called function in the call graph.
+    pub callee: usize,
+    /// The ranges of the calls in the source file of the caller.
+    pub sites: Vec<LocationRange>,
+}
+
+impl ProgramGraphs {
+    pub fn new(engines: &Engines, program: &ty::TyProgram) -> Self {
+        let modules = std::iter::once(&program.root).chain(
+            program
+                .root
+                .submodules_recursive()
+                .map(|(_, submodule)| &submodule.module),
+        );
+        let fn_decls = modules
+            .flat_map(|module| module.functions(engines.de()))
+            .collect::<Vec<_>>();
+
+        // The functions of the call graph by the span of their declaration, which the instances
+        // of generic functions share.
+        let mut call_graph = CallGraph::default();
+        let mut call_graph_indices = HashMap::new();
+        for fn_decl in &fn_decls {
+            call_graph_indices.insert(fn_decl.span.clone(), call_graph.functions.len());
+            call_graph
+                .functions
+                .push(call_graph_function(engines, fn_decl, false));
+        }
+
+        let functions = fn_decls
+            .iter()
+            .map(|fn_decl| {
+                let mut builder = GraphBuilder {
+                    engines,
+                    graph: FunctionGraph {
+                        name: function_name(engines, fn_decl),
+                        path: path(engines, &fn_decl.span),
+                        range: Some(range(&fn_decl.span)),
+                        nodes: vec![],
+                        edges: vec![],
+                    },
+                    loops: vec![],
+                    caller: call_graph_indices[&fn_decl.span],
+                    call_graph: &mut call_graph,
+                    call_graph_indices: &mut call_graph_indices,
+                };
+                builder.add_node(CfgNodeKind::Entry, Some(&fn_decl.span), "", &[]);
+                builder.graph.nodes.push(CfgNode {
+                    kind: CfgNodeKind::Exit,
+                    label: "exit".to_string(),
+                    range: None,
+                });
+                let leaves = builder.code_block(&fn_decl.body, vec![(ENTRY, CfgEdgeKind::Next)]);
+                builder.connect(&leaves, EXIT);
+                builder.graph
+            })
+            .collect();
+
+        ProgramGraphs {
+            functions,
+            call_graph,
+        }
+    }
+
+    /// The control-flow graphs in the GraphViz DOT format, with a cluster per function.
+    pub fn cfg_dot(&self) -> String {
+        let mut dot = "digraph cfg {\n    node [shape = box];\n".to_string();
+        for (fn_idx, function) in self.functions.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{fn_idx} {{");
+            let _ = writeln!(dot, "        label = {:?};", function.name);
+            for (node_idx, node) in function.nodes.iter().enumerate() {
+                let shape = match node.kind {
+                    CfgNodeKind::Entry | CfgNodeKind::Exit => "oval",
+                    CfgNodeKind::Branch | CfgNodeKind::Loop => "diamond",
+                    _ => "box",
+                };
+                let _ = writeln!(
+                    dot,
+                    "        f{fn_idx}_{node_idx} [label = {:?}, shape = {shape}];",
+                    node.label
+                );
+            }
+            for edge in &function.edges {
+                let label = match edge.kind {
+                    CfgEdgeKind::Next => "",
+                    CfgEdgeKind::True => " [label = \"true\"]",
+                    CfgEdgeKind::False => " [label = \"false\"]",
+                };
+                let _ = writeln!(
+                    dot,
+                    "        f{fn_idx}_{} -> f{fn_idx}_{}{label};",
+                    edge.from, edge.to
+                );
+            }
+            dot.push_str("    }\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The call graph in the GraphViz DOT format, in which the functions declared outside of the
+    /// program are dashed.
+    pub fn call_graph_dot(&self) -> String {
+        let mut dot = "digraph call_graph {\n    node [shape = box];\n".to_string();
+        for (idx, function) in self.call_graph.functions.iter().enumerate() {
+            let style = if function.external {
+                ", style = dashed"
+            } else {
+                ""
+            };
+            let _ = writeln!(dot, "    n{idx} [label = {:?}{style}];", function.name);
+        }
+        for call in &self.call_graph.calls {
+            let _ = writeln!(
+                dot,
+                "    n{} -> n{} [label = \"{}\"];",
+                call.caller,
+                call.callee,
+                call.sites.len()
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The nodes which flow into the next node added to a graph, by the kind of their edge to it.
+type Leaves = Vec<(usize, CfgEdgeKind)>;
+
+struct GraphBuilder<'a> {
+    engines: &'a Engines,
+    graph: FunctionGraph,
+    /// The condition of each loop the walk is within, with the `break` nodes out of it.
+    loops: Vec<(usize, Vec<usize>)>,
+    /// The index of the function in the call graph.
+    caller: usize,
+    call_graph: &'a mut CallGraph,
+    call_graph_indices: &'a mut HashMap<Span, usize>,
+}
+
+impl<'a> GraphBuilder<'a> {
+    fn code_block(&mut self, code_block: &ty::TyCodeBlock, mut leaves: Leaves) -> Leaves {
+        for ast_node in &code_block.contents {
+            leaves = match &ast_node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                    let leaves = self.expression(&var_decl.body, leaves);
+                    self.statement(&ast_node.span, leaves)
+                }
+                ty::TyAstNodeContent::Declaration(_) | ty::TyAstNodeContent::SideEffect(_) => {
+                    leaves
+                }
+                ty::TyAstNodeContent::Expression(expr)
+                | ty::TyAstNodeContent::ImplicitReturnExpression(expr) => {
+                    self.statement_expression(expr, &ast_node.span, leaves)
+                }
+            };
+        }
+        leaves
+    }
+
+    /// Adds the nodes of an expression in the position of a statement, which has a node of its
+    /// own unless it's a control-flow construct.
+    fn statement_expression(
+        &mut self,
+        expr: &ty::TyExpression,
+        span: &Span,
+        leaves: Leaves,
+    ) -> Leaves {
+        use crate::ty::TyExpressionVariant::*;
+        let leaves = self.expression(expr, leaves);
+        match &expr.expression {
+            IfExp { .. }
+            | WhileLoop { .. }
+            | MatchExp { .. }
+            | CodeBlock(_)
+            | Return(_)
+            | Break
+            | Continue => leaves,
+            _ => self.statement(span, leaves),
+        }
+    }
+
+    /// Adds the nodes of the control flow within an expression.
+    fn expression(&mut self, expr: &ty::TyExpression, leaves: Leaves) -> Leaves {
+        use crate::ty::TyExpressionVariant::*;
+        match &expr.expression {
+            Literal(_)
+            | ConstantExpression { .. }
+            | VariableExpression { .. }
+            | FunctionParameter
+            | StorageAccess(_)
+            | AbiName(_) => leaves,
+            IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                let leaves = self.expression(condition, leaves);
+                let branch =
+                    self.add_node(CfgNodeKind::Branch, Some(&condition.span), "if ", &leaves);
+                let mut leaves =
+                    self.statement_expression(then, &then.span, vec![(branch, CfgEdgeKind::True)]);
+                match r#else {
+                    Some(else_expr) => leaves.extend(self.statement_expression(
+                        else_expr,
+                        &else_expr.span,
+                        vec![(branch, CfgEdgeKind::False)],
+                    )),
+                    None => leaves.push((branch, CfgEdgeKind::False)),
+                }
+                leaves
+            }
+            WhileLoop { condition, body } => {
+                let leaves = self.expression(condition, leaves);
+                let cond =
+                    self.add_node(CfgNodeKind::Loop, Some(&condition.span), "while ", &leaves);
+                self.loops.push((cond, vec![]));
+                let body_leaves = self.code_block(body, vec![(cond, CfgEdgeKind::True)]);
+                self.connect(&body_leaves, cond);
+                let (_, breaks) = self.loops.pop().expect("the loop was pushed above");
+                std::iter::once((cond, CfgEdgeKind::False))
+                    .chain(breaks.into_iter().map(|node| (node, CfgEdgeKind::Next)))
+                    .collect()
+            }
+            Break => {
+                let node = self.add_node(CfgNodeKind::Break, Some(&expr.span), "", &leaves);
+                if let Some((_, breaks)) = self.loops.last_mut() {
+                    breaks.push(node);
+                }
+                vec![]
+            }
+            Continue => {
+                let node = self.add_node(CfgNodeKind::Continue, Some(&expr.span), "", &leaves);
+                if let Some((cond, _)) = self.loops.last() {
+                    let cond = *cond;
+                    self.connect(&[(node, CfgEdgeKind::Next)], cond);
+                }
+                vec![]
+            }
+            Return(value) => {
+                let leaves = self.expression(value, leaves);
+                let node = self.add_node(CfgNodeKind::Return, Some(&expr.span), "", &leaves);
+                self.connect(&[(node, CfgEdgeKind::Next)], EXIT);
+                vec![]
+            }
+            CodeBlock(code_block) => self.code_block(code_block, leaves),
+            MatchExp { desugared, .. } => self.expression(desugared, leaves),
+            FunctionApplication {
+                arguments, fn_ref, ..
+            } => {
+                let leaves = arguments
+                    .iter()
+                    .fold(leaves, |leaves, (_, arg)| self.expression(arg, leaves));
+                self.call(fn_ref, &expr.span);
+                leaves
+            }
+            LazyOperator { lhs, rhs, .. }
+            | ArrayIndex {
+                prefix: lhs,
+                index: rhs,
+            } => {
+                let leaves = self.expression(lhs, leaves);
+                self.expression(rhs, leaves)
+            }
+            IntrinsicFunction(intrinsic) => intrinsic
+                .arguments
+                .iter()
+                .fold(leaves, |leaves, arg| self.expression(arg, leaves)),
+            Tuple { fields: exprs }
+            | Array {
+                contents: exprs, ..
+            } => exprs
+                .iter()
+                .fold(leaves, |leaves, expr| self.expression(expr, leaves)),
+            StructExpression { fields, .. } => fields.iter().fold(leaves, |leaves, field| {
+                self.expression(&field.value, leaves)
+            }),
+            StructFieldAccess { prefix: expr, .. }
+            | TupleElemAccess { prefix: expr, .. }
+            | EnumTag { exp: expr }
+            | UnsafeDowncast { exp: expr, .. }
+            | AbiCast { address: expr, .. } => self.expression(expr, leaves),
+            EnumInstantiation { contents, .. } => match contents {
+                Some(expr) => self.expression(expr, leaves),
+                None => leaves,
+            },
+            Reassignment(reassignment) => self.expression(&reassignment.rhs, leaves),
+            AsmExpression { registers, .. } => registers
+                .iter()
+                .filter_map(|reg| reg.initializer.as_ref())
+                .fold(leaves, |leaves, initializer| {
+                    self.expression(initializer, leaves)
+                }),
+        }
+    }
+
+    fn statement(&mut self, span: &Span, leaves: Leaves) -> Leaves {
+        let node = self.add_node(CfgNodeKind::Statement, Some(span), "", &leaves);
+        vec![(node, CfgEdgeKind::Next)]
+    }
+
+    fn add_node(
+        &mut self,
+        kind: CfgNodeKind,
+        span: Option<&Span>,
+        prefix: &str,
+        leaves: &[(usize, CfgEdgeKind)],
+    ) -> usize {
+        let node = self.graph.nodes.len();
+        self.graph.nodes.push(CfgNode {
+            kind,
+            label: format!("{prefix}{}", span.map(label).unwrap_or_default()),
+            range: span.map(range),
+        });
+        self.connect(leaves, node);
+        node
+    }
+
+    fn connect(&mut self, leaves: &[(usize, CfgEdgeKind)], to: usize) {
+        self.graph
+            .edges
+            .extend(leaves.iter().map(|(from, kind)| CfgEdge {
+                from: *from,
+                to,
+                kind: *kind,
+            }));
+    }
+
+    /// Records a call from the function to the one `fn_ref` refers to.
+    fn call(&mut self, fn_ref: &DeclRefFunction, span: &Span) {
+        let fn_decl = self.engines.de().get_function(fn_ref);
+        let callee = match self.call_graph_indices.get(&fn_decl.span) {
+            Some(callee) => *callee,
+            None => {
+                let callee = self.call_graph.functions.len();
+                self.call_graph_indices.insert(fn_decl.span.clone(), callee);
+                self.call_graph
+                    .functions
+                    .push(call_graph_function(self.engines, &fn_decl, true));
+                callee
+            }
+        };
+        let caller = self.caller;
+        match self
+            .call_graph
+            .calls
+            .iter_mut()
+            .find(|call| call.caller == caller && call.callee == callee)
+        {
+            Some(call) => call.sites.push(range(span)),
+            None => self.call_graph.calls.push(Call {
+                caller,
+                callee,
+                sites: vec![range(span)],
+            }),
+        }
+    }
+}
+
+fn call_graph_function(
+    engines: &Engines,
+    fn_decl: &ty::TyFunctionDecl,
+    external: bool,
+) -> CallGraphFunction {
+    CallGraphFunction {
+        name: function_name(engines, fn_decl),
+        path: path(engines, &fn_decl.span),
+        range: Some(range(&fn_decl.span)),
+        external,
+    }
+}
+
+fn function_name(engines: &Engines, fn_decl: &ty::TyFunctionDecl) -> String {
+    match &fn_decl.implementing_type {
+        Some(implementing_type) => format!(
+            "{}::{}",
+            implementing_type.friendly_name(engines),
+            fn_decl.name
+        ),
+        None => fn_decl.name.to_string(),
+    }
+}
+
+fn path(engines: &Engines, span: &Span) -> Option<PathBuf> {
+    span.source_id()
+        .map(|source_id| engines.se().get_path(source_id))
+}
+
+fn range(span: &Span) -> LocationRange {
+    LocationRange {
+        start: span.start(),
+        end: span.end(),
+    }
+}
+
+/// The first line of the source of `span`, cut short past [MAX_LABEL_LENGTH] characters.
+fn label(span: &Span) -> String {
+    let mut lines = span.as_str().lines();
+    let first_line = lines.next().unwrap_or_default().trim();
+    if lines.next().is_none() && first_line.chars().count() <= MAX_LABEL_LENGTH {
+        first_line.to_string()
+    } else {
+        let shortened = first_line
+            .chars()
+            .take(MAX_LABEL_LENGTH)
+            .collect::<String>();
+        format!("{} ...", shortened.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        language::Visibility, namespace, BuildConfig, BuildTarget, CompileResult, Programs,
+    };
+    use std::{collections::HashMap, sync::Arc};
+    use sway_types::Ident;
+    use sway_utils::PerformanceData;
+
+    const PROGRAM: &str = r#"
+script;
+
+fn pick(flag: bool, x: u64) -> u64 {
+    let y = x + 1;
+    if flag {
+        y
+    } else {
+        0
+    }
+}
+
+fn count(n: u64) -> u64 {
+    let mut i = 0;
+    while i < n {
+        if i == 5 {
+            break;
+        }
+        i = i + 1;
+    }
+    i
+}
+
+enum Kind {
+    A: (),
+    B: u64,
+}
+
+fn classify(kind: Kind) -> u64 {
+    match kind {
+        Kind::A => 1,
+        Kind::B(x) => x,
+    }
+}
+
+fn main() -> u64 {
+    pick(true, 1) + count(10) + classify(Kind::B(2))
+}
+"#;
+
+    fn compile(
+        engines: &Engines,
+        source: Arc<str>,
+        namespace: namespace::Module,
+        build_config: &BuildConfig,
+        package_name: &str,
+    ) -> ty::TyProgram {
+        let CompileResult { value, errors, .. } = crate::compile_to_ast(
+            engines,
+            source,
+            namespace,
+            Some(build_config),
+            package_name,
+            &mut PerformanceData::default(),
+        );
+        assert!(errors.is_empty(), "{errors:?}");
+        value.and_then(|programs: Programs| programs.typed).unwrap()
+    }
+
+    /// The graphs of [PROGRAM], compiled along with the `core` library of this repository.
+    fn program_graphs() -> ProgramGraphs {
+        let engines = Engines::default();
+        let core_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../sway-lib-core")
+            .canonicalize()
+            .unwrap();
+        let core_entry = core_dir.join("src").join("lib.sw");
+        let core_source = std::fs::read_to_string(&core_entry).unwrap();
+        let core_config = BuildConfig::root_from_file_name_and_manifest_path(
+            core_entry,
+            core_dir,
+            BuildTarget::Fuel,
+        );
+        let core = compile(
+            &engines,
+            Arc::from(core_source),
+            namespace::Module::default(),
+            &core_config,
+            "core",
+        );
+        let mut core_namespace = core.root.namespace.module().clone();
+        core_namespace.name = Some(Ident::new_no_span("core".to_string()));
+        core_namespace.is_external = true;
+        core_namespace.visibility = Visibility::Public;
+
+        let mut namespace = namespace::Module::default();
+        namespace.insert_submodule("core".to_string(), core_namespace);
+        namespace.star_import_with_reexports(
+            &["core", "prelude"].map(|s| Ident::new_no_span(s.into())),
+            &[],
+            &engines,
+            true,
+        );
+        let source: Arc<str> = Arc::from(PROGRAM);
+        let build_config = BuildConfig::root_from_in_memory_sources(
+            PathBuf::from("src/main.sw"),
+            HashMap::from([(PathBuf::from("src/main.sw"), source.clone())]),
+            BuildTarget::Fuel,
+        );
+        let program = compile(&engines, source, namespace, &build_config, "test");
+        ProgramGraphs::new(&engines, &program)
+    }
+
+    fn function<'a>(graphs: &'a ProgramGraphs, name: &str) -> &'a FunctionGraph {
+        graphs
+            .functions
+            .iter()
+            .find(|function| function.name == name)
+            .unwrap_or_else(|| panic!("no graph of `{name}`"))
+    }
+
+    fn kinds(function: &FunctionGraph) -> Vec<CfgNodeKind> {
+        function.nodes.iter().map(|node| node.kind).collect()
+    }
+
+    fn edges(function: &FunctionGraph) -> Vec<(usize, usize, CfgEdgeKind)> {
+        function
+            .edges
+            .iter()
+            .map(|edge| (edge.from, edge.to, edge.kind))
+            .collect()
+    }
+
+    #[test]
+    fn if_expressions_branch() {
+        use CfgEdgeKind::*;
+        use CfgNodeKind::*;
+        let graphs = program_graphs();
+        let pick = function(&graphs, "pick");
+        assert_eq!(
+            kinds(pick),
+            [Entry, Exit, Statement, Branch, Statement, Statement]
+        );
+        assert!(pick.nodes[2].label.starts_with("let y = x + 1"));
+        assert_eq!(pick.nodes[3].label, "if flag");
+        assert_eq!(pick.nodes[4].label, "y");
+        assert_eq!(pick.nodes[5].label, "0");
+        assert_eq!(
+            edges(pick),
+            [
+                (0, 2, Next),
+                (2, 3, Next),
+                (3, 4, True),
+                (3, 5, False),
+                (4, 1, Next),
+                (5, 1, Next),
+            ]
+        );
+    }
+
+    #[test]
+    fn while_loops_go_back_to_their_condition() {
+        use CfgEdgeKind::*;
+        use CfgNodeKind::*;
+        let graphs = program_graphs();
+        let count = function(&graphs, "count");
+        assert_eq!(
+            kinds(count),
+            [Entry, Exit, Statement, Loop, Branch, Break, Statement, Statement]
+        );
+        assert_eq!(count.nodes[3].label, "while i < n");
+        assert_eq!(count.nodes[4].label, "if i == 5");
+        assert_eq!(count.nodes[7].label, "i");
+        assert_eq!(
+            edges(count),
+            [
+                (0, 2, Next),
+                (2, 3, Next),
+                (3, 4, True),
+                (4, 5, True),
+                (4, 6, False),
+                // The end of the body goes back to the condition.
+                (6, 3, Next),
+                // Both the condition and the `break` leave the loop.
+                (3, 7, False),
+                (5, 7, Next),
+                (7, 1, Next),
+            ]
+        );
+    }
+
+    #[test]
+    fn match_expressions_are_chains_of_branches() {
+        let graphs = program_graphs();
+        let classify = function(&graphs, "classify");
+        let branches: Vec<_> = (0..classify.nodes.len())
+            .filter(|node| classify.nodes[*node].kind == CfgNodeKind::Branch)
+            .collect();
+        let [first, second] = branches.as_slice() else {
+            panic!("expected a branch per arm, found {:?}", classify.nodes);
+        };
+        let out_edges = |node: usize| -> Vec<(usize, CfgEdgeKind)> {
+            classify
+                .edges
+                .iter()
+                .filter(|edge| edge.from == node)
+                .map(|edge| (edge.to, edge.kind))
+                .collect()
+        };
+        // The first arm falls through to the condition of the second.
+        assert!(out_edges(*first).contains(&(*second, CfgEdgeKind::False)));
+        for branch in [first, second] {
+            let kinds: Vec<_> = out_edges(*branch)
+                .into_iter()
+                .map(|(_, kind)| kind)
+                .collect();
+            assert_eq!(kinds, [CfgEdgeKind::True, CfgEdgeKind::False]);
+        }
+        // Every node but the exit flows somewhere, and only the exit has no way out.
+        for node in (0..classify.nodes.len()).filter(|node| *node != EXIT) {
+            assert!(!out_edges(node).is_empty(), "{:?}", classify.nodes[node]);
+        }
+        assert!(out_edges(EXIT).is_empty());
+    }
+
+    #[test]
+    fn calls_are_recorded_once_per_caller_and_callee() {
+        let graphs = program_graphs();
+        let call_graph = &graphs.call_graph;
+        let index = |name: &str| {
+            call_graph
+                .functions
+                .iter()
+                .position(|function| function.name == name)
+                .unwrap_or_else(|| panic!("`{name}` is not in the call graph"))
+        };
+        let sites = |caller: usize, callee: usize| {
+            call_graph
+                .calls
+                .iter()
+                .find(|call| call.caller == caller && call.callee == callee)
+                .map_or(0, |call| call.sites.len())
+        };
+
+        let names: Vec<_> = call_graph.functions[..4]
+            .iter()
+            .map(|function| (function.name.as_str(), function.external))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("pick", false),
+                ("count", false),
+                ("classify", false),
+                ("main", false)
+            ]
+        );
+        let main = index("main");
+        for callee in ["pick", "count", "classify"] {
+            assert_eq!(sites(main, index(callee)), 1, "main -> {callee}");
+        }
+
+        // The operators are calls into `core`, whose functions have no graphs of their own.
+        let add = call_graph
+            .functions
+            .iter()
+            .position(|function| function.name.ends_with("add"))
+            .expect("`+` calls `add`");
+        assert!(call_graph.functions[add].external);
+        assert!(!graphs
+            .functions
+            .iter()
+            .any(|function| function.name == call_graph.functions[add].name));
+        assert_eq!(sites(main, add), 2);
+        assert_eq!(sites(index("pick"), add), 1);
+        assert_eq!(sites(index("count"), add), 1);
+    }
+
+    #[test]
+    fn graphs_are_written_as_json() {
+        let graphs = program_graphs();
+        let functions = serde_json::to_value(&graphs.functions).unwrap();
+        let pick = functions
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|function| function["name"] == "pick")
+            .unwrap();
+        assert_eq!(pick["nodes"][0]["kind"], "entry");
+        assert_eq!(pick["nodes"][1]["kind"], "exit");
+        assert_eq!(pick["nodes"][3]["kind"], "branch");
+        assert_eq!(pick["nodes"][3]["label"], "if flag");
+        let range = &pick["nodes"][3]["range"];
+        let (start, end) = (
+            range["start"].as_u64().unwrap() as usize,
+            range["end"].as_u64().unwrap() as usize,
+        );
+        assert_eq!(&PROGRAM[start..end], "flag");
+        assert_eq!(
+            pick["edges"][2],
+            serde_json::json!({ "from": 3, "to": 4, "kind": "true" })
+        );
+        assert!(pick["path"].as_str().unwrap().ends_with("main.sw"));
+
+        let call_graph = serde_json::to_value(&graphs.call_graph).unwrap();
+        let call = &call_graph["calls"][0];
+        let caller = call["caller"].as_u64().unwrap() as usize;
+        assert_eq!(call_graph["functions"][caller]["external"], false);
+        assert!(!call["sites"].as_array().unwrap().is_empty());
+
+        // What's written can be read back.
+        let read_back: Vec<FunctionGraph> = serde_json::from_value(functions).unwrap();
+        assert_eq!(read_back.len(), graphs.functions.len());
+    }
+
+    #[test]
+    fn graphs_are_written_as_dot() {
+        let node = |kind, label: &str| CfgNode {
+            kind,
+            label: label.to_string(),
+            range: None,
+        };
+        let edge = |from, to, kind| CfgEdge { from, to, kind };
+        let graphs = ProgramGraphs {
+            functions: vec![FunctionGraph {
+                name: "main".to_string(),
+                path: None,
+                range: None,
+                nodes: vec![
+                    node(CfgNodeKind::Entry, "fn main() -> u64 {"),
+                    node(CfgNodeKind::Exit, "exit"),
+                    node(CfgNodeKind::Branch, "if x == \"a\""),
+                    node(CfgNodeKind::Statement, "f()"),
+                ],
+                edges: vec![
+                    edge(0, 2, CfgEdgeKind::Next),
+                    edge(2, 3, CfgEdgeKind::True),
+                    edge(2, 1, CfgEdgeKind::False),
+                    edge(3, 1, CfgEdgeKind::Next),
+                ],
+            }],
+            call_graph: CallGraph {
+                functions: vec![
+                    CallGraphFunction {
+                        name: "main".to_string(),
+                        path: None,
+                        range: None,
+                        external: false,
+                    },
+                    CallGraphFunction {
+                        name: "f".to_string(),
+                        path: None,
+                        range: None,
+                        external: true,
+                    },
+                ],
+                calls: vec![Call {
+                    caller: 0,
+                    callee: 1,
+                    sites: vec![LocationRange { start: 0, end: 3 }],
+                }],
+            },
+        };
+
+        assert_eq!(
+            graphs.cfg_dot(),
+            r#"digraph cfg {
+    node [shape = box];
+    subgraph cluster_0 {
+        label = "main";
+        f0_0 [label = "fn main() -> u64 {", shape = oval];
+        f0_1 [label = "exit", shape = oval];
+        f0_2 [label = "if x == \"a\"", shape = diamond];
+        f0_3 [label = "f()", shape = box];
+        f0_0 -> f0_2;
+        f0_2 -> f0_3 [label = "true"];
+        f0_2 -> f0_1 [label = "false"];
+        f0_3 -> f0_1;
+    }
+}
+"#
+        );
+        assert_eq!(
+            graphs.call_graph_dot(),
+            r#"digraph call_graph {
+    node [shape = box];
+    n0 [label = "main"];
+    n1 [label = "f", style = dashed];
+    n0 -> n1 [label = "1"];
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,322 @@
+// The storage fields each function reads and writes.
+//
+// Storage attributes only say whether a function reads or writes storage at all. This analysis
+// tracks which storage fields are accessed, so that the effects of a function can be reported per
+// field, in the warnings about unneeded storage attributes and in the JSON ABI. Fields are accessed
+// by passing them to functions, e.g. to the `StorageKey` methods in `storage.count.read()`, so the
+// slots the callee accesses without knowing which field they belong to, e.g. through
+// `std::storage::storage_api::read` or `srw` instructions, are attributed to the fields it's
+// passed, directly or through the storage keys derived from them, as in
+// `storage.balances.get(owner).read()`. Accesses no field can be found for are attributed to `*`.
+
+use crate::{
+    decl_engine::{DeclEngine, DeclId},
+    language::ty,
+    Engines, TypeEngine, TypeId, TypeInfo,
+};
+use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use sway_types::Span;
+
+/// Stands for the storage slots accessed without going through a storage field.
+pub(crate) const UNKNOWN_STORAGE_FIELD: &str = "*";
+
+/// The names of the storage fields a function reads and writes, including through the functions
+/// it calls. Nested fields are named by their path, e.g. `owner.balance`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct StorageEffects {
+    pub(crate) reads: BTreeSet<String>,
+    pub(crate) writes: BTreeSet<String>,
+}
+
+impl StorageEffects {
+    fn extend(&mut self, other: StorageEffects) {
+        self.reads.extend(other.reads);
+        self.writes.extend(other.writes);
+    }
+
+    /// Attributes the accesses of unknown slots to `fields`, which the function doing them was
+    /// given.
+    fn attribute_unknown_to(&mut self, fields: &[String]) {
+        for accessed in [&mut self.reads, &mut self.writes] {
+            if accessed.remove(UNKNOWN_STORAGE_FIELD) {
+                accessed.extend(fields.iter().cloned());
+            }
+        }
+    }
+
+    /// Describes the effects, e.g. "only reads `count` and `owner`", to follow "This function".
+    pub(crate) fn describe(&self) -> String {
+        fn fields(fields: &BTreeSet<String>) -> String {
+            let names: Vec<_> = fields
+                .iter()
+                .map(|field| match field.as_str() {
+                    UNKNOWN_STORAGE_FIELD => "slots outside of the storage fields".to_string(),
+                    _ => format!("`{field}`"),
+                })
+                .collect();
+            match names.split_last() {
+                Some((last, [])) => last.clone(),
+                Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+                None => String::new(),
+            }
+        }
+        match (self.reads.is_empty(), self.writes.is_empty()) {
+            (true, true) => "doesn't access storage".to_string(),
+            (false, true) => format!("only reads {}", fields(&self.reads)),
+            (true, false) => format!("only writes {}", fields(&self.writes)),
+            (false, false) => format!(
+                "reads {} and writes {}",
+                fields(&self.reads),
+                fields(&self.writes)
+            ),
+        }
+    }
+}
+
+/// The storage effects of every function of the program, by the span of its declaration.
+pub(crate) fn analyze_program(
+    engines: &Engines,
+    prog: &ty::TyProgram,
+) -> HashMap<Span, StorageEffects> {
+    let decl_engine = engines.de();
+    let mut analysis = Analysis::new(engines.te(), decl_engine);
+    let mut effects_by_span: HashMap<Span, StorageEffects> = HashMap::new();
+    let modules = std::iter::once(&prog.root).chain(
+        prog.root
+            .submodules_recursive()
+            .map(|(_, submodule)| &submodule.module),
+    );
+    for module in modules {
+        for fn_decl in module.functions(decl_engine) {
+            let effects = analysis.function(&fn_decl);
+            effects_by_span
+                .entry(fn_decl.span.clone())
+                .or_default()
+                .extend(effects);
+        }
+    }
+    effects_by_span
+}
+
+/// The storage effects of `fn_decl`.
+pub(crate) fn function_storage_effects(
+    type_engine: &TypeEngine,
+    decl_engine: &DeclEngine,
+    fn_decl: &ty::TyFunctionDecl,
+) -> StorageEffects {
+    Analysis::new(type_engine, decl_engine).function(fn_decl)
+}
+
+struct Analysis<'eng> {
+    type_engine: &'eng TypeEngine,
+    decl_engine: &'eng DeclEngine,
+    memos: HashMap<DeclId<ty::TyFunctionDecl>, StorageEffects>,
+    // The functions whose effects are being computed, whose calls are skipped as they can only be
+    // recursive calls, which don't add any effects.
+    in_progress: HashSet<DeclId<ty::TyFunctionDecl>>,
+}
+
+impl<'eng> Analysis<'eng> {
+    fn new(type_engine: &'eng TypeEngine, decl_engine: &'eng DeclEngine) -> Self {
+        Analysis {
+            type_engine,
+            decl_engine,
+            memos: HashMap::new(),
+            in_progress: HashSet::new(),
+        }
+    }
+
+    fn function(&mut self, fn_decl: &ty::TyFunctionDecl) -> StorageEffects {
+        let mut effects = StorageEffects::default();
+        self.code_block(&fn_decl.body, &mut effects);
+        effects
+    }
+
+    fn called_function(&mut self, decl_id: DeclId<ty::TyFunctionDecl>) -> StorageEffects {
+        if let Some(effects) = self.memos.get(&decl_id) {
+            return effects.clone();
+        }
+        if !self.in_progress.insert(decl_id) {
+            return StorageEffects::default();
+        }
+        let fn_decl = self.decl_engine.get_function(&decl_id);
+        let effects = self.function(&fn_decl);
+        self.in_progress.remove(&decl_id);
+        self.memos.insert(decl_id, effects.clone());
+        effects
+    }
+
+    /// The storage fields `expr` is, or is a storage key derived from, e.g. by `StorageMap::get`.
+    fn storage_fields(&self, expr: &ty::TyExpression) -> Vec<String> {
+        match &expr.expression {
+            ty::TyExpressionVariant::StorageAccess(access) => {
+                vec![access.fields.iter().map(|field| &field.name).join(".")]
+            }
+            ty::TyExpressionVariant::FunctionApplication {
+                arguments,
+                selector: None,
+                ..
+            } if self.is_storage_key(expr.return_type) => arguments
+                .iter()
+                .flat_map(|(_, arg)| self.storage_fields(arg))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn is_storage_key(&self, type_id: TypeId) -> bool {
+        match self.type_engine.get(type_id) {
+            TypeInfo::Struct(decl_ref) => {
+                self.decl_engine
+                    .get_struct(&decl_ref)
+                    .call_path
+                    .suffix
+                    .as_str()
+                    == "StorageKey"
+            }
+            _ => false,
+        }
+    }
+
+    fn code_block(&mut self, code_block: &ty::TyCodeBlock, effects: &mut StorageEffects) {
+        for ast_node in &code_block.contents {
+            match &ast_node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                    self.expression(&var_decl.body, effects)
+                }
+                ty::TyAstNodeContent::Declaration(_) => (),
+                ty::TyAstNodeContent::Expression(expr)
+                | ty::TyAstNodeContent::ImplicitReturnExpression(expr) => {
+                    self.expression(expr, effects)
+                }
+                ty::TyAstNodeContent::SideEffect(_) => (),
+            }
+        }
+    }
+
+    fn expression(&mut self, expr: &ty::TyExpression, effects: &mut StorageEffects) {
+        use crate::ty::TyExpressionVariant::*;
+        match &expr.expression {
+            Literal(_)
+            | ConstantExpression { .. }
+            | VariableExpression { .. }
+            | FunctionParameter
+            | StorageAccess(_)
+            | Break
+            | Continue
+            | AbiName(_) => (),
+            Reassignment(reassignment) => self.expression(&reassignment.rhs, effects),
+            CodeBlock(code_block) => self.code_block(code_block, effects),
+            LazyOperator { lhs, rhs, .. }
+            | ArrayIndex {
+                prefix: lhs,
+                index: rhs,
+            } => {
+                self.expression(lhs, effects);
+                self.expression(rhs, effects);
+            }
+            FunctionApplication {
+                arguments,
+                contract_call_params,
+                fn_ref,
+                selector,
+                ..
+            } => {
+                for (_, arg) in arguments {
+                    self.expression(arg, effects);
+                }
+                for param in contract_call_params.values() {
+                    self.expression(param, effects);
+                }
+                // The storage of the called contract is not the storage of this one.
+                if selector.is_some() {
+                    return;
+                }
+                let mut callee_effects = self.called_function(*fn_ref.id());
+                let fields: Vec<_> = arguments
+                    .iter()
+                    .flat_map(|(_, arg)| self.storage_fields(arg))
+                    .collect();
+                if !fields.is_empty() {
+                    callee_effects.attribute_unknown_to(&fields);
+                }
+                effects.extend(callee_effects);
+            }
+            IntrinsicFunction(intrinsic) => {
+                use sway_ast::Intrinsic::*;
+                for arg in &intrinsic.arguments {
+                    self.expression(arg, effects);
+                }
+                match intrinsic.kind {
+                    StateLoadWord | StateLoadQuad => {
+                        effects.reads.insert(UNKNOWN_STORAGE_FIELD.to_string());
+                    }
+                    StateClear | StateStoreWord | StateStoreQuad => {
+                        effects.writes.insert(UNKNOWN_STORAGE_FIELD.to_string());
+                    }
+                    _ => (),
+                }
+            }
+            Tuple { fields: exprs }
+            | Array {
+                contents: exprs, ..
+            } => {
+                for expr in exprs {
+                    self.expression(expr, effects);
+                }
+            }
+            StructExpression { fields, .. } => {
+                for field in fields {
+                    self.expression(&field.value, effects);
+                }
+            }
+            StructFieldAccess { prefix: expr, .. }
+            | TupleElemAccess { prefix: expr, .. }
+            | Return(expr)
+            | EnumTag { exp: expr }
+            | UnsafeDowncast { exp: expr, .. }
+            | AbiCast { address: expr, .. } => self.expression(expr, effects),
+            EnumInstantiation { contents, .. } => {
+                if let Some(expr) = contents {
+                    self.expression(expr, effects);
+                }
+            }
+            MatchExp { desugared, .. } => self.expression(desugared, effects),
+            IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                self.expression(condition, effects);
+                self.expression(then, effects);
+                if let Some(else_expr) = r#else {
+                    self.expression(else_expr, effects);
+                }
+            }
+            WhileLoop { condition, body } => {
+                self.expression(condition, effects);
+                self.code_block(body, effects);
+            }
+            AsmExpression {
+                registers, body, ..
+            } => {
+                for initializer in registers.iter().filter_map(|reg| reg.initializer.as_ref()) {
+                    self.expression(initializer, effects);
+                }
+                // As classified by the purity check of the IR.
+                for op in body {
+                    match op.op_name.as_str() {
+                        "scwq" | "srw" | "srwq" => {
+                            effects.reads.insert(UNKNOWN_STORAGE_FIELD.to_string());
+                        }
+                        "sww" | "swwq" => {
+                            effects.writes.insert(UNKNOWN_STORAGE_FIELD.to_string());
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+}
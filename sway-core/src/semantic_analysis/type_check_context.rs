@@ -8,6 +8,7 @@ use crate::{
     },
     type_system::{
         EnforceTypeArguments, MonomorphizeHelper, SubstTypes, TypeArgument, TypeId, TypeInfo,
+        UnifyCheck,
     },
     CompileResult, CompileWarning,
 };
@@ -40,6 +41,11 @@ pub struct TypeCheckContext<'a> {
     ///
     /// Assists type inference.
     type_annotation: TypeId,
+    /// While type-checking the body of a function, this is its return type, which the values of
+    /// its `return` expressions are expected to have.
+    ///
+    /// Assists type inference of the returned values.
+    return_type_annotation: Option<TypeId>,
     /// Whether or not we're within an `abi` implementation.
     ///
     /// This is `ImplAbiFn` while checking `abi` implementations whether at their original impl
@@ -84,6 +90,7 @@ impl<'a> TypeCheckContext<'a> {
             namespace,
             engines,
             type_annotation: engines.te().insert(engines, TypeInfo::Unknown),
+            return_type_annotation: None,
             help_text: "",
             // TODO: Contract? Should this be passed in based on program kind (aka TreeType)?
             self_type: engines.te().insert(engines, TypeInfo::Contract),
@@ -107,6 +114,7 @@ impl<'a> TypeCheckContext<'a> {
         TypeCheckContext {
             namespace: self.namespace,
             type_annotation: self.type_annotation,
+            return_type_annotation: self.return_type_annotation,
             self_type: self.self_type,
             abi_mode: self.abi_mode.clone(),
             const_shadowing_mode: self.const_shadowing_mode,
@@ -123,6 +131,7 @@ impl<'a> TypeCheckContext<'a> {
         TypeCheckContext {
             namespace,
             type_annotation: self.type_annotation,
+            return_type_annotation: self.return_type_annotation,
             self_type: self.self_type,
             abi_mode: self.abi_mode,
             const_shadowing_mode: self.const_shadowing_mode,
@@ -167,6 +176,14 @@ impl<'a> TypeCheckContext<'a> {
         }
     }
 
+    /// Map this `TypeCheckContext` instance to a new one with the given return type annotation.
+    pub(crate) fn with_return_type_annotation(self, return_type_annotation: TypeId) -> Self {
+        Self {
+            return_type_annotation: Some(return_type_annotation),
+            ..self
+        }
+    }
+
     /// Map this `TypeCheckContext` instance to a new one with the given ABI `mode`.
     pub(crate) fn with_abi_mode(self, abi_mode: AbiMode) -> Self {
         Self { abi_mode, ..self }
@@ -227,6 +244,10 @@ impl<'a> TypeCheckContext<'a> {
         self.type_annotation
     }
 
+    pub(crate) fn return_type_annotation(&self) -> Option<TypeId> {
+        self.return_type_annotation
+    }
+
     pub(crate) fn abi_mode(&self) -> AbiMode {
         self.abi_mode.clone()
     }
@@ -325,6 +346,23 @@ impl<'a> TypeCheckContext<'a> {
         )
     }
 
+    /// Unifies `ty`, the type of the expression being checked, with the type annotation before
+    /// checking the subexpressions it is inferred from, e.g. the arguments of a call, so that the
+    /// type arguments only the annotation gives, like `T` in `let v: Vec<u64> = Vec::new();`, are
+    /// known while checking them.
+    ///
+    /// Mismatches are reported once the whole expression is unified with the annotation, so
+    /// nothing is unified unless `ty` can be coerced into the annotation.
+    pub(crate) fn propagate_type_annotation(&self, ty: TypeId, span: &Span) {
+        let annotation = self.engines.te().get(self.type_annotation());
+        if matches!(annotation, TypeInfo::Unknown)
+            || !UnifyCheck::coercion(self.engines).check(ty, self.type_annotation())
+        {
+            return;
+        }
+        let _ = self.unify_with_self(ty, span);
+    }
+
     /// Short-hand for calling [Namespace::insert_symbol] with the `const_shadowing_mode` provided by
     /// the `TypeCheckContext`.
     pub(crate) fn insert_symbol(&mut self, name: Ident, item: TyDecl) -> CompileResult<()> {
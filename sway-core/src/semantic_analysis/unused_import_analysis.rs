@@ -0,0 +1,136 @@
+// Detects `use` statements that import names which are never referred to in their module.
+//
+// Name resolution records the imported names it goes through in the namespace, so an import is
+// unused if it was never recorded, either from its own module or from a module which imported it
+// from there in turn.
+//
+// Imports are re-exported along with the items of a module, so those of the modules a library's
+// dependents can reach may be used outside of the package and are never reported. Neither are
+// imported traits and ABIs, since they can be required to call their methods without their names
+// being resolved.
+
+use crate::language::{
+    lexed::{LexedModule, LexedProgram},
+    ty::{self, TyDecl},
+};
+use crate::semantic_analysis::namespace::{Items, Path};
+use sway_ast::{ItemKind, UseTree};
+use sway_error::warning::{CompileWarning, Warning};
+use sway_types::{Ident, Spanned};
+
+pub fn analyze_program(lexed: &LexedProgram, typed: &ty::TyProgram) -> Vec<CompileWarning> {
+    let mut warnings = vec![];
+    let is_library = matches!(typed.kind, ty::TyProgramKind::Library { .. });
+    analyze_module(&lexed.root, &typed.root, &[], is_library, &mut warnings);
+    warnings
+}
+
+fn analyze_module(
+    lexed: &LexedModule,
+    typed: &ty::TyModule,
+    mod_path: &Path,
+    is_reachable: bool,
+    warnings: &mut Vec<CompileWarning>,
+) {
+    for (name, lexed_submodule) in &lexed.submodules {
+        if let Some((_, typed_submodule)) = typed
+            .submodules
+            .iter()
+            .find(|(typed_name, _)| typed_name == name)
+        {
+            let submodule = &typed_submodule.module;
+            let submod_path = mod_path
+                .iter()
+                .cloned()
+                .chain(Some(name.clone()))
+                .collect::<Vec<_>>();
+            analyze_module(
+                &lexed_submodule.module,
+                submodule,
+                &submod_path,
+                is_reachable && submodule.namespace.visibility.is_public(),
+                warnings,
+            );
+        }
+    }
+    if is_reachable {
+        return;
+    }
+
+    let item_uses = lexed
+        .tree
+        .items
+        .iter()
+        .filter_map(|item| match &item.value {
+            ItemKind::Use(item_use) => Some((item.span(), item_use)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let items: &Items = &typed.namespace;
+
+    for (span, item_use) in item_uses {
+        // `pub use` is rejected by the parse tree conversion already.
+        if item_use.visibility.is_some() {
+            continue;
+        }
+        let mut bindings = vec![];
+        let mut has_glob = false;
+        collect_bindings(&item_use.tree, None, &mut bindings, &mut has_glob);
+        let unused = bindings
+            .iter()
+            .filter(|name| is_unused_import(items, mod_path, name))
+            .collect::<Vec<_>>();
+        // Only suggest removing the whole statement if none of its imports are needed.
+        let use_span = (!has_glob && unused.len() == bindings.len()).then_some(span);
+        warnings.extend(unused.into_iter().map(|name| CompileWarning {
+            span: name.span(),
+            warning_content: Warning::UnusedImport {
+                name: name.clone(),
+                use_span: use_span.clone(),
+            },
+        }));
+    }
+}
+
+/// Collects the names a use tree binds in the importing module, i.e. the aliases of renamed
+/// imports and the last path segment of the others.
+fn collect_bindings(
+    use_tree: &UseTree,
+    prefix: Option<&Ident>,
+    bindings: &mut Vec<Ident>,
+    has_glob: &mut bool,
+) {
+    match use_tree {
+        UseTree::Group { imports } => {
+            for use_tree in imports.get() {
+                collect_bindings(use_tree, prefix, bindings, has_glob);
+            }
+        }
+        UseTree::Name { name } => {
+            if name.as_str() == "self" {
+                bindings.extend(prefix.cloned());
+            } else {
+                bindings.push(name.clone());
+            }
+        }
+        UseTree::Rename { alias, .. } => bindings.push(alias.clone()),
+        UseTree::Glob { .. } => *has_glob = true,
+        UseTree::Path { prefix, suffix, .. } => {
+            collect_bindings(suffix, Some(prefix), bindings, has_glob)
+        }
+        // Parsing errors are reported elsewhere.
+        UseTree::Error { .. } => *has_glob = true,
+    }
+}
+
+/// Whether `name` was imported into the module at `mod_path` and never resolved. Names which
+/// aren't in the namespace as imports, like those of modules, are never reported.
+fn is_unused_import(items: &Items, mod_path: &Path, name: &Ident) -> bool {
+    match items.use_synonyms.get(name) {
+        Some((_, _, decl, _)) => {
+            !matches!(decl, TyDecl::TraitDecl(_) | TyDecl::AbiDecl(_))
+                && !items.is_synonym_used(mod_path, name)
+        }
+        _ => false,
+    }
+}
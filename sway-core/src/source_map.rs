@@ -12,8 +12,17 @@ use sway_types::span::Span;
 #[serde(transparent)]
 pub struct PathIndex(usize);
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// The version of the schema of the source map, bumped whenever it changes.
+///
+/// Version 2 added the line and column of spans and the call sites of inlined instructions.
+pub const SOURCE_MAP_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceMap {
+    /// The version of the schema of the source map.  Source maps written before it was recorded
+    /// are version 1.
+    #[serde(default = "SourceMap::unversioned")]
+    pub version: u32,
     /// Paths of dependencies in the `~/.forc` directory, with the prefix stripped.
     /// This makes inverse source mapping work on any machine with deps downloaded.
     dependency_paths: Vec<PathBuf>,
@@ -21,12 +30,33 @@ pub struct SourceMap {
     paths: Vec<PathBuf>,
     /// Mapping from opcode index to source location
     map: HashMap<usize, SourceMapSpan>,
+    /// The offsets of the starts of the lines of each source file, to compute line and column
+    /// numbers.
+    #[serde(skip)]
+    line_starts: HashMap<PathIndex, Vec<usize>>,
 }
+
+impl Default for SourceMap {
+    fn default() -> Self {
+        Self {
+            version: SOURCE_MAP_VERSION,
+            dependency_paths: Vec::new(),
+            paths: Vec::new(),
+            map: HashMap::new(),
+            line_starts: HashMap::new(),
+        }
+    }
+}
+
 impl SourceMap {
     pub fn new() -> Self {
         Self::default()
     }
 
+    fn unversioned() -> u32 {
+        1
+    }
+
     /// Inserts dependency path. Unsupported locations are ignored for now.
     pub fn insert_dependency<P: AsRef<Path>>(&mut self, path: P) {
         if let Some(home) = home_dir() {
@@ -38,48 +68,98 @@ impl SourceMap {
         // TODO: Only dependencies in ~/.forc are supported for now
     }
 
-    pub fn insert(&mut self, source_engine: &SourceEngine, pc: usize, span: &Span) {
-        if let Some(source_id) = span.source_id() {
-            let path = source_engine.get_path(source_id);
-            let path_index = self
-                .paths
+    /// Maps the opcode at `pc` to `span`.  If the opcode was inlined, `inlined_at` holds the
+    /// spans of the call sites it was inlined at, innermost first.
+    pub fn insert(
+        &mut self,
+        source_engine: &SourceEngine,
+        pc: usize,
+        span: &Span,
+        inlined_at: &[Span],
+    ) {
+        if let Some(mut sms) = self.to_source_map_span(source_engine, span) {
+            sms.inlined_at = inlined_at
                 .iter()
-                .position(|p| *p == *path)
-                .unwrap_or_else(|| {
-                    self.paths.push((*path).to_owned());
-                    self.paths.len() - 1
-                });
-            self.map.insert(
-                pc,
-                SourceMapSpan {
-                    path: PathIndex(path_index),
-                    range: LocationRange {
-                        start: span.start(),
-                        end: span.end(),
-                    },
-                },
-            );
+                .filter_map(|call_site| self.to_source_map_span(source_engine, call_site))
+                .collect();
+            self.map.insert(pc, sms);
         }
     }
 
+    fn to_source_map_span(
+        &mut self,
+        source_engine: &SourceEngine,
+        span: &Span,
+    ) -> Option<SourceMapSpan> {
+        let source_id = span.source_id()?;
+        let path = source_engine.get_path(source_id);
+        let path_index = self
+            .paths
+            .iter()
+            .position(|p| *p == *path)
+            .unwrap_or_else(|| {
+                self.paths.push((*path).to_owned());
+                self.paths.len() - 1
+            });
+        let path_index = PathIndex(path_index);
+        let src = span.src();
+        let line_starts = self
+            .line_starts
+            .entry(path_index)
+            .or_insert_with(|| line_starts(src));
+        Some(SourceMapSpan {
+            path: path_index,
+            range: LocationRange {
+                start: span.start(),
+                end: span.end(),
+            },
+            start: Some(LineCol::at(src, line_starts, span.start())),
+            end: Some(LineCol::at(src, line_starts, span.end())),
+            inlined_at: Vec::new(),
+        })
+    }
+
     /// Inverse source mapping
     pub fn addr_to_span(&self, pc: usize) -> Option<(PathBuf, LocationRange)> {
-        self.map.get(&pc).map(|sms| {
-            let p = &self.paths[sms.path.0];
-            for dep in &self.dependency_paths {
-                if p.starts_with(dep.file_name().unwrap()) {
-                    let mut path = home_dir().expect("Could not get homedir").join(".forc");
+        self.map
+            .get(&pc)
+            .map(|sms| (self.resolve_path(sms.path), sms.range))
+    }
 
-                    if let Some(dp) = dep.parent() {
-                        path = path.join(dp);
-                    }
+    /// Inverse source mapping of an opcode to the call stack in the sources: the location of the
+    /// opcode itself, followed by the call sites it was inlined at, innermost first.
+    pub fn addr_to_call_stack(&self, pc: usize) -> Vec<SourceLocation> {
+        self.map
+            .get(&pc)
+            .map(|sms| {
+                std::iter::once(sms)
+                    .chain(&sms.inlined_at)
+                    .map(|sms| SourceLocation {
+                        path: self.resolve_path(sms.path),
+                        range: sms.range,
+                        start: sms.start,
+                        end: sms.end,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 
-                    return (path.join(p), sms.range);
+    fn resolve_path(&self, path: PathIndex) -> PathBuf {
+        let p = &self.paths[path.0];
+        for dep in &self.dependency_paths {
+            if p.starts_with(dep.file_name().unwrap()) {
+                let mut path = home_dir().expect("Could not get homedir").join(".forc");
+
+                if let Some(dp) = dep.parent() {
+                    path = path.join(dp);
                 }
+
+                return path.join(p);
             }
+        }
 
-            (p.to_owned(), sms.range)
-        })
+        p.to_owned()
     }
 }
 
@@ -87,6 +167,51 @@ impl SourceMap {
 pub struct SourceMapSpan {
     pub path: PathIndex,
     pub range: LocationRange,
+    /// The line and column of the start of the span.  Missing from version 1 source maps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<LineCol>,
+    /// The line and column of the end of the span.  Missing from version 1 source maps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<LineCol>,
+    /// The call sites the opcode was inlined at, innermost first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inlined_at: Vec<SourceMapSpan>,
+}
+
+/// A line and column in a source file, both starting at 1.  Columns count characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl LineCol {
+    /// The line and column of the byte offset `pos` in `src`, whose lines start at the offsets in
+    /// `line_starts`.
+    fn at(src: &str, line_starts: &[usize], pos: usize) -> Self {
+        let line = line_starts.partition_point(|start| *start <= pos);
+        let line_start = line_starts[line - 1];
+        let col = src
+            .get(line_start..pos)
+            .map_or(pos - line_start, |text| text.chars().count());
+        LineCol { line, col: col + 1 }
+    }
+}
+
+/// The offsets of the starts of the lines of `src`.
+fn line_starts(src: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(src.match_indices('\n').map(|(pos, _)| pos + 1))
+        .collect()
+}
+
+/// A location in a source file, with its path resolved.
+#[derive(Debug, Clone)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+    pub range: LocationRange,
+    pub start: Option<LineCol>,
+    pub end: Option<LineCol>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -94,3 +219,40 @@ pub struct LocationRange {
     pub start: usize,
     pub end: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_col_counts_characters_from_one() {
+        let src = "fn main() {\n    let é = 1;\n}\n";
+        let line_starts = line_starts(src);
+        assert_eq!(line_starts, [0, 12, 28, 30]);
+
+        let line_col = |pos| {
+            let LineCol { line, col } = LineCol::at(src, &line_starts, pos);
+            (line, col)
+        };
+        assert_eq!(line_col(0), (1, 1));
+        assert_eq!(line_col(16), (2, 5));
+        // `é` takes two bytes but a single column.
+        assert_eq!(line_col(23), (2, 11));
+        assert_eq!(line_col(28), (3, 1));
+    }
+
+    #[test]
+    fn version_1_source_maps_still_load() {
+        let json = r#"{
+            "dependency_paths": [],
+            "paths": ["src/main.sw"],
+            "map": { "3": { "path": 0, "range": { "start": 10, "end": 20 } } }
+        }"#;
+        let source_map: SourceMap = serde_json::from_str(json).unwrap();
+        assert_eq!(source_map.version, 1);
+        let call_stack = source_map.addr_to_call_stack(3);
+        assert_eq!(call_stack.len(), 1);
+        assert_eq!(call_stack[0].path, PathBuf::from("src/main.sw"));
+        assert_eq!(call_stack[0].start, None);
+    }
+}
@@ -22,7 +22,10 @@
 
 use sway_ast::Literal;
 use sway_types::{
-    constants::{ALLOW_DEAD_CODE_NAME, CFG_PROGRAM_TYPE_ARG_NAME, CFG_TARGET_ARG_NAME},
+    constants::{
+        ALLOW_CEI_VIOLATION_NAME, ALLOW_DEAD_CODE_NAME, CFG_PROGRAM_TYPE_ARG_NAME,
+        CFG_TARGET_ARG_NAME, CFG_TEST_ARG_NAME,
+    },
     Ident, Span, Spanned,
 };
 
@@ -58,8 +61,15 @@ pub enum AttributeKind {
     DocComment,
     Storage,
     Inline,
+    Memoize,
     Test,
+    BeforeEach,
+    AfterEach,
+    Invariant,
+    Requires,
+    Ensures,
     Payable,
+    WhenNotPaused,
     Allow,
     Cfg,
 }
@@ -73,8 +83,15 @@ impl AttributeKind {
             AttributeKind::DocComment => (0, None),
             AttributeKind::Storage => (0, None),
             AttributeKind::Inline => (0, None),
+            AttributeKind::Memoize => (0, Some(0)),
             AttributeKind::Test => (0, None),
+            AttributeKind::BeforeEach => (0, Some(0)),
+            AttributeKind::AfterEach => (0, Some(0)),
+            AttributeKind::Invariant => (0, Some(0)),
+            AttributeKind::Requires => (0, Some(0)),
+            AttributeKind::Ensures => (0, Some(0)),
             AttributeKind::Payable => (0, None),
+            AttributeKind::WhenNotPaused => (0, Some(0)),
             AttributeKind::Allow => (1, Some(1)),
             AttributeKind::Cfg => (1, Some(1)),
         }
@@ -87,12 +104,23 @@ impl AttributeKind {
             AttributeKind::DocComment => None,
             AttributeKind::Storage => None,
             AttributeKind::Inline => None,
+            AttributeKind::Memoize => None,
             AttributeKind::Test => None,
+            AttributeKind::BeforeEach => None,
+            AttributeKind::AfterEach => None,
+            AttributeKind::Invariant => None,
+            AttributeKind::Requires => None,
+            AttributeKind::Ensures => None,
             AttributeKind::Payable => None,
-            AttributeKind::Allow => Some(vec![ALLOW_DEAD_CODE_NAME.to_string()]),
+            AttributeKind::WhenNotPaused => None,
+            AttributeKind::Allow => Some(vec![
+                ALLOW_DEAD_CODE_NAME.to_string(),
+                ALLOW_CEI_VIOLATION_NAME.to_string(),
+            ]),
             AttributeKind::Cfg => Some(vec![
                 CFG_TARGET_ARG_NAME.to_string(),
                 CFG_PROGRAM_TYPE_ARG_NAME.to_string(),
+                CFG_TEST_ARG_NAME.to_string(),
             ]),
         }
     }
@@ -125,6 +153,16 @@ impl AttributesMap {
         first
     }
 
+    /// Returns whether there is an `#[allow(..)]` attribute for `name`, e.g. `dead_code`.
+    pub fn allows(&self, name: &str) -> bool {
+        self.get(&AttributeKind::Allow).map_or(false, |attrs| {
+            attrs
+                .iter()
+                .flat_map(|attr| &attr.args)
+                .any(|arg| arg.name.as_str() == name)
+        })
+    }
+
     pub fn inner(&self) -> &HashMap<AttributeKind, Vec<Attribute>> {
         &self.0
     }
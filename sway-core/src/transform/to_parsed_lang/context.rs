@@ -1,4 +1,5 @@
-use crate::{language::parsed::TreeType, BuildTarget};
+use crate::{language::parsed::TreeType, BuildTarget, ExperimentalFeature};
+use std::collections::HashSet;
 
 #[derive(Default)]
 pub struct Context {
@@ -14,18 +15,31 @@ pub struct Context {
     /// Unique suffix used to generate unique names for vars returned from `match` expressions
     match_expression_return_var_unique_suffix: usize,
 
+    /// Unique suffix used to generate unique names for the declarations of `if let` chains
+    if_let_chain_unique_suffix: usize,
+
     /// The build target
     build_target: BuildTarget,
 
     /// The build target
     program_type: Option<TreeType>,
+
+    /// The enabled experimental features
+    experimental: HashSet<ExperimentalFeature>,
+
+    /// Whether the items marked `#[cfg(test)]` are included
+    include_tests: bool,
+
+    /// Whether the functions being converted are the methods of an ABI implemented for `Contract`
+    in_contract_abi_impl: bool,
 }
 
 impl Context {
     /// Create a new context
-    pub fn new(build_target: BuildTarget) -> Self {
+    pub fn new(build_target: BuildTarget, experimental: HashSet<ExperimentalFeature>) -> Self {
         Self {
             build_target,
+            experimental,
             ..Default::default()
         }
     }
@@ -59,11 +73,39 @@ impl Context {
         self.match_expression_return_var_unique_suffix
     }
 
+    /// Returns a unique suffix used to generate unique names for the declarations of an `if let`
+    /// chain
+    pub fn next_if_let_chain_unique_suffix(&mut self) -> usize {
+        self.if_let_chain_unique_suffix += 1;
+        self.if_let_chain_unique_suffix
+    }
+
     /// Returns the build target
     pub fn build_target(&self) -> BuildTarget {
         self.build_target
     }
 
+    /// Update the value of `include_tests`
+    pub fn set_include_tests(&mut self, include_tests: bool) {
+        self.include_tests = include_tests;
+    }
+
+    /// Returns whether the items marked `#[cfg(test)]` are included
+    pub fn include_tests(&self) -> bool {
+        self.include_tests
+    }
+
+    /// Update the value of `in_contract_abi_impl`
+    pub fn set_in_contract_abi_impl(&mut self, val: bool) {
+        self.in_contract_abi_impl = val;
+    }
+
+    /// Returns whether the functions being converted are the methods of an ABI implemented for
+    /// `Contract`
+    pub fn in_contract_abi_impl(&self) -> bool {
+        self.in_contract_abi_impl
+    }
+
     /// Returns the build target
     pub fn program_type(&self) -> Option<TreeType> {
         self.program_type.clone()
@@ -73,4 +115,9 @@ impl Context {
     pub fn set_program_type(&mut self, program_type: TreeType) {
         self.program_type = Some(program_type);
     }
+
+    /// Returns whether the experimental `feature` is enabled
+    pub fn is_experimental_feature_enabled(&self, feature: ExperimentalFeature) -> bool {
+        self.experimental.contains(&feature)
+    }
 }
@@ -2,33 +2,40 @@ use crate::{
     language::{parsed::*, *},
     transform::{attribute::*, to_parsed_lang::context::Context},
     type_system::*,
-    BuildTarget, Engines,
+    BuildTarget, Engines, ExperimentalFeature,
 };
 
 use itertools::Itertools;
 use sway_ast::{
     attribute::Annotated,
-    expr::{LoopControlFlow, ReassignmentOp, ReassignmentOpVariant},
+    expr::{asm::AsmLabel, LoopControlFlow, ReassignmentOp, ReassignmentOpVariant},
     ty::TyTupleDescriptor,
     AbiCastArgs, AngleBrackets, AsmBlock, Assignable, AttributeDecl, Braces, CodeBlockContents,
     CommaToken, DoubleColonToken, Expr, ExprArrayDescriptor, ExprStructField, ExprTupleDescriptor,
     FnArg, FnArgs, FnSignature, GenericArgs, GenericParams, IfCondition, IfExpr, Instruction,
     Intrinsic, Item, ItemAbi, ItemConfigurable, ItemConst, ItemEnum, ItemFn, ItemImpl, ItemKind,
-    ItemStorage, ItemStruct, ItemTrait, ItemTraitItem, ItemTypeAlias, ItemUse, LitInt, LitIntType,
-    MatchBranchKind, Module, ModuleKind, Parens, PathExpr, PathExprSegment, PathType,
-    PathTypeSegment, Pattern, PatternStructField, PubToken, Punctuated, QualifiedPathRoot,
-    Statement, StatementLet, Submodule, Traits, Ty, TypeField, UseTree, WhereClause,
+    ItemStorage, ItemStruct, ItemTrait, ItemTraitItem, ItemTypeAlias, ItemUse, LitByteString,
+    LitInt, LitIntType, MatchBranchKind, Module, ModuleKind, Parens, PathExpr, PathExprSegment,
+    PathType, PathTypeSegment, Pattern, PatternStructField, PubToken, Punctuated,
+    QualifiedPathRoot, Statement, StatementLet, Submodule, Traits, Ty, TypeField, UseTree,
+    WhereClause,
 };
 use sway_error::convert_parse_tree_error::ConvertParseTreeError;
+use sway_error::error::CompileError;
 use sway_error::handler::{ErrorEmitted, Handler};
 use sway_error::warning::{CompileWarning, Warning};
 use sway_types::{
     constants::{
-        ALLOW_ATTRIBUTE_NAME, CFG_ATTRIBUTE_NAME, CFG_PROGRAM_TYPE_ARG_NAME, CFG_TARGET_ARG_NAME,
-        DESTRUCTURE_PREFIX, DOC_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME, INLINE_ATTRIBUTE_NAME,
-        MATCH_RETURN_VAR_NAME_PREFIX, PAYABLE_ATTRIBUTE_NAME, STORAGE_PURITY_ATTRIBUTE_NAME,
+        AFTER_EACH_ATTRIBUTE_NAME, ALLOW_ATTRIBUTE_NAME, ALLOW_DEAD_CODE_NAME,
+        BEFORE_EACH_ATTRIBUTE_NAME, CFG_ATTRIBUTE_NAME, CFG_PROGRAM_TYPE_ARG_NAME,
+        CFG_TARGET_ARG_NAME, CFG_TEST_ARG_NAME, DESTRUCTURE_PREFIX, DOC_ATTRIBUTE_NAME,
+        DOC_COMMENT_ATTRIBUTE_NAME, ENSURES_ATTRIBUTE_NAME, IF_LET_CHAIN_NAME_PREFIX,
+        INLINE_ATTRIBUTE_NAME, INVARIANT_ATTRIBUTE_NAME, MATCH_RETURN_VAR_NAME_PREFIX,
+        MEMOIZE_ATTRIBUTE_NAME, PARAM_ATTRIBUTE_NAME, PARAM_MAX_ARG_NAME, PARAM_MIN_ARG_NAME,
+        PAYABLE_ATTRIBUTE_NAME, REQUIRES_ATTRIBUTE_NAME, STORAGE_PURITY_ATTRIBUTE_NAME,
         STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME, TEST_ATTRIBUTE_NAME,
-        TUPLE_NAME_PREFIX, VALID_ATTRIBUTE_NAMES,
+        TUPLE_NAME_PREFIX, VALID_ATTRIBUTE_NAMES, WHEN_NOT_PAUSED_ATTRIBUTE_NAME,
+        WHEN_NOT_PAUSED_GUARD_PATH,
     },
     integer_bits::IntegerBits,
 };
@@ -88,7 +95,7 @@ pub fn module_to_sway_parse_tree(
 
 fn ast_node_is_test_fn(node: &AstNode) -> bool {
     if let AstNodeContent::Declaration(Declaration::FunctionDeclaration(ref decl)) = node.content {
-        if decl.attributes.contains_key(&AttributeKind::Test) {
+        if decl.is_test() {
             return true;
         }
     }
@@ -145,7 +152,7 @@ fn item_to_ast_nodes(
             };
             match prev_item {
                 Some(Annotated {
-                    value: ItemKind::Submodule(_),
+                    value: ItemKind::Submodule(_) | ItemKind::Error(_),
                     ..
                 }) => (),
                 Some(_) => emit_expected_dep_at_beginning(),
@@ -169,7 +176,14 @@ fn item_to_ast_nodes(
         )),
         ItemKind::Fn(item_fn) => {
             let function_declaration = item_fn_to_function_declaration(
-                context, handler, engines, item_fn, attributes, None, None,
+                context,
+                handler,
+                engines,
+                item_fn,
+                attributes,
+                &item.attribute_list,
+                None,
+                None,
             )?;
             error_if_self_param_is_not_allowed(
                 context,
@@ -222,6 +236,8 @@ fn item_to_ast_nodes(
                 attributes,
             )?,
         )),
+        // The error was emitted when parsing the item.
+        ItemKind::Error(_) => vec![],
     };
 
     Ok(contents
@@ -456,12 +472,14 @@ fn item_enum_to_enum_declaration(
     Ok(enum_declaration)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn item_fn_to_function_declaration(
     context: &mut Context,
     handler: &Handler,
     engines: &Engines,
     item_fn: ItemFn,
     attributes: AttributesMap,
+    attribute_list: &[AttributeDecl],
     parent_generic_params_opt: Option<GenericParams>,
     parent_where_clause_opt: Option<WhereClause>,
 ) -> Result<FunctionDeclaration, ErrorEmitted> {
@@ -478,18 +496,28 @@ fn item_fn_to_function_declaration(
             }
         }
     };
+    let purity = get_attributed_purity(context, handler, &attributes)?;
+    error_if_memoized_fn_is_not_pure(handler, &attributes, purity)?;
+    let when_not_paused_guard = when_not_paused_guard(context, handler, &attributes, purity)?;
+    let (requires, ensures) =
+        item_attrs_to_specification(context, handler, engines, attribute_list)?;
+    let parameters = fn_args_to_function_parameters(
+        context,
+        handler,
+        engines,
+        item_fn.fn_signature.arguments.into_inner(),
+    )?;
+    error_if_param_validation_is_not_allowed(handler, &parameters)?;
+    let mut body =
+        braced_code_block_contents_to_code_block(context, handler, engines, item_fn.body)?;
+    body.contents.splice(0..0, when_not_paused_guard);
     Ok(FunctionDeclaration {
-        purity: get_attributed_purity(context, handler, &attributes)?,
+        purity,
         attributes,
         name: item_fn.fn_signature.name,
         visibility: pub_token_opt_to_visibility(item_fn.fn_signature.visibility),
-        body: braced_code_block_contents_to_code_block(context, handler, engines, item_fn.body)?,
-        parameters: fn_args_to_function_parameters(
-            context,
-            handler,
-            engines,
-            item_fn.fn_signature.arguments.into_inner(),
-        )?,
+        body,
+        parameters,
         span,
         return_type,
         type_parameters: generic_params_opt_to_type_parameters_with_parent(
@@ -509,9 +537,99 @@ fn item_fn_to_function_declaration(
             })
             .transpose()?
             .unwrap_or(vec![]),
+        requires,
+        ensures,
     })
 }
 
+/// The call of `std::pausable::require_not_paused` inserted at the start of the ABI methods
+/// annotated with `#[when_not_paused]`, so that they revert while the contract is paused.
+fn when_not_paused_guard(
+    context: &Context,
+    handler: &Handler,
+    attributes: &AttributesMap,
+    purity: Purity,
+) -> Result<Option<AstNode>, ErrorEmitted> {
+    let Some(attribute) = attributes
+        .get(&AttributeKind::WhenNotPaused)
+        .and_then(|attrs| attrs.first())
+    else {
+        return Ok(None);
+    };
+    let span = attribute.span.clone();
+    if !context.in_contract_abi_impl() {
+        let error = ConvertParseTreeError::WhenNotPausedNotAllowed { span };
+        return Err(handler.emit_err(error.into()));
+    }
+    if purity == Purity::Pure {
+        let error = ConvertParseTreeError::WhenNotPausedFnNotReadingStorage { span };
+        return Err(handler.emit_err(error.into()));
+    }
+
+    let mut path = WHEN_NOT_PAUSED_GUARD_PATH
+        .iter()
+        .map(|name| Ident::new_with_override(name.to_string(), span.clone()));
+    let suffix = path.next_back().unwrap();
+    let call_path_binding = TypeBinding {
+        inner: CallPath {
+            prefixes: path.collect(),
+            suffix,
+            is_absolute: true,
+        },
+        type_arguments: TypeArgs::Regular(vec![]),
+        span: span.clone(),
+    };
+    let expression = Expression {
+        kind: ExpressionKind::FunctionApplication(Box::new(FunctionApplicationExpression {
+            call_path_binding,
+            arguments: vec![],
+        })),
+        span: span.clone(),
+    };
+    Ok(Some(AstNode {
+        content: AstNodeContent::Expression(expression),
+        span,
+    }))
+}
+
+/// Converts the conditions of the `#[requires(..)]` and `#[ensures(..)]` attributes of a
+/// function, in order.
+fn item_attrs_to_specification(
+    context: &mut Context,
+    handler: &Handler,
+    engines: &Engines,
+    attribute_list: &[AttributeDecl],
+) -> Result<(Vec<Expression>, Vec<Expression>), ErrorEmitted> {
+    let mut requires = Vec::new();
+    let mut ensures = Vec::new();
+    let attrs = attribute_list
+        .iter()
+        .flat_map(|attr_decl| attr_decl.attribute.get());
+    for attr in attrs {
+        let conditions = match attr.name.as_str() {
+            REQUIRES_ATTRIBUTE_NAME => &mut requires,
+            ENSURES_ATTRIBUTE_NAME => &mut ensures,
+            _ => continue,
+        };
+        match &attr.condition {
+            Some(condition) => conditions.push(expr_to_expression(
+                context,
+                handler,
+                engines,
+                *condition.get().clone(),
+            )?),
+            None => {
+                let error = ConvertParseTreeError::ExpectedSpecificationCondition {
+                    attribute: attr.name.to_string(),
+                    span: attr.span(),
+                };
+                return Err(handler.emit_err(error.into()));
+            }
+        }
+    }
+    Ok((requires, ensures))
+}
+
 fn get_attributed_purity(
     _context: &mut Context,
     handler: &Handler,
@@ -590,8 +708,10 @@ fn item_trait_to_trait_declaration(
             }
             Ok(Some(match annotated.value {
                 ItemTraitItem::Fn(fn_sig) => {
-                    fn_signature_to_trait_fn(context, handler, engines, fn_sig, attributes)
-                        .map(TraitItem::TraitFn)
+                    let trait_fn =
+                        fn_signature_to_trait_fn(context, handler, engines, fn_sig, attributes)?;
+                    error_if_param_validation_is_not_allowed(handler, &trait_fn.parameters)?;
+                    Ok(TraitItem::TraitFn(trait_fn))
                 }
                 ItemTraitItem::Const(const_decl) => item_const_to_constant_declaration(
                     context, handler, engines, const_decl, attributes, false,
@@ -617,6 +737,7 @@ fn item_trait_to_trait_declaration(
                     engines,
                     item_fn.value,
                     attributes,
+                    &item_fn.attribute_list,
                     item_trait.generics.clone(),
                     item_trait.where_clause_opt.clone(),
                 )?))
@@ -649,6 +770,13 @@ fn item_impl_to_declaration(
 ) -> Result<Declaration, ErrorEmitted> {
     let block_span = item_impl.span();
     let implementing_for = ty_to_type_argument(context, handler, engines, item_impl.ty)?;
+    context.set_in_contract_abi_impl(
+        item_impl.trait_opt.is_some()
+            && matches!(
+                engines.te().get(implementing_for.type_id),
+                TypeInfo::Contract
+            ),
+    );
     let items = item_impl
         .contents
         .into_inner()
@@ -665,6 +793,7 @@ fn item_impl_to_declaration(
                     engines,
                     fn_item,
                     attributes,
+                    &item.attribute_list,
                     item_impl.generic_params_opt.clone(),
                     item_impl.where_clause_opt.clone(),
                 )
@@ -676,7 +805,9 @@ fn item_impl_to_declaration(
             }?))
         })
         .filter_map_ok(|item| item)
-        .collect::<Result<_, _>>()?;
+        .collect::<Result<_, _>>();
+    context.set_in_contract_abi_impl(false);
+    let items = items?;
 
     let impl_type_parameters = generic_params_opt_to_type_parameters(
         context,
@@ -764,6 +895,10 @@ fn item_abi_to_abi_declaration(
                     }
                     Ok(Some(match annotated.value {
                         ItemTraitItem::Fn(fn_signature) => {
+                            error_if_abi_method_has_type_parameters(
+                                handler,
+                                &fn_signature.generics,
+                            )?;
                             let trait_fn = fn_signature_to_trait_fn(
                                 context,
                                 handler,
@@ -803,12 +938,17 @@ fn item_abi_to_abi_declaration(
                     if !cfg_eval(context, handler, &attributes)? {
                         return Ok(None);
                     }
+                    error_if_abi_method_has_type_parameters(
+                        handler,
+                        &item_fn.value.fn_signature.generics,
+                    )?;
                     let function_declaration = item_fn_to_function_declaration(
                         context,
                         handler,
                         engines,
                         item_fn.value,
                         attributes,
+                        &item_fn.attribute_list,
                         None,
                         None,
                     )?;
@@ -1224,6 +1364,7 @@ fn fn_args_to_function_parameters(
                     span: self_token.span(),
                     call_path_tree: None,
                 },
+                validation: None,
             }];
             if let Some((_comma_token, args)) = args_opt {
                 for arg in args {
@@ -1407,6 +1548,17 @@ fn fn_signature_to_trait_fn(
         }
     };
 
+    // Signatures have no body to guard, the implementations of ABI methods are annotated instead.
+    if let Some(attribute) = attributes
+        .get(&AttributeKind::WhenNotPaused)
+        .and_then(|attrs| attrs.first())
+    {
+        let error = ConvertParseTreeError::WhenNotPausedNotAllowed {
+            span: attribute.span.clone(),
+        };
+        return Err(handler.emit_err(error.into()));
+    }
+
     let trait_fn = TraitFn {
         name: fn_signature.name.clone(),
         span: fn_signature.span(),
@@ -1772,6 +1924,9 @@ fn expr_to_expression(
             span,
         },
         Expr::Path(path_expr) => path_expr_to_expression(context, handler, engines, path_expr)?,
+        Expr::Literal(sway_ast::Literal::ByteString(lit_byte_string)) => {
+            byte_string_to_array_expression(lit_byte_string)
+        }
         Expr::Literal(literal) => Expression {
             kind: ExpressionKind::Literal(literal_to_literal(context, handler, literal)?),
             span,
@@ -2374,6 +2529,10 @@ fn statement_to_ast_nodes(
         Statement::Expr { expr, .. } => {
             vec![expr_to_ast_node(context, handler, engines, expr, true)?]
         }
+        Statement::Error(spans) => {
+            let expr = Expr::Error(spans);
+            vec![expr_to_ast_node(context, handler, engines, expr, true)?]
+        }
     };
     Ok(ast_nodes)
 }
@@ -2434,10 +2593,80 @@ fn fn_arg_to_function_parameter(
         is_mutable: mutable.is_some(),
         mutability_span,
         type_argument: ty_to_type_argument(context, handler, engines, fn_arg.ty)?,
+        validation: fn_arg_attrs_to_param_validation(handler, &fn_arg.attribute_list)?,
     };
     Ok(function_parameter)
 }
 
+/// Converts the `#[param(..)]` attributes of a function parameter into the validation of its
+/// arguments, which is the only kind of attribute parameters can have.
+fn fn_arg_attrs_to_param_validation(
+    handler: &Handler,
+    attribute_list: &[AttributeDecl],
+) -> Result<Option<ParamValidation>, ErrorEmitted> {
+    let mut validation: Option<ParamValidation> = None;
+    let attrs = attribute_list
+        .iter()
+        .flat_map(|attr_decl| attr_decl.attribute.get());
+    for attr in attrs {
+        if attr.name.as_str() != PARAM_ATTRIBUTE_NAME {
+            let error = ConvertParseTreeError::UnexpectedParameterAttribute {
+                attribute: attr.name.to_string(),
+                span: attr.span(),
+            };
+            return Err(handler.emit_err(error.into()));
+        }
+        let validation = validation.get_or_insert_with(|| ParamValidation {
+            min: None,
+            max: None,
+            span: attr.span(),
+        });
+        for arg in attr.args.iter().flat_map(|args| args.get()) {
+            let bound = match (arg.name.as_str(), &arg.value) {
+                (PARAM_MIN_ARG_NAME, Some(sway_ast::Literal::Int(lit_int))) => {
+                    Some((&mut validation.min, lit_int))
+                }
+                (PARAM_MAX_ARG_NAME, Some(sway_ast::Literal::Int(lit_int))) => {
+                    Some((&mut validation.max, lit_int))
+                }
+                _ => None,
+            };
+            match bound {
+                Some((bound, lit_int)) => match u64::try_from(&lit_int.parsed) {
+                    Ok(value) => *bound = Some(value),
+                    Err(..) => {
+                        let error = ConvertParseTreeError::IntLiteralOutOfRange {
+                            span: lit_int.span(),
+                        };
+                        return Err(handler.emit_err(error.into()));
+                    }
+                },
+                None => {
+                    let error =
+                        ConvertParseTreeError::InvalidParamAttributeArg { span: arg.span() };
+                    return Err(handler.emit_err(error.into()));
+                }
+            }
+        }
+    }
+    if let Some(ParamValidation {
+        min: Some(min),
+        max: Some(max),
+        span,
+    }) = &validation
+    {
+        if min > max {
+            let error = ConvertParseTreeError::EmptyParamRange {
+                min: *min,
+                max: *max,
+                span: span.clone(),
+            };
+            return Err(handler.emit_err(error.into()));
+        }
+    }
+    Ok(validation)
+}
+
 fn expr_to_length(
     context: &mut Context,
     handler: &Handler,
@@ -2637,7 +2866,7 @@ fn if_expr_to_expression(
         kind: ExpressionKind::CodeBlock(braced_code_block_contents_to_code_block(
             context, handler, engines, then_block,
         )?),
-        span: then_block_span.clone(),
+        span: then_block_span,
     };
     let else_block = match else_opt {
         None => None,
@@ -2658,6 +2887,26 @@ fn if_expr_to_expression(
             Some(expression)
         }
     };
+    if_condition_to_expression(
+        context, handler, engines, condition, then_block, else_block, span,
+    )
+}
+
+/// Converts an `if` with the given condition and branches into an `if` or `match` expression.
+///
+/// Chains such as `if let A(x) = a && x > 0 { .. } else { .. }` are desugared into nested
+/// expressions, here a `match` on `a` whose `A(x)` branch holds an `if x > 0`, see
+/// [if_let_chain_to_expression].
+fn if_condition_to_expression(
+    context: &mut Context,
+    handler: &Handler,
+    engines: &Engines,
+    condition: IfCondition,
+    then_block: Expression,
+    else_block: Option<Expression>,
+    span: Span,
+) -> Result<Expression, ErrorEmitted> {
+    let then_block_span = then_block.span();
     let expression = match condition {
         IfCondition::Expr(condition) => Expression {
             kind: ExpressionKind::If(IfExpression {
@@ -2713,10 +2962,259 @@ fn if_expr_to_expression(
                 span,
             }
         }
+        IfCondition::And {
+            lhs,
+            double_ampersand_token,
+            rhs,
+        } => {
+            let feature = ExperimentalFeature::IfLetChains;
+            if !context.is_experimental_feature_enabled(feature) {
+                let error = CompileError::ExperimentalFeatureNotEnabled {
+                    feature: feature.to_string(),
+                    description: feature.description().to_string(),
+                    span: double_ampersand_token.span(),
+                };
+                return Err(handler.emit_err(error));
+            }
+            let condition = IfCondition::And {
+                lhs,
+                double_ampersand_token,
+                rhs,
+            };
+            if_let_chain_to_expression(
+                context, handler, engines, condition, then_block, else_block, span,
+            )?
+        }
     };
     Ok(expression)
 }
 
+/// Converts an `if` whose condition is a chain of conditions joined by `&&`.
+///
+/// Each condition of the chain is nested into the branch of the previous one which is taken when
+/// it holds, so that the bindings of a `let` condition are in scope for the rest of the chain and
+/// for the `then` branch. The chain can fail at any of its conditions, but the `else` branch is
+/// evaluated at a single place, outside of the scope of those bindings. For that, the nested
+/// expressions evaluate to an enum declared for the chain, e.g. for
+/// `if let A(x) = a && x > 0 { then } else { else }`:
+///
+/// ```ignore
+/// {
+///     enum __if_let_chain_1<__T> { Then: __T, Else: () }
+///     let __if_let_chain_value_1 = match a {
+///         A(x) => if x > 0 { __if_let_chain_1::Then(then) } else { __if_let_chain_1::Else },
+///         _ => __if_let_chain_1::Else,
+///     };
+///     match __if_let_chain_value_1 {
+///         __if_let_chain_1::Then(__if_let_chain_value_1) => __if_let_chain_value_1,
+///         _ => else,
+///     }
+/// }
+/// ```
+fn if_let_chain_to_expression(
+    context: &mut Context,
+    handler: &Handler,
+    engines: &Engines,
+    condition: IfCondition,
+    then_block: Expression,
+    else_block: Option<Expression>,
+    span: Span,
+) -> Result<Expression, ErrorEmitted> {
+    // Without an `else`, failing at any of the conditions does nothing.
+    let else_block = match else_block {
+        Some(else_block) => else_block,
+        None => {
+            return if_let_chain_links_to_expression(
+                context, handler, engines, condition, then_block, None, &span,
+            );
+        }
+    };
+
+    // The generated names aren't given spans, so that they don't show up among the tokens of the
+    // source. That also leaves the enum without a span to track it by in the dead code analysis,
+    // so the dead code warnings about it are disabled.
+    let type_engine = engines.te();
+    let suffix = context.next_if_let_chain_unique_suffix();
+    let name = Ident::new_no_span(format!("{IF_LET_CHAIN_NAME_PREFIX}{suffix}"));
+    let value_name = Ident::new_no_span(format!("{IF_LET_CHAIN_NAME_PREFIX}value_{suffix}"));
+    let variant_path = |variant: &str| CallPath {
+        prefixes: vec![name.clone()],
+        suffix: Ident::new_no_span(variant.to_string()),
+        is_absolute: false,
+    };
+    let variant_expression = |variant: &str, args: Option<Vec<Expression>>| Expression {
+        kind: ExpressionKind::DelineatedPath(Box::new(DelineatedPathExpression {
+            call_path_binding: TypeBinding {
+                inner: variant_path(variant),
+                type_arguments: TypeArgs::Regular(vec![]),
+                span: span.clone(),
+            },
+            args,
+        })),
+        span: span.clone(),
+    };
+    let type_argument = |type_info: TypeInfo| {
+        let type_id = type_engine.insert(engines, type_info);
+        TypeArgument {
+            type_id,
+            initial_type_id: type_id,
+            span: Span::dummy(),
+            call_path_tree: None,
+        }
+    };
+    let value = || Expression {
+        kind: ExpressionKind::Variable(value_name.clone()),
+        span: span.clone(),
+    };
+
+    let type_parameter_name = Ident::new_no_span("__T".to_string());
+    let type_parameter_type = TypeInfo::Custom {
+        call_path: type_parameter_name.clone().into(),
+        type_arguments: None,
+    };
+    let type_parameter_id = type_engine.insert(engines, type_parameter_type.clone());
+    let allow_dead_code = Attribute {
+        name: Ident::new_no_span(ALLOW_ATTRIBUTE_NAME.to_string()),
+        args: vec![AttributeArg {
+            name: Ident::new_no_span(ALLOW_DEAD_CODE_NAME.to_string()),
+            value: None,
+            span: Span::dummy(),
+        }],
+        span: Span::dummy(),
+    };
+    let enum_declaration = EnumDeclaration {
+        name: name.clone(),
+        attributes: AttributesMap::new(Arc::new(HashMap::from([(
+            AttributeKind::Allow,
+            vec![allow_dead_code],
+        )]))),
+        type_parameters: vec![TypeParameter {
+            type_id: type_parameter_id,
+            initial_type_id: type_parameter_id,
+            name_ident: type_parameter_name,
+            trait_constraints: vec![],
+            trait_constraints_span: Span::dummy(),
+            is_from_parent: false,
+        }],
+        variants: vec![
+            EnumVariant {
+                name: Ident::new_no_span("Then".to_string()),
+                attributes: AttributesMap::default(),
+                type_argument: type_argument(type_parameter_type),
+                tag: 0,
+                span: Span::dummy(),
+            },
+            EnumVariant {
+                name: Ident::new_no_span("Else".to_string()),
+                attributes: AttributesMap::default(),
+                type_argument: type_argument(TypeInfo::Tuple(vec![])),
+                tag: 1,
+                span: Span::dummy(),
+            },
+        ],
+        span: Span::dummy(),
+        visibility: Visibility::Private,
+    };
+
+    let then_block = variant_expression("Then", Some(vec![then_block]));
+    let chain = if_let_chain_links_to_expression(
+        context,
+        handler,
+        engines,
+        condition,
+        then_block,
+        Some(&variant_expression("Else", None)),
+        &span,
+    )?;
+    let chain_variable = VariableDeclaration {
+        name: value_name.clone(),
+        type_ascription: type_argument(TypeInfo::Unknown),
+        body: chain,
+        is_mutable: false,
+    };
+    let else_block_span = else_block.span();
+    let branches = vec![
+        MatchBranch {
+            scrutinee: Scrutinee::EnumScrutinee {
+                call_path: variant_path("Then"),
+                value: Box::new(Scrutinee::Variable {
+                    name: value_name.clone(),
+                    span: span.clone(),
+                }),
+                span: span.clone(),
+            },
+            result: value(),
+            span: span.clone(),
+        },
+        MatchBranch {
+            scrutinee: Scrutinee::CatchAll {
+                span: else_block_span.clone(),
+            },
+            result: else_block,
+            span: else_block_span,
+        },
+    ];
+    let contents = vec![
+        AstNode {
+            content: AstNodeContent::Declaration(Declaration::EnumDeclaration(enum_declaration)),
+            span: span.clone(),
+        },
+        AstNode {
+            content: AstNodeContent::Declaration(Declaration::VariableDeclaration(chain_variable)),
+            span: span.clone(),
+        },
+        AstNode {
+            content: AstNodeContent::ImplicitReturnExpression(Expression {
+                kind: ExpressionKind::Match(MatchExpression {
+                    value: Box::new(value()),
+                    branches,
+                }),
+                span: span.clone(),
+            }),
+            span: span.clone(),
+        },
+    ];
+    Ok(Expression {
+        kind: ExpressionKind::CodeBlock(CodeBlock {
+            contents,
+            whole_block_span: span.clone(),
+        }),
+        span,
+    })
+}
+
+/// Converts the conditions of an `if let` chain, nesting each into the branch of the previous one
+/// which is taken when it holds, and `fail` into every other branch.
+fn if_let_chain_links_to_expression(
+    context: &mut Context,
+    handler: &Handler,
+    engines: &Engines,
+    condition: IfCondition,
+    then_block: Expression,
+    fail: Option<&Expression>,
+    span: &Span,
+) -> Result<Expression, ErrorEmitted> {
+    match condition {
+        IfCondition::And { lhs, rhs, .. } => {
+            let then_block = if_let_chain_links_to_expression(
+                context, handler, engines, *rhs, then_block, fail, span,
+            )?;
+            if_let_chain_links_to_expression(
+                context, handler, engines, *lhs, then_block, fail, span,
+            )
+        }
+        condition => if_condition_to_expression(
+            context,
+            handler,
+            engines,
+            condition,
+            then_block,
+            fail.cloned(),
+            span.clone(),
+        ),
+    }
+}
+
 /// Determine if the path is in absolute form, e.g., `::foo::bar`.
 ///
 /// Throws an error when given `<Foo as Bar>::baz`.
@@ -2782,14 +3280,16 @@ fn literal_to_literal(
         sway_ast::Literal::Bool(lit_bool) => Literal::Boolean(lit_bool.kind.into()),
         sway_ast::Literal::String(lit_string) => {
             let full_span = lit_string.span();
-            let inner_span = Span::new(
-                full_span.src().clone(),
-                full_span.start() + 1,
-                full_span.end() - 1,
-                full_span.source_id().cloned(),
-            )
-            .unwrap();
-            Literal::String(inner_span)
+            let inner_span = full_span
+                .with_range(full_span.start() + 1, full_span.end() - 1)
+                .unwrap();
+            Literal::String(inner_span, lit_string.parsed)
+        }
+        sway_ast::Literal::ByteString(lit_byte_string) => {
+            let error = ConvertParseTreeError::ByteStringLiteralNotSupportedHere {
+                span: lit_byte_string.span(),
+            };
+            return Err(handler.emit_err(error.into()));
         }
         sway_ast::Literal::Char(lit_char) => {
             let error = ConvertParseTreeError::CharLiteralsNotImplemented {
@@ -2898,6 +3398,25 @@ fn literal_to_literal(
     Ok(literal)
 }
 
+/// Converts a byte string literal, e.g. `b"abc"`, to the `[u8; N]` array of its bytes.
+fn byte_string_to_array_expression(lit_byte_string: LitByteString) -> Expression {
+    let LitByteString { span, parsed } = lit_byte_string;
+    let contents = parsed
+        .into_iter()
+        .map(|byte| Expression {
+            kind: ExpressionKind::Literal(Literal::U8(byte)),
+            span: span.clone(),
+        })
+        .collect();
+    Expression {
+        kind: ExpressionKind::Array(ArrayExpression {
+            contents,
+            length_span: None,
+        }),
+        span,
+    }
+}
+
 /// Like [path_expr_to_call_path], but instead can potentially return type arguments.
 /// Use this when converting a call path that could potentially include type arguments, i.e. the
 /// turbofish.
@@ -2962,8 +3481,15 @@ fn path_expr_to_call_path_binding(
 
     let type_arguments = if !regular_type_arguments.is_empty() && !prefix_type_arguments.is_empty()
     {
-        let error = ConvertParseTreeError::MultipleGenericsNotSupported { span };
-        return Err(handler.emit_err(error.into()));
+        let error = CompileError::DuplicateTypeArguments {
+            name: suffix,
+            span: regular_type_arguments
+                .iter()
+                .map(|type_arg| type_arg.span.clone())
+                .reduce(Span::join)
+                .unwrap_or(span),
+        };
+        return Err(handler.emit_err(error));
     } else if !prefix_type_arguments.is_empty() {
         TypeArgs::Prefix(prefix_type_arguments)
     } else {
@@ -3096,7 +3622,9 @@ fn asm_block_to_asm_expression(
         asm_block_contents
             .instructions
             .into_iter()
-            .map(|(instruction, _semicolon_token)| instruction_to_asm_op(instruction))
+            .map(|(label_opt, instruction, _semicolon_token)| {
+                instruction_to_asm_op(label_opt, instruction)
+            })
             .collect()
     };
     Ok(Box::new(AsmExpression {
@@ -3495,12 +4023,13 @@ fn asm_register_declaration_to_asm_register_declaration(
     })
 }
 
-fn instruction_to_asm_op(instruction: Instruction) -> AsmOp {
+fn instruction_to_asm_op(label_opt: Option<AsmLabel>, instruction: Instruction) -> AsmOp {
     AsmOp {
         op_name: instruction.op_code_ident(),
         op_args: instruction.register_arg_idents(),
         span: instruction.span(),
         immediate: instruction.immediate_ident_opt(),
+        label: label_opt.map(|label| label.name),
     }
 }
 
@@ -4044,10 +4573,17 @@ fn item_attrs_to_map(
                 DOC_COMMENT_ATTRIBUTE_NAME => Some(AttributeKind::DocComment),
                 STORAGE_PURITY_ATTRIBUTE_NAME => Some(AttributeKind::Storage),
                 INLINE_ATTRIBUTE_NAME => Some(AttributeKind::Inline),
+                MEMOIZE_ATTRIBUTE_NAME => Some(AttributeKind::Memoize),
                 TEST_ATTRIBUTE_NAME => Some(AttributeKind::Test),
+                BEFORE_EACH_ATTRIBUTE_NAME => Some(AttributeKind::BeforeEach),
+                AFTER_EACH_ATTRIBUTE_NAME => Some(AttributeKind::AfterEach),
+                INVARIANT_ATTRIBUTE_NAME => Some(AttributeKind::Invariant),
                 PAYABLE_ATTRIBUTE_NAME => Some(AttributeKind::Payable),
+                WHEN_NOT_PAUSED_ATTRIBUTE_NAME => Some(AttributeKind::WhenNotPaused),
                 ALLOW_ATTRIBUTE_NAME => Some(AttributeKind::Allow),
                 CFG_ATTRIBUTE_NAME => Some(AttributeKind::Cfg),
+                REQUIRES_ATTRIBUTE_NAME => Some(AttributeKind::Requires),
+                ENSURES_ATTRIBUTE_NAME => Some(AttributeKind::Ensures),
                 _ => None,
             } {
                 match attrs_map.get_mut(&attr_kind) {
@@ -4125,6 +4661,63 @@ fn error_if_self_param_is_not_allowed(
     Ok(())
 }
 
+/// Only the parameters of ABI method signatures can have a `#[param(..)]` attribute, which their
+/// implementations share.
+fn error_if_param_validation_is_not_allowed(
+    handler: &Handler,
+    parameters: &[FunctionParameter],
+) -> Result<(), ErrorEmitted> {
+    match parameters
+        .iter()
+        .find_map(|param| param.validation.as_ref())
+    {
+        Some(validation) => {
+            let error = ConvertParseTreeError::ParamAttributeNotAllowed {
+                span: validation.span.clone(),
+            };
+            Err(handler.emit_err(error.into()))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Calls of `#[memoize]` functions are folded into their results at compile time, which is only
+/// sound if the function has no effects, so it must not access storage.
+fn error_if_memoized_fn_is_not_pure(
+    handler: &Handler,
+    attributes: &AttributesMap,
+    purity: Purity,
+) -> Result<(), ErrorEmitted> {
+    if let Some(memoize_attr) = attributes
+        .get(&AttributeKind::Memoize)
+        .and_then(|attrs| attrs.first())
+    {
+        if purity != Purity::Pure {
+            let error = ConvertParseTreeError::MemoizedFnNotPure {
+                span: memoize_attr.span.clone(),
+            };
+            return Err(handler.emit_err(error.into()));
+        }
+    }
+    Ok(())
+}
+
+/// ABI methods are called through a selector computed from their concrete signature, so they
+/// cannot be generic themselves. Their parameters can still have generic types such as `Option<T>`
+/// though, as long as the type arguments are concrete.
+fn error_if_abi_method_has_type_parameters(
+    handler: &Handler,
+    generics: &Option<GenericParams>,
+) -> Result<(), ErrorEmitted> {
+    if let Some(generics) = generics {
+        let error = ConvertParseTreeError::AbiMethodTypeParameters {
+            span: generics.parameters.span(),
+        };
+        return Err(handler.emit_err(error.into()));
+    }
+    Ok(())
+}
+
 /// Walks all the cfg attributes in a map, evaluating them
 /// and returning false if any evaluated to false.
 pub fn cfg_eval(
@@ -4196,6 +4789,17 @@ pub fn cfg_eval(
                             return Err(handler.emit_err(error.into()));
                         }
                     }
+                    CFG_TEST_ARG_NAME => {
+                        if let Some(value) = &arg.value {
+                            let error = ConvertParseTreeError::UnexpectedCfgTestArgValue {
+                                span: value.span(),
+                            };
+                            return Err(handler.emit_err(error.into()));
+                        }
+                        if !context.include_tests() {
+                            return Ok(false);
+                        }
+                    }
                     _ => {
                         // Already checked with `AttributeKind::expected_args_*`
                         unreachable!("cfg attribute should only have the `target` or the `program_type` argument");
@@ -285,6 +285,11 @@ impl TypeCheckTypeBinding<ty::TyFunctionDecl> for TypeBinding<CallPath> {
                 }
             }
         }
+        // The declaration through which a function calls itself has no body to copy, and is
+        // found by its ID when generating IR.
+        if new_copy.is_recursive_ref {
+            return ok((fn_ref, None, None), warnings, errors);
+        }
         // Insert the new copy into the declaration engine.
         let new_fn_ref = ctx
             .engines
@@ -339,8 +344,9 @@ impl TypeCheckTypeBinding<ty::TyStructDecl> for TypeBinding<CallPath> {
             warnings,
             errors
         );
-        // Insert the new copy into the declaration engine.
-        let new_struct_ref = ctx.engines.de().insert(new_copy);
+        // Insert the new copy into the declaration engine, unless it's already there.
+        let new_struct_ref =
+            decl_engine.insert_struct_instance(engines, *struct_ref.id(), new_copy);
         // Take any trait items that apply to the old type and copy them to the new type.
         let type_id = type_engine.insert(engines, TypeInfo::Struct(new_struct_ref.clone()));
         ctx.namespace
@@ -373,13 +379,10 @@ impl TypeCheckTypeBinding<ty::TyEnumDecl> for TypeBinding<CallPath> {
             errors
         );
 
-        // Get a new copy from the declaration engine.
-        let mut new_copy = if let ty::TyDecl::EnumVariantDecl(ty::EnumVariantDecl {
-            enum_ref,
-            ..
-        }) = &unknown_decl
+        let enum_id = if let ty::TyDecl::EnumVariantDecl(ty::EnumVariantDecl { enum_ref, .. }) =
+            &unknown_decl
         {
-            decl_engine.get_enum(enum_ref.id())
+            *enum_ref.id()
         } else {
             // Check to see if this is a enum declaration.
             let enum_ref = check!(
@@ -388,8 +391,10 @@ impl TypeCheckTypeBinding<ty::TyEnumDecl> for TypeBinding<CallPath> {
                 warnings,
                 errors
             );
-            decl_engine.get_enum(enum_ref.id())
+            *enum_ref.id()
         };
+        // Get a new copy from the declaration engine.
+        let mut new_copy = decl_engine.get_enum(&enum_id);
 
         // Monomorphize the copy, in place.
         check!(
@@ -403,8 +408,8 @@ impl TypeCheckTypeBinding<ty::TyEnumDecl> for TypeBinding<CallPath> {
             warnings,
             errors
         );
-        // Insert the new copy into the declaration engine.
-        let new_enum_ref = ctx.engines.de().insert(new_copy);
+        // Insert the new copy into the declaration engine, unless it's already there.
+        let new_enum_ref = decl_engine.insert_enum_instance(engines, enum_id, new_copy);
         // Take any trait items that apply to the old type and copy them to the new type.
         let type_id = type_engine.insert(engines, TypeInfo::Enum(new_enum_ref.clone()));
         ctx.namespace
@@ -1,10 +1,12 @@
 use core::fmt::Write;
-use hashbrown::hash_map::RawEntryMut;
+use hashbrown::hash_map::{DefaultHashBuilder, RawEntryMut};
 use hashbrown::HashMap;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::RwLock;
-use sway_types::integer_bits::IntegerBits;
+use sway_types::{integer_bits::IntegerBits, SourceId};
 
-use crate::concurrent_slab::ListDisplay;
+use crate::concurrent_slab::{ListDisplay, Provenance};
 use crate::error::{err, ok};
 use crate::{
     concurrent_slab::ConcurrentSlab, decl_engine::*, engine_threading::*, error::*, language::ty,
@@ -14,37 +16,165 @@ use crate::{
 use sway_error::{error::CompileError, type_error::TypeError, warning::CompileWarning};
 use sway_types::{span::Span, Ident, Spanned};
 
+/// The number of shards of the interned types, each behind its own lock so that types can be
+/// interned from several threads at once.
+const SHARDS: usize = 16;
+
+/// The shard of the interned types with the hash `hash`.
+///
+/// The top 7 bits are skipped, as they're the ones the shard's own table probes with.
+fn shard_index(hash: u64) -> usize {
+    (hash << 7 >> (64 - SHARDS.trailing_zeros())) as usize
+}
+
 #[derive(Debug, Default)]
 pub struct TypeEngine {
     pub(super) slab: ConcurrentSlab<TypeInfo>,
-    id_map: RwLock<HashMap<TypeInfo, TypeId>>,
+    hash_builder: DefaultHashBuilder,
+    shards: [RwLock<HashMap<TypeInfo, TypeId>>; SHARDS],
+    generation: AtomicU32,
 }
 
 impl TypeEngine {
     /// Inserts a [TypeInfo] into the [TypeEngine] and returns a [TypeId]
     /// referring to that [TypeInfo].
     pub(crate) fn insert(&self, engines: &Engines, ty: TypeInfo) -> TypeId {
-        let mut id_map = self.id_map.write().unwrap();
-
-        let hash_builder = id_map.hasher().clone();
-        let ty_hash = make_hasher(&hash_builder, engines)(&ty);
+        let ty_hash = make_hasher(&self.hash_builder, engines)(&ty);
+        let shard = &self.shards[shard_index(ty_hash)];
+        let provenance = self.provenance();
+
+        // Most types are already interned, which only needs a read lock on their shard.
+        if let Some((interned, type_id)) = shard
+            .read()
+            .unwrap()
+            .raw_entry()
+            .from_hash(ty_hash, |x| x.eq(&ty, engines))
+        {
+            if self.can_reuse(interned, *type_id, &ty, provenance.generation) {
+                self.slab.touch(type_id.index(), provenance.generation);
+                return *type_id;
+            }
+        }
 
+        let mut id_map = shard.write().unwrap();
         let raw_entry = id_map
             .raw_entry_mut()
             .from_hash(ty_hash, |x| x.eq(&ty, engines));
         match raw_entry {
-            RawEntryMut::Occupied(o) => return *o.get(),
+            RawEntryMut::Occupied(o)
+                if self.can_reuse(o.key(), *o.get(), &ty, provenance.generation) =>
+            {
+                let type_id = *o.get();
+                self.slab.touch(type_id.index(), provenance.generation);
+                type_id
+            }
+            RawEntryMut::Occupied(mut o) => {
+                // The interned type refers to what a previous compilation of a module declared,
+                // which this compilation declares anew.
+                let provenance = self.interned_provenance(engines, &ty, provenance);
+                let type_id = TypeId::new(self.slab.insert(ty.clone(), provenance));
+                o.insert_key(ty);
+                o.insert(type_id);
+                type_id
+            }
             RawEntryMut::Vacant(_) if ty.can_change(engines.de()) => {
-                TypeId::new(self.slab.insert(ty))
+                TypeId::new(self.slab.insert(ty, provenance))
             }
             RawEntryMut::Vacant(v) => {
-                let type_id = TypeId::new(self.slab.insert(ty.clone()));
-                v.insert_with_hasher(ty_hash, ty, type_id, make_hasher(&hash_builder, engines));
+                let provenance = self.interned_provenance(engines, &ty, provenance);
+                let type_id = TypeId::new(self.slab.insert(ty.clone(), provenance));
+                v.insert_with_hasher(
+                    ty_hash,
+                    ty,
+                    type_id,
+                    make_hasher(&self.hash_builder, engines),
+                );
                 type_id
             }
         }
     }
 
+    /// Whether the interned type `interned`, which is equal to `ty`, can be used for it.
+    ///
+    /// Across generations, interned types referring to declarations or to other types are only
+    /// reused if they refer to the very same ones. Once the module declaring a struct is compiled
+    /// again, its type refers to the new declaration, and the previous type can be collected
+    /// along with the previous declaration.
+    fn can_reuse(
+        &self,
+        interned: &TypeInfo,
+        interned_id: TypeId,
+        ty: &TypeInfo,
+        generation: u32,
+    ) -> bool {
+        if self.slab.provenance(interned_id.index()).generation == generation {
+            return true;
+        }
+        match (interned, ty) {
+            (TypeInfo::Struct(l), TypeInfo::Struct(r)) => l.id() == r.id(),
+            (TypeInfo::Enum(l), TypeInfo::Enum(r)) => l.id() == r.id(),
+            (TypeInfo::Ptr(l), TypeInfo::Ptr(r)) | (TypeInfo::Slice(l), TypeInfo::Slice(r)) => {
+                l.type_id == r.type_id
+            }
+            _ => true,
+        }
+    }
+
+    /// The provenance of a newly interned type `ty`.
+    ///
+    /// Interned types are shared between all the modules using them, but those referring to a
+    /// declaration or to another type belong to the module of what they refer to, so that they
+    /// are collected along with it.
+    fn interned_provenance(
+        &self,
+        engines: &Engines,
+        ty: &TypeInfo,
+        provenance: Provenance,
+    ) -> Provenance {
+        let source_id = match ty {
+            TypeInfo::Struct(decl_ref) => engines.de().struct_provenance(decl_ref.id()).source_id,
+            TypeInfo::Enum(decl_ref) => engines.de().enum_provenance(decl_ref.id()).source_id,
+            TypeInfo::Ptr(elem) | TypeInfo::Slice(elem) => {
+                self.slab.provenance(elem.type_id.index()).source_id
+            }
+            _ => None,
+        };
+        Provenance {
+            source_id,
+            ..provenance
+        }
+    }
+
+    pub(crate) fn provenance(&self) -> Provenance {
+        Provenance::current(self.generation.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set_generation(&self, generation: u32) {
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+
+    /// Drops the types whose provenance is `collectible`, returning how many were.
+    pub(crate) fn collect(&mut self, collectible: &dyn Fn(&Provenance) -> bool) -> usize {
+        let collected = self.slab.collect(collectible);
+        for shard in &self.shards {
+            shard
+                .write()
+                .unwrap()
+                .retain(|_, type_id| !collected.contains(&type_id.index()));
+        }
+        collected.len()
+    }
+
+    /// The modules for which the types of the generations before `generation` were created.
+    pub(crate) fn sources_before(&self, generation: u32) -> HashSet<SourceId> {
+        self.slab.sources_before(generation)
+    }
+
+    /// The number of types which haven't been collected.
+    pub fn live_count(&self) -> usize {
+        self.slab.live_count()
+    }
+
     /// Performs a lookup of `id` into the [TypeEngine].
     pub fn get(&self, id: TypeId) -> TypeInfo {
         self.slab.get(id.index())
@@ -306,6 +436,27 @@ impl TypeEngine {
         }
     }
 
+    /// Return whether a given type is fully known, i.e. it doesn't contain any type which is still
+    /// to be inferred or resolved, nor any generic type.
+    pub(crate) fn is_concrete(&self, engines: &Engines, type_id: TypeId) -> bool {
+        let filter_fn = |type_info: &TypeInfo| {
+            matches!(
+                type_info,
+                TypeInfo::Unknown
+                    | TypeInfo::UnknownGeneric { .. }
+                    | TypeInfo::Placeholder(_)
+                    | TypeInfo::TypeParam(_)
+                    | TypeInfo::Numeric
+                    | TypeInfo::Custom { .. }
+                    | TypeInfo::SelfType
+                    | TypeInfo::ErrorRecovery
+            )
+        };
+        type_id
+            .extract_any_including_self(engines, &filter_fn, vec![])
+            .is_empty()
+    }
+
     /// Resolve all inner types that still are a [TypeInfo::Numeric] to a concrete `u64`
     pub(crate) fn decay_numeric(
         &self,
@@ -456,8 +607,9 @@ impl TypeEngine {
                             errors,
                         );
 
-                        // insert the new copy in the decl engine
-                        let new_decl_ref = decl_engine.insert(new_copy);
+                        // insert the new copy in the decl engine, unless it's already there
+                        let new_decl_ref =
+                            decl_engine.insert_struct_instance(engines, original_id, new_copy);
 
                         // create the type id from the copy
                         let type_id = engines.te().insert(engines, TypeInfo::Struct(new_decl_ref));
@@ -491,8 +643,9 @@ impl TypeEngine {
                             errors
                         );
 
-                        // insert the new copy in the decl engine
-                        let new_decl_ref = decl_engine.insert(new_copy);
+                        // insert the new copy in the decl engine, unless it's already there
+                        let new_decl_ref =
+                            decl_engine.insert_enum_instance(engines, original_id, new_copy);
 
                         // create the type id from the copy
                         let type_id = engines.te().insert(engines, TypeInfo::Enum(new_decl_ref));
@@ -613,10 +766,11 @@ impl TypeEngine {
     /// [DisplayWithEngines].
     pub fn pretty_print(&self, _decl_engine: &DeclEngine, engines: &Engines) -> String {
         let mut builder = String::new();
-        self.slab.with_slice(|elems| {
-            let list = elems
-                .iter()
-                .map(|type_info| format!("{:?}", engines.help_out(type_info)));
+        self.slab.with_values(|elems| {
+            let list = elems.iter().map(|type_info| match type_info {
+                Some(type_info) => format!("{:?}", engines.help_out(*type_info)),
+                None => "<collected>".to_string(),
+            });
             let list = ListDisplay { list };
             write!(builder, "TypeEngine {{\n{list}\n}}").unwrap();
         });
@@ -1,5 +1,5 @@
 use sway_error::error::CompileError;
-use sway_types::{BaseIdent, Span};
+use sway_types::{BaseIdent, Span, Spanned};
 
 use crate::{
     decl_engine::{DeclEngine, DeclEngineInsert},
@@ -80,6 +80,9 @@ impl ReplaceSelfType for TypeId {
                 TypeInfo::TypeParam(_) => None,
                 TypeInfo::SelfType => Some(self_type),
                 TypeInfo::Enum(decl_ref) => {
+                    // the declaration of a recursive type doesn't refer to `Self`
+                    let _visiting =
+                        Visiting::enter(Walk::ReplaceSelfType, Decls::enums(&decl_ref, &decl_ref))?;
                     let mut decl = decl_engine.get_enum(&decl_ref);
                     let mut need_to_create_new = false;
 
@@ -107,6 +110,10 @@ impl ReplaceSelfType for TypeId {
                     }
                 }
                 TypeInfo::Struct(decl_ref) => {
+                    let _visiting = Visiting::enter(
+                        Walk::ReplaceSelfType,
+                        Decls::structs(&decl_ref, &decl_ref),
+                    )?;
                     let mut decl = decl_engine.get_struct(&decl_ref);
                     let mut need_to_create_new = false;
 
@@ -411,27 +418,24 @@ impl TypeId {
                             param: structure_type_info_with_engines.to_string(),
                             trait_name: structure_trait_constraint.trait_name.suffix.to_string(),
                             span: span.clone(),
+                            constraint_span: structure_trait_constraint.trait_name.suffix.span(),
                         });
                     }
                 }
             } else {
-                let generic_trait_constraints_trait_names = ctx
-                    .namespace
-                    .implemented_traits
-                    .get_trait_names_for_type(engines, *structure_type_id);
-                for structure_trait_constraint in structure_trait_constraints {
-                    if !generic_trait_constraints_trait_names.contains(
-                        &structure_trait_constraint
-                            .trait_name
-                            .to_fullpath(ctx.namespace),
-                    ) {
-                        errors.push(CompileError::TraitConstraintNotSatisfied {
-                            ty: structure_type_info_with_engines.to_string(),
-                            trait_name: structure_trait_constraint.trait_name.suffix.to_string(),
-                            span: span.clone(),
-                        });
-                    }
-                }
+                check!(
+                    ctx.namespace
+                        .implemented_traits
+                        .check_if_trait_constraints_are_satisfied_for_type(
+                            *structure_type_id,
+                            structure_trait_constraints,
+                            span,
+                            engines,
+                        ),
+                    (),
+                    warnings,
+                    errors
+                );
             }
         }
 
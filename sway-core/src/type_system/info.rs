@@ -166,11 +166,18 @@ impl HashWithEngines for TypeInfo {
             TypeInfo::Tuple(fields) => {
                 fields.hash(state, engines);
             }
+            // where a recursive type refers to itself, only its name is hashed
             TypeInfo::Enum(decl_ref) => {
-                decl_ref.hash(state, engines);
+                match Visiting::enter(Walk::Hash, Decls::enums(decl_ref, decl_ref)) {
+                    Some(_visiting) => decl_ref.hash(state, engines),
+                    None => decl_ref.name().hash(state),
+                }
             }
             TypeInfo::Struct(decl_ref) => {
-                decl_ref.hash(state, engines);
+                match Visiting::enter(Walk::Hash, Decls::structs(decl_ref, decl_ref)) {
+                    Some(_visiting) => decl_ref.hash(state, engines),
+                    None => decl_ref.name().hash(state),
+                }
             }
             TypeInfo::ContractCaller { abi_name, address } => {
                 abi_name.hash(state);
@@ -263,6 +270,12 @@ impl PartialEqWithEngines for TypeInfo {
             (Self::Str(l), Self::Str(r)) => l.val() == r.val(),
             (Self::UnsignedInteger(l), Self::UnsignedInteger(r)) => l == r,
             (Self::Enum(l_decl_ref), Self::Enum(r_decl_ref)) => {
+                // where recursive types refer to themselves, they are equal if the rest of them is
+                let Some(_visiting) =
+                    Visiting::enter(Walk::Eq, Decls::enums(l_decl_ref, r_decl_ref))
+                else {
+                    return true;
+                };
                 let l_decl = engines.de().get_enum(l_decl_ref);
                 let r_decl = engines.de().get_enum(r_decl_ref);
                 l_decl.call_path.suffix == r_decl.call_path.suffix
@@ -271,6 +284,11 @@ impl PartialEqWithEngines for TypeInfo {
                     && l_decl.type_parameters.eq(&r_decl.type_parameters, engines)
             }
             (Self::Struct(l_decl_ref), Self::Struct(r_decl_ref)) => {
+                let Some(_visiting) =
+                    Visiting::enter(Walk::Eq, Decls::structs(l_decl_ref, r_decl_ref))
+                else {
+                    return true;
+                };
                 let l_decl = engines.de().get_struct(l_decl_ref);
                 let r_decl = engines.de().get_struct(r_decl_ref);
                 l_decl.call_path.suffix == r_decl.call_path.suffix
@@ -360,6 +378,11 @@ impl OrdWithEngines for TypeInfo {
             (Self::Str(l), Self::Str(r)) => l.val().cmp(&r.val()),
             (Self::UnsignedInteger(l), Self::UnsignedInteger(r)) => l.cmp(r),
             (Self::Enum(l_decl_ref), Self::Enum(r_decl_ref)) => {
+                let Some(_visiting) =
+                    Visiting::enter(Walk::Cmp, Decls::enums(l_decl_ref, r_decl_ref))
+                else {
+                    return Ordering::Equal;
+                };
                 let l_decl = decl_engine.get_enum(l_decl_ref);
                 let r_decl = decl_engine.get_enum(r_decl_ref);
                 l_decl
@@ -370,6 +393,11 @@ impl OrdWithEngines for TypeInfo {
                     .then_with(|| l_decl.variants.cmp(&r_decl.variants, engines))
             }
             (Self::Struct(l_decl_ref), Self::Struct(r_decl_ref)) => {
+                let Some(_visiting) =
+                    Visiting::enter(Walk::Cmp, Decls::structs(l_decl_ref, r_decl_ref))
+                else {
+                    return Ordering::Equal;
+                };
                 let l_decl = decl_engine.get_struct(l_decl_ref);
                 let r_decl = decl_engine.get_struct(r_decl_ref);
                 l_decl
@@ -644,6 +672,18 @@ impl TypeInfo {
             }
             B256 => "b256".into(),
             Struct(decl_ref) => {
+                // the selector of a recursive type would never end
+                let Some(_visiting) =
+                    Visiting::enter(Walk::SelectorName, Decls::structs(decl_ref, decl_ref))
+                else {
+                    return err(
+                        vec![],
+                        vec![CompileError::RecursiveTypeInAbi {
+                            name: decl_ref.name().clone(),
+                            span: error_msg_span.clone(),
+                        }],
+                    );
+                };
                 let decl = decl_engine.get_struct(decl_ref);
                 let field_names = {
                     let names = decl
@@ -698,6 +738,17 @@ impl TypeInfo {
                 }
             }
             Enum(decl_ref) => {
+                let Some(_visiting) =
+                    Visiting::enter(Walk::SelectorName, Decls::enums(decl_ref, decl_ref))
+                else {
+                    return err(
+                        vec![],
+                        vec![CompileError::RecursiveTypeInAbi {
+                            name: decl_ref.name().clone(),
+                            span: error_msg_span.clone(),
+                        }],
+                    );
+                };
                 let decl = decl_engine.get_enum(decl_ref);
                 let variant_names = {
                     let names = decl
@@ -913,6 +964,41 @@ impl TypeInfo {
         }
     }
 
+    /// Whether two values of this type are equal exactly when their bytes are, so that they can
+    /// be compared with a single memory comparison: string arrays, and arrays and structs whose
+    /// elements are integers, `bool`s, `b256`s, or arrays of them.
+    ///
+    /// Strings in arrays and structs are only compared if their length is a multiple of a word,
+    /// as the padding after the others isn't necessarily zeroed. Structs in them are never
+    /// compared, as they may implement `Eq` differently.
+    pub(crate) fn is_bytewise_comparable(
+        &self,
+        type_engine: &TypeEngine,
+        decl_engine: &DeclEngine,
+    ) -> bool {
+        fn is_flat_element(type_engine: &TypeEngine, type_id: TypeId) -> bool {
+            match type_engine.get(type_id) {
+                TypeInfo::UnsignedInteger(_)
+                | TypeInfo::Numeric
+                | TypeInfo::Boolean
+                | TypeInfo::B256 => true,
+                TypeInfo::Str(len) => len.val() % 8 == 0,
+                TypeInfo::Array(elem_ty, _) => is_flat_element(type_engine, elem_ty.type_id),
+                _ => false,
+            }
+        }
+        match self {
+            TypeInfo::Str(_) => true,
+            TypeInfo::Array(elem_ty, _) => is_flat_element(type_engine, elem_ty.type_id),
+            TypeInfo::Struct(decl_ref) => decl_engine
+                .get_struct(decl_ref)
+                .fields
+                .iter()
+                .all(|field| is_flat_element(type_engine, field.type_argument.type_id)),
+            _ => false,
+        }
+    }
+
     pub(crate) fn apply_type_arguments(
         self,
         type_arguments: Vec<TypeArgument>,
@@ -1133,6 +1219,12 @@ impl TypeInfo {
             | TypeInfo::Contract
             | TypeInfo::ErrorRecovery => {}
             TypeInfo::Enum(enum_ref) => {
+                // the types within a recursive enum are all found where it first occurs
+                let Some(_visiting) =
+                    Visiting::enter(Walk::ExtractAny, Decls::enums(enum_ref, enum_ref))
+                else {
+                    return found;
+                };
                 let enum_decl = decl_engine.get_enum(enum_ref);
                 for type_param in enum_decl.type_parameters.iter() {
                     extend(
@@ -1156,6 +1248,11 @@ impl TypeInfo {
                 }
             }
             TypeInfo::Struct(struct_ref) => {
+                let Some(_visiting) =
+                    Visiting::enter(Walk::ExtractAny, Decls::structs(struct_ref, struct_ref))
+                else {
+                    return found;
+                };
                 let struct_decl = decl_engine.get_struct(struct_ref);
                 for type_param in struct_decl.type_parameters.iter() {
                     extend(
@@ -3,6 +3,7 @@ mod engine;
 mod id;
 mod info;
 mod priv_prelude;
+mod recursion;
 mod substitute;
 mod unify;
 
@@ -197,3 +198,120 @@ fn unify_numerics_2() {
         TypeInfo::UnsignedInteger(IntegerBits::Eight)
     ));
 }
+
+#[test]
+fn recursive_enum_resolution() {
+    use crate::{
+        decl_engine::{DeclEngineInsert, DeclEngineReplace},
+        engine_threading::{OrdWithEngines, PartialEqWithEngines},
+        language::{ty, Visibility},
+        Engines, Ident,
+    };
+    use std::cmp::Ordering;
+    use sway_error::error::CompileError;
+
+    /*
+    List {
+        Nil: (),
+        Cons: Box<List>,
+    }
+    */
+    fn declare_list(engines: &Engines) -> TypeId {
+        let sp = Span::dummy();
+        let ident = |name: &str| Ident::new_with_override(name.to_string(), sp.clone());
+        let type_argument = |type_id| TypeArgument {
+            type_id,
+            initial_type_id: type_id,
+            span: sp.clone(),
+            call_path_tree: None,
+        };
+
+        // The enum is declared before its variants, as the compiler does, so that they refer to it.
+        let list_decl = TyEnumDecl {
+            call_path: ident("List").into(),
+            type_parameters: vec![],
+            variants: vec![],
+            span: sp.clone(),
+            visibility: Visibility::Public,
+            attributes: AttributesMap::default(),
+        };
+        let list_ref = engines.de().insert(list_decl.clone());
+        let list_type = engines
+            .te()
+            .insert(engines, TypeInfo::Enum(list_ref.clone()));
+
+        let ptr_type = engines.te().insert(engines, TypeInfo::RawUntypedPtr);
+        let box_ref = engines.de().insert(ty::TyStructDecl {
+            call_path: ident("Box").into(),
+            type_parameters: vec![TypeParameter {
+                type_id: list_type,
+                initial_type_id: list_type,
+                name_ident: ident("T"),
+                trait_constraints: vec![],
+                trait_constraints_span: sp.clone(),
+                is_from_parent: false,
+            }],
+            fields: vec![ty::TyStructField {
+                name: ident("ptr"),
+                span: sp.clone(),
+                type_argument: type_argument(ptr_type),
+                attributes: AttributesMap::default(),
+            }],
+            visibility: Visibility::Public,
+            span: sp.clone(),
+            attributes: AttributesMap::default(),
+        });
+        let box_type = engines.te().insert(engines, TypeInfo::Struct(box_ref));
+
+        let unit_type = engines.te().insert(engines, TypeInfo::Tuple(vec![]));
+        let variant = |name, tag, type_id| ty::TyEnumVariant {
+            name: ident(name),
+            type_argument: type_argument(type_id),
+            tag,
+            span: sp.clone(),
+            attributes: AttributesMap::default(),
+        };
+        engines.de().replace(
+            *list_ref.id(),
+            TyEnumDecl {
+                variants: vec![variant("Nil", 0, unit_type), variant("Cons", 1, box_type)],
+                ..list_decl
+            },
+        );
+        list_type
+    }
+
+    let engines = Engines::default();
+    let type_engine = engines.te();
+    let sp = Span::dummy();
+    let list_1 = declare_list(&engines);
+    let list_2 = declare_list(&engines);
+
+    // Walking either list comes back to it through its `Box`, where the walk must stop.
+    assert!(type_engine
+        .get(list_1)
+        .eq(&type_engine.get(list_2), &engines));
+    assert_eq!(
+        type_engine
+            .get(list_1)
+            .cmp(&type_engine.get(list_2), &engines),
+        Ordering::Equal
+    );
+    assert_eq!(
+        type_engine.insert(&engines, type_engine.get(list_1)),
+        type_engine.insert(&engines, type_engine.get(list_2))
+    );
+    let (_, errors) = type_engine.unify(&engines, list_1, list_2, &sp, "", None);
+    assert!(errors.is_empty());
+    assert!(type_engine
+        .get(list_1)
+        .extract_inner_types(&engines)
+        .contains(&list_1));
+
+    // a recursive type has no selector
+    let selector = type_engine.get(list_1).to_selector_name(&engines, &sp);
+    assert!(matches!(
+        selector.errors.as_slice(),
+        [CompileError::RecursiveTypeInAbi { .. }]
+    ));
+}
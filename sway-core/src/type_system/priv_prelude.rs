@@ -8,6 +8,7 @@ pub(crate) use super::{
     },
     engine::{EnforceTypeArguments, MonomorphizeHelper},
     info::VecSet,
+    recursion::{Decls, Visiting, Walk},
     substitute::{subst_list::SubstList, subst_map::TypeSubstMap, subst_types::SubstTypes},
     unify::unify_check::UnifyCheck,
 };
@@ -0,0 +1,70 @@
+//! Keeps the walks over types from looping forever on recursive types.
+//!
+//! A struct or enum may refer to itself through a `Box`, e.g.
+//! `enum List { Nil: (), Cons: (u64, Box<List>) }`. Walking into the fields of `List` then
+//! reaches `Box<List>`, whose type argument is `List` again. Each walk which follows the fields of
+//! structs and the variants of enums marks the declarations it is in while walking them, and stops
+//! where it comes back to one of them.
+
+use std::{cell::RefCell, collections::HashSet};
+
+use crate::decl_engine::{DeclRefEnum, DeclRefStruct};
+
+/// The walks which follow the fields of structs and the variants of enums.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum Walk {
+    Hash,
+    Eq,
+    Cmp,
+    ExtractAny,
+    ReplaceSelfType,
+    FindMatch,
+    Unify,
+    SelectorName,
+    AbiComponents,
+}
+
+/// The declaration walked into, or the pair of them when two types are walked side by side.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum Decls {
+    Struct(usize, usize),
+    Enum(usize, usize),
+}
+
+impl Decls {
+    pub(crate) fn structs(l: &DeclRefStruct, r: &DeclRefStruct) -> Decls {
+        Decls::Struct(l.id().inner(), r.id().inner())
+    }
+
+    pub(crate) fn enums(l: &DeclRefEnum, r: &DeclRefEnum) -> Decls {
+        Decls::Enum(l.id().inner(), r.id().inner())
+    }
+}
+
+thread_local! {
+    static VISITING: RefCell<HashSet<(Walk, Decls)>> = RefCell::new(HashSet::new());
+}
+
+/// Marks declarations as being walked into, until dropped.
+pub(crate) struct Visiting {
+    key: (Walk, Decls),
+}
+
+impl Visiting {
+    /// Starts walking into `decls`, or returns `None` if `walk` is already within them further up
+    /// the stack, i.e. the type refers to itself and the walk must not go any deeper.
+    pub(crate) fn enter(walk: Walk, decls: Decls) -> Option<Visiting> {
+        let key = (walk, decls);
+        VISITING
+            .with(|visiting| visiting.borrow_mut().insert(key))
+            .then(|| Visiting { key })
+    }
+}
+
+impl Drop for Visiting {
+    fn drop(&mut self) {
+        VISITING.with(|visiting| {
+            visiting.borrow_mut().remove(&self.key);
+        });
+    }
+}
@@ -323,6 +323,10 @@ impl TypeSubstMap {
             TypeInfo::Placeholder(_) => iter_for_match(engines, self, &type_info),
             TypeInfo::TypeParam(_) => None,
             TypeInfo::Struct(decl_ref) => {
+                // a recursive type isn't generic, so there is nothing to match where it refers to
+                // itself
+                let _visiting =
+                    Visiting::enter(Walk::FindMatch, Decls::structs(&decl_ref, &decl_ref))?;
                 let mut decl = decl_engine.get_struct(&decl_ref);
                 let mut need_to_create_new = false;
                 for field in decl.fields.iter_mut() {
@@ -345,6 +349,8 @@ impl TypeSubstMap {
                 }
             }
             TypeInfo::Enum(decl_ref) => {
+                let _visiting =
+                    Visiting::enter(Walk::FindMatch, Decls::enums(&decl_ref, &decl_ref))?;
                 let mut decl = decl_engine.get_enum(&decl_ref);
                 let mut need_to_create_new = false;
 
@@ -94,6 +94,12 @@ impl<'a> Unifier<'a> {
                 self.unify_arrays(received, expected, span, re.type_id, ee.type_id)
             }
             (Struct(r_decl_ref), Struct(e_decl_ref)) => {
+                // where recursive types refer to themselves, they are already being unified
+                let Some(_visiting) =
+                    Visiting::enter(Walk::Unify, Decls::structs(&r_decl_ref, &e_decl_ref))
+                else {
+                    return (vec![], vec![]);
+                };
                 let r_decl = self.engines.de().get_struct(&r_decl_ref);
                 let e_decl = self.engines.de().get_struct(&e_decl_ref);
 
@@ -125,6 +131,11 @@ impl<'a> Unifier<'a> {
                 (vec![], vec![])
             }
             (Enum(r_decl_ref), Enum(e_decl_ref)) => {
+                let Some(_visiting) =
+                    Visiting::enter(Walk::Unify, Decls::enums(&r_decl_ref, &e_decl_ref))
+                else {
+                    return (vec![], vec![]);
+                };
                 let r_decl = self.engines.de().get_enum(&r_decl_ref);
                 let e_decl = self.engines.de().get_enum(&e_decl_ref);
 
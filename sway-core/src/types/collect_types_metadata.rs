@@ -8,7 +8,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use crate::{type_system::TypeId, CompileResult, Engines};
+use crate::{language::ty, type_system::TypeId, CompileResult, Engines};
 use sway_types::{Ident, Span};
 
 /// If any types contained by this node are unresolved or have yet to be inferred, throw an
@@ -46,6 +46,19 @@ impl MessageId {
     }
 }
 
+/// A revert whose code is known at compile time, e.g. `revert(42)`, or the revert performed by
+/// `require` with `FAILED_REQUIRE_SIGNAL`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct RevertCode {
+    pub code: u64,
+    /// The constant the code was given as, if any.
+    pub name: Option<Ident>,
+    /// The outermost call leading to the revert, e.g. `require(amount > 0, "amount too low")`.
+    pub call_site: Span,
+    /// The last log made by the same call before reverting, e.g. of the error `require` is given.
+    pub log_id: Option<LogId>,
+}
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone)]
 pub enum TypeMetadata {
@@ -55,6 +68,8 @@ pub enum TypeMetadata {
     LoggedType(LogId, TypeId),
     // An smo with a unique message ID and the type ID of the type of the message data being sent
     MessageType(MessageId, TypeId),
+    // A revert with a code known at compile time
+    RevertCode(RevertCode),
 }
 
 // A simple context that only contains a single counter for now but may expand in the future.
@@ -68,6 +83,16 @@ pub struct CollectTypesMetadataContext<'cx> {
     message_id_counter: usize,
 
     call_site_spans: Vec<Arc<Mutex<HashMap<TypeId, Span>>>>,
+
+    // The calls currently being walked, outermost first, each with the arguments whose values
+    // are known at compile time. These let revert codes be traced through calls like
+    // `revert(FAILED_REQUIRE_SIGNAL)` down to the `__revert` which uses them.
+    calls: Vec<(Span, HashMap<Ident, (u64, Option<Ident>)>)>,
+
+    // The last log made within the outermost call currently being walked, with the span of that
+    // call.
+    last_call_log: Option<(Span, LogId)>,
+
     pub(crate) engines: &'cx Engines,
 }
 
@@ -116,12 +141,88 @@ impl<'cx> CollectTypesMetadataContext<'cx> {
         None
     }
 
+    /// Enters a call of a function with `parameters`. Mutable parameters can be reassigned
+    /// within the function, so only the values of the immutable ones are known.
+    pub(crate) fn call_push(
+        &mut self,
+        span: Span,
+        parameters: &[ty::TyFunctionParameter],
+        arguments: &[(Ident, ty::TyExpression)],
+    ) {
+        let known_arguments = arguments
+            .iter()
+            .filter(|(name, _)| {
+                parameters
+                    .iter()
+                    .any(|param| param.name == *name && !param.is_mutable && !param.is_reference)
+            })
+            .filter_map(|(name, arg)| Some((name.clone(), self.known_u64(arg)?)))
+            .collect();
+        self.calls.push((span, known_arguments));
+    }
+
+    pub(crate) fn call_pop(&mut self) {
+        self.calls.pop();
+    }
+
+    /// Forgets the value of the argument `name` of the innermost call, once a variable declared
+    /// within the called function shadows it.
+    pub(crate) fn forget_argument(&mut self, name: &Ident) {
+        if let Some((_, known_arguments)) = self.calls.last_mut() {
+            known_arguments.remove(name);
+        }
+    }
+
+    /// The span of the outermost call currently being walked.
+    pub(crate) fn outermost_call_span(&self) -> Option<Span> {
+        self.calls.first().map(|(span, _)| span.clone())
+    }
+
+    /// Records `log_id` as the last log made within the outermost call currently being walked.
+    pub(crate) fn record_call_log(&mut self, log_id: LogId) {
+        self.last_call_log = self.outermost_call_span().map(|span| (span, log_id));
+    }
+
+    /// The last log made within the outermost call currently being walked, if any.
+    pub(crate) fn last_call_log(&self) -> Option<LogId> {
+        let outermost_call_span = self.outermost_call_span()?;
+        self.last_call_log
+            .as_ref()
+            .filter(|(span, _)| *span == outermost_call_span)
+            .map(|(_, log_id)| *log_id)
+    }
+
+    /// The value of `expr` if it is known at compile time to be an integer, along with the name
+    /// of the constant it comes from, if any.
+    pub(crate) fn known_u64(&self, expr: &ty::TyExpression) -> Option<(u64, Option<Ident>)> {
+        match &expr.expression {
+            ty::TyExpressionVariant::Literal(literal) => {
+                literal.as_u64().map(|value| (value, None))
+            }
+            ty::TyExpressionVariant::ConstantExpression { const_decl, .. } => {
+                match const_decl.value.as_ref().map(|value| &value.expression) {
+                    Some(ty::TyExpressionVariant::Literal(literal)) => literal
+                        .as_u64()
+                        .map(|value| (value, Some(const_decl.call_path.suffix.clone()))),
+                    _ => None,
+                }
+            }
+            ty::TyExpressionVariant::VariableExpression { name, .. } => self
+                .calls
+                .last()
+                .and_then(|(_, known_arguments)| known_arguments.get(name).cloned()),
+            _ => None,
+        }
+    }
+
     pub fn new(engines: &'cx Engines) -> Self {
         let mut ctx = Self {
             engines,
             log_id_counter: 0,
             message_id_counter: 0,
             call_site_spans: vec![],
+            calls: vec![],
+            last_call_log: None,
         };
         ctx.call_site_push();
         ctx
@@ -134,3 +235,126 @@ pub(crate) trait CollectTypesMetadata {
         ctx: &mut CollectTypesMetadataContext,
     ) -> CompileResult<Vec<TypeMetadata>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        language::{CallPath, Literal, Visibility},
+        transform::AttributesMap,
+        TypeArgument, TypeInfo,
+    };
+    use sway_types::integer_bits::IntegerBits;
+
+    fn expression(engines: &Engines, expression: ty::TyExpressionVariant) -> ty::TyExpression {
+        ty::TyExpression {
+            expression,
+            return_type: engines
+                .te()
+                .insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour)),
+            span: Span::dummy(),
+        }
+    }
+
+    fn variable(engines: &Engines, name: &str) -> ty::TyExpression {
+        expression(
+            engines,
+            ty::TyExpressionVariant::VariableExpression {
+                name: Ident::new_no_span(name.to_string()),
+                span: Span::dummy(),
+                mutability: ty::VariableMutability::Immutable,
+                call_path: None,
+            },
+        )
+    }
+
+    fn parameter(engines: &Engines, name: &str, is_mutable: bool) -> ty::TyFunctionParameter {
+        ty::TyFunctionParameter {
+            name: Ident::new_no_span(name.to_string()),
+            is_reference: false,
+            is_mutable,
+            mutability_span: Span::dummy(),
+            type_argument: TypeArgument::from(
+                engines
+                    .te()
+                    .insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour)),
+            ),
+            validation: None,
+        }
+    }
+
+    #[test]
+    fn known_u64_of_literals_and_constants() {
+        let engines = Engines::default();
+        let ctx = CollectTypesMetadataContext::new(&engines);
+
+        let literal = expression(&engines, ty::TyExpressionVariant::Literal(Literal::U8(7)));
+        assert_eq!(ctx.known_u64(&literal), Some((7, None)));
+
+        let boolean = expression(
+            &engines,
+            ty::TyExpressionVariant::Literal(Literal::Boolean(true)),
+        );
+        assert_eq!(ctx.known_u64(&boolean), None);
+
+        let name = Ident::new_no_span("FAILED".to_string());
+        let const_decl = ty::TyConstantDecl {
+            call_path: CallPath {
+                prefixes: vec![],
+                suffix: name.clone(),
+                is_absolute: false,
+            },
+            value: Some(expression(
+                &engines,
+                ty::TyExpressionVariant::Literal(Literal::U64(u64::MAX)),
+            )),
+            visibility: Visibility::Private,
+            is_configurable: false,
+            attributes: AttributesMap::default(),
+            return_type: literal.return_type,
+            type_ascription: TypeArgument::from(literal.return_type),
+            span: Span::dummy(),
+            implementing_type: None,
+        };
+        let constant = expression(
+            &engines,
+            ty::TyExpressionVariant::ConstantExpression {
+                span: Span::dummy(),
+                const_decl: Box::new(const_decl),
+                call_path: None,
+            },
+        );
+        assert_eq!(ctx.known_u64(&constant), Some((u64::MAX, Some(name))));
+    }
+
+    #[test]
+    fn known_u64_of_arguments() {
+        let engines = Engines::default();
+        let mut ctx = CollectTypesMetadataContext::new(&engines);
+
+        let code = Ident::new_no_span("code".to_string());
+        let other = Ident::new_no_span("other".to_string());
+        let literal = expression(&engines, ty::TyExpressionVariant::Literal(Literal::U64(42)));
+        ctx.call_push(
+            Span::dummy(),
+            &[
+                parameter(&engines, "code", false),
+                parameter(&engines, "other", true),
+            ],
+            &[(code.clone(), literal.clone()), (other, literal)],
+        );
+        assert_eq!(ctx.known_u64(&variable(&engines, "code")), Some((42, None)));
+        // Mutable parameters may be reassigned before they are used.
+        assert_eq!(ctx.known_u64(&variable(&engines, "other")), None);
+
+        // An argument is known within the call it is passed to, but not within nested calls.
+        ctx.call_push(Span::dummy(), &[], &[]);
+        assert_eq!(ctx.known_u64(&variable(&engines, "code")), None);
+        ctx.call_pop();
+
+        // A local shadowing the parameter hides the value of the argument.
+        ctx.forget_argument(&code);
+        assert_eq!(ctx.known_u64(&variable(&engines, "code")), None);
+        ctx.call_pop();
+    }
+}
@@ -9,8 +9,6 @@ pub enum ConvertParseTreeError {
     FunctionArbitraryExpression { span: Span },
     #[error("generics are not supported here")]
     GenericsNotSupportedHere { span: Span },
-    #[error("multiple generics are not supported")]
-    MultipleGenericsNotSupported { span: Span },
     #[error("tuple index out of range")]
     TupleIndexOutOfRange { span: Span },
     #[error("shift-left expressions are not implemented")]
@@ -29,6 +27,8 @@ pub enum ConvertParseTreeError {
     QualifiedPathRootsNotImplemented { span: Span },
     #[error("char literals are not implemented")]
     CharLiteralsNotImplemented { span: Span },
+    #[error("byte string literals are not supported here")]
+    ByteStringLiteralNotSupportedHere { span: Span },
     #[error("hex literals must have 1..16 or 64 digits")]
     HexLiteralLength { span: Span },
     #[error("binary literals must have either 1..64 or 256 digits")]
@@ -73,6 +73,8 @@ pub enum ConvertParseTreeError {
     ContractCallerOneGenericArg { span: Span },
     #[error("ContractCaller requires a named type for its generic argument")]
     ContractCallerNamedTypeGenericArg { span: Span },
+    #[error("ABI methods cannot have type parameters. Use generic types with concrete type arguments, such as `Option<u64>`, in their signatures instead.")]
+    AbiMethodTypeParameters { span: Span },
     #[error("invalid argument for '{attribute}' attribute")]
     InvalidAttributeArgument { attribute: String, span: Span },
     #[error("cannot find type \"{ty_name}\" in this scope")]
@@ -123,6 +125,26 @@ pub enum ConvertParseTreeError {
     ExpectedCfgProgramTypeArgValue { span: Span },
     #[error("Unexpected call path segments between qualified root and method name.")]
     UnexpectedCallPathPrefixAfterQualifiedRoot { span: Span },
+    #[error("`#[memoize]` functions must be pure and cannot access storage.")]
+    MemoizedFnNotPure { span: Span },
+    #[error("The test argument doesn't take a value.")]
+    UnexpectedCfgTestArgValue { span: Span },
+    #[error("Expected a condition for the `{attribute}` attribute, e.g. `#[{attribute}(x > 0)]`.")]
+    ExpectedSpecificationCondition { attribute: String, span: Span },
+    #[error("Unexpected attribute `{attribute}` on a function parameter. Only the `param` attribute is allowed there.")]
+    UnexpectedParameterAttribute { attribute: String, span: Span },
+    #[error("The `param` attribute is only allowed on the parameters of ABI method signatures.")]
+    ParamAttributeNotAllowed { span: Span },
+    #[error(
+        "Expected `min = <integer>` or `max = <integer>` as an argument of the `param` attribute."
+    )]
+    InvalidParamAttributeArg { span: Span },
+    #[error("The `param` attribute allows no arguments, its minimum {min} is greater than its maximum {max}.")]
+    EmptyParamRange { min: u64, max: u64, span: Span },
+    #[error("The `when_not_paused` attribute is only allowed on the methods of ABIs implemented for `Contract`.")]
+    WhenNotPausedNotAllowed { span: Span },
+    #[error("`#[when_not_paused]` methods read the paused flag from storage and must be annotated with `#[storage(read)]`.")]
+    WhenNotPausedFnNotReadingStorage { span: Span },
 }
 
 impl Spanned for ConvertParseTreeError {
@@ -131,7 +153,6 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::PubUseNotSupported { span } => span.clone(),
             ConvertParseTreeError::FunctionArbitraryExpression { span } => span.clone(),
             ConvertParseTreeError::GenericsNotSupportedHere { span } => span.clone(),
-            ConvertParseTreeError::MultipleGenericsNotSupported { span } => span.clone(),
             ConvertParseTreeError::TupleIndexOutOfRange { span } => span.clone(),
             ConvertParseTreeError::ShlNotImplemented { span } => span.clone(),
             ConvertParseTreeError::ShrNotImplemented { span } => span.clone(),
@@ -141,6 +162,7 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::IntLiteralExpected { span } => span.clone(),
             ConvertParseTreeError::QualifiedPathRootsNotImplemented { span } => span.clone(),
             ConvertParseTreeError::CharLiteralsNotImplemented { span } => span.clone(),
+            ConvertParseTreeError::ByteStringLiteralNotSupportedHere { span } => span.clone(),
             ConvertParseTreeError::HexLiteralLength { span } => span.clone(),
             ConvertParseTreeError::BinaryLiteralLength { span } => span.clone(),
             ConvertParseTreeError::U8LiteralOutOfRange { span } => span.clone(),
@@ -163,6 +185,7 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::FullySpecifiedTypesNotSupported { span } => span.clone(),
             ConvertParseTreeError::ContractCallerOneGenericArg { span } => span.clone(),
             ConvertParseTreeError::ContractCallerNamedTypeGenericArg { span } => span.clone(),
+            ConvertParseTreeError::AbiMethodTypeParameters { span } => span.clone(),
             ConvertParseTreeError::InvalidAttributeArgument { span, .. } => span.clone(),
             ConvertParseTreeError::ConstrainedNonExistentType { span, .. } => span.clone(),
             ConvertParseTreeError::GetStorageKeyTooManyArgs { span, .. } => span.clone(),
@@ -190,6 +213,94 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::UnexpectedCallPathPrefixAfterQualifiedRoot { span } => {
                 span.clone()
             }
+            ConvertParseTreeError::MemoizedFnNotPure { span } => span.clone(),
+            ConvertParseTreeError::UnexpectedCfgTestArgValue { span } => span.clone(),
+            ConvertParseTreeError::ExpectedSpecificationCondition { span, .. } => span.clone(),
+            ConvertParseTreeError::UnexpectedParameterAttribute { span, .. } => span.clone(),
+            ConvertParseTreeError::ParamAttributeNotAllowed { span } => span.clone(),
+            ConvertParseTreeError::InvalidParamAttributeArg { span } => span.clone(),
+            ConvertParseTreeError::EmptyParamRange { span, .. } => span.clone(),
+            ConvertParseTreeError::WhenNotPausedNotAllowed { span } => span.clone(),
+            ConvertParseTreeError::WhenNotPausedFnNotReadingStorage { span } => span.clone(),
+        }
+    }
+}
+
+impl ConvertParseTreeError {
+    /// The stable code identifying this kind of error, see [CompileError::code].
+    pub fn code(&self) -> &'static str {
+        use ConvertParseTreeError::*;
+        match self {
+            PubUseNotSupported { .. } => "E3001",
+            FunctionArbitraryExpression { .. } => "E3002",
+            GenericsNotSupportedHere { .. } => "E3003",
+            TupleIndexOutOfRange { .. } => "E3005",
+            ShlNotImplemented { .. } => "E3006",
+            ShrNotImplemented { .. } => "E3007",
+            BitXorNotImplemented { .. } => "E3008",
+            IntTySuffixNotSupported { .. } => "E3009",
+            IntLiteralOutOfRange { .. } => "E3010",
+            IntLiteralExpected { .. } => "E3011",
+            QualifiedPathRootsNotImplemented { .. } => "E3012",
+            CharLiteralsNotImplemented { .. } => "E3013",
+            ByteStringLiteralNotSupportedHere { .. } => "E3014",
+            HexLiteralLength { .. } => "E3015",
+            BinaryLiteralLength { .. } => "E3016",
+            U8LiteralOutOfRange { .. } => "E3017",
+            U16LiteralOutOfRange { .. } => "E3018",
+            U32LiteralOutOfRange { .. } => "E3019",
+            U64LiteralOutOfRange { .. } => "E3020",
+            SignedIntegersNotSupported { .. } => "E3021",
+            RefVariablesNotSupported { .. } => "E3022",
+            LiteralPatternsNotSupportedHere { .. } => "E3023",
+            ConstantPatternsNotSupportedHere { .. } => "E3024",
+            ConstructorPatternsNotSupportedHere { .. } => "E3025",
+            StructPatternsNotSupportedHere { .. } => "E3026",
+            WildcardPatternsNotSupportedHere { .. } => "E3027",
+            OrPatternsNotSupportedHere { .. } => "E3028",
+            TuplePatternsNotSupportedHere { .. } => "E3029",
+            RefPatternsNotSupportedHere { .. } => "E3030",
+            ConstructorPatternOneArg { .. } => "E3031",
+            ConstructorPatternSubPatterns { .. } => "E3032",
+            PathsNotSupportedHere { .. } => "E3033",
+            FullySpecifiedTypesNotSupported { .. } => "E3034",
+            ContractCallerOneGenericArg { .. } => "E3035",
+            ContractCallerNamedTypeGenericArg { .. } => "E3036",
+            AbiMethodTypeParameters { .. } => "E3037",
+            InvalidAttributeArgument { .. } => "E3038",
+            ConstrainedNonExistentType { .. } => "E3039",
+            GetStorageKeyTooManyArgs { .. } => "E3040",
+            RecursiveType { .. } => "E3041",
+            DuplicateEnumVariant { .. } => "E3042",
+            DuplicateStorageField { .. } => "E3043",
+            DuplicateConfigurable { .. } => "E3044",
+            MultipleConfigurableBlocksInModule { .. } => "E3045",
+            DuplicateStructField { .. } => "E3046",
+            DuplicateParameterIdentifier { .. } => "E3047",
+            SelfParameterNotAllowedForFn { .. } => "E3048",
+            TestFnOnlyAllowedAtModuleLevel { .. } => "E3049",
+            SelfImplForContract { .. } => "E3050",
+            CannotDocCommentDependency { .. } => "E3051",
+            CannotAnnotateDependency { .. } => "E3052",
+            ExpectedDependencyAtBeginning { .. } => "E3053",
+            RefExprNotYetSupported { .. } => "E3054",
+            DerefExprNotYetSupported { .. } => "E3055",
+            ConstantRequiresExpression { .. } => "E3056",
+            ConstantRequiresTypeAscription { .. } => "E3057",
+            InvalidCfgTargetArgValue { .. } => "E3058",
+            ExpectedCfgTargetArgValue { .. } => "E3059",
+            InvalidCfgProgramTypeArgValue { .. } => "E3060",
+            ExpectedCfgProgramTypeArgValue { .. } => "E3061",
+            UnexpectedCallPathPrefixAfterQualifiedRoot { .. } => "E3062",
+            MemoizedFnNotPure { .. } => "E3063",
+            UnexpectedCfgTestArgValue { .. } => "E3064",
+            ExpectedSpecificationCondition { .. } => "E3065",
+            UnexpectedParameterAttribute { .. } => "E3066",
+            ParamAttributeNotAllowed { .. } => "E3067",
+            InvalidParamAttributeArg { .. } => "E3068",
+            EmptyParamRange { .. } => "E3069",
+            WhenNotPausedNotAllowed { .. } => "E3070",
+            WhenNotPausedFnNotReadingStorage { .. } => "E3071",
         }
     }
 }
@@ -68,11 +68,26 @@ pub enum CompileError {
     #[error("Function \"{name}\" was already defined in scope.")]
     MultipleDefinitionsOfFunction { name: Ident, span: Span },
     #[error("Name \"{name}\" is defined multiple times.")]
-    MultipleDefinitionsOfName { name: Ident, span: Span },
+    MultipleDefinitionsOfName {
+        name: Ident,
+        span: Span,
+        /// The span of the previous definition of the name, if known.
+        previous_span: Option<Span>,
+    },
     #[error("Constant \"{name}\" was already defined in scope.")]
-    MultipleDefinitionsOfConstant { name: Ident, span: Span },
+    MultipleDefinitionsOfConstant {
+        name: Ident,
+        span: Span,
+        /// The span of the previous definition of the constant, if known.
+        previous_span: Option<Span>,
+    },
     #[error("Assignment to immutable variable. Variable {name} is not declared as mutable.")]
-    AssignmentToNonMutable { name: Ident, span: Span },
+    AssignmentToNonMutable {
+        name: Ident,
+        span: Span,
+        /// The span of the name in the variable's declaration.
+        decl_span: Span,
+    },
     #[error(
         "Cannot call method \"{method_name}\" on variable \"{variable_name}\" because \
             \"{variable_name}\" is not declared as mutable."
@@ -86,6 +101,25 @@ pub enum CompileError {
         "This parameter was declared as mutable, which is not supported yet, did you mean to use ref mut?"
     )]
     MutableParameterNotSupported { param_name: Ident, span: Span },
+    #[error("The `param` attribute of \"{param_name}\" can't validate arguments of type \"{ty}\", only unsigned integers.")]
+    ParamValidationNotSupportedForType {
+        param_name: Ident,
+        ty: String,
+        span: Span,
+    },
+    #[error("The bound {bound} of the `param` attribute of \"{param_name}\" is out of the range of \"{ty}\".")]
+    ParamBoundOutOfRange {
+        param_name: Ident,
+        bound: u64,
+        ty: String,
+        span: Span,
+    },
+    #[error("The predicate is {size} bytes, which is more than the {max_size} bytes a predicate can be.")]
+    PredicateTooLarge {
+        size: u64,
+        max_size: u64,
+        span: Span,
+    },
     #[error("Cannot pass immutable argument to mutable parameter.")]
     ImmutableArgumentToMutableParameter { span: Span },
     #[error("ref mut or mut parameter is not allowed for contract ABI function.")]
@@ -156,6 +190,8 @@ pub enum CompileError {
     DoesNotTakeTypeArgumentsAsPrefix { name: Ident, span: Span },
     #[error("Type arguments are not allowed for this type.")]
     TypeArgumentsNotAllowed { span: Span },
+    #[error("Type arguments are given twice for \"{name}\". They can either follow \"{name}\" or the path segment before it, but not both.")]
+    DuplicateTypeArguments { name: Ident, span: Span },
     #[error("\"{name}\" needs type arguments.")]
     NeedsTypeArguments { name: Ident, span: Span },
     #[error(
@@ -304,6 +340,12 @@ pub enum CompileError {
         ty: String,
         trait_name: String,
         span: Span,
+        /// The span of the constraint requiring the trait.
+        constraint_span: Span,
+        /// The types and traits whose implementations required the trait, innermost first, e.g.
+        /// the type `Wrapper<u64>` and the trait `Eq` when `u64` doesn't implement `Eq` but the
+        /// implementation of `Eq` for `Wrapper<T>` requires `T: Eq`.
+        required_by: Vec<(String, String)>,
     },
     #[error(
         "Expects trait constraint \"{param}: {trait_name}\" which is missing from type parameter \"{param}\"."
@@ -312,6 +354,9 @@ pub enum CompileError {
         param: String,
         trait_name: String,
         span: Span,
+        /// The span of the constraint requiring the trait, e.g. in the declaration of the struct
+        /// whose type parameter `param` is given to.
+        constraint_span: Span,
     },
     #[error("The value \"{val}\" is too large to fit in this 6-bit immediate spot.")]
     Immediate06TooLarge { val: u64, span: Span },
@@ -435,6 +480,8 @@ pub enum CompileError {
         type_chain: String, // Pretty list of symbols, e.g., "a, b and c".
         span: Span,
     },
+    #[error("Type {name} is recursive, so it cannot be part of the signature of an ABI method.")]
+    RecursiveTypeInAbi { name: Ident, span: Span },
     #[error("The GM (get-metadata) opcode, when called from an external context, will cause the VM to panic.")]
     GMFromExternalContext { span: Span },
     #[error("The MINT opcode cannot be used in an external context.")]
@@ -620,6 +667,14 @@ pub enum CompileError {
     InitializedRegisterReassignment { name: String, span: Span },
     #[error("Control flow VM instructions are not allowed in assembly blocks.")]
     DisallowedControlFlowInstruction { name: String, span: Span },
+    #[error(
+        "\"{name}\" is neither a constant in scope nor a label declared in this assembly block."
+    )]
+    UnknownAsmImmediate { name: Ident, span: Span },
+    #[error("Constant \"{name}\" cannot be used as an assembly immediate. Only constants initialized with an integer literal are supported.")]
+    AsmImmediateConstantNotAnInteger { name: Ident, span: Span },
+    #[error("Label \"{name}\" is already declared in this assembly block.")]
+    DuplicateAsmLabel { name: Ident, span: Span },
     #[error("Calling private library method {name} is not allowed.")]
     CallingPrivateLibraryMethod { name: String, span: Span },
     #[error("Using intrinsic \"{intrinsic}\" in a predicate is not allowed.")]
@@ -669,6 +724,54 @@ pub enum CompileError {
     },
     #[error("Cannot call ABI supertrait's method as a contract method: \"{fn_name}\"")]
     AbiSupertraitMethodCallAsContractCall { fn_name: Ident, span: Span },
+    #[error("{description} are experimental and require the \"{feature}\" feature. Enable it by adding \"{feature}\" to `experimental` in the `[project]` section of Forc.toml, or with `--experimental {feature}`.")]
+    ExperimentalFeatureNotEnabled {
+        feature: String,
+        description: String,
+        span: Span,
+    },
+    #[error("The compiler cannot generate code for revision \"{vm_version}\" of the \"{build_target}\" VM. {}", if supported_vm_versions.is_empty() { "This VM has no revisions to choose from.".to_string() } else { format!("Supported revisions: {}.", supported_vm_versions.join(", ")) })]
+    UnsupportedVmVersion {
+        build_target: String,
+        vm_version: String,
+        supported_vm_versions: Vec<String>,
+        span: Span,
+    },
+    #[error("The \"{opcode}\" opcode requires VM >= {required_vm_version}, but code is generated for VM {vm_version}.")]
+    OpcodeRequiresVmVersion {
+        opcode: String,
+        required_vm_version: String,
+        vm_version: String,
+        span: Span,
+    },
+    #[error("Calling \"{fn_name}\" requires VM >= {required_vm_version}, as it uses the \"{opcode}\" opcode, but code is generated for VM {vm_version}.")]
+    CallRequiresVmVersion {
+        fn_name: String,
+        opcode: String,
+        required_vm_version: String,
+        vm_version: String,
+        span: Span,
+    },
+    #[error("{warning}")]
+    DeniedWarning {
+        warning: String,
+        lint: String,
+        span: Span,
+    },
+    #[error("There is no IR pass named \"{name}\".")]
+    UnknownIrPass {
+        name: String,
+        valid_passes: Vec<String>,
+        span: Span,
+    },
+    #[error("The IR could not be parsed: {error}")]
+    InvalidIr { error: String, span: Span },
+    #[error("{error}")]
+    WithContext {
+        error: Box<CompileError>,
+        /// Where the error surfaced, from the innermost to the outermost frame.
+        context: Vec<ErrorContext>,
+    },
 }
 
 impl std::convert::From<TypeError> for CompileError {
@@ -697,6 +800,9 @@ impl Spanned for CompileError {
             MultipleDefinitionsOfConstant { span, .. } => span.clone(),
             AssignmentToNonMutable { span, .. } => span.clone(),
             MutableParameterNotSupported { span, .. } => span.clone(),
+            ParamValidationNotSupportedForType { span, .. } => span.clone(),
+            ParamBoundOutOfRange { span, .. } => span.clone(),
+            PredicateTooLarge { span, .. } => span.clone(),
             ImmutableArgumentToMutableParameter { span } => span.clone(),
             RefMutableNotAllowedInContractAbi { span, .. } => span.clone(),
             MethodRequiresMutableSelf { span, .. } => span.clone(),
@@ -712,6 +818,7 @@ impl Spanned for CompileError {
             DoesNotTakeTypeArguments { span, .. } => span.clone(),
             DoesNotTakeTypeArgumentsAsPrefix { span, .. } => span.clone(),
             TypeArgumentsNotAllowed { span } => span.clone(),
+            DuplicateTypeArguments { span, .. } => span.clone(),
             NeedsTypeArguments { span, .. } => span.clone(),
             StructMissingField { span, .. } => span.clone(),
             StructDoesNotHaveField { span, .. } => span.clone(),
@@ -771,6 +878,7 @@ impl Spanned for CompileError {
             RecursiveCallChain { span, .. } => span.clone(),
             RecursiveType { span, .. } => span.clone(),
             RecursiveTypeChain { span, .. } => span.clone(),
+            RecursiveTypeInAbi { span, .. } => span.clone(),
             GMFromExternalContext { span, .. } => span.clone(),
             MintFromExternalContext { span, .. } => span.clone(),
             BurnFromExternalContext { span, .. } => span.clone(),
@@ -836,6 +944,9 @@ impl Spanned for CompileError {
             NestedSliceReturnNotAllowedInMain { span } => span.clone(),
             InitializedRegisterReassignment { span, .. } => span.clone(),
             DisallowedControlFlowInstruction { span, .. } => span.clone(),
+            UnknownAsmImmediate { span, .. } => span.clone(),
+            AsmImmediateConstantNotAnInteger { span, .. } => span.clone(),
+            DuplicateAsmLabel { span, .. } => span.clone(),
             CallingPrivateLibraryMethod { span, .. } => span.clone(),
             DisallowedIntrinsicInPredicate { span, .. } => span.clone(),
             CoinsPassedToNonPayableMethod { span, .. } => span.clone(),
@@ -847,6 +958,14 @@ impl Spanned for CompileError {
             AbiShadowsSuperAbiMethod { span, .. } => span.clone(),
             ConflictingSuperAbiMethods { span, .. } => span.clone(),
             AbiSupertraitMethodCallAsContractCall { span, .. } => span.clone(),
+            ExperimentalFeatureNotEnabled { span, .. } => span.clone(),
+            UnsupportedVmVersion { span, .. } => span.clone(),
+            OpcodeRequiresVmVersion { span, .. } => span.clone(),
+            CallRequiresVmVersion { span, .. } => span.clone(),
+            DeniedWarning { span, .. } => span.clone(),
+            UnknownIrPass { span, .. } => span.clone(),
+            InvalidIr { span, .. } => span.clone(),
+            WithContext { error, .. } => error.span(),
         }
     }
 }
@@ -855,6 +974,349 @@ impl CompileError {
     pub fn source_id(&self) -> Option<SourceId> {
         self.span().source_id().cloned()
     }
+
+    /// The stable code identifying this kind of error, e.g. `E0001`, which `forc explain` takes
+    /// to describe the error in detail.
+    ///
+    /// Codes are never reassigned, so that they can be searched for. See
+    /// [crate::explanations] for how the codes are allocated.
+    pub fn code(&self) -> &'static str {
+        use CompileError::*;
+        match self {
+            UnknownVariable { .. } => "E0001",
+            NotAVariable { .. } => "E0002",
+            Unimplemented(..) => "E0003",
+            UnimplementedWithHelp(..) => "E0004",
+            TypeError(error) => error.code(),
+            ParseError { .. } => "E0005",
+            Internal(..) => "E0006",
+            InternalOwned(..) => "E0007",
+            NoPredicateMainFunction(..) => "E0008",
+            PredicateMainDoesNotReturnBool(..) => "E0009",
+            NoScriptMainFunction(..) => "E0010",
+            MultipleDefinitionsOfFunction { .. } => "E0011",
+            MultipleDefinitionsOfName { .. } => "E0012",
+            MultipleDefinitionsOfConstant { .. } => "E0013",
+            AssignmentToNonMutable { .. } => "E0014",
+            MethodRequiresMutableSelf { .. } => "E0015",
+            MutableParameterNotSupported { .. } => "E0016",
+            ImmutableArgumentToMutableParameter { .. } => "E0017",
+            RefMutableNotAllowedInContractAbi { .. } => "E0018",
+            AssociatedFunctionCalledAsMethod { .. } => "E0019",
+            TypeParameterNotInTypeScope { .. } => "E0020",
+            MismatchedTypeInInterfaceSurface { .. } => "E0021",
+            UnknownTrait { .. } => "E0022",
+            FunctionNotAPartOfInterfaceSurface { .. } => "E0023",
+            ConstantNotAPartOfInterfaceSurface { .. } => "E0024",
+            MissingInterfaceSurfaceConstants { .. } => "E0025",
+            MissingInterfaceSurfaceMethods { .. } => "E0026",
+            IncorrectNumberOfTypeArguments { .. } => "E0027",
+            DoesNotTakeTypeArguments { .. } => "E0028",
+            DoesNotTakeTypeArgumentsAsPrefix { .. } => "E0029",
+            TypeArgumentsNotAllowed { .. } => "E0030",
+            NeedsTypeArguments { .. } => "E0031",
+            EnumNotFound { .. } => "E0032",
+            StructMissingField { .. } => "E0033",
+            StructDoesNotHaveField { .. } => "E0034",
+            MethodNotFound { .. } => "E0035",
+            ModuleNotFound { .. } => "E0036",
+            FieldAccessOnNonStruct { .. } => "E0037",
+            NotATuple { .. } => "E0038",
+            NotIndexable { .. } => "E0039",
+            NotAnEnum { .. } => "E0040",
+            NotAStruct { .. } => "E0041",
+            DeclIsNotAnEnum { .. } => "E0042",
+            DeclIsNotAStruct { .. } => "E0043",
+            DeclIsNotAFunction { .. } => "E0044",
+            DeclIsNotAVariable { .. } => "E0045",
+            DeclIsNotAnAbi { .. } => "E0046",
+            DeclIsNotATrait { .. } => "E0047",
+            DeclIsNotAnImplTrait { .. } => "E0048",
+            DeclIsNotATraitFn { .. } => "E0049",
+            DeclIsNotStorage { .. } => "E0050",
+            DeclIsNotAConstant { .. } => "E0051",
+            DeclIsNotATypeAlias { .. } => "E0052",
+            FieldNotFound { .. } => "E0053",
+            SymbolNotFound { .. } => "E0054",
+            ImportPrivateSymbol { .. } => "E0055",
+            ImportPrivateModule { .. } => "E0056",
+            NoElseBranch { .. } => "E0057",
+            NotAType { .. } => "E0058",
+            MissingEnumInstantiator { .. } => "E0059",
+            PathDoesNotReturn { .. } => "E0060",
+            ExpectedModuleDocComment { .. } => "E0061",
+            UnknownRegister { .. } => "E0062",
+            MissingImmediate { .. } => "E0063",
+            InvalidImmediateValue { .. } => "E0064",
+            UnknownEnumVariant { .. } => "E0065",
+            UnrecognizedOp { .. } => "E0066",
+            UnableToInferGeneric { .. } => "E0067",
+            UnconstrainedGenericParameter { .. } => "E0068",
+            TraitConstraintNotSatisfied { .. } => "E0069",
+            TraitConstraintMissing { .. } => "E0070",
+            Immediate06TooLarge { .. } => "E0071",
+            Immediate12TooLarge { .. } => "E0072",
+            Immediate18TooLarge { .. } => "E0073",
+            Immediate24TooLarge { .. } => "E0074",
+            IncorrectNumberOfAsmRegisters { .. } => "E0075",
+            UnnecessaryImmediate { .. } => "E0076",
+            AmbiguousPath { .. } => "E0077",
+            UnknownType { .. } => "E0078",
+            UnknownTypeName { .. } => "E0079",
+            FileCouldNotBeRead { .. } => "E0080",
+            ImportMustBeLibrary { .. } => "E0081",
+            MoreThanOneEnumInstantiator { .. } => "E0082",
+            UnnecessaryEnumInstantiator { .. } => "E0083",
+            UnitVariantWithParenthesesEnumInstantiator { .. } => "E0084",
+            TraitNotFound { .. } => "E0085",
+            InvalidExpressionOnLhs { .. } => "E0086",
+            CannotBeEvaluatedToConst { .. } => "E0087",
+            TooManyArgumentsForFunction { .. } => "E0088",
+            TooFewArgumentsForFunction { .. } => "E0089",
+            MissingParenthesesForFunction { .. } => "E0090",
+            InvalidAbiType { .. } => "E0091",
+            NotAnAbi { .. } => "E0092",
+            ImplAbiForNonContract { .. } => "E0093",
+            ConflictingImplsForTraitAndType { .. } => "E0094",
+            DuplicateDeclDefinedForType { .. } => "E0095",
+            IncorrectNumberOfInterfaceSurfaceFunctionParameters { .. } => "E0096",
+            ArgumentParameterTypeMismatch { .. } => "E0097",
+            RecursiveCall { .. } => "E0098",
+            RecursiveCallChain { .. } => "E0099",
+            RecursiveType { .. } => "E0100",
+            RecursiveTypeChain { .. } => "E0101",
+            GMFromExternalContext { .. } => "E0102",
+            MintFromExternalContext { .. } => "E0103",
+            BurnFromExternalContext { .. } => "E0104",
+            ContractStorageFromExternalContext { .. } => "E0105",
+            InvalidOpcodeFromPredicate { .. } => "E0106",
+            ArrayOutOfBounds { .. } => "E0107",
+            TupleIndexOutOfBounds { .. } => "E0108",
+            VariableShadowsConstant { .. } => "E0109",
+            ConstantShadowsVariable { .. } => "E0110",
+            ConstantShadowsConstant { .. } => "E0111",
+            ShadowsOtherSymbol { .. } => "E0112",
+            GenericShadowsGeneric { .. } => "E0113",
+            MatchExpressionNonExhaustive { .. } => "E0114",
+            MatchStructPatternMissingFields { .. } => "E0115",
+            MatchVariableNotBoundInAllPatterns { .. } => "E0116",
+            StorageAccessMismatch { .. } => "E0117",
+            TraitDeclPureImplImpure { .. } => "E0118",
+            TraitImplPurityMismatch { .. } => "E0119",
+            ImpureInNonContract { .. } => "E0120",
+            ImpureInPureContext { .. } => "E0121",
+            ParameterRefMutabilityMismatch { .. } => "E0122",
+            IntegerTooLarge { .. } => "E0123",
+            IntegerTooSmall { .. } => "E0124",
+            IntegerContainsInvalidDigit { .. } => "E0125",
+            AbiAsSupertrait { .. } => "E0126",
+            SupertraitImplRequired { .. } => "E0127",
+            ContractCallParamRepeated { .. } => "E0128",
+            UnrecognizedContractParam { .. } => "E0129",
+            CallParamForNonContractCallMethod { .. } => "E0130",
+            StorageFieldDoesNotExist { .. } => "E0131",
+            NoDeclaredStorage { .. } => "E0132",
+            MultipleStorageDeclarations { .. } => "E0133",
+            InvalidStorageOnlyTypeDecl { .. } => "E0134",
+            UnexpectedDeclaration { .. } => "E0135",
+            ContractAddressMustBeKnown { .. } => "E0136",
+            ConvertParseTree { error } => error.code(),
+            Lex { error } => error.kind.code(),
+            Parse { error } => error.kind.code(),
+            NonConstantDeclValue { .. } => "E0137",
+            StorageDeclarationInNonContract { .. } => "E0138",
+            IntrinsicUnsupportedArgType { .. } => "E0139",
+            IntrinsicIncorrectNumArgs { .. } => "E0140",
+            IntrinsicIncorrectNumTArgs { .. } => "E0141",
+            BreakOutsideLoop { .. } => "E0142",
+            ContinueOutsideLoop { .. } => "E0143",
+            ContractIdConstantNotAConstDecl { .. } => "E0144",
+            ContractIdValueNotALiteral { .. } => "E0145",
+            TypeNotAllowedInContractStorage { .. } => "E0146",
+            RefMutableNotAllowedInMain { .. } => "E0147",
+            NestedSliceReturnNotAllowedInMain { .. } => "E0148",
+            InitializedRegisterReassignment { .. } => "E0149",
+            DisallowedControlFlowInstruction { .. } => "E0150",
+            UnknownAsmImmediate { .. } => "E0151",
+            AsmImmediateConstantNotAnInteger { .. } => "E0152",
+            DuplicateAsmLabel { .. } => "E0153",
+            CallingPrivateLibraryMethod { .. } => "E0154",
+            DisallowedIntrinsicInPredicate { .. } => "E0155",
+            CoinsPassedToNonPayableMethod { .. } => "E0156",
+            TraitImplPayabilityMismatch { .. } => "E0157",
+            ConfigurableInLibrary { .. } => "E0158",
+            MultipleApplicableItemsInScope { .. } => "E0159",
+            ContractCallsItsOwnMethod { .. } => "E0160",
+            AbiShadowsSuperAbiMethod { .. } => "E0161",
+            ConflictingSuperAbiMethods { .. } => "E0162",
+            AbiSupertraitMethodCallAsContractCall { .. } => "E0163",
+            ExperimentalFeatureNotEnabled { .. } => "E0164",
+            UnsupportedVmVersion { .. } => "E0165",
+            OpcodeRequiresVmVersion { .. } => "E0166",
+            CallRequiresVmVersion { .. } => "E0167",
+            DeniedWarning { .. } => "E0168",
+            UnknownIrPass { .. } => "E0169",
+            DuplicateTypeArguments { .. } => "E0170",
+            ParamValidationNotSupportedForType { .. } => "E0171",
+            ParamBoundOutOfRange { .. } => "E0172",
+            PredicateTooLarge { .. } => "E0173",
+            RecursiveTypeInAbi { .. } => "E0174",
+            InvalidIr { .. } => "E0175",
+            WithContext { error, .. } => error.code(),
+        }
+    }
+
+    /// Adds `frame` as the outermost frame of context about where this error surfaced.
+    pub fn with_context(self, frame: ErrorContext) -> CompileError {
+        match self {
+            CompileError::WithContext { error, mut context } => {
+                context.push(frame);
+                CompileError::WithContext { error, context }
+            }
+            error => CompileError::WithContext {
+                error: Box::new(error),
+                context: vec![frame],
+            },
+        }
+    }
+
+    /// The frames of context about where this error surfaced, from the innermost to the
+    /// outermost one.
+    pub fn context(&self) -> &[ErrorContext] {
+        match self {
+            CompileError::WithContext { context, .. } => context,
+            _ => &[],
+        }
+    }
+
+    /// The secondary spans of the error, each labeled with how it relates to the error, e.g.
+    /// pointing at a conflicting declaration.
+    pub fn secondary_labels(&self) -> Vec<SecondaryLabel> {
+        use CompileError::*;
+        match self {
+            MultipleDefinitionsOfName {
+                name,
+                previous_span: Some(previous_span),
+                ..
+            }
+            | MultipleDefinitionsOfConstant {
+                name,
+                previous_span: Some(previous_span),
+                ..
+            } => vec![SecondaryLabel::new(
+                previous_span.clone(),
+                format!("previous definition of \"{name}\" here"),
+            )],
+            AssignmentToNonMutable {
+                name, decl_span, ..
+            } => vec![SecondaryLabel::new(
+                decl_span.clone(),
+                format!("variable \"{name}\" is declared here as immutable"),
+            )],
+            TraitConstraintNotSatisfied {
+                trait_name,
+                constraint_span,
+                ..
+            }
+            | TraitConstraintMissing {
+                trait_name,
+                constraint_span,
+                ..
+            } => vec![SecondaryLabel::new(
+                constraint_span.clone(),
+                format!("\"{trait_name}\" is required by this constraint"),
+            )],
+            WithContext { error, .. } => error.secondary_labels(),
+            _ => vec![],
+        }
+    }
+
+    /// Messages suggesting how to fix the error, shown along with it.
+    pub fn help(&self) -> Vec<String> {
+        use CompileError::*;
+        match self {
+            AssignmentToNonMutable { name, .. } => vec![format!(
+                "consider making the variable mutable by declaring it with `let mut {name}`"
+            )],
+            TraitConstraintNotSatisfied {
+                ty,
+                trait_name,
+                required_by,
+                ..
+            } => required_by
+                .iter()
+                .map(|(ty, trait_name)| {
+                    format!("required for \"{ty}\" to implement \"{trait_name}\"")
+                })
+                .chain(std::iter::once(format!(
+                    "implement \"{trait_name}\" for \"{ty}\", or use a type implementing it"
+                )))
+                .collect(),
+            DeniedWarning { lint, .. } => vec![format!(
+                "the \"{lint}\" lint is denied, so its warnings are reported as errors"
+            )],
+            UnknownIrPass { valid_passes, .. } => {
+                vec![format!("valid pass names are: {}", valid_passes.join(", "))]
+            }
+            PredicateTooLarge { .. } => vec![
+                "the size of each function is printed by `forc build --size-report`".to_string(),
+            ],
+            RecursiveType { name, .. } => vec![format!(
+                "a type which isn't generic may refer to itself through a `Box`, e.g. `Box<{name}>`"
+            )],
+            WithContext { error, .. } => error.help(),
+            _ => vec![],
+        }
+    }
+
+    /// This error without any of its context.
+    pub fn without_context(&self) -> &CompileError {
+        match self {
+            CompileError::WithContext { error, .. } => error,
+            error => error,
+        }
+    }
+}
+
+/// A secondary span of an error, labeled with how the code it points at relates to the error.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecondaryLabel {
+    pub span: Span,
+    pub message: String,
+}
+
+impl SecondaryLabel {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// A frame of context about where an error surfaced, shown along with the error as e.g.
+/// "while type checking function `foo`".
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorContext {
+    /// The phase of the compilation, e.g. "type checking".
+    pub phase: &'static str,
+    /// The declaration being processed, e.g. "function `foo`".
+    pub declaration: String,
+}
+
+impl ErrorContext {
+    pub fn new(phase: &'static str, declaration: impl Into<String>) -> Self {
+        Self {
+            phase,
+            declaration: declaration.into(),
+        }
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "while {} {}", self.phase, self.declaration)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -877,3 +1339,66 @@ impl fmt::Display for Hint {
         write!(f, "Hint: {}", &self.msg.as_ref().unwrap_or(&"".to_string()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn context_frames_accumulate_outwards() {
+        let error = CompileError::Internal("oops", Span::dummy())
+            .with_context(ErrorContext::new("type checking", "function `foo`"))
+            .with_context(ErrorContext::new("type checking", "impl for `Bar`"));
+
+        assert_eq!(
+            error.to_string(),
+            CompileError::Internal("oops", Span::dummy()).to_string()
+        );
+        assert!(matches!(
+            error.without_context(),
+            CompileError::Internal("oops", _)
+        ));
+        let frames = error
+            .context()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            [
+                "while type checking function `foo`",
+                "while type checking impl for `Bar`"
+            ]
+        );
+    }
+
+    #[test]
+    fn secondary_labels_and_help_survive_context() {
+        let name = Ident::new_no_span("x".to_string());
+        let error = CompileError::AssignmentToNonMutable {
+            name: name.clone(),
+            span: Span::dummy(),
+            decl_span: Span::dummy(),
+        }
+        .with_context(ErrorContext::new("type checking", "function `main`"));
+
+        let labels = error.secondary_labels();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(
+            labels[0].message,
+            "variable \"x\" is declared here as immutable"
+        );
+        assert_eq!(
+            error.help(),
+            ["consider making the variable mutable by declaring it with `let mut x`"]
+        );
+
+        let error = CompileError::MultipleDefinitionsOfName {
+            name,
+            span: Span::dummy(),
+            previous_span: None,
+        };
+        assert!(error.secondary_labels().is_empty());
+        assert!(error.help().is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+//! Extended explanations of diagnostics, looked up by their codes, e.g. with `forc explain E0001`.
+//!
+//! Every kind of error and warning has a stable code, returned by [CompileError::code] and
+//! [Warning::code]. The codes are allocated in ranges by the kind of the diagnostic:
+//!
+//! - `E0001` to `E0999`: errors of [CompileError] itself.
+//! - `E1001` to `E1999`: parse errors, [ParseErrorKind].
+//! - `E2001` to `E2999`: lexing errors, [LexErrorKind].
+//! - `E3001` to `E3999`: errors converting the parse tree, [ConvertParseTreeError].
+//! - `E4001` to `E4999`: type errors, [TypeError].
+//! - `W0001` to `W0999`: warnings, [Warning].
+//!
+//! A new variant takes the next unused code of its range, and the codes of removed variants are
+//! never reused.
+//!
+//! [CompileError::code]: crate::error::CompileError::code
+//! [Warning::code]: crate::warning::Warning::code
+//! [CompileError]: crate::error::CompileError
+//! [ParseErrorKind]: crate::parser_error::ParseErrorKind
+//! [LexErrorKind]: crate::lex_error::LexErrorKind
+//! [ConvertParseTreeError]: crate::convert_parse_tree_error::ConvertParseTreeError
+//! [TypeError]: crate::type_error::TypeError
+//! [Warning]: crate::warning::Warning
+
+/// The explanations of diagnostics, in Markdown, by their codes. Closely related diagnostics,
+/// e.g. the naming convention warnings, share an explanation.
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("E0001", include_str!("explanations/E0001.md")),
+    ("E0002", include_str!("explanations/E0002.md")),
+    ("E0011", include_str!("explanations/E0011.md")),
+    ("E0012", include_str!("explanations/E0012.md")),
+    ("E0014", include_str!("explanations/E0014.md")),
+    ("E0015", include_str!("explanations/E0015.md")),
+    ("E0017", include_str!("explanations/E0017.md")),
+    ("E0022", include_str!("explanations/E0022.md")),
+    ("E0026", include_str!("explanations/E0026.md")),
+    ("E0027", include_str!("explanations/E0027.md")),
+    ("E0033", include_str!("explanations/E0033.md")),
+    ("E0034", include_str!("explanations/E0034.md")),
+    ("E0035", include_str!("explanations/E0035.md")),
+    ("E0054", include_str!("explanations/E0054.md")),
+    ("E0057", include_str!("explanations/E0057.md")),
+    ("E0060", include_str!("explanations/E0060.md")),
+    ("E0065", include_str!("explanations/E0065.md")),
+    ("E0067", include_str!("explanations/E0067.md")),
+    ("E0069", include_str!("explanations/E0069.md")),
+    ("E0088", include_str!("explanations/E0088.md")),
+    ("E0089", include_str!("explanations/E0089.md")),
+    ("E0098", include_str!("explanations/E0098.md")),
+    ("E0114", include_str!("explanations/E0114.md")),
+    ("E0117", include_str!("explanations/E0117.md")),
+    ("E0120", include_str!("explanations/E0120.md")),
+    ("E0142", include_str!("explanations/E0142.md")),
+    ("E0164", include_str!("explanations/E0164.md")),
+    ("E4001", include_str!("explanations/E4001.md")),
+    ("E4002", include_str!("explanations/E4002.md")),
+    ("E4003", include_str!("explanations/E4003.md")),
+    ("W0001", include_str!("explanations/W0001.md")),
+    ("W0002", include_str!("explanations/W0001.md")),
+    ("W0003", include_str!("explanations/W0001.md")),
+    ("W0004", include_str!("explanations/W0001.md")),
+    ("W0005", include_str!("explanations/W0001.md")),
+    ("W0006", include_str!("explanations/W0007.md")),
+    ("W0007", include_str!("explanations/W0007.md")),
+    ("W0008", include_str!("explanations/W0008.md")),
+    ("W0009", include_str!("explanations/W0009.md")),
+    ("W0013", include_str!("explanations/W0013.md")),
+    ("W0014", include_str!("explanations/W0014.md")),
+    ("W0015", include_str!("explanations/W0015.md")),
+    ("W0016", include_str!("explanations/W0013.md")),
+    ("W0017", include_str!("explanations/W0017.md")),
+    ("W0018", include_str!("explanations/W0013.md")),
+    ("W0019", include_str!("explanations/W0013.md")),
+    ("W0020", include_str!("explanations/W0020.md")),
+    ("W0021", include_str!("explanations/W0013.md")),
+    ("W0022", include_str!("explanations/W0013.md")),
+    ("W0023", include_str!("explanations/W0013.md")),
+    ("W0025", include_str!("explanations/W0013.md")),
+    ("W0027", include_str!("explanations/W0027.md")),
+    ("W0031", include_str!("explanations/W0031.md")),
+    ("W0034", include_str!("explanations/W0034.md")),
+    ("W0035", include_str!("explanations/W0035.md")),
+];
+
+/// Returns the extended explanation, in Markdown, of the diagnostic with the given code, if
+/// there is one.
+///
+/// Codes are matched case-insensitively, so that both `E0001` and `e0001` are found.
+pub fn explanation(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(known_code, _)| known_code.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| *explanation)
+}
+
+/// Whether `code` has the form of a diagnostic code, i.e. `E` or `W` followed by four digits.
+pub fn is_well_formed_code(code: &str) -> bool {
+    let mut chars = code.chars();
+    matches!(chars.next(), Some('E' | 'e' | 'W' | 'w'))
+        && code.len() == 5
+        && chars.all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::CompileError;
+    use sway_types::{Ident, Span};
+
+    #[test]
+    fn explanations_are_sorted_and_well_formed() {
+        for window in EXPLANATIONS.windows(2) {
+            assert!(window[0].0 < window[1].0, "{} is out of order", window[1].0);
+        }
+        for (code, explanation) in EXPLANATIONS {
+            assert!(is_well_formed_code(code), "{code} is not a valid code");
+            assert!(!explanation.trim().is_empty(), "{code} has no explanation");
+        }
+    }
+
+    #[test]
+    fn errors_are_explained_by_their_code() {
+        let error = CompileError::UnknownVariable {
+            var_name: Ident::new_no_span("x".to_string()),
+            span: Span::dummy(),
+        };
+        assert_eq!(error.code(), "E0001");
+        assert!(explanation(error.code()).is_some());
+        assert_eq!(explanation("e0001"), explanation("E0001"));
+        assert!(explanation("E0999").is_none());
+        assert_eq!(explanation("W0016"), explanation("W0013"));
+        assert!(!is_well_formed_code("E001"));
+        assert!(!is_well_formed_code("X0001"));
+    }
+}
@@ -42,6 +42,12 @@ pub enum LexErrorKind {
     IncompleteBinaryIntLiteral { position: usize },
     #[error("incomplete octal int literal")]
     IncompleteOctalIntLiteral { position: usize },
+    #[error("invalid digit {:?} in {} int literal", digit, radix_name(*radix))]
+    InvalidDigitInIntLiteral {
+        position: usize,
+        digit: char,
+        radix: u32,
+    },
     #[error("invalid int suffix: {}", suffix)]
     InvalidIntSuffix { suffix: Ident },
     #[error("invalid character")]
@@ -58,6 +64,17 @@ pub enum LexErrorKind {
     UnicodeEscapeInvalidCharValue { span: Span },
     #[error("invalid escape code")]
     InvalidEscapeCode { position: usize },
+    #[error("byte string literals may only contain ASCII characters and byte escapes")]
+    InvalidByteStringCharacter { position: usize },
+}
+
+fn radix_name(radix: u32) -> &'static str {
+    match radix {
+        2 => "binary",
+        8 => "octal",
+        16 => "hex",
+        _ => "decimal",
+    }
 }
 
 impl Spanned for LexError {
@@ -71,3 +88,32 @@ impl LexError {
         &self.span
     }
 }
+
+impl LexErrorKind {
+    /// The stable code identifying this kind of lexing error, see [CompileError::code].
+    pub fn code(&self) -> &'static str {
+        use LexErrorKind::*;
+        match self {
+            UnclosedMultilineComment { .. } => "E2001",
+            UnexpectedCloseDelimiter { .. } => "E2002",
+            MismatchedDelimiters { .. } => "E2003",
+            UnclosedDelimiter { .. } => "E2004",
+            UnclosedStringLiteral { .. } => "E2005",
+            UnclosedCharLiteral { .. } => "E2006",
+            ExpectedCloseQuote { .. } => "E2007",
+            IncompleteHexIntLiteral { .. } => "E2008",
+            IncompleteBinaryIntLiteral { .. } => "E2009",
+            IncompleteOctalIntLiteral { .. } => "E2010",
+            InvalidDigitInIntLiteral { .. } => "E2011",
+            InvalidIntSuffix { .. } => "E2012",
+            InvalidCharacter { .. } => "E2013",
+            InvalidHexEscape => "E2014",
+            UnicodeEscapeMissingBrace { .. } => "E2015",
+            InvalidUnicodeEscapeDigit { .. } => "E2016",
+            UnicodeEscapeOutOfRange { .. } => "E2017",
+            UnicodeEscapeInvalidCharValue { .. } => "E2018",
+            InvalidEscapeCode { .. } => "E2019",
+            InvalidByteStringCharacter { .. } => "E2020",
+        }
+    }
+}
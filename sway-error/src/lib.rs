@@ -1,5 +1,6 @@
 pub mod convert_parse_tree_error;
 pub mod error;
+pub mod explanations;
 pub mod handler;
 pub mod lex_error;
 pub mod parser_error;
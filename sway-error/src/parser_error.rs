@@ -86,6 +86,8 @@ pub enum ParseErrorKind {
     UnexpectedTokenAfterSliceType,
     #[error("Expected a path type.")]
     ExpectedPathType,
+    #[error("`||` cannot follow an `if let` scrutinee. Wrap the scrutinee in parentheses.")]
+    LogicalOrAfterLetScrutinee,
 }
 
 #[derive(Debug, Error, Clone, PartialEq, Eq, Hash)]
@@ -94,3 +96,54 @@ pub struct ParseError {
     pub span: Span,
     pub kind: ParseErrorKind,
 }
+
+impl ParseErrorKind {
+    /// The stable code identifying this kind of parse error, see [CompileError::code].
+    pub fn code(&self) -> &'static str {
+        use ParseErrorKind::*;
+        match self {
+            ExpectedImportNameGroupOrGlob => "E1001",
+            ExpectedAnItem => "E1002",
+            ExpectedCommaOrCloseParenInFnArgs => "E1003",
+            UnrecognizedOpCode => "E1004",
+            UnexpectedTokenInStatement => "E1005",
+            UnassignableExpression => "E1006",
+            UnexpectedTokenAfterArrayIndex => "E1007",
+            InvalidLiteralFieldName => "E1008",
+            IntFieldWithTypeSuffix => "E1009",
+            ExpectedFieldName => "E1010",
+            ExpectedCommaOrCloseParenInTupleOrParenExpression => "E1011",
+            ExpectedExpression => "E1012",
+            UnexpectedTokenAfterArrayLength => "E1013",
+            ExpectedCommaSemicolonOrCloseBracketInArray => "E1014",
+            UnexpectedTokenAfterAsmReturnType => "E1015",
+            MalformedAsmImmediate => "E1016",
+            ExpectedIdent => "E1017",
+            UnexpectedTokenAfterStrLength => "E1018",
+            ExpectedType => "E1019",
+            UnexpectedTokenAfterArrayTypeLength => "E1020",
+            ExpectedOpenBrace => "E1021",
+            ExpectedOpenParen => "E1022",
+            ExpectedOpenBracket => "E1023",
+            ExpectedLiteral => "E1024",
+            ExpectedModuleKind => "E1025",
+            ExpectedPunct { .. } => "E1026",
+            ExpectedKeyword { .. } => "E1027",
+            UnexpectedTokenAfterAbiAddress => "E1028",
+            ExpectedAnAttribute => "E1029",
+            UnexpectedTokenAfterAttribute => "E1030",
+            InvalidDoubleUnderscore => "E1031",
+            UnexpectedRestPattern => "E1032",
+            ReservedKeywordIdentifier => "E1033",
+            UnnecessaryVisibilityQualifier { .. } => "E1034",
+            ExpectedDocComment => "E1035",
+            ExpectedModuleDocComment => "E1036",
+            UnexpectedClass => "E1037",
+            FieldProjectionWithGenericArgs => "E1038",
+            UnexpectedTokenAfterPtrType => "E1039",
+            UnexpectedTokenAfterSliceType => "E1040",
+            ExpectedPathType => "E1041",
+            LogicalOrAfterLetScrutinee => "E1042",
+        }
+    }
+}
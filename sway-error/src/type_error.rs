@@ -41,3 +41,15 @@ impl Spanned for TypeError {
         }
     }
 }
+
+impl TypeError {
+    /// The stable code identifying this kind of type error, see [CompileError::code].
+    pub fn code(&self) -> &'static str {
+        use TypeError::*;
+        match self {
+            MismatchedType { .. } => "E4001",
+            UnknownType { .. } => "E4002",
+            MatchArmScrutineeWrongType { .. } => "E4003",
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use core::fmt;
+use std::collections::{hash_map::Entry, HashMap};
 
 use sway_types::{Ident, SourceId, Span, Spanned};
 
@@ -24,6 +25,78 @@ impl CompileWarning {
     pub fn source_id(&self) -> Option<SourceId> {
         self.span.source_id().cloned()
     }
+
+    /// The stable code identifying the kind of this warning, see [Warning::code].
+    pub fn code(&self) -> &str {
+        self.warning_content.code()
+    }
+
+    /// Returns the machine-applicable fixes for this warning, if any.
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        match &self.warning_content {
+            Warning::UnusedVariable { variable_name } => vec![Suggestion {
+                message: format!(
+                    "If this is intentional, prefix it with an underscore: `_{variable_name}`"
+                ),
+                span: variable_name.span(),
+                replacement: format!("_{variable_name}"),
+            }],
+            Warning::UnusedImport {
+                use_span: Some(use_span),
+                ..
+            } => vec![Suggestion {
+                message: "Remove the unused import".to_string(),
+                span: use_span.clone(),
+                replacement: String::new(),
+            }],
+            Warning::Replayed { suggestions, .. } => suggestions.clone(),
+            _ => vec![],
+        }
+    }
+}
+
+/// A warning that was emitted one or more times for the same span.
+#[derive(Debug, Clone, Copy)]
+pub struct AggregatedWarning<'a> {
+    /// The first of the emitted warnings.
+    pub warning: &'a CompileWarning,
+    /// How many times a warning of this kind was emitted for the span, e.g. once per
+    /// instantiation of a generic function.
+    pub occurrences: usize,
+}
+
+/// Groups the warnings of the same kind for the same span, such as those emitted once per
+/// monomorphized instantiation of a generic function, in the order the groups were first emitted.
+pub fn aggregate_warnings(warnings: &[CompileWarning]) -> Vec<AggregatedWarning> {
+    let mut aggregated: Vec<AggregatedWarning> = vec![];
+    let mut indexes = HashMap::new();
+    for warning in warnings {
+        // Replayed warnings are all of the same variant, but keep the codes of their kinds.
+        let kind = (
+            std::mem::discriminant(&warning.warning_content),
+            warning.code(),
+        );
+        match indexes.entry((kind, warning.span.clone())) {
+            Entry::Occupied(entry) => aggregated[*entry.get()].occurrences += 1,
+            Entry::Vacant(entry) => {
+                entry.insert(aggregated.len());
+                aggregated.push(AggregatedWarning {
+                    warning,
+                    occurrences: 1,
+                });
+            }
+        }
+    }
+    aggregated
+}
+
+/// A machine-applicable fix for a diagnostic, replacing the source code at `span` with
+/// `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -65,6 +138,14 @@ pub enum Warning {
     },
     OverridingTraitImplementation,
     DeadDeclaration,
+    UnusedVariable {
+        variable_name: Ident,
+    },
+    UnusedImport {
+        name: Ident,
+        /// The span of the whole `use` statement, if none of its imports are used.
+        use_span: Option<Span>,
+    },
     DeadEnumDeclaration,
     DeadFunctionDeclaration,
     DeadStructDeclaration,
@@ -81,6 +162,8 @@ pub enum Warning {
     DeadStorageDeclaration,
     DeadStorageDeclarationForFunction {
         unneeded_attrib: String,
+        /// The storage fields the function actually accesses, e.g. "only reads `count`", if known.
+        effects: Option<String>,
     },
     MatchExpressionUnreachableArm,
     UnrecognizedAttribute {
@@ -103,6 +186,148 @@ pub enum Warning {
         block_name: Ident,
     },
     ModulePrivacyDisabled,
+    OverlappingMutableAccess {
+        name: String,
+        param_name: Ident,
+    },
+    UseAfterMove {
+        name: String,
+        moved_into: String,
+    },
+    NumericFallback {
+        name: Ident,
+    },
+    /// A warning reported by an earlier compilation, replayed e.g. when the package is loaded
+    /// from the build cache rather than compiled again.
+    Replayed {
+        code: String,
+        lint_name: String,
+        message: String,
+        suggestions: Vec<Suggestion>,
+    },
+}
+
+impl Warning {
+    /// The stable code identifying this kind of warning, e.g. `W0001`, which `forc explain` takes
+    /// to describe the warning in detail.
+    ///
+    /// Codes are never reassigned, so that they can be searched for. See
+    /// [crate::explanations] for how the codes are allocated.
+    pub fn code(&self) -> &str {
+        use Warning::*;
+        match self {
+            NonClassCaseStructName { .. } => "W0001",
+            NonClassCaseTypeParameter { .. } => "W0002",
+            NonClassCaseTraitName { .. } => "W0003",
+            NonClassCaseEnumName { .. } => "W0004",
+            NonClassCaseEnumVariantName { .. } => "W0005",
+            NonSnakeCaseStructFieldName { .. } => "W0006",
+            NonSnakeCaseFunctionName { .. } => "W0007",
+            NonScreamingSnakeCaseConstName { .. } => "W0008",
+            UnusedReturnValue { .. } => "W0009",
+            SimilarMethodFound { .. } => "W0010",
+            ShadowsOtherSymbol { .. } => "W0011",
+            OverridingTraitImplementation => "W0012",
+            DeadDeclaration => "W0013",
+            UnusedVariable { .. } => "W0014",
+            UnusedImport { .. } => "W0015",
+            DeadEnumDeclaration => "W0016",
+            DeadFunctionDeclaration => "W0017",
+            DeadStructDeclaration => "W0018",
+            DeadTrait => "W0019",
+            UnreachableCode => "W0020",
+            DeadEnumVariant { .. } => "W0021",
+            DeadMethod => "W0022",
+            StructFieldNeverRead => "W0023",
+            ShadowingReservedRegister { .. } => "W0024",
+            DeadStorageDeclaration => "W0025",
+            DeadStorageDeclarationForFunction { .. } => "W0026",
+            MatchExpressionUnreachableArm => "W0027",
+            UnrecognizedAttribute { .. } => "W0028",
+            AttributeExpectedNumberOfArguments { .. } => "W0029",
+            UnexpectedAttributeArgumentValue { .. } => "W0030",
+            EffectAfterInteraction { .. } => "W0031",
+            ModulePrivacyDisabled => "W0032",
+            OverlappingMutableAccess { .. } => "W0033",
+            UseAfterMove { .. } => "W0034",
+            NumericFallback { .. } => "W0035",
+            Replayed { code, .. } => code,
+        }
+    }
+
+    /// The name of the lint this warning belongs to, e.g. `unused_imports`, by which its level
+    /// can be configured, e.g. with `forc build -D unused_imports`.
+    ///
+    /// The names of all lints are listed in [Warning::LINTS].
+    pub fn lint_name(&self) -> &str {
+        use Warning::*;
+        match self {
+            NonClassCaseStructName { .. }
+            | NonClassCaseTypeParameter { .. }
+            | NonClassCaseTraitName { .. }
+            | NonClassCaseEnumName { .. }
+            | NonClassCaseEnumVariantName { .. } => "non_class_case_names",
+            NonSnakeCaseStructFieldName { .. } | NonSnakeCaseFunctionName { .. } => {
+                "non_snake_case_names"
+            }
+            NonScreamingSnakeCaseConstName { .. } => "non_screaming_snake_case_names",
+            UnusedReturnValue { .. } => "unused_return_values",
+            SimilarMethodFound { .. } => "similar_methods",
+            ShadowsOtherSymbol { .. } => "shadowing",
+            OverridingTraitImplementation => "overriding_trait_implementations",
+            DeadDeclaration
+            | DeadEnumDeclaration
+            | DeadFunctionDeclaration
+            | DeadStructDeclaration
+            | DeadTrait
+            | DeadEnumVariant { .. }
+            | DeadMethod
+            | StructFieldNeverRead
+            | DeadStorageDeclaration => "dead_code",
+            UnusedVariable { .. } => "unused_variables",
+            UnusedImport { .. } => "unused_imports",
+            UnreachableCode | MatchExpressionUnreachableArm => "unreachable_code",
+            ShadowingReservedRegister { .. } => "reserved_registers",
+            DeadStorageDeclarationForFunction { .. } => "unneeded_storage_attributes",
+            UnrecognizedAttribute { .. }
+            | AttributeExpectedNumberOfArguments { .. }
+            | UnexpectedAttributeArgumentValue { .. } => "invalid_attributes",
+            EffectAfterInteraction { .. } => "cei_pattern_violations",
+            ModulePrivacyDisabled => "module_privacy",
+            OverlappingMutableAccess { .. } => "overlapping_borrows",
+            UseAfterMove { .. } => "use_after_move",
+            NumericFallback { .. } => "numeric_fallback",
+            Replayed { lint_name, .. } => lint_name,
+        }
+    }
+
+    /// The names of all lints, see [Warning::lint_name].
+    pub const LINTS: &'static [&'static str] = &[
+        "non_class_case_names",
+        "non_snake_case_names",
+        "non_screaming_snake_case_names",
+        "unused_return_values",
+        "similar_methods",
+        "shadowing",
+        "overriding_trait_implementations",
+        "dead_code",
+        "unused_variables",
+        "unused_imports",
+        "unreachable_code",
+        "reserved_registers",
+        "unneeded_storage_attributes",
+        "invalid_attributes",
+        "cei_pattern_violations",
+        "module_privacy",
+        "overlapping_borrows",
+        "use_after_move",
+        "numeric_fallback",
+    ];
+
+    /// The lints whose warnings are only reported if their level is set, e.g. with
+    /// `forc build -W numeric_fallback`, because they point at code which is fine most of the
+    /// time.
+    pub const ALLOWED_BY_DEFAULT: &'static [&'static str] = &["numeric_fallback"];
 }
 
 impl fmt::Display for Warning {
@@ -192,6 +417,8 @@ impl fmt::Display for Warning {
                 "This trait implementation overrides another one that was previously defined."
             ),
             DeadDeclaration => write!(f, "This declaration is never used."),
+            UnusedVariable { .. } => write!(f, "This declaration is never used."),
+            UnusedImport { name, .. } => write!(f, "The import \"{name}\" is never used."),
             DeadEnumDeclaration => write!(f, "This enum is never used."),
             DeadStructDeclaration => write!(f, "This struct is never used."),
             DeadFunctionDeclaration => write!(f, "This function is never called."),
@@ -210,11 +437,17 @@ impl fmt::Display for Warning {
                 f,
                 "This storage declaration is never accessed and can be removed."
             ),
-            DeadStorageDeclarationForFunction { unneeded_attrib } => write!(
-                f,
-                "This function's storage attributes declaration does not match its \
-                 actual storage access pattern: '{unneeded_attrib}' attribute(s) can be removed."
-            ),
+            DeadStorageDeclarationForFunction { unneeded_attrib, effects } => {
+                write!(
+                    f,
+                    "This function's storage attributes declaration does not match its \
+                     actual storage access pattern: '{unneeded_attrib}' attribute(s) can be removed."
+                )?;
+                match effects {
+                    Some(effects) => write!(f, " This function {effects}."),
+                    None => Ok(()),
+                }
+            }
             MatchExpressionUnreachableArm => write!(f, "This match arm is unreachable."),
             UnrecognizedAttribute {attrib_name} => write!(f, "Unknown attribute: \"{attrib_name}\"."),
             AttributeExpectedNumberOfArguments {attrib_name, received_args, expected_min_len, expected_max_len } => write!(
@@ -241,6 +474,23 @@ impl fmt::Display for Warning {
             ModulePrivacyDisabled => write!(f, "Module privacy rules will soon change to make modules private by default.
                                             You can enable the new behavior with the --experimental-private-modules flag, which will become the default behavior in a later release.
                                             More details are available in the related RFC: https://github.com/FuelLabs/sway-rfcs/blob/master/rfcs/0008-private-modules.md"),
+            OverlappingMutableAccess { name, param_name } => write!(
+                f,
+                "\"{name}\" is borrowed mutably by the parameter \"{param_name}\" of this call, \
+                 so it must not be passed to the same call again."
+            ),
+            UseAfterMove { name, moved_into } => write!(
+                f,
+                "\"{name}\" is used after being copied into \"{moved_into}\". Both share the same \
+                 heap buffer, so changing either of them can corrupt the other. Consider using \
+                 \"{moved_into}\" instead."
+            ),
+            NumericFallback { name } => write!(
+                f,
+                "The integer width of \"{name}\" can't be inferred from its uses, so it defaults \
+                 to \"u64\". Consider annotating the type of \"{name}\"."
+            ),
+            Replayed { message, .. } => write!(f, "{message}"),
         }
     }
 }
@@ -372,4 +622,33 @@ mod test {
         assert_eq!("__MixOfThings", to_upper_camel_case("__Mix_Of_Things"));
         assert_eq!("FooBar123", to_upper_camel_case("FooBar_123"));
     }
+
+    #[test]
+    fn aggregate_warnings_of_the_same_kind_and_span() {
+        use super::*;
+        use std::sync::Arc;
+
+        let src: Arc<str> = "fn foo() {}".into();
+        let span = |start, end| Span::new(src.clone(), start, end, None).unwrap();
+        let unused_return_value = |ty: &str| CompileWarning {
+            span: span(0, 2),
+            warning_content: Warning::UnusedReturnValue {
+                r#type: ty.to_string(),
+            },
+        };
+        let warnings = [
+            unused_return_value("u64"),
+            CompileWarning {
+                span: span(3, 6),
+                warning_content: Warning::DeadFunctionDeclaration,
+            },
+            unused_return_value("bool"),
+        ];
+
+        let aggregated = aggregate_warnings(&warnings)
+            .into_iter()
+            .map(|aggregated| (aggregated.warning, aggregated.occurrences))
+            .collect::<Vec<_>>();
+        assert_eq!(aggregated, [(&warnings[0], 2), (&warnings[1], 1)]);
+    }
 }
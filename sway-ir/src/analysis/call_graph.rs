@@ -51,3 +51,25 @@ pub fn callee_first_order(cg: &CallGraph) -> Vec<Function> {
 
     res
 }
+
+/// Given a call graph, return the functions which may call themselves, either directly or
+/// through other functions.
+pub fn recursive_functions(cg: &CallGraph) -> FxHashSet<Function> {
+    fn reaches(
+        cg: &CallGraph,
+        visited: &mut FxHashSet<Function>,
+        node: Function,
+        target: Function,
+    ) -> bool {
+        cg.get(&node).map_or(false, |callees| {
+            callees.iter().any(|callee| {
+                *callee == target
+                    || (visited.insert(*callee) && reaches(cg, visited, *callee, target))
+            })
+        })
+    }
+    cg.keys()
+        .filter(|node| reaches(cg, &mut FxHashSet::default(), **node, **node))
+        .copied()
+        .collect()
+}
@@ -114,6 +114,12 @@ fn compute_dom_tree(
     function: Function,
 ) -> Result<AnalysisResult, IrError> {
     let po: &PostOrder = analyses.get_analysis_result(function);
+    Ok(Box::new(compute_dominators(context, po, function)))
+}
+
+/// Compute the dominator tree of the blocks of `function` in the post order `po`, i.e. those
+/// reachable from its entry block.
+pub fn compute_dominators(context: &Context, po: &PostOrder, function: Function) -> DomTree {
     let mut dom_tree = DomTree::default();
     let entry = function.get_entry_block(context);
 
@@ -193,7 +199,7 @@ fn compute_dom_tree(
         dom_tree.get_mut(&parent).unwrap().children.push(child);
     }
 
-    Ok(Box::new(dom_tree))
+    dom_tree
 }
 
 pub const DOMFRONTS_NAME: &str = "dominance_frontiers";
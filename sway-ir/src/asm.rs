@@ -13,6 +13,9 @@
 //!     r3: u64
 //! }
 //! ```
+//!
+//! Instructions may be preceded by a label, e.g. `loop_start: addi r1 r1 i1;`, which the `ji`,
+//! `jnei` and `jnzi` instructions of the same block may use as their immediate.
 
 use sway_types::ident::Ident;
 
@@ -42,6 +45,8 @@ pub struct AsmArg {
 
 #[derive(Clone, Debug)]
 pub struct AsmInstruction {
+    /// A label declared in front of the instruction, which jumps within the block may target.
+    pub label: Option<Ident>,
     pub name: Ident,
     pub args: Vec<Ident>,
     pub immediate: Option<Ident>,
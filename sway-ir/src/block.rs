@@ -163,6 +163,39 @@ impl Block {
         }
     }
 
+    /// Remove the i'th block arg, along with the parameters passed to it by the predecessors.
+    ///
+    /// The arg must no longer be used.
+    pub fn remove_arg(&self, context: &mut Context, index: usize) {
+        let preds = self.pred_iter(context).copied().collect::<Vec<_>>();
+        for pred in preds {
+            match pred.get_terminator_mut(context) {
+                Some(Instruction::Branch(to_block)) if to_block.block == *self => {
+                    to_block.args.remove(index);
+                }
+                Some(Instruction::ConditionalBranch {
+                    true_block,
+                    false_block,
+                    ..
+                }) => {
+                    for to_block in [true_block, false_block] {
+                        if to_block.block == *self {
+                            to_block.args.remove(index);
+                        }
+                    }
+                }
+                _otherwise => (),
+            }
+        }
+
+        context.blocks[self.0].args.remove(index);
+        for arg in context.blocks[self.0].args[index..].to_vec() {
+            if let ValueDatum::Argument(block_arg) = &mut context.values[arg.0].value {
+                block_arg.idx -= 1;
+            }
+        }
+    }
+
     /// Get an iterator over this block's args.
     pub fn arg_iter<'a>(&'a self, context: &'a Context) -> impl Iterator<Item = &Value> {
         context.blocks[self.0].args.iter()
@@ -13,7 +13,7 @@ use sway_types::SourceEngine;
 use crate::{
     asm::AsmBlockContent, block::BlockContent, function::FunctionContent,
     local_var::LocalVarContent, metadata::Metadatum, module::Kind, module::ModuleContent,
-    module::ModuleIterator, value::ValueContent, Type, TypeContent,
+    module::ModuleIterator, value::ValueContent, InlineParams, Type, TypeContent,
 };
 
 /// The main IR context handle.
@@ -35,6 +35,12 @@ pub struct Context<'eng> {
 
     pub program_kind: Kind,
 
+    /// The heuristics of the inliner, for the functions of every module.
+    pub inline_params: InlineParams,
+
+    /// Whether common subexpression elimination also deduplicates storage reads.
+    pub cse_storage_reads: bool,
+
     next_unique_sym_tag: u64,
 }
 
@@ -53,6 +59,8 @@ impl<'eng> Context<'eng> {
             metadata: Default::default(),
             next_unique_sym_tag: Default::default(),
             program_kind: Kind::Contract,
+            inline_params: InlineParams::default(),
+            cse_storage_reads: false,
         };
         Type::create_basic_types(&mut def);
         def
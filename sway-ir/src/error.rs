@@ -6,6 +6,7 @@
 #[derive(Debug)]
 pub enum IrError {
     FunctionLocalClobbered(String, String),
+    InvalidAfterPass(String, Box<IrError>),
     InvalidMetadatum(String),
     InvalidPhi,
     MisplacedTerminator(String),
@@ -65,6 +66,7 @@ pub enum IrError {
     VerifyStoreMismatchedTypes,
     VerifyStoreToNonPointer(String),
     VerifyUntypedValuePassedToFunction,
+    VerifyUseNotDominatedByDefinition(String, String),
 }
 
 impl std::error::Error for IrError {}
@@ -78,6 +80,9 @@ impl fmt::Display for IrError {
                 f,
                 "Local storage for function {fn_str} already has an entry for variable {var_str}."
             ),
+            IrError::InvalidAfterPass(pass_name, err) => {
+                write!(f, "The IR is invalid after the '{pass_name}' pass. {err}")
+            }
             IrError::InvalidMetadatum(why_str) => {
                 write!(f, "Unable to convert from invalid metadatum: {why_str}.")
             }
@@ -318,6 +323,11 @@ impl fmt::Display for IrError {
                 f,
                 "Verification failed: An untyped/void value has been passed to a function call."
             ),
+            IrError::VerifyUseNotDominatedByDefinition(fn_str, blk_str) => write!(
+                f,
+                "Verification failed: Block {blk_str} in function {fn_str} uses a value which \
+                isn't defined before it."
+            ),
             IrError::VerifyInvalidGtfIndexType => write!(
                 f,
                 "Verification failed: An non-integer value has been passed to a 'gtf' instruction."
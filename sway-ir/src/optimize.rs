@@ -13,14 +13,22 @@
 //! over blocks or instructions can be invalidated, and starting over is a safer option than trying
 //! to attempt multiple changes at once.
 
+pub mod adce;
+pub use adce::*;
 pub mod arg_demotion;
 pub use arg_demotion::*;
 pub mod const_demotion;
 pub use const_demotion::*;
 pub mod constants;
 pub use constants::*;
+pub mod cse;
+pub use cse::*;
 pub mod dce;
 pub use dce::*;
+pub mod fn_dedup;
+pub use fn_dedup::*;
+pub mod heap_to_stack;
+pub use heap_to_stack::*;
 pub mod inline;
 pub use inline::*;
 pub mod mem2reg;
@@ -33,6 +41,8 @@ pub mod ret_demotion;
 pub use ret_demotion::*;
 pub mod simplify_cfg;
 pub use simplify_cfg::*;
+pub mod sroa;
+pub use sroa::*;
 
 mod target_fuel;
 
@@ -0,0 +1,148 @@
+//! ## Aggressive Dead Code Elimination
+//!
+//! Where [dce](super::dce) removes the definitions which have no uses, this pass assumes that
+//! everything is dead until proven otherwise, which also removes definitions which are only used
+//! by each other, e.g. a block argument of a loop which is only passed back to the loop header.
+//!
+//!   1. Blocks which are unreachable from the entry block are removed.
+//!   2. The instructions with side effects and the terminators are live, and so is every value
+//!      they use, transitively.  A block argument is live if it is used, and then so are the
+//!      values passed to it by the branches to its block.
+//!   3. Instructions and block arguments which aren't live are removed, along with the values
+//!      passed to the removed block arguments.
+//!
+//! Stores are always considered live, it is up to [dce](super::dce) to remove those into memory
+//! which is never read.
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    AnalysisResults, Block, BlockArgument, BranchToWithArgs, Context, Function, Instruction,
+    IrError, Pass, PassMutability, ScopedPass, Value, ValueDatum,
+};
+
+pub const ADCE_NAME: &str = "adce";
+
+pub fn create_adce_pass() -> Pass {
+    Pass {
+        name: ADCE_NAME,
+        descr: "Aggressive dead code elimination.",
+        deps: vec![],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(adce)),
+    }
+}
+
+/// Perform aggressive dead code elimination and return true if the function was modified.
+pub fn adce(
+    context: &mut Context,
+    _: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let mut modified = remove_unreachable_blocks(context, function)?;
+
+    // Start with the instructions which must be kept, and the values they use.
+    let mut live = FxHashSet::<Value>::default();
+    let mut worklist = Vec::new();
+    for (_block, inst) in function.instruction_iter(context) {
+        let instruction = inst.get_instruction(context).unwrap();
+        if instruction.is_terminator() || instruction.may_have_side_effect() {
+            live.insert(inst);
+            worklist.extend(used_values(instruction));
+        }
+    }
+
+    // Propagate liveness to the definitions of the live values.
+    while let Some(val) = worklist.pop() {
+        if !live.insert(val) {
+            continue;
+        }
+        match &context.values[val.0].value {
+            ValueDatum::Instruction(instruction) => worklist.extend(used_values(instruction)),
+            ValueDatum::Argument(BlockArgument { block, idx, .. }) => {
+                for pred in block.pred_iter(context) {
+                    worklist.extend(
+                        pred.successors(context)
+                            .into_iter()
+                            .filter(|branch| branch.block == *block)
+                            .map(|branch| branch.args[*idx]),
+                    );
+                }
+            }
+            ValueDatum::Configurable(_) | ValueDatum::Constant(_) => (),
+        }
+    }
+
+    // Remove what isn't live.  The entry block's arguments are the function's.
+    let entry_block = function.get_entry_block(context);
+    for block in function.block_iter(context) {
+        let num_instructions = block.num_instructions(context);
+        block.remove_instructions(context, |inst| !live.contains(&inst));
+        modified |= block.num_instructions(context) != num_instructions;
+
+        if block != entry_block {
+            let dead_args = block
+                .arg_iter(context)
+                .enumerate()
+                .filter_map(|(idx, arg)| (!live.contains(arg)).then_some(idx))
+                .collect::<Vec<_>>();
+            for idx in dead_args.into_iter().rev() {
+                block.remove_arg(context, idx);
+                modified = true;
+            }
+        }
+    }
+
+    // Remove the locals which are no longer used.
+    let used_locals = function
+        .instruction_iter(context)
+        .filter_map(|(_block, inst)| match inst.get_instruction(context) {
+            Some(Instruction::GetLocal(local)) => Some(*local),
+            _ => None,
+        })
+        .collect::<FxHashSet<_>>();
+    let local_removals = function
+        .locals_iter(context)
+        .filter_map(|(name, local)| (!used_locals.contains(local)).then_some(name.clone()))
+        .collect::<Vec<_>>();
+    if !local_removals.is_empty() {
+        modified = true;
+        function.remove_locals(context, &local_removals);
+    }
+
+    Ok(modified)
+}
+
+/// The values an instruction uses itself.  The arguments passed by a branch are only used if the
+/// block arguments they are passed to are, so they aren't included.
+fn used_values(instruction: &Instruction) -> Vec<Value> {
+    match instruction {
+        Instruction::Branch(_) => vec![],
+        Instruction::ConditionalBranch { cond_value, .. } => vec![*cond_value],
+        _otherwise => instruction.get_operands(),
+    }
+}
+
+fn remove_unreachable_blocks(context: &mut Context, function: Function) -> Result<bool, IrError> {
+    let entry_block = function.get_entry_block(context);
+    let mut reachable = FxHashSet::<Block>::from_iter([entry_block]);
+    let mut worklist = vec![entry_block];
+    while let Some(block) = worklist.pop() {
+        for BranchToWithArgs { block: succ, .. } in block.successors(context) {
+            if reachable.insert(succ) {
+                worklist.push(succ);
+            }
+        }
+    }
+
+    let unreachable = function
+        .block_iter(context)
+        .filter(|block| !reachable.contains(block))
+        .collect::<Vec<_>>();
+    for block in &unreachable {
+        for BranchToWithArgs { block: succ, .. } in block.successors(context) {
+            succ.remove_pred(context, block);
+        }
+        function.remove_block(context, block)?;
+    }
+    Ok(!unreachable.is_empty())
+}
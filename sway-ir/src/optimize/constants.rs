@@ -3,6 +3,8 @@
 //! - combining - compile time evaluation of constant expressions.
 //!   - combine insert_values - reduce expressions which insert a constant value into a constant
 //!     struct.
+//! - propagation - replacing block arguments with the constant which every predecessor passes to
+//!   them, so that the expressions using them can be combined in turn.
 
 use crate::{
     constant::{Constant, ConstantValue},
@@ -10,7 +12,7 @@ use crate::{
     error::IrError,
     function::Function,
     instruction::Instruction,
-    value::ValueDatum,
+    value::{Value, ValueDatum},
     AnalysisResults, BranchToWithArgs, Pass, PassMutability, Predicate, ScopedPass,
 };
 
@@ -19,7 +21,7 @@ pub const CONSTCOMBINE_NAME: &str = "constcombine";
 pub fn create_const_combine_pass() -> Pass {
     Pass {
         name: CONSTCOMBINE_NAME,
-        descr: "constant folding.",
+        descr: "constant folding and propagation.",
         deps: vec![],
         runner: ScopedPass::FunctionPass(PassMutability::Transform(combine_constants)),
     }
@@ -53,6 +55,11 @@ pub fn combine_constants(
             continue;
         }
 
+        if propagate_block_args(context, &function) {
+            modified = true;
+            continue;
+        }
+
         // Other passes here... always continue to the top if pass returns true.
         break;
     }
@@ -196,7 +203,7 @@ fn combine_binary_op(context: &mut Context, function: &Function) -> bool {
                             _ => None,
                         },
                         crate::BinaryOpKind::Mod => match (&val1.value, &val2.value) {
-                            (ConstantValue::Uint(l), ConstantValue::Uint(r)) => Some(l % r),
+                            (ConstantValue::Uint(l), ConstantValue::Uint(r)) => l.checked_rem(*r),
                             _ => None,
                         },
                         crate::BinaryOpKind::Rsh => match (&val1.value, &val2.value) {
@@ -282,6 +289,42 @@ fn combine_unary_op(context: &mut Context, function: &Function) -> bool {
     })
 }
 
+fn propagate_block_args(context: &mut Context, function: &Function) -> bool {
+    // The entry block's args are the function's args.
+    let entry_block = function.get_entry_block(context);
+    let candidate = function
+        .block_iter(context)
+        .filter(|block| *block != entry_block)
+        .find_map(|block| {
+            let branches = block
+                .pred_iter(context)
+                .flat_map(|pred| pred.successors(context))
+                .filter(|branch| branch.block == block)
+                .collect::<Vec<_>>();
+            let (first_branch, other_branches) = branches.split_first()?;
+            (0..block.num_args(context)).find_map(|idx| {
+                let constant = first_branch.args[idx].get_constant(context)?;
+                other_branches
+                    .iter()
+                    .all(|branch| {
+                        branch.args[idx]
+                            .get_constant(context)
+                            .map_or(false, |other| other.eq(context, constant))
+                    })
+                    .then(|| (block, idx, constant.clone()))
+            })
+        });
+
+    // Replace the uses of this block arg with the constant, and remove it.
+    candidate.map_or(false, |(block, idx, constant)| {
+        let arg_val = block.get_arg(context, idx).unwrap();
+        let const_val = Value::new_constant(context, constant);
+        function.replace_value(context, arg_val, const_val, None);
+        block.remove_arg(context, idx);
+        true
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::optimize::tests::*;
@@ -347,6 +390,7 @@ mod tests {
         assert_operator("sub", "0", Some("1"), None);
         assert_operator("mul", &u64::MAX.to_string(), Some("2"), None);
         assert_operator("div", "1", Some("0"), None);
+        assert_operator("mod", "1", Some("0"), None);
 
         assert_operator("rsh", "1", Some("64"), None);
         assert_operator("lsh", "1", Some("64"), None);
@@ -0,0 +1,189 @@
+//! ## Common Subexpression Elimination
+//!
+//! An instruction which computes the same pure expression as one in a block which dominates it,
+//! e.g. the same `get_elem_ptr` into a struct or the same arithmetic, is replaced with the result
+//! of the dominating instruction.
+//!
+//! Storage reads aren't pure, as the slot may be written in between, so they are only
+//! deduplicated if [Context::cse_storage_reads] is set, and then only within a block and when
+//! nothing which may write to storage or memory is between them.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    AnalysisResults, BinaryOpKind, Block, Context, DomTree, FuelVmInstruction, Function,
+    Instruction, IrError, LocalVar, Pass, PassMutability, Predicate, ScopedPass, Type, UnaryOpKind,
+    Value, DOMINATORS_NAME,
+};
+
+pub const CSE_NAME: &str = "cse";
+
+pub fn create_cse_pass() -> Pass {
+    Pass {
+        name: CSE_NAME,
+        descr: "Common subexpression elimination.",
+        deps: vec![DOMINATORS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(cse)),
+    }
+}
+
+/// A pure expression, with its operands already replaced by those they are equal to.
+#[derive(PartialEq, Eq, Hash)]
+enum Expr {
+    UnaryOp(UnaryOpKind, Value),
+    BinaryOp(BinaryOpKind, Value, Value),
+    BitCast(Value, Type),
+    CastPtr(Value, Type),
+    Cmp(Predicate, Value, Value),
+    GetLocal(LocalVar),
+    GetElemPtr(Value, Type, Vec<Value>),
+    IntToPtr(Value, Type),
+    PtrToInt(Value, Type),
+    Gtf(Value, u64),
+}
+
+pub fn cse(
+    context: &mut Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let dom_tree: &DomTree = analyses.get_analysis_result(function);
+
+    // Equal constants are often separate values, so each one is replaced by the first which is
+    // equal to it, to be able to match the expressions using them.
+    let mut replacements = FxHashMap::<Value, Value>::default();
+    let mut constants = Vec::<Value>::new();
+    for (_block, inst) in function.instruction_iter(context) {
+        for operand in inst.get_instruction(context).unwrap().get_operands() {
+            let Some(constant) = operand.get_constant(context) else {
+                continue;
+            };
+            match constants
+                .iter()
+                .find(|other| other.get_constant(context).unwrap().eq(context, constant))
+            {
+                Some(&other) if other != operand => {
+                    replacements.insert(operand, other);
+                }
+                Some(_) => (),
+                None => constants.push(operand),
+            }
+        }
+    }
+    let num_constant_replacements = replacements.len();
+
+    let mut available = FxHashMap::<Expr, Value>::default();
+    let mut eliminated = Vec::new();
+    visit_block(
+        context,
+        dom_tree,
+        function.get_entry_block(context),
+        &mut available,
+        &mut replacements,
+        &mut eliminated,
+    );
+
+    if replacements.is_empty() {
+        return Ok(false);
+    }
+    function.replace_values(context, &replacements, None);
+    for (block, inst) in &eliminated {
+        block.remove_instruction(context, *inst);
+    }
+    Ok(!eliminated.is_empty() || num_constant_replacements > 0)
+}
+
+/// Eliminate the expressions in `block` which are `available` from its dominators, then do the
+/// same for the blocks it dominates.
+fn visit_block(
+    context: &Context,
+    dom_tree: &DomTree,
+    block: Block,
+    available: &mut FxHashMap<Expr, Value>,
+    replacements: &mut FxHashMap<Value, Value>,
+    eliminated: &mut Vec<(Block, Value)>,
+) {
+    let mut defined = FxHashSet::default();
+    // The storage reads since the last instruction which may have written to storage, by key.
+    let mut storage_reads = FxHashMap::<Value, Value>::default();
+
+    for inst in block.instruction_iter(context) {
+        let instruction = inst.get_instruction(context).unwrap();
+        let resolve = |val: &Value| *replacements.get(val).unwrap_or(val);
+
+        if let Instruction::FuelVm(FuelVmInstruction::StateLoadWord(key)) = instruction {
+            if context.cse_storage_reads {
+                let key = resolve(key);
+                match storage_reads.get(&key) {
+                    Some(&prev) => {
+                        replacements.insert(inst, prev);
+                        eliminated.push((block, inst));
+                    }
+                    None => {
+                        storage_reads.insert(key, inst);
+                    }
+                }
+            }
+            continue;
+        }
+        if instruction.may_have_side_effect() {
+            storage_reads.clear();
+        }
+
+        let Some(expr) = expr(instruction, resolve) else {
+            continue;
+        };
+        match available.get(&expr) {
+            Some(&prev) => {
+                replacements.insert(inst, prev);
+                eliminated.push((block, inst));
+            }
+            None => {
+                available.insert(expr, inst);
+                defined.insert(inst);
+            }
+        }
+    }
+
+    for child in &dom_tree[&block].children {
+        visit_block(
+            context,
+            dom_tree,
+            *child,
+            available,
+            replacements,
+            eliminated,
+        );
+    }
+
+    // The expressions of this block aren't available to those it doesn't dominate.
+    available.retain(|_, val| !defined.contains(val));
+}
+
+fn expr(instruction: &Instruction, resolve: impl Fn(&Value) -> Value) -> Option<Expr> {
+    Some(match instruction {
+        Instruction::UnaryOp { op, arg } => Expr::UnaryOp(*op, resolve(arg)),
+        Instruction::BinaryOp { op, arg1, arg2 } => {
+            Expr::BinaryOp(*op, resolve(arg1), resolve(arg2))
+        }
+        Instruction::BitCast(val, ty) => Expr::BitCast(resolve(val), *ty),
+        Instruction::CastPtr(val, ty) => Expr::CastPtr(resolve(val), *ty),
+        Instruction::Cmp(pred, lhs, rhs) => Expr::Cmp(*pred, resolve(lhs), resolve(rhs)),
+        Instruction::GetLocal(local_var) => Expr::GetLocal(*local_var),
+        Instruction::GetElemPtr {
+            base,
+            elem_ptr_ty,
+            indices,
+        } => Expr::GetElemPtr(
+            resolve(base),
+            *elem_ptr_ty,
+            indices.iter().map(resolve).collect(),
+        ),
+        Instruction::IntToPtr(val, ty) => Expr::IntToPtr(resolve(val), *ty),
+        Instruction::PtrToInt(val, ty) => Expr::PtrToInt(resolve(val), *ty),
+        Instruction::FuelVm(FuelVmInstruction::Gtf { index, tx_field_id }) => {
+            Expr::Gtf(resolve(index), *tx_field_id)
+        }
+        _otherwise => return None,
+    })
+}
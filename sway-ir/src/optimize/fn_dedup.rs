@@ -0,0 +1,825 @@
+//! ## Function Deduplication
+//!
+//! Functions which are identical apart from their names and metadata are merged: calls to each of
+//! them are redirected to the first, and the others are removed.  Monomorphization often produces
+//! such functions, as many generic instantiations compile to the same code, e.g. for types with
+//! the same layout.
+//!
+//! Merging functions can make their callers identical in turn, so this is repeated until there is
+//! nothing left to merge.  Entry functions are never merged, as they are referred to from outside
+//! the module.
+//!
+//! ## Outlining
+//!
+//! Sequences of instructions repeated within the module are moved into a function of their own,
+//! which is called in their place.  This is kept conservative: a sequence must be straight-line
+//! code which doesn't refer to the locals or registers of its function, only a single value it
+//! produces may be used after it, and it is only outlined if the calls are estimated to take less
+//! bytecode than the copies they replace.
+
+use std::hash::{Hash, Hasher};
+
+use rustc_hash::{FxHashMap, FxHasher};
+
+use crate::{
+    AnalysisResults, AsmBlock, Block, BranchToWithArgs, Constant, Context, FuelVmInstruction,
+    Function, Instruction, IrError, LocalVar, Module, Pass, PassMutability, ScopedPass, Type,
+    Value, ValueDatum,
+};
+
+pub const FN_DEDUP_NAME: &str = "fn_dedup";
+
+pub fn create_fn_dedup_pass() -> Pass {
+    Pass {
+        name: FN_DEDUP_NAME,
+        descr: "Deduplication of identical functions.",
+        deps: vec![],
+        runner: ScopedPass::ModulePass(PassMutability::Transform(fn_dedup)),
+    }
+}
+
+/// Merge the identical functions of `module`, and return true if any were merged.
+pub fn fn_dedup(
+    context: &mut Context,
+    _: &AnalysisResults,
+    module: Module,
+) -> Result<bool, IrError> {
+    let mut modified = false;
+    loop {
+        let duplicates = find_duplicates(context, module);
+        if duplicates.is_empty() {
+            break;
+        }
+        for function in module.function_iter(context) {
+            let calls = function
+                .instruction_iter(context)
+                .filter_map(|(_, inst)| match inst.get_instruction(context) {
+                    Some(Instruction::Call(callee, args)) => duplicates
+                        .get(callee)
+                        .map(|original| (inst, *original, args.clone())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            for (inst, original, args) in calls {
+                inst.replace(
+                    context,
+                    ValueDatum::Instruction(Instruction::Call(original, args)),
+                );
+            }
+        }
+        for duplicate in duplicates.keys() {
+            module.remove_function(context, duplicate);
+        }
+        modified = true;
+    }
+    Ok(modified)
+}
+
+/// The functions of `module` which are identical to an earlier one, mapped to it.
+fn find_duplicates(context: &Context, module: Module) -> FxHashMap<Function, Function> {
+    // Only the functions with the same signature and size need to be compared.
+    let mut originals = FxHashMap::<(Type, Vec<Type>, usize, usize), Vec<Function>>::default();
+    let mut duplicates = FxHashMap::default();
+    for function in module.function_iter(context) {
+        if function.is_entry(context) {
+            continue;
+        }
+        let signature = (
+            function.get_return_type(context),
+            function
+                .args_iter(context)
+                .map(|(_, arg)| arg.get_type(context).unwrap())
+                .collect(),
+            function.num_blocks(context),
+            function.num_instructions(context),
+        );
+        let candidates = originals.entry(signature).or_default();
+        match candidates
+            .iter()
+            .find(|original| functions_equal(context, **original, function))
+        {
+            Some(original) => {
+                duplicates.insert(function, *original);
+            }
+            None => candidates.push(function),
+        }
+    }
+    duplicates
+}
+
+/// The correspondence between the blocks, locals and values of two functions being compared.
+#[derive(Default)]
+struct Mapping {
+    blocks: FxHashMap<Block, Block>,
+    locals: FxHashMap<LocalVar, LocalVar>,
+    values: FxHashMap<Value, Value>,
+}
+
+/// Whether the functions `a` and `b` are identical apart from their names and metadata.
+fn functions_equal(context: &Context, a: Function, b: Function) -> bool {
+    if a.get_return_type(context) != b.get_return_type(context)
+        || a.num_args(context) != b.num_args(context)
+        || a.num_blocks(context) != b.num_blocks(context)
+        || a.num_instructions(context) != b.num_instructions(context)
+    {
+        return false;
+    }
+    let mut mapping = Mapping::default();
+
+    for ((_, arg_a), (_, arg_b)) in a.args_iter(context).zip(b.args_iter(context)) {
+        if arg_a.get_type(context) != arg_b.get_type(context) {
+            return false;
+        }
+        mapping.values.insert(*arg_a, *arg_b);
+    }
+
+    let locals_a = a.locals_iter(context).collect::<Vec<_>>();
+    let locals_b = b.locals_iter(context).collect::<Vec<_>>();
+    if locals_a.len() != locals_b.len() {
+        return false;
+    }
+    for ((_, local_a), (_, local_b)) in locals_a.into_iter().zip(locals_b) {
+        let initializers_equal = match (
+            local_a.get_initializer(context),
+            local_b.get_initializer(context),
+        ) {
+            (Some(init_a), Some(init_b)) => init_a.eq(context, init_b),
+            (None, None) => true,
+            _ => false,
+        };
+        if local_a.get_type(context) != local_b.get_type(context)
+            || local_a.is_mutable(context) != local_b.is_mutable(context)
+            || !initializers_equal
+        {
+            return false;
+        }
+        mapping.locals.insert(*local_a, *local_b);
+    }
+
+    // The blocks, their arguments and their instructions correspond by position.  They're all
+    // mapped before any instruction is compared, as a value may be used in a block before the one
+    // defining it.
+    for (block_a, block_b) in a.block_iter(context).zip(b.block_iter(context)) {
+        if block_a.num_args(context) != block_b.num_args(context)
+            || block_a.num_instructions(context) != block_b.num_instructions(context)
+        {
+            return false;
+        }
+        mapping.blocks.insert(block_a, block_b);
+        for (arg_a, arg_b) in block_a.arg_iter(context).zip(block_b.arg_iter(context)) {
+            if arg_a.get_type(context) != arg_b.get_type(context) {
+                return false;
+            }
+            mapping.values.insert(*arg_a, *arg_b);
+        }
+        for (inst_a, inst_b) in block_a
+            .instruction_iter(context)
+            .zip(block_b.instruction_iter(context))
+        {
+            mapping.values.insert(inst_a, inst_b);
+        }
+    }
+
+    a.instruction_iter(context)
+        .zip(b.instruction_iter(context))
+        .all(|((_, inst_a), (_, inst_b))| {
+            instructions_equal(
+                context,
+                &mapping,
+                Some((a, b)),
+                inst_a.get_instruction(context).unwrap(),
+                inst_b.get_instruction(context).unwrap(),
+            )
+        })
+}
+
+/// Whether `a` is used where `b` is.  Values from outside of the functions, e.g. configurables,
+/// must be the same value, whereas constants only need to be equal.
+fn values_equal(context: &Context, mapping: &Mapping, a: &Value, b: &Value) -> bool {
+    match mapping.values.get(a) {
+        Some(mapped) => mapped == b,
+        None => match (a.get_constant(context), b.get_constant(context)) {
+            (Some(const_a), Some(const_b)) => const_a.eq(context, const_b),
+            _ => a == b,
+        },
+    }
+}
+
+/// Whether the instructions `a` and `b` are identical.  `functions` are the functions being
+/// compared, if whole functions are.
+fn instructions_equal(
+    context: &Context,
+    mapping: &Mapping,
+    functions: Option<(Function, Function)>,
+    a: &Instruction,
+    b: &Instruction,
+) -> bool {
+    let values = |a: &Value, b: &Value| values_equal(context, mapping, a, b);
+    let all_values =
+        |a: &[Value], b: &[Value]| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values(a, b));
+    let branches = |a: &BranchToWithArgs, b: &BranchToWithArgs| {
+        mapping.blocks.get(&a.block) == Some(&b.block) && all_values(&a.args, &b.args)
+    };
+    match (a, b) {
+        (Instruction::AsmBlock(asm_a, args_a), Instruction::AsmBlock(asm_b, args_b)) => {
+            asm_blocks_equal(context, asm_a, asm_b)
+                && args_a.len() == args_b.len()
+                && args_a.iter().zip(args_b).all(|(arg_a, arg_b)| {
+                    arg_a.name == arg_b.name
+                        && match (&arg_a.initializer, &arg_b.initializer) {
+                            (Some(init_a), Some(init_b)) => values(init_a, init_b),
+                            (None, None) => true,
+                            _ => false,
+                        }
+                })
+        }
+        (
+            Instruction::UnaryOp {
+                op: op_a,
+                arg: arg_a,
+            },
+            Instruction::UnaryOp {
+                op: op_b,
+                arg: arg_b,
+            },
+        ) => op_a == op_b && values(arg_a, arg_b),
+        (
+            Instruction::BinaryOp {
+                op: op_a,
+                arg1: arg1_a,
+                arg2: arg2_a,
+            },
+            Instruction::BinaryOp {
+                op: op_b,
+                arg1: arg1_b,
+                arg2: arg2_b,
+            },
+        ) => op_a == op_b && values(arg1_a, arg1_b) && values(arg2_a, arg2_b),
+        (Instruction::BitCast(val_a, ty_a), Instruction::BitCast(val_b, ty_b))
+        | (Instruction::CastPtr(val_a, ty_a), Instruction::CastPtr(val_b, ty_b))
+        | (Instruction::IntToPtr(val_a, ty_a), Instruction::IntToPtr(val_b, ty_b))
+        | (Instruction::PtrToInt(val_a, ty_a), Instruction::PtrToInt(val_b, ty_b))
+        | (Instruction::Ret(val_a, ty_a), Instruction::Ret(val_b, ty_b)) => {
+            ty_a == ty_b && values(val_a, val_b)
+        }
+        (Instruction::Branch(to_a), Instruction::Branch(to_b)) => branches(to_a, to_b),
+        (Instruction::Call(callee_a, args_a), Instruction::Call(callee_b, args_b)) => {
+            // Recursive calls are equal if both functions call themselves.
+            (callee_a == callee_b || Some((*callee_a, *callee_b)) == functions)
+                && all_values(args_a, args_b)
+        }
+        (Instruction::Cmp(pred_a, lhs_a, rhs_a), Instruction::Cmp(pred_b, lhs_b, rhs_b)) => {
+            pred_a == pred_b && values(lhs_a, lhs_b) && values(rhs_a, rhs_b)
+        }
+        (
+            Instruction::ConditionalBranch {
+                cond_value: cond_a,
+                true_block: true_a,
+                false_block: false_a,
+            },
+            Instruction::ConditionalBranch {
+                cond_value: cond_b,
+                true_block: true_b,
+                false_block: false_b,
+            },
+        ) => values(cond_a, cond_b) && branches(true_a, true_b) && branches(false_a, false_b),
+        (
+            Instruction::ContractCall {
+                return_type: return_type_a,
+                name: name_a,
+                params: params_a,
+                coins: coins_a,
+                asset_id: asset_id_a,
+                gas: gas_a,
+            },
+            Instruction::ContractCall {
+                return_type: return_type_b,
+                name: name_b,
+                params: params_b,
+                coins: coins_b,
+                asset_id: asset_id_b,
+                gas: gas_b,
+            },
+        ) => {
+            return_type_a == return_type_b
+                && name_a == name_b
+                && values(params_a, params_b)
+                && values(coins_a, coins_b)
+                && values(asset_id_a, asset_id_b)
+                && values(gas_a, gas_b)
+        }
+        (Instruction::FuelVm(fuel_vm_a), Instruction::FuelVm(fuel_vm_b)) => {
+            fuel_vm_instructions_equal(fuel_vm_a, fuel_vm_b, &values)
+        }
+        (Instruction::GetLocal(local_a), Instruction::GetLocal(local_b)) => {
+            mapping.locals.get(local_a) == Some(local_b)
+        }
+        (
+            Instruction::GetElemPtr {
+                base: base_a,
+                elem_ptr_ty: ty_a,
+                indices: indices_a,
+            },
+            Instruction::GetElemPtr {
+                base: base_b,
+                elem_ptr_ty: ty_b,
+                indices: indices_b,
+            },
+        ) => ty_a == ty_b && values(base_a, base_b) && all_values(indices_a, indices_b),
+        (Instruction::Load(ptr_a), Instruction::Load(ptr_b)) => values(ptr_a, ptr_b),
+        (
+            Instruction::MemCopyBytes {
+                dst_val_ptr: dst_a,
+                src_val_ptr: src_a,
+                byte_len: len_a,
+            },
+            Instruction::MemCopyBytes {
+                dst_val_ptr: dst_b,
+                src_val_ptr: src_b,
+                byte_len: len_b,
+            },
+        ) => len_a == len_b && values(dst_a, dst_b) && values(src_a, src_b),
+        (
+            Instruction::MemCopyVal {
+                dst_val_ptr: dst_a,
+                src_val_ptr: src_a,
+            },
+            Instruction::MemCopyVal {
+                dst_val_ptr: dst_b,
+                src_val_ptr: src_b,
+            },
+        )
+        | (
+            Instruction::Store {
+                dst_val_ptr: dst_a,
+                stored_val: src_a,
+            },
+            Instruction::Store {
+                dst_val_ptr: dst_b,
+                stored_val: src_b,
+            },
+        ) => values(dst_a, dst_b) && values(src_a, src_b),
+        (Instruction::Nop, Instruction::Nop) => true,
+        _otherwise => false,
+    }
+}
+
+fn fuel_vm_instructions_equal(
+    a: &FuelVmInstruction,
+    b: &FuelVmInstruction,
+    values: impl Fn(&Value, &Value) -> bool,
+) -> bool {
+    match (a, b) {
+        (
+            FuelVmInstruction::Gtf {
+                index: index_a,
+                tx_field_id: field_a,
+            },
+            FuelVmInstruction::Gtf {
+                index: index_b,
+                tx_field_id: field_b,
+            },
+        ) => field_a == field_b && values(index_a, index_b),
+        (
+            FuelVmInstruction::Log {
+                log_val: val_a,
+                log_ty: ty_a,
+                log_id: id_a,
+            },
+            FuelVmInstruction::Log {
+                log_val: val_b,
+                log_ty: ty_b,
+                log_id: id_b,
+            },
+        ) => ty_a == ty_b && values(val_a, val_b) && values(id_a, id_b),
+        (FuelVmInstruction::ReadRegister(reg_a), FuelVmInstruction::ReadRegister(reg_b)) => {
+            std::mem::discriminant(reg_a) == std::mem::discriminant(reg_b)
+        }
+        (FuelVmInstruction::Revert(val_a), FuelVmInstruction::Revert(val_b))
+        | (FuelVmInstruction::StateLoadWord(val_a), FuelVmInstruction::StateLoadWord(val_b)) => {
+            values(val_a, val_b)
+        }
+        (
+            FuelVmInstruction::Smo {
+                recipient: recipient_a,
+                message: message_a,
+                message_size: size_a,
+                coins: coins_a,
+            },
+            FuelVmInstruction::Smo {
+                recipient: recipient_b,
+                message: message_b,
+                message_size: size_b,
+                coins: coins_b,
+            },
+        ) => {
+            values(recipient_a, recipient_b)
+                && values(message_a, message_b)
+                && values(size_a, size_b)
+                && values(coins_a, coins_b)
+        }
+        (
+            FuelVmInstruction::StateClear {
+                key: key_a,
+                number_of_slots: slots_a,
+            },
+            FuelVmInstruction::StateClear {
+                key: key_b,
+                number_of_slots: slots_b,
+            },
+        ) => values(key_a, key_b) && values(slots_a, slots_b),
+        (
+            FuelVmInstruction::StateLoadQuadWord {
+                load_val: val_a,
+                key: key_a,
+                number_of_slots: slots_a,
+            },
+            FuelVmInstruction::StateLoadQuadWord {
+                load_val: val_b,
+                key: key_b,
+                number_of_slots: slots_b,
+            },
+        )
+        | (
+            FuelVmInstruction::StateStoreQuadWord {
+                stored_val: val_a,
+                key: key_a,
+                number_of_slots: slots_a,
+            },
+            FuelVmInstruction::StateStoreQuadWord {
+                stored_val: val_b,
+                key: key_b,
+                number_of_slots: slots_b,
+            },
+        ) => values(val_a, val_b) && values(key_a, key_b) && values(slots_a, slots_b),
+        (
+            FuelVmInstruction::StateStoreWord {
+                stored_val: val_a,
+                key: key_a,
+            },
+            FuelVmInstruction::StateStoreWord {
+                stored_val: val_b,
+                key: key_b,
+            },
+        ) => values(val_a, val_b) && values(key_a, key_b),
+        _otherwise => false,
+    }
+}
+
+fn asm_blocks_equal(context: &Context, a: &AsmBlock, b: &AsmBlock) -> bool {
+    let a = &context.asm_blocks[a.0];
+    let b = &context.asm_blocks[b.0];
+    a.args_names == b.args_names
+        && a.return_type == b.return_type
+        && a.return_name == b.return_name
+        && a.body.len() == b.body.len()
+        && a.body.iter().zip(&b.body).all(|(op_a, op_b)| {
+            op_a.label == op_b.label
+                && op_a.name == op_b.name
+                && op_a.args == op_b.args
+                && op_a.immediate == op_b.immediate
+        })
+}
+
+pub const OUTLINE_NAME: &str = "outline";
+
+pub fn create_outline_pass() -> Pass {
+    Pass {
+        name: OUTLINE_NAME,
+        descr: "Outlining of repeated instruction sequences.",
+        deps: vec![],
+        runner: ScopedPass::ModulePass(PassMutability::Transform(outline)),
+    }
+}
+
+/// The shortest and longest sequences of instructions considered for outlining.
+const MIN_OUTLINED_LEN: usize = 4;
+const MAX_OUTLINED_LEN: usize = 32;
+
+/// The Fuel backend passes at most this many arguments, all in registers.
+const MAX_OUTLINED_ARGS: usize = 6;
+
+/// Roughly how many VM instructions a call takes apart from moving its arguments, at each call
+/// site and once within the called function.
+const CALL_SITE_COST: usize = 3;
+const CALLEE_COST: usize = 6;
+
+/// Outline the repeated instruction sequences of `module`, and return true if any were outlined.
+pub fn outline(
+    context: &mut Context,
+    _: &AnalysisResults,
+    module: Module,
+) -> Result<bool, IrError> {
+    let mut modified = false;
+    while let Some(candidate) = find_best_outline(context, module) {
+        outline_sequences(context, module, candidate);
+        modified = true;
+    }
+    Ok(modified)
+}
+
+/// A sequence of instructions found repeated which can be outlined.
+struct Candidate {
+    /// The occurrences of the sequence, in their blocks.  The first is moved into the new
+    /// function.
+    occurrences: Vec<(Block, Vec<Value>)>,
+    /// The position of the instruction whose value is used after the sequence, if any.
+    result: Option<usize>,
+}
+
+/// The repeated sequence of `module` whose outlining saves the most, if any saves anything.
+fn find_best_outline(context: &Context, module: Module) -> Option<Candidate> {
+    // The runs of consecutive instructions which may be outlined, and how often each value is
+    // used.
+    let mut runs = Vec::<(Block, Vec<Value>)>::new();
+    let mut uses = FxHashMap::<Value, usize>::default();
+    for function in module.function_iter(context) {
+        for block in function.block_iter(context) {
+            let mut run = Vec::new();
+            for inst in block.instruction_iter(context) {
+                let instruction = inst.get_instruction(context).unwrap();
+                for operand in instruction.get_operands() {
+                    *uses.entry(operand).or_default() += 1;
+                }
+                if is_outlinable(instruction) {
+                    run.push(inst);
+                } else if run.len() < MIN_OUTLINED_LEN {
+                    run.clear();
+                } else {
+                    runs.push((block, std::mem::take(&mut run)));
+                }
+            }
+            if run.len() >= MIN_OUTLINED_LEN {
+                runs.push((block, run));
+            }
+        }
+    }
+
+    // Only the sequences of the same length and kinds of instructions need to be compared.
+    let mut buckets = FxHashMap::<(u64, usize), Vec<(usize, usize)>>::default();
+    for (run_idx, (_, run)) in runs.iter().enumerate() {
+        for start in 0..run.len() {
+            let mut hasher = FxHasher::default();
+            for (offset, inst) in run[start..].iter().take(MAX_OUTLINED_LEN).enumerate() {
+                std::mem::discriminant(inst.get_instruction(context).unwrap()).hash(&mut hasher);
+                let len = offset + 1;
+                if len >= MIN_OUTLINED_LEN {
+                    buckets
+                        .entry((hasher.finish(), len))
+                        .or_default()
+                        .push((run_idx, start));
+                }
+            }
+        }
+    }
+
+    let sequence = |(run_idx, start): (usize, usize), len: usize| -> &[Value] {
+        &runs[run_idx].1[start..start + len]
+    };
+    let mut best: Option<(usize, Candidate)> = None;
+    for ((_, len), occurrences) in buckets {
+        if occurrences.len() < 2 {
+            continue;
+        }
+        let mut classes = Vec::<Vec<(usize, usize)>>::new();
+        for occurrence in occurrences {
+            match classes.iter_mut().find(|class| {
+                sequences_equal(context, sequence(class[0], len), sequence(occurrence, len))
+            }) {
+                Some(class) => class.push(occurrence),
+                None => classes.push(vec![occurrence]),
+            }
+        }
+        for class in classes {
+            // Of overlapping occurrences only the first can be replaced.
+            let mut kept = Vec::<(usize, usize)>::new();
+            for (run_idx, start) in class {
+                match kept.last() {
+                    Some((last_run_idx, last_start))
+                        if *last_run_idx == run_idx && start < last_start + len => {}
+                    _ => kept.push((run_idx, start)),
+                }
+            }
+            if kept.len() < 2 {
+                continue;
+            }
+
+            let first = sequence(kept[0], len);
+            let results = used_results(context, &uses, kept.iter().map(|k| sequence(*k, len)));
+            let result = match results[..] {
+                [] => None,
+                [position] if is_passed_in_register(context, first[position]) => Some(position),
+                _ => continue,
+            };
+            let inputs = sequence_inputs(context, first);
+            if inputs.len() > MAX_OUTLINED_ARGS
+                || !inputs
+                    .iter()
+                    .all(|input| is_passed_in_register(context, *input))
+            {
+                continue;
+            }
+
+            let count = kept.len();
+            let saved = (count - 1) * len;
+            let cost = count * (inputs.len() + CALL_SITE_COST) + inputs.len() + CALLEE_COST;
+            if saved > cost && best.as_ref().map_or(true, |(most, _)| saved - cost > *most) {
+                let occurrences = kept
+                    .iter()
+                    .map(|k| (runs[k.0].0, sequence(*k, len).to_vec()))
+                    .collect();
+                best = Some((
+                    saved - cost,
+                    Candidate {
+                        occurrences,
+                        result,
+                    },
+                ));
+            }
+        }
+    }
+    best.map(|(_, candidate)| candidate)
+}
+
+/// Whether `instruction` may be moved into another function.  Those referring to the locals or
+/// registers of their function, and those changing the control flow, may not.
+fn is_outlinable(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::UnaryOp { .. }
+        | Instruction::BinaryOp { .. }
+        | Instruction::BitCast(..)
+        | Instruction::Call(..)
+        | Instruction::CastPtr(..)
+        | Instruction::Cmp(..)
+        | Instruction::GetElemPtr { .. }
+        | Instruction::IntToPtr(..)
+        | Instruction::Load(_)
+        | Instruction::MemCopyBytes { .. }
+        | Instruction::MemCopyVal { .. }
+        | Instruction::PtrToInt(..)
+        | Instruction::Store { .. } => true,
+        Instruction::FuelVm(fuel_vm) => matches!(
+            fuel_vm,
+            FuelVmInstruction::Gtf { .. }
+                | FuelVmInstruction::Log { .. }
+                | FuelVmInstruction::StateClear { .. }
+                | FuelVmInstruction::StateLoadQuadWord { .. }
+                | FuelVmInstruction::StateLoadWord(_)
+                | FuelVmInstruction::StateStoreQuadWord { .. }
+                | FuelVmInstruction::StateStoreWord { .. }
+        ),
+        _otherwise => false,
+    }
+}
+
+/// Whether `value` can be passed to or returned from a function in a register.
+fn is_passed_in_register(context: &Context, value: Value) -> bool {
+    value.get_type(context).map_or(false, |ty| {
+        ty.is_unit(context) || ty.is_bool(context) || ty.is_uint(context) || ty.is_ptr(context)
+    })
+}
+
+/// The values from outside of `sequence` which it uses, in the order they're first used.
+/// Constants and configurables are left where they are.
+fn sequence_inputs(context: &Context, sequence: &[Value]) -> Vec<Value> {
+    let mut inputs = Vec::new();
+    for inst in sequence {
+        for operand in inst.get_instruction(context).unwrap().get_operands() {
+            if !sequence.contains(&operand)
+                && !operand.is_constant(context)
+                && !operand.is_configurable(context)
+                && !inputs.contains(&operand)
+            {
+                inputs.push(operand);
+            }
+        }
+    }
+    inputs
+}
+
+/// The positions within `sequences` of the instructions whose values are used after them.
+fn used_results<'a>(
+    context: &Context,
+    uses: &FxHashMap<Value, usize>,
+    sequences: impl Iterator<Item = &'a [Value]>,
+) -> Vec<usize> {
+    let mut positions = Vec::new();
+    for sequence in sequences {
+        let mut uses_within = FxHashMap::<Value, usize>::default();
+        for inst in sequence {
+            for operand in inst.get_instruction(context).unwrap().get_operands() {
+                *uses_within.entry(operand).or_default() += 1;
+            }
+        }
+        for (position, inst) in sequence.iter().enumerate() {
+            if uses.get(inst) != uses_within.get(inst) && !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+    }
+    positions
+}
+
+/// Whether the sequences `a` and `b` are identical apart from the values they use from outside.
+fn sequences_equal(context: &Context, a: &[Value], b: &[Value]) -> bool {
+    let inputs_a = sequence_inputs(context, a);
+    let inputs_b = sequence_inputs(context, b);
+    if inputs_a.len() != inputs_b.len()
+        || inputs_a
+            .iter()
+            .zip(&inputs_b)
+            .any(|(input_a, input_b)| input_a.get_type(context) != input_b.get_type(context))
+    {
+        return false;
+    }
+    let mut mapping = Mapping::default();
+    mapping.values.extend(inputs_a.into_iter().zip(inputs_b));
+    mapping
+        .values
+        .extend(a.iter().copied().zip(b.iter().copied()));
+    a.iter().zip(b).all(|(inst_a, inst_b)| {
+        instructions_equal(
+            context,
+            &mapping,
+            None,
+            inst_a.get_instruction(context).unwrap(),
+            inst_b.get_instruction(context).unwrap(),
+        )
+    })
+}
+
+/// Move the first occurrence of the sequence of `candidate` into a new function, and replace each
+/// occurrence with a call to it.
+fn outline_sequences(context: &mut Context, module: Module, candidate: Candidate) {
+    let Candidate {
+        occurrences,
+        result,
+    } = candidate;
+    let first = occurrences[0].1.clone();
+    let inputs = sequence_inputs(context, &first);
+    let args = inputs
+        .iter()
+        .enumerate()
+        .map(|(idx, input)| (format!("arg{idx}"), input.get_type(context).unwrap(), None))
+        .collect();
+    let return_type = match result {
+        Some(position) => first[position].get_type(context).unwrap(),
+        None => Type::get_unit(context),
+    };
+    let mut idx = 0;
+    let name = loop {
+        let name = format!("outlined_{idx}");
+        if module
+            .function_iter(context)
+            .all(|function| function.get_name(context) != name)
+        {
+            break name;
+        }
+        idx += 1;
+    };
+    let outlined = Function::new(
+        context,
+        module,
+        name,
+        args,
+        return_type,
+        None,
+        false,
+        false,
+        None,
+    );
+
+    for (block, sequence) in &occurrences {
+        let call_args = sequence_inputs(context, sequence);
+        let md_idx = sequence[0].get_metadata(context);
+        let call = Value::new_instruction(context, Instruction::Call(outlined, call_args))
+            .add_metadatum(context, md_idx);
+        let instructions = &mut context.blocks[block.0].instructions;
+        let start = instructions
+            .iter()
+            .position(|inst| *inst == sequence[0])
+            .unwrap();
+        instructions.splice(start..start + sequence.len(), [call]);
+        if let Some(position) = result {
+            block
+                .get_function(context)
+                .replace_value(context, sequence[position], call, None);
+        }
+    }
+
+    // The instructions of the first occurrence become the body of the new function.
+    let entry_block = outlined.get_entry_block(context);
+    let arg_map = inputs
+        .into_iter()
+        .zip(outlined.args_iter(context).map(|(_, arg)| *arg))
+        .collect::<FxHashMap<_, _>>();
+    for inst in &first {
+        inst.replace_instruction_values(context, &arg_map);
+    }
+    context.blocks[entry_block.0].instructions.extend(&first);
+    let ret_val = match result {
+        Some(position) => first[position],
+        None => Constant::get_unit(context),
+    };
+    entry_block.ins(context).ret(ret_val, return_type);
+}
@@ -0,0 +1,338 @@
+//! Turn small heap allocations which do not escape their function into stack allocations.
+//!
+//! Heap allocations are ASM blocks of the form `aloc size; move ptr hp;` returning `ptr`, which
+//! is what `std::alloc::alloc()` and friends reduce to once inlined.  Allocating on the heap costs
+//! gas and the memory is never freed, whereas a local variable is part of the function's stack
+//! frame.  So an allocation of a small constant size, which isn't repeated within a loop and
+//! whose address is only ever used to access the allocated memory within the function, is
+//! replaced with a zero-initialised local of the same size.
+//!
+//! The address escapes if it is passed to a call, returned, logged, compared or stored anywhere
+//! other than in a local variable which doesn't escape itself, or if any value derived from it
+//! does so.
+
+use rustc_hash::FxHashSet;
+use sway_types::Ident;
+
+use crate::{
+    asm::AsmBlock, get_symbols, AnalysisResults, BinaryOpKind, Block, BranchToWithArgs, Constant,
+    ConstantValue, Context, EscapedSymbols, Function, Instruction, IrError, Pass, PassMutability,
+    ScopedPass, Symbol, Type, Value, ESCAPED_SYMBOLS_NAME,
+};
+
+pub const HEAP2STACK_NAME: &str = "heap2stack";
+
+/// The largest heap allocation, in bytes, which may be moved to the stack.
+const MAX_STACK_ALLOCATION_SIZE: u64 = 256;
+
+pub fn create_heap2stack_pass() -> Pass {
+    Pass {
+        name: HEAP2STACK_NAME,
+        descr: "Promotion of small non-escaping heap allocations to the stack.",
+        deps: vec![ESCAPED_SYMBOLS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(heap_to_stack)),
+    }
+}
+
+pub fn heap_to_stack(
+    context: &mut Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let escaped_symbols: &EscapedSymbols = analyses.get_analysis_result(function);
+
+    let candidates = function
+        .instruction_iter(context)
+        .filter_map(|(block, alloc_val)| {
+            let size = allocation_size(context, alloc_val)?;
+            (size > 0
+                && size <= MAX_STACK_ALLOCATION_SIZE
+                && !is_in_loop(context, block)
+                && !escapes(context, function, escaped_symbols, alloc_val))
+            .then_some((block, alloc_val, size))
+        })
+        .collect::<Vec<_>>();
+
+    if candidates.is_empty() {
+        return Ok(false);
+    }
+
+    for (block, alloc_val, size) in candidates {
+        // Heap allocations are zeroed, so the local is too.  Its size is rounded up to whole
+        // words, which is how locals are laid out on the stack anyway.
+        let word_ty = Type::get_uint64(context);
+        let num_words = (size + 7) / 8;
+        let zero = Constant::new_uint(context, 64, 0);
+        let initializer = Constant::new_array(context, word_ty, vec![zero; num_words as usize]);
+        let local_ty = Type::new_array(context, word_ty, num_words);
+        let local_var = function.new_unique_local_var(
+            context,
+            "__stack_alloc".to_owned(),
+            local_ty,
+            Some(initializer),
+            true,
+        );
+
+        let md_idx = alloc_val.get_metadata(context);
+        let get_local_val = Value::new_instruction(context, Instruction::GetLocal(local_var))
+            .add_metadatum(context, md_idx);
+        let ptr_val =
+            Value::new_instruction(context, Instruction::PtrToInt(get_local_val, word_ty))
+                .add_metadatum(context, md_idx);
+
+        // Replace the ASM block with the address of the local, and put the `get_local` before it.
+        let block_instrs = &mut context.blocks[block.0].instructions;
+        let alloc_idx = block_instrs
+            .iter()
+            .position(|&instr_val| instr_val == alloc_val)
+            .unwrap();
+        block_instrs[alloc_idx] = ptr_val;
+        block_instrs.insert(alloc_idx, get_local_val);
+
+        // The allocation may be used before its block in the block order, e.g. by a loop header.
+        function.replace_value(context, alloc_val, ptr_val, None);
+    }
+
+    Ok(true)
+}
+
+/// The number of bytes allocated by `val`, if it is a heap allocation of a constant size.
+fn allocation_size(context: &Context, val: Value) -> Option<u64> {
+    let Some(Instruction::AsmBlock(asm_block, args)) = val.get_instruction(context) else {
+        return None;
+    };
+    let content = asm_block.get_content(context);
+    let [aloc, mov] = content.body.as_slice() else {
+        return None;
+    };
+    let is_allocation = aloc.name.as_str() == "aloc"
+        && aloc.args.len() == 1
+        && mov.name.as_str() == "move"
+        && mov.args.len() == 2
+        && mov.args[1].as_str() == "hp"
+        && Some(&mov.args[0]) == content.return_name.as_ref();
+    if !is_allocation {
+        return None;
+    }
+
+    let size_arg = args.iter().find(|arg| arg.name == aloc.args[0])?;
+    match size_arg.initializer?.get_constant(context)?.value {
+        ConstantValue::Uint(size) => Some(size),
+        _ => None,
+    }
+}
+
+/// Whether `block` is part of a cycle in the CFG, i.e. whether it may execute more than once.
+fn is_in_loop(context: &Context, block: Block) -> bool {
+    let mut visited = FxHashSet::default();
+    let mut worklist = block
+        .successors(context)
+        .into_iter()
+        .map(|branch| branch.block)
+        .collect::<Vec<_>>();
+    while let Some(succ) = worklist.pop() {
+        if succ == block {
+            return true;
+        }
+        if visited.insert(succ) {
+            worklist.extend(
+                succ.successors(context)
+                    .into_iter()
+                    .map(|branch| branch.block),
+            );
+        }
+    }
+    false
+}
+
+/// Whether the address returned by the allocation `alloc_val` may escape the function.
+///
+/// Tracks the values which may hold the address, directly or as an address derived from it, and
+/// the locals which may have it stored in them, until either an escaping use is found or there is
+/// nothing new to track.
+fn escapes(
+    context: &Context,
+    function: Function,
+    escaped_symbols: &EscapedSymbols,
+    alloc_val: Value,
+) -> bool {
+    let mut pointers = FxHashSet::from_iter([alloc_val]);
+    let mut holders = FxHashSet::<Symbol>::default();
+
+    loop {
+        let mut new_pointers = Vec::new();
+        let mut new_holders = Vec::new();
+
+        let is_ptr = |val: &Value| pointers.contains(val);
+        let is_in_holder = |val: &Value| {
+            get_symbols(context, *val)
+                .iter()
+                .any(|symbol| holders.contains(symbol))
+        };
+        // The address may only be stored in locals which are known not to escape.
+        let mut store_into = |dst_val_ptr: &Value| {
+            let symbols = get_symbols(context, *dst_val_ptr);
+            let escapes = symbols.is_empty()
+                || symbols.iter().any(|symbol| {
+                    !matches!(symbol, Symbol::Local(_)) || escaped_symbols.contains(symbol)
+                });
+            new_holders.extend(symbols);
+            escapes
+        };
+
+        for (_block, inst) in function.instruction_iter(context) {
+            match inst.get_instruction(context).unwrap() {
+                Instruction::AsmBlock(asm_block, args) => {
+                    for arg in args
+                        .iter()
+                        .filter(|arg| arg.initializer.filter(is_ptr).is_some())
+                    {
+                        match asm_register_use(context, asm_block, &arg.name) {
+                            RegisterUse::Address => (),
+                            RegisterUse::Derives => new_pointers.push(inst),
+                            RegisterUse::Escapes => return true,
+                        }
+                    }
+                }
+                Instruction::BinaryOp {
+                    op: BinaryOpKind::Add | BinaryOpKind::Sub,
+                    arg1,
+                    arg2,
+                } if is_ptr(arg1) || is_ptr(arg2) => new_pointers.push(inst),
+                Instruction::BitCast(val, _)
+                | Instruction::CastPtr(val, _)
+                | Instruction::IntToPtr(val, _)
+                | Instruction::PtrToInt(val, _)
+                    if is_ptr(val) =>
+                {
+                    new_pointers.push(inst)
+                }
+                Instruction::GetElemPtr { base, indices, .. } => {
+                    if indices.iter().any(is_ptr) {
+                        return true;
+                    }
+                    if is_ptr(base) {
+                        new_pointers.push(inst);
+                    }
+                }
+                Instruction::GetLocal(_) | Instruction::Nop => (),
+                Instruction::Load(src_val_ptr) => {
+                    if is_in_holder(src_val_ptr) {
+                        new_pointers.push(inst);
+                    }
+                }
+                Instruction::Store {
+                    dst_val_ptr,
+                    stored_val,
+                } => {
+                    if is_ptr(stored_val) && store_into(dst_val_ptr) {
+                        return true;
+                    }
+                }
+                Instruction::MemCopyBytes {
+                    dst_val_ptr,
+                    src_val_ptr,
+                    ..
+                }
+                | Instruction::MemCopyVal {
+                    dst_val_ptr,
+                    src_val_ptr,
+                } => {
+                    if is_in_holder(src_val_ptr) && store_into(dst_val_ptr) {
+                        return true;
+                    }
+                }
+                Instruction::Branch(to_block) => {
+                    new_pointers.extend(block_args_receiving_ptr(context, to_block, is_ptr));
+                }
+                Instruction::ConditionalBranch {
+                    cond_value,
+                    true_block,
+                    false_block,
+                } => {
+                    if is_ptr(cond_value) {
+                        return true;
+                    }
+                    new_pointers.extend(block_args_receiving_ptr(context, true_block, is_ptr));
+                    new_pointers.extend(block_args_receiving_ptr(context, false_block, is_ptr));
+                }
+                // Any other use, e.g. in a call, a return or a log, lets the address escape.  So do
+                // comparisons, as the address may be compared with the heap pointer.
+                other => {
+                    if other.get_operands().iter().any(is_ptr) {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        let pointers_len = pointers.len();
+        let holders_len = holders.len();
+        pointers.extend(new_pointers);
+        holders.extend(new_holders);
+        if pointers.len() == pointers_len && holders.len() == holders_len {
+            return false;
+        }
+    }
+}
+
+fn block_args_receiving_ptr(
+    context: &Context,
+    to_block: &BranchToWithArgs,
+    is_ptr: impl Fn(&Value) -> bool,
+) -> Vec<Value> {
+    to_block
+        .args
+        .iter()
+        .enumerate()
+        .filter(|(_, arg)| is_ptr(*arg))
+        .filter_map(|(idx, _)| to_block.block.get_arg(context, idx))
+        .collect()
+}
+
+/// How an ASM block uses a register initialised with the address of an allocation.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum RegisterUse {
+    /// Only to access the allocated memory.
+    Address,
+    /// To compute an address which the block may return.
+    Derives,
+    /// In any other way, e.g. by storing it to memory.
+    Escapes,
+}
+
+fn asm_register_use(context: &Context, asm_block: &AsmBlock, register: &Ident) -> RegisterUse {
+    let content = asm_block.get_content(context);
+    let mut visited = FxHashSet::default();
+    let mut worklist = vec![register.clone()];
+    let mut register_use = RegisterUse::Address;
+    while let Some(register) = worklist.pop() {
+        if !visited.insert(register.clone()) {
+            continue;
+        }
+        if content.return_name.as_ref() == Some(&register) {
+            register_use = register_use.max(RegisterUse::Derives);
+        }
+        for op in &content.body {
+            for (idx, _) in op
+                .args
+                .iter()
+                .enumerate()
+                .filter(|(_, arg)| **arg == register)
+            {
+                match (op.name.as_str(), idx) {
+                    ("lw" | "lb" | "meq", 1)
+                    | ("sw" | "sb" | "mcl" | "mcli", 0)
+                    | ("mcp" | "mcpi", 0 | 1)
+                    | ("meq", 2) => (),
+                    // Arithmetic on the address derives a new one in the destination register.
+                    ("add" | "addi" | "sub" | "subi" | "move", _) => {
+                        worklist.push(op.args[0].clone())
+                    }
+                    _ => return RegisterUse::Escapes,
+                }
+            }
+        }
+    }
+    register_use
+}
@@ -2,7 +2,10 @@
 //!
 //! Function inlining is pretty hairy so these passes must be maintained with care.
 
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
 
 use rustc_hash::FxHashMap;
 
@@ -16,7 +19,7 @@ use crate::{
     instruction::{FuelVmInstruction, Instruction},
     irtype::Type,
     local_var::LocalVar,
-    metadata::{combine, MetadataIndex},
+    metadata::{combine, MetadataIndex, Metadatum},
     value::{Value, ValueContent, ValueDatum},
     AnalysisResults, BlockArgument, Module, Pass, PassMutability, ScopedPass,
 };
@@ -43,6 +46,39 @@ pub fn create_inline_in_module_pass() -> Pass {
     }
 }
 
+/// The heuristics deciding which calls [inline_in_module] inlines, see [Context::inline_params].
+///
+/// Functions marked `#[inline(always)]` and those with more arguments than can be passed in
+/// registers are inlined regardless, and functions marked `#[inline(never)]` and recursive ones
+/// never are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InlineParams {
+    /// Inline functions with at most this many instructions.
+    pub max_instrs: usize,
+    /// Only inline small functions if they also have at most this many blocks.
+    pub max_blocks: Option<usize>,
+    /// Inline functions which are called only once, regardless of their size.
+    pub single_call_sites: bool,
+    /// Don't inline functions into others if it nests inlined code deeper than this, e.g. `1` to
+    /// only inline functions which have nothing inlined into them.
+    pub max_depth: Option<usize>,
+    /// Inline every call but those to recursive functions, regardless of the other heuristics and
+    /// of `#[inline(never)]`, e.g. for targets which can't return from calls.
+    pub inline_all: bool,
+}
+
+impl Default for InlineParams {
+    fn default() -> Self {
+        Self {
+            max_instrs: 4,
+            max_blocks: None,
+            single_call_sites: true,
+            max_depth: None,
+            inline_all: false,
+        }
+    }
+}
+
 /// This is a copy of sway_core::inline::Inline.
 /// TODO: Reuse: Depend on sway_core? Move it to sway_types?
 #[derive(Debug)]
@@ -109,12 +145,32 @@ pub fn inline_in_module(
                 counts
             });
 
-    let inline_heuristic = |ctx: &Context, func: &Function, _call_site: &Value| {
+    let cg =
+        call_graph::build_call_graph(context, &module.function_iter(context).collect::<Vec<_>>());
+
+    // Inlining a call to a recursive function only unrolls a single level of recursion, so those
+    // calls are left in place.
+    let recursive_fns = call_graph::recursive_functions(&cg);
+
+    let params = context.inline_params;
+    let is_small = is_small_fn(params.max_blocks, Some(params.max_instrs), None);
+
+    // How deeply inlined code is nested within each function, once calls have been inlined into
+    // it.  Functions are visited callee first, so their callees' depths are known.
+    let depths = RefCell::new(HashMap::<Function, usize>::new());
+
+    let inline_heuristic = |ctx: &Context, func: &Function, call_site: &Value| {
+        if recursive_fns.contains(func) {
+            return false;
+        }
+        if params.inline_all {
+            return true;
+        }
+
         let attributed_inline = metadata_to_inline(ctx, func.get_metadata(ctx));
         match attributed_inline {
             Some(Inline::Always) => {
-                // TODO: check if inlining of function is possible
-                // return true;
+                return true;
             }
             Some(Inline::Never) => {
                 return false;
@@ -140,27 +196,42 @@ pub fn inline_in_module(
             return true;
         }
 
-        // If the function is called only once then definitely inline it.
-        if call_counts.get(func).copied().unwrap_or(0) == 1 {
-            return true;
+        // Beyond the maximum depth only the calls which must be inlined are.
+        let depth = depths.borrow().get(func).copied().unwrap_or(0) + 1;
+        if params
+            .max_depth
+            .map_or(false, |max_depth| depth > max_depth)
+        {
+            return false;
         }
 
-        // If the function is (still) small then also inline it.
-        const MAX_INLINE_INSTRS_COUNT: usize = 4;
-        if func.num_instructions(ctx) <= MAX_INLINE_INSTRS_COUNT {
+        // If the function is called only once then definitely inline it.
+        if params.single_call_sites && call_counts.get(func).copied().unwrap_or(0) == 1 {
             return true;
         }
 
-        false
+        // If the function is (still) small then also inline it.
+        is_small(ctx, func, call_site)
     };
 
-    let cg =
-        call_graph::build_call_graph(context, &module.function_iter(context).collect::<Vec<_>>());
     let functions = call_graph::callee_first_order(&cg);
     let mut modified = false;
 
     for function in functions {
-        modified |= inline_some_function_calls(context, &function, inline_heuristic)?;
+        let depth = Cell::new(0);
+        let inlined = inline_some_function_calls(context, &function, |ctx, func, call_site| {
+            let inline = inline_heuristic(ctx, func, call_site);
+            if inline {
+                depth.set(
+                    depth
+                        .get()
+                        .max(depths.borrow().get(func).copied().unwrap_or(0) + 1),
+                );
+            }
+            inline
+        })?;
+        depths.borrow_mut().insert(function, depth.get());
+        modified |= inlined;
     }
     Ok(modified)
 }
@@ -352,6 +423,7 @@ pub fn inline_function_call(
     // Get the metadata attached to the function call which may need to be propagated to the
     // inlined instructions.
     let metadata = context.values[call_site.0].metadata;
+    let mut inlined_md_map = HashMap::new();
 
     // Now remove the call altogether.
     context.values.remove(call_site.0);
@@ -414,6 +486,7 @@ pub fn inline_function_call(
                 &mut value_map,
                 &ptr_map,
                 metadata,
+                &mut inlined_md_map,
             );
         }
     }
@@ -421,6 +494,64 @@ pub fn inline_function_call(
     Ok(())
 }
 
+/// The metadata of an instruction with `val_metadata` once inlined at a call site with
+/// `call_metadata`.
+///
+/// The call site metadata is wrapped in an `inlined_at` struct, so that the spans of the call
+/// sites can be told apart from the span of the instruction itself.  If the instruction had
+/// already been inlined from another function, its `inlined_at` struct is rewritten rather than
+/// a second one added, so that the call sites nest with the innermost first.
+fn inlined_metadata(
+    context: &mut Context,
+    call_metadata: Option<MetadataIndex>,
+    val_metadata: Option<MetadataIndex>,
+) -> Option<MetadataIndex> {
+    let Some(call_md_idx) = call_metadata else {
+        return val_metadata;
+    };
+
+    let mut md_idcs = match val_metadata {
+        None => Vec::new(),
+        Some(md_idx) => match md_idx.get_content(context) {
+            Metadatum::List(md_idcs) => md_idcs.clone(),
+            _otherwise => vec![md_idx],
+        },
+    };
+    let inner_call_site = md_idcs.iter().enumerate().find_map(|(pos, md_idx)| {
+        md_idx
+            .get_content(context)
+            .unwrap_struct("inlined_at", 1)
+            .and_then(|fields| fields[0].unwrap_index())
+            .map(|inner_call_md_idx| (pos, inner_call_md_idx))
+    });
+
+    match inner_call_site {
+        Some((pos, inner_call_md_idx)) => {
+            let inner_call_md_idx =
+                inlined_metadata(context, call_metadata, Some(inner_call_md_idx))
+                    .expect("Call site metadata is always present.");
+            md_idcs[pos] = MetadataIndex::new_struct(
+                context,
+                "inlined_at",
+                vec![Metadatum::Index(inner_call_md_idx)],
+            );
+            Some(if md_idcs.len() == 1 {
+                md_idcs[0]
+            } else {
+                MetadataIndex::new_list(context, md_idcs)
+            })
+        }
+        None => {
+            let inlined_at = MetadataIndex::new_struct(
+                context,
+                "inlined_at",
+                vec![Metadatum::Index(call_md_idx)],
+            );
+            combine(context, &val_metadata, &Some(inlined_at))
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn inline_instruction(
     context: &mut Context,
@@ -431,6 +562,7 @@ fn inline_instruction(
     value_map: &mut HashMap<Value, Value>,
     local_map: &HashMap<LocalVar, LocalVar>,
     fn_metadata: Option<MetadataIndex>,
+    inlined_md_map: &mut HashMap<Option<MetadataIndex>, Option<MetadataIndex>>,
 ) {
     // Util to translate old blocks to new.  If an old block isn't in the map then we panic, since
     // it should be guaranteed to be there...that's a bug otherwise.
@@ -453,9 +585,11 @@ fn inline_instruction(
         metadata: val_metadata,
     } = context.values[instruction.0].clone()
     {
-        // Combine the function metadata with this instruction metadata so we don't lose the
-        // function metadata after inlining.
-        let metadata = combine(context, &fn_metadata, &val_metadata);
+        // Record the call site in the instruction metadata so we don't lose the function
+        // metadata after inlining.
+        let metadata = *inlined_md_map
+            .entry(val_metadata)
+            .or_insert_with(|| inlined_metadata(context, fn_metadata, val_metadata));
 
         let new_ins = match old_ins {
             Instruction::AsmBlock(asm, args) => {
@@ -0,0 +1,212 @@
+//! ## Scalar Replacement of Aggregates
+//!
+//! Local structs and arrays whose fields are only accessed through `get_elem_ptr` with constant
+//! indices are split into a separate local per field, which [mem2reg](super::mem2reg) can then
+//! promote to SSA registers, so that the field accesses no longer go through memory.
+//!
+//! A field local may itself be an aggregate, in which case it is split again.  A local is only
+//! split if the pointers to its fields don't escape, i.e. they are only loaded from, stored to,
+//! copied to or from, or indexed further, as anything else could rely on the fields being laid
+//! out next to each other.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    AnalysisResults, Block, ConstantValue, Context, Function, Instruction, IrError, LocalVar, Pass,
+    PassMutability, ScopedPass, Type, Value, ValueDatum,
+};
+
+pub const SROA_NAME: &str = "sroa";
+
+pub fn create_sroa_pass() -> Pass {
+    Pass {
+        name: SROA_NAME,
+        descr: "Scalar replacement of aggregates.",
+        deps: vec![],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(sroa)),
+    }
+}
+
+/// Split the aggregate locals of `function` into their fields, and return true if any were split.
+pub fn sroa(
+    context: &mut Context,
+    _: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let mut modified = false;
+    // The fields of a split local may be aggregates which can be split in turn.
+    loop {
+        let candidates = splittable_locals(context, function);
+        if candidates.is_empty() {
+            break;
+        }
+        for (name, local_var) in candidates {
+            split_local(context, function, name, local_var);
+        }
+        modified = true;
+    }
+    Ok(modified)
+}
+
+/// The `get_local` instructions of each local, and the instructions using each value.
+struct Uses {
+    get_locals: FxHashMap<LocalVar, Vec<(Block, Value)>>,
+    users: FxHashMap<Value, Vec<Value>>,
+}
+
+fn collect_uses(context: &Context, function: Function) -> Uses {
+    let mut get_locals = FxHashMap::<LocalVar, Vec<(Block, Value)>>::default();
+    let mut users = FxHashMap::<Value, Vec<Value>>::default();
+    for (block, inst) in function.instruction_iter(context) {
+        let instruction = inst.get_instruction(context).unwrap();
+        if let Instruction::GetLocal(local_var) = instruction {
+            get_locals
+                .entry(*local_var)
+                .or_default()
+                .push((block, inst));
+        }
+        for operand in instruction.get_operands() {
+            users.entry(operand).or_default().push(inst);
+        }
+    }
+    Uses { get_locals, users }
+}
+
+fn splittable_locals(context: &Context, function: Function) -> Vec<(String, LocalVar)> {
+    let uses = collect_uses(context, function);
+    function
+        .locals_iter(context)
+        .filter(|(_, local_var)| {
+            let ty = local_var.get_inner_type(context);
+            (ty.is_struct(context) || ty.is_array(context))
+                && local_var.get_initializer(context).is_none()
+                && uses.get_locals.get(local_var).map_or(true, |get_locals| {
+                    get_locals
+                        .iter()
+                        .all(|(_, get_local)| only_indexes_fields(context, &uses, ty, *get_local))
+                })
+        })
+        .map(|(name, local_var)| (name.clone(), *local_var))
+        .collect()
+}
+
+/// Whether `ptr`, a pointer to an aggregate of type `ty`, is only used to get pointers to its
+/// fields which don't escape.
+fn only_indexes_fields(context: &Context, uses: &Uses, ty: Type, ptr: Value) -> bool {
+    uses.users.get(&ptr).map_or(true, |users| {
+        users
+            .iter()
+            .all(|user| match user.get_instruction(context) {
+                Some(Instruction::GetElemPtr { base, indices, .. }) if *base == ptr => {
+                    match indices.first().and_then(|idx| field_index(context, *idx)) {
+                        Some(idx) => {
+                            field_type(context, ty, idx).is_some()
+                                && !indices.contains(&ptr)
+                                && does_not_escape(context, uses, *user)
+                        }
+                        None => false,
+                    }
+                }
+                _ => false,
+            })
+    })
+}
+
+/// Whether the field pointer `ptr` is only accessed directly or indexed further.
+fn does_not_escape(context: &Context, uses: &Uses, ptr: Value) -> bool {
+    uses.users.get(&ptr).map_or(true, |users| {
+        users
+            .iter()
+            .all(|user| match user.get_instruction(context) {
+                Some(Instruction::Load(_)) | Some(Instruction::MemCopyVal { .. }) => true,
+                Some(Instruction::Store { stored_val, .. }) => *stored_val != ptr,
+                Some(Instruction::GetElemPtr { base, indices, .. }) => {
+                    *base == ptr && !indices.contains(&ptr) && does_not_escape(context, uses, *user)
+                }
+                _ => false,
+            })
+    })
+}
+
+fn field_index(context: &Context, idx: Value) -> Option<u64> {
+    match idx.get_constant(context)?.value {
+        ConstantValue::Uint(idx) => Some(idx),
+        _ => None,
+    }
+}
+
+fn field_type(context: &Context, ty: Type, idx: u64) -> Option<Type> {
+    if ty.is_array(context) {
+        (idx < ty.get_array_len(context)?)
+            .then(|| ty.get_array_elem_type(context))
+            .flatten()
+    } else {
+        ty.get_field_type(context, idx)
+    }
+}
+
+fn split_local(context: &mut Context, function: Function, name: String, local_var: LocalVar) {
+    let ty = local_var.get_inner_type(context);
+    let mutable = local_var.is_mutable(context);
+    let uses = collect_uses(context, function);
+    let get_locals = uses.get_locals.get(&local_var).cloned().unwrap_or_default();
+
+    // The new locals and a `get_local` for each, by field index.  The `get_local`s are put in the
+    // entry block, where they dominate every use.
+    let mut field_locals = FxHashMap::<u64, (LocalVar, Value)>::default();
+    let mut new_get_locals = Vec::new();
+    let mut used_new_get_locals = FxHashSet::default();
+
+    for (_, get_local) in &get_locals {
+        for gep in uses.users.get(get_local).cloned().unwrap_or_default() {
+            let Some(Instruction::GetElemPtr {
+                elem_ptr_ty,
+                indices,
+                ..
+            }) = gep.get_instruction(context).cloned()
+            else {
+                unreachable!("the local only has its fields indexed");
+            };
+            let idx = field_index(context, indices[0]).unwrap();
+            let (field_local, field_ptr) = *field_locals.entry(idx).or_insert_with(|| {
+                let field_ty = field_type(context, ty, idx).unwrap();
+                let field_local = function.new_unique_local_var(
+                    context,
+                    format!("{name}_{idx}"),
+                    field_ty,
+                    None,
+                    mutable,
+                );
+                let field_ptr = Value::new_instruction(context, Instruction::GetLocal(field_local));
+                new_get_locals.push(field_ptr);
+                (field_local, field_ptr)
+            });
+
+            if indices.len() == 1 {
+                gep.replace(
+                    context,
+                    ValueDatum::Instruction(Instruction::GetLocal(field_local)),
+                );
+            } else {
+                used_new_get_locals.insert(field_ptr);
+                gep.replace(
+                    context,
+                    ValueDatum::Instruction(Instruction::GetElemPtr {
+                        base: field_ptr,
+                        elem_ptr_ty,
+                        indices: indices[1..].to_vec(),
+                    }),
+                );
+            }
+        }
+    }
+
+    new_get_locals.retain(|get_local| used_new_get_locals.contains(get_local));
+    function
+        .get_entry_block(context)
+        .prepend_instructions(context, new_get_locals);
+    for (block, get_local) in get_locals {
+        block.remove_instruction(context, get_local);
+    }
+    function.remove_locals(context, &vec![name]);
+}
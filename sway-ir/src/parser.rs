@@ -403,8 +403,9 @@ mod ir_builder {
                 }
 
             rule asm_op() -> IrAstAsmOp
-                = name:id_id() args:asm_op_arg()* imm:asm_op_arg_imm()? meta_idx:comma_metadata_idx()? {
+                = label:asm_label()? name:id_id() args:asm_op_arg()* imm:asm_op_arg_imm()? meta_idx:comma_metadata_idx()? {
                     IrAstAsmOp {
+                        label,
                         name,
                         args,
                         imm,
@@ -412,8 +413,13 @@ mod ir_builder {
                     }
                 }
 
+            rule asm_label() -> Ident
+                = label:id_id() ":" _ {
+                    label
+                }
+
             rule asm_op_arg() -> Ident
-                = !asm_op_arg_imm() arg:id_id() {
+                = !asm_op_arg_imm() !asm_label() arg:id_id() {
                     arg
                 }
 
@@ -738,6 +744,7 @@ mod ir_builder {
 
     #[derive(Debug)]
     struct IrAstAsmOp {
+        label: Option<Ident>,
         name: Ident,
         args: Vec<Ident>,
         imm: Option<Ident>,
@@ -854,7 +861,10 @@ mod ir_builder {
 
     // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
-    use std::{collections::HashMap, iter::FromIterator};
+    use std::{
+        collections::{HashMap, HashSet},
+        iter::FromIterator,
+    };
 
     pub(super) fn build_context(
         ir_ast_mod: IrAstModule,
@@ -1017,21 +1027,40 @@ mod ir_builder {
                                 }),
                             })
                             .collect();
+                        // Jump targets are printed like registers, so a trailing argument
+                        // naming one of the block's labels is really the immediate.
+                        let labels = ops
+                            .iter()
+                            .filter_map(|op| op.label.as_ref().map(|label| label.as_str()))
+                            .collect::<HashSet<_>>();
                         let body = ops
-                            .into_iter()
+                            .iter()
                             .map(
                                 |IrAstAsmOp {
+                                     label,
                                      name,
                                      args,
                                      imm,
                                      meta_idx,
-                                 }| AsmInstruction {
-                                    name,
-                                    args,
-                                    immediate: imm,
-                                    metadata: meta_idx
-                                        .as_ref()
-                                        .and_then(|meta_idx| self.md_map.get(meta_idx).copied()),
+                                 }| {
+                                    let mut args = args.clone();
+                                    let mut imm = imm.clone();
+                                    if imm.is_none()
+                                        && args
+                                            .last()
+                                            .map_or(false, |arg| labels.contains(arg.as_str()))
+                                    {
+                                        imm = args.pop();
+                                    }
+                                    AsmInstruction {
+                                        label: label.clone(),
+                                        name: name.clone(),
+                                        args,
+                                        immediate: imm,
+                                        metadata: meta_idx.as_ref().and_then(|meta_idx| {
+                                            self.md_map.get(meta_idx).copied()
+                                        }),
+                                    }
                                 },
                             )
                             .collect();
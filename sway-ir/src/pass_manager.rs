@@ -1,17 +1,21 @@
 use crate::{
-    create_arg_demotion_pass, create_const_combine_pass, create_const_demotion_pass,
-    create_dce_pass, create_dom_fronts_pass, create_dominators_pass, create_escaped_symbols_pass,
-    create_func_dce_pass, create_inline_in_main_pass, create_inline_in_module_pass,
-    create_mem2reg_pass, create_memcpyopt_pass, create_misc_demotion_pass,
-    create_module_printer_pass, create_module_verifier_pass, create_postorder_pass,
-    create_ret_demotion_pass, create_simplify_cfg_pass, Context, Function, IrError, Module,
-    CONSTCOMBINE_NAME, DCE_NAME, FUNC_DCE_NAME, INLINE_MODULE_NAME, MEM2REG_NAME, SIMPLIFYCFG_NAME,
+    create_adce_pass, create_arg_demotion_pass, create_const_combine_pass,
+    create_const_demotion_pass, create_cse_pass, create_dce_pass, create_dom_fronts_pass,
+    create_dominators_pass, create_escaped_symbols_pass, create_fn_dedup_pass,
+    create_func_dce_pass, create_heap2stack_pass, create_inline_in_main_pass,
+    create_inline_in_module_pass, create_mem2reg_pass, create_memcpyopt_pass,
+    create_misc_demotion_pass, create_module_printer_pass, create_module_verifier_pass,
+    create_outline_pass, create_postorder_pass, create_ret_demotion_pass, create_simplify_cfg_pass,
+    create_smt_printer_pass, create_sroa_pass, Context, Function, IrError, Module, ADCE_NAME,
+    CONSTCOMBINE_NAME, CSE_NAME, DCE_NAME, FUNC_DCE_NAME, INLINE_MODULE_NAME, MEM2REG_NAME,
+    SIMPLIFYCFG_NAME, SROA_NAME,
 };
 use downcast_rs::{impl_downcast, Downcast};
 use rustc_hash::FxHashMap;
 use std::{
     any::{type_name, TypeId},
     collections::hash_map,
+    time::{Duration, Instant},
 };
 
 /// Result of an analysis. Specific result must be downcasted to.
@@ -140,10 +144,24 @@ impl AnalysisResults {
     }
 }
 
+/// The time taken by the runs of a pass.
+#[derive(Clone, Debug)]
+pub struct PassTiming {
+    pub name: &'static str,
+    pub elapsed: Duration,
+    pub runs: usize,
+}
+
 #[derive(Default)]
 pub struct PassManager {
     passes: FxHashMap<&'static str, Pass>,
     analyses: AnalysisResults,
+    /// Whether to verify the IR after each transformation which modifies it.
+    verify: bool,
+    /// Whether to time the passes. Clocks are not available on every target, e.g. WebAssembly.
+    time: bool,
+    /// The passes which have run, in the order they first ran, if they're timed.
+    timings: Vec<PassTiming>,
 }
 
 impl PassManager {
@@ -187,8 +205,10 @@ impl PassManager {
 
         // To please the borrow checker, get current pass again.
         let pass_t = self.passes.get(pass).expect("Unregistered pass");
+        let start = self.time.then(Instant::now);
 
         for m in ir.module_iter() {
+            let mut module_modified = false;
             match &pass_t.runner {
                 ScopedPass::ModulePass(mp) => match mp {
                     PassMutability::Analysis(analysis) => {
@@ -203,7 +223,7 @@ impl PassManager {
                             for f in m.function_iter(ir) {
                                 self.analyses.invalidate_all_results_at_scope(f);
                             }
-                            modified = true;
+                            module_modified = true;
                         }
                     }
                 },
@@ -220,17 +240,54 @@ impl PassManager {
                                 if transform(ir, &self.analyses, f)? {
                                     self.analyses.invalidate_all_results_at_scope(f);
                                     self.analyses.invalidate_all_results_at_scope(m);
-                                    modified = true;
+                                    module_modified = true;
                                 }
                             }
                         }
                     }
                 }
             }
+            if module_modified && self.verify {
+                ir.verify_module(&ir.modules[m.0]).map_err(|err| {
+                    IrError::InvalidAfterPass(pass_t.name.to_string(), Box::new(err))
+                })?;
+            }
+            modified |= module_modified;
+        }
+
+        if let Some(start) = start {
+            let elapsed = start.elapsed();
+            match self.timings.iter_mut().find(|timing| timing.name == pass) {
+                Some(timing) => {
+                    timing.elapsed += elapsed;
+                    timing.runs += 1;
+                }
+                None => self.timings.push(PassTiming {
+                    name: pass,
+                    elapsed,
+                    runs: 1,
+                }),
+            }
         }
         Ok(modified)
     }
 
+    /// The time taken by each pass which has run, excluding the passes it depends on, in the
+    /// order they first ran. Empty unless the passes are timed.
+    pub fn timings(&self) -> &[PassTiming] {
+        &self.timings
+    }
+
+    /// Time each pass, to be reported by [PassManager::timings].
+    pub fn time_passes(&mut self, time: bool) {
+        self.time = time;
+    }
+
+    /// Verify the IR after each pass which modifies it, to catch the pass which makes it invalid.
+    pub fn verify_after_each_pass(&mut self, verify: bool) {
+        self.verify = verify;
+    }
+
     /// Run the passes specified in `config`.
     pub fn run(&mut self, ir: &mut Context, passes: &PassGroup) -> Result<bool, IrError> {
         let mut modified = false;
@@ -245,6 +302,13 @@ impl PassManager {
         self.passes.get(name)
     }
 
+    /// The names of the registered passes, in alphabetical order.
+    pub fn pass_names(&self) -> Vec<&'static str> {
+        let mut names = self.passes.keys().copied().collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
     pub fn help_text(&self) -> String {
         let summary = self
             .passes
@@ -303,20 +367,27 @@ pub fn register_known_passes(pm: &mut PassManager) {
     pm.register(create_dom_fronts_pass());
     pm.register(create_escaped_symbols_pass());
     pm.register(create_module_printer_pass());
+    pm.register(create_smt_printer_pass());
     pm.register(create_module_verifier_pass());
     // Optimization passes.
     pm.register(create_mem2reg_pass());
+    pm.register(create_sroa_pass());
     pm.register(create_inline_in_module_pass());
     pm.register(create_inline_in_main_pass());
     pm.register(create_const_combine_pass());
     pm.register(create_simplify_cfg_pass());
+    pm.register(create_cse_pass());
     pm.register(create_func_dce_pass());
+    pm.register(create_fn_dedup_pass());
+    pm.register(create_outline_pass());
     pm.register(create_dce_pass());
+    pm.register(create_adce_pass());
     pm.register(create_arg_demotion_pass());
     pm.register(create_const_demotion_pass());
     pm.register(create_ret_demotion_pass());
     pm.register(create_misc_demotion_pass());
     pm.register(create_memcpyopt_pass());
+    pm.register(create_heap2stack_pass());
 }
 
 pub fn create_o1_pass_group() -> PassGroup {
@@ -325,16 +396,62 @@ pub fn create_o1_pass_group() -> PassGroup {
     // Configure to run our passes.
     o1.append_pass(MEM2REG_NAME);
     o1.append_pass(INLINE_MODULE_NAME);
+    o1.append_pass(SROA_NAME);
+    o1.append_pass(MEM2REG_NAME);
     o1.append_pass(CONSTCOMBINE_NAME);
     o1.append_pass(SIMPLIFYCFG_NAME);
     o1.append_pass(CONSTCOMBINE_NAME);
     o1.append_pass(SIMPLIFYCFG_NAME);
+    o1.append_pass(CSE_NAME);
     o1.append_pass(FUNC_DCE_NAME);
+    o1.append_pass(ADCE_NAME);
     o1.append_pass(DCE_NAME);
 
     o1
 }
 
+/// The passes of [create_o1_pass_group], followed by another round of inlining and of the
+/// simplifications which find more to do once functions have been inlined and constants folded.
+pub fn create_o2_pass_group() -> PassGroup {
+    let mut o2 = create_o1_pass_group();
+    o2.append_pass(INLINE_MODULE_NAME);
+    o2.append_pass(SROA_NAME);
+    o2.append_pass(MEM2REG_NAME);
+    o2.append_pass(CONSTCOMBINE_NAME);
+    o2.append_pass(SIMPLIFYCFG_NAME);
+    o2.append_pass(CSE_NAME);
+    o2.append_pass(FUNC_DCE_NAME);
+    o2.append_pass(ADCE_NAME);
+    o2.append_pass(DCE_NAME);
+
+    o2
+}
+
+/// Create the passes optimizing at the given level, from `0` for no optimizations at all to `2`,
+/// the most there is, which is also what the levels above it optimize as.
+pub fn create_opt_level_pass_group(level: u8) -> PassGroup {
+    match level {
+        0 => PassGroup::default(),
+        1 => create_o1_pass_group(),
+        _ => create_o2_pass_group(),
+    }
+}
+
+/// Create a group of the registered passes with the given names, to be run in the given order.
+///
+/// Fails with the first name which isn't the name of a registered pass.
+pub fn create_named_pass_group<'a>(
+    pass_mgr: &PassManager,
+    names: impl IntoIterator<Item = &'a str>,
+) -> Result<PassGroup, &'a str> {
+    let mut passes = PassGroup::default();
+    for name in names {
+        let pass = pass_mgr.lookup_registered_pass(name).ok_or(name)?;
+        passes.append_pass(pass.name);
+    }
+    Ok(passes)
+}
+
 /// Utility to insert a pass after every pass in the given group
 pub fn insert_after_each(pg: PassGroup, pass: &'static str) -> PassGroup {
     PassGroup(
@@ -343,3 +460,48 @@ pub fn insert_after_each(pg: PassGroup, pass: &'static str) -> PassGroup {
             .collect(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_opt_level_has_its_own_passes() {
+        let o0 = create_opt_level_pass_group(0).flatten_pass_group();
+        let o1 = create_opt_level_pass_group(1).flatten_pass_group();
+        let o2 = create_opt_level_pass_group(2).flatten_pass_group();
+
+        assert!(o0.is_empty());
+        assert_eq!(o1, create_o1_pass_group().flatten_pass_group());
+        assert_eq!(o2[..o1.len()], o1[..]);
+        assert_eq!(
+            o2[o1.len()..],
+            [
+                INLINE_MODULE_NAME,
+                SROA_NAME,
+                MEM2REG_NAME,
+                CONSTCOMBINE_NAME,
+                SIMPLIFYCFG_NAME,
+                CSE_NAME,
+                FUNC_DCE_NAME,
+                ADCE_NAME,
+                DCE_NAME,
+            ]
+        );
+        assert_eq!(create_opt_level_pass_group(3).flatten_pass_group(), o2);
+    }
+
+    #[test]
+    fn named_passes_are_looked_up() {
+        let mut pass_mgr = PassManager::default();
+        register_known_passes(&mut pass_mgr);
+
+        let passes = create_named_pass_group(&pass_mgr, [MEM2REG_NAME, DCE_NAME]).unwrap();
+        assert_eq!(passes.flatten_pass_group(), [MEM2REG_NAME, DCE_NAME]);
+        assert_eq!(
+            create_named_pass_group(&pass_mgr, [MEM2REG_NAME, "no_such_pass", "nor_this"]).err(),
+            Some("no_such_pass")
+        );
+        assert!(pass_mgr.pass_names().contains(&MEM2REG_NAME));
+    }
+}
@@ -101,6 +101,19 @@ pub fn to_string(context: &Context) -> String {
         .build()
 }
 
+/// Pretty-print a single [`Function`] to a string, along with the metadata it refers to.
+pub fn function_to_string(context: &Context, function: Function) -> String {
+    let mut md_namer = MetadataNamer::default();
+    function_to_doc(
+        context,
+        &mut md_namer,
+        &mut Namer::new(function, GlobalNamer::new()),
+        &context.functions[function.0],
+    )
+    .append(md_namer.to_doc(context))
+    .build()
+}
+
 pub struct ModulePrinterResult;
 impl AnalysisResultT for ModulePrinterResult {}
 
@@ -896,13 +909,21 @@ fn asm_block_to_doc(
                 body.iter()
                     .map(
                         |AsmInstruction {
+                             label,
                              name,
                              args,
                              immediate,
                              metadata,
                          }| {
                             Doc::line(
-                                Doc::text(format!("{:6} ", name.as_str())).append(
+                                Doc::text(
+                                    label
+                                        .as_ref()
+                                        .map(|label| format!("{label}: "))
+                                        .unwrap_or_default(),
+                                )
+                                .append(Doc::text(format!("{:6} ", name.as_str())))
+                                .append(
                                     Doc::list_sep(
                                         args.iter().map(|arg| Doc::text(arg.as_str())).collect(),
                                         Doc::text(" "),
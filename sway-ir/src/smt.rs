@@ -0,0 +1,626 @@
+//! Export the pure functions of a module to SMT-LIB 2, for formal-methods tools to check them.
+//!
+//! The output is a script in the `QF_BV` logic, starting with a `(reset)` so that the scripts of
+//! several modules can be run one after the other.  Every function whose arguments and return
+//! value are `bool`, integers or `b256`, and which only computes with them, branches, returns and
+//! reverts is exported.  The others are skipped, with a comment saying why, e.g. because they
+//! access memory or call other functions, so the exporter is best run after inlining.
+//!
+//! # Format
+//!
+//! `bool` values are `Bool`s, `b256` values are `(_ BitVec 256)`s and integers of any width are
+//! `(_ BitVec 64)`s, as the VM computes on whole words.  Configurables have their default values.
+//! The symbols of a function `f` are all prefixed with `f.`:
+//!
+//! - Each argument `x` is declared as the constant `f.x`.
+//! - Each value computed by `f` is defined as a constant `f.vN` of the terms it's computed from.
+//! - `f` is explored from its entry block along every path through it, forking at each conditional
+//!   branch and at each instruction which makes the VM panic for some operands, i.e. additions,
+//!   subtractions and multiplications which overflow and divisions by zero.  The `k`th path has:
+//!   - A comment naming the blocks it goes through, and how it ends.
+//!   - Its path condition `f.pk`, under which `f` takes it.  The conditions of the paths are
+//!     mutually exclusive and together cover every value of the arguments.
+//!   - `f.pk.ret`, the value returned at the end of the path if it returns one, or `f.pk.revert`,
+//!     the revert code if it reverts.  Paths which panic end with neither.
+//!
+//!   The exploration is bounded: paths going through a block more than 8 times, or beyond the
+//!   first 256 paths, are unexplored, which their comment says.
+//!
+//! The entry functions of scripts and predicates end with a query checking whether their
+//! assertions can fail, i.e. whether they can revert or panic: `sat` if they can, in which case the
+//! model has the arguments making them fail.  `unsat` proves they can't, unless some of their paths
+//! are unexplored.
+
+use std::fmt::Write;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    block::Block,
+    constant::{Constant, ConstantValue},
+    context::Context,
+    function::Function,
+    instruction::{BranchToWithArgs, FuelVmInstruction, Instruction, Predicate},
+    irtype::Type,
+    module::{Kind, Module},
+    value::Value,
+    AnalysisResult, AnalysisResultT, AnalysisResults, BinaryOpKind, IrError, Pass, PassMutability,
+    ScopedPass,
+};
+
+/// The maximum number of times each path goes through the same block, i.e. the bound on the
+/// number of iterations of loops.
+const MAX_VISITS: usize = 8;
+
+/// The maximum number of paths explored through each function.
+const MAX_PATHS: usize = 256;
+
+pub struct SmtPrinterResult;
+impl AnalysisResultT for SmtPrinterResult {}
+
+/// Print the pure functions of a module to stdout in SMT-LIB 2.
+pub fn smt_printer(
+    context: &Context,
+    _analyses: &AnalysisResults,
+    module: Module,
+) -> Result<AnalysisResult, IrError> {
+    print!("{}", module_to_smt(context, module));
+    Ok(Box::new(SmtPrinterResult))
+}
+
+pub const SMTPRINTER_NAME: &str = "smt_printer";
+
+pub fn create_smt_printer_pass() -> Pass {
+    Pass {
+        name: SMTPRINTER_NAME,
+        descr: "Print the pure functions of module to stdout in SMT-LIB 2",
+        deps: vec![],
+        runner: ScopedPass::ModulePass(PassMutability::Analysis(smt_printer)),
+    }
+}
+
+/// Export the pure functions of `module` to an SMT-LIB 2 script, in the format described in the
+/// [module documentation](self).
+pub fn module_to_smt(context: &Context, module: Module) -> String {
+    let kind = module.get_kind(context);
+    let kind_name = match kind {
+        Kind::Contract => "contract",
+        Kind::Library => "library",
+        Kind::Predicate => "predicate",
+        Kind::Script => "script",
+    };
+    let mut smt = format!("; The pure functions of the {kind_name}.\n(reset)\n(set-logic QF_BV)\n");
+    for function in module.function_iter(context) {
+        smt.push('\n');
+        match unsupported(context, function) {
+            Some(reason) => {
+                writeln!(
+                    smt,
+                    "; fn {} is skipped: {reason}.",
+                    function.get_name(context)
+                )
+                .unwrap();
+            }
+            None => {
+                let check_failures =
+                    function.is_entry(context) && matches!(kind, Kind::Script | Kind::Predicate);
+                smt.push_str(&function_to_smt(context, function, check_failures));
+            }
+        }
+    }
+    smt
+}
+
+/// The sorts of the values of the supported types.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Sort {
+    Bool,
+    Word,
+    B256,
+}
+
+impl Sort {
+    fn of_type(context: &Context, ty: Type) -> Option<Sort> {
+        if ty.is_bool(context) {
+            Some(Sort::Bool)
+        } else if ty.is_uint(context) {
+            Some(Sort::Word)
+        } else if ty.is_b256(context) {
+            Some(Sort::B256)
+        } else {
+            None
+        }
+    }
+
+    fn of_value(context: &Context, value: Value) -> Option<Sort> {
+        value
+            .get_type(context)
+            .and_then(|ty| Sort::of_type(context, ty))
+    }
+
+    fn as_smt(self) -> &'static str {
+        match self {
+            Sort::Bool => "Bool",
+            Sort::Word => "(_ BitVec 64)",
+            Sort::B256 => "(_ BitVec 256)",
+        }
+    }
+}
+
+fn literal(constant: &Constant) -> Option<String> {
+    match &constant.value {
+        ConstantValue::Bool(b) => Some(b.to_string()),
+        ConstantValue::Uint(n) => Some(format!("(_ bv{n} 64)")),
+        ConstantValue::B256(bytes) => Some(format!(
+            "#x{}",
+            bytes.iter().map(|b| format!("{b:02x}")).collect::<String>()
+        )),
+        _ => None,
+    }
+}
+
+/// Quote `name` unless it's a simple symbol.
+fn symbol(name: String) -> String {
+    let is_simple = !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "~!@$%^&*_-+=<>.?/".contains(c));
+    if is_simple {
+        name
+    } else {
+        format!("|{name}|")
+    }
+}
+
+/// Why `function` can't be exported, if it can't.
+fn unsupported(context: &Context, function: Function) -> Option<String> {
+    let unsupported_type = |ty: Type| {
+        Sort::of_type(context, ty)
+            .is_none()
+            .then(|| ty.as_string(context))
+    };
+    let ret_type = function.get_return_type(context);
+    if let Some(ty) = unsupported_type(ret_type).filter(|_| !ret_type.is_unit(context)) {
+        return Some(format!("it returns a `{ty}`"));
+    }
+    for block in function.block_iter(context) {
+        for arg in block.arg_iter(context) {
+            if let Some(ty) = arg.get_type(context).and_then(unsupported_type) {
+                return Some(format!("it has a `{ty}` argument"));
+            }
+        }
+        let reason = block
+            .instruction_iter(context)
+            .filter_map(|value| value.get_instruction(context))
+            .find_map(|ins| unsupported_instruction(context, ins));
+        if reason.is_some() {
+            return reason;
+        }
+    }
+    None
+}
+
+fn unsupported_instruction(context: &Context, ins: &Instruction) -> Option<String> {
+    let sort = |value: &Value| Sort::of_value(context, *value);
+    let supported = match ins {
+        Instruction::UnaryOp { arg, .. } => matches!(sort(arg), Some(Sort::Bool | Sort::Word)),
+        Instruction::BinaryOp { op, arg1, .. } => match sort(arg1) {
+            Some(Sort::Word) => true,
+            Some(Sort::Bool) => {
+                matches!(op, BinaryOpKind::And | BinaryOpKind::Or | BinaryOpKind::Xor)
+            }
+            _ => false,
+        },
+        Instruction::BitCast(value, ty) => matches!(
+            (sort(value), Sort::of_type(context, *ty)),
+            (Some(Sort::Bool | Sort::Word), Some(Sort::Bool | Sort::Word))
+        ),
+        Instruction::Cmp(Predicate::Equal, lhs, _) => sort(lhs).is_some(),
+        Instruction::Cmp(_, lhs, _) => matches!(sort(lhs), Some(Sort::Word | Sort::B256)),
+        Instruction::Ret(_, ty) if ty.is_unit(context) => return None,
+        Instruction::Branch(_)
+        | Instruction::ConditionalBranch { .. }
+        | Instruction::Nop
+        | Instruction::Ret(..)
+        | Instruction::FuelVm(FuelVmInstruction::Revert(_)) => true,
+        Instruction::Call(callee, _) => {
+            return Some(format!("it calls `{}`", callee.get_name(context)));
+        }
+        Instruction::AsmBlock(..) => return Some("it contains an ASM block".to_string()),
+        Instruction::ContractCall { .. } => return Some("it calls a contract".to_string()),
+        Instruction::FuelVm(_) => {
+            return Some("it uses an instruction specific to the FuelVM".to_string());
+        }
+        Instruction::CastPtr(..)
+        | Instruction::GetElemPtr { .. }
+        | Instruction::GetLocal(_)
+        | Instruction::IntToPtr(..)
+        | Instruction::Load(_)
+        | Instruction::MemCopyBytes { .. }
+        | Instruction::MemCopyVal { .. }
+        | Instruction::PtrToInt(..)
+        | Instruction::Store { .. } => return Some("it accesses memory".to_string()),
+    };
+    let has_unsupported_constant = ins.get_operands().iter().any(|operand| {
+        matches!(
+            operand.get_constant_or_configurable(context),
+            Some(constant) if literal(constant).is_none()
+        )
+    });
+    if !supported || has_unsupported_constant {
+        Some("it computes on unsupported values".to_string())
+    } else {
+        None
+    }
+}
+
+fn function_to_smt(context: &Context, function: Function, check_failures: bool) -> String {
+    let name = function.get_name(context);
+    let mut exporter = Exporter {
+        context,
+        name,
+        definitions: Vec::new(),
+        paths: Vec::new(),
+    };
+
+    let args = function
+        .args_iter(context)
+        .map(|(arg_name, arg)| {
+            let ty = arg.get_type(context).unwrap();
+            format!("{arg_name}: {}", ty.as_string(context))
+        })
+        .collect::<Vec<_>>();
+    let mut smt = format!("; fn {name}({})", args.join(", "));
+    let ret_type = function.get_return_type(context);
+    if !ret_type.is_unit(context) {
+        write!(smt, " -> {}", ret_type.as_string(context)).unwrap();
+    }
+    smt.push('\n');
+
+    let mut entry = State {
+        block: function.get_entry_block(context),
+        blocks: Vec::new(),
+        visits: FxHashMap::default(),
+        conditions: Vec::new(),
+        terms: FxHashMap::default(),
+    };
+    for (arg_name, arg) in function.args_iter(context) {
+        let arg_symbol = exporter.symbol(arg_name);
+        let sort = Sort::of_value(context, *arg).unwrap();
+        writeln!(smt, "(declare-const {arg_symbol} {})", sort.as_smt()).unwrap();
+        entry.terms.insert(*arg, arg_symbol);
+    }
+    exporter.explore(entry);
+
+    for definition in &exporter.definitions {
+        writeln!(smt, "{definition}").unwrap();
+    }
+
+    let mut failures = Vec::new();
+    let mut unexplored = false;
+    for (k, path) in exporter.paths.iter().enumerate() {
+        let path_symbol = exporter.symbol(&format!("p{k}"));
+        let ending = match &path.outcome {
+            Outcome::Return(_) => "returns".to_string(),
+            Outcome::Revert(_) => "reverts".to_string(),
+            Outcome::Panic(reason) => format!("panics on {reason}"),
+            Outcome::Unexplored => "is unexplored".to_string(),
+        };
+        writeln!(smt, "; {path_symbol}: {}; {ending}", path.blocks.join(", ")).unwrap();
+        let condition = match path.conditions.as_slice() {
+            [] => "true".to_string(),
+            [condition] => condition.clone(),
+            conditions => format!("(and {})", conditions.join(" ")),
+        };
+        writeln!(smt, "(define-fun {path_symbol} () Bool {condition})").unwrap();
+        match &path.outcome {
+            Outcome::Return(Some((sort, term))) => {
+                let ret_symbol = exporter.symbol(&format!("p{k}.ret"));
+                writeln!(smt, "(define-fun {ret_symbol} () {} {term})", sort.as_smt()).unwrap();
+            }
+            Outcome::Revert(term) => {
+                let revert_symbol = exporter.symbol(&format!("p{k}.revert"));
+                writeln!(
+                    smt,
+                    "(define-fun {revert_symbol} () {} {term})",
+                    Sort::Word.as_smt()
+                )
+                .unwrap();
+                failures.push(path_symbol);
+            }
+            Outcome::Panic(_) => failures.push(path_symbol),
+            Outcome::Return(None) => (),
+            Outcome::Unexplored => unexplored = true,
+        }
+    }
+
+    if check_failures {
+        let explored = if unexplored {
+            " on the explored paths"
+        } else {
+            ""
+        };
+        if failures.is_empty() {
+            writeln!(smt, "; {name} can't revert or panic{explored}.").unwrap();
+        } else {
+            let failure = match failures.as_slice() {
+                [failure] => failure.clone(),
+                failures => format!("(or {})", failures.join(" ")),
+            };
+            writeln!(smt, "; Can {name} revert or panic{explored}?").unwrap();
+            writeln!(smt, "(push 1)\n(assert {failure})\n(check-sat)\n(pop 1)").unwrap();
+        }
+    }
+    smt
+}
+
+struct Exporter<'a, 'eng> {
+    context: &'a Context<'eng>,
+    /// The name of the exported function, prefixing its symbols.
+    name: &'a str,
+    /// The definitions of the values computed along the explored paths.
+    definitions: Vec<String>,
+    paths: Vec<ExploredPath>,
+}
+
+/// A path through a function, up to the block it's at.
+#[derive(Clone)]
+struct State {
+    block: Block,
+    /// The labels of the blocks the path went through.
+    blocks: Vec<String>,
+    visits: FxHashMap<Block, usize>,
+    conditions: Vec<String>,
+    /// The terms of the values computed along the path.
+    terms: FxHashMap<Value, String>,
+}
+
+struct ExploredPath {
+    blocks: Vec<String>,
+    conditions: Vec<String>,
+    outcome: Outcome,
+}
+
+enum Outcome {
+    Return(Option<(Sort, String)>),
+    Revert(String),
+    Panic(&'static str),
+    Unexplored,
+}
+
+impl Exporter<'_, '_> {
+    fn symbol(&self, suffix: &str) -> String {
+        symbol(format!("{}.{suffix}", self.name))
+    }
+
+    fn explore(&mut self, entry: State) {
+        let mut pending = vec![entry];
+        while let Some(mut state) = pending.pop() {
+            let outcome = self.run(&mut state, &mut pending);
+            self.paths.push(ExploredPath {
+                blocks: state.blocks,
+                conditions: state.conditions,
+                outcome,
+            });
+        }
+    }
+
+    /// Whether there's room for another path besides those explored, those pending and the one
+    /// being explored.
+    fn can_fork(&self, pending: &[State]) -> bool {
+        self.paths.len() + pending.len() + 2 <= MAX_PATHS
+    }
+
+    /// Run `state` until the end of its path, pushing the paths forking from it to `pending`.
+    fn run(&mut self, state: &mut State, pending: &mut Vec<State>) -> Outcome {
+        let context = self.context;
+        'blocks: loop {
+            let visits = state.visits.entry(state.block).or_default();
+            if *visits == MAX_VISITS {
+                return Outcome::Unexplored;
+            }
+            *visits += 1;
+            state.blocks.push(state.block.get_label(context));
+
+            for value in state.block.instruction_iter(context) {
+                let sort = Sort::of_value(context, value);
+                let term = match value.get_instruction(context).unwrap() {
+                    Instruction::UnaryOp { arg, .. } => match sort {
+                        Some(Sort::Bool) => format!("(not {})", self.term(state, *arg)),
+                        _ => format!("(bvnot {})", self.term(state, *arg)),
+                    },
+                    Instruction::BinaryOp { op, arg1, arg2 } => {
+                        let lhs = self.term(state, *arg1);
+                        let rhs = self.term(state, *arg2);
+                        if sort == Some(Sort::Bool) {
+                            let op = match op {
+                                BinaryOpKind::And => "and",
+                                BinaryOpKind::Or => "or",
+                                _ => "xor",
+                            };
+                            format!("({op} {lhs} {rhs})")
+                        } else {
+                            if let Some((reason, panics)) =
+                                panic_condition(context, *op, arg2, &lhs, &rhs)
+                            {
+                                let panics = self.define(Sort::Bool, panics);
+                                if !self.split_off_panic(state, pending, panics, reason) {
+                                    return Outcome::Unexplored;
+                                }
+                            }
+                            format!("({} {lhs} {rhs})", bv_op(*op))
+                        }
+                    }
+                    Instruction::BitCast(arg, _) => {
+                        let arg_term = self.term(state, *arg);
+                        match (Sort::of_value(context, *arg), sort) {
+                            (Some(Sort::Bool), Some(Sort::Word)) => {
+                                format!("(ite {arg_term} (_ bv1 64) (_ bv0 64))")
+                            }
+                            (Some(Sort::Word), Some(Sort::Bool)) => {
+                                format!("(not (= {arg_term} (_ bv0 64)))")
+                            }
+                            _ => {
+                                state.terms.insert(value, arg_term);
+                                continue;
+                            }
+                        }
+                    }
+                    Instruction::Cmp(pred, lhs, rhs) => {
+                        let op = match pred {
+                            Predicate::Equal => "=",
+                            Predicate::LessThan => "bvult",
+                            Predicate::GreaterThan => "bvugt",
+                        };
+                        format!(
+                            "({op} {} {})",
+                            self.term(state, *lhs),
+                            self.term(state, *rhs)
+                        )
+                    }
+                    Instruction::Branch(to_block) => {
+                        self.jump(state, to_block);
+                        continue 'blocks;
+                    }
+                    Instruction::ConditionalBranch {
+                        cond_value,
+                        true_block,
+                        false_block,
+                    } => {
+                        if !self.can_fork(pending) {
+                            return Outcome::Unexplored;
+                        }
+                        let cond = self.term(state, *cond_value);
+                        let mut other = state.clone();
+                        other.conditions.push(format!("(not {cond})"));
+                        self.jump(&mut other, false_block);
+                        pending.push(other);
+                        state.conditions.push(cond);
+                        self.jump(state, true_block);
+                        continue 'blocks;
+                    }
+                    Instruction::Ret(ret_value, ty) => {
+                        return Outcome::Return(
+                            Sort::of_type(context, *ty)
+                                .map(|sort| (sort, self.term(state, *ret_value))),
+                        );
+                    }
+                    Instruction::FuelVm(FuelVmInstruction::Revert(code)) => {
+                        return Outcome::Revert(self.term(state, *code));
+                    }
+                    Instruction::Nop => continue,
+                    _ => unreachable!("Functions with other instructions aren't exported."),
+                };
+                let value_symbol = self.define(sort.unwrap(), term);
+                state.terms.insert(value, value_symbol);
+            }
+            unreachable!("Blocks end with a terminator.");
+        }
+    }
+
+    fn term(&self, state: &State, value: Value) -> String {
+        match value.get_constant_or_configurable(self.context) {
+            Some(constant) => literal(constant).unwrap(),
+            None => state.terms[&value].clone(),
+        }
+    }
+
+    /// Define a new value as `term`, returning its symbol.
+    fn define(&mut self, sort: Sort, term: String) -> String {
+        let value_symbol = self.symbol(&format!("v{}", self.definitions.len()));
+        self.definitions.push(format!(
+            "(define-fun {value_symbol} () {} {term})",
+            sort.as_smt()
+        ));
+        value_symbol
+    }
+
+    /// Bind the arguments of the block branched to, and move `state` to it.
+    fn jump(&self, state: &mut State, to_block: &BranchToWithArgs) {
+        let args = to_block
+            .args
+            .iter()
+            .map(|arg| self.term(state, *arg))
+            .collect::<Vec<_>>();
+        for (param, arg) in to_block.block.arg_iter(self.context).zip(args) {
+            state.terms.insert(*param, arg);
+        }
+        state.block = to_block.block;
+    }
+
+    /// End a path where the VM panics under `panics`, and continue `state` where it doesn't.
+    /// Returns false if there's no room for the new path.
+    fn split_off_panic(
+        &mut self,
+        state: &mut State,
+        pending: &[State],
+        panics: String,
+        reason: &'static str,
+    ) -> bool {
+        if !self.can_fork(pending) {
+            return false;
+        }
+        let mut conditions = state.conditions.clone();
+        conditions.push(panics.clone());
+        self.paths.push(ExploredPath {
+            blocks: state.blocks.clone(),
+            conditions,
+            outcome: Outcome::Panic(reason),
+        });
+        state.conditions.push(format!("(not {panics})"));
+        true
+    }
+}
+
+fn bv_op(op: BinaryOpKind) -> &'static str {
+    match op {
+        BinaryOpKind::Add => "bvadd",
+        BinaryOpKind::Sub => "bvsub",
+        BinaryOpKind::Mul => "bvmul",
+        BinaryOpKind::Div => "bvudiv",
+        BinaryOpKind::And => "bvand",
+        BinaryOpKind::Or => "bvor",
+        BinaryOpKind::Xor => "bvxor",
+        BinaryOpKind::Mod => "bvurem",
+        BinaryOpKind::Rsh => "bvlshr",
+        BinaryOpKind::Lsh => "bvshl",
+    }
+}
+
+/// The condition under which the VM panics computing `op` on words, and why.
+fn panic_condition(
+    context: &Context,
+    op: BinaryOpKind,
+    rhs_value: &Value,
+    lhs: &str,
+    rhs: &str,
+) -> Option<(&'static str, String)> {
+    match op {
+        BinaryOpKind::Add => Some(("an overflow", format!("(bvult (bvadd {lhs} {rhs}) {lhs})"))),
+        BinaryOpKind::Sub => Some(("an overflow", format!("(bvult {lhs} {rhs})"))),
+        BinaryOpKind::Mul => {
+            // The product overflows if its upper word isn't zero.
+            let widen = |word: &str| format!("((_ zero_extend 64) {word})");
+            let product = format!("(bvmul {} {})", widen(lhs), widen(rhs));
+            Some((
+                "an overflow",
+                format!("(not (= ((_ extract 127 64) {product}) (_ bv0 64)))"),
+            ))
+        }
+        BinaryOpKind::Div | BinaryOpKind::Mod => {
+            // Dividing by a constant other than zero can't panic.
+            let divisor = rhs_value
+                .get_constant_or_configurable(context)
+                .map(|constant| &constant.value);
+            match divisor {
+                Some(ConstantValue::Uint(n)) if *n != 0 => None,
+                _ => Some(("a division by zero", format!("(= {rhs} (_ bv0 64))"))),
+            }
+        }
+        BinaryOpKind::And
+        | BinaryOpKind::Or
+        | BinaryOpKind::Xor
+        | BinaryOpKind::Rsh
+        | BinaryOpKind::Lsh => None,
+    }
+}
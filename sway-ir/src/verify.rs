@@ -3,8 +3,11 @@
 //! During creation, deserialization and optimization the IR should be verified to be in a
 //! consistent valid state, using the functions in this module.
 
+use rustc_hash::FxHashMap;
+
 use crate::{
-    block::BlockContent,
+    block::{Block, BlockContent},
+    compute_dominators, compute_post_order,
     context::Context,
     error::IrError,
     function::{Function, FunctionContent},
@@ -51,7 +54,7 @@ impl<'eng> Context<'eng> {
         Ok(self)
     }
 
-    fn verify_module(&self, module: &ModuleContent) -> Result<(), IrError> {
+    pub(crate) fn verify_module(&self, module: &ModuleContent) -> Result<(), IrError> {
         for function in &module.functions {
             self.verify_function(module, function)?;
         }
@@ -74,11 +77,72 @@ impl<'eng> Context<'eng> {
             }
         }
 
-        let function = &self.functions[function.0];
-        for block in &function.blocks {
-            self.verify_block(cur_module, function, &self.blocks[block.0])?;
+        let function_content = &self.functions[function.0];
+        for block in &function_content.blocks {
+            self.verify_block(cur_module, function_content, &self.blocks[block.0])?;
+        }
+        self.verify_dominance(function)?;
+        self.verify_metadata(function_content.metadata)?;
+        Ok(())
+    }
+
+    /// Verify that the values used by each instruction are defined in the same function, before
+    /// they are used.
+    ///
+    /// That is, an instruction can only use the instructions before it in its block, and those
+    /// and the arguments of the blocks which dominate its block.  The uses in blocks which are
+    /// unreachable from the entry block aren't checked, they are never executed.
+    fn verify_dominance(&self, function: &Function) -> Result<(), IrError> {
+        let po = compute_post_order(self, function);
+        let dom_tree = compute_dominators(self, &po, *function);
+        let dominates = |def_block: Block, use_block: Block| {
+            std::iter::successors(Some(use_block), |block| dom_tree[block].parent)
+                .any(|block| block == def_block)
+        };
+
+        // The block and position within it of each instruction in the function.
+        let mut positions = FxHashMap::<Value, (Block, usize)>::default();
+        for block in function.block_iter(self) {
+            for (idx, inst) in block.instruction_iter(self).enumerate() {
+                positions.insert(inst, (block, idx));
+            }
+        }
+
+        for block in po.po_to_block.iter().copied() {
+            for (use_idx, inst) in block.instruction_iter(self).enumerate() {
+                let instruction = inst.get_instruction(self).unwrap();
+                for operand in instruction.get_operands() {
+                    let defined_before_use = match &self.values[operand.0].value {
+                        ValueDatum::Instruction(_) => {
+                            positions
+                                .get(&operand)
+                                .map_or(false, |(def_block, def_idx)| {
+                                    if *def_block == block {
+                                        *def_idx < use_idx
+                                    } else {
+                                        po.block_to_po.contains_key(def_block)
+                                            && dominates(*def_block, block)
+                                    }
+                                })
+                        }
+                        ValueDatum::Argument(BlockArgument {
+                            block: def_block, ..
+                        }) => {
+                            def_block.get_function(self) == *function
+                                && po.block_to_po.contains_key(def_block)
+                                && dominates(*def_block, block)
+                        }
+                        ValueDatum::Configurable(_) | ValueDatum::Constant(_) => true,
+                    };
+                    if !defined_before_use {
+                        return Err(IrError::VerifyUseNotDominatedByDefinition(
+                            function.get_name(self).to_string(),
+                            block.get_label(self),
+                        ));
+                    }
+                }
+            }
         }
-        self.verify_metadata(function.metadata)?;
         Ok(())
     }
 
@@ -1,11 +1,13 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use sway_ir::{
-    create_arg_demotion_pass, create_const_combine_pass, create_const_demotion_pass,
-    create_dce_pass, create_dom_fronts_pass, create_dominators_pass, create_escaped_symbols_pass,
-    create_mem2reg_pass, create_memcpyopt_pass, create_misc_demotion_pass, create_postorder_pass,
-    create_ret_demotion_pass, create_simplify_cfg_pass, optimize as opt, Context, PassGroup,
-    PassManager,
+    create_adce_pass, create_arg_demotion_pass, create_const_combine_pass,
+    create_const_demotion_pass, create_cse_pass, create_dce_pass, create_dom_fronts_pass,
+    create_dominators_pass, create_escaped_symbols_pass, create_fn_dedup_pass,
+    create_heap2stack_pass, create_inline_in_module_pass, create_mem2reg_pass,
+    create_memcpyopt_pass, create_misc_demotion_pass, create_outline_pass, create_postorder_pass,
+    create_ret_demotion_pass, create_simplify_cfg_pass, create_sroa_pass, optimize as opt, Context,
+    IrError, PassGroup, PassManager,
 };
 use sway_types::SourceEngine;
 
@@ -42,26 +44,30 @@ fn run_tests<F: Fn(&str, &mut Context) -> bool>(sub_dir: &str, opt_fn: F) {
 
         let output = sway_ir::printer::to_string(&ir);
 
-        let chkr = filecheck::CheckerBuilder::new()
-            .text(&input)
-            .unwrap()
-            .finish();
-        if chkr.is_empty() {
-            println!("{output}");
-            panic!("No filecheck directives found in test: {}", path.display());
-        }
+        check_output(&path, &input, &output);
+    }
+}
 
-        match chkr.explain(&output, filecheck::NO_VARIABLES) {
-            Ok((success, report)) if !success => {
-                println!("--- FILECHECK FAILED FOR {}", path.display());
-                println!("{report}");
-                panic!()
-            }
-            Err(e) => {
-                panic!("filecheck directive error while checking: {e}");
-            }
-            _ => (),
+fn check_output(path: &Path, input: &str, output: &str) {
+    let chkr = filecheck::CheckerBuilder::new()
+        .text(input)
+        .unwrap()
+        .finish();
+    if chkr.is_empty() {
+        println!("{output}");
+        panic!("No filecheck directives found in test: {}", path.display());
+    }
+
+    match chkr.explain(output, filecheck::NO_VARIABLES) {
+        Ok((success, report)) if !success => {
+            println!("--- FILECHECK FAILED FOR {}", path.display());
+            println!("{report}");
+            panic!()
         }
+        Err(e) => {
+            panic!("filecheck directive error while checking: {e}");
+        }
+        _ => (),
     }
 }
 
@@ -119,6 +125,31 @@ fn inline() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[test]
+fn inline_module() {
+    run_tests("inline_module", |first_line, ir: &mut Context| {
+        // The inliner's parameters are given on the first line, e.g. `// max_depth 1 instrs 4`.
+        let params = first_line.split(' ').skip(1).collect::<Vec<_>>();
+        for param_and_arg in params.windows(2) {
+            match param_and_arg[0] {
+                "instrs" => ir.inline_params.max_instrs = param_and_arg[1].parse().unwrap(),
+                "blocks" => ir.inline_params.max_blocks = param_and_arg[1].parse().ok(),
+                "max_depth" => ir.inline_params.max_depth = param_and_arg[1].parse().ok(),
+                "inline_all" => ir.inline_params.inline_all = param_and_arg[1].parse().unwrap(),
+                _ => (),
+            }
+        }
+
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_inline_in_module_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 // Clippy suggests using the map iterator below directly instead of collecting from it first, but
 // if we try that then we have borrowing issues with `ir` which is used within the closure.
 #[allow(clippy::needless_collect)]
@@ -135,6 +166,24 @@ fn constants() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[test]
+fn cse() {
+    run_tests("cse", |first_line, ir: &mut Context| {
+        // Storage reads are only deduplicated if the first line is `// storage_reads`.
+        ir.cse_storage_reads = first_line == "// storage_reads";
+
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_postorder_pass());
+        pass_mgr.register(create_dominators_pass());
+        let pass = pass_mgr.register(create_cse_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[allow(clippy::needless_collect)]
 #[test]
 fn simplify_cfg() {
@@ -164,6 +213,20 @@ fn dce() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[allow(clippy::needless_collect)]
+#[test]
+fn adce() {
+    run_tests("adce", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_adce_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[allow(clippy::needless_collect)]
 #[test]
 fn mem2reg() {
@@ -181,6 +244,48 @@ fn mem2reg() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[allow(clippy::needless_collect)]
+#[test]
+fn sroa() {
+    run_tests("sroa", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_sroa_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn fn_dedup() {
+    run_tests("fn_dedup", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_fn_dedup_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn outline() {
+    run_tests("outline", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_outline_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[allow(clippy::needless_collect)]
 #[test]
 fn demote_arg() {
@@ -252,6 +357,21 @@ fn memcpyopt() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[allow(clippy::needless_collect)]
+#[test]
+fn heap_to_stack() {
+    run_tests("heap_to_stack", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_escaped_symbols_pass());
+        let pass = pass_mgr.register(create_heap2stack_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[test]
 fn serialize() {
     // This isn't running a pass, it's just confirming that the IR can be loaded and printed, and
@@ -260,3 +380,77 @@ fn serialize() {
 }
 
 // -------------------------------------------------------------------------------------------------
+
+#[test]
+fn smt() {
+    // The SMT-LIB 2 exported for each module is checked rather than the IR.
+    let source_engine = SourceEngine::default();
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let dir: PathBuf = format!("{manifest_dir}/tests/smt").into();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let input = std::fs::read_to_string(&path).unwrap();
+        let ir = sway_ir::parser::parse(&input, &source_engine).unwrap();
+
+        let output = ir
+            .module_iter()
+            .map(|module| sway_ir::module_to_smt(&ir, module))
+            .collect::<String>();
+
+        check_output(&path, &input, &output);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn verify_dominance() {
+    // Values used in blocks which their definitions don't dominate are rejected.
+    let source_engine = SourceEngine::default();
+    let invalid_irs = [
+        "script {
+            fn main(n: u64) -> u64 {
+                entry(n: u64):
+                one = const u64 1
+                cond = cmp lt n one
+                cbr cond, block0(), block1()
+
+                block0():
+                v0 = add n, one
+                br block2()
+
+                block1():
+                br block2()
+
+                block2():
+                ret u64 v0
+            }
+        }",
+        "script {
+            fn main(n: u64) -> u64 {
+                entry(n: u64):
+                one = const u64 1
+                cond = cmp lt n one
+                cbr cond, block0(n), block1()
+
+                block0(x: u64):
+                br block2()
+
+                block1():
+                br block2()
+
+                block2():
+                ret u64 x
+            }
+        }",
+    ];
+    for ir in invalid_irs {
+        match sway_ir::parser::parse(ir, &source_engine) {
+            Err(IrError::VerifyUseNotDominatedByDefinition(..)) => (),
+            Err(err) => panic!("Unexpected error: {err}"),
+            Ok(_) => panic!("Invalid IR was verified:\n{ir}"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -238,12 +238,7 @@ impl Eq for u8 {
 
 impl Eq for b256 {
     fn eq(self, other: Self) -> bool {
-        // Both self and other are addresses of the values, so we can use MEQ.
-        asm(r1: self, r2: other, r3, r4) {
-            addi r3 zero i32;
-            meq r4 r1 r2 r3;
-            r4: bool
-        }
+        __memcmp(__addr_of(self), __addr_of(other), 32)
     }
 }
 
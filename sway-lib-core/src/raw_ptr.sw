@@ -31,10 +31,7 @@ impl raw_ptr {
 
     /// Copies `count * size_of<T>` bytes from `self` to `dst`.
     pub fn copy_to<T>(self, dst: Self, count: u64) {
-        let len = __mul(count, __size_of::<T>());
-        asm(dst: dst, src: self, len: len) {
-            mcp dst src len;
-        };
+        __memcpy(dst, self, __mul(count, __size_of::<T>()));
     }
 
     /// Writes the given value to the address.
@@ -68,9 +65,7 @@ impl raw_ptr {
 
     /// Copies `count` bytes from `self` to `dst`
     pub fn copy_bytes_to(self, dst: Self, count: u64) {
-        asm(dst: dst, src: self, len: count) {
-            mcp dst src len;
-        };
+        __memcpy(dst, self, count);
     }
 
     /// Add a u64 offset to a raw_ptr
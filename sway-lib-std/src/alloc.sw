@@ -35,16 +35,12 @@ pub fn alloc<T>(count: u64) -> raw_ptr {
 }
 
 /// Reallocates the given area of memory.
+///
+/// If `new_count` is larger than `count`, the `count` values at `ptr` are copied to a new
+/// allocation for `new_count` values, otherwise `ptr` is returned as is.
 pub fn realloc<T>(ptr: raw_ptr, count: u64, new_count: u64) -> raw_ptr {
-    if new_count > count {
-        let new_ptr = alloc::<T>(new_count);
-        if count > 0 {
-            ptr.copy_to::<T>(new_ptr, count);
-        }
-        new_ptr
-    } else {
-        ptr
-    }
+    let size = __size_of::<T>();
+    __realloc(ptr, size * count, size * new_count)
 }
 
 /// Allocates zeroed memory on the heap in individual bytes.
@@ -58,13 +54,21 @@ pub fn alloc_bytes(count: u64) -> raw_ptr {
 
 /// Reallocates the given area of memory in individual bytes.
 pub fn realloc_bytes(ptr: raw_ptr, count: u64, new_count: u64) -> raw_ptr {
-    if new_count > count {
-        let new_ptr = alloc_bytes(new_count);
-        if count > 0 {
-            ptr.copy_bytes_to(new_ptr, count);
-        }
-        new_ptr
-    } else {
-        ptr
-    }
+    __realloc(ptr, count, new_count)
+}
+
+/// Returns the current heap pointer(`$hp`) and the number of bytes allocated on the heap so far.
+///
+/// ### Examples
+///
+/// ```sway
+/// use std::alloc::{alloc_bytes, heap_stats};
+///
+/// let (_, before) = heap_stats();
+/// let _ptr = alloc_bytes(8);
+/// let (_, after) = heap_stats();
+/// assert(after - before == 8);
+/// ```
+pub fn heap_stats() -> (raw_ptr, u64) {
+    __heap_stats()
 }
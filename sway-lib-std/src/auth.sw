@@ -4,7 +4,7 @@ library;
 use ::address::Address;
 use ::contract_id::ContractId;
 use ::identity::Identity;
-use ::option::Option::{self, *};
+use ::option::Option::*;
 use ::result::Result::{self, *};
 use ::inputs::{Input, input_count, input_owner, input_type};
 
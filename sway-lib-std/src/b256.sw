@@ -4,7 +4,6 @@ use ::assert::assert;
 use ::bytes::Bytes;
 use ::convert::TryFrom;
 use ::option::Option::{self, *};
-use ::logging::log;
 
 impl TryFrom<Bytes> for b256 {
     fn try_from(b: Bytes) -> Option<Self> {
@@ -4,7 +4,6 @@ library;
 use ::assert::assert;
 use ::constants::ZERO_B256;
 use ::result::Result::{self, *};
-use ::logging::log;
 
 enum BlockHashError {
     BlockHeightTooHigh: (),
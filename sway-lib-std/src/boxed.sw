@@ -0,0 +1,73 @@
+//! A pointer type for heap allocation.
+library;
+
+use ::alloc::alloc;
+
+/// A value of type `T` allocated on the heap.
+///
+/// A `Box<T>` is a single word pointing at its value, regardless of the size of `T`.
+pub struct Box<T> {
+    ptr: raw_ptr,
+}
+
+impl<T> Box<T> {
+    /// Allocates memory on the heap and moves `value` into it.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to allocate on the heap.
+    ///
+    /// ### Examples
+    ///
+    /// ```sway
+    /// use std::boxed::Box;
+    ///
+    /// let five = Box::new(5);
+    /// assert(five.get() == 5);
+    /// ```
+    pub fn new(value: T) -> Self {
+        let ptr = alloc::<T>(1);
+        ptr.write::<T>(value);
+        Self { ptr }
+    }
+
+    /// Returns the value stored on the heap.
+    ///
+    /// ### Examples
+    ///
+    /// ```sway
+    /// use std::boxed::Box;
+    ///
+    /// let five = Box::new(5);
+    /// assert(five.get() == 5);
+    /// ```
+    pub fn get(self) -> T {
+        self.ptr.read::<T>()
+    }
+
+    /// Replaces the value stored on the heap with `value`.
+    ///
+    /// Every copy of the box points at the same allocation and observes the new value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value.
+    ///
+    /// ### Examples
+    ///
+    /// ```sway
+    /// use std::boxed::Box;
+    ///
+    /// let five = Box::new(5);
+    /// five.set(6);
+    /// assert(five.get() == 6);
+    /// ```
+    pub fn set(self, value: T) {
+        self.ptr.write::<T>(value);
+    }
+
+    /// Returns the pointer to the value on the heap.
+    pub fn ptr(self) -> raw_ptr {
+        self.ptr
+    }
+}
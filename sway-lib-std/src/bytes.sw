@@ -608,10 +608,7 @@ impl core::ops::Eq for Bytes {
             return false;
         }
 
-        asm(result, r2: self.buf.ptr, r3: other.buf.ptr, r4: self.len) {
-            meq result r2 r3 r4;
-            result: bool
-        }
+        __memcmp(self.buf.ptr, other.buf.ptr, self.len)
     }
 }
 
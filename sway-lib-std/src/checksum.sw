@@ -0,0 +1,148 @@
+//! Checksummed hexadecimal encoding of 32 byte values such as addresses and contract IDs.
+//!
+//! The checksum is carried by the case of the letters: a letter is uppercase when the matching
+//! nibble of the `SHA-2-256` hash of the lowercase hexadecimal string (without the `0x` prefix)
+//! is greater than 7. This is the same format `forc` prints and accepts.
+library;
+
+use ::bytes::Bytes;
+use ::convert::From;
+use ::option::Option::{self, *};
+use ::string::String;
+
+/// Returns the `0x` prefixed, checksummed hexadecimal representation of `value`.
+///
+/// # Arguments
+///
+/// * `value` - The address, contract ID or other 32 byte value to encode.
+///
+/// ### Examples
+///
+/// ```sway
+/// use std::{checksum::checksum_encode, constants::ZERO_B256};
+///
+/// let encoded = checksum_encode(ZERO_B256);
+/// ```
+pub fn checksum_encode(value: b256) -> String {
+    let hex = lowercase_hex(value);
+    let hash = Bytes::from(hex.sha256());
+
+    let mut encoded = Bytes::with_capacity(66);
+    encoded.push(48u8);
+    encoded.push(120u8);
+    let mut i = 0;
+    while i < 64 {
+        let c = hex.get(i).unwrap();
+        let hash_byte = hash.get(i / 2).unwrap();
+        let nibble = if i % 2 == 0 {
+            hash_byte >> 4
+        } else {
+            hash_byte & 15u8
+        };
+        if c >= 97u8 && nibble > 7u8 {
+            encoded.push(c - 32u8);
+        } else {
+            encoded.push(c);
+        }
+        i += 1;
+    }
+    String::from_ascii(encoded)
+}
+
+/// Parses a hexadecimal representation of a 32 byte value, with or without the `0x` prefix.
+///
+/// All lowercase and all uppercase strings are accepted as is, while mixed case strings must
+/// carry a valid checksum as produced by `checksum_encode`.
+///
+/// # Arguments
+///
+/// * `s` - The string to parse.
+///
+/// # Returns
+///
+/// * [Option<b256>] - `None` if `s` is not 64 hexadecimal characters or its checksum is invalid.
+///
+/// ### Examples
+///
+/// ```sway
+/// use std::{checksum::{checksum_decode, checksum_encode}, constants::ZERO_B256};
+///
+/// let encoded = checksum_encode(ZERO_B256);
+/// assert(checksum_decode(encoded).unwrap() == ZERO_B256);
+/// ```
+pub fn checksum_decode(s: String) -> Option<b256> {
+    let chars = s.as_bytes();
+    let len = chars.len();
+    let mut start = 0;
+    if len == 66 && chars.get(0).unwrap() == 48u8 && chars.get(1).unwrap() == 120u8 {
+        start = 2;
+    }
+    if len - start != 64 {
+        return None;
+    }
+
+    let mut bytes = Bytes::with_capacity(32);
+    let mut has_lowercase = false;
+    let mut has_uppercase = false;
+    let mut i = 0;
+    while i < 64 {
+        let high = chars.get(start + i).unwrap();
+        let low = chars.get(start + i + 1).unwrap();
+        let high_value = hex_value(high);
+        let low_value = hex_value(low);
+        if high_value.is_none() || low_value.is_none() {
+            return None;
+        }
+        has_lowercase = has_lowercase || high >= 97u8 || low >= 97u8;
+        has_uppercase = has_uppercase || (high >= 65u8 && high <= 70u8) || (low >= 65u8 && low <= 70u8);
+        bytes.push((high_value.unwrap() << 4) | low_value.unwrap());
+        i += 2;
+    }
+    let value: b256 = bytes.into();
+
+    if has_lowercase && has_uppercase {
+        let expected = checksum_encode(value).as_bytes();
+        let mut i = 0;
+        while i < 64 {
+            if expected.get(i + 2).unwrap() != chars.get(start + i).unwrap() {
+                return None;
+            }
+            i += 1;
+        }
+    }
+    Some(value)
+}
+
+/// Returns the lowercase hexadecimal ASCII representation of `value`, without a prefix.
+fn lowercase_hex(value: b256) -> Bytes {
+    let bytes = Bytes::from(value);
+    let mut hex = Bytes::with_capacity(64);
+    let mut i = 0;
+    while i < 32 {
+        let byte = bytes.get(i).unwrap();
+        hex.push(hex_digit(byte >> 4));
+        hex.push(hex_digit(byte & 15u8));
+        i += 1;
+    }
+    hex
+}
+
+fn hex_digit(nibble: u8) -> u8 {
+    if nibble < 10u8 {
+        nibble + 48u8
+    } else {
+        nibble + 87u8
+    }
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    if c >= 48u8 && c <= 57u8 {
+        Some(c - 48u8)
+    } else if c >= 97u8 && c <= 102u8 {
+        Some(c - 87u8)
+    } else if c >= 65u8 && c <= 70u8 {
+        Some(c - 55u8)
+    } else {
+        None
+    }
+}
@@ -1,6 +1,7 @@
 //! Functionality for accessing context-specific information about the current contract or message.
 library;
 
+use ::assert::assert_eq;
 use ::contract_id::ContractId;
 use ::call_frames::contract_id;
 use ::registers::balance;
@@ -18,6 +19,28 @@ pub fn balance_of(asset_id: ContractId, target: ContractId) -> u64 {
     }
 }
 
+/// Asserts that the contract at `target` holds exactly `expected` coins of `asset_id`, e.g. in a
+/// test checking the effect of a contract call on balances.
+///
+/// ### Reverts
+///
+/// Reverts when the balance differs from `expected`, logging the balance and then `expected`.
+///
+/// ### Examples
+///
+/// ```sway
+/// #[test]
+/// fn test_mint() {
+///     let token = abi(Token, CONTRACT_ID);
+///     token.mint(100);
+///     let contract_id = ContractId::from(CONTRACT_ID);
+///     assert_balance(contract_id, contract_id, 100);
+/// }
+/// ```
+pub fn assert_balance(target: ContractId, asset_id: ContractId, expected: u64) {
+    assert_eq(balance_of(asset_id, target), expected);
+}
+
 /// Get the amount of units of `call_frames::msg_asset_id()` being sent.
 pub fn msg_amount() -> u64 {
     balance()
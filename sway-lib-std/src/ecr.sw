@@ -3,7 +3,6 @@ library;
 
 use ::address::Address;
 use ::b512::B512;
-use ::registers::error;
 use ::hash::sha256;
 use ::result::Result::{self, *};
 
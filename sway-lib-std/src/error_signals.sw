@@ -16,3 +16,23 @@ pub const FAILED_ASSERT_EQ_SIGNAL = 0xffff_ffff_ffff_0003;
 /// Revert with this value for a failing call to `std::assert::assert`.
 /// 18446744073709486084
 pub const FAILED_ASSERT_SIGNAL = 0xffff_ffff_ffff_0004;
+
+/// Revert with this value for a call to `std::revert::revert_with`.
+/// 18446744073709486085
+pub const FAILED_REVERT_WITH_SIGNAL = 0xffff_ffff_ffff_0005;
+
+/// Revert with this value for a `#[requires]` condition which doesn't hold when a function is called.
+/// 18446744073709486086
+pub const FAILED_REQUIRES_SIGNAL = 0xffff_ffff_ffff_0006;
+
+/// Revert with this value for an `#[ensures]` condition which doesn't hold when a function returns.
+/// 18446744073709486087
+pub const FAILED_ENSURES_SIGNAL = 0xffff_ffff_ffff_0007;
+
+/// Revert with this value for an argument of an ABI method outside the bounds of its `#[param]` attribute.
+/// 18446744073709486088
+pub const FAILED_PARAM_SIGNAL = 0xffff_ffff_ffff_0008;
+
+/// Revert with this value for a checked arithmetic operation which overflows.
+/// 18446744073709486089
+pub const FAILED_OVERFLOW_SIGNAL = 0xffff_ffff_ffff_0009;
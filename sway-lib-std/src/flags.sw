@@ -1,7 +1,7 @@
 //! Functionality for setting and unsetting FuelVM flags to modify behavior related to the `$err` and `$of` registers.
 library;
 
-use ::{assert::assert, logging::log, registers::{flags, error}};
+use ::{assert::assert, registers::{flags, error}};
 
 // Mask second bit, which is `F_WRAPPING`.
 const F_WRAPPING_DISABLE_MASK: u64 = 0b00000000_00000000_00000000_00000000_00000000_00000000_00000000_00000010;
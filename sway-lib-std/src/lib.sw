@@ -10,6 +10,7 @@ pub mod convert;
 pub mod intrinsics;
 pub mod assert;
 pub mod alloc;
+pub mod boxed;
 pub mod contract_id;
 pub mod constants;
 pub mod external;
@@ -25,6 +26,7 @@ pub mod identity;
 pub mod vec;
 pub mod bytes;
 pub mod string;
+pub mod checksum;
 pub mod r#storage;
 pub mod b256;
 pub mod inputs;
@@ -40,5 +42,7 @@ pub mod u256;
 pub mod message;
 pub mod prelude;
 pub mod low_level_call;
+pub mod test_utils;
+pub mod pausable;
 
 use core::*;
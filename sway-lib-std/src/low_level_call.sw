@@ -1,10 +1,8 @@
 //! Utilities to help with low level calls.
 library;
 
-use ::assert::assert;
 use ::bytes::Bytes;
 use ::contract_id::ContractId;
-use ::option::Option;
 use ::revert::require;
 use ::vec::Vec;
 
@@ -1,10 +1,7 @@
 //! Helper functions to sign and send messages.
 library;
 
-use ::alloc::alloc_bytes;
 use ::bytes::Bytes;
-use ::outputs::{Output, output_count, output_type};
-use ::revert::revert;
 
 /// Sends a message `msg_data` to `recipient` with a `coins` amount of the base asset.
 ///
@@ -0,0 +1,120 @@
+//! Functionality for pausing and unpausing a contract.
+//!
+//! The paused flag of a contract is kept in a dedicated storage slot, so that contracts can be
+//! made pausable without declaring it in their `storage` block. ABI methods annotated with
+//! `#[when_not_paused]` revert while the contract is paused.
+library;
+
+use ::auth::msg_sender;
+use ::identity::Identity;
+use ::logging::log;
+use ::revert::require;
+use ::storage::storage_api::{read, write};
+
+/// The storage slot of the paused flag, `sha256("std::pausable::paused")`.
+const PAUSED_SLOT: b256 = 0x4570a0f1091e5d942fc03d390d24a3defe91400e3381d6daecb09cbdb23be5c4;
+
+/// The errors logged when the contract isn't in the state an operation requires.
+pub enum PauseError {
+    /// The contract is paused.
+    Paused: (),
+    /// The contract is not paused.
+    NotPaused: (),
+}
+
+/// Logged when the contract is paused, with the identity that paused it.
+pub struct Paused {
+    account: Identity,
+}
+
+/// Logged when the contract is unpaused, with the identity that unpaused it.
+pub struct Unpaused {
+    account: Identity,
+}
+
+/// The ABI of pausable contracts.
+///
+/// The library leaves it to the implementations to check who may pause and unpause the contract,
+/// before calling `_pause` and `_unpause`.
+///
+/// ### Examples
+///
+/// ```sway
+/// use std::pausable::*;
+///
+/// impl Pausable for Contract {
+///     #[storage(read, write)]
+///     fn pause() {
+///         require(msg_sender().unwrap() == storage.admin.read(), "not the admin");
+///         _pause();
+///     }
+///
+///     #[storage(read, write)]
+///     fn unpause() {
+///         require(msg_sender().unwrap() == storage.admin.read(), "not the admin");
+///         _unpause();
+///     }
+///
+///     #[storage(read)]
+///     fn is_paused() -> bool {
+///         _is_paused()
+///     }
+/// }
+/// ```
+abi Pausable {
+    #[storage(read, write)]
+    fn pause();
+    #[storage(read, write)]
+    fn unpause();
+    #[storage(read)]
+    fn is_paused() -> bool;
+}
+
+/// Returns whether the contract is paused.
+#[storage(read)]
+pub fn _is_paused() -> bool {
+    read::<bool>(PAUSED_SLOT, 0).unwrap_or(false)
+}
+
+/// Pauses the contract and logs `Paused`.
+///
+/// ### Reverts
+///
+/// Reverts, logging `PauseError::Paused`, when the contract is already paused.
+#[storage(read, write)]
+pub fn _pause() {
+    require_not_paused();
+    write(PAUSED_SLOT, 0, true);
+    log(Paused {
+        account: msg_sender().unwrap(),
+    });
+}
+
+/// Unpauses the contract and logs `Unpaused`.
+///
+/// ### Reverts
+///
+/// Reverts, logging `PauseError::NotPaused`, when the contract is not paused.
+#[storage(read, write)]
+pub fn _unpause() {
+    require_paused();
+    write(PAUSED_SLOT, 0, false);
+    log(Unpaused {
+        account: msg_sender().unwrap(),
+    });
+}
+
+/// Reverts, logging `PauseError::Paused`, when the contract is paused.
+///
+/// This is the guard the compiler inserts at the start of the ABI methods annotated with
+/// `#[when_not_paused]`.
+#[storage(read)]
+pub fn require_not_paused() {
+    require(!_is_paused(), PauseError::Paused);
+}
+
+/// Reverts, logging `PauseError::NotPaused`, when the contract is not paused.
+#[storage(read)]
+pub fn require_paused() {
+    require(_is_paused(), PauseError::NotPaused);
+}
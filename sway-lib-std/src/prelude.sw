@@ -19,7 +19,7 @@ use ::vec::Vec;
 use ::assert::{assert, assert_eq};
 use ::option::Option::{self, *};
 use ::result::Result::{self, *};
-use ::revert::{require, revert};
+use ::revert::{require, revert, revert_with};
 
 // Convert
 use ::convert::From;
@@ -2,7 +2,7 @@
 library;
 
 use ::logging::log;
-use ::error_signals::FAILED_REQUIRE_SIGNAL;
+use ::error_signals::{FAILED_REQUIRE_SIGNAL, FAILED_REVERT_WITH_SIGNAL};
 
 /// Will either panic or revert with a given number depending on the context.
 /// If used in a predicate, it will panic.
@@ -60,3 +60,35 @@ pub fn require<T>(condition: bool, value: T) {
         revert(FAILED_REQUIRE_SIGNAL)
     }
 }
+
+/// Logs `error` and reverts with `FAILED_REVERT_WITH_SIGNAL`.
+///
+/// This is the preferred way of failing with a typed error, usually a variant of an error enum.
+/// The type of `error` is recorded among the logged types of the JSON ABI, so SDKs can decode the
+/// logged value when they see a revert with `FAILED_REVERT_WITH_SIGNAL`.
+///
+/// ### Arguments
+///
+/// * `error` - The error to log before reverting.
+///
+/// ### Reverts
+///
+/// Always reverts, in contracts and scripts alike. Panics when called in a predicate.
+///
+/// ### Examples
+///
+/// ```sway
+/// enum TransferError {
+///     InsufficientBalance: u64,
+/// }
+///
+/// fn transfer(amount: u64, balance: u64) {
+///     if amount > balance {
+///         revert_with(TransferError::InsufficientBalance(balance));
+///     }
+/// }
+/// ```
+pub fn revert_with<E>(error: E) {
+    log(error);
+    revert(FAILED_REVERT_WITH_SIGNAL)
+}
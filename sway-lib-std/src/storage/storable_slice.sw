@@ -1,6 +1,6 @@
 library;
 
-use ::alloc::{alloc, alloc_bytes, realloc_bytes};
+use ::alloc::{alloc_bytes, realloc_bytes};
 use ::hash::sha256;
 use ::option::Option::{self, *};
 use ::storage::storage_api::*;
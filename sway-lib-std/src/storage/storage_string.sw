@@ -1,6 +1,5 @@
 library;
 
-use ::bytes::Bytes;
 use ::option::Option;
 use ::storage::storable_slice::*;
 use ::storage::storage_api::read;
@@ -2,7 +2,6 @@ library;
 
 use ::bytes::Bytes;
 use ::convert::From;
-use ::option::Option;
 use ::assert::assert;
 
 /// A UTF-8 encoded growable string
@@ -0,0 +1,203 @@
+//! Deterministic pseudo-random test data.
+//!
+//! Everything in this module is only available in test builds, i.e. when running `forc test`.
+//! The same seed always produces the same data, so a failing test can be reproduced.
+library;
+
+use ::address::Address;
+use ::assert::assert;
+use ::contract_id::{AssetId, ContractId};
+use ::convert::From;
+use ::logging::log;
+
+/// Logged by `assert_snapshot` ahead of the value it asserts, so that `forc test` can tell the
+/// value apart from other logs.
+/// 18446744073709490176
+pub const SNAPSHOT_SIGNAL = 0xffff_ffff_ffff_1000;
+
+/// A seeded pseudo-random number generator, based on xorshift64.
+///
+/// It is not cryptographically secure and must only be used to generate test data.
+///
+/// ### Examples
+///
+/// ```sway
+/// use std::test_utils::Rng;
+///
+/// #[test]
+/// fn test_with_random_data() {
+///     let mut rng = Rng::new(42);
+///     let amount = rng.next_u64_in(1, 1000);
+///     let recipient = rng.next_address();
+/// }
+/// ```
+#[cfg(test)]
+pub struct Rng {
+    state: u64,
+}
+
+#[cfg(test)]
+impl Rng {
+    /// Create a generator from `seed`.  A seed of `0` is replaced by a fixed non-zero seed, as
+    /// xorshift would only ever produce zeros from it.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 { 0x9e3779b97f4a7c15 } else { seed },
+        }
+    }
+
+    /// The next pseudo-random `u64`.
+    pub fn next_u64(ref mut self) -> u64 {
+        let mut x = self.state;
+        x = x ^ (x << 13);
+        x = x ^ (x >> 7);
+        x = x ^ (x << 17);
+        self.state = x;
+        x
+    }
+
+    /// The next pseudo-random `u64` in the range `[min, max)`.
+    ///
+    /// ### Reverts
+    ///
+    /// * When `max` is not greater than `min`.
+    pub fn next_u64_in(ref mut self, min: u64, max: u64) -> u64 {
+        assert(min < max);
+        min + self.next_u64() % (max - min)
+    }
+
+    /// The next pseudo-random `bool`.
+    pub fn next_bool(ref mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// The next pseudo-random `b256`.
+    pub fn next_b256(ref mut self) -> b256 {
+        let words = (self.next_u64(), self.next_u64(), self.next_u64(), self.next_u64());
+        asm(r1: words) { r1: b256 }
+    }
+
+    /// The next pseudo-random `Address`.
+    pub fn next_address(ref mut self) -> Address {
+        Address::from(self.next_b256())
+    }
+
+    /// The next pseudo-random `ContractId`.
+    pub fn next_contract_id(ref mut self) -> ContractId {
+        ContractId::from(self.next_b256())
+    }
+
+    /// The next pseudo-random `AssetId`.
+    pub fn next_asset_id(ref mut self) -> AssetId {
+        AssetId::from(self.next_b256())
+    }
+}
+
+/// Types whose values can be filled in with pseudo-random data.
+///
+/// Implement it for the structs of a program to generate them from an `Rng`, by filling in each
+/// of their fields.
+///
+/// ### Examples
+///
+/// ```sway
+/// use std::test_utils::{Random, Rng};
+///
+/// struct Order {
+///     owner: Address,
+///     amount: u64,
+/// }
+///
+/// impl Random for Order {
+///     fn random(ref mut rng: Rng) -> Self {
+///         Self {
+///             owner: Address::random(rng),
+///             amount: u64::random(rng),
+///         }
+///     }
+/// }
+/// ```
+#[cfg(test)]
+pub trait Random {
+    /// A value filled in with data from `rng`.
+    fn random(ref mut rng: Rng) -> Self;
+}
+
+#[cfg(test)]
+impl Random for u8 {
+    fn random(ref mut rng: Rng) -> Self {
+        asm(r1: rng.next_u64() & 0xff) { r1: u8 }
+    }
+}
+
+#[cfg(test)]
+impl Random for u64 {
+    fn random(ref mut rng: Rng) -> Self {
+        rng.next_u64()
+    }
+}
+
+#[cfg(test)]
+impl Random for bool {
+    fn random(ref mut rng: Rng) -> Self {
+        rng.next_bool()
+    }
+}
+
+#[cfg(test)]
+impl Random for b256 {
+    fn random(ref mut rng: Rng) -> Self {
+        rng.next_b256()
+    }
+}
+
+#[cfg(test)]
+impl Random for Address {
+    fn random(ref mut rng: Rng) -> Self {
+        rng.next_address()
+    }
+}
+
+#[cfg(test)]
+impl Random for ContractId {
+    fn random(ref mut rng: Rng) -> Self {
+        rng.next_contract_id()
+    }
+}
+
+/// Asserts that `value` matches its snapshot, stored in the `tests/snapshots` directory of the
+/// package.
+///
+/// The value is logged, and `forc test` decodes it with the ABI of the package and compares it
+/// against the snapshot file, failing the test if they differ or if there is no snapshot yet.
+/// The first snapshot of a test `my_test` is stored in `my_test.snap`, and the following ones in
+/// `my_test-2.snap`, `my_test-3.snap` and so on.  Run `forc test --update-goldens` to store the
+/// values of the missing and mismatching snapshots.
+///
+/// ### Arguments
+///
+/// * `value` - The value to compare against its snapshot.
+///
+/// ### Examples
+///
+/// ```sway
+/// use std::test_utils::assert_snapshot;
+///
+/// struct Order {
+///     amount: u64,
+///     filled: bool,
+/// }
+///
+/// #[test]
+/// fn test_order() {
+///     let order = Order { amount: 42, filled: false };
+///     assert_snapshot(order);
+/// }
+/// ```
+#[cfg(test)]
+pub fn assert_snapshot<T>(value: T) {
+    asm(r1: SNAPSHOT_SIGNAL) {
+        log r1 zero zero zero;
+    }
+    log(value);
+}
@@ -1,7 +1,6 @@
 //! A wrapper around the `b256` type to help enhance type-safety.
 library;
 
-use ::intrinsics::size_of_val;
 use ::convert::From;
 
 /// The `EvmAddress` type, a struct wrapper around the inner `b256` value.
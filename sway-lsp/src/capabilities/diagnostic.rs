@@ -1,4 +1,5 @@
-use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Position, Range};
+use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, NumberOrString, Position, Range};
+use serde_json::{json, Value};
 use sway_error::warning::CompileWarning;
 use sway_error::{error::CompileError, warning::Warning};
 use sway_types::{LineCol, Spanned};
@@ -13,6 +14,7 @@ fn get_error_diagnostics(errors: &[CompileError]) -> Vec<Diagnostic> {
     Vec::from_iter(errors.iter().map(|error| Diagnostic {
         range: get_range(error.span().line_col()),
         severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(error.code().to_string())),
         message: format!("{error}"),
         ..Default::default()
     }))
@@ -22,12 +24,34 @@ fn get_warning_diagnostics(warnings: &[CompileWarning]) -> Vec<Diagnostic> {
     Vec::from_iter(warnings.iter().map(|warning| Diagnostic {
         range: get_range(warning.span().line_col()),
         severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(warning.code().to_string())),
         message: warning.to_friendly_warning_string(),
         tags: get_warning_diagnostic_tags(&warning.warning_content),
+        data: get_warning_diagnostic_data(warning),
         ..Default::default()
     }))
 }
 
+/// Returns the machine-applicable suggestions of the warning as a list of text edits, each with
+/// a message describing it.
+fn get_warning_diagnostic_data(warning: &CompileWarning) -> Option<Value> {
+    let suggestions = warning.suggestions();
+    if suggestions.is_empty() {
+        return None;
+    }
+    let suggestions = suggestions
+        .into_iter()
+        .map(|suggestion| {
+            json!({
+                "message": suggestion.message,
+                "range": get_range(suggestion.span.line_col()),
+                "newText": suggestion.replacement,
+            })
+        })
+        .collect::<Vec<_>>();
+    Some(Value::Array(suggestions))
+}
+
 pub fn get_diagnostics(warnings: &[CompileWarning], errors: &[CompileError]) -> Diagnostics {
     Diagnostics {
         warnings: get_warning_diagnostics(warnings),
@@ -46,6 +70,8 @@ fn get_warning_diagnostic_tags(warning: &Warning) -> Option<Vec<DiagnosticTag>>
     match warning {
         Warning::StructFieldNeverRead
         | Warning::DeadDeclaration
+        | Warning::UnusedVariable { .. }
+        | Warning::UnusedImport { .. }
         | Warning::DeadEnumDeclaration
         | Warning::DeadEnumVariant { .. }
         | Warning::DeadFunctionDeclaration
@@ -80,7 +80,7 @@ fn visibility_as_str(visibility: &Visibility) -> &'static str {
 }
 
 /// Expects a span from either a `FunctionDeclaration` or a `TypedFunctionDeclaration`.
-fn extract_fn_signature(span: &Span) -> String {
+pub(crate) fn extract_fn_signature(span: &Span) -> String {
     let value = span.as_str();
     value.split('{').take(1).map(|v| v.trim()).collect()
 }
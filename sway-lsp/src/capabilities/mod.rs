@@ -10,6 +10,7 @@ pub mod on_enter;
 pub mod rename;
 pub mod runnable;
 pub mod semantic_tokens;
+pub mod trait_impls;
 
 pub(crate) use code_actions::code_actions;
 pub(crate) use on_enter::on_enter;
@@ -0,0 +1,69 @@
+use crate::{
+    capabilities::hover::extract_fn_signature,
+    core::{
+        session::Session,
+        token::{get_range_from_span, to_ident_key, TypeDefinition, TypedAstToken},
+    },
+    lsp_ext::TraitImpl,
+    utils::document::get_url_from_span,
+};
+use lsp_types::{Location, Position, Url};
+use std::sync::Arc;
+use sway_core::{namespace::TraitImplInfo, Engines};
+use sway_types::Spanned;
+
+/// Returns the traits implemented for the type of the token at the given position.
+pub(crate) fn trait_impls(
+    session: Arc<Session>,
+    url: &Url,
+    position: Position,
+) -> Option<Vec<TraitImpl>> {
+    let engines = session.engines.read();
+    let (_, token) = session
+        .token_map()
+        .token_at_position(engines.se(), url, position)?;
+    let namespace = session.namespace()?;
+
+    // Prefer the declaration of the token's type, as the token itself may only refer to it.
+    let decl_token = token.declared_token_ident(&engines).and_then(|decl_ident| {
+        session
+            .token_map()
+            .try_get(&to_ident_key(&decl_ident))
+            .try_unwrap()
+            .map(|item| item.value().clone())
+    });
+    let impls = match (decl_token.unwrap_or(token.clone()).typed, &token.type_def) {
+        (Some(TypedAstToken::TypedDeclaration(ty_decl)), _) => {
+            namespace.get_impls_for_decl(&engines, &ty_decl)
+        }
+        (_, Some(TypeDefinition::TypeId(type_id))) => {
+            namespace.get_impls_for_type(&engines, *type_id)
+        }
+        _ => return None,
+    };
+
+    Some(
+        impls
+            .iter()
+            .filter_map(|info| to_trait_impl(&session, &engines, info))
+            .collect(),
+    )
+}
+
+fn to_trait_impl(session: &Session, engines: &Engines, info: &TraitImplInfo) -> Option<TraitImpl> {
+    let span = session
+        .sync
+        .temp_to_workspace_span(engines.se(), &info.impl_span)
+        .ok()?;
+    let uri = get_url_from_span(engines.se(), &span).ok()?;
+    let methods = info
+        .methods()
+        .map(|decl_ref| extract_fn_signature(&engines.de().get_function(decl_ref.id()).span()))
+        .collect();
+    Some(TraitImpl {
+        trait_name: info.trait_name.to_string(),
+        is_impl_self: info.trait_decl_span.is_none(),
+        location: Location::new(uri, get_range_from_span(&span)),
+        methods,
+    })
+}
@@ -14,6 +14,8 @@ pub struct Config {
     pub diagnostic: DiagnosticConfig,
     #[serde(default)]
     pub on_enter: OnEnterConfig,
+    #[serde(default)]
+    pub artifacts: ArtifactsConfig,
     #[serde(default, skip_serializing)]
     trace: TraceConfig,
 }
@@ -53,6 +55,15 @@ impl Default for DiagnosticConfig {
     }
 }
 
+// Options for building the project and publishing its artifacts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactsConfig {
+    /// Whether to build the project when a document is saved without errors, and publish the
+    /// locations of its artifacts to the client.
+    pub publish_on_save: bool,
+}
+
 // Options for confguring server logging.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LoggingConfig {
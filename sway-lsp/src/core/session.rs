@@ -12,12 +12,14 @@ use crate::{
         token_map::{TokenMap, TokenMapExt},
     },
     error::{DocumentError, LanguageServerError},
+    lsp_ext::PackageArtifacts,
     traverse::{
         dependency, lexed_tree, parsed_tree::ParsedTree, typed_tree::TypedTree, ParseContext,
     },
 };
 use dashmap::DashMap;
-use forc_pkg as pkg;
+use forc_pkg::{self as pkg, BuildProfile};
+use forc_util::{default_output_directory, program_type_str};
 use lsp_types::{
     CompletionItem, GotoDefinitionResponse, Location, Position, Range, SymbolInformation,
     TextDocumentContentChangeEvent, TextEdit, Url,
@@ -35,7 +37,7 @@ use sway_core::{
     decl_engine::DeclEngine,
     language::{
         lexed::LexedProgram,
-        parsed::{AstNode, ParseProgram},
+        parsed::{AstNode, ParseProgram, TreeType},
         ty,
     },
     BuildTarget, CompileResult, Engines, Namespace, Programs,
@@ -177,23 +179,30 @@ impl Session {
         )
         .map_err(LanguageServerError::BuildPlanFailed)?;
 
-        let new_engines = Engines::default();
         let tests_enabled = true;
 
-        let results = pkg::check(
-            &plan,
-            BuildTarget::default(),
-            true,
-            tests_enabled,
-            &new_engines,
-        )
-        .map_err(LanguageServerError::FailedToCompile)?;
+        // Compile into the engines of the previous runs, so they keep being readable while we
+        // compile. Everything inserted from here on belongs to a new generation.
+        let engines = self.engines.read();
+        engines.next_generation();
+        let results = match pkg::check(&plan, BuildTarget::default(), true, tests_enabled, &engines)
+        {
+            Ok(results) => results,
+            Err(err) => {
+                // Keep the results of the previous runs, which don't refer to anything created
+                // by this one.
+                drop(engines);
+                self.engines.write().discard_generation();
+                return Err(LanguageServerError::FailedToCompile(err));
+            }
+        };
+        drop(engines);
 
         // Acquire locks for the engines before clearing anything.
         let mut engines = self.engines.write();
 
-        // Update the engines with the new data.
-        *engines = new_engines;
+        // Every module was compiled again, so the entries of the previous generations are garbage.
+        engines.gc(|_| true);
 
         // Clear other data stores.
         self.token_map.clear();
@@ -270,6 +279,42 @@ impl Session {
         Ok(true)
     }
 
+    /// Builds the project in the user's workspace, writing its artifacts to the output
+    /// directory like `forc build` does, and returns their locations.
+    pub fn build_artifacts(&self) -> Result<Vec<PackageArtifacts>, LanguageServerError> {
+        let manifest_dir = self.sync.manifest_dir()?;
+        let build_opts = pkg::BuildOpts {
+            pkg: pkg::PkgOpts {
+                path: Some(manifest_dir.to_string_lossy().to_string()),
+                terse: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let built =
+            pkg::build_with_options(build_opts).map_err(LanguageServerError::FailedToCompile)?;
+
+        let artifacts = built
+            .into_members()
+            .filter(|(_, built_pkg)| !matches!(built_pkg.tree_type, TreeType::Library { .. }))
+            .filter_map(|(_, built_pkg)| {
+                let name = &built_pkg.descriptor.name;
+                let output_dir = default_output_directory(built_pkg.descriptor.manifest_file.dir())
+                    .join(BuildProfile::DEBUG);
+                let bin = Url::from_file_path(output_dir.join(name).with_extension("bin")).ok()?;
+                let abi = Url::from_file_path(output_dir.join(format!("{name}-abi.json"))).ok()?;
+                Some(PackageArtifacts {
+                    name: name.clone(),
+                    program_type: program_type_str(&built_pkg.tree_type).to_string(),
+                    bin,
+                    abi,
+                    bytecode_size: built_pkg.bytecode.bytes.len(),
+                })
+            })
+            .collect();
+        Ok(artifacts)
+    }
+
     pub fn token_ranges(&self, url: &Url, position: Position) -> Option<Vec<Range>> {
         let (_, token) =
             self.token_map
@@ -78,8 +78,15 @@ pub(crate) async fn handle_did_save_text_document(
                     }
                 });
             state
-                .parse_project(uri, params.text_document.uri, session)
+                .parse_project(uri, params.text_document.uri.clone(), session.clone())
                 .await;
+
+            let publish_artifacts = state.config.read().artifacts.publish_on_save;
+            if publish_artifacts {
+                state
+                    .publish_artifacts(params.text_document.uri, session)
+                    .await;
+            }
         }
         Err(err) => tracing::error!("{}", err.to_string()),
     }
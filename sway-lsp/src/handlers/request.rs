@@ -413,3 +413,29 @@ pub(crate) fn handle_show_ast(
         }
     }
 }
+
+/// This method is triggered by the "sway/trait_impls" request, sent by clients
+/// which want to list the traits implemented for the type at the given position,
+/// along with the signatures of the methods each impl block provides.
+pub(crate) fn handle_trait_impls(
+    state: &ServerState,
+    params: lsp_ext::TraitImplsParams,
+) -> Result<Option<Vec<lsp_ext::TraitImpl>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let _ = session.wait_for_parsing();
+            Ok(capabilities::trait_impls::trait_impls(
+                session,
+                &uri,
+                params.position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! sway-lsp extensions to the LSP.
 
-use lsp_types::{TextDocumentIdentifier, Url};
+use lsp_types::{notification::Notification, Location, Position, TextDocumentIdentifier, Url};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -10,3 +10,53 @@ pub struct ShowAstParams {
     pub ast_kind: String,
     pub save_path: Url,
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraitImplsParams {
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+}
+
+/// A trait implemented for the type at the requested position.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraitImpl {
+    pub trait_name: String,
+    /// Whether this is an `impl Type` block rather than a trait implementation.
+    pub is_impl_self: bool,
+    /// The location of the impl block.
+    pub location: Location,
+    /// The signatures of the methods provided by the impl block.
+    pub methods: Vec<String>,
+}
+
+/// Sent to the client after the project has been built on save, if the
+/// `artifacts.publishOnSave` option is enabled.
+#[derive(Debug)]
+pub enum PublishArtifacts {}
+
+impl Notification for PublishArtifacts {
+    type Params = PublishArtifactsParams;
+    const METHOD: &'static str = "sway/publishArtifacts";
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishArtifactsParams {
+    /// The document whose save triggered the build.
+    pub uri: Url,
+    pub packages: Vec<PackageArtifacts>,
+}
+
+/// The artifacts of a built package, as written to its output directory.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageArtifacts {
+    pub name: String,
+    /// The program type, i.e. "contract", "script" or "predicate".
+    pub program_type: String,
+    pub bin: Url,
+    pub abi: Url,
+    pub bytecode_size: usize,
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     handlers::{notification, request},
-    lsp_ext::ShowAstParams,
+    lsp_ext::{ShowAstParams, TraitImpl, TraitImplsParams},
     server_state::ServerState,
 };
 use lsp_types::{
@@ -117,4 +117,8 @@ impl ServerState {
     pub async fn show_ast(&self, params: ShowAstParams) -> Result<Option<TextDocumentIdentifier>> {
         request::handle_show_ast(self, params)
     }
+
+    pub async fn trait_impls(&self, params: TraitImplsParams) -> Result<Option<Vec<TraitImpl>>> {
+        request::handle_trait_impls(self, params)
+    }
 }
@@ -4,6 +4,7 @@ use crate::{
     config::{Config, Warnings},
     core::session::Session,
     error::{DirectoryError, DocumentError, LanguageServerError},
+    lsp_ext::{PublishArtifacts, PublishArtifactsParams},
     utils::debug,
     utils::keyword_docs::KeywordDocs,
 };
@@ -91,6 +92,30 @@ impl ServerState {
     }
 }
 
+impl ServerState {
+    /// Builds the project, if it has no errors, and publishes the locations of its artifacts
+    /// to the client.
+    pub(crate) async fn publish_artifacts(&self, workspace_uri: Url, session: Arc<Session>) {
+        if !session.wait_for_parsing().errors.is_empty() {
+            return;
+        }
+        let packages = match task::spawn_blocking(move || session.build_artifacts()).await {
+            Ok(Ok(packages)) => packages,
+            Ok(Err(err)) => {
+                tracing::error!("{}", err);
+                return;
+            }
+            Err(_) => return,
+        };
+        self.client
+            .send_notification::<PublishArtifacts>(PublishArtifactsParams {
+                uri: workspace_uri,
+                packages,
+            })
+            .await;
+    }
+}
+
 /// Runs parse_project in a blocking thread, because parsing is not async.
 async fn run_blocking_parse_project(uri: Url, session: Arc<Session>) -> bool {
     task::spawn_blocking(move || match session.parse_project(&uri) {
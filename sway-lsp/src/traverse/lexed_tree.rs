@@ -89,6 +89,7 @@ impl Parse for ItemKind {
             ItemKind::TypeAlias(item_type_alias) => {
                 item_type_alias.parse(ctx);
             }
+            ItemKind::Error(_) => {}
         }
     }
 }
@@ -541,6 +542,7 @@ impl Parse for Statement {
             Statement::Item(item) => {
                 item.value.parse(ctx);
             }
+            Statement::Error(_) => {}
         }
     }
 }
@@ -607,6 +609,10 @@ impl Parse for IfCondition {
                 lhs.parse(ctx);
                 rhs.parse(ctx);
             }
+            IfCondition::And { lhs, rhs, .. } => {
+                lhs.parse(ctx);
+                rhs.parse(ctx);
+            }
         }
     }
 }
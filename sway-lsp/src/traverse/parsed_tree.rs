@@ -715,6 +715,10 @@ impl Parse for FunctionDeclaration {
             });
         }
         self.return_type.parse(ctx);
+        self.requires
+            .iter()
+            .chain(&self.ensures)
+            .for_each(|condition| condition.parse(ctx));
         self.attributes.parse(ctx);
     }
 }
@@ -955,6 +955,10 @@ impl Parse for ty::TyFunctionDecl {
         }
         self.body.contents.iter().for_each(|node| node.parse(ctx));
         self.parameters.iter().for_each(|param| param.parse(ctx));
+        self.requires
+            .iter()
+            .chain(&self.ensures)
+            .for_each(|condition| condition.parse(ctx));
         self.type_parameters.iter().for_each(|type_param| {
             collect_type_id(
                 ctx,
@@ -52,7 +52,7 @@ fn literal_to_string(literal: &Literal) -> String {
         Literal::U32(_) => "u32".into(),
         Literal::U64(_) => "u64".into(),
         Literal::Numeric(_) => "u64".into(),
-        Literal::String(len) => format!("str[{}]", len.as_str().len()),
+        Literal::String(_, value) => format!("str[{}]", value.len()),
         Literal::Boolean(_) => "bool".into(),
         Literal::B256(_) => "b256".into(),
     }
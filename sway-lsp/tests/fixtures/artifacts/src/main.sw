@@ -0,0 +1,5 @@
+script;
+
+fn main() -> u64 {
+    42
+}
@@ -31,7 +31,17 @@ pub(crate) async fn call_request(
 }
 
 pub(crate) async fn initialize_request(service: &mut LspService<ServerState>) -> Request {
-    let params = json!({ "capabilities": sway_lsp::server_capabilities() });
+    initialize_request_with_options(service, json!(null)).await
+}
+
+pub(crate) async fn initialize_request_with_options(
+    service: &mut LspService<ServerState>,
+    initialization_options: serde_json::Value,
+) -> Request {
+    let params = json!({
+        "capabilities": sway_lsp::server_capabilities(),
+        "initializationOptions": initialization_options,
+    });
     let initialize = build_request_with_id("initialize", params, 1);
     let response = call_request(service, initialize.clone()).await;
     let expected = Response::from_ok(
@@ -117,6 +127,19 @@ pub(crate) async fn did_change_request(
     did_change
 }
 
+pub(crate) async fn did_save_notification(service: &mut LspService<ServerState>, uri: &Url) {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+    });
+    let did_save = Request::build("textDocument/didSave")
+        .params(params)
+        .finish();
+    let response = call_request(service, did_save).await;
+    assert_eq!(response, Ok(None));
+}
+
 pub(crate) async fn did_close_notification(service: &mut LspService<ServerState>) {
     let exit = Request::build("textDocument/didClose").finish();
     let response = call_request(service, exit.clone()).await;
@@ -152,6 +175,26 @@ pub(crate) async fn show_ast_request(
     show_ast
 }
 
+pub(crate) async fn trait_impls_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+    line: u32,
+    character: u32,
+) -> serde_json::Value {
+    let params = json!({
+        "textDocument": {
+            "uri": uri
+        },
+        "position": {
+            "line": line,
+            "character": character
+        }
+    });
+    let trait_impls = build_request_with_id("sway/trait_impls", params, 1);
+    let response = call_request(service, trait_impls).await.unwrap().unwrap();
+    response.result().unwrap().clone()
+}
+
 pub(crate) async fn semantic_tokens_request(
     service: &mut LspService<ServerState>,
     uri: &Url,
@@ -1,8 +1,13 @@
 pub mod integration;
 
 use crate::integration::{code_actions, lsp};
+use futures::{future, StreamExt};
+use serde_json::json;
 use std::{fs, path::PathBuf};
-use sway_lsp::server_state::ServerState;
+use sway_lsp::{
+    lsp_ext::{PublishArtifacts, PublishArtifactsParams},
+    server_state::ServerState,
+};
 use sway_lsp_test_utils::{
     assert_server_requests, dir_contains_forc_manifest, doc_comments_dir, e2e_language_dir,
     e2e_test_dir, generic_impl_self_dir, get_fixture, load_sway_example, runnables_test_dir,
@@ -10,7 +15,7 @@ use sway_lsp_test_utils::{
 };
 use tower_lsp::{
     jsonrpc::{self, Response},
-    lsp_types::*,
+    lsp_types::{notification::Notification, *},
     LspService,
 };
 
@@ -207,6 +212,35 @@ async fn show_ast() {
     shutdown_and_exit(&mut service).await;
 }
 
+#[tokio::test]
+async fn trait_impls() {
+    let (mut service, _) = LspService::build(ServerState::new)
+        .custom_method("sway/trait_impls", ServerState::trait_impls)
+        .finish();
+
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("completion/src/main.sw"),
+    )
+    .await;
+    let impls = lsp::trait_impls_request(&mut service, &uri, 11, 24).await;
+    let impls = impls.as_array().unwrap();
+    assert_eq!(impls.len(), 1);
+    assert_eq!(impls[0]["isImplSelf"], json!(true));
+    assert_eq!(
+        impls[0]["location"]["range"],
+        json!({
+            "start": { "line": 6, "character": 0 },
+            "end": { "line": 14, "character": 1 },
+        })
+    );
+    assert_eq!(
+        impls[0]["methods"],
+        json!(["fn get(self, foo: Self) -> Self", "fn new() -> Self"])
+    );
+    shutdown_and_exit(&mut service).await;
+}
+
 //------------------- GO TO DEFINITION -------------------//
 
 #[tokio::test]
@@ -1790,6 +1824,50 @@ async fn publish_diagnostics_dead_code_warning() {
     shutdown_and_exit(&mut service).await;
 }
 
+#[tokio::test]
+async fn publish_artifacts_on_save() {
+    let (mut service, socket) = LspService::new(ServerState::new);
+    let artifacts = tokio::spawn(async move {
+        let mut published = Box::pin(
+            socket.filter(|request| future::ready(request.method() == PublishArtifacts::METHOD)),
+        );
+        published
+            .next()
+            .await
+            .map(|request| serde_json::to_value(request).unwrap()["params"].clone())
+    });
+
+    let options = json!({ "artifacts": { "publishOnSave": true } });
+    let _ = lsp::initialize_request_with_options(&mut service, options).await;
+    lsp::initialized_notification(&mut service).await;
+    let (uri, sway_program) = load_sway_example(test_fixtures_dir().join("artifacts/src/main.sw"));
+    lsp::did_open_notification(&mut service, &uri, &sway_program).await;
+    lsp::did_save_notification(&mut service, &uri).await;
+
+    let params = tokio::time::timeout(std::time::Duration::from_secs(60), artifacts)
+        .await
+        .expect("no artifacts were published")
+        .unwrap()
+        .unwrap();
+    let params: PublishArtifactsParams = serde_json::from_value(params).unwrap();
+    assert_eq!(params.uri, uri);
+    let [package] = params.packages.as_slice() else {
+        panic!(
+            "expected the artifacts of one package, got {:?}",
+            params.packages
+        );
+    };
+    assert_eq!(package.name, "artifacts");
+    assert_eq!(package.program_type, "script");
+    let bin = package.bin.to_file_path().unwrap();
+    assert!(bin.ends_with("out/debug/artifacts.bin"));
+    assert_eq!(fs::read(bin).unwrap().len(), package.bytecode_size);
+    let abi = package.abi.to_file_path().unwrap();
+    assert!(abi.ends_with("out/debug/artifacts-abi.json"));
+    assert!(abi.exists());
+    shutdown_and_exit(&mut service).await;
+}
+
 // This macro allows us to spin up a server / client for testing
 // It initializes and performs the necessary handshake and then loads
 // the sway example that was passed into `example_dir`.
@@ -7,7 +7,9 @@ use sway_ast::keywords::{EqToken, HashBangToken, HashToken, StorageToken, Token}
 use sway_ast::punctuated::Punctuated;
 use sway_ast::token::{DocComment, DocStyle};
 use sway_error::parser_error::ParseErrorKind;
-use sway_types::constants::DOC_COMMENT_ATTRIBUTE_NAME;
+use sway_types::constants::{
+    DOC_COMMENT_ATTRIBUTE_NAME, ENSURES_ATTRIBUTE_NAME, REQUIRES_ATTRIBUTE_NAME,
+};
 use sway_types::Ident;
 
 impl Peek for DocComment {
@@ -50,6 +52,7 @@ impl<T: Parse> Parse for Annotated<T> {
                             Punctuated::single(AttributeArg { name, value: None }),
                             doc_comment.content_span,
                         )),
+                        condition: None,
                     }),
                     doc_comment.span,
                 ),
@@ -113,8 +116,26 @@ impl Parse for Attribute {
         } else {
             parser.parse()?
         };
+        // The specification attributes have a condition rather than arguments.
+        if matches!(
+            name.as_str(),
+            REQUIRES_ATTRIBUTE_NAME | ENSURES_ATTRIBUTE_NAME
+        ) {
+            let condition = Parens::try_parse_all_inner(parser, |mut parser| {
+                parser.emit_error(ParseErrorKind::UnexpectedTokenAfterAttribute)
+            })?;
+            return Ok(Attribute {
+                name,
+                args: None,
+                condition,
+            });
+        }
         let args = Parens::try_parse(parser)?;
-        Ok(Attribute { name, args })
+        Ok(Attribute {
+            name,
+            args,
+            condition: None,
+        })
     }
 }
 
@@ -173,6 +194,7 @@ mod tests {
                       ),
                       span: (85, 108),
                     )),
+                    condition: None,
                   )),
                 ),
                 span: (82, 108),
@@ -203,6 +225,7 @@ mod tests {
                       ),
                       span: (130, 136),
                     )),
+                    condition: None,
                   )),
                 ),
                 span: (122, 137),
@@ -290,6 +313,35 @@ mod tests {
             ),
             span: (17, 45),
           )),
+          condition: None,
+        )
+        "###);
+    }
+    #[test]
+    fn parse_specification_attribute() {
+        assert_ron_snapshot!(parse::<Attribute>(r#"
+            requires(x)
+        "#,), @r###"
+        Attribute(
+          name: Ident(
+            to_string: "requires",
+            span: (13, 21),
+          ),
+          args: None,
+          condition: Some(Parens(
+            inner: Path(PathExpr(
+              root_opt: None,
+              prefix: PathExprSegment(
+                name: Ident(
+                  to_string: "x",
+                  span: (22, 23),
+                ),
+                generics_opt: None,
+              ),
+              suffix: [],
+            )),
+            span: (21, 24),
+          )),
         )
         "###);
     }
@@ -5,8 +5,9 @@ use core::str::FromStr;
 use num_bigint::BigUint;
 
 use sway_ast::expr::asm::{
-    AsmBlock, AsmBlockContents, AsmFinalExpr, AsmImmediate, AsmRegisterDeclaration,
+    AsmBlock, AsmBlockContents, AsmFinalExpr, AsmImmediate, AsmLabel, AsmRegisterDeclaration,
 };
+use sway_ast::keywords::{ColonToken, SemicolonToken};
 use sway_error::parser_error::ParseErrorKind;
 use sway_types::{Ident, Spanned};
 
@@ -50,6 +51,21 @@ impl ParseToEnd for AsmBlockContents {
                 break (None, consumed);
             }
 
+            // A label is an identifier and a colon followed by an instruction, which sets it
+            // apart from a final `reg: ty` expression.
+            let label_opt = if parser.peek::<(Ident, ColonToken, Ident, Ident)>().is_some()
+                || parser
+                    .peek::<(Ident, ColonToken, Ident, SemicolonToken)>()
+                    .is_some()
+            {
+                Some(AsmLabel {
+                    name: parser.parse()?,
+                    colon_token: parser.parse()?,
+                })
+            } else {
+                None
+            };
+
             // Parse the opcode directly instead of calling `parser.parse()` to avoid checking for
             // illegal identifiers such as keywords. opcode names should not be subject to those
             // checks because some opcodes, such as `mod`, are also Sway keywords.
@@ -83,7 +99,7 @@ impl ParseToEnd for AsmBlockContents {
             }
             let instruction = parse_instruction(ident, &mut parser)?;
             let semicolon_token = parser.parse()?;
-            instructions.push((instruction, semicolon_token));
+            instructions.push((label_opt, instruction, semicolon_token));
         };
         let contents = AsmBlockContents {
             instructions,
@@ -96,16 +112,63 @@ impl ParseToEnd for AsmBlockContents {
 impl Parse for AsmImmediate {
     fn parse(parser: &mut Parser) -> ParseResult<AsmImmediate> {
         let ident = parser.parse::<Ident>()?;
-        let digits = ident
-            .as_str()
-            .strip_prefix('i')
-            .ok_or_else(|| parser.emit_error(ParseErrorKind::MalformedAsmImmediate))?;
-        let parsed = BigUint::from_str(digits)
-            .ok()
-            .ok_or_else(|| parser.emit_error(ParseErrorKind::MalformedAsmImmediate))?;
+        // Anything other than `i` followed by digits names a constant or a label, and is resolved
+        // during type checking.
+        let parsed = match ident.as_str().strip_prefix('i') {
+            Some(digits) if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) => {
+                Some(
+                    BigUint::from_str(digits)
+                        .ok()
+                        .ok_or_else(|| parser.emit_error(ParseErrorKind::MalformedAsmImmediate))?,
+                )
+            }
+            _ => None,
+        };
         Ok(AsmImmediate {
             span: ident.span(),
             parsed,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::parse;
+
+    #[test]
+    fn parse_asm_labels_and_symbolic_immediates() {
+        let asm_block = parse::<AsmBlock>(
+            r#"
+            asm(idx: 0, n: N, cond) {
+                loop_start: addi idx idx i1;
+                lt cond idx n;
+                jnzi cond loop_start;
+                idx: u64
+            }
+            "#,
+        );
+        let contents = asm_block.contents.into_inner();
+        let labels = contents
+            .instructions
+            .iter()
+            .map(|(label_opt, _, _)| label_opt.as_ref().map(|label| label.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec![Some("loop_start"), None, None]);
+
+        let immediates = contents
+            .instructions
+            .iter()
+            .map(|(_, instruction, _)| instruction.immediate_ident_opt())
+            .collect::<Vec<_>>();
+        assert_eq!(immediates[0].as_ref().map(Ident::as_str), Some("i1"));
+        assert_eq!(
+            immediates[2].as_ref().map(Ident::as_str),
+            Some("loop_start")
+        );
+
+        let final_expr = contents.final_expr_opt.unwrap();
+        assert_eq!(final_expr.register.as_str(), "idx");
+        assert!(final_expr.ty_opt.is_some());
+    }
+}
@@ -1,12 +1,14 @@
+use crate::item::is_at_item_start;
 use crate::{Parse, ParseBracket, ParseResult, ParseToEnd, Parser, ParserConsumed, Peek};
 
 use sway_ast::brackets::{Braces, Parens, SquareBrackets};
 use sway_ast::expr::{LoopControlFlow, ReassignmentOp, ReassignmentOpVariant};
 use sway_ast::keywords::{
     AbiToken, AddEqToken, AsmToken, CommaToken, ConfigurableToken, ConstToken, DivEqToken,
-    DoubleColonToken, EnumToken, EqToken, FalseToken, FnToken, IfToken, ImplToken, LetToken,
-    OpenAngleBracketToken, PubToken, SemicolonToken, ShlEqToken, ShrEqToken, StarEqToken,
-    StorageToken, StructToken, SubEqToken, Token, TraitToken, TrueToken, TypeToken, UseToken,
+    DoubleAmpersandToken, DoubleColonToken, DoublePipeToken, EnumToken, EqToken, FalseToken,
+    FnToken, IfToken, ImplToken, LetToken, OpenAngleBracketToken, PubToken, SemicolonToken,
+    ShlEqToken, ShrEqToken, StarEqToken, StorageToken, StructToken, SubEqToken, Token, TraitToken,
+    TrueToken, TypeToken, UseToken,
 };
 use sway_ast::literal::{LitBool, LitBoolType};
 use sway_ast::punctuated::Punctuated;
@@ -69,20 +71,48 @@ impl Parse for IfExpr {
 
 impl Parse for IfCondition {
     fn parse(parser: &mut Parser) -> ParseResult<IfCondition> {
-        if let Some(let_token) = parser.take() {
-            let lhs = parser.parse()?;
-            let eq_token = parser.parse()?;
-            let rhs = Box::new(parse_condition(parser)?);
-            Ok(IfCondition::Let {
-                let_token,
-                lhs,
-                eq_token,
-                rhs,
-            })
-        } else {
-            let expr = Box::new(parse_condition(parser)?);
-            Ok(IfCondition::Expr(expr))
+        let mut condition = parse_if_condition_operand(parser)?;
+        // An expression operand only stops at `&&` when a `let` follows, so any `&&` left here
+        // continues a chain of conditions containing a `let`.
+        while let Some(double_ampersand_token) = parser.take() {
+            let rhs = parse_if_condition_operand(parser)?;
+            condition = IfCondition::And {
+                lhs: Box::new(condition),
+                double_ampersand_token,
+                rhs: Box::new(rhs),
+            };
         }
+        Ok(condition)
+    }
+}
+
+fn parse_if_condition_operand(parser: &mut Parser) -> ParseResult<IfCondition> {
+    if let Some(let_token) = parser.take() {
+        let lhs = parser.parse()?;
+        let eq_token = parser.parse()?;
+        // The scrutinee excludes the lazy operators, so that `let Some(x) = a && x > 0` chains
+        // the `let` with `x > 0` instead of matching on `a && x > 0`.
+        let ctx = ParseExprCtx {
+            parsing_conditional: true,
+            at_start_of_statement: false,
+            parsing_let_scrutinee: true,
+        };
+        let rhs = Box::new(parse_logical_or(parser, ctx)?);
+        if let Some(double_pipe_token) = parser.peek::<DoublePipeToken>() {
+            return Err(parser.emit_error_with_span(
+                ParseErrorKind::LogicalOrAfterLetScrutinee,
+                double_pipe_token.span(),
+            ));
+        }
+        Ok(IfCondition::Let {
+            let_token,
+            lhs,
+            eq_token,
+            rhs,
+        })
+    } else {
+        let expr = Box::new(parse_condition(parser)?);
+        Ok(IfCondition::Expr(expr))
     }
 }
 
@@ -132,9 +162,21 @@ impl ParseToEnd for CodeBlockContents {
             if let Some(consumed) = parser.check_empty() {
                 break (None, consumed);
             }
-            match parse_stmt(&mut parser)? {
-                StmtOrTail::Stmt(s) => statements.push(s),
-                StmtOrTail::Tail(e, c) => break (Some(e), c),
+            match parser.parse_with_recovery(parse_stmt, is_at_statement_start) {
+                Ok(StmtOrTail::Stmt(s)) => statements.push(s),
+                Ok(StmtOrTail::Tail(e, c)) => break (Some(e), c),
+                Err(mut spans) => {
+                    // Resume after the semicolon ending the malformed statement, if any.
+                    if let Some(semicolon_token) = parser.take::<SemicolonToken>() {
+                        spans.push(semicolon_token.span());
+                        statements.push(Statement::Error(spans.into()));
+                    } else if let Some(consumed) = parser.check_empty() {
+                        // A malformed tail expression keeps the block from being typed as `()`.
+                        break (Some(Box::new(Expr::Error(spans.into()))), consumed);
+                    } else {
+                        statements.push(Statement::Error(spans.into()));
+                    }
+                }
             }
         };
         let code_block_contents = CodeBlockContents {
@@ -145,6 +187,16 @@ impl ParseToEnd for CodeBlockContents {
     }
 }
 
+/// Whether the parser is at a `;` or at the start of a `let` or an item.
+///
+/// Parsing resumes at such a token after a malformed statement, so that the statements following
+/// it are still parsed.
+fn is_at_statement_start(parser: &Parser) -> bool {
+    parser.peek::<SemicolonToken>().is_some()
+        || parser.peek::<LetToken>().is_some()
+        || is_at_item_start(parser)
+}
+
 /// A statement or a tail expression in a block.
 #[allow(clippy::large_enum_variant)]
 enum StmtOrTail<'a> {
@@ -216,6 +268,7 @@ fn parse_stmt<'a>(parser: &mut Parser<'a, '_>) -> ParseResult<StmtOrTail<'a>> {
 struct ParseExprCtx {
     pub parsing_conditional: bool,
     pub at_start_of_statement: bool,
+    pub parsing_let_scrutinee: bool,
 }
 
 impl ParseExprCtx {
@@ -231,6 +284,7 @@ fn parse_condition(parser: &mut Parser) -> ParseResult<Expr> {
     let ctx = ParseExprCtx {
         parsing_conditional: true,
         at_start_of_statement: false,
+        parsing_let_scrutinee: false,
     };
     parse_reassignment(parser, ctx)
 }
@@ -239,6 +293,7 @@ fn parse_statement_expr(parser: &mut Parser) -> ParseResult<Expr> {
     let ctx = ParseExprCtx {
         parsing_conditional: false,
         at_start_of_statement: true,
+        parsing_let_scrutinee: false,
     };
     parse_reassignment(parser, ctx)
 }
@@ -317,6 +372,10 @@ fn parse_binary<O: Peek>(
 }
 
 fn parse_logical_or(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
+    // An `if let` scrutinee leaves `||` to be reported by the caller.
+    if ctx.parsing_let_scrutinee {
+        return parse_logical_and(parser, ctx);
+    }
     let combine = |lhs, rhs, double_pipe_token| Expr::LogicalOr {
         lhs,
         double_pipe_token,
@@ -326,12 +385,33 @@ fn parse_logical_or(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr>
 }
 
 fn parse_logical_and(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
-    let combine = |lhs, rhs, double_ampersand_token| Expr::LogicalAnd {
-        lhs,
-        double_ampersand_token,
-        rhs,
-    };
-    parse_binary(parser, ctx, parse_comparison, combine)
+    // An `if let` scrutinee leaves `&&` to chain with the next condition.
+    if ctx.parsing_let_scrutinee {
+        return parse_comparison(parser, ctx);
+    }
+    let mut expr = parse_comparison(parser, ctx)?;
+    if expr.is_control_flow() && ctx.at_start_of_statement {
+        return Ok(expr);
+    }
+    loop {
+        // In a condition, `&& let` continues an `if let` chain rather than this expression.
+        let let_chain_continues =
+            ctx.parsing_conditional && parser.peek::<(DoubleAmpersandToken, LetToken)>().is_some();
+        if let_chain_continues {
+            break;
+        }
+        match parse_op_rhs(parser, ctx, parse_comparison)? {
+            Some((double_ampersand_token, rhs)) => {
+                expr = Expr::LogicalAnd {
+                    lhs: Box::new(expr),
+                    double_ampersand_token,
+                    rhs,
+                };
+            }
+            None => break,
+        }
+    }
+    Ok(expr)
 }
 
 fn parse_comparison(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
@@ -812,3 +892,99 @@ impl Parse for MatchBranchKind {
         Ok(MatchBranchKind::Expr { expr, comma_token })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::parse;
+    use std::sync::Arc;
+    use sway_error::error::CompileError;
+
+    fn method_call_target(input: &str) -> Expr {
+        match parse::<Expr>(input) {
+            Expr::MethodCall { target, .. } => *target,
+            expr => panic!("expected a method call, found {expr:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_method_call_on_parens() {
+        let target = method_call_target("(a + b).wrapping_mul(c)");
+        assert!(matches!(target, Expr::Parens(_)));
+    }
+
+    #[test]
+    fn parse_method_call_on_literal() {
+        let target = method_call_target("5u64.pow(2)");
+        assert!(matches!(
+            target,
+            Expr::Literal(Literal::Int(LitInt {
+                ty_opt: Some(_),
+                ..
+            }))
+        ));
+
+        let target = method_call_target("5.pow(2)");
+        assert!(matches!(
+            target,
+            Expr::Literal(Literal::Int(LitInt { ty_opt: None, .. }))
+        ));
+    }
+
+    #[test]
+    fn parse_method_call_on_tuple() {
+        let target = method_call_target("(1, 2).sum()");
+        assert!(matches!(
+            target,
+            Expr::Tuple(tuple) if matches!(tuple.get(), ExprTupleDescriptor::Cons { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_if_let_chain_stops_scrutinee_at_double_ampersand() {
+        let condition = parse::<IfCondition>("let Some(x) = a && x > 0");
+        match condition {
+            IfCondition::And { lhs, rhs, .. } => {
+                assert!(
+                    matches!(*lhs, IfCondition::Let { ref rhs, .. } if matches!(**rhs, Expr::Path(_)))
+                );
+                assert!(
+                    matches!(*rhs, IfCondition::Expr(ref expr) if matches!(**expr, Expr::GreaterThan { .. }))
+                );
+            }
+            condition => panic!("expected an `&&` chain, found {condition:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_if_let_scrutinee_keeps_comparisons() {
+        let condition = parse::<IfCondition>("let true = a == b");
+        assert!(matches!(
+            condition,
+            IfCondition::Let { rhs, .. } if matches!(*rhs, Expr::Equal { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_if_let_scrutinee_with_parenthesized_lazy_operators() {
+        let condition = parse::<IfCondition>("let true = (a || b && c)");
+        assert!(matches!(
+            condition,
+            IfCondition::Let { rhs, .. } if matches!(*rhs, Expr::Parens(_))
+        ));
+    }
+
+    #[test]
+    fn parse_if_let_scrutinee_rejects_double_pipe() {
+        let input = "let true = a || b";
+        let handler = <_>::default();
+        let ts = crate::token::lex(&handler, &Arc::from(input), 0, input.len(), None).unwrap();
+        assert!(Parser::new(&handler, &ts).parse::<IfCondition>().is_err());
+        let (errors, _) = handler.consume();
+        assert!(errors.iter().any(|error| matches!(
+            error,
+            CompileError::Parse { error }
+                if error.kind == ParseErrorKind::LogicalOrAfterLetScrutinee
+        )));
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{Parse, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
 use sway_ast::keywords::{
-    AbiToken, ClassToken, ConfigurableToken, ConstToken, EnumToken, FnToken, ImplToken, ModToken,
-    MutToken, OpenAngleBracketToken, RefToken, SelfToken, SemicolonToken, StorageToken,
-    StructToken, TraitToken, TypeToken, UseToken, WhereToken,
+    AbiToken, ClassToken, ConfigurableToken, ConstToken, EnumToken, FnToken, HashToken, ImplToken,
+    ModToken, MutToken, OpenAngleBracketToken, PubToken, RefToken, SelfToken, SemicolonToken,
+    StorageToken, StructToken, TraitToken, TypeToken, UseToken, WhereToken,
 };
+use sway_ast::token::DocComment;
 use sway_ast::{
     FnArg, FnArgs, FnSignature, ItemConst, ItemEnum, ItemFn, ItemKind, ItemStruct, ItemTrait,
     ItemTypeAlias, ItemUse, Submodule, TypeField,
@@ -78,6 +79,29 @@ impl Parse for ItemKind {
     }
 }
 
+/// Whether the parser is at the start of an item or of its attributes.
+///
+/// Parsing resumes at such a token after a malformed item, so that the items following it are
+/// still parsed.
+pub(crate) fn is_at_item_start(parser: &Parser) -> bool {
+    parser.peek::<DocComment>().is_some()
+        || parser.peek::<HashToken>().is_some()
+        || parser.peek::<PubToken>().is_some()
+        || parser.peek::<ModToken>().is_some()
+        || parser.peek::<UseToken>().is_some()
+        || parser.peek::<ClassToken>().is_some()
+        || parser.peek::<StructToken>().is_some()
+        || parser.peek::<EnumToken>().is_some()
+        || parser.peek::<FnToken>().is_some()
+        || parser.peek::<TraitToken>().is_some()
+        || parser.peek::<ImplToken>().is_some()
+        || parser.peek::<AbiToken>().is_some()
+        || parser.peek::<ConstToken>().is_some()
+        || parser.peek::<StorageToken>().is_some()
+        || parser.peek::<ConfigurableToken>().is_some()
+        || parser.peek::<TypeToken>().is_some()
+}
+
 impl Parse for TypeField {
     fn parse(parser: &mut Parser) -> ParseResult<TypeField> {
         Ok(TypeField {
@@ -139,7 +163,12 @@ impl ParseToEnd for FnArgs {
 
 impl Parse for FnArg {
     fn parse(parser: &mut Parser) -> ParseResult<FnArg> {
+        let mut attribute_list = Vec::new();
+        while let Some(attr) = parser.guarded_parse::<HashToken, _>()? {
+            attribute_list.push(attr);
+        }
         Ok(FnArg {
+            attribute_list,
             pattern: parser.parse()?,
             colon_token: parser.parse()?,
             ty: parser.parse()?,
@@ -594,6 +623,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_attributes_fn_args() {
+        let item = parse::<Item>(
+            r#"
+            abi A {
+                fn f(self, #[param(min = 1, max = 100)] amount: u64, to: b256);
+            }
+            "#,
+        );
+
+        if let ItemKind::Abi(item_abi) = item.value {
+            let (f_sig, _) = item_abi.abi_items.get().iter().next().unwrap();
+            let fn_signature = match &f_sig.value {
+                ItemTraitItem::Fn(fn_signature) => fn_signature,
+                _ => panic!("Parsed ABI item is not a method signature."),
+            };
+            let args = match fn_signature.arguments.get() {
+                FnArgs::NonStatic {
+                    args_opt: Some((_, args)),
+                    ..
+                } => args.into_iter().collect::<Vec<_>>(),
+                _ => panic!("Parsed method signature has no arguments after `self`."),
+            };
+
+            assert_eq!(
+                attributes(&args[0].attribute_list),
+                vec![[("param", Some(vec!["min", "max"]))]]
+            );
+            assert!(args[1].attribute_list.is_empty());
+        } else {
+            panic!("Parsed ABI is not an ABI.");
+        }
+    }
+
     #[test]
     fn parse_attributes_doc_comment() {
         let item = parse::<Item>(
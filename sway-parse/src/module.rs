@@ -1,11 +1,11 @@
-use crate::{Parse, ParseResult, ParseToEnd, Parser, ParserConsumed};
+use crate::{item::is_at_item_start, Parse, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
 use sway_ast::{
     attribute::{Annotated, Attribute, AttributeArg, AttributeHashKind},
     brackets::SquareBrackets,
     keywords::{HashBangToken, Token},
     token::{DocComment, DocStyle},
-    AttributeDecl, Module, ModuleKind, Parens, Punctuated,
+    AttributeDecl, ItemKind, Module, ModuleKind, Parens, Punctuated,
 };
 use sway_error::parser_error::ParseErrorKind;
 use sway_types::{constants::DOC_COMMENT_ATTRIBUTE_NAME, Ident};
@@ -50,6 +50,7 @@ impl ParseToEnd for Annotated<Module> {
                                 Punctuated::single(AttributeArg { name, value: None }),
                                 doc_comment.content_span,
                             )),
+                            condition: None,
                         }),
                         doc_comment.span,
                     ),
@@ -61,7 +62,19 @@ impl ParseToEnd for Annotated<Module> {
         }
         let (kind, semicolon_token) = parser.parse()?;
 
-        let (items, consumed) = parser.parse_to_end()?;
+        let mut items = Vec::new();
+        let consumed = loop {
+            if let Some(consumed) = parser.check_empty() {
+                break consumed;
+            }
+            let item = parser
+                .parse_with_recovery(|parser| parser.parse(), is_at_item_start)
+                .unwrap_or_else(|spans| Annotated {
+                    attribute_list: vec![],
+                    value: ItemKind::Error(spans.into()),
+                });
+            items.push(item);
+        };
 
         let module = Annotated {
             attribute_list,
@@ -142,4 +155,40 @@ mod tests {
         )
         "###);
     }
+
+    #[test]
+    fn recover_from_malformed_items_and_statements() {
+        let handler = <_>::default();
+        let module = crate::parse_file(
+            &handler,
+            r#"
+            script;
+
+            fn broken() -> { 0 }
+
+            struct Foo { x: u64 }
+
+            fn main() -> u64 {
+                foo bar;
+                let y = 1;
+                y
+            }
+        "#
+            .into(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(handler.consume().0.len(), 2);
+
+        let items = &module.value.items;
+        assert!(matches!(items[0].value, ItemKind::Error(_)));
+        assert!(matches!(items[1].value, ItemKind::Struct(_)));
+        let ItemKind::Fn(main_fn) = &items[2].value else {
+            panic!("expected `main` to be parsed");
+        };
+        let body = main_fn.body.get();
+        assert!(matches!(body.statements[0], sway_ast::Statement::Error(_)));
+        assert!(matches!(body.statements[1], sway_ast::Statement::Let(_)));
+        assert!(body.final_expr_opt.is_some());
+    }
 }
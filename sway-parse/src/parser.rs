@@ -40,11 +40,9 @@ impl<'a, 'e> Parser<'a, 'e> {
                 } else {
                     num_trailing_spaces
                 };
-                Span::new(
-                    self.full_span.src().clone(),
+                self.full_span.with_range(
                     self.full_span.end().saturating_sub(trim_offset),
                     (self.full_span.end() + 1).saturating_sub(trim_offset),
-                    self.full_span.source_id().cloned(),
                 )
             }
             .unwrap(),
@@ -85,6 +83,38 @@ impl<'a, 'e> Parser<'a, 'e> {
         self.peek::<G>().map(|_| self.parse()).transpose()
     }
 
+    /// Parses with `parse`, recovering from a failure by skipping ahead to the next token at which
+    /// `is_sync` holds, or to the end of the stream.
+    ///
+    /// On failure, the spans of the tokens consumed by the failed attempt and those skipped are
+    /// returned. At least one token is skipped, so that recovering in a loop always progresses.
+    pub fn parse_with_recovery<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+        is_sync: impl Fn(&Self) -> bool,
+    ) -> Result<T, Vec<Span>> {
+        let start = self.token_trees;
+        match parse(self) {
+            Ok(value) => Ok(value),
+            Err(_) => {
+                if self.token_trees.len() == start.len() {
+                    self.skip_token_tree();
+                }
+                while !self.is_empty() && !is_sync(self) {
+                    self.skip_token_tree();
+                }
+                let skipped = &start[..start.len() - self.token_trees.len()];
+                Err(skipped.iter().map(Spanned::span).collect())
+            }
+        }
+    }
+
+    fn skip_token_tree(&mut self) {
+        if let [_, rest @ ..] = self.token_trees {
+            self.token_trees = rest;
+        }
+    }
+
     pub fn parse_to_end<T: ParseToEnd>(self) -> ParseResult<(T, ParserConsumed<'a>)> {
         T::parse_to_end(self)
     }
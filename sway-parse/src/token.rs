@@ -2,7 +2,7 @@ use core::mem;
 use extension_trait::extension_trait;
 use num_bigint::BigUint;
 use std::sync::Arc;
-use sway_ast::literal::{LitChar, LitInt, LitIntType, LitString, Literal};
+use sway_ast::literal::{LitByteString, LitChar, LitInt, LitIntType, LitString, Literal};
 use sway_ast::token::{
     Comment, CommentKind, CommentedGroup, CommentedTokenStream, CommentedTokenTree, Delimiter,
     DocComment, DocStyle, GenericTokenTree, Punct, PunctKind, Spacing, TokenStream,
@@ -87,7 +87,8 @@ type Result<T> = core::result::Result<T, ErrorEmitted>;
 struct Lexer<'l> {
     handler: &'l Handler,
     src: &'l Arc<str>,
-    source_id: &'l Option<SourceId>,
+    // A span in `src`, which the spans of the tokens are created from.
+    src_span: Span,
     stream: &'l mut CharIndices<'l>,
 }
 
@@ -116,7 +117,7 @@ pub fn lex_commented(
     let mut l = Lexer {
         handler,
         src,
-        source_id,
+        src_span: Span::new(src.clone(), start, start, *source_id).unwrap(),
         stream,
     };
     let mut gather_module_docs = false;
@@ -196,6 +197,11 @@ pub fn lex_commented(
             gather_module_docs = false;
         }
 
+        // Byte strings, e.g. `b"abc"`, start like identifiers.
+        if let Some(token) = lex_byte_string(&mut l, index, character)? {
+            token_trees.push(token);
+            continue;
+        }
         if character.is_xid_start() || character == '_' {
             // Raw identifier, e.g., `r#foo`? Then mark as such, stripping the prefix `r#`.
             let is_raw_ident = character == 'r' && matches!(l.stream.peek(), Some((_, '#')));
@@ -478,6 +484,57 @@ fn lex_string(
     Ok(Some(CommentedTokenTree::Tree(literal.into())))
 }
 
+fn lex_byte_string(
+    l: &mut Lexer<'_>,
+    index: usize,
+    character: char,
+) -> Result<Option<CommentedTokenTree>> {
+    if character != 'b' || !matches!(l.stream.peek(), Some((_, '"'))) {
+        return Ok(None);
+    }
+    let _ = l.stream.next();
+    let mut parsed = Vec::new();
+    loop {
+        let unclosed_string_lit = |l: &Lexer<'_>, end| {
+            error(
+                l.handler,
+                LexError {
+                    kind: LexErrorKind::UnclosedStringLiteral { position: index },
+                    span: span(l, index, end),
+                },
+            )
+        };
+        let (position, next_character) = l
+            .stream
+            .next()
+            .ok_or_else(|| unclosed_string_lit(l, l.src.len() - 1))?;
+        let invalid_character = |l: &Lexer<'_>, span| {
+            let kind = LexErrorKind::InvalidByteStringCharacter { position };
+            error(l.handler, LexError { kind, span });
+        };
+        match next_character {
+            '"' => break,
+            '\\' => {
+                let is_unicode_escape = matches!(l.stream.peek(), Some((_, 'u')));
+                let parsed_character = parse_escape_code(l)
+                    .map_err(|e| e.unwrap_or_else(|| unclosed_string_lit(l, l.src.len())))?;
+                if is_unicode_escape {
+                    let span = span_until(l, position);
+                    invalid_character(l, span);
+                } else {
+                    // Every other escape, including `\xNN`, is at most `\xFF`.
+                    parsed.push(parsed_character as u8);
+                }
+            }
+            _ if next_character.is_ascii() => parsed.push(next_character as u8),
+            _ => invalid_character(l, span_one(l, position, next_character)),
+        }
+    }
+    let span = span_until(l, index);
+    let literal = Literal::ByteString(LitByteString { span, parsed });
+    Ok(Some(CommentedTokenTree::Tree(literal.into())))
+}
+
 fn lex_char(
     l: &mut Lexer<'_>,
     index: usize,
@@ -648,6 +705,8 @@ fn lex_int_lit(
     let (big_uint, end_opt) = if digit == 0 {
         let prefixed_int_lit = |l: &mut Lexer<'_>, radix| {
             let _ = l.stream.next();
+            // Like the digits themselves, the first digit may be preceded by underscores.
+            while l.stream.next_if(|(_, c)| *c == '_').is_some() {}
             let d = l.stream.next();
             let incomplete_int_lit = |end| {
                 let kind = match radix {
@@ -664,7 +723,23 @@ fn lex_int_lit(
                 .to_digit(radix)
                 .ok_or_else(|| incomplete_int_lit(digit_pos))?;
             let mut big_uint = BigUint::from(radix_digit);
-            let end_opt = parse_digits(&mut big_uint, l, radix);
+            let mut end_opt = parse_digits(&mut big_uint, l, radix);
+            // Decimal digits out of range of the radix, e.g. the `8` of `0o78`, would otherwise be
+            // reported as an invalid suffix. Report them as such and skip them instead.
+            while let Some((position, digit)) =
+                l.stream.next_if(|(_, c)| c.is_ascii_digit() || *c == '_')
+            {
+                if digit != '_' {
+                    let kind = LexErrorKind::InvalidDigitInIntLiteral {
+                        position,
+                        digit,
+                        radix,
+                    };
+                    let span = span_one(l, position, digit);
+                    error(l.handler, LexError { kind, span });
+                }
+                end_opt = l.stream.peek().map(|&(end, _)| end);
+            }
             Ok((big_uint, end_opt))
         };
 
@@ -775,7 +850,7 @@ fn span_one(l: &Lexer<'_>, start: usize, c: char) -> Span {
 }
 
 fn span(l: &Lexer<'_>, start: usize, end: usize) -> Span {
-    Span::new(l.src.clone(), start, end, *l.source_id).unwrap()
+    l.src_span.with_range(start, end).unwrap()
 }
 
 /// Emit a lexer error.
@@ -790,7 +865,7 @@ mod tests {
     use assert_matches::assert_matches;
     use std::sync::Arc;
     use sway_ast::{
-        literal::{LitChar, Literal},
+        literal::{LitByteString, LitChar, LitInt, LitIntType, LitString, Literal},
         token::{
             Comment, CommentKind, CommentedTokenTree, CommentedTree, DocComment, DocStyle,
             TokenTree,
@@ -996,4 +1071,89 @@ mod tests {
         );
         assert_eq!(tts.next(), None);
     }
+
+    #[test]
+    fn lex_int_lits_with_underscores_and_suffixes() {
+        let input = "1_000_000u64 0x_ff_u8 0b_1010 0o777";
+        let handler = Handler::default();
+        let stream = lex(&handler, &Arc::from(input), 0, input.len(), None).unwrap();
+        assert!(handler.consume().0.is_empty());
+        let lits = stream
+            .token_trees()
+            .iter()
+            .map(|tt| match tt {
+                TokenTree::Literal(Literal::Int(LitInt { parsed, ty_opt, .. })) => (
+                    u64::try_from(parsed).unwrap(),
+                    ty_opt.as_ref().map(|(ty, _)| ty.clone()),
+                ),
+                _ => panic!("expected int literal"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lits,
+            vec![
+                (1_000_000, Some(LitIntType::U64)),
+                (0xff, Some(LitIntType::U8)),
+                (0b1010, None),
+                (0o777, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_int_lit_invalid_digit() {
+        let input = "0o78u8";
+        let handler = Handler::default();
+        let stream = lex(&handler, &Arc::from(input), 0, input.len(), None).unwrap();
+        let (errors, _) = handler.consume();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "invalid digit '8' in octal int literal"
+        );
+        let mut tts = stream.token_trees().iter();
+        assert_matches!(
+            tts.next(),
+            Some(TokenTree::Literal(Literal::Int(LitInt {
+                ty_opt: Some((LitIntType::U8, _)),
+                ..
+            })))
+        );
+        assert_eq!(tts.next(), None);
+    }
+
+    #[test]
+    fn lex_string_and_byte_string_escapes() {
+        let input = r#""a\n\t\x41\u{e9}" b"\x00\xffz\"""#;
+        let handler = Handler::default();
+        let stream = lex(&handler, &Arc::from(input), 0, input.len(), None).unwrap();
+        assert!(handler.consume().0.is_empty());
+        let mut tts = stream.token_trees().iter();
+        assert_matches!(
+            tts.next(),
+            Some(TokenTree::Literal(Literal::String(LitString { parsed, .. })))
+                if parsed == "a\n\tA\u{e9}"
+        );
+        assert_matches!(
+            tts.next(),
+            Some(TokenTree::Literal(Literal::ByteString(LitByteString { parsed, .. })))
+                if parsed == &[0x00, 0xff, b'z', b'"']
+        );
+        assert_eq!(tts.next(), None);
+    }
+
+    #[test]
+    fn lex_byte_string_invalid_characters() {
+        let input = r#"b"\u{41}é""#;
+        let handler = Handler::default();
+        let stream = lex(&handler, &Arc::from(input), 0, input.len(), None).unwrap();
+        assert_eq!(handler.consume().0.len(), 2);
+        let mut tts = stream.token_trees().iter();
+        assert_matches!(
+            tts.next(),
+            Some(TokenTree::Literal(Literal::ByteString(LitByteString { parsed, .. })))
+                if parsed.is_empty()
+        );
+        assert_eq!(tts.next(), None);
+    }
 }
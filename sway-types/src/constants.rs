@@ -27,6 +27,9 @@ pub const DESTRUCTURE_PREFIX: &str = "__destructure_";
 /// The default prefix for the compiler generated names of match
 pub const MATCH_RETURN_VAR_NAME_PREFIX: &str = "__match_return_var_name_";
 
+/// The default prefix for the compiler generated names of the declarations of `if let` chains
+pub const IF_LET_CHAIN_NAME_PREFIX: &str = "__if_let_chain_";
+
 /// The valid attribute strings related to storage and purity.
 pub const STORAGE_PURITY_ATTRIBUTE_NAME: &str = "storage";
 pub const STORAGE_PURITY_READ_NAME: &str = "read";
@@ -37,6 +40,9 @@ pub const INLINE_ATTRIBUTE_NAME: &str = "inline";
 pub const INLINE_NEVER_NAME: &str = "never";
 pub const INLINE_ALWAYS_NAME: &str = "always";
 
+/// The attribute folding calls of pure functions with constant arguments into their results.
+pub const MEMOIZE_ATTRIBUTE_NAME: &str = "memoize";
+
 /// The valid attribute strings related to documentation control.
 pub const DOC_ATTRIBUTE_NAME: &str = "doc";
 
@@ -46,17 +52,55 @@ pub const DOC_COMMENT_ATTRIBUTE_NAME: &str = "doc-comment";
 /// The attribute used for Sway in-language unit tests.
 pub const TEST_ATTRIBUTE_NAME: &str = "test";
 
+/// The attributes used for the functions run before and after each unit test.
+pub const BEFORE_EACH_ATTRIBUTE_NAME: &str = "before_each";
+pub const AFTER_EACH_ATTRIBUTE_NAME: &str = "after_each";
+
+/// The attribute used for the functions checking an invariant after each unit test.
+pub const INVARIANT_ATTRIBUTE_NAME: &str = "invariant";
+
+/// The specification attributes, with the conditions checked when a function is entered and when
+/// it returns, in which `result` is the returned value.
+pub const REQUIRES_ATTRIBUTE_NAME: &str = "requires";
+pub const ENSURES_ATTRIBUTE_NAME: &str = "ensures";
+pub const ENSURES_RESULT_NAME: &str = "result";
+
+/// The revert codes of the failed conditions of specification attributes, as in
+/// `std::error_signals`.
+pub const FAILED_REQUIRES_SIGNAL: u64 = 0xffff_ffff_ffff_0006;
+pub const FAILED_ENSURES_SIGNAL: u64 = 0xffff_ffff_ffff_0007;
+
+/// The attribute validating the arguments of an ABI method parameter, e.g.
+/// `#[param(min = 1, max = 100)]`, and its arguments.
+pub const PARAM_ATTRIBUTE_NAME: &str = "param";
+pub const PARAM_MIN_ARG_NAME: &str = "min";
+pub const PARAM_MAX_ARG_NAME: &str = "max";
+
+/// The revert code of an argument failing the validation of its parameter, as in
+/// `std::error_signals`.
+pub const FAILED_PARAM_SIGNAL: u64 = 0xffff_ffff_ffff_0008;
+
+/// The revert code of a checked arithmetic operation which overflows, as in `std::error_signals`.
+pub const FAILED_OVERFLOW_SIGNAL: u64 = 0xffff_ffff_ffff_0009;
+
+/// The attribute guarding ABI methods against being called while the contract is paused, and the
+/// absolute path of the guard, as in `std::pausable`.
+pub const WHEN_NOT_PAUSED_ATTRIBUTE_NAME: &str = "when_not_paused";
+pub const WHEN_NOT_PAUSED_GUARD_PATH: &[&str] = &["std", "pausable", "require_not_paused"];
+
 /// The valid attribute string used for payable functions.
 pub const PAYABLE_ATTRIBUTE_NAME: &str = "payable";
 
 /// The valid attribute strings related to allow.
 pub const ALLOW_ATTRIBUTE_NAME: &str = "allow";
 pub const ALLOW_DEAD_CODE_NAME: &str = "dead_code";
+pub const ALLOW_CEI_VIOLATION_NAME: &str = "cei_violation";
 
 /// The valid attribute strings related to conditional compilation.
 pub const CFG_ATTRIBUTE_NAME: &str = "cfg";
 pub const CFG_TARGET_ARG_NAME: &str = "target";
 pub const CFG_PROGRAM_TYPE_ARG_NAME: &str = "program_type";
+pub const CFG_TEST_ARG_NAME: &str = "test";
 
 /// The list of valid attributes.
 pub const VALID_ATTRIBUTE_NAMES: &[&str] = &[
@@ -64,8 +108,15 @@ pub const VALID_ATTRIBUTE_NAMES: &[&str] = &[
     DOC_ATTRIBUTE_NAME,
     DOC_COMMENT_ATTRIBUTE_NAME,
     TEST_ATTRIBUTE_NAME,
+    BEFORE_EACH_ATTRIBUTE_NAME,
+    AFTER_EACH_ATTRIBUTE_NAME,
+    INVARIANT_ATTRIBUTE_NAME,
+    REQUIRES_ATTRIBUTE_NAME,
+    ENSURES_ATTRIBUTE_NAME,
     INLINE_ATTRIBUTE_NAME,
+    MEMOIZE_ATTRIBUTE_NAME,
     PAYABLE_ATTRIBUTE_NAME,
+    WHEN_NOT_PAUSED_ATTRIBUTE_NAME,
     ALLOW_ATTRIBUTE_NAME,
     CFG_ATTRIBUTE_NAME,
 ];
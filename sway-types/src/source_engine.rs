@@ -1,6 +1,10 @@
-use std::{collections::HashMap, path::PathBuf, sync::RwLock};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock, Weak},
+};
 
-use crate::SourceId;
+use crate::{SourceId, Span};
 
 /// The Source Engine manages a relationship between file paths and their corresponding
 /// integer-based source IDs. Additionally, it maintains a reserve - a map that traces
@@ -8,6 +12,9 @@ use crate::SourceId;
 /// system is to enable clients that need to reference a file path to do so using an
 /// integer-based ID. This numeric representation can be stored more efficiently as
 /// a key in a hashmap.
+/// It also knows the text of each source, which the spans in the source share. The engine doesn't
+/// keep the texts alive itself: a text is freed along with the last span pointing into it, e.g.
+/// once the entries of the engines created from it have been collected.
 /// The Source Engine is designed to be thread-safe. Its internal structures are
 /// secured by the RwLock mechanism. This allows its functions to be invoked using
 /// a straightforward non-mutable reference, ensuring safe concurrent access.
@@ -16,6 +23,7 @@ pub struct SourceEngine {
     next_id: RwLock<u32>,
     source_map: RwLock<HashMap<PathBuf, SourceId>>,
     path_map: RwLock<HashMap<SourceId, PathBuf>>,
+    texts: RwLock<HashMap<SourceId, Weak<SourceText>>>,
 }
 
 impl SourceEngine {
@@ -56,4 +64,44 @@ impl SourceEngine {
             .unwrap()
             .clone()
     }
+
+    /// This function records `text` as the text of the specified source ID and returns an empty
+    /// span at its start, which the spans in the source can be created from with
+    /// [Span::with_range]. If the source already has the same text, e.g. when the language server
+    /// compiles an unchanged file again, the spans share the text they already point into.
+    pub fn set_source_text(&self, source_id: SourceId, text: Arc<str>) -> Span {
+        let text = Arc::new(SourceText {
+            text,
+            source_id: Some(source_id),
+        });
+        let mut texts = self.texts.write().unwrap();
+        let text = match texts.get(&source_id).and_then(Weak::upgrade) {
+            Some(recorded) if recorded.text == text.text => recorded,
+            _ => {
+                texts.insert(source_id, Arc::downgrade(&text));
+                text
+            }
+        };
+        Span::from_source_text(text, 0, 0)
+    }
+
+    /// This function provides an empty span at the start of the text of a specified source ID,
+    /// if it's still pointed into.
+    pub fn get_source_text(&self, source_id: &SourceId) -> Option<Span> {
+        self.texts
+            .read()
+            .unwrap()
+            .get(source_id)
+            .and_then(Weak::upgrade)
+            .map(|text| Span::from_source_text(text, 0, 0))
+    }
+}
+
+/// A text spans point into, along with the source it was read from, if any.
+///
+/// Spans share the text they point into, which is freed once the last of them is dropped.
+#[derive(Debug)]
+pub(crate) struct SourceText {
+    pub(crate) text: Arc<str>,
+    pub(crate) source_id: Option<SourceId>,
 }
@@ -1,10 +1,15 @@
 use serde::Serialize;
 
-use crate::SourceId;
+use crate::{SourceId, SourceText};
 
 use {
     lazy_static::lazy_static,
-    std::{cmp, fmt, hash::Hash, sync::Arc},
+    std::{
+        cmp::{self, Ordering},
+        fmt,
+        hash::{Hash, Hasher},
+        sync::Arc,
+    },
 };
 
 lazy_static! {
@@ -68,34 +73,53 @@ impl Position {
 }
 
 /// Represents a span of the source code in a specific file.
-#[derive(Clone, Ord, PartialOrd)]
+///
+/// Spans only hold the text they point into, shared with the other spans in it and the source
+/// engine, and their byte offsets in it. They are compared, ordered and hashed by their source and
+/// offsets alone, never by the text, which keeps that cheap. Spans without a source, e.g. those of
+/// generated code, are therefore equal whenever their offsets are.
+#[derive(Clone)]
 pub struct Span {
-    // The original source code.
-    src: Arc<str>,
-    // The byte position in the string of the start of the span.
-    start: usize,
-    // The byte position in the string of the end of the span.
-    end: usize,
-    // A reference counted pointer to the file from which this span originated.
-    source_id: Option<SourceId>,
+    // The text the span points into, along with its source.
+    text: Arc<SourceText>,
+    // The byte position in the text of the start of the span.
+    start: u32,
+    // The byte position in the text of the end of the span.
+    end: u32,
 }
 
-impl Hash for Span {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.start.hash(state);
-        self.end.hash(state);
-        self.source_id.hash(state);
+impl Span {
+    fn key(&self) -> (Option<&SourceId>, u32, u32) {
+        (self.source_id(), self.start, self.end)
     }
 }
 
 impl PartialEq for Span {
     fn eq(&self, other: &Self) -> bool {
-        self.start == other.start && self.end == other.end && self.source_id == other.source_id
+        self.key() == other.key()
     }
 }
 
 impl Eq for Span {}
 
+impl Hash for Span {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for Span {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Span {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
 impl Serialize for Span {
     // Serialize a tuple two fields: `start` and `end`.
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -111,8 +135,8 @@ impl Serialize for Span {
 impl From<Span> for std::ops::Range<usize> {
     fn from(value: Span) -> Self {
         Self {
-            start: value.start,
-            end: value.end,
+            start: value.start(),
+            end: value.end(),
         }
     }
 }
@@ -125,10 +149,27 @@ impl Span {
     pub fn new(src: Arc<str>, start: usize, end: usize, source: Option<SourceId>) -> Option<Span> {
         let _ = src.get(start..end)?;
         Some(Span {
-            src,
-            start,
-            end,
-            source_id: source,
+            start: u32::try_from(start).ok()?,
+            end: u32::try_from(end).ok()?,
+            text: Arc::new(SourceText {
+                text: src,
+                source_id: source,
+            }),
+        })
+    }
+
+    pub(crate) fn from_source_text(text: Arc<SourceText>, start: u32, end: u32) -> Span {
+        Span { text, start, end }
+    }
+
+    /// Returns the span from `start` to `end` in the text this span points into, if the range is
+    /// within it. Unlike [Span::new], this doesn't need to look up the text.
+    pub fn with_range(&self, start: usize, end: usize) -> Option<Span> {
+        let _ = self.input().get(start..end)?;
+        Some(Span {
+            text: self.text.clone(),
+            start: u32::try_from(start).ok()?,
+            end: u32::try_from(end).ok()?,
         })
     }
 
@@ -138,27 +179,27 @@ impl Span {
     }
 
     pub fn src(&self) -> &Arc<str> {
-        &self.src
+        &self.text.text
     }
 
     pub fn start(&self) -> usize {
-        self.start
+        self.start as usize
     }
 
     pub fn end(&self) -> usize {
-        self.end
+        self.end as usize
     }
 
     pub fn source_id(&self) -> Option<&SourceId> {
-        self.source_id.as_ref()
+        self.text.source_id.as_ref()
     }
 
     pub fn start_pos(&self) -> Position {
-        Position::new(self.src.clone(), self.start).unwrap()
+        Position::new(self.src().clone(), self.start()).unwrap()
     }
 
     pub fn end_pos(&self) -> Position {
-        Position::new(self.src.clone(), self.end).unwrap()
+        Position::new(self.src().clone(), self.end()).unwrap()
     }
 
     pub fn split(&self) -> (Position, Position) {
@@ -172,21 +213,20 @@ impl Span {
     }
 
     pub fn as_str(&self) -> &str {
-        &self.src[self.start..self.end]
+        &self.input()[self.start()..self.end()]
     }
 
     pub fn input(&self) -> &str {
-        &self.src
+        &self.text.text
     }
 
     pub fn trim(self) -> Span {
         let start_delta = self.as_str().len() - self.as_str().trim_start().len();
         let end_delta = self.as_str().len() - self.as_str().trim_end().len();
         Span {
-            src: self.src,
-            start: self.start + start_delta,
-            end: self.end - end_delta,
-            source_id: self.source_id,
+            text: self.text,
+            start: self.start + start_delta as u32,
+            end: self.end - end_delta as u32,
         }
     }
 
@@ -194,15 +234,14 @@ impl Span {
     /// only be used on spans that are actually next to each other.
     pub fn join(s1: Span, s2: Span) -> Span {
         assert!(
-            Arc::ptr_eq(&s1.src, &s2.src) && s1.source_id == s2.source_id,
+            s1.source_id() == s2.source_id() && s1.src() == s2.src(),
             "Spans from different files cannot be joined.",
         );
 
         Span {
-            src: s1.src,
+            text: s1.text,
             start: cmp::min(s1.start, s2.start),
             end: cmp::max(s1.end, s2.end),
-            source_id: s1.source_id,
         }
     }
 
@@ -226,8 +265,8 @@ impl fmt::Debug for Span {
     #[cfg(not(feature = "no-span-debug"))]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("Span")
-            .field("src (ptr)", &self.src.as_ptr())
-            .field("source_id", &self.source_id)
+            .field("src (ptr)", &self.src().as_ptr())
+            .field("source_id", &self.source_id())
             .field("start", &self.start)
             .field("end", &self.end)
             .field("as_str()", &self.as_str())
@@ -257,3 +296,83 @@ impl From<(usize, usize)> for LineCol {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hash_of(span: &Span) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        let mut hasher = DefaultHasher::new();
+        span.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn spans_are_compared_by_source_and_offsets() {
+        let source_id = crate::SourceEngine::default().get_source_id(&"main.sw".into());
+        let src: Arc<str> = Arc::from("let x = 42;");
+        let span = Span::new(src, 4, 5, Some(source_id)).unwrap();
+        // E.g. the span of the same node before the file was edited.
+        let edited = Span::new(Arc::from("let y = 43;"), 4, 5, Some(source_id)).unwrap();
+        assert_eq!(span, edited);
+        assert_eq!(hash_of(&span), hash_of(&edited));
+        assert_eq!(span.cmp(&edited), Ordering::Equal);
+
+        let later = span.with_range(8, 10).unwrap();
+        assert!(span < later);
+        assert_ne!(hash_of(&span), hash_of(&later));
+        let detached = Span::new(Arc::from("let x = 42;"), 8, 10, None).unwrap();
+        assert!(detached < span);
+    }
+
+    #[test]
+    fn spans_in_texts_with_the_same_contents_are_equal() {
+        let src: Arc<str> = Arc::from("let x = 42;");
+        let span = Span::new(src, 4, 5, None).unwrap();
+        let same = Span::new(Arc::from("let x = 42;"), 4, 5, None).unwrap();
+        assert_eq!(span, same);
+        assert_eq!(hash_of(&span), hash_of(&same));
+        assert!(!Arc::ptr_eq(span.src(), same.src()));
+
+        let sub_span = span.with_range(8, 10).unwrap();
+        assert_eq!(sub_span.as_str(), "42");
+        assert_eq!(Span::join(span, sub_span.clone()).as_str(), "x = 42");
+        assert!(sub_span.with_range(8, 12).is_none());
+    }
+
+    #[test]
+    fn spans_in_different_sources_differ() {
+        let source_id = crate::SourceEngine::default().get_source_id(&"main.sw".into());
+        let src: Arc<str> = Arc::from("fn f() {}");
+        let span = Span::new(src.clone(), 3, 4, None).unwrap();
+        let other = Span::new(src, 3, 4, Some(source_id)).unwrap();
+        assert_ne!(span, other);
+        assert_eq!(span.as_str(), other.as_str());
+        assert_eq!(other.source_id(), Some(&source_id));
+    }
+
+    #[test]
+    fn source_texts_are_shared_until_no_span_points_into_them() {
+        let engine = crate::SourceEngine::default();
+        let source_id = engine.get_source_id(&"main.sw".into());
+        let src: Arc<str> = Arc::from("fn f() {}");
+        let text = Arc::downgrade(&src);
+
+        let span = engine
+            .set_source_text(source_id, src)
+            .with_range(3, 4)
+            .unwrap();
+        let same = engine.set_source_text(source_id, Arc::from("fn f() {}"));
+        assert!(Arc::ptr_eq(span.src(), same.src()));
+        assert_eq!(span.source_id(), Some(&source_id));
+        assert_eq!(engine.get_source_text(&source_id).unwrap().as_str(), "");
+
+        // The text is freed along with the spans once the source has a different text.
+        let other = engine.set_source_text(source_id, Arc::from("fn g() {}"));
+        drop((span, same));
+        assert!(text.upgrade().is_none());
+        let current = engine.get_source_text(&source_id).unwrap();
+        assert!(Arc::ptr_eq(current.src(), other.src()));
+    }
+}
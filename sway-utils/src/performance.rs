@@ -5,12 +5,49 @@ pub struct PerformanceMetric {
     pub phase: String,
     pub elapsed: f64,
     pub memory_usage: Option<u64>,
+    /// The peak resident set size of the whole process at the end of the phase, in bytes, where
+    /// the platform reports it. It includes the memory of anything compiled in parallel.
+    pub process_peak_rss: Option<u64>,
+}
+
+/// The time taken by the runs of an IR pass over a package.
+#[derive(Debug, Serialize)]
+pub struct PassMetric {
+    pub pass: String,
+    /// The wall time of all of the runs of the pass, in seconds.
+    pub elapsed: f64,
+    pub runs: usize,
 }
 
 #[derive(Debug, Default, Serialize)]
 pub struct PerformanceData {
     pub bytecode_size: usize,
     pub metrics: Vec<PerformanceMetric>,
+    /// The IR passes, in the order they first ran.
+    pub passes: Vec<PassMetric>,
+}
+
+/// The peak resident set size of this process so far, in bytes.
+///
+/// Only Linux reports it, as the `VmHWM` of `/proc/self/status`.
+pub fn peak_rss() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let kib = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmHWM:"))?
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(kib * 1024)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
 }
 
 #[macro_export]
@@ -26,20 +63,21 @@ macro_rules! time_expr {
                     println!("  Time elapsed to {}: {:?}", $description, elapsed);
                 }
                 if cfg.metrics_outfile.is_some() {
-                    #[cfg(not(target_os = "macos"))]
+                    #[cfg(not(any(target_os = "macos", target_arch = "wasm32")))]
                     let memory_usage = {
                         use sysinfo::{System, SystemExt};
                         let mut sys = System::new();
                         sys.refresh_system();
                         Some(sys.used_memory())
                     };
-                    #[cfg(target_os = "macos")]
+                    #[cfg(any(target_os = "macos", target_arch = "wasm32"))]
                     let memory_usage = None;
 
                     $data.metrics.push(PerformanceMetric {
                         phase: $key.to_string(),
                         elapsed: elapsed.as_secs_f64(),
                         memory_usage,
+                        process_peak_rss: $crate::peak_rss(),
                     });
                 }
                 output
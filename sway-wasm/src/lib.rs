@@ -0,0 +1,277 @@
+//! A JavaScript API for the Sway compiler, e.g. for an in-browser playground.
+//!
+//! Sources are passed in as a JSON object mapping virtual file paths to module sources, the root
+//! module being at `src/main.sw` for programs and `src/lib.sw` for libraries. Results are
+//! returned as JSON strings.
+//!
+//! ```js
+//! import init, { Compiler } from "sway-wasm";
+//!
+//! await init();
+//! const compiler = new Compiler();
+//! compiler.addLibrary("core", JSON.stringify(coreSources));
+//! const output = JSON.parse(compiler.compile(JSON.stringify({
+//!   "src/main.sw": "script; fn main() -> u64 { 42 }",
+//! })));
+//! ```
+
+use serde::Serialize;
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use sway_core::{
+    asm_generation::ProgramABI, compile_sources_to_bytecode, compile_to_ast, language::Visibility,
+    namespace, BuildConfig, BuildTarget, CancellationToken, CompileResult, Engines,
+};
+use sway_error::{error::CompileError, warning::CompileWarning};
+use sway_types::{Ident, Span, Spanned};
+use sway_utils::PerformanceData;
+use wasm_bindgen::prelude::*;
+
+const CORE: &str = "core";
+const STD: &str = "std";
+const PRELUDE: &str = "prelude";
+const LIBRARY_ROOT_MODULE: &str = "src/lib.sw";
+const PROGRAM_ROOT_MODULE: &str = "src/main.sw";
+const PACKAGE_NAME: &str = "playground";
+
+/// Compiles Sway programs against a set of libraries that are held in memory.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Compiler {
+    engines: Engines,
+    /// The namespaces of the added libraries, in the order they were added.
+    libraries: Vec<(String, namespace::Module)>,
+}
+
+#[derive(Serialize)]
+struct Diagnostic {
+    severity: &'static str,
+    /// The stable code of the error or warning, e.g. `E0001`.
+    code: String,
+    message: String,
+    path: Option<String>,
+    start: Position,
+    end: Position,
+}
+
+#[derive(Serialize)]
+struct Position {
+    line: usize,
+    col: usize,
+}
+
+#[derive(Serialize)]
+struct CheckOutput {
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize)]
+struct CompileOutput {
+    diagnostics: Vec<Diagnostic>,
+    /// The hex encoded bytecode, if the compilation succeeded.
+    bytecode: Option<String>,
+    abi: Option<serde_json::Value>,
+}
+
+#[wasm_bindgen]
+impl Compiler {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Compiler {
+        Self::default()
+    }
+
+    /// Compiles the library package `name`, e.g. `core` or `std`, and makes it available to
+    /// everything compiled afterwards.
+    ///
+    /// Libraries must be added after the libraries they depend on. Fails with the formatted
+    /// errors if the library does not compile.
+    #[wasm_bindgen(js_name = addLibrary)]
+    pub fn add_library(&mut self, name: &str, sources: &str) -> Result<(), JsError> {
+        let sources = parse_sources(sources)?;
+        let res = self.compile_to_ast(name, sources, LIBRARY_ROOT_MODULE);
+        let module = match res
+            .value
+            .as_ref()
+            .and_then(|programs| programs.typed.as_ref())
+        {
+            Some(typed_program) if res.errors.is_empty() => {
+                let mut module = typed_program.root.namespace.module().clone();
+                module.name = Some(Ident::new_no_span(name.to_string()));
+                module
+            }
+            _ => {
+                let errors = res
+                    .errors
+                    .iter()
+                    .map(|error| self.diagnostic_for_error(error).message)
+                    .collect::<Vec<_>>();
+                return Err(JsError::new(&format!(
+                    "failed to compile library `{name}`:\n{}",
+                    errors.join("\n")
+                )));
+            }
+        };
+        self.libraries.push((name.to_string(), module));
+        Ok(())
+    }
+
+    /// Parses and type checks the program, returning its diagnostics.
+    pub fn check(&self, sources: &str) -> Result<String, JsError> {
+        let sources = parse_sources(sources)?;
+        let res = self.compile_to_ast(PACKAGE_NAME, sources, PROGRAM_ROOT_MODULE);
+        let output = CheckOutput {
+            diagnostics: self.diagnostics(&res.warnings, &res.errors),
+        };
+        Ok(serde_json::to_string(&output)?)
+    }
+
+    /// Compiles the program to bytecode, returning the bytecode and ABI along with the
+    /// diagnostics.
+    pub fn compile(&self, sources: &str) -> Result<String, JsError> {
+        let sources = parse_sources(sources)?;
+        let res = compile_sources_to_bytecode(
+            &self.engines,
+            sources,
+            PathBuf::from(PROGRAM_ROOT_MODULE),
+            self.dependency_namespace(PACKAGE_NAME),
+            BuildTarget::Fuel,
+            PACKAGE_NAME,
+            // Compilation is synchronous in the browser, so it is never cancelled.
+            &CancellationToken::new(),
+        )
+        .expect("the compilation is never cancelled");
+
+        let diagnostics = self.diagnostics(&res.warnings, &res.errors);
+        let output = match res.value {
+            Some(compiled) if res.errors.is_empty() => CompileOutput {
+                diagnostics,
+                bytecode: Some(hex_encode(&compiled.bytecode.bytecode)),
+                abi: match compiled.abi {
+                    ProgramABI::Fuel(abi) => Some(serde_json::to_value(abi)?),
+                    ProgramABI::Evm(_) | ProgramABI::MidenVM(()) => None,
+                },
+            },
+            _ => CompileOutput {
+                diagnostics,
+                bytecode: None,
+                abi: None,
+            },
+        };
+        Ok(serde_json::to_string(&output)?)
+    }
+}
+
+impl Compiler {
+    fn compile_to_ast(
+        &self,
+        package_name: &str,
+        sources: HashMap<PathBuf, Arc<str>>,
+        root_module: &str,
+    ) -> CompileResult<sway_core::Programs> {
+        let root_module = PathBuf::from(root_module);
+        let Some(root_source) = sources.get(&root_module).cloned() else {
+            return CompileResult::new(
+                None,
+                vec![],
+                vec![CompileError::FileCouldNotBeRead {
+                    span: Span::dummy(),
+                    file_path: root_module.to_string_lossy().to_string(),
+                    stringified_error: "no source was provided for this path".to_string(),
+                }],
+            );
+        };
+        let build_config =
+            BuildConfig::root_from_in_memory_sources(root_module, sources, BuildTarget::Fuel);
+        compile_to_ast(
+            &self.engines,
+            root_source,
+            self.dependency_namespace(package_name),
+            Some(&build_config),
+            package_name,
+            &mut PerformanceData::default(),
+        )
+    }
+
+    /// Builds the initial namespace of the package `package_name`, with all added libraries as
+    /// dependencies, the same way `forc` does for packages depending on them.
+    fn dependency_namespace(&self, package_name: &str) -> namespace::Module {
+        let mut namespace = namespace::Module::default();
+        namespace.is_external = true;
+        namespace.name = Some(Ident::new_no_span(package_name.to_string()));
+        namespace.visibility = Visibility::Public;
+
+        for (name, module) in &self.libraries {
+            namespace.insert_submodule(name.clone(), module.clone());
+        }
+        // The preludes are imported into every package except the libraries providing them.
+        for library in [CORE, STD] {
+            if package_name != library && self.libraries.iter().any(|(name, _)| name == library) {
+                // Imports from a library's own prelude are validated when compiling it.
+                let _ = namespace.star_import_with_reexports(
+                    &[library, PRELUDE].map(|s| Ident::new_no_span(s.into())),
+                    &[],
+                    &self.engines,
+                    true,
+                );
+            }
+        }
+        namespace
+    }
+
+    fn diagnostics(&self, warnings: &[CompileWarning], errors: &[CompileError]) -> Vec<Diagnostic> {
+        errors
+            .iter()
+            .map(|error| self.diagnostic_for_error(error))
+            .chain(warnings.iter().map(|warning| {
+                self.diagnostic(
+                    "warning",
+                    warning.code(),
+                    warning.to_friendly_warning_string(),
+                    warning.span(),
+                )
+            }))
+            .collect()
+    }
+
+    fn diagnostic_for_error(&self, error: &CompileError) -> Diagnostic {
+        self.diagnostic("error", error.code(), error.to_string(), error.span())
+    }
+
+    fn diagnostic(
+        &self,
+        severity: &'static str,
+        code: &str,
+        message: String,
+        span: Span,
+    ) -> Diagnostic {
+        let (start, end) = span.line_col();
+        Diagnostic {
+            severity,
+            code: code.to_string(),
+            message,
+            path: span
+                .source_id()
+                .map(|id| self.engines.se().get_path(id).to_string_lossy().to_string()),
+            start: Position {
+                line: start.line,
+                col: start.col,
+            },
+            end: Position {
+                line: end.line,
+                col: end.col,
+            },
+        }
+    }
+}
+
+/// Parses a JSON object mapping virtual file paths to module sources.
+fn parse_sources(sources: &str) -> Result<HashMap<PathBuf, Arc<str>>, JsError> {
+    let sources: HashMap<PathBuf, String> = serde_json::from_str(sources)?;
+    Ok(sources
+        .into_iter()
+        .map(|(path, source)| (path, Arc::from(source)))
+        .collect())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
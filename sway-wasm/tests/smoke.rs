@@ -0,0 +1,56 @@
+//! Smoke tests of the JavaScript API, run under Node with `wasm-pack test --node sway-wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use serde_json::{json, Value};
+use sway_wasm::Compiler;
+use wasm_bindgen::JsError;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn parse_output(output: Result<String, JsError>) -> Value {
+    match output {
+        Ok(output) => serde_json::from_str(&output).expect("the output is valid JSON"),
+        Err(_) => panic!("the sources were rejected"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn compiles_a_script() {
+    let compiler = Compiler::new();
+    let sources = json!({ "src/main.sw": "script; fn main() -> u64 { 42 }" });
+    let output = parse_output(compiler.compile(&sources.to_string()));
+
+    assert_eq!(output["diagnostics"], json!([]));
+    let bytecode = output["bytecode"].as_str().expect("the script compiled");
+    assert!(!bytecode.is_empty() && bytecode.len() % 2 == 0);
+    assert_eq!(output["abi"]["functions"][0]["name"], "main");
+}
+
+#[wasm_bindgen_test]
+fn compiles_a_script_against_a_library() {
+    let mut compiler = Compiler::new();
+    let library = json!({ "src/lib.sw": "library; pub fn answer() -> u64 { 42 }" });
+    assert!(compiler
+        .add_library("answers", &library.to_string())
+        .is_ok());
+
+    let sources = json!({
+        "src/main.sw": "script; use answers::answer; fn main() -> u64 { answer() }",
+    });
+    let output = parse_output(compiler.compile(&sources.to_string()));
+
+    assert_eq!(output["diagnostics"], json!([]));
+    assert!(output["bytecode"].is_string());
+}
+
+#[wasm_bindgen_test]
+fn reports_diagnostics_with_their_codes_and_positions() {
+    let compiler = Compiler::new();
+    let sources = json!({ "src/main.sw": "script;\nfn main() -> u64 { x }" });
+    let output = parse_output(compiler.check(&sources.to_string()));
+
+    let diagnostic = &output["diagnostics"][0];
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["code"], "E0001");
+    assert_eq!(diagnostic["path"], "src/main.sw");
+    assert_eq!(diagnostic["start"], json!({ "line": 2, "col": 20 }));
+}
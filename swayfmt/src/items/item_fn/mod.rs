@@ -301,6 +301,12 @@ impl Format for FnArg {
         formatted_code: &mut FormattedCode,
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
+        // attributes e.g. `#[param(min = 1)] `, on the same line as the parameter
+        for attr in &self.attribute_list {
+            let mut formatted_attr = FormattedCode::new();
+            attr.format(&mut formatted_attr, formatter)?;
+            write!(formatted_code, "{} ", formatted_attr.trim_end())?;
+        }
         self.pattern.format(formatted_code, formatter)?;
         // `: `
         write!(formatted_code, "{} ", self.colon_token.span().as_str())?;
@@ -380,6 +386,9 @@ impl LeafSpans for FnArgs {
 impl LeafSpans for FnArg {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         let mut collected_spans = Vec::new();
+        for attr in &self.attribute_list {
+            collected_spans.append(&mut attr.leaf_spans());
+        }
         collected_spans.append(&mut self.pattern.leaf_spans());
         collected_spans.push(ByteSpan::from(self.colon_token.span()));
         collected_spans.push(ByteSpan::from(self.ty.span()));
@@ -88,3 +88,11 @@ fmt_test_item!(  fn_nested_if_lets
     };
 }"
 );
+
+fmt_test_item!(  fn_args_with_attributes
+"fn set(self, #[param(min = 1, max = 100)] amount: u64, to: b256) {}",
+            intermediate_whitespace
+"fn set(self,   #[param(min = 1,   max = 100)]
+    amount : u64 , to: b256) {
+}"
+);
@@ -90,3 +90,21 @@ fmt_test_item!(    impl_empty_fn_args
 }
 "
 );
+
+fmt_test_item!(  impl_with_specification_attributes
+"impl Counter {
+    #[requires(amount > 0)]
+    #[ensures(result > self.count)]
+    fn add(self, amount: u64) -> u64 {
+        self.count + amount
+    }
+}",
+            intermediate_whitespace
+"impl Counter {
+    #[requires(  amount>0 )]
+    #[ ensures(result >   self.count)]
+    fn add(self, amount: u64) -> u64 {
+        self.count + amount
+    }
+}"
+);
@@ -23,6 +23,7 @@ impl Format for ItemKind {
             Storage(item_storage) => item_storage.format(formatted_code, formatter),
             Configurable(item_configurable) => item_configurable.format(formatted_code, formatter),
             TypeAlias(item_type_alias) => item_type_alias.format(formatted_code, formatter),
+            Error(_) => Ok(()),
         }
     }
 }
@@ -42,6 +43,7 @@ impl LeafSpans for ItemKind {
             Use(item_use) => item_use.leaf_spans(),
             Configurable(item_configurable) => item_configurable.leaf_spans(),
             TypeAlias(item_type_alias) => item_type_alias.leaf_spans(),
+            Error(spans) => spans.iter().cloned().map(ByteSpan::from).collect(),
         }
     }
 }
@@ -119,6 +119,14 @@ impl Format for AttributeDecl {
                         // ')'
                         Self::close_parenthesis(formatted_code, formatter)?;
                     };
+                    if let Some(condition) = &attr.condition {
+                        // `(`
+                        Self::open_parenthesis(formatted_code, formatter)?;
+                        // format and add the condition e.g. `x > 0`
+                        condition.get().format(formatted_code, formatter)?;
+                        // ')'
+                        Self::close_parenthesis(formatted_code, formatter)?;
+                    };
                     Ok(())
                 },
             )?;
@@ -183,6 +191,9 @@ impl LeafSpans for Attribute {
         if let Some(args) = &self.args {
             collected_spans.append(&mut args.leaf_spans());
         }
+        if let Some(condition) = &self.condition {
+            collected_spans.append(&mut condition.leaf_spans());
+        }
         collected_spans
     }
 }
@@ -8,7 +8,7 @@ use crate::{
 };
 use std::fmt::Write;
 use sway_ast::{
-    expr::asm::{AsmBlock, AsmBlockContents, AsmFinalExpr, AsmRegisterDeclaration},
+    expr::asm::{AsmBlock, AsmBlockContents, AsmFinalExpr, AsmLabel, AsmRegisterDeclaration},
     token::Delimiter,
     Instruction,
 };
@@ -174,12 +174,15 @@ impl Format for AsmBlockContents {
         formatted_code: &mut FormattedCode,
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
-        for (instruction, semicolon_token) in self.instructions.iter() {
+        for (label_opt, instruction, semicolon_token) in self.instructions.iter() {
             write!(
                 formatted_code,
                 "{}",
                 formatter.shape.indent.to_string(&formatter.config)?
             )?;
+            if let Some(label) = label_opt {
+                label.format(formatted_code, formatter)?;
+            }
             instruction.format(formatted_code, formatter)?;
             writeln!(formatted_code, "{}", semicolon_token.span().as_str())?
         }
@@ -201,6 +204,19 @@ impl Format for AsmBlockContents {
     }
 }
 
+impl Format for AsmLabel {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        self.name.format(formatted_code, formatter)?;
+        write!(formatted_code, "{} ", self.colon_token.span().as_str())?;
+
+        Ok(())
+    }
+}
+
 impl Format for AsmFinalExpr {
     fn format(
         &self,
@@ -239,8 +255,12 @@ impl LeafSpans for AsmRegisterDeclaration {
 impl LeafSpans for AsmBlockContents {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         let mut collected_spans = Vec::new();
-        for instruction in &self.instructions {
+        for (label_opt, instruction, semicolon_token) in &self.instructions {
+            if let Some(label) = label_opt {
+                collected_spans.append(&mut label.leaf_spans());
+            }
             collected_spans.append(&mut instruction.leaf_spans());
+            collected_spans.append(&mut semicolon_token.leaf_spans());
         }
         if let Some(final_expr) = &self.final_expr_opt {
             collected_spans.append(&mut final_expr.leaf_spans());
@@ -256,6 +276,15 @@ impl LeafSpans for Instruction {
     }
 }
 
+impl LeafSpans for AsmLabel {
+    fn leaf_spans(&self) -> Vec<ByteSpan> {
+        vec![
+            ByteSpan::from(self.name.span()),
+            ByteSpan::from(self.colon_token.span()),
+        ]
+    }
+}
+
 impl LeafSpans for AsmFinalExpr {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         let mut collected_spans = vec![ByteSpan::from(self.register.span())];
@@ -275,6 +275,19 @@ impl Format for IfCondition {
                 write!(formatted_code, " {} ", eq_token.span().as_str())?;
                 rhs.format(formatted_code, formatter)?;
             }
+            Self::And {
+                lhs,
+                double_ampersand_token,
+                rhs,
+            } => {
+                lhs.format(formatted_code, formatter)?;
+                write!(
+                    formatted_code,
+                    " {} ",
+                    double_ampersand_token.span().as_str()
+                )?;
+                rhs.format(formatted_code, formatter)?;
+            }
         }
 
         Ok(())
@@ -419,6 +432,16 @@ impl LeafSpans for IfCondition {
                 collected_spans.append(&mut rhs.leaf_spans());
                 collected_spans
             }
+            IfCondition::And {
+                lhs,
+                double_ampersand_token,
+                rhs,
+            } => {
+                let mut collected_spans = lhs.leaf_spans();
+                collected_spans.push(ByteSpan::from(double_ampersand_token.span()));
+                collected_spans.append(&mut rhs.leaf_spans());
+                collected_spans
+            }
         }
     }
 }
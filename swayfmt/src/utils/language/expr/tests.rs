@@ -119,6 +119,10 @@ fmt_test_expr!(  small_if_let "if let Result::Ok(x) = x { 100 } else { 1 }",
             intermediate_whitespace "if    let    Result   ::   Ok( x ) =    x {     100 }   else  {    1 }"
 );
 
+fmt_test_expr!(  if_let_chain "if let Some(x) = a && let Some(y) = b { x } else { y }",
+            intermediate_whitespace "if  let Some( x ) = a   &&   let   Some(y)=b {  x }  else {    y }"
+);
+
 fmt_test_expr!(  match_nested_conditional
 "match foo {
     Foo::foo => {
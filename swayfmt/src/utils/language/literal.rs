@@ -14,6 +14,9 @@ impl Format for Literal {
         match self {
             // TODO: do more digging into `Literal` and see if there is more formatting to do.
             Self::String(lit_string) => write!(formatted_code, "{}", lit_string.span.as_str())?,
+            Self::ByteString(lit_byte_string) => {
+                write!(formatted_code, "{}", lit_byte_string.span.as_str())?
+            }
             Self::Char(lit_char) => write!(formatted_code, "{}", lit_char.span.as_str())?,
             Self::Int(lit_int) => {
                 write!(formatted_code, "{}", lit_int.span.as_str())?;
@@ -31,6 +34,7 @@ impl LeafSpans for Literal {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         match self {
             Literal::String(str_lit) => vec![ByteSpan::from(str_lit.span.clone())],
+            Literal::ByteString(byte_str_lit) => vec![ByteSpan::from(byte_str_lit.span.clone())],
             Literal::Char(chr_lit) => vec![ByteSpan::from(chr_lit.span.clone())],
             Literal::Int(int_lit) => vec![ByteSpan::from(int_lit.span.clone())],
             Literal::Bool(bool_lit) => vec![ByteSpan::from(bool_lit.span.clone())],
@@ -14,7 +14,12 @@ use futures::Future;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use regex::{Captures, Regex};
-use std::{fs, io::Read, path::PathBuf, str::FromStr};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use sway_core::{asm_generation::ProgramABI, BuildTarget};
 
 use super::RunConfig;
@@ -138,38 +143,9 @@ pub(crate) fn runs_in_vm(
 ) -> Result<VMExecutionResult> {
     match script.descriptor.target {
         BuildTarget::Fuel => {
-            let storage = MemoryStorage::default();
-
-            let rng = &mut StdRng::seed_from_u64(2322u64);
-            let maturity = 1.into();
-            let script_data = script_data.unwrap_or_default();
-            let block_height = (u32::MAX >> 1).into();
-            let params = ConsensusParameters {
-                // The default max length is 1MB which isn't enough for the bigger tests.
-                max_script_length: 64 * 1024 * 1024,
-                ..ConsensusParameters::DEFAULT
-            };
-
-            let tx = TransactionBuilder::script(script.bytecode.bytes, script_data)
-                .with_params(params)
-                .add_unsigned_coin_input(
-                    rng.gen(),
-                    rng.gen(),
-                    1,
-                    Default::default(),
-                    rng.gen(),
-                    0u32.into(),
-                )
-                .gas_limit(fuel_tx::ConsensusParameters::DEFAULT.max_gas_per_tx)
-                .maturity(maturity)
-                .finalize_checked(block_height, &GasCosts::default());
-
-            let mut i = Interpreter::with_storage(storage, Default::default(), GasCosts::default());
-            let transition = i.transact(tx)?;
-            Ok(VMExecutionResult::Fuel(
-                *transition.state(),
-                transition.receipts().to_vec(),
-            ))
+            let (state, receipts) =
+                runs_bytecode_in_fuel_vm(script.bytecode.bytes, script_data.unwrap_or_default())?;
+            Ok(VMExecutionResult::Fuel(state, receipts))
         }
         BuildTarget::EVM => {
             let mut evm = revm::new();
@@ -221,6 +197,173 @@ pub(crate) fn runs_in_vm(
     }
 }
 
+/// Runs the given script bytecode in a fresh Fuel VM instance.
+///
+/// The transaction is built deterministically so that running the same bytecode and script data
+/// twice produces the same state and receipts.
+fn runs_bytecode_in_fuel_vm(
+    bytecode: Vec<u8>,
+    script_data: Vec<u8>,
+) -> Result<(ProgramState, Vec<Receipt>)> {
+    let storage = MemoryStorage::default();
+
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let maturity = 1.into();
+    let block_height = (u32::MAX >> 1).into();
+    let params = ConsensusParameters {
+        // The default max length is 1MB which isn't enough for the bigger tests.
+        max_script_length: 64 * 1024 * 1024,
+        ..ConsensusParameters::DEFAULT
+    };
+
+    let tx = TransactionBuilder::script(bytecode, script_data)
+        .with_params(params)
+        .add_unsigned_coin_input(
+            rng.gen(),
+            rng.gen(),
+            1,
+            Default::default(),
+            rng.gen(),
+            0u32.into(),
+        )
+        .gas_limit(fuel_tx::ConsensusParameters::DEFAULT.max_gas_per_tx)
+        .maturity(maturity)
+        .finalize_checked(block_height, &GasCosts::default());
+
+    let mut i = Interpreter::with_storage(storage, Default::default(), GasCosts::default());
+    let transition = i.transact(tx)?;
+    Ok((*transition.state(), transition.receipts().to_vec()))
+}
+
+/// Builds the test program with an external `forc` binary, typically a pinned previous release,
+/// and returns the resulting bytecode.
+///
+/// The program is built in a copy of its directory, so that the lock file and `out` directory
+/// written by the external `forc` don't end up among the tests.
+fn compile_to_bytes_with_forc(file_name: &str, forc: &Path) -> Result<Vec<u8>> {
+    println!("Compiling {} with {} ...", file_name.bold(), forc.display());
+    let build_dir = std::env::temp_dir().join(format!(
+        "sway-differential-{}",
+        file_name.replace(['/', '\\'], "_")
+    ));
+    if build_dir.exists() {
+        fs::remove_dir_all(&build_dir)?;
+    }
+    copy_test_program(&test_program_file_path(file_name, ""), &build_dir)?;
+    let out_path = build_dir.join("reference.bin");
+    let output = std::process::Command::new(forc)
+        .arg("build")
+        .arg("--path")
+        .arg(&build_dir)
+        .arg("--output-bin")
+        .arg(&out_path)
+        .output()
+        .map_err(|e| anyhow!("Failed to execute {}: {e}", forc.display()))?;
+    if !output.status.success() {
+        bail!(
+            "Reference compiler failed to build the test:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let bytes = fs::read(&out_path)?;
+    let _ = fs::remove_dir_all(&build_dir);
+    Ok(bytes)
+}
+
+/// Copies the sources and manifest of the test program in `src` to `dst`, leaving out its build
+/// artifacts and lock file, and makes the paths of its path dependencies absolute so that they
+/// still resolve from `dst`.
+fn copy_test_program(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "out" || name == "Forc.lock" {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            copy_test_program(&entry.path(), &dst.join(&name))?;
+        } else if name == "Forc.toml" {
+            let mut manifest: toml::Value = toml::from_str(&fs::read_to_string(entry.path())?)?;
+            for section in ["dependencies", "contract-dependencies"] {
+                let Some(deps) = manifest
+                    .get_mut(section)
+                    .and_then(|deps| deps.as_table_mut())
+                else {
+                    continue;
+                };
+                for dep in deps.values_mut() {
+                    if let Some(toml::Value::String(path)) = dep.get_mut("path") {
+                        *path = src.join(&*path).to_string_lossy().into_owned();
+                    }
+                }
+            }
+            fs::write(dst.join(&name), toml::to_string(&manifest)?)?;
+        } else {
+            fs::copy(entry.path(), dst.join(&name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Reduces a receipt to its observable behaviour by removing the program counter, instruction
+/// start, gas figures and pointers into memory, which are expected to differ between compiler
+/// versions as their memory layouts do.
+fn observable_receipt(receipt: &Receipt) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(receipt)?;
+    if let Some(fields) = value
+        .as_object_mut()
+        .and_then(|variant| variant.values_mut().next())
+        .and_then(|fields| fields.as_object_mut())
+    {
+        for key in ["pc", "is", "gas", "gas_used", "ptr"] {
+            fields.remove(key);
+        }
+    }
+    Ok(value)
+}
+
+/// Runs the already built test program alongside the same program built by the `reference_forc`
+/// binary, using the same script data, and fails if the program state or the observable receipts
+/// differ. This catches silent miscompiles which don't otherwise change a test's expected result.
+pub(crate) fn test_differential(
+    file_name: &str,
+    built_package: &BuiltPackage,
+    script_data: Option<Vec<u8>>,
+    reference_forc: &Path,
+) -> Result<()> {
+    let reference_bytecode = compile_to_bytes_with_forc(file_name, reference_forc)?;
+    let script_data = script_data.unwrap_or_default();
+    let (state, receipts) =
+        runs_bytecode_in_fuel_vm(built_package.bytecode.bytes.clone(), script_data.clone())?;
+    let (reference_state, reference_receipts) =
+        runs_bytecode_in_fuel_vm(reference_bytecode, script_data)?;
+
+    if state != reference_state {
+        bail!(
+            "Program state differs from the reference compiler.\n\
+            expected: {reference_state:?}\nactual: {state:?}"
+        );
+    }
+
+    let to_json = |receipts: &[Receipt]| -> Result<String> {
+        let values = receipts
+            .iter()
+            .map(observable_receipt)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(serde_json::to_string_pretty(&values)?)
+    };
+    let receipts = to_json(&receipts)?;
+    let reference_receipts = to_json(&reference_receipts)?;
+    if receipts != reference_receipts {
+        bail!(
+            "Receipts differ from the reference compiler.\n{}",
+            prettydiff::diff_lines(&reference_receipts, &receipts)
+        );
+    }
+    Ok(())
+}
+
 /// Compiles the code and optionally captures the output of forc and the compilation.
 /// Returns a tuple with the result of the compilation, as well as the output.
 pub(crate) async fn compile_to_bytes(file_name: &str, run_config: &RunConfig) -> Result<Built> {
@@ -290,31 +433,19 @@ pub(crate) async fn compile_and_run_unit_tests(
     .await
 }
 
-pub(crate) fn test_json_abi(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
+pub(crate) fn test_json_abi(
+    file_name: &str,
+    built_package: &BuiltPackage,
+    update_snapshots: bool,
+) -> Result<()> {
     emit_json_abi(file_name, built_package)?;
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let oracle_path = format!(
-        "{}/src/e2e_vm_tests/test_programs/{}/{}",
-        manifest_dir, file_name, "json_abi_oracle.json"
-    );
-    let output_path = format!(
-        "{}/src/e2e_vm_tests/test_programs/{}/{}",
-        manifest_dir, file_name, "json_abi_output.json"
-    );
-    if fs::metadata(oracle_path.clone()).is_err() {
-        bail!("JSON ABI oracle file does not exist for this test.");
-    }
-    if fs::metadata(output_path.clone()).is_err() {
-        bail!("JSON ABI output file does not exist for this test.");
-    }
-    let oracle_contents =
-        fs::read_to_string(oracle_path).expect("Something went wrong reading the file.");
-    let output_contents =
-        fs::read_to_string(output_path).expect("Something went wrong reading the file.");
-    if oracle_contents != output_contents {
-        bail!("Mismatched ABI JSON output.");
-    }
-    Ok(())
+    check_snapshot(
+        file_name,
+        "JSON ABI",
+        "json_abi_oracle.json",
+        "json_abi_output.json",
+        update_snapshots,
+    )
 }
 
 fn emit_json_abi(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
@@ -324,52 +455,132 @@ fn emit_json_abi(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
         ProgramABI::Evm(abi) => serde_json::json!(abi),
         ProgramABI::MidenVM(_) => todo!(),
     };
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let file = std::fs::File::create(format!(
-        "{}/src/e2e_vm_tests/test_programs/{}/{}",
-        manifest_dir, file_name, "json_abi_output.json"
-    ))?;
+    let file = std::fs::File::create(test_program_file_path(file_name, "json_abi_output.json"))?;
     let res = serde_json::to_writer_pretty(&file, &json_abi);
     res?;
     Ok(())
 }
 
-pub(crate) fn test_json_storage_slots(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
-    emit_json_storage_slots(file_name, built_package)?;
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let oracle_path = format!(
-        "{}/src/e2e_vm_tests/test_programs/{}/{}",
-        manifest_dir, file_name, "json_storage_slots_oracle.json"
-    );
-    let output_path = format!(
-        "{}/src/e2e_vm_tests/test_programs/{}/{}",
-        manifest_dir, file_name, "json_storage_slots_output.json"
-    );
-    if fs::metadata(oracle_path.clone()).is_err() {
-        bail!("JSON storage slots oracle file does not exist for this test.");
-    }
-    if fs::metadata(output_path.clone()).is_err() {
-        bail!("JSON storage slots output file does not exist for this test.");
-    }
-    let oracle_contents =
-        fs::read_to_string(oracle_path).expect("Something went wrong reading the file.");
-    let output_contents =
-        fs::read_to_string(output_path).expect("Something went wrong reading the file.");
-    if oracle_contents != output_contents {
-        bail!("Mismatched storage slots JSON output.");
+pub(crate) fn test_json_abi_extensions(
+    file_name: &str,
+    built_package: &BuiltPackage,
+    update_snapshots: bool,
+) -> Result<()> {
+    emit_json_abi_extensions(file_name, built_package)?;
+    check_snapshot(
+        file_name,
+        "JSON ABI extensions",
+        "json_abi_extensions_oracle.json",
+        "json_abi_extensions_output.json",
+        update_snapshots,
+    )
+}
+
+fn emit_json_abi_extensions(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
+    tracing::info!("ABI extensions gen {} ...", file_name.bold());
+    let file = std::fs::File::create(test_program_file_path(
+        file_name,
+        "json_abi_extensions_output.json",
+    ))?;
+    let res = serde_json::to_writer_pretty(&file, &built_package.abi_extensions);
+    res?;
+    Ok(())
+}
+
+/// Checks that the code `built_package` reverted with is one of the revert codes listed in its
+/// ABI extensions.
+pub(crate) fn test_revert_code_is_known(built_package: &BuiltPackage, code: u64) -> Result<()> {
+    let code = code.to_string();
+    if !built_package
+        .abi_extensions
+        .revert_codes
+        .iter()
+        .any(|revert_code| revert_code.code == code)
+    {
+        bail!("The program reverted with {code}, which is not one of its known revert codes.");
     }
     Ok(())
 }
 
+pub(crate) fn test_json_storage_slots(
+    file_name: &str,
+    built_package: &BuiltPackage,
+    update_snapshots: bool,
+) -> Result<()> {
+    emit_json_storage_slots(file_name, built_package)?;
+    check_snapshot(
+        file_name,
+        "JSON storage slots",
+        "json_storage_slots_oracle.json",
+        "json_storage_slots_output.json",
+        update_snapshots,
+    )
+}
+
 fn emit_json_storage_slots(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
     tracing::info!("Storage slots JSON gen {} ...", file_name.bold());
     let json_storage_slots = serde_json::json!(built_package.storage_slots);
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let file = std::fs::File::create(format!(
-        "{}/src/e2e_vm_tests/test_programs/{}/{}",
-        manifest_dir, file_name, "json_storage_slots_output.json"
+    let file = std::fs::File::create(test_program_file_path(
+        file_name,
+        "json_storage_slots_output.json",
     ))?;
     let res = serde_json::to_writer_pretty(&file, &json_storage_slots);
     res?;
     Ok(())
 }
+
+/// Returns the path of `file` within the test program directory `file_name`.
+fn test_program_file_path(file_name: &str, file: &str) -> PathBuf {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    [
+        manifest_dir,
+        "src",
+        "e2e_vm_tests",
+        "test_programs",
+        file_name,
+        file,
+    ]
+    .iter()
+    .collect()
+}
+
+/// Compares a freshly emitted output file against its committed snapshot (the "oracle").
+///
+/// When `update_snapshots` is set the oracle is overwritten with the output instead, which is how
+/// snapshots are created for new tests and refreshed after intended changes. Otherwise any
+/// difference fails the test and is printed as a line diff so it is visible in review.
+fn check_snapshot(
+    file_name: &str,
+    descr: &str,
+    oracle_file: &str,
+    output_file: &str,
+    update_snapshots: bool,
+) -> Result<()> {
+    let oracle_path = test_program_file_path(file_name, oracle_file);
+    let output_path = test_program_file_path(file_name, output_file);
+    if fs::metadata(&output_path).is_err() {
+        bail!("{descr} output file does not exist for this test.");
+    }
+    let output_contents =
+        fs::read_to_string(&output_path).expect("Something went wrong reading the file.");
+    if update_snapshots {
+        fs::write(&oracle_path, &output_contents)?;
+        println!("Updated {descr} snapshot {}", oracle_path.display());
+        return Ok(());
+    }
+    if fs::metadata(&oracle_path).is_err() {
+        bail!(
+            "{descr} oracle file does not exist for this test. \
+            Run with `--update-snapshots` to create it."
+        );
+    }
+    let oracle_contents =
+        fs::read_to_string(&oracle_path).expect("Something went wrong reading the file.");
+    if oracle_contents != output_contents {
+        bail!(
+            "Mismatched {descr} output.\n{}\nRun with `--update-snapshots` if this change is intended.",
+            prettydiff::diff_lines(&oracle_contents, &output_contents)
+        );
+    }
+    Ok(())
+}
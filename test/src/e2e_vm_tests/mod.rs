@@ -65,6 +65,7 @@ struct TestDescription {
     expected_warnings: u32,
     contract_paths: Vec<String>,
     validate_abi: bool,
+    validate_abi_extensions: bool,
     validate_storage_slots: bool,
     supported_targets: HashSet<BuildTarget>,
     checker: filecheck::Checker,
@@ -100,6 +101,7 @@ impl TestContext {
             expected_warnings,
             contract_paths,
             validate_abi,
+            validate_abi_extensions,
             validate_storage_slots,
             checker,
             ..
@@ -140,7 +142,7 @@ impl TestContext {
                     )));
                 }
 
-                let result = harness::runs_in_vm(compiled.clone(), script_data)?;
+                let result = harness::runs_in_vm(compiled.clone(), script_data.clone())?;
                 let result = match result {
                     harness::VMExecutionResult::Fuel(state, receipts) => {
                         match state {
@@ -184,12 +186,46 @@ impl TestContext {
                 } else {
                     if validate_abi {
                         let (result, out) = run_and_capture_output(|| async {
-                            harness::test_json_abi(&name, &compiled)
+                            harness::test_json_abi(
+                                &name,
+                                &compiled,
+                                context.run_config.update_snapshots,
+                            )
                         })
                         .await;
                         result?;
                         output.push_str(&out);
                     }
+                    if validate_abi_extensions {
+                        let (result, out) = run_and_capture_output(|| async {
+                            harness::test_json_abi_extensions(
+                                &name,
+                                &compiled,
+                                context.run_config.update_snapshots,
+                            )
+                        })
+                        .await;
+                        result?;
+                        output.push_str(&out);
+                        if let TestResult::Revert(code) = res {
+                            harness::test_revert_code_is_known(&compiled, code)?;
+                        }
+                    }
+                    if let Some(reference_forc) = &context.run_config.differential {
+                        if compiled.descriptor.target == BuildTarget::Fuel {
+                            let (result, out) = run_and_capture_output(|| async {
+                                harness::test_differential(
+                                    &name,
+                                    &compiled,
+                                    script_data,
+                                    reference_forc,
+                                )
+                            })
+                            .await;
+                            output.push_str(&out);
+                            result?;
+                        }
+                    }
                     Ok(())
                 }
             }
@@ -227,7 +263,26 @@ impl TestContext {
                 if validate_abi {
                     for (name, built_pkg) in &compiled_pkgs {
                         let (result, out) = run_and_capture_output(|| async {
-                            harness::test_json_abi(name, built_pkg)
+                            harness::test_json_abi(
+                                name,
+                                built_pkg,
+                                context.run_config.update_snapshots,
+                            )
+                        })
+                        .await;
+                        result?;
+                        output.push_str(&out);
+                    }
+                }
+
+                if validate_abi_extensions {
+                    for (name, built_pkg) in &compiled_pkgs {
+                        let (result, out) = run_and_capture_output(|| async {
+                            harness::test_json_abi_extensions(
+                                name,
+                                built_pkg,
+                                context.run_config.update_snapshots,
+                            )
                         })
                         .await;
                         result?;
@@ -238,7 +293,11 @@ impl TestContext {
                 if validate_storage_slots {
                     for (name, built_pkg) in &compiled_pkgs {
                         let (result, out) = run_and_capture_output(|| async {
-                            harness::test_json_storage_slots(name, built_pkg)
+                            harness::test_json_storage_slots(
+                                name,
+                                built_pkg,
+                                context.run_config.update_snapshots,
+                            )
                         })
                         .await;
                         result?;
@@ -653,6 +712,11 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         .map(|v| v.as_bool().unwrap_or(false))
         .unwrap_or(false);
 
+    let validate_abi_extensions = toml_content
+        .get("validate_abi_extensions")
+        .map(|v| v.as_bool().unwrap_or(false))
+        .unwrap_or(false);
+
     let expected_warnings = u32::try_from(
         toml_content
             .get("expected_warnings")
@@ -706,6 +770,7 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         expected_warnings,
         contract_paths,
         validate_abi,
+        validate_abi_extensions,
         validate_storage_slots,
         supported_targets,
         checker,
@@ -0,0 +1,11 @@
+contract;
+
+abi MyContract {
+    fn generic_method<T>(arg: T) -> T;
+}
+
+impl MyContract for Contract {
+    fn generic_method<T>(arg: T) -> T {
+        arg
+    }
+}
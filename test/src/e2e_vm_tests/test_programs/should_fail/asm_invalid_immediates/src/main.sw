@@ -0,0 +1,14 @@
+script;
+
+const FLAG: bool = true;
+
+fn main() -> u64 {
+    asm(r1) {
+        movi r1 UNKNOWN;
+        movi r1 FLAG;
+        again: noop;
+        again: noop;
+        ji nowhere;
+        r1: u64
+    }
+}
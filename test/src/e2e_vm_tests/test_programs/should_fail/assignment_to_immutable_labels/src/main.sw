@@ -0,0 +1,7 @@
+script;
+
+fn main() -> u64 {
+    let x = 0;
+    x = 1;
+    x
+}
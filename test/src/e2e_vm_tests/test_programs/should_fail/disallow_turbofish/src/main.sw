@@ -10,6 +10,16 @@ struct Elem<T> {
   value: T
 }
 
+impl<T> Elem<T> {
+  fn get(self) -> T {
+    self.value
+  }
+
+  fn replace<U>(self, other: U) -> U {
+    other
+  }
+}
+
 struct Result<T, E> {
   yes: T,
   no: E
@@ -38,4 +48,7 @@ fn main() {
     no: 8u32
   };
   foo::<u64>();
+  let _value = _elem2.get::<u64>();
+  let _other = _elem2.replace::<u8, u8>(1u8);
+  let _other = _elem2.replace::<u8>(1u8);
 }
@@ -0,0 +1,10 @@
+script;
+
+enum MyOption<T> {
+    Some: T,
+    None: (),
+}
+
+fn main() {
+    let _x: MyOption<u64> = MyOption::Some(true);
+}
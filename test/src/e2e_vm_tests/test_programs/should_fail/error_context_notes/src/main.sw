@@ -0,0 +1,16 @@
+script;
+
+struct Counter {
+    value: u64,
+}
+
+impl Counter {
+    fn incremented(self) -> u64 {
+        self.value + step
+    }
+}
+
+fn main() -> u64 {
+    let counter = Counter { value: 1 };
+    counter.incremented()
+}
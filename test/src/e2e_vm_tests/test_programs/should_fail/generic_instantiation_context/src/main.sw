@@ -0,0 +1,14 @@
+script;
+
+struct MyStruct {
+    value: u64,
+}
+
+fn get<T, U>(first: T, second: U, third: T) -> U {
+    second
+}
+
+fn main() -> u64 {
+    let my_struct = get(1u8, MyStruct { value: 2 }, true);
+    my_struct.value
+}
@@ -0,0 +1,15 @@
+script;
+
+enum Result<T, E> {
+    Ok: T,
+    Err: E,
+}
+
+fn main() -> u64 {
+    let a = Result::Ok::<u64, bool>(2);
+    if let Result::Ok(x) = a && x > 1 {
+        x
+    } else {
+        0
+    }
+}
@@ -0,0 +1,19 @@
+contract;
+
+abi Counter {
+    #[storage(read)]
+    fn get() -> u64;
+}
+
+#[memoize]
+#[storage(read)]
+fn stored_key() -> u64 {
+    42
+}
+
+impl Counter for Contract {
+    #[storage(read)]
+    fn get() -> u64 {
+        stored_key()
+    }
+}
@@ -0,0 +1,42 @@
+script;
+
+mod pair;
+
+use pair::*;
+
+struct Different {}
+
+// Missing the constraint `U: Same` required by the field.
+struct Unbounded<U> {
+    pair: Pair<U>,
+}
+
+// Missing the constraint `U: Same` required by the variant.
+enum MaybePair<U> {
+    Some: Pair<U>,
+    None: (),
+}
+
+struct Bounded<U> where U: Same {
+    pair: Pair<U>,
+}
+
+struct Nested<V> where V: Same {
+    bounded: Bounded<V>,
+}
+
+fn main() -> bool {
+    let _ok = Nested {
+        bounded: Bounded {
+            pair: Pair::new(true, false),
+        },
+    };
+
+    // Trait "Same" is not implemented for type "Different".
+    let _pair = Pair::<Different>::new(Different {}, Different {});
+
+    // Trait "Same" is not implemented for type "Different", through the fields of `Nested`.
+    let _nested = Nested { bounded: Bounded { pair: Pair { first: Different {}, second: Different {} } } };
+
+    true
+}
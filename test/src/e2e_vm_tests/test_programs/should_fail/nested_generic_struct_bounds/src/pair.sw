@@ -0,0 +1,22 @@
+library;
+
+pub trait Same {
+    fn same(self, other: Self) -> bool;
+}
+
+impl Same for bool {
+    fn same(self, other: Self) -> bool {
+        if self { other } else { !other }
+    }
+}
+
+pub struct Pair<T> where T: Same {
+    first: T,
+    second: T,
+}
+
+impl<T> Pair<T> where T: Same {
+    pub fn new(first: T, second: T) -> Self {
+        Pair { first, second }
+    }
+}
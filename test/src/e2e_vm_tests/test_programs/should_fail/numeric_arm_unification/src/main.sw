@@ -0,0 +1,16 @@
+script;
+
+fn main() {
+    let small = 200u8;
+
+    // The literal takes the type of the other branch, so the `if` expression is a `u8`.
+    let choice = if small > 100u8 { 1 } else { small };
+    let _wide: u64 = choice;
+
+    // Likewise for the arms of a `match` expression.
+    let matched = match small {
+        201 => 2,
+        _ => small,
+    };
+    let _also_wide: u64 = matched;
+}
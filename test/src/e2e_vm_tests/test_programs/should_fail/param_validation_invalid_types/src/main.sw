@@ -0,0 +1,13 @@
+contract;
+
+abi Invalid {
+    fn set_name(#[param(min = 1)] name: str[4]);
+    fn set_flag(#[param(max = 1)] flag: bool);
+    fn set_level(#[param(min = 1, max = 300)] level: u8);
+}
+
+impl Invalid for Contract {
+    fn set_name(name: str[4]) {}
+    fn set_flag(flag: bool) {}
+    fn set_level(level: u8) {}
+}
@@ -74,8 +74,13 @@ fn main() -> bool {
   }
 
   // cannot test storage opcodes due to needing to annotate main
-  // with #[storage(read, write)] which is not allowed for predicates
+  // with #[storage(read, write)] which is not allowed for predicates,
+  // they're rejected by the unit tests in `asm_generation::fuel::checks`
   /*
+  asm(r1: 0, r2: 0, r3: 0) {
+    scwq r1 r2 r3;
+  }
+
   asm(r1: 0, r2: 0, r3) {
     srw r1 r2 r3;
   }
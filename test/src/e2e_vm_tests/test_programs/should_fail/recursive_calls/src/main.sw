@@ -1,7 +1,7 @@
 script;
 
-// a -> a
-fn a(n: u64) -> u64 {
+// a -> a, which is only supported for functions that aren't generic.
+fn a<T>(n: T) -> T {
     a(n)
 }
 
@@ -27,6 +27,15 @@ fn f(n: u64) -> u64 {
     d(n)
 }
 
+// h -> i -> h, generic functions
+fn h<T>(n: T) -> T {
+    i(n)
+}
+
+fn i<T>(n: T) -> T {
+    h(n)
+}
+
 // Depends on symbols 'a' and 'b' but is not recursive.
 fn g(a: u64) -> u64 {
   let b = a;
@@ -0,0 +1,26 @@
+script;
+
+use std::boxed::Box;
+
+// `Option<T>` holds a `T`, so this type would hold itself.
+enum List {
+    Nil: (),
+    Cons: (u64, Option<List>),
+}
+
+// Generic types may not refer to themselves, even through a `Box`.
+enum GenericList<T> {
+    Nil: (),
+    Cons: (T, Box<GenericList<T>>),
+}
+
+// Nor may types refer to each other.
+struct A {
+    b: Box<B>,
+}
+
+struct B {
+    a: A,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+script;
+
+use std::boxed::Box as StdBox;
+
+// Only std's `Box` holds a pointer. This one holds its value, so `List` would hold itself.
+struct Box<T> {
+    value: T,
+}
+
+enum List {
+    Nil: (),
+    Cons: (u64, Box<List>),
+}
+
+// Importing std's `Box` under another name doesn't change which `Box` is meant.
+struct Tree {
+    left: Box<Tree>,
+    right: StdBox<Tree>,
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+script;
+
+trait MyEq {
+    fn my_eq(self, other: Self) -> bool;
+}
+
+impl MyEq for u64 {
+    fn my_eq(self, other: Self) -> bool {
+        self == other
+    }
+}
+
+struct Wrapper<T> {
+    value: T,
+}
+
+impl<T> MyEq for Wrapper<T> where T: MyEq {
+    fn my_eq(self, other: Self) -> bool {
+        self.value.my_eq(other.value)
+    }
+}
+
+struct NoEq {}
+
+trait Describe: MyEq {
+    fn describe(self) -> u64;
+}
+
+impl Describe for NoEq {
+    fn describe(self) -> u64 {
+        0
+    }
+}
+
+fn all_eq<T>(a: T, b: T) -> bool where T: MyEq {
+    a.my_eq(b)
+}
+
+fn main() -> bool {
+    let wrapped = all_eq(Wrapper { value: 1 }, Wrapper { value: 2 });
+    let a = Wrapper { value: Wrapper { value: NoEq {} } };
+    let b = Wrapper { value: Wrapper { value: NoEq {} } };
+    let doubly_wrapped = all_eq(a, b);
+    wrapped && doubly_wrapped
+}
@@ -0,0 +1,13 @@
+contract;
+
+abi ClearStorage {
+    #[storage(write)]
+    fn clear(key: b256) -> bool;
+}
+
+impl ClearStorage for Contract {
+    #[storage(write)]
+    fn clear(key: b256) -> bool {
+        __state_clear(key, 1)
+    }
+}
@@ -0,0 +1,13 @@
+script;
+
+fn count_down(n: u64) -> u64 {
+    if __eq(n, 0) {
+        0
+    } else {
+        count_down(__sub(n, 1))
+    }
+}
+
+fn main() -> u64 {
+    count_down(3)
+}
@@ -0,0 +1,5 @@
+contract;
+
+#[when_not_paused]
+#[storage(read)]
+fn not_a_method() {}
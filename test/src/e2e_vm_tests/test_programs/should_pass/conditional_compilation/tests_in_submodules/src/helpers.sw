@@ -0,0 +1,19 @@
+library;
+
+pub fn double(value: u64) -> u64 {
+    value * 2
+}
+
+// Only compiled along with the tests, so a test of this submodule using it must be excluded from
+// normal builds too.
+#[cfg(test)]
+fn expected_double() -> u64 {
+    42
+}
+
+#[test]
+fn test_double() {
+    if double(21) != expected_double() {
+        __revert(0);
+    }
+}
@@ -0,0 +1,7 @@
+script;
+
+mod helpers;
+
+fn main() -> u64 {
+    helpers::double(21)
+}
@@ -0,0 +1,24 @@
+library;
+
+pub struct S {
+    value: u64,
+}
+
+impl S {
+    pub fn value(self) -> u64 {
+        self.inner()
+    }
+
+    fn inner(self) -> u64 {
+        self.value
+    }
+
+    fn unused(self) -> u64 {
+        self.value
+    }
+
+    #[allow(dead_code)]
+    fn allowed(self) -> u64 {
+        self.value
+    }
+}
@@ -0,0 +1,14 @@
+script;
+
+mod utils;
+
+use utils::{Bar, Foo};
+use utils::baz;
+use utils::Qux as Quux;
+use utils::Trait;
+// Only the field below is called `value`, the function isn't used.
+use utils::value;
+
+fn main() {
+    let _foo = Foo { value: 0 };
+}
@@ -0,0 +1,22 @@
+library;
+
+pub struct Foo {
+    pub value: u64,
+}
+
+#[allow(dead_code)]
+pub fn value() {}
+
+#[allow(dead_code)]
+pub struct Bar {}
+
+#[allow(dead_code)]
+pub struct Qux {}
+
+#[allow(dead_code)]
+pub fn baz() {}
+
+#[allow(dead_code)]
+pub trait Trait {
+    fn trait_method(self);
+}
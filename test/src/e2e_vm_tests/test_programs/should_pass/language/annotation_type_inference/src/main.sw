@@ -0,0 +1,20 @@
+script;
+
+struct Wrapper<T> {
+    inner: T,
+}
+
+fn main() -> u64 {
+    // The type arguments of empty collections and `None` are given by the annotations alone.
+    let mut vec: Vec<u64> = Vec::new();
+    vec.push(1);
+    let none: Option<u64> = Option::None;
+
+    // Including when they are nested in other expressions.
+    let nested: Option<Vec<u64>> = Some(Vec::new());
+    let wrapped: Wrapper<Vec<u64>> = Wrapper {
+        inner: Vec::new(),
+    };
+
+    vec.len() + nested.unwrap().len() + wrapped.inner.len() + none.unwrap_or(2)
+}
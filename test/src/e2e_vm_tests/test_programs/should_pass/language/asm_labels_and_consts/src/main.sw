@@ -0,0 +1,43 @@
+script;
+
+const LIMIT: u64 = 10;
+const STEP: u64 = 1;
+
+// Sums 1..=LIMIT with a backwards conditional jump.
+fn sum_to_limit() -> u64 {
+    asm(i, acc, limit, cond) {
+        movi i i0;
+        movi acc i0;
+        movi limit LIMIT;
+        loop_start: addi i i STEP;
+        add acc acc i;
+        lt cond i limit;
+        jnzi cond loop_start;
+        acc: u64
+    }
+}
+
+// Counts LIMIT down to zero using `jnei` against the zero register.
+fn count_down() -> u64 {
+    asm(n) {
+        movi n LIMIT;
+        again: subi n n STEP;
+        jnei n zero again;
+        n: u64
+    }
+}
+
+// Skips an instruction with an unconditional forward jump.
+fn skip_forward() -> u64 {
+    asm(r) {
+        movi r i1;
+        ji done;
+        movi r i2;
+        done: noop;
+        r: u64
+    }
+}
+
+fn main() -> bool {
+    __eq(sum_to_limit(), 55) && __eq(count_down(), 0) && __eq(skip_forward(), 1)
+}
@@ -0,0 +1,78 @@
+script;
+
+struct Flags {
+    small: u8,
+    set: bool,
+    value: u64,
+}
+
+struct Tagged {
+    tag: u64,
+    payload: u64,
+}
+
+// Only the tags are compared, so values with different bytes can still be equal.
+impl Eq for Tagged {
+    fn eq(self, other: Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+fn main() -> bool {
+    let a = [1, 2, 3];
+    assert(a == [1, 2, 3]);
+    assert(!(a == [1, 2, 4]));
+    assert(a != [3, 2, 1]);
+    assert(!(a != [1, 2, 3]));
+
+    let s = "abc";
+    assert(s == "abc");
+    assert(!(s == "abd"));
+    assert(s != "bbc");
+    assert(!(s != "abc"));
+
+    let f = Flags {
+        small: 1u8,
+        set: true,
+        value: 3,
+    };
+    assert(f == Flags {
+        small: 1u8,
+        set: true,
+        value: 3,
+    });
+    assert(!(f == Flags {
+        small: 2u8,
+        set: true,
+        value: 3,
+    }));
+    assert(f != Flags {
+        small: 1u8,
+        set: false,
+        value: 3,
+    });
+    assert(!(f != Flags {
+        small: 1u8,
+        set: true,
+        value: 3,
+    }));
+
+    let t = Tagged {
+        tag: 1,
+        payload: 2,
+    };
+    assert(t == Tagged {
+        tag: 1,
+        payload: 3,
+    });
+    assert(!(t != Tagged {
+        tag: 1,
+        payload: 3,
+    }));
+    assert(t != Tagged {
+        tag: 2,
+        payload: 2,
+    });
+
+    true
+}
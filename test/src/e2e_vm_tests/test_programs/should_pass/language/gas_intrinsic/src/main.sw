@@ -0,0 +1,8 @@
+script;
+
+fn main() -> bool {
+    let before = __gas();
+    let after = __gas();
+    // Some gas must have been spent between the two reads.
+    __gt(before, after)
+}
@@ -0,0 +1,51 @@
+script;
+
+// Only `u8` implements this, so calling it checks that the receiver was inferred to be a `u8`.
+trait OnlyU8 {
+    fn only_u8(self) -> u8;
+}
+
+impl OnlyU8 for u8 {
+    fn only_u8(self) -> u8 {
+        self
+    }
+}
+
+fn max<T>(first: T, second: T) -> T
+where
+    T: Ord,
+{
+    if first > second { first } else { second }
+}
+
+fn returned(x: u8) -> u8 {
+    if x == 0 {
+        // The literals get their width from the return type of the function.
+        return max(5, 3);
+    }
+    max(x, 5)
+}
+
+fn main() -> u8 {
+    let x: u8 = 7;
+
+    // The literal gets its width from the other argument, whichever comes first.
+    assert_eq(x, 7);
+    assert_eq(7, x);
+
+    // The literals get their width from the annotation, rather than defaulting to `u64`.
+    let a: u8 = max(5, 3);
+
+    // The literal comes before the argument which gives it its width.
+    let b = max(5, x);
+
+    // The first branch only gets its width from the second one.
+    let five = 5;
+    let c = match x {
+        7 => five,
+        _ => x,
+    };
+    five.only_u8();
+
+    a.only_u8() + b.only_u8() + c.only_u8() + returned(2)
+}
@@ -0,0 +1,28 @@
+script;
+
+fn alloc(size: u64) -> raw_ptr {
+    asm(size: size, ptr) {
+        aloc size;
+        move ptr hp;
+        ptr: raw_ptr
+    }
+}
+
+fn main() -> bool {
+    let (_, before) = __heap_stats();
+    let ptr = alloc(8);
+    let (hp, after) = __heap_stats();
+    let allocates = __eq(hp, ptr) && __eq(__sub(after, before), 8);
+
+    // Growing copies into a new allocation of the new size.
+    let grown = __realloc(ptr, 8, 24);
+    let (hp, after_growing) = __heap_stats();
+    let grows = __eq(hp, grown) && __eq(__sub(after_growing, after), 24);
+
+    // Shrinking neither allocates nor moves.
+    let shrunk = __realloc(grown, 24, 16);
+    let (_, after_shrinking) = __heap_stats();
+    let shrinks = __eq(shrunk, grown) && __eq(after_shrinking, after_growing);
+
+    allocates && grows && shrinks
+}
@@ -0,0 +1,68 @@
+script;
+
+enum Result<T, E> {
+    Ok: T,
+    Err: E,
+}
+
+fn both(a: Result<u64, bool>, b: Result<u64, bool>) -> u64 {
+    if let Result::Ok(x) = a && let Result::Ok(y) = b {
+        x + y
+    } else if let Result::Err(true) = a {
+        1
+    } else {
+        0
+    }
+}
+
+fn in_range(a: Result<u64, bool>) -> u64 {
+    if let Result::Ok(x) = a && x > 10 && x < 100 {
+        x
+    } else {
+        0
+    }
+}
+
+fn when(flag: bool, a: Result<u64, bool>) -> u64 {
+    if flag && let Result::Ok(x) = a {
+        x
+    } else {
+        0
+    }
+}
+
+// The `else` runs outside of the chain's bindings, so `x` is the argument here.
+fn shadowed(x: u64, a: Result<u64, bool>) -> u64 {
+    if let Result::Ok(x) = a && x > 10 {
+        x
+    } else {
+        x
+    }
+}
+
+fn either_then_first(a: bool, b: bool) -> u64 {
+    if let true = (a || b) && a {
+        1
+    } else {
+        0
+    }
+}
+
+// should return 88
+fn main() -> u64 {
+    let ok = Result::Ok::<u64, bool>(2);
+    let err = Result::Err::<u64, bool>(false);
+
+    both(ok, Result::Ok(3)) // 5
+        + both(ok, err) // 0
+        + both(Result::Err(true), Result::Ok(3)) // 1
+        + in_range(Result::Ok(20)) // 20
+        + in_range(Result::Ok(200)) // 0
+        + in_range(err) // 0
+        + when(true, Result::Ok(7)) // 7
+        + when(false, Result::Ok(7)) // 0
+        + shadowed(4, Result::Ok(5)) // 4
+        + shadowed(4, Result::Ok(50)) // 50
+        + either_then_first(true, false) // 1
+        + either_then_first(false, true) // 0
+}
@@ -0,0 +1,38 @@
+script;
+
+trait Double {
+    fn double(self) -> Self;
+}
+
+impl Double for u64 {
+    fn double(self) -> u64 {
+        self * 2
+    }
+}
+
+trait Sum {
+    fn sum(self) -> u64;
+}
+
+impl Sum for (u64, u64) {
+    fn sum(self) -> u64 {
+        self.0 + self.1
+    }
+}
+
+fn main() -> u64 {
+    let a = 1;
+    let b = 2;
+
+    // Parenthesized receiver.
+    let x = (a + b).double();
+
+    // Literal receivers, with and without a type suffix.
+    let y = 5u64.double();
+    let w = 2.double().double();
+
+    // Tuple literal receiver.
+    let z = (3, 4).sum();
+
+    x + y + z + w
+}
@@ -0,0 +1,27 @@
+script;
+
+fn consume<T>(_value: T) {}
+
+fn main() -> u8 {
+    let small = 200u8;
+
+    // A literal on the left of an operator takes the width of the right operand.
+    let sum = 1 + small;
+    let is_small = 1 < small;
+
+    // Literals in one branch take the width of the other branches.
+    let choice = if is_small { 1 } else { small };
+    let matched = match sum {
+        201 => 2,
+        _ => small,
+    };
+
+    // Or the width of the result, if the right operand doesn't have the same type.
+    let shifted: u8 = 1 << 3;
+
+    // Never constrained, so it defaults to `u64`.
+    let fallback = 5;
+    consume(fallback);
+
+    choice + matched + shifted
+}
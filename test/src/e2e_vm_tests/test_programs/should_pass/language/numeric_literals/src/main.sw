@@ -0,0 +1,34 @@
+script;
+
+fn takes_u8(x: u8) -> u8 {
+    x
+}
+
+fn takes_u32(x: u32) -> u32 {
+    x
+}
+
+fn main() -> bool {
+    // Underscores and suffixes
+    let a = 1_000_000u64;
+    let b = 1_000_000_u64;
+    let c = 255_u8;
+
+    // Binary, octal and hex, with and without suffixes
+    let d = 0b1010;
+    let e = 0b_1010_1010u8;
+    let f = 0o777;
+    let g = 0o_17_u32;
+    let h = 0x_ffu8;
+    let i = 0xdead_beef_u32;
+
+    __eq(a, 1000000)
+        && __eq(b, a)
+        && __eq(takes_u8(c), 255)
+        && __eq(d, 10)
+        && __eq(takes_u8(e), 170)
+        && __eq(f, 511)
+        && __eq(takes_u32(g), 15)
+        && __eq(takes_u8(h), 255)
+        && __eq(takes_u32(i), 3735928559)
+}
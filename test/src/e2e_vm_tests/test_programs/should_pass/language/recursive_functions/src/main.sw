@@ -0,0 +1,89 @@
+script;
+
+fn factorial(n: u64) -> u64 {
+    if n == 0 {
+        1
+    } else {
+        n * factorial(n - 1)
+    }
+}
+
+fn fibonacci(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    fibonacci(n - 1) + fibonacci(n - 2)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+// Sums the nodes of a complete binary tree laid out in an array, where the children of node `i`
+// are at `2i + 1` and `2i + 2`.
+fn tree_sum(tree: [u64; 7], node: u64) -> u64 {
+    if node >= 7 {
+        return 0;
+    }
+    tree[node] + tree_sum(tree, 2 * node + 1) + tree_sum(tree, 2 * node + 2)
+}
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+// Recursion with reference type arguments and return values.
+fn walk(p: Point, steps: u64) -> Point {
+    if steps == 0 {
+        p
+    } else {
+        walk(Point { x: p.x + 1, y: p.y + 2 }, steps - 1)
+    }
+}
+
+fn identity<T>(value: T) -> T {
+    value
+}
+
+// Recursion through the instances of a generic function.
+fn count_down(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        1 + count_down(identity(n - 1))
+    }
+}
+
+// A recursive function called from the instances of a generic function.
+fn count_down_with<T>(_value: T, n: u64) -> u64 {
+    count_down(n)
+}
+
+// A parameter shadowing the function name refers to the parameter.
+fn shadowed(shadowed: u64) -> u64 {
+    shadowed + 1
+}
+
+fn main() -> bool {
+    assert(factorial(0) == 1);
+    assert(factorial(10) == 3628800);
+    assert(fibonacci(15) == 610);
+    assert(gcd(1071, 462) == 21);
+    assert(tree_sum([1, 2, 3, 4, 5, 6, 7], 0) == 28);
+    assert(tree_sum([1, 2, 3, 4, 5, 6, 7], 2) == 16);
+
+    let p = walk(Point { x: 0, y: 0 }, 5);
+    assert(p.x == 5 && p.y == 10);
+
+    assert(count_down(5) == 5);
+    assert(count_down_with(true, 3) == 3);
+    assert(count_down_with(1u8, 4) == 4);
+
+    assert(shadowed(41) == 42);
+
+    true
+}
@@ -0,0 +1,25 @@
+script;
+
+const INVALID_AMOUNT: u64 = 7;
+
+fn check(amount: u64) {
+    if amount > 100 {
+        revert(INVALID_AMOUNT);
+    }
+}
+
+fn shadowed(code: u64) {
+    // The revert code is computed, so it is not known at compile time.
+    let code = code + 1;
+    revert(code);
+}
+
+fn main() {
+    let amount: u64 = 150;
+    require(amount > 0, 1u64);
+    check(amount);
+    if amount == 50 {
+        revert(42);
+    }
+    shadowed(3);
+}
@@ -0,0 +1,13 @@
+script;
+
+enum MyError {
+    ZeroAmount: (),
+    TooLarge: u64,
+}
+
+fn main() {
+    let amount: u64 = 150;
+    // Both errors are of the same enum, which is listed once.
+    require(amount > 0, MyError::ZeroAmount);
+    require(amount <= 100, MyError::TooLarge(amount));
+}
@@ -0,0 +1,43 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+// `prev` is carried from one iteration to the next, while `next` is only live within one.
+fn loop_carried(n: u64) -> u64 {
+    let mut i = 0;
+    let mut prev = 0;
+    let mut sum = 0;
+    while i < n {
+        sum = sum + prev;
+        let next = i * 2;
+        prev = next;
+        i = i + 1;
+    }
+    sum
+}
+
+// `p` and `q` are live in every iteration, `after` only once the loop is done.
+fn sequential_structs(n: u64) -> u64 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < n {
+        let p = Point { x: i, y: i * 10 };
+        let q = Point { x: p.y, y: p.x };
+        total = total + q.x + q.y;
+        i = i + 1;
+    }
+    let after = Point {
+        x: total,
+        y: 1,
+    };
+    after.x + after.y
+}
+
+fn main() -> bool {
+    assert(loop_carried(5) == 12);
+    assert(sequential_structs(4) == 67);
+    true
+}
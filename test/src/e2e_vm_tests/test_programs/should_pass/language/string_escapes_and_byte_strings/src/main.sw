@@ -0,0 +1,16 @@
+script;
+
+fn main() -> bool {
+    // Escapes count as the characters they stand for.
+    let _s: str[6] = "a\tb\nc\\";
+    let _s: str[4] = "\x41\u{e9}\"";
+
+    let bytes: [u8; 5] = b"\x00\xffz\"\\";
+    let _empty: [u8; 0] = b"";
+
+    __eq(bytes[0], 0)
+        && __eq(bytes[1], 255)
+        && __eq(bytes[2], 122)
+        && __eq(bytes[3], 34)
+        && __eq(bytes[4], 92)
+}
@@ -0,0 +1,62 @@
+script;
+
+enum Slot<T> {
+    Full: T,
+    Empty: (),
+}
+
+impl<T> Slot<T> {
+    fn new(value: T) -> Self {
+        Slot::Full(value)
+    }
+
+    fn or<U>(self, value: U) -> U {
+        value
+    }
+}
+
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+impl<T> Pair<T> {
+    fn new(first: T, second: T) -> Self {
+        Pair { first, second }
+    }
+
+    fn second_or<U>(self, _other: U) -> T {
+        self.second
+    }
+}
+
+fn identity<T>(value: T) -> T {
+    value
+}
+
+fn unwrap_or_zero(slot: Slot<u64>) -> u64 {
+    match slot {
+        Slot::Full(value) => value,
+        Slot::Empty => 0,
+    }
+}
+
+fn main() -> u64 {
+    // Free functions.
+    let a = identity::<u64>(1);
+
+    // Enum variant constructors, with the type arguments either on the enum or on the variant.
+    let b = unwrap_or_zero(Slot::<u64>::Full(2));
+    let c = unwrap_or_zero(Slot::Full::<u64>(3));
+    let d = unwrap_or_zero(Slot::Empty::<u64>);
+
+    // Associated functions of generic types.
+    let e = unwrap_or_zero(Slot::<u64>::new(4));
+    let pair = Pair::<u64>::new(5, 6);
+
+    // Methods of generic types.
+    let f = pair.second_or::<bool>(true);
+    let g = Slot::<u64>::new(0).or::<u64>(21);
+
+    a + b + c + d + e + pair.first + f + g
+}
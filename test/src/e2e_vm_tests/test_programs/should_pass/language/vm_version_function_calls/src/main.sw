@@ -0,0 +1,18 @@
+script;
+
+// VM 0.25 can't return from calls, so these are all inlined rather than called.
+
+fn double(x: u64) -> u64 {
+    __add(x, x)
+}
+
+#[inline(never)]
+fn sum(x: u64, y: u64, z: u64) -> u64 {
+    __add(__add(x, y), z)
+}
+
+fn main() -> u64 {
+    let a = double(3);
+    let b = double(a);
+    sum(a, b, double(b))
+}
@@ -0,0 +1,23 @@
+script;
+
+use std::bytes::Bytes;
+
+fn main() -> u64 {
+    let mut bytes = Bytes::new();
+    bytes.push(1u8);
+    bytes.append(bytes);
+
+    let mut vec = Vec::new();
+    // Arguments copied out of the borrowed local don't alias it.
+    vec.push(vec.len());
+    let other = vec;
+    vec.push(2);
+
+    // Reassigning a moved-from local makes it usable again.
+    let mut reused = Vec::new();
+    let copy = reused;
+    reused = Vec::new();
+    reused.push(3);
+
+    bytes.len() + other.len() + copy.len() + reused.len()
+}
@@ -0,0 +1,22 @@
+contract;
+
+use std::storage::storage_api::write;
+
+abi TestAbi {
+    #[storage(write)]
+    fn deposit();
+}
+
+impl TestAbi for Contract {
+    #[storage(write)]
+    #[allow(cei_violation)]
+    fn deposit() {
+        let other_contract = abi(TestAbi, 0x3dba0a4455b598b7655a7fb430883d96c9527ef275b49739e7b0ad12f8280eae);
+
+        // interaction
+        other_contract.deposit();
+        // effect -- a violation of CEI, which is allowed
+        let storage_key = 0x3dba0a4455b598b7655a7fb430883d96c9527ef275b49739e7b0ad12f8280eae;
+        write(storage_key, 0, ());
+    }
+}
@@ -0,0 +1,17 @@
+contract;
+
+storage {
+    count: u64 = 0,
+}
+
+abi MyContract {
+    #[storage(read, write)]
+    fn count() -> u64;
+}
+
+impl MyContract for Contract {
+    #[storage(read, write)]
+    fn count() -> u64 {
+        storage.count.read()
+    }
+}
@@ -0,0 +1,42 @@
+script;
+
+use std::boxed::Box;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+struct Node {
+    value: u64,
+    next: Option<Box<Point>>,
+}
+
+fn main() -> bool {
+    // test new() and get()
+    let five = Box::new(5);
+    assert(five.get() == 5);
+
+    let point = Box::new(Point { x: 1, y: 2 });
+    let p = point.get();
+    assert(p.x == 1 && p.y == 2);
+
+    // test set(), observed through every copy of the box
+    let copy = five;
+    five.set(6);
+    assert(copy.get() == 6);
+
+    // test nested boxes
+    let nested = Box::new(Box::new(7));
+    assert(nested.get().get() == 7);
+
+    // test a box stored in another type
+    let node = Node {
+        value: 3,
+        next: Some(point),
+    };
+    assert(node.value == 3);
+    assert(node.next.unwrap().get().y == 2);
+
+    true
+}
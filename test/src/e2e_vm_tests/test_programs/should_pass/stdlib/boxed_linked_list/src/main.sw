@@ -0,0 +1,80 @@
+script;
+
+use std::boxed::Box;
+
+// A type which isn't generic may refer to itself through a `Box`.
+enum List {
+    Nil: (),
+    Cons: (u64, Box<List>),
+}
+
+struct Node {
+    value: u64,
+    next: Option<Box<Node>>,
+}
+
+fn push_front(list: List, value: u64) -> List {
+    List::Cons((value, Box::new(list)))
+}
+
+fn sum(list: List) -> u64 {
+    let mut total = 0;
+    let mut current = list;
+    let mut done = false;
+    while !done {
+        match current {
+            List::Nil => {
+                done = true;
+            },
+            List::Cons((value, rest)) => {
+                total += value;
+                current = rest.get();
+            },
+        }
+    }
+    total
+}
+
+fn len(node: Node) -> u64 {
+    let mut count = 1;
+    let mut current = node;
+    let mut done = false;
+    while !done {
+        match current.next {
+            Some(next) => {
+                count += 1;
+                current = next.get();
+            },
+            None => {
+                done = true;
+            },
+        }
+    }
+    count
+}
+
+fn main() -> u64 {
+    let list = push_front(push_front(push_front(List::Nil, 3), 2), 1);
+    assert(sum(list) == 6);
+    assert(sum(List::Nil) == 0);
+
+    if let List::Cons((head, rest)) = list {
+        assert(head == 1);
+        assert(sum(rest.get()) == 5);
+    } else {
+        revert(0);
+    }
+
+    let last = Node {
+        value: 5,
+        next: None,
+    };
+    let first = Node {
+        value: 4,
+        next: Some(Box::new(last)),
+    };
+    assert(len(first) == 2);
+    assert(first.next.unwrap().get().value == 5);
+
+    sum(list) + len(first)
+}
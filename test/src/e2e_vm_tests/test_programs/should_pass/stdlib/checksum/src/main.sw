@@ -0,0 +1,27 @@
+script;
+
+use std::{checksum::{checksum_decode, checksum_encode}, constants::ZERO_B256, string::String};
+
+fn main() -> bool {
+    let bits = 0x8900c5bec4ca97d4febf9ceb4754a60d782abbf3cd815836c1872116f203f861;
+    let checksummed = String::from_ascii_str("0x8900c5BEC4CA97D4FEBF9cEb4754A60D782abbf3CD815836C1872116f203F861");
+
+    // test checksum_encode()
+    assert(checksum_encode(bits).as_bytes() == checksummed.as_bytes());
+    let zero = String::from_ascii_str("0x0000000000000000000000000000000000000000000000000000000000000000");
+    assert(checksum_encode(ZERO_B256).as_bytes() == zero.as_bytes());
+
+    // test checksum_decode() on checksummed, lowercase and uppercase input
+    assert(checksum_decode(checksummed).unwrap() == bits);
+    assert(checksum_decode(String::from_ascii_str("0x8900c5bec4ca97d4febf9ceb4754a60d782abbf3cd815836c1872116f203f861")).unwrap() == bits);
+    assert(checksum_decode(String::from_ascii_str("8900C5BEC4CA97D4FEBF9CEB4754A60D782ABBF3CD815836C1872116F203F861")).unwrap() == bits);
+    assert(checksum_decode(zero).unwrap() == ZERO_B256);
+
+    // test checksum_decode() rejects a wrong checksum, bad characters and bad lengths
+    assert(checksum_decode(String::from_ascii_str("0x8900C5BEC4CA97D4FEBF9cEb4754A60D782abbf3CD815836C1872116f203F861")).is_none());
+    assert(checksum_decode(String::from_ascii_str("0x8900c5bec4ca97d4febf9ceb4754a60d782abbf3cd815836c1872116f203f86g")).is_none());
+    assert(checksum_decode(String::from_ascii_str("0x8900c5bec4ca97d4febf9ceb4754a60d782abbf3cd815836c1872116f203f8")).is_none());
+    assert(checksum_decode(String::new()).is_none());
+
+    true
+}
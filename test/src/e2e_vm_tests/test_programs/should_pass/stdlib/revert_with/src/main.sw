@@ -0,0 +1,10 @@
+script;
+
+enum TransferError {
+    InsufficientBalance: u64,
+    Unauthorized: (),
+}
+
+fn main() {
+    revert_with(TransferError::InsufficientBalance(42));
+}
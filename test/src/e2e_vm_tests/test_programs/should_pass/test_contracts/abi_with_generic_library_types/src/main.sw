@@ -0,0 +1,27 @@
+contract;
+
+enum Either<L, R> {
+    Left: L,
+    Right: R,
+}
+
+abi GenericTypes {
+    fn maybe_double(value: Option<u64>) -> Option<u64>;
+    fn swap(choice: Either<u64, bool>) -> Either<bool, u64>;
+}
+
+impl GenericTypes for Contract {
+    fn maybe_double(value: Option<u64>) -> Option<u64> {
+        match value {
+            Some(value) => Some(value * 2),
+            None => None,
+        }
+    }
+
+    fn swap(choice: Either<u64, bool>) -> Either<bool, u64> {
+        match choice {
+            Either::Left(value) => Either::Right(value),
+            Either::Right(value) => Either::Left(value),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+library;
+
+use std::block::{height, timestamp};
+
+// ANCHOR: time_lock_test
+// The timestamp of block 100 in `forc test`.
+const UNLOCK_TIME: u64 = 4611686018427388914;
+
+fn is_unlocked() -> bool {
+    timestamp() >= UNLOCK_TIME
+}
+
+#[test(timestamp = 4611686018427388904)]
+fn test_locked_before_unlock_time() {
+    assert(!is_unlocked());
+}
+
+#[test(block_height = 100)]
+fn test_unlocked_at_unlock_time() {
+    assert(is_unlocked());
+}
+// ANCHOR_END: time_lock_test
+
+#[test]
+fn test_genesis_block_by_default() {
+    assert(height() == 0);
+    assert(timestamp() == 4611686018427387914);
+}
+
+#[test(block_height = 7)]
+fn test_block_height() {
+    assert(height() == 7);
+    assert(timestamp() == 4611686018427387984);
+}
+
+#[test(timestamp = 4611686018427388904)]
+fn test_timestamp() {
+    assert(height() == 99);
+}
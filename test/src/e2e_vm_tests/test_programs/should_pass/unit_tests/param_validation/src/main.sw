@@ -0,0 +1,44 @@
+contract;
+
+// ANCHOR: param_validation
+abi Mixer {
+    fn set_volume(#[param(min = 1, max = 100)] volume: u8) -> u8;
+    fn set_delay(channel: u64, #[param(min = 10)] delay: u64) -> u64;
+}
+
+impl Mixer for Contract {
+    fn set_volume(volume: u8) -> u8 {
+        volume
+    }
+
+    fn set_delay(channel: u64, delay: u64) -> u64 {
+        channel + delay
+    }
+}
+// ANCHOR_END: param_validation
+
+#[test]
+fn test_within_bounds() {
+    let mixer = abi(Mixer, CONTRACT_ID);
+    assert(mixer.set_volume(1) == 1);
+    assert(mixer.set_volume(100) == 100);
+    assert(mixer.set_delay(0, 10) == 10);
+}
+
+#[test(should_revert = "18446744073709486088")]
+fn test_below_min() {
+    let mixer = abi(Mixer, CONTRACT_ID);
+    let _ = mixer.set_volume(0);
+}
+
+#[test(should_revert = "18446744073709486088")]
+fn test_above_max() {
+    let mixer = abi(Mixer, CONTRACT_ID);
+    let _ = mixer.set_volume(101);
+}
+
+#[test(should_revert = "18446744073709486088")]
+fn test_below_min_without_max() {
+    let mixer = abi(Mixer, CONTRACT_ID);
+    let _ = mixer.set_delay(100, 9);
+}
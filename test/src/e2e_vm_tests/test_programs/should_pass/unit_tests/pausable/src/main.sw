@@ -0,0 +1,77 @@
+contract;
+
+// ANCHOR: pausable
+use std::pausable::*;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment() -> u64;
+}
+
+storage {
+    count: u64 = 0,
+}
+
+impl Pausable for Contract {
+    #[storage(read, write)]
+    fn pause() {
+        _pause();
+    }
+
+    #[storage(read, write)]
+    fn unpause() {
+        _unpause();
+    }
+
+    #[storage(read)]
+    fn is_paused() -> bool {
+        _is_paused()
+    }
+}
+
+impl Counter for Contract {
+    #[when_not_paused]
+    #[storage(read, write)]
+    fn increment() -> u64 {
+        let count = storage.count.read() + 1;
+        storage.count.write(count);
+        count
+    }
+}
+// ANCHOR_END: pausable
+
+#[test]
+fn test_increment_when_not_paused() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.increment() == 1);
+    assert(counter.increment() == 2);
+}
+
+#[test(should_revert = "18446744073709486080")]
+fn test_increment_when_paused() {
+    let pausable = abi(Pausable, CONTRACT_ID);
+    pausable.pause();
+    assert(pausable.is_paused());
+    let _ = abi(Counter, CONTRACT_ID).increment();
+}
+
+#[test]
+fn test_increment_after_unpause() {
+    let pausable = abi(Pausable, CONTRACT_ID);
+    pausable.pause();
+    pausable.unpause();
+    assert(!pausable.is_paused());
+    assert(abi(Counter, CONTRACT_ID).increment() == 1);
+}
+
+#[test(should_revert = "18446744073709486080")]
+fn test_pause_when_paused() {
+    let pausable = abi(Pausable, CONTRACT_ID);
+    pausable.pause();
+    pausable.pause();
+}
+
+#[test(should_revert = "18446744073709486080")]
+fn test_unpause_when_not_paused() {
+    abi(Pausable, CONTRACT_ID).unpause();
+}
@@ -0,0 +1,34 @@
+script;
+
+use std::test_utils::assert_snapshot;
+
+struct Order {
+    amount: u64,
+    filled: bool,
+}
+
+fn fill(order: Order) -> Order {
+    Order {
+        amount: order.amount,
+        filled: true,
+    }
+}
+
+fn main() -> u64 {
+    fill(Order {
+        amount: 1,
+        filled: false,
+    }).amount
+}
+
+// ANCHOR: assert_snapshot
+#[test]
+fn test_fill() {
+    let order = Order {
+        amount: 42,
+        filled: false,
+    };
+    assert_snapshot(order);
+    assert_snapshot(fill(order));
+}
+// ANCHOR_END: assert_snapshot
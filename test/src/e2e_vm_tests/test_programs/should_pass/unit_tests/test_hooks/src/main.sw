@@ -0,0 +1,53 @@
+contract;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment() -> u64;
+
+    #[storage(read)]
+    fn get() -> u64;
+}
+
+storage {
+    count: u64 = 0,
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() -> u64 {
+        let count = storage.count.read() + 1;
+        storage.count.write(count);
+        count
+    }
+
+    #[storage(read)]
+    fn get() -> u64 {
+        storage.count.read()
+    }
+}
+
+// ANCHOR: test_hooks
+#[before_each]
+fn setup() {
+    let counter = abi(Counter, CONTRACT_ID);
+    counter.increment();
+}
+
+#[after_each]
+fn teardown() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.get() >= 1);
+}
+
+#[test]
+fn test_setup_ran() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.get() == 1);
+}
+
+#[test]
+fn test_storage_is_reset() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.increment() == 2);
+}
+// ANCHOR_END: test_hooks
@@ -0,0 +1,72 @@
+contract;
+
+abi Token {
+    #[storage(read, write)]
+    fn mint_to_alice(amount: u64);
+
+    #[storage(read, write)]
+    fn transfer_to_bob(amount: u64);
+
+    #[storage(read)]
+    fn total_supply() -> u64;
+
+    #[storage(read)]
+    fn balances() -> (u64, u64);
+}
+
+storage {
+    total_supply: u64 = 0,
+    alice: u64 = 0,
+    bob: u64 = 0,
+}
+
+impl Token for Contract {
+    #[storage(read, write)]
+    fn mint_to_alice(amount: u64) {
+        storage.total_supply.write(storage.total_supply.read() + amount);
+        storage.alice.write(storage.alice.read() + amount);
+    }
+
+    #[storage(read, write)]
+    fn transfer_to_bob(amount: u64) {
+        let alice = storage.alice.read();
+        assert(alice >= amount);
+        storage.alice.write(alice - amount);
+        storage.bob.write(storage.bob.read() + amount);
+    }
+
+    #[storage(read)]
+    fn total_supply() -> u64 {
+        storage.total_supply.read()
+    }
+
+    #[storage(read)]
+    fn balances() -> (u64, u64) {
+        (storage.alice.read(), storage.bob.read())
+    }
+}
+
+// ANCHOR: invariant
+#[invariant]
+fn total_supply_is_sum_of_balances() {
+    let token = abi(Token, CONTRACT_ID);
+    let (alice, bob) = token.balances();
+    assert(token.total_supply() == alice + bob);
+}
+
+#[test]
+fn test_mint() {
+    let token = abi(Token, CONTRACT_ID);
+    token.mint_to_alice(100);
+    assert(token.total_supply() == 100);
+}
+
+#[test]
+fn test_transfer() {
+    let token = abi(Token, CONTRACT_ID);
+    token.mint_to_alice(100);
+    token.transfer_to_bob(40);
+    let (alice, bob) = token.balances();
+    assert(alice == 60 && bob == 40);
+}
+// ANCHOR_END: invariant
@@ -0,0 +1,50 @@
+library;
+
+// ANCHOR: specifications
+#[requires(divisor != 0)]
+#[ensures(result <= dividend)]
+fn divide(dividend: u64, divisor: u64) -> u64 {
+    dividend / divisor
+}
+
+#[requires(n < 64)]
+#[ensures(result > 0)]
+fn pow2(n: u64) -> u64 {
+    if n == 0 {
+        return 1;
+    }
+    let mut result = 1;
+    let mut i = 0;
+    while i < n {
+        result = result * 2;
+        i += 1;
+    }
+    result
+}
+
+#[test]
+fn test_divide() {
+    assert(divide(10, 3) == 3);
+}
+
+#[test(should_revert = "18446744073709486086")]
+fn test_divide_by_zero() {
+    let _ = divide(10, 0);
+}
+// ANCHOR_END: specifications
+
+#[test]
+fn test_pow2() {
+    assert(pow2(0) == 1);
+    assert(pow2(10) == 1024);
+}
+
+#[ensures(result)]
+fn is_even(n: u64) -> bool {
+    n % 2 == 0
+}
+
+#[test(should_revert = "18446744073709486087")]
+fn test_violated_ensures() {
+    let _ = is_even(3);
+}
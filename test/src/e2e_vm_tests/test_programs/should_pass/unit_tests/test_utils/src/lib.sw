@@ -0,0 +1,61 @@
+library;
+
+use std::test_utils::{Random, Rng};
+
+// ANCHOR: random_test_data
+struct Order {
+    owner: Address,
+    asset: ContractId,
+    amount: u64,
+}
+
+#[cfg(test)]
+impl Random for Order {
+    fn random(ref mut rng: Rng) -> Self {
+        Self {
+            owner: Address::random(rng),
+            asset: ContractId::random(rng),
+            amount: rng.next_u64_in(1, 1000),
+        }
+    }
+}
+
+#[test]
+fn test_random_order() {
+    let mut rng = Rng::new(42);
+    let order = Order::random(rng);
+    assert(order.amount >= 1 && order.amount < 1000);
+}
+// ANCHOR_END: random_test_data
+
+#[cfg(test)]
+fn is_test_build() -> bool {
+    true
+}
+
+#[test]
+fn test_cfg_test_items_are_included() {
+    assert(is_test_build());
+}
+
+#[test]
+fn test_same_seed_same_data() {
+    let mut first = Rng::new(7);
+    let mut second = Rng::new(7);
+    let mut i = 0;
+    while i < 10 {
+        assert(first.next_u64() == second.next_u64());
+        i += 1;
+    }
+    assert(first.next_address() == second.next_address());
+    assert(first.next_b256() == second.next_b256());
+
+    let mut other = Rng::new(8);
+    assert(Rng::new(7).next_u64() != other.next_u64());
+}
+
+#[test]
+fn test_zero_seed() {
+    let mut rng = Rng::new(0);
+    assert(rng.next_u64() != 0);
+}
@@ -9,7 +9,7 @@ use anyhow::Result;
 use colored::Colorize;
 use sway_core::{
     compile_ir_to_asm, compile_to_ast, ir_generation::compile_program, namespace, BuildTarget,
-    Engines,
+    Engines, OverflowBehavior,
 };
 use sway_ir::{
     create_inline_in_module_pass, register_known_passes, PassGroup, PassManager, ARGDEMOTION_NAME,
@@ -188,10 +188,16 @@ pub(super) async fn run(filter_regex: Option<&regex::Regex>, verbose: bool) -> R
 
             let mut optimisation_inline = false;
             let mut target_fuelvm = false;
+            let mut overflow = OverflowBehavior::default();
 
             if let Some(first_line) = input.lines().next() {
                 optimisation_inline = first_line.contains("optimisation-inline");
                 target_fuelvm = first_line.contains("target-fuelvm");
+                if first_line.contains("overflow-wrap") {
+                    overflow = OverflowBehavior::Wrap;
+                } else if first_line.contains("overflow-checked") {
+                    overflow = OverflowBehavior::Checked;
+                }
             }
 
             (
@@ -200,10 +206,11 @@ pub(super) async fn run(filter_regex: Option<&regex::Regex>, verbose: bool) -> R
                 checkers,
                 optimisation_inline,
                 target_fuelvm,
+                overflow,
             )
         })
         .for_each(
-            |(path, sway_str, checkers, optimisation_inline, target_fuelvm)| {
+            |(path, sway_str, checkers, optimisation_inline, target_fuelvm, overflow)| {
                 let test_file_name = path.file_name().unwrap().to_string_lossy().to_string();
                 tracing::info!("Testing {} ...", test_file_name.bold());
 
@@ -248,7 +255,14 @@ pub(super) async fn run(filter_regex: Option<&regex::Regex>, verbose: bool) -> R
 
                 // Compile to IR.
                 let include_tests = true;
-                let mut ir = compile_program(typed_program, include_tests, &engines)
+                let check_specs = true;
+                let mut ir = compile_program(
+                    typed_program,
+                    include_tests,
+                    check_specs,
+                    overflow,
+                    &engines,
+                )
                     .unwrap_or_else(|e| {
                         use sway_types::span::Spanned;
                         let span = e.span();
@@ -504,6 +518,7 @@ fn compile_core(build_target: BuildTarget, engines: &Engines) -> namespace::Modu
         disable_tests: false,
         locked: false,
         ipfs_node: None,
+        sarif: None,
     };
 
     let res = match forc::test::forc_check::check(check_cmd, engines) {
@@ -0,0 +1,47 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+    z: u64,
+}
+
+struct Tagged {
+    tag: u64,
+    payload: u64,
+}
+
+impl Eq for Tagged {
+    fn eq(self, other: Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+fn main() -> bool {
+    let a = [1, 2, 3];
+    let b = [1, 2, 4];
+    let p = Point { x: 1, y: 2, z: 3 };
+    let q = Point { x: 1, y: 2, z: 4 };
+    let t = Tagged { tag: 1, payload: 2 };
+    let u = Tagged { tag: 1, payload: 3 };
+    a != b && p == q && "abc" == "abd" && t == u
+}
+
+// ::check-ir::
+
+// check: $(arr_len=$VAL) = const u64 24
+// nextln: $(arr_eq=$VAL) = asm(lhs: $VAL, rhs: $VAL, len: $arr_len, res) -> bool res
+// nextln: meq    res lhs rhs len
+// check: $(f=$VAL) = const bool false
+// check: $VAL = cmp eq $arr_eq $f
+
+// check: $(struct_len=$VAL) = const u64 24
+// nextln: $VAL = asm(lhs: $VAL, rhs: $VAL, len: $struct_len, res) -> bool res
+// nextln: meq    res lhs rhs len
+
+// check: $(str_len=$VAL) = const u64 3
+// nextln: $VAL = asm(lhs: $VAL, rhs: $VAL, len: $str_len, res) -> bool res
+// nextln: meq    res lhs rhs len
+
+// A struct which implements `Eq` is compared with its `eq`.
+// check: $VAL = call eq_
@@ -0,0 +1,31 @@
+script;
+
+#[cfg(target = "fuel")]
+const ON_FUEL: bool = true;
+#[cfg(target = "evm")]
+const ON_FUEL: bool = false;
+
+const VERBOSE: bool = false;
+
+fn fuel_only() -> u64 {
+    42
+}
+
+fn evm_only() -> u64 {
+    1_000_000
+}
+
+fn main() -> u64 {
+    if ON_FUEL || VERBOSE {
+        fuel_only()
+    } else {
+        evm_only()
+    }
+}
+
+// The condition is known at compile time, so only the taken branch is compiled.
+
+// not: cbr
+// check: call $(fuel_only=$ID)()
+// not: evm_only
+// not: const u64 1000000
@@ -0,0 +1,20 @@
+script;
+
+fn main() -> bool {
+    let a = 0x0202020202020202020202020202020202020202020202020202020202020202;
+    let b = 0x0303030303030303030303030303030303030303030303030303030303030303;
+    __memcpy(__addr_of(b), __addr_of(a), 32);
+    __memcmp(__addr_of(a), __addr_of(b), 32)
+}
+
+// ::check-ir::
+
+// check: $(len=$VAL) = const u64 32
+// check: $VAL = asm(lhs: $VAL, rhs: $VAL, len: $len) {
+// nextln: mcp    lhs rhs len
+// nextln: }
+
+// check: $(res=$VAL) = asm(lhs: $VAL, rhs: $VAL, len: $VAL, res) -> bool res {
+// nextln: meq    res lhs rhs len
+// nextln: }
+// check: ret bool $res
@@ -0,0 +1,24 @@
+script;
+
+#[memoize]
+fn storage_key(slot: u64, offset: u64) -> u64 {
+    let base = slot * 1000;
+    base + offset
+}
+
+fn main() -> u64 {
+    let a = storage_key(1, 2);
+    let b = storage_key(1, 2);
+    storage_key(a, b)
+}
+
+// Calls with constant arguments are folded, others are compiled as usual.
+
+// check: fn main() -> u64
+// check: $(a_val=$VAL) = const u64 1002
+// check: $(a_addr=$VAL) = get_local ptr u64, a
+// check: store $a_val to $a_addr
+// check: $(b_val=$VAL) = const u64 1002
+// check: $(b_addr=$VAL) = get_local ptr u64, b
+// check: store $b_val to $b_addr
+// check: call storage_key_
@@ -0,0 +1,35 @@
+// overflow-checked
+
+script;
+
+fn main() -> u64 {
+    let a: u64 = 2;
+    let _ = __wrapping_mul(a, 3);
+    __add(a, 1)
+}
+
+// ::check-ir::
+
+// check: $VAL = asm(lhs: $VAL, rhs: $VAL, flags, wrapping: $VAL, res) -> u64 res
+// check: mul    res lhs rhs
+// nextln: flag   flags
+// nextln: }
+
+// check: $(ovf=$VAL) = asm(lhs: $VAL, rhs: $VAL, flags, wrapping: $VAL, res, ovf) -> u64 ovf
+// nextln: move   flags flag
+// nextln: or     wrapping wrapping flags
+// nextln: flag   wrapping
+// nextln: add    res lhs rhs
+// nextln: move   ovf of
+// nextln: flag   flags
+// nextln: }
+// check: $(zero=$VAL) = const u64 0
+// check: $(fits=$VAL) = cmp eq $ovf $zero
+// check: cbr $fits, $(fits_block=$ID)(), $(overflows_block=$ID)()
+
+// check: $fits_block():
+// check: $VAL = add $VAL, $VAL
+
+// check: $overflows_block():
+// check: $(code=$VAL) = const u64 18446744073709486089
+// check: revert $code
@@ -0,0 +1,28 @@
+// overflow-wrap
+
+script;
+
+fn main() {
+    let a: u8 = 255;
+    let _ = __add(a, 1);
+    let _ = __div(a, 2);
+    let _ = __checked_sub(a, 1);
+}
+
+// ::check-ir::
+
+// check: $(max=$VAL) = const u64 255
+// check: $VAL = asm(lhs: $VAL, rhs: $VAL, flags, wrapping: $VAL, res, max: $max) -> u8 res
+// nextln: move   flags flag
+// nextln: or     wrapping wrapping flags
+// nextln: flag   wrapping
+// nextln: add    res lhs rhs
+// nextln: flag   flags
+// nextln: and    res res max
+// nextln: }
+
+// check: $VAL = div $VAL, $VAL
+
+// check: $VAL = asm(lhs: $VAL, rhs: $VAL, flags, wrapping: $VAL, res, ovf, max: $VAL) -> u64 ovf
+// check: sub    res lhs rhs
+// nextln: move   ovf of
@@ -0,0 +1,31 @@
+// This is to test that the calls to a generic function with the same type arguments share a
+// single instance of it.
+
+script;
+
+// -------------------------------------------------------------------------------------------------
+// `first` is called twice for `u64`, once with inferred type arguments and once with explicit ones,
+// and once for `bool`.
+
+fn first<T>(x: T, y: T) -> T {
+    x
+}
+
+// -------------------------------------------------------------------------------------------------
+
+fn main() -> u64 {
+    let a = first(1u64, 2u64);
+    let b = first::<u64>(a, 3);
+    if first(true, false) { b } else { a }
+}
+
+// regex: FIRST_FN=first_\d
+
+// check: fn main
+
+// check: fn $FIRST_FN
+// sameln: u64
+// check: fn $FIRST_FN
+// sameln: bool
+
+// not: fn $FIRST_FN
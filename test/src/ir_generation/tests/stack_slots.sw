@@ -0,0 +1,67 @@
+// target-fuelvm
+
+script;
+
+// `a`, `b` and `c` are never live at the same time, so they share one word.
+fn sequential(x: u64) -> u64 {
+    let a = x + 1;
+    let b = a * 2;
+    let c = b + 3;
+    c
+}
+
+// `prev` is carried from one iteration to the next, so it doesn't share with `next`, which is
+// only live within one.
+fn loop_carried(n: u64) -> u64 {
+    let mut i = 0;
+    let mut prev = 0;
+    let mut sum = 0;
+    while i < n {
+        sum = sum + prev;
+        let next = i * 2;
+        prev = next;
+        i = i + 1;
+    }
+    sum
+}
+
+// `a` has its address taken, so it keeps its four words for the whole function, while `addr`
+// and `after` share a fifth.
+fn address_taken(x: b256, y: b256) -> u64 {
+    let mut a = x;
+    a = y;
+    let addr = __addr_of(a);
+    let after = addr + 1;
+    after
+}
+
+fn main() -> u64 {
+    let x = 0x0000000000000000000000000000000000000000000000000000000000000001;
+    let y = 0x0000000000000000000000000000000000000000000000000000000000000002;
+    sequential(1) + loop_carried(3) + address_taken(x, y)
+}
+
+// ::check-ir::
+
+// check: fn sequential
+// check: local u64 a
+// check: local u64 b
+// check: local u64 c
+
+// check: fn loop_carried
+// check: local mut u64 i
+// check: local u64 next
+// check: local mut u64 prev
+// check: local mut u64 sum
+
+// check: fn address_taken
+// check: local mut b256 a
+// check: local u64 addr
+// check: local u64 after
+
+// ::check-asm::
+
+// One word for `sequential`, four for `loop_carried` and five for `address_taken`.
+// unordered: allocate 8 bytes for locals
+// unordered: allocate 32 bytes for locals
+// unordered: allocate 40 bytes for locals
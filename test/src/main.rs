@@ -4,7 +4,7 @@ mod ir_generation;
 use anyhow::Result;
 use clap::Parser;
 use forc_tracing::init_tracing_subscriber;
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 use sway_core::BuildTarget;
 use tracing::Instrument;
 
@@ -45,6 +45,15 @@ struct Cli {
     /// Build target.
     #[arg(long, visible_alias = "target")]
     build_target: Option<String>,
+
+    /// Overwrite ABI and storage slots snapshots with the current compiler output
+    #[arg(long, env = "SWAY_TEST_UPDATE_SNAPSHOTS")]
+    update_snapshots: bool,
+
+    /// Path to a `forc` binary of another compiler version, e.g. a pinned previous release.
+    /// Every `run` test is also built with it, and both bytecodes must behave identically.
+    #[arg(long, value_name = "FORC_BINARY", env = "SWAY_TEST_DIFFERENTIAL_FORC")]
+    differential: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +71,8 @@ pub struct RunConfig {
     pub build_target: BuildTarget,
     pub locked: bool,
     pub verbose: bool,
+    pub update_snapshots: bool,
+    pub differential: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -88,6 +99,8 @@ async fn main() -> Result<()> {
     let run_config = RunConfig {
         locked: cli.locked,
         verbose: cli.verbose,
+        update_snapshots: cli.update_snapshots,
+        differential: cli.differential,
         build_target,
     };
 